@@ -0,0 +1,121 @@
+//! AES-GCM operations with detached nonce and tag
+//!
+//! Used by container formats such as JWE that carry the IV, ciphertext and
+//! authentication tag as separate fields and require the AAD to be
+//! authenticated by the cipher itself.
+
+use super::builder_types::AesWithKey;
+use crate::{CryptError, Result};
+use aes_gcm::{
+    Aes256Gcm, KeyInit,
+    aead::{AeadInPlace, generic_array::GenericArray},
+};
+use rand::RngCore;
+
+/// AES-GCM nonce size in bytes
+pub const AES_GCM_NONCE_SIZE: usize = 12;
+
+/// AES-GCM authentication tag size in bytes
+pub const AES_GCM_TAG_SIZE: usize = 16;
+
+/// AES-256-GCM output with nonce and tag kept apart from the ciphertext
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedCiphertext {
+    /// Random 96-bit nonce used for this message
+    pub nonce: [u8; AES_GCM_NONCE_SIZE],
+    /// Ciphertext, same length as the plaintext
+    pub ciphertext: Vec<u8>,
+    /// 128-bit authentication tag over the AAD and ciphertext
+    pub tag: [u8; AES_GCM_TAG_SIZE],
+}
+
+impl AesWithKey {
+    /// Encrypt with a fresh random nonce, authenticating the AAD, and return
+    /// nonce, ciphertext and tag separately
+    ///
+    /// # Errors
+    /// Returns `CryptError` if the key is not 32 bytes or encryption fails
+    pub async fn encrypt_detached<T: Into<Vec<u8>>>(self, data: T) -> Result<DetachedCiphertext> {
+        let mut nonce = [0u8; AES_GCM_NONCE_SIZE];
+        rand::rng().fill_bytes(&mut nonce);
+        self.encrypt_detached_with_nonce(nonce, data).await
+    }
+
+    /// Encrypt under a caller-chosen nonce, authenticating the AAD, and return
+    /// nonce, ciphertext and tag separately
+    ///
+    /// For protocols that derive nonces, such as per-record counters. A nonce
+    /// must never be used twice with the same key.
+    ///
+    /// # Errors
+    /// Returns `CryptError` if the key is not 32 bytes or encryption fails
+    pub async fn encrypt_detached_with_nonce<T: Into<Vec<u8>>>(
+        self,
+        nonce: [u8; AES_GCM_NONCE_SIZE],
+        data: T,
+    ) -> Result<DetachedCiphertext> {
+        let cipher = new_cipher(&self.key)?;
+        let mut ciphertext = data.into();
+        let tag = cipher
+            .encrypt_in_place_detached(
+                GenericArray::from_slice(&nonce),
+                self.aad.as_deref().unwrap_or_default(),
+                &mut ciphertext,
+            )
+            .map_err(|e| CryptError::EncryptionFailed(e.to_string()))?;
+
+        Ok(DetachedCiphertext {
+            nonce,
+            ciphertext,
+            tag: tag.into(),
+        })
+    }
+
+    /// Decrypt a detached nonce, ciphertext and tag, authenticating the AAD
+    ///
+    /// # Errors
+    /// Returns `CryptError` if the key, nonce or tag size is wrong or
+    /// authentication fails
+    pub async fn decrypt_detached(
+        self,
+        nonce: &[u8],
+        ciphertext: &[u8],
+        tag: &[u8],
+    ) -> Result<Vec<u8>> {
+        if nonce.len() != AES_GCM_NONCE_SIZE {
+            return Err(CryptError::InvalidNonceLength {
+                expected: AES_GCM_NONCE_SIZE,
+                actual: nonce.len(),
+            });
+        }
+        if tag.len() != AES_GCM_TAG_SIZE {
+            return Err(CryptError::InvalidEncryptedData(format!(
+                "Authentication tag must be {AES_GCM_TAG_SIZE} bytes, got {}",
+                tag.len()
+            )));
+        }
+
+        let cipher = new_cipher(&self.key)?;
+        let mut plaintext = ciphertext.to_vec();
+        cipher
+            .decrypt_in_place_detached(
+                GenericArray::from_slice(nonce),
+                self.aad.as_deref().unwrap_or_default(),
+                &mut plaintext,
+                GenericArray::from_slice(tag),
+            )
+            .map_err(|e| CryptError::DecryptionFailed(e.to_string()))?;
+
+        Ok(plaintext)
+    }
+}
+
+fn new_cipher(key: &[u8]) -> Result<Aes256Gcm> {
+    if key.len() != 32 {
+        return Err(CryptError::InvalidKeySize {
+            expected: 32,
+            actual: key.len(),
+        });
+    }
+    Ok(Aes256Gcm::new(GenericArray::from_slice(key)))
+}
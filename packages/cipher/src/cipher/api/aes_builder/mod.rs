@@ -9,8 +9,10 @@ pub mod stream;
 // Declare new decomposed submodules
 mod builder_types;
 mod decrypt_operations;
+mod detached_operations;
 mod encrypt_operations;
 mod stream_operations;
 
 // Re-export all public types and functions from decomposed modules
 pub use builder_types::{AesBuilder, AesWithKey, AesWithKeyAndChunkHandler, AesWithKeyAndHandler};
+pub use detached_operations::{AES_GCM_NONCE_SIZE, AES_GCM_TAG_SIZE, DetachedCiphertext};
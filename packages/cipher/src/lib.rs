@@ -31,7 +31,7 @@ pub use cipher::CipherAlgorithm;
 pub use cipher::api::chacha_builder::{ChaChaBuilder, ChaChaWithKey};
 pub use cipher::api::{
     Cipher,
-    aes_builder::{AesBuilder, AesWithKey, AesWithKeyAndHandler},
+    aes_builder::{AesBuilder, AesWithKey, AesWithKeyAndHandler, DetachedCiphertext},
 };
 
 // Re-export common handlers from cryypt_common
@@ -151,3 +151,29 @@ async fn test_aes_encrypt_to_hex_and_back() {
 
     assert_eq!(plaintext.to_vec(), decrypted);
 }
+
+#[tokio::test]
+async fn test_aes_detached_with_caller_nonce() {
+    // AES-256-GCM test case 14 of the GCM specification
+    let detached = Cipher::aes()
+        .with_key(vec![0u8; 32])
+        .encrypt_detached_with_nonce([0u8; 12], vec![0u8; 16])
+        .await
+        .expect("encrypt");
+    assert_eq!(detached.nonce, [0u8; 12]);
+    assert_eq!(
+        detached.ciphertext,
+        hex_literal::hex!("cea7403d4d606b6e074ec5d3baf39d18")
+    );
+    assert_eq!(
+        detached.tag,
+        hex_literal::hex!("d0d1c8a799996bf0265b98b5d48ab919")
+    );
+
+    let decrypted = Cipher::aes()
+        .with_key(vec![0u8; 32])
+        .decrypt_detached(&detached.nonce, &detached.ciphertext, &detached.tag)
+        .await
+        .expect("decrypt");
+    assert_eq!(decrypted, vec![0u8; 16]);
+}
@@ -20,6 +20,24 @@ impl KeyMasterBuilder {
         cryypt_key::api::RsaKeyBuilder::new()
     }
 
+    /// Ed25519 key operations - polymorphic pattern
+    #[must_use]
+    pub fn ed25519(self) -> cryypt_key::api::Ed25519KeyBuilder {
+        cryypt_key::api::Ed25519KeyBuilder::new()
+    }
+
+    /// X25519 key operations - polymorphic pattern
+    #[must_use]
+    pub fn x25519(self) -> cryypt_key::api::X25519KeyBuilder {
+        cryypt_key::api::X25519KeyBuilder::new()
+    }
+
+    /// NIST P-256 / P-384 key operations - polymorphic pattern
+    #[must_use]
+    pub fn ec(self) -> cryypt_key::api::EcKeyBuilder {
+        cryypt_key::api::EcKeyBuilder::new()
+    }
+
     /// Generate a new key - README.md pattern
     #[must_use]
     pub fn generate(self) -> cryypt_key::api::KeyGenerator {
@@ -11,7 +11,7 @@ impl PqcryptoMasterBuilder {
     /// Use ML-KEM (Kyber) key encapsulation mechanism - README.md pattern
     #[must_use]
     pub fn kyber(self) -> cryypt_pqcrypto::api::KemBuilder {
-        cryypt_pqcrypto::api::KemBuilder
+        cryypt_pqcrypto::api::KemBuilder::new()
     }
 
     /// Use ML-DSA (Dilithium) digital signature algorithm - README.md pattern
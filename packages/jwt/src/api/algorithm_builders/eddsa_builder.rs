@@ -0,0 +1,266 @@
+//! `EdDSA` JWT Builder - Polymorphic pattern for Ed25519/Ed448 JWT operations
+//!
+//! Provides polymorphic builder pattern for RFC 8037 `EdDSA` JWT key generation,
+//! signing and verification. Keys are PKCS#8 (private) and SPKI (public) in DER
+//! or PEM form.
+
+use crate::api::algorithms::eddsa::{generate_eddsa_keypair, sign_eddsa};
+use crate::api::keys::{key_to_der, validate_okp_private_key};
+use crate::error::{JwtError, JwtResult};
+use crate::types::{EdDsaCurve, EdDsaKeyPair};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::Serialize;
+
+/// `EdDSA` JWT builder - initial state
+#[derive(Debug, Clone)]
+pub struct EdDsaJwtBuilder {
+    curve: EdDsaCurve,
+}
+
+/// `EdDSA` key generator with result handler
+#[derive(Debug)]
+pub struct EdDsaJwtKeyGenWithHandler<F> {
+    curve: EdDsaCurve,
+    handler: F,
+}
+
+/// `EdDSA` JWT builder with private key configured
+#[derive(Debug, Clone)]
+pub struct EdDsaJwtWithPrivateKey {
+    private_key: Vec<u8>,
+}
+
+/// `EdDSA` JWT builder with private key and claims configured
+#[derive(Debug, Clone)]
+pub struct EdDsaJwtWithPrivateKeyAndClaims<T> {
+    private_key: Vec<u8>,
+    claims: T,
+}
+
+/// `EdDSA` JWT builder with private key, claims and result handler
+#[derive(Debug)]
+pub struct EdDsaJwtWithPrivateKeyAndClaimsAndHandler<T, F> {
+    private_key: Vec<u8>,
+    claims: T,
+    handler: F,
+}
+
+/// `EdDSA` JWT builder with public key configured
+#[derive(Debug, Clone)]
+pub struct EdDsaJwtWithPublicKey {
+    public_key: Vec<u8>,
+}
+
+/// `EdDSA` JWT builder with public key and result handler
+#[derive(Debug)]
+pub struct EdDsaJwtWithPublicKeyAndHandler<F> {
+    public_key: Vec<u8>,
+    handler: F,
+}
+
+impl Default for EdDsaJwtBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EdDsaJwtBuilder {
+    /// Create new `EdDSA` JWT builder (Ed25519 by default)
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            curve: EdDsaCurve::Ed25519,
+        }
+    }
+
+    /// Use Ed25519 for key generation
+    #[must_use]
+    pub fn ed25519(mut self) -> Self {
+        self.curve = EdDsaCurve::Ed25519;
+        self
+    }
+
+    /// Use Ed448 for key generation
+    #[must_use]
+    pub fn ed448(mut self) -> Self {
+        self.curve = EdDsaCurve::Ed448;
+        self
+    }
+
+    /// Set result handler for key generation
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> EdDsaJwtKeyGenWithHandler<F>
+    where
+        F: FnOnce(JwtResult<EdDsaKeyPair>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        EdDsaJwtKeyGenWithHandler {
+            curve: self.curve,
+            handler,
+        }
+    }
+
+    /// Generate a new key pair for the configured curve
+    ///
+    /// # Errors
+    /// Returns `JwtError` if the system entropy source fails
+    pub async fn generate_keys(self) -> JwtResult<EdDsaKeyPair> {
+        tokio::task::yield_now().await;
+        generate_keys(self.curve)
+    }
+
+    /// Set PKCS#8 private key (DER or PEM) for `EdDSA` signing
+    #[must_use]
+    pub fn with_private_key(self, private_key: &[u8]) -> EdDsaJwtWithPrivateKey {
+        EdDsaJwtWithPrivateKey {
+            private_key: private_key.to_vec(),
+        }
+    }
+
+    /// Set SPKI public key (DER or PEM) for `EdDSA` verification
+    #[must_use]
+    pub fn with_public_key(self, public_key: &[u8]) -> EdDsaJwtWithPublicKey {
+        EdDsaJwtWithPublicKey {
+            public_key: public_key.to_vec(),
+        }
+    }
+}
+
+impl<F, R> EdDsaJwtKeyGenWithHandler<F>
+where
+    F: FnOnce(JwtResult<EdDsaKeyPair>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Generate a new key pair and apply the result handler
+    pub async fn generate_keys(self) -> R {
+        tokio::task::yield_now().await;
+        (self.handler)(generate_keys(self.curve))
+    }
+}
+
+impl EdDsaJwtWithPrivateKey {
+    /// Set claims for JWT
+    #[must_use]
+    pub fn with_claims<T: Serialize + Clone>(
+        self,
+        claims: T,
+    ) -> EdDsaJwtWithPrivateKeyAndClaims<T> {
+        EdDsaJwtWithPrivateKeyAndClaims {
+            private_key: self.private_key,
+            claims,
+        }
+    }
+}
+
+impl<T: Serialize + Clone> EdDsaJwtWithPrivateKeyAndClaims<T> {
+    /// Set result handler for single JWT signing
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> EdDsaJwtWithPrivateKeyAndClaimsAndHandler<T, F>
+    where
+        F: FnOnce(JwtResult<Vec<u8>>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        EdDsaJwtWithPrivateKeyAndClaimsAndHandler {
+            private_key: self.private_key,
+            claims: self.claims,
+            handler,
+        }
+    }
+}
+
+impl<T, F, R> EdDsaJwtWithPrivateKeyAndClaimsAndHandler<T, F>
+where
+    T: Serialize + Clone + Send + 'static,
+    F: FnOnce(JwtResult<Vec<u8>>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Sign single JWT with `EdDSA`
+    #[must_use]
+    pub async fn sign(self) -> R {
+        let result = async {
+            // Yield control to allow other tasks to run
+            tokio::task::yield_now().await;
+
+            let jwt_token = sign_eddsa_jwt(&self.claims, &self.private_key)?;
+            Ok(jwt_token.into_bytes())
+        }
+        .await;
+
+        // Apply result handler
+        (self.handler)(result)
+    }
+}
+
+impl EdDsaJwtWithPublicKey {
+    /// Set result handler for JWT verification
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> EdDsaJwtWithPublicKeyAndHandler<F>
+    where
+        F: FnOnce(JwtResult<serde_json::Value>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        EdDsaJwtWithPublicKeyAndHandler {
+            public_key: self.public_key,
+            handler,
+        }
+    }
+}
+
+impl<F, R> EdDsaJwtWithPublicKeyAndHandler<F>
+where
+    F: FnOnce(JwtResult<serde_json::Value>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Verify JWT with `EdDSA`, rejecting tokens signed with any other algorithm
+    pub async fn verify<S: AsRef<str>>(self, token: S) -> R {
+        let token = token.as_ref().to_string();
+        let result = match key_to_der(&self.public_key) {
+            Ok(public_key) => {
+                crate::api::algorithms::verify_jwt_for(&["EdDSA"], token, None, Some(public_key))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        (self.handler)(result)
+    }
+}
+
+/// Generate an `EdDSA` key pair for the given curve
+fn generate_keys(curve: EdDsaCurve) -> JwtResult<EdDsaKeyPair> {
+    let (private_key, public_key) = generate_eddsa_keypair(curve)?;
+    Ok(EdDsaKeyPair {
+        curve,
+        private_key,
+        public_key,
+    })
+}
+
+/// Production `EdDSA` JWT signing implementation
+fn sign_eddsa_jwt<T: Serialize>(claims: &T, private_key: &[u8]) -> JwtResult<String> {
+    let private_key = key_to_der(private_key)?;
+    validate_okp_private_key(&private_key)?;
+
+    // Create header
+    let header = serde_json::json!({
+        "alg": "EdDSA",
+        "typ": "JWT"
+    });
+
+    // Serialize header and claims
+    let header_bytes =
+        serde_json::to_vec(&header).map_err(|e| JwtError::InvalidToken(e.to_string()))?;
+    let claims_bytes =
+        serde_json::to_vec(claims).map_err(|e| JwtError::InvalidToken(e.to_string()))?;
+
+    let header_b64 = URL_SAFE_NO_PAD.encode(&header_bytes);
+    let claims_b64 = URL_SAFE_NO_PAD.encode(&claims_bytes);
+
+    // Create signature payload
+    let signature_payload = format!("{header_b64}.{claims_b64}");
+
+    let signature = sign_eddsa(&signature_payload, &private_key)?;
+    let signature_b64 = URL_SAFE_NO_PAD.encode(&signature);
+
+    Ok(format!("{header_b64}.{claims_b64}.{signature_b64}"))
+}
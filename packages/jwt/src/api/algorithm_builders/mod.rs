@@ -3,13 +3,29 @@
 //! This module provides algorithm-specific builders that integrate with the existing
 //! JWT system while following the polymorphic pattern from cipher module.
 
+pub mod eddsa_builder;
 pub mod hs256_builder;
+pub mod pq_builder;
+pub mod ps_builder;
 pub mod rs256_builder;
 
+pub use eddsa_builder::{
+    EdDsaJwtBuilder, EdDsaJwtKeyGenWithHandler, EdDsaJwtWithPrivateKey,
+    EdDsaJwtWithPrivateKeyAndClaims, EdDsaJwtWithPrivateKeyAndClaimsAndHandler,
+    EdDsaJwtWithPublicKey, EdDsaJwtWithPublicKeyAndHandler,
+};
 pub use hs256_builder::{
     HsJwtBuilder, HsJwtWithSecret, HsJwtWithSecretAndClaims,
     HsJwtWithSecretAndClaimsAndChunkHandler, HsJwtWithSecretAndClaimsAndHandler,
 };
+pub use pq_builder::{
+    PqJwtBuilder, PqJwtKeyGenWithHandler, PqJwtWithPrivateKey, PqJwtWithPrivateKeyAndClaims,
+    PqJwtWithPrivateKeyAndClaimsAndHandler, PqJwtWithPublicKey, PqJwtWithPublicKeyAndHandler,
+};
+pub use ps_builder::{
+    PsJwtBuilder, PsJwtKeyGenWithHandler, PsJwtWithPrivateKey, PsJwtWithPrivateKeyAndClaims,
+    PsJwtWithPrivateKeyAndClaimsAndHandler, PsJwtWithPublicKey, PsJwtWithPublicKeyAndHandler,
+};
 pub use rs256_builder::{
     RsJwtBuilder, RsJwtWithPrivateKey, RsJwtWithPrivateKeyAndClaims,
     RsJwtWithPrivateKeyAndClaimsAndChunkHandler, RsJwtWithPrivateKeyAndClaimsAndHandler,
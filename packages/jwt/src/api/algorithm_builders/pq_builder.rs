@@ -0,0 +1,279 @@
+//! Post-quantum JWT Builder - Polymorphic pattern for ML-DSA/SLH-DSA JWT operations
+//!
+//! Provides polymorphic builder pattern for post-quantum JWT key generation,
+//! signing and verification, including the composite ML-DSA-65 + ES256 algorithm.
+//! Keys are the raw encodings produced by `cryypt_pqcrypto`.
+
+use crate::api::algorithms::PQ_ALGORITHMS;
+use crate::api::algorithms::pq::generate_pq_keypair;
+use crate::error::{JwtError, JwtResult};
+use crate::types::PqKeyPair;
+use serde::Serialize;
+
+/// Post-quantum JWT builder - initial state
+#[derive(Debug, Clone)]
+pub struct PqJwtBuilder {
+    algorithm: &'static str,
+}
+
+/// Post-quantum key generator with result handler
+#[derive(Debug)]
+pub struct PqJwtKeyGenWithHandler<F> {
+    algorithm: &'static str,
+    handler: F,
+}
+
+/// Post-quantum JWT builder with private key configured
+#[derive(Debug, Clone)]
+pub struct PqJwtWithPrivateKey {
+    algorithm: &'static str,
+    private_key: Vec<u8>,
+}
+
+/// Post-quantum JWT builder with private key and claims configured
+#[derive(Debug, Clone)]
+pub struct PqJwtWithPrivateKeyAndClaims<T> {
+    algorithm: &'static str,
+    private_key: Vec<u8>,
+    claims: T,
+}
+
+/// Post-quantum JWT builder with private key, claims and result handler
+#[derive(Debug)]
+pub struct PqJwtWithPrivateKeyAndClaimsAndHandler<T, F> {
+    algorithm: &'static str,
+    private_key: Vec<u8>,
+    claims: T,
+    handler: F,
+}
+
+/// Post-quantum JWT builder with public key configured
+#[derive(Debug, Clone)]
+pub struct PqJwtWithPublicKey {
+    algorithm: &'static str,
+    public_key: Vec<u8>,
+}
+
+/// Post-quantum JWT builder with public key and result handler
+#[derive(Debug)]
+pub struct PqJwtWithPublicKeyAndHandler<F> {
+    algorithm: &'static str,
+    public_key: Vec<u8>,
+    handler: F,
+}
+
+impl PqJwtBuilder {
+    /// Create a post-quantum JWT builder for any supported `alg`
+    /// (see [`PQ_ALGORITHMS`])
+    ///
+    /// # Errors
+    /// Returns `JwtError::UnsupportedAlgorithm` for unknown identifiers
+    pub fn new(algorithm: &str) -> JwtResult<Self> {
+        PQ_ALGORITHMS
+            .iter()
+            .find(|&&supported| supported == algorithm)
+            .map(|&algorithm| Self { algorithm })
+            .ok_or_else(|| JwtError::unsupported_algorithm(algorithm))
+    }
+
+    /// Create new ML-DSA-44 JWT builder (NIST security level 2)
+    #[must_use]
+    pub fn ml_dsa_44() -> Self {
+        Self {
+            algorithm: "ML-DSA-44",
+        }
+    }
+
+    /// Create new ML-DSA-65 JWT builder (NIST security level 3)
+    #[must_use]
+    pub fn ml_dsa_65() -> Self {
+        Self {
+            algorithm: "ML-DSA-65",
+        }
+    }
+
+    /// Create new ML-DSA-87 JWT builder (NIST security level 5)
+    #[must_use]
+    pub fn ml_dsa_87() -> Self {
+        Self {
+            algorithm: "ML-DSA-87",
+        }
+    }
+
+    /// Create new SLH-DSA-SHA2-128s JWT builder (small signatures)
+    #[must_use]
+    pub fn slh_dsa_sha2_128s() -> Self {
+        Self {
+            algorithm: "SLH-DSA-SHA2-128s",
+        }
+    }
+
+    /// Create new SLH-DSA-SHA2-128f JWT builder (fast signing)
+    #[must_use]
+    pub fn slh_dsa_sha2_128f() -> Self {
+        Self {
+            algorithm: "SLH-DSA-SHA2-128f",
+        }
+    }
+
+    /// Create new composite ML-DSA-65 + ES256 JWT builder
+    #[must_use]
+    pub fn ml_dsa_65_es256() -> Self {
+        Self {
+            algorithm: "ML-DSA-65-ES256",
+        }
+    }
+
+    /// Get the JOSE `alg` identifier used by this builder
+    #[must_use]
+    pub fn algorithm(&self) -> &'static str {
+        self.algorithm
+    }
+
+    /// Set result handler for key generation
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> PqJwtKeyGenWithHandler<F>
+    where
+        F: FnOnce(JwtResult<PqKeyPair>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        PqJwtKeyGenWithHandler {
+            algorithm: self.algorithm,
+            handler,
+        }
+    }
+
+    /// Generate a new key pair for the configured algorithm
+    ///
+    /// # Errors
+    /// Returns `JwtError` if key generation fails
+    pub async fn generate_keys(self) -> JwtResult<PqKeyPair> {
+        generate_keys(self.algorithm).await
+    }
+
+    /// Set private key for signing
+    #[must_use]
+    pub fn with_private_key(self, private_key: &[u8]) -> PqJwtWithPrivateKey {
+        PqJwtWithPrivateKey {
+            algorithm: self.algorithm,
+            private_key: private_key.to_vec(),
+        }
+    }
+
+    /// Set public key for verification
+    #[must_use]
+    pub fn with_public_key(self, public_key: &[u8]) -> PqJwtWithPublicKey {
+        PqJwtWithPublicKey {
+            algorithm: self.algorithm,
+            public_key: public_key.to_vec(),
+        }
+    }
+}
+
+impl<F, R> PqJwtKeyGenWithHandler<F>
+where
+    F: FnOnce(JwtResult<PqKeyPair>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Generate a new key pair and apply the result handler
+    pub async fn generate_keys(self) -> R {
+        (self.handler)(generate_keys(self.algorithm).await)
+    }
+}
+
+impl PqJwtWithPrivateKey {
+    /// Set claims for JWT
+    #[must_use]
+    pub fn with_claims<T: Serialize + Clone>(self, claims: T) -> PqJwtWithPrivateKeyAndClaims<T> {
+        PqJwtWithPrivateKeyAndClaims {
+            algorithm: self.algorithm,
+            private_key: self.private_key,
+            claims,
+        }
+    }
+}
+
+impl<T: Serialize + Clone> PqJwtWithPrivateKeyAndClaims<T> {
+    /// Set result handler for single JWT signing
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> PqJwtWithPrivateKeyAndClaimsAndHandler<T, F>
+    where
+        F: FnOnce(JwtResult<Vec<u8>>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        PqJwtWithPrivateKeyAndClaimsAndHandler {
+            algorithm: self.algorithm,
+            private_key: self.private_key,
+            claims: self.claims,
+            handler,
+        }
+    }
+}
+
+impl<T, F, R> PqJwtWithPrivateKeyAndClaimsAndHandler<T, F>
+where
+    T: Serialize + Clone + Send + 'static,
+    F: FnOnce(JwtResult<Vec<u8>>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Sign single JWT with the configured post-quantum algorithm
+    #[must_use]
+    pub async fn sign(self) -> R {
+        let result = crate::api::algorithms::sign_jwt(
+            self.algorithm.to_string(),
+            self.claims,
+            None,
+            Some(self.private_key),
+        )
+        .await
+        .map(String::into_bytes);
+
+        // Apply result handler
+        (self.handler)(result)
+    }
+}
+
+impl PqJwtWithPublicKey {
+    /// Set result handler for JWT verification
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> PqJwtWithPublicKeyAndHandler<F>
+    where
+        F: FnOnce(JwtResult<serde_json::Value>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        PqJwtWithPublicKeyAndHandler {
+            algorithm: self.algorithm,
+            public_key: self.public_key,
+            handler,
+        }
+    }
+}
+
+impl<F, R> PqJwtWithPublicKeyAndHandler<F>
+where
+    F: FnOnce(JwtResult<serde_json::Value>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Verify JWT with the configured algorithm only
+    pub async fn verify<S: AsRef<str>>(self, token: S) -> R {
+        let result = crate::api::algorithms::verify_jwt_for(
+            &[self.algorithm],
+            token.as_ref().to_string(),
+            None,
+            Some(self.public_key),
+        )
+        .await;
+
+        (self.handler)(result)
+    }
+}
+
+/// Generate a key pair for the given post-quantum `alg`
+async fn generate_keys(algorithm: &'static str) -> JwtResult<PqKeyPair> {
+    let (private_key, public_key) = generate_pq_keypair(algorithm).await?;
+    Ok(PqKeyPair {
+        algorithm: algorithm.to_string(),
+        private_key,
+        public_key,
+    })
+}
@@ -0,0 +1,278 @@
+//! PS256/PS384/PS512 JWT Builder - Polymorphic pattern for RSA-PSS JWT operations
+//!
+//! Provides polymorphic builder pattern for RSASSA-PSS JWT key generation,
+//! signing and verification. Keys are PKCS#8 (private) and SPKI (public) in DER
+//! or PEM form.
+
+use crate::api::algorithms::rsa_pss::{
+    RSA_PSS_KEY_BITS, generate_rsa_keypair, sign_ps256, sign_ps384, sign_ps512,
+};
+use crate::api::keys::{key_to_der, validate_rsa_private_key};
+use crate::error::{JwtError, JwtResult};
+use crate::types::RsaPssKeyPair;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::Serialize;
+
+/// RSA-PSS JWT builder - initial state
+#[derive(Debug, Clone)]
+pub struct PsJwtBuilder {
+    algorithm: &'static str,
+}
+
+/// RSA-PSS key generator with result handler
+#[derive(Debug)]
+pub struct PsJwtKeyGenWithHandler<F> {
+    handler: F,
+}
+
+/// RSA-PSS JWT builder with private key configured
+#[derive(Debug, Clone)]
+pub struct PsJwtWithPrivateKey {
+    algorithm: &'static str,
+    private_key: Vec<u8>,
+}
+
+/// RSA-PSS JWT builder with private key and claims configured
+#[derive(Debug, Clone)]
+pub struct PsJwtWithPrivateKeyAndClaims<T> {
+    algorithm: &'static str,
+    private_key: Vec<u8>,
+    claims: T,
+}
+
+/// RSA-PSS JWT builder with private key, claims and result handler
+#[derive(Debug)]
+pub struct PsJwtWithPrivateKeyAndClaimsAndHandler<T, F> {
+    algorithm: &'static str,
+    private_key: Vec<u8>,
+    claims: T,
+    handler: F,
+}
+
+/// RSA-PSS JWT builder with public key configured
+#[derive(Debug, Clone)]
+pub struct PsJwtWithPublicKey {
+    algorithm: &'static str,
+    public_key: Vec<u8>,
+}
+
+/// RSA-PSS JWT builder with public key and result handler
+#[derive(Debug)]
+pub struct PsJwtWithPublicKeyAndHandler<F> {
+    algorithm: &'static str,
+    public_key: Vec<u8>,
+    handler: F,
+}
+
+impl PsJwtBuilder {
+    /// Create new PS256 JWT builder
+    #[must_use]
+    pub fn ps256() -> Self {
+        Self { algorithm: "PS256" }
+    }
+
+    /// Create new PS384 JWT builder
+    #[must_use]
+    pub fn ps384() -> Self {
+        Self { algorithm: "PS384" }
+    }
+
+    /// Create new PS512 JWT builder
+    #[must_use]
+    pub fn ps512() -> Self {
+        Self { algorithm: "PS512" }
+    }
+
+    /// Set result handler for key generation
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> PsJwtKeyGenWithHandler<F>
+    where
+        F: FnOnce(JwtResult<RsaPssKeyPair>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        PsJwtKeyGenWithHandler { handler }
+    }
+
+    /// Generate a new 2048-bit RSA key pair
+    ///
+    /// # Errors
+    /// Returns `JwtError` if key generation or encoding fails
+    pub async fn generate_keys(self) -> JwtResult<RsaPssKeyPair> {
+        generate_keys().await
+    }
+
+    /// Set PKCS#8 RSA private key (DER or PEM) for signing
+    #[must_use]
+    pub fn with_private_key(self, private_key: &[u8]) -> PsJwtWithPrivateKey {
+        PsJwtWithPrivateKey {
+            algorithm: self.algorithm,
+            private_key: private_key.to_vec(),
+        }
+    }
+
+    /// Set SPKI RSA public key (DER or PEM) for verification
+    #[must_use]
+    pub fn with_public_key(self, public_key: &[u8]) -> PsJwtWithPublicKey {
+        PsJwtWithPublicKey {
+            algorithm: self.algorithm,
+            public_key: public_key.to_vec(),
+        }
+    }
+}
+
+impl<F, R> PsJwtKeyGenWithHandler<F>
+where
+    F: FnOnce(JwtResult<RsaPssKeyPair>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Generate a new 2048-bit RSA key pair and apply the result handler
+    pub async fn generate_keys(self) -> R {
+        (self.handler)(generate_keys().await)
+    }
+}
+
+impl PsJwtWithPrivateKey {
+    /// Set claims for JWT
+    #[must_use]
+    pub fn with_claims<T: Serialize + Clone>(self, claims: T) -> PsJwtWithPrivateKeyAndClaims<T> {
+        PsJwtWithPrivateKeyAndClaims {
+            algorithm: self.algorithm,
+            private_key: self.private_key,
+            claims,
+        }
+    }
+}
+
+impl<T: Serialize + Clone> PsJwtWithPrivateKeyAndClaims<T> {
+    /// Set result handler for single JWT signing
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> PsJwtWithPrivateKeyAndClaimsAndHandler<T, F>
+    where
+        F: FnOnce(JwtResult<Vec<u8>>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        PsJwtWithPrivateKeyAndClaimsAndHandler {
+            algorithm: self.algorithm,
+            private_key: self.private_key,
+            claims: self.claims,
+            handler,
+        }
+    }
+}
+
+impl<T, F, R> PsJwtWithPrivateKeyAndClaimsAndHandler<T, F>
+where
+    T: Serialize + Clone + Send + 'static,
+    F: FnOnce(JwtResult<Vec<u8>>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Sign single JWT with RSA-PSS
+    #[must_use]
+    pub async fn sign(self) -> R {
+        let algorithm = self.algorithm;
+        let private_key = self.private_key;
+        let claims = self.claims;
+
+        // RSA signing is CPU heavy - keep it off the async worker threads
+        let result = tokio::task::spawn_blocking(move || {
+            sign_ps_jwt(algorithm, &claims, &private_key).map(String::into_bytes)
+        })
+        .await
+        .unwrap_or_else(|_| Err(JwtError::TaskFailed));
+
+        // Apply result handler
+        (self.handler)(result)
+    }
+}
+
+impl PsJwtWithPublicKey {
+    /// Set result handler for JWT verification
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> PsJwtWithPublicKeyAndHandler<F>
+    where
+        F: FnOnce(JwtResult<serde_json::Value>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        PsJwtWithPublicKeyAndHandler {
+            algorithm: self.algorithm,
+            public_key: self.public_key,
+            handler,
+        }
+    }
+}
+
+impl<F, R> PsJwtWithPublicKeyAndHandler<F>
+where
+    F: FnOnce(JwtResult<serde_json::Value>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Verify JWT with the configured RSA-PSS algorithm only
+    pub async fn verify<S: AsRef<str>>(self, token: S) -> R {
+        let token = token.as_ref().to_string();
+        let result = match key_to_der(&self.public_key) {
+            Ok(public_key) => {
+                crate::api::algorithms::verify_jwt_for(
+                    &[self.algorithm],
+                    token,
+                    None,
+                    Some(public_key),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+
+        (self.handler)(result)
+    }
+}
+
+/// Generate an RSA key pair for PS256/PS384/PS512 on the blocking pool
+async fn generate_keys() -> JwtResult<RsaPssKeyPair> {
+    let (private_key, public_key) =
+        tokio::task::spawn_blocking(|| generate_rsa_keypair(RSA_PSS_KEY_BITS))
+            .await
+            .map_err(|_| JwtError::TaskFailed)??;
+
+    Ok(RsaPssKeyPair {
+        private_key,
+        public_key,
+    })
+}
+
+/// Production RSA-PSS JWT signing implementation
+fn sign_ps_jwt<T: Serialize>(
+    algorithm: &str,
+    claims: &T,
+    private_key: &[u8],
+) -> JwtResult<String> {
+    let private_key = key_to_der(private_key)?;
+    validate_rsa_private_key(&private_key)?;
+
+    // Create header
+    let header = serde_json::json!({
+        "alg": algorithm,
+        "typ": "JWT"
+    });
+
+    // Serialize header and claims
+    let header_bytes =
+        serde_json::to_vec(&header).map_err(|e| JwtError::InvalidToken(e.to_string()))?;
+    let claims_bytes =
+        serde_json::to_vec(claims).map_err(|e| JwtError::InvalidToken(e.to_string()))?;
+
+    let header_b64 = URL_SAFE_NO_PAD.encode(&header_bytes);
+    let claims_b64 = URL_SAFE_NO_PAD.encode(&claims_bytes);
+
+    // Create signature payload
+    let signature_payload = format!("{header_b64}.{claims_b64}");
+
+    let signature = match algorithm {
+        "PS256" => sign_ps256(&signature_payload, &private_key)?,
+        "PS384" => sign_ps384(&signature_payload, &private_key)?,
+        "PS512" => sign_ps512(&signature_payload, &private_key)?,
+        _ => return Err(JwtError::unsupported_algorithm(algorithm)),
+    };
+    let signature_b64 = URL_SAFE_NO_PAD.encode(&signature);
+
+    Ok(format!("{header_b64}.{claims_b64}.{signature_b64}"))
+}
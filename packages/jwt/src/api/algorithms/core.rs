@@ -4,9 +4,13 @@
 //! core JWT signing and verification operations with production-grade security.
 
 use super::ecdsa::{sign_es256, sign_es384, verify_es256, verify_es384};
+use super::eddsa::{sign_eddsa, verify_eddsa};
 use super::hmac::{sign_hs256, sign_hs384, sign_hs512, verify_hs256, verify_hs384, verify_hs512};
 use super::rsa::{sign_rs256, sign_rs384, sign_rs512, verify_rs256, verify_rs384, verify_rs512};
+use super::rsa_pss::{sign_ps256, sign_ps384, sign_ps512, verify_ps256, verify_ps384, verify_ps512};
 use super::utils::{base64_url_decode, base64_url_encode};
+use crate::api::claims::ClaimsValidator;
+use crate::api::claims::validator::check_critical;
 use crate::{error::JwtError, types::JwtHeader};
 use serde::Serialize;
 use tokio::sync::oneshot;
@@ -18,6 +22,17 @@ pub(crate) async fn sign_jwt<C: Serialize + Send + 'static>(
     claims: C,
     secret: Option<Vec<u8>>,
     private_key: Option<Vec<u8>>,
+) -> Result<String, JwtError> {
+    sign_jwt_with_kid(algorithm, None, claims, secret, private_key).await
+}
+
+/// Internal JWT signing operation carrying an optional `kid` header
+pub(crate) async fn sign_jwt_with_kid<C: Serialize + Send + 'static>(
+    algorithm: String,
+    kid: Option<String>,
+    claims: C,
+    secret: Option<Vec<u8>>,
+    private_key: Option<Vec<u8>>,
 ) -> Result<String, JwtError> {
     let (tx, rx) = oneshot::channel();
 
@@ -25,7 +40,7 @@ pub(crate) async fn sign_jwt<C: Serialize + Send + 'static>(
         // Yield for cooperative multitasking
         tokio::task::yield_now().await;
 
-        let result = (|| {
+        let result = async move {
             // Serialize claims with zero-allocation patterns
             let claims_value = serde_json::to_value(&claims)
                 .map_err(|e| JwtError::InvalidClaims(e.to_string()))?;
@@ -34,7 +49,7 @@ pub(crate) async fn sign_jwt<C: Serialize + Send + 'static>(
             let header = JwtHeader {
                 alg: algorithm.clone(),
                 typ: "JWT".to_string(),
-                kid: None,
+                kid,
             };
 
             // Encode header and payload with zero-allocation base64
@@ -114,12 +129,53 @@ pub(crate) async fn sign_jwt<C: Serialize + Send + 'static>(
                     crate::api::keys::validate_ec_private_key(&key, "ES384")?;
                     sign_es384(&message, &key)?
                 }
+                "PS256" => {
+                    let key = private_key.ok_or_else(|| {
+                        JwtError::MissingKey("Private key required for PS256".to_string())
+                    })?;
+                    // Validate RSA private key for security
+                    crate::api::keys::validate_rsa_private_key(&key)?;
+                    sign_ps256(&message, &key)?
+                }
+                "PS384" => {
+                    let key = private_key.ok_or_else(|| {
+                        JwtError::MissingKey("Private key required for PS384".to_string())
+                    })?;
+                    // Validate RSA private key for security
+                    crate::api::keys::validate_rsa_private_key(&key)?;
+                    sign_ps384(&message, &key)?
+                }
+                "PS512" => {
+                    let key = private_key.ok_or_else(|| {
+                        JwtError::MissingKey("Private key required for PS512".to_string())
+                    })?;
+                    // Validate RSA private key for security
+                    crate::api::keys::validate_rsa_private_key(&key)?;
+                    sign_ps512(&message, &key)?
+                }
+                "EdDSA" => {
+                    let key = private_key.ok_or_else(|| {
+                        JwtError::MissingKey("Private key required for EdDSA".to_string())
+                    })?;
+                    // Validate Ed25519/Ed448 private key for security
+                    crate::api::keys::validate_okp_private_key(&key)?;
+                    sign_eddsa(&message, &key)?
+                }
+                alg if super::pq::is_pq_algorithm(alg) => {
+                    let key = private_key.ok_or_else(|| {
+                        JwtError::MissingKey(format!("Private key required for {alg}"))
+                    })?;
+                    // Validate post-quantum private key for security
+                    crate::api::keys::validate_pq_private_key(&key, alg)?;
+                    super::pq::sign_pq(alg, message.as_bytes(), &key).await?
+                }
                 _ => return Err(JwtError::UnsupportedAlgorithm(algorithm)),
             };
 
             let signature_b64 = base64_url_encode(&signature);
             Ok(format!("{message}.{signature_b64}"))
-        })();
+        }
+        .await;
 
         let _ = tx.send(result);
     });
@@ -129,7 +185,7 @@ pub(crate) async fn sign_jwt<C: Serialize + Send + 'static>(
 }
 
 /// Verify JWT signature based on algorithm
-fn verify_jwt_signature(
+async fn verify_jwt_signature(
     algorithm: &str,
     message: &str,
     signature: &[u8],
@@ -201,6 +257,46 @@ fn verify_jwt_signature(
             crate::api::keys::validate_ec_public_key(&key, "ES384")?;
             verify_es384(message, signature, &key)
         }
+        "PS256" => {
+            let key = public_key.ok_or_else(|| {
+                JwtError::MissingKey("Public key required for PS256 verification".to_string())
+            })?;
+            // Validate RSA public key for security
+            crate::api::keys::validate_rsa_public_key(&key)?;
+            verify_ps256(message, signature, &key)
+        }
+        "PS384" => {
+            let key = public_key.ok_or_else(|| {
+                JwtError::MissingKey("Public key required for PS384 verification".to_string())
+            })?;
+            // Validate RSA public key for security
+            crate::api::keys::validate_rsa_public_key(&key)?;
+            verify_ps384(message, signature, &key)
+        }
+        "PS512" => {
+            let key = public_key.ok_or_else(|| {
+                JwtError::MissingKey("Public key required for PS512 verification".to_string())
+            })?;
+            // Validate RSA public key for security
+            crate::api::keys::validate_rsa_public_key(&key)?;
+            verify_ps512(message, signature, &key)
+        }
+        "EdDSA" => {
+            let key = public_key.ok_or_else(|| {
+                JwtError::MissingKey("Public key required for EdDSA verification".to_string())
+            })?;
+            // Validate Ed25519/Ed448 public key for security
+            crate::api::keys::validate_okp_public_key(&key)?;
+            verify_eddsa(message, signature, &key)
+        }
+        alg if super::pq::is_pq_algorithm(alg) => {
+            let key = public_key.ok_or_else(|| {
+                JwtError::MissingKey(format!("Public key required for {alg} verification"))
+            })?;
+            // Validate post-quantum public key for security
+            crate::api::keys::validate_pq_public_key(&key, alg)?;
+            super::pq::verify_pq(alg, message.as_bytes(), signature, &key).await
+        }
         _ => Err(JwtError::UnsupportedAlgorithm(algorithm.to_string())),
     }
 }
@@ -208,10 +304,39 @@ fn verify_jwt_signature(
 /// Internal JWT verification operation using pure async
 /// Zero-allocation async coordination with blazing-fast performance
 /// Fixed: Removed nested `tokio::spawn` to prevent deadlock in spawned contexts
+///
+/// The token's `alg` header must equal `algorithm`; the header never picks
+/// the verification algorithm on its own.
 pub(crate) async fn verify_jwt(
+    algorithm: &str,
+    token: String,
+    secret: Option<Vec<u8>>,
+    public_key: Option<Vec<u8>>,
+) -> Result<serde_json::Value, JwtError> {
+    verify_jwt_for(&[algorithm], token, secret, public_key).await
+}
+
+/// Internal JWT verification restricted to the given algorithms
+/// An empty `algorithms` slice accepts any supported algorithm from the header
+pub(crate) async fn verify_jwt_for(
+    algorithms: &[&str],
     token: String,
     secret: Option<Vec<u8>>,
     public_key: Option<Vec<u8>>,
+) -> Result<serde_json::Value, JwtError> {
+    verify_jwt_validated(algorithms, token, secret, public_key, None).await
+}
+
+/// Internal JWT verification with header and claims checked by `validator`
+///
+/// Without a validator, standard claims are checked with no leeway and any
+/// `crit` header parameter is rejected.
+pub(crate) async fn verify_jwt_validated(
+    algorithms: &[&str],
+    token: String,
+    secret: Option<Vec<u8>>,
+    public_key: Option<Vec<u8>>,
+    validator: Option<&ClaimsValidator>,
 ) -> Result<serde_json::Value, JwtError> {
     // Yield for cooperative multitasking
     tokio::task::yield_now().await;
@@ -229,8 +354,15 @@ pub(crate) async fn verify_jwt(
     // Decode header with blazing-fast base64
     let header_bytes = base64_url_decode(header_b64)
         .map_err(|_| JwtError::InvalidToken("Invalid header encoding".to_string()))?;
-    let header: JwtHeader = serde_json::from_slice(&header_bytes)
+    let header_value: serde_json::Value = serde_json::from_slice(&header_bytes)
         .map_err(|_| JwtError::InvalidToken("Invalid header JSON".to_string()))?;
+    let header: JwtHeader = serde_json::from_value(header_value.clone())
+        .map_err(|_| JwtError::InvalidToken("Invalid header JSON".to_string()))?;
+
+    // Reject algorithm substitution when the caller pinned the algorithm
+    if !algorithms.is_empty() && !algorithms.contains(&header.alg.as_str()) {
+        return Err(JwtError::UnsupportedAlgorithm(header.alg));
+    }
 
     // Decode payload with blazing-fast base64
     let payload_bytes = base64_url_decode(payload_b64)
@@ -243,14 +375,24 @@ pub(crate) async fn verify_jwt(
     let signature = base64_url_decode(signature_b64)
         .map_err(|_| JwtError::InvalidToken("Invalid signature encoding".to_string()))?;
 
-    let valid = verify_jwt_signature(&header.alg, &message, &signature, secret, public_key)?;
+    let valid =
+        verify_jwt_signature(&header.alg, &message, &signature, secret, public_key).await?;
 
     if !valid {
         return Err(JwtError::InvalidSignature);
     }
 
-    // Validate claims with blazing-fast validation
-    super::utils::validate_standard_claims(&claims)?;
+    // Validate header and claims with blazing-fast validation
+    match validator {
+        Some(validator) => {
+            validator.validate_header(&header_value)?;
+            validator.validate(&claims)?;
+        }
+        None => {
+            check_critical(&header_value, &[])?;
+            super::utils::validate_standard_claims(&claims)?;
+        }
+    }
 
     Ok(claims)
 }
@@ -0,0 +1,185 @@
+//! EdDSA-based JWT Algorithm Implementations
+//!
+//! This module provides implementations of the RFC 8037 `EdDSA` algorithm for
+//! JWT signing and verification over the Ed25519 and Ed448 curves. The curve
+//! is selected by the key, which is carried as PKCS#8 (private) or SPKI
+//! (public) DER. Ed25519 uses `ed25519-dalek` and Ed448 the RustCrypto
+//! `ed448-goldilocks` crate, both with constant-time field arithmetic.
+
+use crate::error::JwtError;
+use crate::types::EdDsaCurve;
+
+/// DER prefix of a PKCS#8 v1 `PrivateKeyInfo` holding an Ed25519 seed
+const ED25519_PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20,
+];
+
+/// DER prefix of a PKCS#8 v1 `PrivateKeyInfo` holding an Ed448 seed
+const ED448_PKCS8_PREFIX: [u8; 16] = [
+    0x30, 0x47, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x71, 0x04, 0x3b, 0x04, 0x39,
+];
+
+/// DER prefix of an Ed25519 `SubjectPublicKeyInfo`
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// DER prefix of an Ed448 `SubjectPublicKeyInfo`
+const ED448_SPKI_PREFIX: [u8; 12] = [
+    0x30, 0x43, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x71, 0x03, 0x3a, 0x00,
+];
+
+/// Decode a PKCS#8 DER Ed25519/Ed448 private key into its curve and raw seed
+pub(crate) fn decode_okp_private_key(der: &[u8]) -> Result<(EdDsaCurve, &[u8]), JwtError> {
+    if der.len() == ED25519_PKCS8_PREFIX.len() + 32 && der.starts_with(&ED25519_PKCS8_PREFIX) {
+        Ok((EdDsaCurve::Ed25519, &der[ED25519_PKCS8_PREFIX.len()..]))
+    } else if der.len() == ED448_PKCS8_PREFIX.len() + 57 && der.starts_with(&ED448_PKCS8_PREFIX) {
+        Ok((EdDsaCurve::Ed448, &der[ED448_PKCS8_PREFIX.len()..]))
+    } else {
+        Err(JwtError::invalid_key(
+            "EdDSA private key must be PKCS#8 DER for Ed25519 or Ed448",
+        ))
+    }
+}
+
+/// Decode an SPKI DER Ed25519/Ed448 public key into its curve and raw point
+pub(crate) fn decode_okp_public_key(der: &[u8]) -> Result<(EdDsaCurve, &[u8]), JwtError> {
+    if der.len() == ED25519_SPKI_PREFIX.len() + 32 && der.starts_with(&ED25519_SPKI_PREFIX) {
+        Ok((EdDsaCurve::Ed25519, &der[ED25519_SPKI_PREFIX.len()..]))
+    } else if der.len() == ED448_SPKI_PREFIX.len() + 57 && der.starts_with(&ED448_SPKI_PREFIX) {
+        Ok((EdDsaCurve::Ed448, &der[ED448_SPKI_PREFIX.len()..]))
+    } else {
+        Err(JwtError::invalid_key(
+            "EdDSA public key must be SPKI DER for Ed25519 or Ed448",
+        ))
+    }
+}
+
+/// Encode a raw Ed25519/Ed448 seed as PKCS#8 DER
+pub(crate) fn encode_okp_private_key(curve: EdDsaCurve, seed: &[u8]) -> Vec<u8> {
+    let prefix: &[u8] = match curve {
+        EdDsaCurve::Ed25519 => &ED25519_PKCS8_PREFIX,
+        EdDsaCurve::Ed448 => &ED448_PKCS8_PREFIX,
+    };
+    let mut der = Vec::with_capacity(prefix.len() + seed.len());
+    der.extend_from_slice(prefix);
+    der.extend_from_slice(seed);
+    der
+}
+
+/// Encode a raw Ed25519/Ed448 public point as SPKI DER
+pub(crate) fn encode_okp_public_key(curve: EdDsaCurve, point: &[u8]) -> Vec<u8> {
+    let prefix: &[u8] = match curve {
+        EdDsaCurve::Ed25519 => &ED25519_SPKI_PREFIX,
+        EdDsaCurve::Ed448 => &ED448_SPKI_PREFIX,
+    };
+    let mut der = Vec::with_capacity(prefix.len() + point.len());
+    der.extend_from_slice(prefix);
+    der.extend_from_slice(point);
+    der
+}
+
+/// Derive the raw public point for a raw Ed25519/Ed448 seed
+pub(crate) fn okp_public_from_seed(curve: EdDsaCurve, seed: &[u8]) -> Result<Vec<u8>, JwtError> {
+    match curve {
+        EdDsaCurve::Ed25519 => {
+            let seed: [u8; 32] = seed
+                .try_into()
+                .map_err(|_| JwtError::invalid_key("Ed25519 seed must be 32 bytes"))?;
+            let signing_key = ed25519_dalek::SigningKey::from_bytes(&seed);
+            Ok(signing_key.verifying_key().to_bytes().to_vec())
+        }
+        EdDsaCurve::Ed448 => Ok(ed448_signing_key(seed)?.verifying_key().to_bytes().to_vec()),
+    }
+}
+
+/// Generate a fresh `EdDSA` key pair as (PKCS#8 DER, SPKI DER)
+pub(crate) fn generate_eddsa_keypair(curve: EdDsaCurve) -> Result<(Vec<u8>, Vec<u8>), JwtError> {
+    let mut seed = vec![0u8; curve.key_size()];
+    getrandom::fill(&mut seed)
+        .map_err(|e| JwtError::Internal(format!("Failed to gather entropy: {e}")))?;
+
+    let public = okp_public_from_seed(curve, &seed)?;
+    let private_der = encode_okp_private_key(curve, &seed);
+    zeroize::Zeroize::zeroize(&mut seed);
+
+    Ok((private_der, encode_okp_public_key(curve, &public)))
+}
+
+/// Sign with `EdDSA` (Ed25519 or Ed448, chosen by the PKCS#8 key)
+#[inline]
+pub(crate) fn sign_eddsa(message: &str, private_key: &[u8]) -> Result<Vec<u8>, JwtError> {
+    let (curve, seed) = decode_okp_private_key(private_key)?;
+
+    match curve {
+        EdDsaCurve::Ed25519 => {
+            use ed25519_dalek::Signer;
+
+            let seed: [u8; 32] = seed
+                .try_into()
+                .map_err(|_| JwtError::invalid_key("Ed25519 seed must be 32 bytes"))?;
+            let signing_key = ed25519_dalek::SigningKey::from_bytes(&seed);
+            Ok(signing_key.sign(message.as_bytes()).to_bytes().to_vec())
+        }
+        EdDsaCurve::Ed448 => Ok(ed448_signing_key(seed)?
+            .sign_raw(message.as_bytes())
+            .to_bytes()
+            .to_vec()),
+    }
+}
+
+/// Verify `EdDSA` signature (Ed25519 or Ed448, chosen by the SPKI key)
+#[inline]
+pub(crate) fn verify_eddsa(
+    message: &str,
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<bool, JwtError> {
+    let (curve, point) = decode_okp_public_key(public_key)?;
+
+    match curve {
+        EdDsaCurve::Ed25519 => {
+            let point: [u8; 32] = point
+                .try_into()
+                .map_err(|_| JwtError::invalid_key("Ed25519 public key must be 32 bytes"))?;
+            let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&point)
+                .map_err(|e| JwtError::InvalidKey(format!("Invalid Ed25519 public key: {e}")))?;
+            let signature = ed25519_dalek::Signature::from_slice(signature)
+                .map_err(|_| JwtError::InvalidSignature)?;
+
+            Ok(verifying_key
+                .verify_strict(message.as_bytes(), &signature)
+                .is_ok())
+        }
+        EdDsaCurve::Ed448 => {
+            let verifying_key = ed448_verifying_key(point)?;
+            let signature: [u8; 114] = signature
+                .try_into()
+                .map_err(|_| JwtError::InvalidSignature)?;
+            let signature = ed448_goldilocks::Signature::from_bytes(&signature)
+                .map_err(|_| JwtError::InvalidSignature)?;
+
+            Ok(verifying_key
+                .verify_raw(&signature, message.as_bytes())
+                .is_ok())
+        }
+    }
+}
+
+/// Ed448 signing key for a raw 57-byte seed
+fn ed448_signing_key(seed: &[u8]) -> Result<ed448_goldilocks::SigningKey, JwtError> {
+    let seed = ed448_goldilocks::SecretKey::try_from(seed)
+        .map_err(|_| JwtError::invalid_key("Ed448 seed must be 57 bytes"))?;
+    Ok(ed448_goldilocks::SigningKey::from_bytes(&seed))
+}
+
+/// Ed448 verifying key for a raw 57-byte point
+pub(crate) fn ed448_verifying_key(
+    point: &[u8],
+) -> Result<ed448_goldilocks::VerifyingKey, JwtError> {
+    let point: [u8; 57] = point
+        .try_into()
+        .map_err(|_| JwtError::invalid_key("Ed448 public key must be 57 bytes"))?;
+    ed448_goldilocks::VerifyingKey::from_bytes(&point)
+        .map_err(|_| JwtError::invalid_key("Invalid Ed448 public key"))
+}
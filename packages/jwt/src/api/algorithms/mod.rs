@@ -5,9 +5,14 @@
 
 mod core;
 mod ecdsa;
+pub(crate) mod eddsa;
 mod hmac;
+pub(crate) mod pq;
 pub mod rsa;
+pub(crate) mod rsa_pss;
 mod utils;
 
 // Re-export the main API functions
-pub(crate) use core::{sign_jwt, verify_jwt};
+pub(crate) use core::{sign_jwt, sign_jwt_with_kid, verify_jwt, verify_jwt_for, verify_jwt_validated};
+pub use pq::PQ_ALGORITHMS;
+pub(crate) use utils::validate_standard_claims;
@@ -0,0 +1,196 @@
+//! Post-quantum JWT Algorithm Implementations
+//!
+//! This module provides ML-DSA (FIPS 204) and SLH-DSA (FIPS 205) JWT signing and
+//! verification backed by `cryypt_pqcrypto`, using the `alg` identifiers from
+//! the IETF JOSE/COSE post-quantum drafts. Keys are the raw encodings produced
+//! by `cryypt_pqcrypto`. SLH-DSA always runs on the native FIPS 205 backend,
+//! never on the PQClean SPHINCS+ round 3.1 implementation.
+//!
+//! The composite `ML-DSA-65-ES256` algorithm is the `cryypt_pqcrypto`
+//! `MlDsa65EcdsaP256` composite (draft-ietf-lamps-pq-composite-sigs): it signs
+//! with both ML-DSA and ECDSA P-256 and only verifies when both signatures are
+//! valid, so a token stays secure as long as either primitive holds during
+//! migration. Keys and signatures use that composite's encoding.
+
+use crate::error::JwtError;
+use cryypt_pqcrypto::api::{CompositeBuilder, MlDsaBuilder};
+use cryypt_pqcrypto::{
+    Backend, CompositeAlgorithm, MessageBuilder, PqCryptoError, SignBuilder, SignatureAlgorithm,
+    SignatureDataBuilder, SignatureKeyPairBuilder, SigningOptions, VerifyBuilder,
+};
+
+/// Post-quantum `alg` identifiers supported for JWT signing
+pub const PQ_ALGORITHMS: [&str; 10] = [
+    "ML-DSA-44",
+    "ML-DSA-65",
+    "ML-DSA-87",
+    "SLH-DSA-SHA2-128s",
+    "SLH-DSA-SHA2-128f",
+    "SLH-DSA-SHA2-192s",
+    "SLH-DSA-SHA2-192f",
+    "SLH-DSA-SHA2-256s",
+    "SLH-DSA-SHA2-256f",
+    "ML-DSA-65-ES256",
+];
+
+/// Map a standalone post-quantum `alg` to its `cryypt_pqcrypto` algorithm
+pub(crate) fn pq_signature_algorithm(alg: &str) -> Option<SignatureAlgorithm> {
+    match alg {
+        "ML-DSA-44" => Some(SignatureAlgorithm::MlDsa44),
+        "ML-DSA-65" => Some(SignatureAlgorithm::MlDsa65),
+        "ML-DSA-87" => Some(SignatureAlgorithm::MlDsa87),
+        "SLH-DSA-SHA2-128s" => Some(SignatureAlgorithm::SphincsShaSha256_128sSimple),
+        "SLH-DSA-SHA2-128f" => Some(SignatureAlgorithm::SphincsShaSha256_128fSimple),
+        "SLH-DSA-SHA2-192s" => Some(SignatureAlgorithm::SphincsShaSha256_192sSimple),
+        "SLH-DSA-SHA2-192f" => Some(SignatureAlgorithm::SphincsShaSha256_192fSimple),
+        "SLH-DSA-SHA2-256s" => Some(SignatureAlgorithm::SphincsShaSha256_256sSimple),
+        "SLH-DSA-SHA2-256f" => Some(SignatureAlgorithm::SphincsShaSha256_256fSimple),
+        _ => None,
+    }
+}
+
+/// Map a composite `alg` to its `cryypt_pqcrypto` composite algorithm
+pub(crate) fn composite_algorithm(alg: &str) -> Option<CompositeAlgorithm> {
+    match alg {
+        "ML-DSA-65-ES256" => Some(CompositeAlgorithm::MlDsa65EcdsaP256),
+        _ => None,
+    }
+}
+
+/// Whether `alg` is handled by this module
+pub(crate) fn is_pq_algorithm(alg: &str) -> bool {
+    PQ_ALGORITHMS.contains(&alg)
+}
+
+/// Generate a key pair for a post-quantum `alg` as (private key, public key)
+pub(crate) async fn generate_pq_keypair(alg: &str) -> Result<(Vec<u8>, Vec<u8>), JwtError> {
+    if let Some(composite) = composite_algorithm(alg) {
+        let keypair = CompositeBuilder::new(composite)
+            .generate()
+            .await
+            .map_err(map_pq_error)?;
+        return Ok((
+            keypair.secret_key_vec().map_err(map_pq_error)?,
+            keypair.public_key_vec().map_err(map_pq_error)?,
+        ));
+    }
+
+    let algorithm =
+        pq_signature_algorithm(alg).ok_or_else(|| JwtError::unsupported_algorithm(alg))?;
+    if !is_ml_dsa(algorithm) {
+        let (public_key, secret_key) = Backend::Native
+            .signature_keypair(algorithm)
+            .map_err(map_pq_error)?;
+        return Ok((secret_key.to_vec(), public_key));
+    }
+
+    let keypair = MlDsaBuilder::new(algorithm)
+        .map_err(map_pq_error)?
+        .generate()
+        .await
+        .map_err(map_pq_error)?;
+    Ok((
+        keypair.secret_key_vec().map_err(map_pq_error)?,
+        keypair.public_key_vec().map_err(map_pq_error)?,
+    ))
+}
+
+/// Sign `message` with a post-quantum or composite `alg`
+pub(crate) async fn sign_pq(
+    alg: &str,
+    message: &[u8],
+    private_key: &[u8],
+) -> Result<Vec<u8>, JwtError> {
+    if let Some(composite) = composite_algorithm(alg) {
+        let signature = CompositeBuilder::new(composite)
+            .with_secret_key(private_key)
+            .map_err(map_pq_error)?
+            .with_message(message)
+            .sign()
+            .await
+            .map_err(map_pq_error)?;
+        return Ok(signature.signature_vec());
+    }
+
+    let algorithm =
+        pq_signature_algorithm(alg).ok_or_else(|| JwtError::unsupported_algorithm(alg))?;
+    if !is_ml_dsa(algorithm) {
+        return Backend::Native
+            .sign(algorithm, private_key, message, &SigningOptions::new())
+            .map_err(map_pq_error);
+    }
+
+    let signature = MlDsaBuilder::new(algorithm)
+        .and_then(|builder| builder.with_secret_key(private_key))
+        .map_err(map_pq_error)?
+        .with_message(message)
+        .sign()
+        .await
+        .map_err(map_pq_error)?;
+    Ok(signature.signature_vec())
+}
+
+/// Verify a post-quantum or composite `alg` signature over `message`
+pub(crate) async fn verify_pq(
+    alg: &str,
+    message: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<bool, JwtError> {
+    if let Some(composite) = composite_algorithm(alg) {
+        // Both components must verify - the composite never falls back to one primitive
+        let result = CompositeBuilder::new(composite)
+            .with_public_key(public_key)
+            .map_err(map_pq_error)?
+            .with_message(message)
+            .with_signature(signature)
+            .verify()
+            .await
+            .map_err(map_pq_error)?;
+        return Ok(result.is_valid());
+    }
+
+    let algorithm =
+        pq_signature_algorithm(alg).ok_or_else(|| JwtError::unsupported_algorithm(alg))?;
+    if !is_ml_dsa(algorithm) {
+        return Backend::Native
+            .verify(
+                algorithm,
+                public_key,
+                message,
+                signature,
+                &SigningOptions::new(),
+            )
+            .map_err(map_pq_error);
+    }
+
+    let result = MlDsaBuilder::new(algorithm)
+        .and_then(|builder| builder.with_public_key(public_key))
+        .map_err(map_pq_error)?
+        .with_message(message)
+        .with_signature(signature)
+        .verify()
+        .await
+        .map_err(map_pq_error)?;
+    Ok(result.is_valid())
+}
+
+/// Whether `algorithm` is one of the ML-DSA parameter sets
+fn is_ml_dsa(algorithm: SignatureAlgorithm) -> bool {
+    matches!(
+        algorithm,
+        SignatureAlgorithm::MlDsa44 | SignatureAlgorithm::MlDsa65 | SignatureAlgorithm::MlDsa87
+    )
+}
+
+/// Convert a `cryypt_pqcrypto` error into the closest `JwtError`
+fn map_pq_error(error: PqCryptoError) -> JwtError {
+    match error {
+        PqCryptoError::InvalidKey(msg) => JwtError::InvalidKey(msg),
+        PqCryptoError::InvalidParameters(_) | PqCryptoError::AuthenticationFailed(_) => {
+            JwtError::InvalidSignature
+        }
+        PqCryptoError::UnsupportedAlgorithm(alg) => JwtError::UnsupportedAlgorithm(alg),
+        other => JwtError::SigningError(other.to_string()),
+    }
+}
@@ -0,0 +1,123 @@
+//! RSA-PSS-based JWT Algorithm Implementations
+//!
+//! This module provides implementations of the RSASSA-PSS algorithms
+//! (PS256, PS384, PS512) for JWT signing and verification. Salt length equals
+//! the digest length as required by RFC 7518 section 3.5.
+
+use crate::error::JwtError;
+use rsa::pss::{Signature, SigningKey, VerifyingKey};
+use rsa::sha2::{Sha256, Sha384, Sha512};
+use rsa::signature::{RandomizedSigner, SignatureEncoding, Verifier};
+use rsa::{
+    RsaPrivateKey, RsaPublicKey,
+    pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey},
+};
+
+/// Modulus size used for generated PS256/384/512 keys
+pub(crate) const RSA_PSS_KEY_BITS: usize = 2048;
+
+/// Generate a fresh RSA key pair as (PKCS#8 DER, SPKI DER)
+pub(crate) fn generate_rsa_keypair(bits: usize) -> Result<(Vec<u8>, Vec<u8>), JwtError> {
+    let mut rng = rand::rng();
+    let private_key = RsaPrivateKey::new(&mut rng, bits)
+        .map_err(|e| JwtError::Internal(format!("RSA key generation failed: {e}")))?;
+
+    let private_der = private_key
+        .to_pkcs8_der()
+        .map_err(|e| JwtError::Internal(format!("Failed to encode RSA private key: {e}")))?;
+    let public_der = RsaPublicKey::from(&private_key)
+        .to_public_key_der()
+        .map_err(|e| JwtError::Internal(format!("Failed to encode RSA public key: {e}")))?;
+
+    Ok((
+        private_der.as_bytes().to_vec(),
+        public_der.as_bytes().to_vec(),
+    ))
+}
+
+/// Sign with RSA-PSS SHA-256 (PS256)
+#[inline]
+pub(crate) fn sign_ps256(message: &str, private_key: &[u8]) -> Result<Vec<u8>, JwtError> {
+    let private_key = RsaPrivateKey::from_pkcs8_der(private_key)
+        .map_err(|e| JwtError::InvalidKey(format!("Invalid RSA private key: {e}")))?;
+
+    let signing_key = SigningKey::<Sha256>::new(private_key);
+    let signature = signing_key.sign_with_rng(&mut rand::rng(), message.as_bytes());
+    Ok(signature.to_bytes().as_ref().to_vec())
+}
+
+/// Verify RSA-PSS SHA-256 (PS256) signature
+#[inline]
+pub(crate) fn verify_ps256(
+    message: &str,
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<bool, JwtError> {
+    let public_key = RsaPublicKey::from_public_key_der(public_key)
+        .map_err(|e| JwtError::InvalidKey(format!("Invalid RSA public key: {e}")))?;
+
+    let verifying_key = VerifyingKey::<Sha256>::new(public_key);
+    let signature = Signature::try_from(signature).map_err(|_| JwtError::InvalidSignature)?;
+
+    Ok(verifying_key
+        .verify(message.as_bytes(), &signature)
+        .is_ok())
+}
+
+/// Sign with RSA-PSS SHA-384 (PS384)
+#[inline]
+pub(crate) fn sign_ps384(message: &str, private_key: &[u8]) -> Result<Vec<u8>, JwtError> {
+    let private_key = RsaPrivateKey::from_pkcs8_der(private_key)
+        .map_err(|e| JwtError::InvalidKey(format!("Invalid RSA private key: {e}")))?;
+
+    let signing_key = SigningKey::<Sha384>::new(private_key);
+    let signature = signing_key.sign_with_rng(&mut rand::rng(), message.as_bytes());
+    Ok(signature.to_bytes().as_ref().to_vec())
+}
+
+/// Verify RSA-PSS SHA-384 (PS384) signature
+#[inline]
+pub(crate) fn verify_ps384(
+    message: &str,
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<bool, JwtError> {
+    let public_key = RsaPublicKey::from_public_key_der(public_key)
+        .map_err(|e| JwtError::InvalidKey(format!("Invalid RSA public key: {e}")))?;
+
+    let verifying_key = VerifyingKey::<Sha384>::new(public_key);
+    let signature = Signature::try_from(signature).map_err(|_| JwtError::InvalidSignature)?;
+
+    Ok(verifying_key
+        .verify(message.as_bytes(), &signature)
+        .is_ok())
+}
+
+/// Sign with RSA-PSS SHA-512 (PS512)
+#[inline]
+pub(crate) fn sign_ps512(message: &str, private_key: &[u8]) -> Result<Vec<u8>, JwtError> {
+    let private_key = RsaPrivateKey::from_pkcs8_der(private_key)
+        .map_err(|e| JwtError::InvalidKey(format!("Invalid RSA private key: {e}")))?;
+
+    let signing_key = SigningKey::<Sha512>::new(private_key);
+    let signature = signing_key.sign_with_rng(&mut rand::rng(), message.as_bytes());
+    Ok(signature.to_bytes().as_ref().to_vec())
+}
+
+/// Verify RSA-PSS SHA-512 (PS512) signature
+#[inline]
+pub(crate) fn verify_ps512(
+    message: &str,
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<bool, JwtError> {
+    let public_key = RsaPublicKey::from_public_key_der(public_key)
+        .map_err(|e| JwtError::InvalidKey(format!("Invalid RSA public key: {e}")))?;
+
+    let verifying_key = VerifyingKey::<Sha512>::new(public_key);
+    let signature = Signature::try_from(signature).map_err(|_| JwtError::InvalidSignature)?;
+
+    Ok(verifying_key
+        .verify(message.as_bytes(), &signature)
+        .is_ok())
+}
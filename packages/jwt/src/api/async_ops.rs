@@ -32,7 +32,9 @@ where
     /// Zero-allocation handler transformation with blazing-fast performance
     pub async fn verify<S: AsRef<str>>(self, token: S) -> T {
         let token = token.as_ref().to_string();
-        let result = super::algorithms::verify_jwt(token, self.secret, self.public_key).await;
+        let algorithm = self.algorithm.as_deref().unwrap_or("HS256");
+        let result =
+            super::algorithms::verify_jwt(algorithm, token, self.secret, self.public_key).await;
         (self.handler)(result)
     }
 }
@@ -114,6 +116,7 @@ where
     /// Blazing-fast async coordination with sophisticated error handling
     pub fn verify<S: AsRef<str>>(self, token: S) -> AsyncJwtResultWithError<serde_json::Value, E> {
         let token = token.as_ref().to_string();
+        let algorithm = self.algorithm.unwrap_or_else(|| "HS256".to_string());
         let secret = self.secret;
         let public_key = self.public_key;
         let error_handler = self.error_handler;
@@ -121,7 +124,8 @@ where
         let (tx, rx) = oneshot::channel();
 
         tokio::spawn(async move {
-            let result = super::algorithms::verify_jwt(token, secret, public_key).await;
+            let result =
+                super::algorithms::verify_jwt(&algorithm, token, secret, public_key).await;
             let _ = tx.send(result);
         });
 
@@ -24,6 +24,88 @@ impl JwtMasterBuilder {
         crate::api::algorithm_builders::RsJwtBuilder::new()
     }
 
+    /// `EdDSA` (Ed25519/Ed448) JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn eddsa(self) -> crate::api::algorithm_builders::EdDsaJwtBuilder {
+        crate::api::algorithm_builders::EdDsaJwtBuilder::new()
+    }
+
+    /// PS256 (RSA-PSS SHA-256) JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ps256(self) -> crate::api::algorithm_builders::PsJwtBuilder {
+        crate::api::algorithm_builders::PsJwtBuilder::ps256()
+    }
+
+    /// PS384 (RSA-PSS SHA-384) JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ps384(self) -> crate::api::algorithm_builders::PsJwtBuilder {
+        crate::api::algorithm_builders::PsJwtBuilder::ps384()
+    }
+
+    /// PS512 (RSA-PSS SHA-512) JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ps512(self) -> crate::api::algorithm_builders::PsJwtBuilder {
+        crate::api::algorithm_builders::PsJwtBuilder::ps512()
+    }
+
+    /// ML-DSA-44 (NIST level 2) post-quantum JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ml_dsa_44(self) -> crate::api::algorithm_builders::PqJwtBuilder {
+        crate::api::algorithm_builders::PqJwtBuilder::ml_dsa_44()
+    }
+
+    /// ML-DSA-65 (NIST level 3) post-quantum JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ml_dsa_65(self) -> crate::api::algorithm_builders::PqJwtBuilder {
+        crate::api::algorithm_builders::PqJwtBuilder::ml_dsa_65()
+    }
+
+    /// ML-DSA-87 (NIST level 5) post-quantum JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ml_dsa_87(self) -> crate::api::algorithm_builders::PqJwtBuilder {
+        crate::api::algorithm_builders::PqJwtBuilder::ml_dsa_87()
+    }
+
+    /// Composite ML-DSA-65 + ES256 post-quantum JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ml_dsa_65_es256(self) -> crate::api::algorithm_builders::PqJwtBuilder {
+        crate::api::algorithm_builders::PqJwtBuilder::ml_dsa_65_es256()
+    }
+
+    /// Post-quantum JWT operations for any supported `alg`, e.g. `SLH-DSA-SHA2-128s`
+    ///
+    /// # Errors
+    /// Returns `JwtError::UnsupportedAlgorithm` for unknown identifiers
+    pub fn post_quantum(
+        self,
+        algorithm: &str,
+    ) -> crate::JwtResult<crate::api::algorithm_builders::PqJwtBuilder> {
+        crate::api::algorithm_builders::PqJwtBuilder::new(algorithm)
+    }
+
+    /// Verify tokens against a JWK Set, selecting the key by `kid` and `alg`
+    #[must_use]
+    pub fn with_jwks(self, jwks: crate::api::jwk::Jwks) -> crate::api::jwk::JwksVerifier {
+        crate::api::jwk::JwksVerifier::new(jwks)
+    }
+
+    /// Verify tokens against a refreshing JWKS cache, selecting the key by `kid` and `alg`
+    #[must_use]
+    pub fn with_jwks_cache(
+        self,
+        cache: crate::api::jwk::JwksCache,
+    ) -> crate::api::jwk::JwksVerifier {
+        crate::api::jwk::JwksVerifier::from_cache(cache)
+    }
+
+    /// Encrypted JWT (JWE) operations for any key management `alg` in `JWE_ALGORITHMS`
+    ///
+    /// # Errors
+    /// Returns `JwtError::UnsupportedAlgorithm` for unknown algorithms
+    pub fn jwe(self, algorithm: &str) -> crate::JwtResult<crate::api::jwe::JweBuilder> {
+        crate::api::jwe::JweBuilder::new(algorithm)
+    }
+
     /// Create new JWT builder - unified entry point
     #[must_use]
     pub fn builder() -> JwtBuilder {
@@ -257,7 +339,13 @@ impl JwtBuilder {
     pub async fn verify(&self, token: String) -> Result<serde_json::Value, JwtError> {
         use crate::api::algorithms::verify_jwt;
 
-        verify_jwt(token, self.secret.clone(), self.public_key.clone()).await
+        verify_jwt(
+            &self.get_algorithm(),
+            token,
+            self.secret.clone(),
+            self.public_key.clone(),
+        )
+        .await
     }
 
     /// Add `on_result` handler - polymorphic based on subsequent method call (legacy)
@@ -4,8 +4,10 @@
 
 use super::validation::AsyncJwtResultWithError;
 use crate::api::algorithms::{sign_jwt, verify_jwt};
+use crate::api::claims::ClaimsValidator;
 use crate::error::JwtError;
 use serde::Serialize;
+use std::sync::Arc;
 use tokio::sync::oneshot;
 
 /// Master builder for JWT operations - README.md pattern
@@ -42,14 +44,107 @@ impl Jwt {
     pub fn builder() -> JwtBuilder {
         JwtBuilder::new()
     }
+
+    /// HS256 JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn hs256() -> crate::api::algorithm_builders::HsJwtBuilder {
+        crate::api::algorithm_builders::HsJwtBuilder::new()
+    }
+
+    /// RS256 JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn rs256() -> crate::api::algorithm_builders::RsJwtBuilder {
+        crate::api::algorithm_builders::RsJwtBuilder::new()
+    }
+
+    /// `EdDSA` (Ed25519/Ed448) JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn eddsa() -> crate::api::algorithm_builders::EdDsaJwtBuilder {
+        crate::api::algorithm_builders::EdDsaJwtBuilder::new()
+    }
+
+    /// PS256 (RSA-PSS SHA-256) JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ps256() -> crate::api::algorithm_builders::PsJwtBuilder {
+        crate::api::algorithm_builders::PsJwtBuilder::ps256()
+    }
+
+    /// PS384 (RSA-PSS SHA-384) JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ps384() -> crate::api::algorithm_builders::PsJwtBuilder {
+        crate::api::algorithm_builders::PsJwtBuilder::ps384()
+    }
+
+    /// PS512 (RSA-PSS SHA-512) JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ps512() -> crate::api::algorithm_builders::PsJwtBuilder {
+        crate::api::algorithm_builders::PsJwtBuilder::ps512()
+    }
+
+    /// ML-DSA-44 (NIST level 2) post-quantum JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ml_dsa_44() -> crate::api::algorithm_builders::PqJwtBuilder {
+        crate::api::algorithm_builders::PqJwtBuilder::ml_dsa_44()
+    }
+
+    /// ML-DSA-65 (NIST level 3) post-quantum JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ml_dsa_65() -> crate::api::algorithm_builders::PqJwtBuilder {
+        crate::api::algorithm_builders::PqJwtBuilder::ml_dsa_65()
+    }
+
+    /// ML-DSA-87 (NIST level 5) post-quantum JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ml_dsa_87() -> crate::api::algorithm_builders::PqJwtBuilder {
+        crate::api::algorithm_builders::PqJwtBuilder::ml_dsa_87()
+    }
+
+    /// Composite ML-DSA-65 + ES256 post-quantum JWT operations - polymorphic pattern
+    #[must_use]
+    pub fn ml_dsa_65_es256() -> crate::api::algorithm_builders::PqJwtBuilder {
+        crate::api::algorithm_builders::PqJwtBuilder::ml_dsa_65_es256()
+    }
+
+    /// Post-quantum JWT operations for any supported `alg`, e.g. `SLH-DSA-SHA2-128s`
+    ///
+    /// # Errors
+    /// Returns `JwtError::UnsupportedAlgorithm` for unknown identifiers
+    pub fn post_quantum(
+        algorithm: &str,
+    ) -> crate::JwtResult<crate::api::algorithm_builders::PqJwtBuilder> {
+        crate::api::algorithm_builders::PqJwtBuilder::new(algorithm)
+    }
+
+    /// Verify tokens against a JWK Set, selecting the key by `kid` and `alg`
+    #[must_use]
+    pub fn with_jwks(jwks: crate::api::jwk::Jwks) -> crate::api::jwk::JwksVerifier {
+        crate::api::jwk::JwksVerifier::new(jwks)
+    }
+
+    /// Verify tokens against a refreshing JWKS cache, selecting the key by `kid` and `alg`
+    #[must_use]
+    pub fn with_jwks_cache(cache: crate::api::jwk::JwksCache) -> crate::api::jwk::JwksVerifier {
+        crate::api::jwk::JwksVerifier::from_cache(cache)
+    }
+
+    /// Encrypted JWT (JWE) operations for any key management `alg` in `JWE_ALGORITHMS`
+    ///
+    /// # Errors
+    /// Returns `JwtError::UnsupportedAlgorithm` for unknown algorithms
+    pub fn jwe(algorithm: &str) -> crate::JwtResult<crate::api::jwe::JweBuilder> {
+        crate::api::jwe::JweBuilder::new(algorithm)
+    }
 }
 
 /// Unified JWT builder - follows README.md pattern
+#[derive(Clone)]
 pub struct JwtBuilder {
     pub(crate) algorithm: Option<String>,
+    pub(crate) key_id: Option<String>,
     pub(crate) secret: Option<Vec<u8>>,
     pub(crate) private_key: Option<Vec<u8>>,
     pub(crate) public_key: Option<Vec<u8>>,
+    pub(crate) validator: Option<Arc<ClaimsValidator>>,
 }
 
 /// JWT builder with handler - polymorphic based on usage
@@ -100,9 +195,11 @@ impl JwtBuilder {
     pub fn new() -> Self {
         Self {
             algorithm: None,
+            key_id: None,
             secret: None,
             private_key: None,
             public_key: None,
+            validator: None,
         }
     }
 
@@ -130,6 +227,14 @@ impl JwtBuilder {
         self
     }
 
+    /// Set the `kid` header so verifiers can select the key from a JWKS
+    #[inline]
+    #[must_use]
+    pub fn with_key_id(mut self, kid: &str) -> Self {
+        self.key_id = Some(kid.to_string());
+        self
+    }
+
     /// Set private key for asymmetric algorithms - README.md pattern
     #[inline]
     #[must_use]
@@ -146,6 +251,17 @@ impl JwtBuilder {
         self
     }
 
+    /// Check headers and claims with `validator` on verification
+    ///
+    /// Replaces the default checks: configure audience, issuer, leeway, `typ`
+    /// and understood `crit` parameters on the validator.
+    #[inline]
+    #[must_use]
+    pub fn with_validator(mut self, validator: ClaimsValidator) -> Self {
+        self.validator = Some(Arc::new(validator));
+        self
+    }
+
     /// Add `on_result` handler - polymorphic based on subsequent method call (legacy)
     pub fn on_result_legacy<F>(self, handler: F) -> JwtBuilderWithHandler<F>
     where
@@ -184,7 +300,8 @@ where
     /// Verify JWT with handler - returns unwrapped type T
     pub async fn verify<S: AsRef<str>>(self, token: S) -> T {
         let token = token.as_ref().to_string();
-        let result = verify_jwt(token, self.secret, self.public_key).await;
+        let algorithm = self.algorithm.as_deref().unwrap_or("HS256");
+        let result = verify_jwt(algorithm, token, self.secret, self.public_key).await;
         (self.handler)(result)
     }
 }
@@ -262,6 +379,7 @@ where
     /// Verify JWT with error handler - returns `AsyncJwtResult`
     pub fn verify<S: AsRef<str>>(self, token: S) -> AsyncJwtResultWithError<serde_json::Value, E> {
         let token = token.as_ref().to_string();
+        let algorithm = self.algorithm.unwrap_or_else(|| "HS256".to_string());
         let secret = self.secret;
         let public_key = self.public_key;
         let error_handler = self.error_handler;
@@ -269,7 +387,7 @@ where
         let (tx, rx) = oneshot::channel();
 
         tokio::spawn(async move {
-            let result = verify_jwt(token, secret, public_key).await;
+            let result = verify_jwt(&algorithm, token, secret, public_key).await;
             let _ = tx.send(result);
         });
 
@@ -208,6 +208,11 @@ impl ClaimsBuilder {
         self
     }
 
+    /// The claims set so far as a JSON object
+    pub(crate) fn into_map(self) -> serde_json::Map<String, Value> {
+        self.claims
+    }
+
     /// Build claims as structured Claims object
     #[inline]
     #[must_use]
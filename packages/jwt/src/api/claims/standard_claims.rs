@@ -1,10 +1,14 @@
-use serde::{Deserialize, Serialize};
+use crate::types::Audience;
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 
 /// Immutable JWT claims.
+///
+/// `T` holds every non-registered claim; use your own struct to get typed
+/// custom claims from [`JwtBuilder::verify_as`](crate::builders::JwtBuilder::verify_as).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Claims {
+pub struct Claims<T = HashMap<String, Value>> {
     /// Subject.
     pub sub: String,
     /// Expiry (unix seconds).
@@ -14,8 +18,12 @@ pub struct Claims {
     /// Issuer.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub iss: Option<String>,
-    /// Audience.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Audience - accepted as a string or a list.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "deserialize_audience"
+    )]
     pub aud: Option<Vec<String>>,
     /// Not before (unix seconds).
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -25,5 +33,11 @@ pub struct Claims {
     pub jti: Option<String>,
     /// Custom data.
     #[serde(flatten)]
-    pub extra: HashMap<String, Value>,
+    pub extra: T,
+}
+
+fn deserialize_audience<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Vec<String>>, D::Error> {
+    Ok(Option::<Audience>::deserialize(deserializer)?.map(Audience::into_vec))
 }
@@ -18,21 +18,27 @@ fn get_current_timestamp() -> Result<i64, JwtError> {
     .map_err(|_| JwtError::Internal("Time conversion error".to_string()))
 }
 
-/// Validate time-based claims (exp, nbf, iat)
+/// Minimum clock skew tolerated for a future `iat`, in seconds
+const IAT_SKEW_SECONDS: i64 = 300;
+
+/// Validate time-based claims (exp, nbf, iat) allowing `leeway` seconds of clock skew
 #[inline]
-fn validate_time_claims(obj: &serde_json::Map<String, Value>) -> Result<(), JwtError> {
+fn validate_time_claims(
+    obj: &serde_json::Map<String, Value>,
+    leeway: i64,
+) -> Result<(), JwtError> {
     let now = get_current_timestamp()?;
 
     // Check expiration
     if let Some(exp) = obj.get("exp").and_then(Value::as_i64)
-        && now > exp
+        && now > exp.saturating_add(leeway)
     {
         return Err(JwtError::TokenExpired);
     }
 
     // Check not before
     if let Some(nbf) = obj.get("nbf").and_then(Value::as_i64)
-        && now < nbf
+        && now.saturating_add(leeway) < nbf
     {
         return Err(JwtError::TokenNotYetValid);
     }
@@ -46,9 +52,9 @@ fn validate_time_claims(obj: &serde_json::Map<String, Value>) -> Result<(), JwtE
         return Err(JwtError::InvalidClaims("exp must be after nbf".to_string()));
     }
 
-    // Validate issued at time (allow 5 minutes clock skew)
+    // Validate issued at time (allow at least 5 minutes clock skew)
     if let Some(iat) = obj.get("iat").and_then(Value::as_i64)
-        && iat > now + 300
+        && iat > now.saturating_add(leeway.max(IAT_SKEW_SECONDS))
     {
         return Err(JwtError::InvalidClaims("iat is in the future".to_string()));
     }
@@ -120,10 +126,14 @@ fn validate_audience_claim(obj: &serde_json::Map<String, Value>) -> Result<(), J
 
 /// Validate standard JWT claims with blazing-fast performance
 /// Zero-allocation validation with comprehensive security checks
+/// Allows `leeway` seconds of clock skew for `exp`, `nbf` and `iat`
 #[inline]
-pub(crate) fn validate_standard_claims(claims: &serde_json::Value) -> Result<(), JwtError> {
+pub(crate) fn validate_standard_claims_with_leeway(
+    claims: &serde_json::Value,
+    leeway: i64,
+) -> Result<(), JwtError> {
     if let Some(obj) = claims.as_object() {
-        validate_time_claims(obj)?;
+        validate_time_claims(obj, leeway)?;
         validate_string_claim(obj, "jti")?;
         validate_string_claim(obj, "iss")?;
         validate_string_claim(obj, "sub")?;
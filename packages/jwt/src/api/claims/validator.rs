@@ -3,7 +3,7 @@
 //! This module provides blazing-fast custom validation with sophisticated
 //! claims validation using a zero-allocation builder pattern.
 
-use super::validation::validate_standard_claims;
+use super::validation::validate_standard_claims_with_leeway;
 use crate::error::JwtError;
 use serde_json::Value;
 
@@ -15,10 +15,13 @@ type CustomValidator = Box<dyn Fn(&Value) -> Result<(), JwtError> + Send + Sync>
 pub struct ClaimsValidator {
     required_claims: Vec<String>,
     custom_validators: Vec<CustomValidator>,
-    audience_validation: Option<String>,
+    audience_validation: Vec<String>,
     issuer_validation: Option<String>,
     subject_validation: Option<String>,
     max_age_seconds: Option<i64>,
+    leeway_seconds: i64,
+    expected_type: Option<String>,
+    understood_critical: Vec<String>,
 }
 
 impl ClaimsValidator {
@@ -30,10 +33,13 @@ impl ClaimsValidator {
         Self {
             required_claims: Vec::new(),
             custom_validators: Vec::new(),
-            audience_validation: None,
+            audience_validation: Vec::new(),
             issuer_validation: None,
             subject_validation: None,
             max_age_seconds: None,
+            leeway_seconds: 0,
+            expected_type: None,
+            understood_critical: Vec::new(),
         }
     }
 
@@ -60,13 +66,70 @@ impl ClaimsValidator {
 
     /// Validate specific audience
     /// Blazing-fast audience validation
+    ///
+    /// May be called repeatedly; the token must name at least one accepted audience.
     #[inline]
     #[must_use]
     pub fn with_audience(mut self, audience: &str) -> Self {
-        self.audience_validation = Some(audience.to_string());
+        self.audience_validation.push(audience.to_string());
+        self
+    }
+
+    /// Accept tokens intended for any of `audiences`
+    #[inline]
+    #[must_use]
+    pub fn with_audiences(mut self, audiences: &[&str]) -> Self {
+        self.audience_validation
+            .extend(audiences.iter().map(|&aud| aud.to_string()));
+        self
+    }
+
+    /// Allow `seconds` of clock skew when checking `exp`, `nbf` and `iat`
+    #[inline]
+    #[must_use]
+    pub fn with_leeway(mut self, seconds: u64) -> Self {
+        self.leeway_seconds = i64::try_from(seconds).unwrap_or(i64::MAX);
         self
     }
 
+    /// Require the `typ` header to equal `typ`, e.g. `JWT` or `at+jwt`
+    ///
+    /// Compared case-insensitively, with an optional `application/` prefix (RFC 7515).
+    #[inline]
+    #[must_use]
+    pub fn with_type(mut self, typ: &str) -> Self {
+        self.expected_type = Some(typ.to_string());
+        self
+    }
+
+    /// Declare `crit` header parameters this application understands and enforces
+    ///
+    /// Tokens listing any other parameter in `crit` are rejected.
+    #[inline]
+    #[must_use]
+    pub fn with_critical(mut self, parameters: &[&str]) -> Self {
+        self.understood_critical
+            .extend(parameters.iter().map(|&param| param.to_string()));
+        self
+    }
+
+    /// Validate the JOSE header: `typ` if configured, and `crit` always
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidToken` if `typ` does not match or `crit`
+    /// names a parameter that is not understood
+    pub fn validate_header(&self, header: &Value) -> Result<(), JwtError> {
+        if let Some(expected) = &self.expected_type {
+            let typ = header.get("typ").and_then(Value::as_str).unwrap_or_default();
+            if !media_type_eq(typ, expected) {
+                return Err(JwtError::InvalidToken(format!(
+                    "Unexpected token type '{typ}', expected '{expected}'"
+                )));
+            }
+        }
+        check_critical(header, &self.understood_critical)
+    }
+
     /// Validate specific issuer
     /// Blazing-fast issuer validation
     #[inline]
@@ -101,7 +164,7 @@ impl ClaimsValidator {
     /// Returns `JwtError` if claims validation fails due to expired tokens, invalid issuer, or custom rules
     pub fn validate(&self, claims: &Value) -> Result<(), JwtError> {
         // First run standard claims validation
-        validate_standard_claims(claims)?;
+        validate_standard_claims_with_leeway(claims, self.leeway_seconds)?;
 
         let obj = claims
             .as_object()
@@ -117,17 +180,16 @@ impl ClaimsValidator {
         }
 
         // Validate audience if specified
-        if let Some(expected_aud) = &self.audience_validation {
+        if !self.audience_validation.is_empty() {
+            let accepted = |aud: &str| self.audience_validation.iter().any(|a| a == aud);
             match obj.get("aud") {
                 Some(Value::String(aud)) => {
-                    if aud != expected_aud {
+                    if !accepted(aud) {
                         return Err(JwtError::InvalidClaims("Invalid audience".to_string()));
                     }
                 }
                 Some(Value::Array(aud_array)) => {
-                    let found = aud_array
-                        .iter()
-                        .any(|aud| aud.as_str().is_some_and(|s| s == expected_aud));
+                    let found = aud_array.iter().filter_map(Value::as_str).any(accepted);
                     if !found {
                         return Err(JwtError::InvalidClaims(
                             "Audience not found in array".to_string(),
@@ -173,7 +235,7 @@ impl ClaimsValidator {
                 )
                 .map_err(|_| JwtError::Internal("Time conversion error".to_string()))?;
 
-                if now - iat > max_age {
+                if now - iat > max_age.saturating_add(self.leeway_seconds) {
                     return Err(JwtError::InvalidClaims("Token is too old".to_string()));
                 }
             } else {
@@ -198,3 +260,47 @@ impl Default for ClaimsValidator {
         Self::new()
     }
 }
+
+/// Header parameters registered by RFC 7515, 7516 and 7518 that may never appear in `crit`
+const REGISTERED_HEADER_PARAMETERS: [&str; 20] = [
+    "alg", "jku", "jwk", "kid", "x5u", "x5c", "x5t", "x5t#S256", "typ", "cty", "crit", "enc",
+    "zip", "epk", "apu", "apv", "iv", "tag", "p2s", "p2c",
+];
+
+/// Enforce RFC 7515 section 4.1.11: every `crit` entry must be understood and present
+pub(crate) fn check_critical(header: &Value, understood: &[String]) -> Result<(), JwtError> {
+    let Some(crit) = header.get("crit") else {
+        return Ok(());
+    };
+    let invalid = |msg: String| Err(JwtError::InvalidToken(msg));
+
+    let Some(parameters) = crit.as_array().filter(|params| !params.is_empty()) else {
+        return invalid("crit must be a non-empty array".to_string());
+    };
+    for parameter in parameters {
+        let Some(name) = parameter.as_str() else {
+            return invalid("crit entries must be strings".to_string());
+        };
+        if REGISTERED_HEADER_PARAMETERS.contains(&name) {
+            return invalid(format!("crit must not list registered parameter '{name}'"));
+        }
+        if !understood.iter().any(|param| param == name) {
+            return invalid(format!("Unsupported critical header parameter '{name}'"));
+        }
+        if header.get(name).is_none() {
+            return invalid(format!("Critical header parameter '{name}' is missing"));
+        }
+    }
+    Ok(())
+}
+
+/// Compare media types per RFC 7515 section 4.1.9
+fn media_type_eq(actual: &str, expected: &str) -> bool {
+    fn strip(media_type: &str) -> &str {
+        media_type
+            .get(..12)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("application/"))
+            .map_or(media_type, |_| &media_type[12..])
+    }
+    strip(actual).eq_ignore_ascii_case(strip(expected))
+}
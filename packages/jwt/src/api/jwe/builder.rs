@@ -0,0 +1,294 @@
+//! JWE builder - polymorphic pattern for encrypted JWTs
+//!
+//! Encrypts claims directly, or signs them first with a [`JwtBuilder`] and
+//! encrypts the resulting JWS (`cty: JWT`). Decryption of a nested token always
+//! verifies the inner signature before any claim is returned.
+
+use super::compact::{decrypt_compact, encrypt_compact};
+use super::header::JweHeader;
+use super::key_management::CONTENT_ENCRYPTION;
+use super::JWE_ALGORITHMS;
+use crate::api::algorithms::{sign_jwt_with_kid, validate_standard_claims, verify_jwt_for};
+use crate::api::builders::JwtBuilder;
+use crate::error::{JwtError, JwtResult};
+use serde::Serialize;
+
+/// JWE builder - initial state
+#[derive(Debug, Clone)]
+pub struct JweBuilder {
+    algorithm: &'static str,
+}
+
+/// JWE builder with recipient key configured
+pub struct JweWithRecipientKey {
+    algorithm: &'static str,
+    key: Vec<u8>,
+    key_id: Option<String>,
+    signer: Option<JwtBuilder>,
+}
+
+/// JWE builder with recipient key and claims configured
+pub struct JweWithRecipientKeyAndClaims<T> {
+    recipient: JweWithRecipientKey,
+    claims: T,
+}
+
+/// JWE builder with recipient key, claims and result handler
+pub struct JweWithRecipientKeyAndClaimsAndHandler<T, F> {
+    recipient: JweWithRecipientKey,
+    claims: T,
+    handler: F,
+}
+
+/// JWE builder with decryption key configured
+pub struct JweWithDecryptionKey {
+    algorithm: &'static str,
+    key: Vec<u8>,
+    verifier: Option<JwtBuilder>,
+}
+
+/// JWE builder with decryption key and result handler
+pub struct JweWithDecryptionKeyAndHandler<F> {
+    decryptor: JweWithDecryptionKey,
+    handler: F,
+}
+
+impl JweBuilder {
+    /// Create a JWE builder for any key management algorithm in [`JWE_ALGORITHMS`]
+    ///
+    /// # Errors
+    /// Returns `JwtError::UnsupportedAlgorithm` for unknown algorithms
+    pub fn new(algorithm: &str) -> JwtResult<Self> {
+        JWE_ALGORITHMS
+            .iter()
+            .find(|&&alg| alg == algorithm)
+            .map(|&algorithm| Self { algorithm })
+            .ok_or_else(|| JwtError::UnsupportedAlgorithm(algorithm.to_string()))
+    }
+
+    /// Direct encryption with a shared 256-bit key (`dir`)
+    #[must_use]
+    pub fn dir() -> Self {
+        Self { algorithm: "dir" }
+    }
+
+    /// AES-256 key wrap with a shared 256-bit key (`A256KW`)
+    #[must_use]
+    pub fn a256kw() -> Self {
+        Self { algorithm: "A256KW" }
+    }
+
+    /// RSAES-OAEP with SHA-256 to an RSA public key (`RSA-OAEP-256`)
+    #[must_use]
+    pub fn rsa_oaep_256() -> Self {
+        Self {
+            algorithm: "RSA-OAEP-256",
+        }
+    }
+
+    /// Ephemeral-static P-256 ECDH with AES-256 key wrap (`ECDH-ES+A256KW`)
+    #[must_use]
+    pub fn ecdh_es_a256kw() -> Self {
+        Self {
+            algorithm: "ECDH-ES+A256KW",
+        }
+    }
+
+    /// ML-KEM-768 key encapsulation (`MLKEM768`)
+    #[must_use]
+    pub fn ml_kem_768() -> Self {
+        Self {
+            algorithm: "MLKEM768",
+        }
+    }
+
+    /// ML-KEM-1024 key encapsulation (`MLKEM1024`)
+    #[must_use]
+    pub fn ml_kem_1024() -> Self {
+        Self {
+            algorithm: "MLKEM1024",
+        }
+    }
+
+    /// The `alg` header value this builder produces and accepts
+    #[must_use]
+    pub fn algorithm(&self) -> &'static str {
+        self.algorithm
+    }
+
+    /// Set the recipient key used for encryption
+    ///
+    /// A 32-byte secret for `dir` and `A256KW`, an SPKI public key (DER or
+    /// PEM) for `RSA-OAEP-256` and `ECDH-ES+A256KW`, or a raw ML-KEM public key.
+    #[must_use]
+    pub fn with_recipient_key(self, key: &[u8]) -> JweWithRecipientKey {
+        JweWithRecipientKey {
+            algorithm: self.algorithm,
+            key: key.to_vec(),
+            key_id: None,
+            signer: None,
+        }
+    }
+
+    /// Set the key used for decryption
+    ///
+    /// A 32-byte secret for `dir` and `A256KW`, a PKCS#8 private key (DER or
+    /// PEM) for `RSA-OAEP-256` and `ECDH-ES+A256KW`, or a raw ML-KEM secret key.
+    #[must_use]
+    pub fn with_decryption_key(self, key: &[u8]) -> JweWithDecryptionKey {
+        JweWithDecryptionKey {
+            algorithm: self.algorithm,
+            key: key.to_vec(),
+            verifier: None,
+        }
+    }
+}
+
+impl JweWithRecipientKey {
+    /// Set the `kid` header naming the recipient key
+    #[must_use]
+    pub fn with_key_id(mut self, kid: &str) -> Self {
+        self.key_id = Some(kid.to_string());
+        self
+    }
+
+    /// Sign the claims with `signer` and encrypt the resulting JWS (nested JWT)
+    #[must_use]
+    pub fn sign_with(mut self, signer: JwtBuilder) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Set claims for the JWE payload
+    #[must_use]
+    pub fn with_claims<T: Serialize>(self, claims: T) -> JweWithRecipientKeyAndClaims<T> {
+        JweWithRecipientKeyAndClaims {
+            recipient: self,
+            claims,
+        }
+    }
+}
+
+impl<T: Serialize> JweWithRecipientKeyAndClaims<T> {
+    /// Set result handler for JWE encryption
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> JweWithRecipientKeyAndClaimsAndHandler<T, F>
+    where
+        F: FnOnce(JwtResult<Vec<u8>>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        JweWithRecipientKeyAndClaimsAndHandler {
+            recipient: self.recipient,
+            claims: self.claims,
+            handler,
+        }
+    }
+}
+
+impl<T, F, R> JweWithRecipientKeyAndClaimsAndHandler<T, F>
+where
+    T: Serialize + Send + 'static,
+    F: FnOnce(JwtResult<Vec<u8>>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Encrypt the claims as a compact JWE
+    pub async fn encrypt(self) -> R {
+        let result = encrypt(self.recipient, self.claims).await;
+        (self.handler)(result.map(String::into_bytes))
+    }
+}
+
+impl JweWithDecryptionKey {
+    /// Require a nested JWS and verify it with `verifier` after decryption
+    ///
+    /// The verifier's algorithm is pinned; a nested token signed with any other
+    /// algorithm is rejected.
+    #[must_use]
+    pub fn verify_with(mut self, verifier: JwtBuilder) -> Self {
+        self.verifier = Some(verifier);
+        self
+    }
+
+    /// Set result handler for JWE decryption
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> JweWithDecryptionKeyAndHandler<F>
+    where
+        F: FnOnce(JwtResult<serde_json::Value>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        JweWithDecryptionKeyAndHandler {
+            decryptor: self,
+            handler,
+        }
+    }
+}
+
+impl<F, R> JweWithDecryptionKeyAndHandler<F>
+where
+    F: FnOnce(JwtResult<serde_json::Value>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Decrypt a compact JWE and return its claims
+    pub async fn decrypt<S: AsRef<str>>(self, token: S) -> R {
+        let result = decrypt(self.decryptor, token.as_ref()).await;
+        (self.handler)(result)
+    }
+}
+
+async fn encrypt<T: Serialize + Send + 'static>(
+    recipient: JweWithRecipientKey,
+    claims: T,
+) -> JwtResult<String> {
+    let mut header = JweHeader::new(recipient.algorithm, CONTENT_ENCRYPTION);
+    header.kid = recipient.key_id;
+
+    let payload = match recipient.signer {
+        Some(signer) => {
+            header.cty = Some("JWT".to_string());
+            let algorithm = signer.get_algorithm();
+            sign_jwt_with_kid(
+                algorithm,
+                signer.key_id,
+                claims,
+                signer.secret,
+                signer.private_key,
+            )
+            .await?
+            .into_bytes()
+        }
+        None => serde_json::to_vec(&claims).map_err(|e| JwtError::Serialization(e.to_string()))?,
+    };
+
+    encrypt_compact(header, &recipient.key, &payload).await
+}
+
+async fn decrypt(decryptor: JweWithDecryptionKey, token: &str) -> JwtResult<serde_json::Value> {
+    let (header, payload) = decrypt_compact(decryptor.algorithm, token, &decryptor.key).await?;
+
+    match (header.is_nested(), decryptor.verifier) {
+        (true, Some(verifier)) => {
+            let inner = std::str::from_utf8(&payload)
+                .map_err(|_| JwtError::InvalidToken("Nested JWT is not UTF-8".to_string()))?;
+            let algorithm = verifier.get_algorithm();
+            verify_jwt_for(
+                &[algorithm.as_str()],
+                inner.to_string(),
+                verifier.secret,
+                verifier.public_key,
+            )
+            .await
+        }
+        (true, None) => Err(JwtError::InvalidToken(
+            "Nested JWT requires a verifier; use verify_with".to_string(),
+        )),
+        (false, Some(_)) => Err(JwtError::InvalidToken(
+            "Expected a nested signed JWT".to_string(),
+        )),
+        (false, None) => {
+            let claims: serde_json::Value = serde_json::from_slice(&payload)
+                .map_err(|_| JwtError::InvalidToken("Invalid JWE payload JSON".to_string()))?;
+            validate_standard_claims(&claims)?;
+            Ok(claims)
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! JWE compact serialization (RFC 7516 section 7.1)
+//!
+//! `header.encrypted_key.iv.ciphertext.tag`, each segment base64url without
+//! padding. The encoded protected header is the AAD for `A256GCM`.
+
+use super::header::JweHeader;
+use super::key_management::{CONTENT_ENCRYPTION, decrypt_cek, encrypt_cek};
+use crate::error::{JwtError, JwtResult};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use cryypt_cipher::Cipher;
+use zeroize::Zeroizing;
+
+/// Encrypt `payload` to the recipient `key` as a compact JWE
+pub(crate) async fn encrypt_compact(
+    mut header: JweHeader,
+    key: &[u8],
+    payload: &[u8],
+) -> JwtResult<String> {
+    let (cek, encrypted_key) = encrypt_cek(&mut header, key).await?;
+    let protected = header.encode()?;
+
+    let sealed = Cipher::aes()
+        .with_key(cek.to_vec())
+        .with_aad(protected.as_bytes())
+        .encrypt_detached(payload)
+        .await
+        .map_err(|e| JwtError::Internal(format!("JWE content encryption failed: {e}")))?;
+
+    Ok(format!(
+        "{protected}.{}.{}.{}.{}",
+        URL_SAFE_NO_PAD.encode(encrypted_key),
+        URL_SAFE_NO_PAD.encode(sealed.nonce),
+        URL_SAFE_NO_PAD.encode(&sealed.ciphertext),
+        URL_SAFE_NO_PAD.encode(sealed.tag),
+    ))
+}
+
+/// Decrypt a compact JWE whose `alg` must equal `expected_alg`
+pub(crate) async fn decrypt_compact(
+    expected_alg: &str,
+    token: &str,
+    key: &[u8],
+) -> JwtResult<(JweHeader, Zeroizing<Vec<u8>>)> {
+    let segments: Vec<&str> = token.split('.').collect();
+    let [protected, encrypted_key, iv, ciphertext, tag] = segments.as_slice() else {
+        return Err(JwtError::InvalidToken(
+            "JWE must have five segments".to_string(),
+        ));
+    };
+
+    let header = JweHeader::decode(protected)?;
+    if header.alg != expected_alg {
+        return Err(JwtError::UnsupportedAlgorithm(header.alg));
+    }
+    if header.enc != CONTENT_ENCRYPTION {
+        return Err(JwtError::UnsupportedAlgorithm(header.enc));
+    }
+    if let Some(crit) = &header.crit {
+        return Err(JwtError::InvalidToken(format!(
+            "Unsupported critical header parameters: {}",
+            crit.join(", ")
+        )));
+    }
+
+    let encrypted_key = decode_segment(encrypted_key, "encrypted key")?;
+    let iv = decode_segment(iv, "IV")?;
+    let ciphertext = decode_segment(ciphertext, "ciphertext")?;
+    let tag = decode_segment(tag, "tag")?;
+
+    let cek = decrypt_cek(&header, &encrypted_key, key).await?;
+    let plaintext = Cipher::aes()
+        .with_key(cek.to_vec())
+        .with_aad(protected.as_bytes())
+        .decrypt_detached(&iv, &ciphertext, &tag)
+        .await
+        .map_err(|_| JwtError::InvalidToken("JWE decryption failed".to_string()))?;
+
+    Ok((header, Zeroizing::new(plaintext)))
+}
+
+fn decode_segment(segment: &str, name: &str) -> JwtResult<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|_| JwtError::InvalidToken(format!("Invalid JWE {name} encoding")))
+}
@@ -0,0 +1,76 @@
+//! JWE protected header (RFC 7516 section 4)
+
+use crate::api::jwk::Jwk;
+use crate::error::{JwtError, JwtResult};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+
+/// JWE protected header
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JweHeader {
+    /// Key management algorithm
+    pub alg: String,
+    /// Content encryption algorithm
+    pub enc: String,
+    /// Recipient key identifier
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub kid: Option<String>,
+    /// Media type of the complete JWE
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub typ: Option<String>,
+    /// Media type of the payload - `JWT` for a nested signed token
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub cty: Option<String>,
+    /// Ephemeral public key for ECDH-ES
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub epk: Option<Jwk>,
+    /// Agreement `PartyUInfo`, base64url
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub apu: Option<String>,
+    /// Agreement `PartyVInfo`, base64url
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub apv: Option<String>,
+    /// Extensions the recipient must understand
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub crit: Option<Vec<String>>,
+}
+
+impl JweHeader {
+    /// Header for `alg` with `A256GCM` content encryption
+    pub(crate) fn new(alg: &str, enc: &str) -> Self {
+        Self {
+            alg: alg.to_string(),
+            enc: enc.to_string(),
+            kid: None,
+            typ: None,
+            cty: None,
+            epk: None,
+            apu: None,
+            apv: None,
+            crit: None,
+        }
+    }
+
+    /// Whether the payload is a nested JWT
+    #[must_use]
+    pub fn is_nested(&self) -> bool {
+        self.cty
+            .as_deref()
+            .is_some_and(|cty| cty.eq_ignore_ascii_case("JWT"))
+    }
+
+    /// Serialize as the base64url protected header segment
+    pub(crate) fn encode(&self) -> JwtResult<String> {
+        let json = serde_json::to_vec(self).map_err(|e| JwtError::Serialization(e.to_string()))?;
+        Ok(URL_SAFE_NO_PAD.encode(json))
+    }
+
+    /// Parse a base64url protected header segment
+    pub(crate) fn decode(segment: &str) -> JwtResult<Self> {
+        let json = URL_SAFE_NO_PAD
+            .decode(segment)
+            .map_err(|_| JwtError::InvalidToken("Invalid JWE header encoding".to_string()))?;
+        serde_json::from_slice(&json)
+            .map_err(|_| JwtError::InvalidToken("Invalid JWE header JSON".to_string()))
+    }
+}
@@ -0,0 +1,295 @@
+//! JWE key management - delivering the content encryption key to the recipient
+//!
+//! Every algorithm produces a fresh 256-bit content encryption key (CEK) for
+//! `A256GCM` and the JWE Encrypted Key that lets the recipient recover it.
+
+use super::header::JweHeader;
+use crate::api::jwk::Jwk;
+use crate::api::keys::key_to_der;
+use crate::error::{JwtError, JwtResult};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use cryypt_key::KeyWrap;
+use cryypt_pqcrypto::api::MlKemBuilder;
+use cryypt_pqcrypto::{
+    CiphertextBuilder, DecapsulateBuilder, EncapsulateBuilder, KemAlgorithm, KemKeyPairBuilder,
+};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+/// Content encryption key size for `A256GCM`
+pub(crate) const CEK_SIZE: usize = 32;
+
+/// Content encryption algorithm - the only `enc` value produced or accepted
+pub(crate) const CONTENT_ENCRYPTION: &str = "A256GCM";
+
+/// Map an ML-KEM `alg` to its parameter set
+pub(crate) fn kem_algorithm(alg: &str) -> Option<KemAlgorithm> {
+    match alg {
+        "MLKEM512" => Some(KemAlgorithm::MlKem512),
+        "MLKEM768" => Some(KemAlgorithm::MlKem768),
+        "MLKEM1024" => Some(KemAlgorithm::MlKem1024),
+        _ => None,
+    }
+}
+
+/// Produce a CEK and its JWE Encrypted Key for the recipient `key`
+///
+/// Algorithms that agree on a key record their parameters (`epk`) in `header`.
+pub(crate) async fn encrypt_cek(
+    header: &mut JweHeader,
+    key: &[u8],
+) -> JwtResult<(Zeroizing<Vec<u8>>, Vec<u8>)> {
+    match header.alg.as_str() {
+        "dir" => Ok((symmetric_key(key, "dir")?, Vec::new())),
+        "A256KW" => {
+            let cek = random_cek();
+            let encrypted_key = a256kw_wrap(&symmetric_key(key, "A256KW")?, &cek)?;
+            Ok((cek, encrypted_key))
+        }
+        "RSA-OAEP-256" => {
+            let cek = random_cek();
+            let encrypted_key = rsa_oaep_encrypt(key, &cek)?;
+            Ok((cek, encrypted_key))
+        }
+        "ECDH-ES+A256KW" => {
+            let (z, epk) = ecdh_es_agree(key)?;
+            let kek = concat_kdf(&z, &header.alg, header)?;
+            header.epk = Some(epk);
+
+            let cek = random_cek();
+            let encrypted_key = a256kw_wrap(&kek, &cek)?;
+            Ok((cek, encrypted_key))
+        }
+        alg => match kem_algorithm(alg) {
+            Some(algorithm) => {
+                let encapsulation = MlKemBuilder::new(algorithm)
+                    .with_public_key(key.to_vec())
+                    .map_err(map_kem_error)?
+                    .encapsulate()
+                    .await
+                    .map_err(map_kem_error)?;
+                let cek = concat_kdf(
+                    encapsulation.shared_secret().as_bytes(),
+                    &header.enc,
+                    header,
+                )?;
+                Ok((cek, encapsulation.ciphertext_vec()))
+            }
+            None => Err(JwtError::UnsupportedAlgorithm(alg.to_string())),
+        },
+    }
+}
+
+/// Recover the CEK from a JWE Encrypted Key with the recipient's private `key`
+pub(crate) async fn decrypt_cek(
+    header: &JweHeader,
+    encrypted_key: &[u8],
+    key: &[u8],
+) -> JwtResult<Zeroizing<Vec<u8>>> {
+    let cek = match header.alg.as_str() {
+        "dir" => {
+            if !encrypted_key.is_empty() {
+                return Err(JwtError::invalid_token(
+                    "JWE Encrypted Key must be empty for dir",
+                ));
+            }
+            symmetric_key(key, "dir")?
+        }
+        "A256KW" => a256kw_unwrap(&symmetric_key(key, "A256KW")?, encrypted_key)?,
+        "RSA-OAEP-256" => rsa_oaep_decrypt(key, encrypted_key)?,
+        "ECDH-ES+A256KW" => {
+            let epk = header
+                .epk
+                .as_ref()
+                .ok_or_else(|| JwtError::MissingClaim("epk".to_string()))?;
+            let z = ecdh_es_recover(key, epk)?;
+            let kek = concat_kdf(&z, &header.alg, header)?;
+            a256kw_unwrap(&kek, encrypted_key)?
+        }
+        alg => match kem_algorithm(alg) {
+            Some(algorithm) => {
+                if encrypted_key.len() != algorithm.ciphertext_size() {
+                    return Err(JwtError::invalid_token("Invalid ML-KEM ciphertext length"));
+                }
+                let decapsulation = MlKemBuilder::new(algorithm)
+                    .with_secret_key(key.to_vec())
+                    .map_err(map_kem_error)?
+                    .with_ciphertext(encrypted_key)
+                    .decapsulate()
+                    .await
+                    .map_err(map_kem_error)?;
+                concat_kdf(
+                    decapsulation.shared_secret().as_bytes(),
+                    &header.enc,
+                    header,
+                )?
+            }
+            None => return Err(JwtError::UnsupportedAlgorithm(alg.to_string())),
+        },
+    };
+
+    if cek.len() == CEK_SIZE {
+        Ok(cek)
+    } else {
+        Err(JwtError::invalid_token("Content encryption key has the wrong size"))
+    }
+}
+
+fn random_cek() -> Zeroizing<Vec<u8>> {
+    let mut cek = Zeroizing::new(vec![0u8; CEK_SIZE]);
+    rand::rng().fill_bytes(&mut cek);
+    cek
+}
+
+/// Check a shared symmetric key for `dir` and `A256KW`
+fn symmetric_key(key: &[u8], alg: &str) -> JwtResult<Zeroizing<Vec<u8>>> {
+    if key.len() == CEK_SIZE {
+        Ok(Zeroizing::new(key.to_vec()))
+    } else {
+        Err(JwtError::invalid_key(&format!(
+            "{alg} requires a {CEK_SIZE}-byte key, got {} bytes",
+            key.len()
+        )))
+    }
+}
+
+/// AES Key Wrap (RFC 3394) of `cek` under a 256-bit `kek`
+fn a256kw_wrap(kek: &[u8], cek: &[u8]) -> JwtResult<Vec<u8>> {
+    a256kw(kek)?
+        .wrap(cek)
+        .map_err(|e| JwtError::Internal(format!("AES key wrap failed: {e}")))
+}
+
+/// Unwrap an RFC 3394 JWE Encrypted Key under a 256-bit `kek`
+fn a256kw_unwrap(kek: &[u8], encrypted_key: &[u8]) -> JwtResult<Zeroizing<Vec<u8>>> {
+    a256kw(kek)?
+        .unwrap(encrypted_key)
+        .map_err(|_| JwtError::invalid_token("AES key unwrap failed"))
+}
+
+/// `cryypt_key` key wrapper restricted to the 256-bit KEKs of `A256KW`
+fn a256kw(kek: &[u8]) -> JwtResult<KeyWrap> {
+    if kek.len() != CEK_SIZE {
+        return Err(JwtError::invalid_key(
+            "A256KW key-encryption key must be 32 bytes",
+        ));
+    }
+    KeyWrap::new(kek).map_err(|e| JwtError::InvalidKey(e.to_string()))
+}
+
+/// RSAES-OAEP with SHA-256 and MGF1-SHA-256 to an SPKI public key
+fn rsa_oaep_encrypt(public_key: &[u8], cek: &[u8]) -> JwtResult<Vec<u8>> {
+    use rsa::oaep::EncryptingKey;
+    use rsa::pkcs8::DecodePublicKey;
+    use rsa::sha2::Sha256;
+    use rsa::traits::RandomizedEncryptor;
+
+    let public_key = rsa::RsaPublicKey::from_public_key_der(&key_to_der(public_key)?)
+        .map_err(|e| JwtError::invalid_key(&format!("Invalid RSA public key: {e}")))?;
+    EncryptingKey::<Sha256>::new(public_key)
+        .encrypt_with_rng(&mut rand::rng(), cek)
+        .map_err(|e| JwtError::Internal(format!("RSA-OAEP encryption failed: {e}")))
+}
+
+/// RSAES-OAEP decryption with a PKCS#8 private key
+fn rsa_oaep_decrypt(private_key: &[u8], encrypted_key: &[u8]) -> JwtResult<Zeroizing<Vec<u8>>> {
+    use rsa::oaep::DecryptingKey;
+    use rsa::pkcs8::DecodePrivateKey;
+    use rsa::sha2::Sha256;
+    use rsa::traits::Decryptor;
+
+    let der = Zeroizing::new(key_to_der(private_key)?);
+    let private_key = rsa::RsaPrivateKey::from_pkcs8_der(&der)
+        .map_err(|e| JwtError::invalid_key(&format!("Invalid RSA private key: {e}")))?;
+    DecryptingKey::<Sha256>::new(private_key)
+        .decrypt(encrypted_key)
+        .map(Zeroizing::new)
+        .map_err(|_| JwtError::invalid_token("RSA-OAEP decryption failed"))
+}
+
+/// Ephemeral-static P-256 agreement; returns `Z` and the ephemeral public JWK
+fn ecdh_es_agree(recipient: &[u8]) -> JwtResult<(Zeroizing<Vec<u8>>, Jwk)> {
+    use p256::ecdh::EphemeralSecret;
+    use p256::elliptic_curve::rand_core::OsRng;
+    use p256::pkcs8::{DecodePublicKey, EncodePublicKey};
+
+    let recipient = p256::PublicKey::from_public_key_der(&key_to_der(recipient)?)
+        .map_err(|e| JwtError::invalid_key(&format!("Invalid P-256 public key: {e}")))?;
+
+    let ephemeral = EphemeralSecret::random(&mut OsRng);
+    let shared = ephemeral.diffie_hellman(&recipient);
+    let epk_der = ephemeral
+        .public_key()
+        .to_public_key_der()
+        .map_err(|e| JwtError::Internal(format!("Failed to encode ephemeral key: {e}")))?;
+
+    Ok((
+        Zeroizing::new(shared.raw_secret_bytes().to_vec()),
+        Jwk::from_ec_public_key(epk_der.as_bytes())?,
+    ))
+}
+
+/// Recompute `Z` from the recipient's PKCS#8 P-256 key and the sender's `epk`
+fn ecdh_es_recover(private_key: &[u8], epk: &Jwk) -> JwtResult<Zeroizing<Vec<u8>>> {
+    use p256::pkcs8::{DecodePrivateKey, DecodePublicKey};
+
+    if epk.kty != "EC" || epk.crv.as_deref() != Some("P-256") {
+        return Err(JwtError::invalid_token("epk must be a P-256 EC key"));
+    }
+    let epk = p256::PublicKey::from_public_key_der(&epk.public_key_bytes()?)
+        .map_err(|_| JwtError::invalid_token("Invalid epk"))?;
+
+    let der = Zeroizing::new(key_to_der(private_key)?);
+    let secret = p256::SecretKey::from_pkcs8_der(&der)
+        .map_err(|e| JwtError::invalid_key(&format!("Invalid P-256 private key: {e}")))?;
+    let shared = p256::ecdh::diffie_hellman(secret.to_nonzero_scalar(), epk.as_affine());
+
+    Ok(Zeroizing::new(shared.raw_secret_bytes().to_vec()))
+}
+
+/// Concat KDF (NIST SP 800-56A, RFC 7518 section 4.6.2) for a 256-bit key
+///
+/// A single SHA-256 round suffices for 256 bits of output.
+fn concat_kdf(
+    z: &[u8],
+    algorithm_id: &str,
+    header: &JweHeader,
+) -> JwtResult<Zeroizing<Vec<u8>>> {
+    let apu = decode_party_info(header.apu.as_deref(), "apu")?;
+    let apv = decode_party_info(header.apv.as_deref(), "apv")?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(1u32.to_be_bytes());
+    hasher.update(z);
+    for field in [algorithm_id.as_bytes(), apu.as_slice(), apv.as_slice()] {
+        let len = u32::try_from(field.len())
+            .map_err(|_| JwtError::invalid_token("Concat KDF input too long"))?;
+        hasher.update(len.to_be_bytes());
+        hasher.update(field);
+    }
+    hasher.update(256u32.to_be_bytes());
+
+    Ok(Zeroizing::new(hasher.finalize().to_vec()))
+}
+
+fn decode_party_info(value: Option<&str>, name: &str) -> JwtResult<Vec<u8>> {
+    value.map_or_else(
+        || Ok(Vec::new()),
+        |value| {
+            URL_SAFE_NO_PAD
+                .decode(value)
+                .map_err(|_| JwtError::invalid_token(&format!("Invalid {name} encoding")))
+        },
+    )
+}
+
+fn map_kem_error(error: cryypt_pqcrypto::PqCryptoError) -> JwtError {
+    match error {
+        cryypt_pqcrypto::PqCryptoError::InvalidKey(msg) => JwtError::InvalidKey(msg),
+        cryypt_pqcrypto::PqCryptoError::InvalidKeySize { expected, actual } => {
+            JwtError::InvalidKey(format!("ML-KEM key must be {expected} bytes, got {actual}"))
+        }
+        other => JwtError::Internal(format!("ML-KEM operation failed: {other}")),
+    }
+}
@@ -0,0 +1,35 @@
+//! JSON Web Encryption (RFC 7516) for encrypted JWTs
+//!
+//! Tokens use the compact serialization with `A256GCM` content encryption
+//! from `cryypt_cipher`. The per-token content encryption key reaches the
+//! recipient through one of the key management algorithms in
+//! [`JWE_ALGORITHMS`]:
+//!
+//! - `dir` and `A256KW` with a shared 256-bit key
+//! - `RSA-OAEP-256` to an RSA public key
+//! - `ECDH-ES+A256KW` to a P-256 public key
+//! - `MLKEM512`, `MLKEM768` and `MLKEM1024`: the ML-KEM ciphertext is the JWE
+//!   Encrypted Key and the CEK is derived from the shared secret with the
+//!   Concat KDF, exactly as for ECDH-ES direct key agreement
+
+mod builder;
+mod compact;
+mod header;
+mod key_management;
+
+pub use builder::{
+    JweBuilder, JweWithDecryptionKey, JweWithDecryptionKeyAndHandler, JweWithRecipientKey,
+    JweWithRecipientKeyAndClaims, JweWithRecipientKeyAndClaimsAndHandler,
+};
+pub use header::JweHeader;
+
+/// JWE key management algorithms supported by [`JweBuilder`]
+pub const JWE_ALGORITHMS: [&str; 7] = [
+    "dir",
+    "A256KW",
+    "RSA-OAEP-256",
+    "ECDH-ES+A256KW",
+    "MLKEM512",
+    "MLKEM768",
+    "MLKEM1024",
+];
@@ -0,0 +1,321 @@
+//! Refreshing JWKS cache backed by a file or an HTTP(S) URL
+//!
+//! Keys are served from an in-memory snapshot that is reloaded once it is
+//! older than the refresh interval. A token naming an unknown `kid` may force
+//! an early reload, throttled by the minimum refresh interval so that forged
+//! `kid` values cannot hammer the key server. If a reload fails, the last good
+//! snapshot keeps being served and retries back off exponentially.
+
+use super::Jwks;
+use crate::error::{JwtError, JwtResult};
+use arc_swap::ArcSwapOption;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// Default age after which cached keys are reloaded
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Default minimum spacing between reloads forced by unknown `kid` values
+const DEFAULT_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default HTTP request timeout
+const DEFAULT_HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest JWKS response body accepted, far above any real key set
+const MAX_JWKS_BODY: usize = 1024 * 1024;
+
+/// Location a JWKS document is loaded from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwksSource {
+    /// Local JWKS file
+    File(PathBuf),
+    /// HTTP(S) JWKS endpoint
+    Url(String),
+}
+
+/// Refresh-capable JWKS cache - cheap to clone, clones share the snapshot
+#[derive(Debug, Clone)]
+pub struct JwksCache {
+    source: Arc<JwksSource>,
+    refresh_interval: Duration,
+    min_refresh_interval: Duration,
+    http: reqwest::Client,
+    state: Arc<CacheState>,
+}
+
+/// Snapshot shared between cache clones
+#[derive(Debug, Default)]
+struct CacheState {
+    keys: ArcSwapOption<Jwks>,
+    // Serializes reloads; never held on the fresh-snapshot path
+    reload_lock: Mutex<()>,
+    // Outcome of past reloads, read without waiting on `reload_lock`
+    attempts: std::sync::Mutex<Attempts>,
+}
+
+/// When reloads last ran and how many have failed in a row
+#[derive(Debug, Default, Clone, Copy)]
+struct Attempts {
+    last_success: Option<Instant>,
+    last_attempt: Option<Instant>,
+    failures: u32,
+}
+
+impl JwksCache {
+    /// Create a cache that loads from `source`; nothing is fetched until first use
+    ///
+    /// # Errors
+    /// Returns `JwtError::Internal` if the HTTP client cannot be initialised
+    pub fn new(source: JwksSource) -> JwtResult<Self> {
+        let http = reqwest::Client::builder()
+            .timeout(DEFAULT_HTTP_TIMEOUT)
+            .build()
+            .map_err(|e| JwtError::Internal(format!("Failed to build JWKS HTTP client: {e}")))?;
+
+        Ok(Self {
+            source: Arc::new(source),
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            min_refresh_interval: DEFAULT_MIN_REFRESH_INTERVAL,
+            http,
+            state: Arc::new(CacheState::default()),
+        })
+    }
+
+    /// Create a cache that loads from a local JWKS file
+    ///
+    /// # Errors
+    /// Returns `JwtError::Internal` if the HTTP client cannot be initialised
+    pub fn from_file(path: impl Into<PathBuf>) -> JwtResult<Self> {
+        Self::new(JwksSource::File(path.into()))
+    }
+
+    /// Create a cache that loads from an HTTP(S) JWKS endpoint
+    ///
+    /// Responses larger than 1 MiB fail the reload.
+    ///
+    /// # Errors
+    /// Returns `JwtError::Internal` if the HTTP client cannot be initialised
+    pub fn from_url(url: impl Into<String>) -> JwtResult<Self> {
+        Self::new(JwksSource::Url(url.into()))
+    }
+
+    /// Set the age after which cached keys are reloaded
+    #[must_use]
+    pub fn with_refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Set the minimum spacing between reloads forced by unknown `kid` values
+    #[must_use]
+    pub fn with_min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    /// Where this cache loads keys from
+    #[must_use]
+    pub fn source(&self) -> &JwksSource {
+        &self.source
+    }
+
+    /// Current snapshot without triggering a reload
+    #[must_use]
+    pub fn cached(&self) -> Option<Arc<Jwks>> {
+        self.state.keys.load_full()
+    }
+
+    /// Current keys, reloading first if the snapshot is missing or stale
+    ///
+    /// A fresh snapshot is returned without taking any lock. After a failed
+    /// reload, further attempts back off exponentially from the minimum
+    /// refresh interval up to the refresh interval, serving the last good
+    /// snapshot in the meantime.
+    ///
+    /// # Errors
+    /// Returns `JwtError` if no snapshot exists and loading fails or is backing off
+    pub async fn keys(&self) -> JwtResult<Arc<Jwks>> {
+        if let Some(keys) = self.usable_snapshot() {
+            return Ok(keys);
+        }
+
+        let _reload = self.state.reload_lock.lock().await;
+        // Another caller may have reloaded, or failed, while we waited
+        if let Some(keys) = self.usable_snapshot() {
+            return Ok(keys);
+        }
+        if self.backing_off() {
+            return Err(JwtError::Internal(
+                "JWKS unavailable, retrying after backoff".to_string(),
+            ));
+        }
+
+        match self.reload().await {
+            Ok(keys) => Ok(keys),
+            Err(e) => match self.cached() {
+                Some(keys) => {
+                    tracing::warn!("JWKS refresh failed, serving cached keys: {e}");
+                    Ok(keys)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Reload the keys now, regardless of age or backoff
+    ///
+    /// # Errors
+    /// Returns `JwtError` if the source cannot be read or parsed
+    pub async fn refresh(&self) -> JwtResult<Arc<Jwks>> {
+        let _reload = self.state.reload_lock.lock().await;
+        self.reload().await
+    }
+
+    /// Reload after a `kid` miss unless a reload was attempted too recently
+    ///
+    /// Returns `None` when the reload was throttled.
+    pub(crate) async fn refresh_for_unknown_kid(&self) -> JwtResult<Option<Arc<Jwks>>> {
+        if self.kid_refresh_throttled() {
+            return Ok(None);
+        }
+        let _reload = self.state.reload_lock.lock().await;
+        if self.kid_refresh_throttled() {
+            return Ok(None);
+        }
+        self.reload().await.map(Some)
+    }
+
+    /// Reload the keys every refresh interval until the returned task is aborted
+    #[must_use]
+    pub fn spawn_refresh(&self) -> tokio::task::JoinHandle<()> {
+        let cache = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(cache.refresh_interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = cache.refresh().await {
+                    tracing::warn!("Background JWKS refresh failed: {e}");
+                }
+            }
+        })
+    }
+
+    /// Fetch, parse and publish a new snapshot; callers hold `reload_lock`
+    async fn reload(&self) -> JwtResult<Arc<Jwks>> {
+        let result = self.load().await;
+
+        let mut attempts = self.attempts();
+        let now = Instant::now();
+        attempts.last_attempt = Some(now);
+        match &result {
+            Ok(keys) => {
+                self.state.keys.store(Some(Arc::clone(keys)));
+                attempts.last_success = Some(now);
+                attempts.failures = 0;
+            }
+            Err(_) => attempts.failures = attempts.failures.saturating_add(1),
+        }
+        *self
+            .state
+            .attempts
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = attempts;
+        result
+    }
+
+    /// Read and parse the source document
+    async fn load(&self) -> JwtResult<Arc<Jwks>> {
+        let document = match self.source.as_ref() {
+            JwksSource::File(path) => tokio::fs::read_to_string(path).await.map_err(|e| {
+                JwtError::Internal(format!("Failed to read JWKS file {}: {e}", path.display()))
+            })?,
+            JwksSource::Url(url) => self.fetch(url).await?,
+        };
+        Ok(Arc::new(Jwks::from_json(&document)?))
+    }
+
+    /// Copy of the reload bookkeeping
+    fn attempts(&self) -> Attempts {
+        *self
+            .state
+            .attempts
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Cached keys that need no reload: fresh, or stale while a failed reload backs off
+    fn usable_snapshot(&self) -> Option<Arc<Jwks>> {
+        let keys = self.cached()?;
+        let fresh = self
+            .attempts()
+            .last_success
+            .is_some_and(|at| at.elapsed() < self.refresh_interval);
+        (fresh || self.backing_off()).then_some(keys)
+    }
+
+    /// Whether the last reload failed and its backoff delay has not yet passed
+    fn backing_off(&self) -> bool {
+        let attempts = self.attempts();
+        attempts.failures > 0
+            && attempts
+                .last_attempt
+                .is_some_and(|at| at.elapsed() < self.backoff_delay(attempts.failures))
+    }
+
+    /// Whether a `kid` miss must wait before reloading
+    fn kid_refresh_throttled(&self) -> bool {
+        self.backing_off()
+            || self
+                .attempts()
+                .last_attempt
+                .is_some_and(|at| at.elapsed() < self.min_refresh_interval)
+    }
+
+    /// Delay after `failures` consecutive failed reloads
+    fn backoff_delay(&self, failures: u32) -> Duration {
+        let doublings = failures.saturating_sub(1).min(16);
+        self.min_refresh_interval
+            .saturating_mul(1 << doublings)
+            .min(self.refresh_interval.max(self.min_refresh_interval))
+    }
+
+    /// Download a JWKS document
+    async fn fetch(&self, url: &str) -> JwtResult<String> {
+        let mut response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| JwtError::Internal(format!("Failed to fetch JWKS from {url}: {e}")))?;
+
+        let too_large = || {
+            JwtError::Internal(format!(
+                "JWKS from {url} exceeds the {MAX_JWKS_BODY}-byte limit"
+            ))
+        };
+        if response
+            .content_length()
+            .is_some_and(|length| length > MAX_JWKS_BODY as u64)
+        {
+            return Err(too_large());
+        }
+
+        // The declared length may be absent or wrong, so the read itself is capped
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| JwtError::Internal(format!("Failed to read JWKS from {url}: {e}")))?
+        {
+            if body.len() + chunk.len() > MAX_JWKS_BODY {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        String::from_utf8(body)
+            .map_err(|_| JwtError::Internal(format!("JWKS from {url} is not valid UTF-8")))
+    }
+}
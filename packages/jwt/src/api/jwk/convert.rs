@@ -0,0 +1,454 @@
+//! Conversions between JWKs and the key formats accepted by the JWT builders
+//!
+//! HMAC keys are raw secrets, RSA/EC/OKP keys are PKCS#8 (private) and SPKI
+//! (public) DER - PEM is accepted on input - and post-quantum keys are the raw
+//! `cryypt_pqcrypto` encodings.
+
+use super::der::{
+    RsaPrivateComponents, RsaPublicComponents, decode_rsa_pkcs8, decode_rsa_spki,
+    encode_rsa_pkcs8, encode_rsa_spki,
+};
+use super::{Jwk, decode_member, encode_member};
+use crate::api::algorithms::eddsa::{
+    decode_okp_private_key, decode_okp_public_key, encode_okp_private_key, encode_okp_public_key,
+    okp_public_from_seed,
+};
+use crate::api::keys::{
+    key_to_der, validate_hmac_key, validate_pq_private_key, validate_pq_public_key,
+    validate_rsa_private_key, validate_rsa_public_key,
+};
+use crate::error::{JwtError, JwtResult};
+use crate::types::EdDsaCurve;
+
+impl Jwk {
+    /// Build an `oct` JWK for an HMAC secret
+    ///
+    /// # Errors
+    /// Returns `JwtError` if `alg` is not HS256/384/512 or the secret is too short
+    pub fn from_hmac_secret(alg: &str, secret: &[u8]) -> JwtResult<Self> {
+        validate_hmac_key(secret, alg)?;
+        Ok(Self {
+            kty: "oct".to_string(),
+            alg: Some(alg.to_string()),
+            key_use: Some("sig".to_string()),
+            k: encode_member(secret),
+            ..Self::default()
+        })
+    }
+
+    /// Build an `RSA` public JWK from an SPKI key (DER or PEM)
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if the key is not an RSA public key
+    pub fn from_rsa_public_key(public_key: &[u8]) -> JwtResult<Self> {
+        let der = key_to_der(public_key)?;
+        validate_rsa_public_key(&der)?;
+        let components = decode_rsa_spki(&der)?;
+
+        Ok(Self {
+            kty: "RSA".to_string(),
+            key_use: Some("sig".to_string()),
+            n: encode_member(&components.n),
+            e: encode_member(&components.e),
+            ..Self::default()
+        })
+    }
+
+    /// Build an `RSA` private JWK from a PKCS#8 key (DER or PEM)
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if the key is not a two-prime RSA private key
+    pub fn from_rsa_private_key(private_key: &[u8]) -> JwtResult<Self> {
+        let der = zeroize::Zeroizing::new(key_to_der(private_key)?);
+        validate_rsa_private_key(&der)?;
+        let components = decode_rsa_pkcs8(&der)?;
+
+        Ok(Self {
+            kty: "RSA".to_string(),
+            key_use: Some("sig".to_string()),
+            n: encode_member(&components.n),
+            e: encode_member(&components.e),
+            d: encode_member(&components.d),
+            p: encode_member(&components.p),
+            q: encode_member(&components.q),
+            dp: encode_member(&components.dp),
+            dq: encode_member(&components.dq),
+            qi: encode_member(&components.qi),
+            ..Self::default()
+        })
+    }
+
+    /// Build an `EC` public JWK from a P-256 or P-384 SPKI key (DER or PEM)
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if the key is not a P-256/P-384 public key
+    pub fn from_ec_public_key(public_key: &[u8]) -> JwtResult<Self> {
+        let der = key_to_der(public_key)?;
+        let (crv, point) = ec_point_from_spki(&der)?;
+        Ok(ec_jwk(crv, &point, None))
+    }
+
+    /// Build an `EC` private JWK from a P-256 or P-384 PKCS#8 key (DER or PEM)
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if the key is not a P-256/P-384 private key
+    pub fn from_ec_private_key(private_key: &[u8]) -> JwtResult<Self> {
+        let der = zeroize::Zeroizing::new(key_to_der(private_key)?);
+
+        if let Ok(signing_key) = p256_signing_key(&der) {
+            let point = signing_key.verifying_key().to_encoded_point(false);
+            let scalar = zeroize::Zeroizing::new(signing_key.to_bytes().to_vec());
+            return Ok(ec_jwk("P-256", point.as_bytes(), Some(scalar.as_slice())));
+        }
+        if let Ok(signing_key) = p384_signing_key(&der) {
+            let point = signing_key.verifying_key().to_encoded_point(false);
+            let scalar = zeroize::Zeroizing::new(signing_key.to_bytes().to_vec());
+            return Ok(ec_jwk("P-384", point.as_bytes(), Some(scalar.as_slice())));
+        }
+
+        Err(JwtError::invalid_key(
+            "EC private key must be PKCS#8 for P-256 or P-384",
+        ))
+    }
+
+    /// Build an `OKP` public JWK from an Ed25519/Ed448 SPKI key (DER or PEM)
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if the key is not an Ed25519/Ed448 public key
+    pub fn from_okp_public_key(public_key: &[u8]) -> JwtResult<Self> {
+        let der = key_to_der(public_key)?;
+        let (curve, point) = decode_okp_public_key(&der)?;
+
+        Ok(Self {
+            kty: "OKP".to_string(),
+            alg: Some("EdDSA".to_string()),
+            key_use: Some("sig".to_string()),
+            crv: Some(curve.crv().to_string()),
+            x: encode_member(point),
+            ..Self::default()
+        })
+    }
+
+    /// Build an `OKP` private JWK from an Ed25519/Ed448 PKCS#8 key (DER or PEM)
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if the key is not an Ed25519/Ed448 private key
+    pub fn from_okp_private_key(private_key: &[u8]) -> JwtResult<Self> {
+        let der = zeroize::Zeroizing::new(key_to_der(private_key)?);
+        let (curve, seed) = decode_okp_private_key(&der)?;
+        let point = okp_public_from_seed(curve, seed)?;
+
+        Ok(Self {
+            kty: "OKP".to_string(),
+            alg: Some("EdDSA".to_string()),
+            key_use: Some("sig".to_string()),
+            crv: Some(curve.crv().to_string()),
+            x: encode_member(&point),
+            d: encode_member(seed),
+            ..Self::default()
+        })
+    }
+
+    /// Build an `AKP` public JWK for a post-quantum or composite `alg`
+    ///
+    /// # Errors
+    /// Returns `JwtError::UnsupportedAlgorithm` if `alg` is not post-quantum
+    pub fn from_pq_public_key(alg: &str, public_key: &[u8]) -> JwtResult<Self> {
+        validate_pq_public_key(public_key, alg)?;
+        Ok(Self {
+            kty: "AKP".to_string(),
+            alg: Some(alg.to_string()),
+            key_use: Some("sig".to_string()),
+            public: encode_member(public_key),
+            ..Self::default()
+        })
+    }
+
+    /// Build an `AKP` private JWK for a post-quantum or composite `alg`
+    ///
+    /// # Errors
+    /// Returns `JwtError` if `alg` is not post-quantum or a key is malformed
+    pub fn from_pq_private_key(
+        alg: &str,
+        private_key: &[u8],
+        public_key: &[u8],
+    ) -> JwtResult<Self> {
+        validate_pq_private_key(private_key, alg)?;
+        let mut jwk = Self::from_pq_public_key(alg, public_key)?;
+        jwk.private = encode_member(private_key);
+        Ok(jwk)
+    }
+
+    /// Decode the secret of an `oct` JWK
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if this is not an `oct` key
+    pub fn hmac_secret(&self) -> JwtResult<Vec<u8>> {
+        self.expect_kty("oct")?;
+        decode_member(self.k.as_deref(), "k")
+    }
+
+    /// Decode the verification key in the form the JWT builders accept:
+    /// SPKI DER for `RSA`/`EC`/`OKP`, raw bytes for `AKP`
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if required members are missing or invalid
+    pub fn public_key_bytes(&self) -> JwtResult<Vec<u8>> {
+        match self.kty.as_str() {
+            "RSA" => {
+                let der = encode_rsa_spki(&RsaPublicComponents {
+                    n: decode_member(self.n.as_deref(), "n")?,
+                    e: decode_member(self.e.as_deref(), "e")?,
+                });
+                validate_rsa_public_key(&der)?;
+                Ok(der)
+            }
+            "EC" => {
+                let crv = self.crv.as_deref().unwrap_or_default();
+                let point = self.ec_point()?;
+                ec_spki_from_point(crv, &point)
+            }
+            "OKP" => {
+                let curve = self.okp_curve()?;
+                let point = decode_member(self.x.as_deref(), "x")?;
+                if point.len() != curve.key_size() {
+                    return Err(JwtError::invalid_key(&format!(
+                        "{} public key must be {} bytes",
+                        curve.crv(),
+                        curve.key_size()
+                    )));
+                }
+                let der = encode_okp_public_key(curve, &point);
+                crate::api::keys::validate_okp_public_key(&der)?;
+                Ok(der)
+            }
+            "AKP" => self.pq_public_key(),
+            kty => Err(JwtError::invalid_key(&format!(
+                "JWK of type {kty} has no public key"
+            ))),
+        }
+    }
+
+    /// Decode the signing key in the form the JWT builders accept:
+    /// the secret for `oct`, PKCS#8 DER for `RSA`/`EC`/`OKP`, raw bytes for `AKP`
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if private members are missing or invalid
+    pub fn private_key_bytes(&self) -> JwtResult<Vec<u8>> {
+        match self.kty.as_str() {
+            "oct" => self.hmac_secret(),
+            "RSA" => {
+                if self.p.is_none() || self.q.is_none() {
+                    return Err(JwtError::invalid_key(
+                        "RSA private JWK must include the CRT parameters p, q, dp, dq and qi",
+                    ));
+                }
+                let der = encode_rsa_pkcs8(&RsaPrivateComponents {
+                    n: decode_member(self.n.as_deref(), "n")?,
+                    e: decode_member(self.e.as_deref(), "e")?,
+                    d: decode_member(self.d.as_deref(), "d")?,
+                    p: decode_member(self.p.as_deref(), "p")?,
+                    q: decode_member(self.q.as_deref(), "q")?,
+                    dp: decode_member(self.dp.as_deref(), "dp")?,
+                    dq: decode_member(self.dq.as_deref(), "dq")?,
+                    qi: decode_member(self.qi.as_deref(), "qi")?,
+                });
+                validate_rsa_private_key(&der)?;
+                Ok(der)
+            }
+            "EC" => self.ec_private_key(),
+            "OKP" => {
+                let curve = self.okp_curve()?;
+                let seed = zeroize::Zeroizing::new(decode_member(self.d.as_deref(), "d")?);
+                let point = okp_public_from_seed(curve, &seed)?;
+                if self.x.is_some() && decode_member(self.x.as_deref(), "x")? != point {
+                    return Err(JwtError::invalid_key(
+                        "OKP private key does not match its public key",
+                    ));
+                }
+                Ok(encode_okp_private_key(curve, &seed))
+            }
+            "AKP" => self.pq_private_key(),
+            kty => Err(JwtError::invalid_key(&format!(
+                "Unsupported JWK key type {kty}"
+            ))),
+        }
+    }
+
+    /// Decode the raw public key of an `AKP` JWK
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if this is not a valid `AKP` public key
+    pub fn pq_public_key(&self) -> JwtResult<Vec<u8>> {
+        let alg = self.akp_algorithm()?;
+        let public_key = decode_member(self.public.as_deref(), "pub")?;
+        validate_pq_public_key(&public_key, alg)?;
+        Ok(public_key)
+    }
+
+    /// Decode the raw private key of an `AKP` JWK
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if this is not a valid `AKP` private key
+    pub fn pq_private_key(&self) -> JwtResult<Vec<u8>> {
+        let alg = self.akp_algorithm()?;
+        let private_key = decode_member(self.private.as_deref(), "priv")?;
+        validate_pq_private_key(&private_key, alg)?;
+        Ok(private_key)
+    }
+
+    /// Require a specific key type
+    fn expect_kty(&self, kty: &str) -> JwtResult<()> {
+        if self.kty == kty {
+            Ok(())
+        } else {
+            Err(JwtError::invalid_key(&format!(
+                "Expected a {kty} key, got kty {}",
+                self.kty
+            )))
+        }
+    }
+
+    /// The post-quantum `alg` of an `AKP` key
+    fn akp_algorithm(&self) -> JwtResult<&str> {
+        self.expect_kty("AKP")?;
+        match self.alg.as_deref() {
+            Some(alg) if crate::api::algorithms::pq::is_pq_algorithm(alg) => Ok(alg),
+            Some(alg) => Err(JwtError::unsupported_algorithm(alg)),
+            None => Err(JwtError::invalid_key("AKP key is missing alg")),
+        }
+    }
+
+    /// The Edwards curve of an `OKP` key
+    fn okp_curve(&self) -> JwtResult<EdDsaCurve> {
+        match self.crv.as_deref() {
+            Some("Ed25519") => Ok(EdDsaCurve::Ed25519),
+            Some("Ed448") => Ok(EdDsaCurve::Ed448),
+            Some(crv) => Err(JwtError::invalid_key(&format!(
+                "Unsupported OKP curve {crv}"
+            ))),
+            None => Err(JwtError::invalid_key("OKP key is missing crv")),
+        }
+    }
+
+    /// Uncompressed SEC1 point of an `EC` key
+    fn ec_point(&self) -> JwtResult<Vec<u8>> {
+        self.expect_kty("EC")?;
+        let x = decode_member(self.x.as_deref(), "x")?;
+        let y = decode_member(self.y.as_deref(), "y")?;
+        let size = ec_coordinate_size(self.crv.as_deref().unwrap_or_default())?;
+        if x.len() != size || y.len() != size {
+            return Err(JwtError::invalid_key(&format!(
+                "EC coordinates must be {size} bytes"
+            )));
+        }
+
+        let mut point = Vec::with_capacity(1 + 2 * size);
+        point.push(0x04);
+        point.extend_from_slice(&x);
+        point.extend_from_slice(&y);
+        Ok(point)
+    }
+
+    /// PKCS#8 DER of an `EC` private key, checked against its public point
+    fn ec_private_key(&self) -> JwtResult<Vec<u8>> {
+        use p256::pkcs8::EncodePrivateKey as _;
+
+        let point = self.ec_point()?;
+        let scalar = zeroize::Zeroizing::new(decode_member(self.d.as_deref(), "d")?);
+        let mismatch = || JwtError::invalid_key("EC private key does not match its public key");
+
+        let der = match self.crv.as_deref() {
+            Some("P-256") => {
+                let signing_key = p256::ecdsa::SigningKey::from_slice(&scalar)
+                    .map_err(|e| JwtError::InvalidKey(format!("Invalid P-256 scalar: {e}")))?;
+                if signing_key.verifying_key().to_encoded_point(false).as_bytes() != point {
+                    return Err(mismatch());
+                }
+                signing_key.to_pkcs8_der()
+            }
+            Some("P-384") => {
+                let signing_key = p384::ecdsa::SigningKey::from_slice(&scalar)
+                    .map_err(|e| JwtError::InvalidKey(format!("Invalid P-384 scalar: {e}")))?;
+                if signing_key.verifying_key().to_encoded_point(false).as_bytes() != point {
+                    return Err(mismatch());
+                }
+                signing_key.to_pkcs8_der()
+            }
+            _ => return Err(JwtError::invalid_key("EC key must use P-256 or P-384")),
+        }
+        .map_err(|e| JwtError::Internal(format!("Failed to encode EC private key: {e}")))?;
+
+        Ok(der.as_bytes().to_vec())
+    }
+}
+
+/// Build an `EC` JWK from an uncompressed SEC1 point and optional scalar
+fn ec_jwk(crv: &str, point: &[u8], scalar: Option<&[u8]>) -> Jwk {
+    let size = (point.len() - 1) / 2;
+    Jwk {
+        kty: "EC".to_string(),
+        alg: Some(if crv == "P-256" { "ES256" } else { "ES384" }.to_string()),
+        key_use: Some("sig".to_string()),
+        crv: Some(crv.to_string()),
+        x: encode_member(&point[1..=size]),
+        y: encode_member(&point[size + 1..]),
+        d: scalar.and_then(encode_member),
+        ..Jwk::default()
+    }
+}
+
+/// Coordinate size in bytes for a supported `EC` curve
+fn ec_coordinate_size(crv: &str) -> JwtResult<usize> {
+    match crv {
+        "P-256" => Ok(32),
+        "P-384" => Ok(48),
+        _ => Err(JwtError::invalid_key("EC key must use P-256 or P-384")),
+    }
+}
+
+/// Extract the curve and uncompressed SEC1 point from an EC SPKI key
+fn ec_point_from_spki(der: &[u8]) -> JwtResult<(&'static str, Vec<u8>)> {
+    use p256::pkcs8::DecodePublicKey as _;
+
+    if let Ok(key) = p256::ecdsa::VerifyingKey::from_public_key_der(der) {
+        return Ok(("P-256", key.to_encoded_point(false).as_bytes().to_vec()));
+    }
+    if let Ok(key) = p384::ecdsa::VerifyingKey::from_public_key_der(der) {
+        return Ok(("P-384", key.to_encoded_point(false).as_bytes().to_vec()));
+    }
+
+    Err(JwtError::invalid_key(
+        "EC public key must be SPKI for P-256 or P-384",
+    ))
+}
+
+/// Encode an uncompressed SEC1 point as SPKI DER
+fn ec_spki_from_point(crv: &str, point: &[u8]) -> JwtResult<Vec<u8>> {
+    use p256::pkcs8::EncodePublicKey as _;
+
+    let der = match crv {
+        "P-256" => p256::ecdsa::VerifyingKey::from_sec1_bytes(point)
+            .map_err(|e| JwtError::InvalidKey(format!("Invalid P-256 point: {e}")))?
+            .to_public_key_der(),
+        "P-384" => p384::ecdsa::VerifyingKey::from_sec1_bytes(point)
+            .map_err(|e| JwtError::InvalidKey(format!("Invalid P-384 point: {e}")))?
+            .to_public_key_der(),
+        _ => return Err(JwtError::invalid_key("EC key must use P-256 or P-384")),
+    }
+    .map_err(|e| JwtError::Internal(format!("Failed to encode EC public key: {e}")))?;
+
+    Ok(der.as_bytes().to_vec())
+}
+
+/// Parse a P-256 PKCS#8 private key
+fn p256_signing_key(der: &[u8]) -> Result<p256::ecdsa::SigningKey, p256::pkcs8::Error> {
+    use p256::pkcs8::DecodePrivateKey as _;
+    p256::ecdsa::SigningKey::from_pkcs8_der(der)
+}
+
+/// Parse a P-384 PKCS#8 private key
+fn p384_signing_key(der: &[u8]) -> Result<p384::ecdsa::SigningKey, p384::pkcs8::Error> {
+    use p384::pkcs8::DecodePrivateKey as _;
+    p384::ecdsa::SigningKey::from_pkcs8_der(der)
+}
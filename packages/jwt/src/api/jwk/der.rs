@@ -0,0 +1,247 @@
+//! Minimal DER codec for RSA key structures
+//!
+//! Converts between RSA JWK members and PKCS#8 `PrivateKeyInfo` /
+//! `SubjectPublicKeyInfo` DER without depending on the big-integer API of a
+//! particular `rsa` release. Only the definite-length forms produced by
+//! PKCS#1/PKCS#8 encoders are supported.
+
+use crate::error::{JwtError, JwtResult};
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_SEQUENCE: u8 = 0x30;
+
+/// DER `AlgorithmIdentifier` for rsaEncryption (1.2.840.113549.1.1.1) with NULL parameters
+const RSA_ALGORITHM_ID: [u8; 15] = [
+    0x30, 0x0d, 0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01, 0x05, 0x00,
+];
+
+/// RSA public key components as unsigned big-endian integers
+pub(super) struct RsaPublicComponents {
+    pub n: Vec<u8>,
+    pub e: Vec<u8>,
+}
+
+/// RSA private key components as unsigned big-endian integers
+pub(super) struct RsaPrivateComponents {
+    pub n: Vec<u8>,
+    pub e: Vec<u8>,
+    pub d: Vec<u8>,
+    pub p: Vec<u8>,
+    pub q: Vec<u8>,
+    pub dp: Vec<u8>,
+    pub dq: Vec<u8>,
+    pub qi: Vec<u8>,
+}
+
+impl Drop for RsaPrivateComponents {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.d.zeroize();
+        self.p.zeroize();
+        self.q.zeroize();
+        self.dp.zeroize();
+        self.dq.zeroize();
+        self.qi.zeroize();
+    }
+}
+
+/// Decode an RSA `SubjectPublicKeyInfo`
+pub(super) fn decode_rsa_spki(der: &[u8]) -> JwtResult<RsaPublicComponents> {
+    let mut spki = Reader::new(expect_only(der, TAG_SEQUENCE)?);
+    expect_rsa_algorithm(spki.read(TAG_SEQUENCE)?)?;
+    let bits = spki.read(TAG_BIT_STRING)?;
+    spki.finish()?;
+
+    // First BIT STRING octet is the count of unused bits, always zero here
+    let (&0, rsa_public_key) = bits.split_first().ok_or_else(malformed)? else {
+        return Err(malformed());
+    };
+
+    let mut key = Reader::new(expect_only(rsa_public_key, TAG_SEQUENCE)?);
+    let components = RsaPublicComponents {
+        n: unsigned(key.read(TAG_INTEGER)?),
+        e: unsigned(key.read(TAG_INTEGER)?),
+    };
+    key.finish()?;
+    Ok(components)
+}
+
+/// Decode an RSA PKCS#8 `PrivateKeyInfo`
+pub(super) fn decode_rsa_pkcs8(der: &[u8]) -> JwtResult<RsaPrivateComponents> {
+    let mut info = Reader::new(expect_only(der, TAG_SEQUENCE)?);
+    info.read(TAG_INTEGER)?;
+    expect_rsa_algorithm(info.read(TAG_SEQUENCE)?)?;
+    let rsa_private_key = info.read(TAG_OCTET_STRING)?;
+
+    let mut key = Reader::new(expect_only(rsa_private_key, TAG_SEQUENCE)?);
+    // Two-prime keys only: version must be 0
+    if key.read(TAG_INTEGER)? != [0] {
+        return Err(JwtError::invalid_key(
+            "Multi-prime RSA keys cannot be represented as a JWK",
+        ));
+    }
+    let mut next = || key.read(TAG_INTEGER).map(unsigned);
+    Ok(RsaPrivateComponents {
+        n: next()?,
+        e: next()?,
+        d: next()?,
+        p: next()?,
+        q: next()?,
+        dp: next()?,
+        dq: next()?,
+        qi: next()?,
+    })
+}
+
+/// Encode RSA public components as `SubjectPublicKeyInfo`
+pub(super) fn encode_rsa_spki(components: &RsaPublicComponents) -> Vec<u8> {
+    let mut rsa_public_key = integer(&components.n);
+    rsa_public_key.extend(integer(&components.e));
+    let rsa_public_key = tlv(TAG_SEQUENCE, &rsa_public_key);
+
+    let mut bits = Vec::with_capacity(rsa_public_key.len() + 1);
+    bits.push(0);
+    bits.extend(rsa_public_key);
+
+    let mut spki = RSA_ALGORITHM_ID.to_vec();
+    spki.extend(tlv(TAG_BIT_STRING, &bits));
+    tlv(TAG_SEQUENCE, &spki)
+}
+
+/// Encode RSA private components as PKCS#8 `PrivateKeyInfo`
+pub(super) fn encode_rsa_pkcs8(components: &RsaPrivateComponents) -> Vec<u8> {
+    let mut rsa_private_key = integer(&[0]);
+    for value in [
+        &components.n,
+        &components.e,
+        &components.d,
+        &components.p,
+        &components.q,
+        &components.dp,
+        &components.dq,
+        &components.qi,
+    ] {
+        rsa_private_key.extend(integer(value));
+    }
+    let rsa_private_key = zeroize::Zeroizing::new(tlv(TAG_SEQUENCE, &rsa_private_key));
+
+    let mut info = integer(&[0]);
+    info.extend_from_slice(&RSA_ALGORITHM_ID);
+    info.extend(tlv(TAG_OCTET_STRING, &rsa_private_key));
+    tlv(TAG_SEQUENCE, &info)
+}
+
+/// Sequential reader over concatenated DER elements
+struct Reader<'a> {
+    input: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(input: &'a [u8]) -> Self {
+        Self { input }
+    }
+
+    /// Read the next element, which must carry `tag`, returning its contents
+    fn read(&mut self, tag: u8) -> JwtResult<&'a [u8]> {
+        let (&actual, rest) = self.input.split_first().ok_or_else(malformed)?;
+        if actual != tag {
+            return Err(malformed());
+        }
+        let (&first, rest) = rest.split_first().ok_or_else(malformed)?;
+
+        let (length, rest) = if first < 0x80 {
+            (usize::from(first), rest)
+        } else {
+            let count = usize::from(first & 0x7f);
+            if count == 0 || count > 4 || rest.len() < count {
+                return Err(malformed());
+            }
+            let length = rest[..count]
+                .iter()
+                .fold(0usize, |acc, &byte| (acc << 8) | usize::from(byte));
+            (length, &rest[count..])
+        };
+
+        if rest.len() < length {
+            return Err(malformed());
+        }
+        let (contents, rest) = rest.split_at(length);
+        self.input = rest;
+        Ok(contents)
+    }
+
+    /// Ensure no trailing data remains
+    fn finish(&self) -> JwtResult<()> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(malformed())
+        }
+    }
+}
+
+/// Read a single element spanning the whole input
+fn expect_only(input: &[u8], tag: u8) -> JwtResult<&[u8]> {
+    let mut reader = Reader::new(input);
+    let contents = reader.read(tag)?;
+    reader.finish()?;
+    Ok(contents)
+}
+
+/// Check an `AlgorithmIdentifier` body names rsaEncryption
+fn expect_rsa_algorithm(contents: &[u8]) -> JwtResult<()> {
+    if tlv(TAG_SEQUENCE, contents) == RSA_ALGORITHM_ID {
+        Ok(())
+    } else {
+        Err(JwtError::invalid_key("Key is not an rsaEncryption key"))
+    }
+}
+
+/// Strip the sign-padding zero octets of a DER INTEGER
+fn unsigned(contents: &[u8]) -> Vec<u8> {
+    let start = contents
+        .iter()
+        .position(|&byte| byte != 0)
+        .unwrap_or(contents.len().saturating_sub(1));
+    contents[start..].to_vec()
+}
+
+/// Encode an unsigned big-endian value as a DER INTEGER
+fn integer(value: &[u8]) -> Vec<u8> {
+    let start = value
+        .iter()
+        .position(|&byte| byte != 0)
+        .unwrap_or(value.len());
+    let value = &value[start..];
+
+    let mut contents = Vec::with_capacity(value.len() + 1);
+    if value.first().is_none_or(|&byte| byte & 0x80 != 0) {
+        contents.push(0);
+    }
+    contents.extend_from_slice(value);
+    tlv(TAG_INTEGER, &contents)
+}
+
+/// Encode a tag-length-value element
+fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(contents.len() + 6);
+    out.push(tag);
+    if let Ok(short @ 0..0x80) = u8::try_from(contents.len()) {
+        out.push(short);
+    } else {
+        // Long form: count octet followed by the minimal big-endian length
+        let bytes = contents.len().to_be_bytes();
+        let skip = bytes.iter().take_while(|&&byte| byte == 0).count();
+        let count = u8::try_from(bytes.len() - skip).unwrap_or(u8::MAX);
+        out.push(0x80 | count);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+    out.extend_from_slice(contents);
+    out
+}
+
+fn malformed() -> JwtError {
+    JwtError::invalid_key("Malformed RSA key DER")
+}
@@ -0,0 +1,104 @@
+//! JWK Set (RFC 7517 section 5)
+
+use super::Jwk;
+use crate::error::{JwtError, JwtResult};
+use serde::{Deserialize, Serialize};
+
+/// JSON Web Key Set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwks {
+    /// Keys in the set
+    pub keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    /// Create an empty key set
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a key to the set
+    #[must_use]
+    pub fn with_key(mut self, jwk: Jwk) -> Self {
+        self.keys.push(jwk);
+        self
+    }
+
+    /// Parse a JWKS document
+    ///
+    /// # Errors
+    /// Returns `JwtError::Serialization` if the document is not a valid JWKS
+    pub fn from_json(json: &str) -> JwtResult<Self> {
+        serde_json::from_str(json).map_err(|e| JwtError::Serialization(e.to_string()))
+    }
+
+    /// Serialize this key set as a JWKS document
+    ///
+    /// # Errors
+    /// Returns `JwtError::Serialization` if serialization fails
+    pub fn to_json(&self) -> JwtResult<String> {
+        serde_json::to_string(self).map_err(|e| JwtError::Serialization(e.to_string()))
+    }
+
+    /// Copy of this set with every private member removed, suitable for publishing
+    ///
+    /// Symmetric (`oct`) keys are dropped entirely.
+    #[must_use]
+    pub fn to_public(&self) -> Self {
+        Self {
+            keys: self
+                .keys
+                .iter()
+                .filter(|jwk| jwk.kty != "oct")
+                .map(Jwk::to_public)
+                .collect(),
+        }
+    }
+
+    /// Find a key by `kid`
+    #[must_use]
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys.iter().find(|jwk| jwk.kid.as_deref() == Some(kid))
+    }
+
+    /// Select the key that verifies a token with the given `kid` and `alg`
+    ///
+    /// Without a `kid` the set must hold exactly one key usable with `alg`.
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if no single key matches
+    pub fn select(&self, kid: Option<&str>, alg: &str) -> JwtResult<&Jwk> {
+        let mut candidates = self.keys.iter().filter(|jwk| {
+            jwk.supports_alg(alg) && kid.is_none_or(|kid| jwk.kid.as_deref() == Some(kid))
+        });
+
+        match (candidates.next(), candidates.next(), kid) {
+            (Some(jwk), None, _) => Ok(jwk),
+            (Some(_), Some(_), Some(kid)) => Err(JwtError::invalid_key(&format!(
+                "JWKS holds several {alg} keys with kid {kid}"
+            ))),
+            (Some(_), Some(_), None) => Err(JwtError::invalid_key(&format!(
+                "Token has no kid and JWKS holds several {alg} keys"
+            ))),
+            (None, _, Some(kid)) => Err(JwtError::invalid_key(&format!(
+                "No {alg} key with kid {kid} in JWKS"
+            ))),
+            (None, _, None) => Err(JwtError::invalid_key(&format!(
+                "No {alg} key in JWKS"
+            ))),
+        }
+    }
+
+    /// Number of keys in the set
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Whether the set holds no keys
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
@@ -0,0 +1,201 @@
+//! JSON Web Key (RFC 7517) and JWK Set support
+//!
+//! Converts HMAC, RSA, EC, OKP and post-quantum keys to and from JWKs,
+//! computes RFC 7638 thumbprints, and verifies tokens against a JWK Set by
+//! `kid` and `alg` - either a static [`Jwks`] or a refreshing [`JwksCache`].
+//!
+//! Post-quantum keys use the `AKP` (Algorithm Key Pair) key type from the
+//! IETF JOSE post-quantum drafts: the `alg` member names the parameter set and
+//! `pub`/`priv` carry the base64url-encoded raw key bytes.
+
+mod cache;
+mod convert;
+mod der;
+mod jwks;
+mod thumbprint;
+mod verifier;
+
+pub use cache::{JwksCache, JwksSource};
+pub use jwks::Jwks;
+pub use verifier::{JwksVerifier, JwksVerifierWithHandler};
+
+use crate::error::{JwtError, JwtResult};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+
+/// JSON Web Key (RFC 7517)
+///
+/// `Debug` output redacts the private members.
+#[derive(Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Jwk {
+    /// Key type (`oct`, `RSA`, `EC`, `OKP` or `AKP`)
+    pub kty: String,
+    /// Key identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// Algorithm the key is intended for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// Public key use (`sig` or `enc`)
+    #[serde(default, rename = "use", skip_serializing_if = "Option::is_none")]
+    pub key_use: Option<String>,
+    /// `EC`/`OKP` curve name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    /// `EC` x coordinate or `OKP` public key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    /// `EC` y coordinate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// `EC`/`OKP`/`RSA` private exponent or scalar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
+    /// `RSA` modulus
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n: Option<String>,
+    /// `RSA` public exponent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub e: Option<String>,
+    /// `RSA` first prime factor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p: Option<String>,
+    /// `RSA` second prime factor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    /// `RSA` first factor CRT exponent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dp: Option<String>,
+    /// `RSA` second factor CRT exponent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dq: Option<String>,
+    /// `RSA` first CRT coefficient
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qi: Option<String>,
+    /// `oct` symmetric key value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub k: Option<String>,
+    /// `AKP` public key
+    #[serde(default, rename = "pub", skip_serializing_if = "Option::is_none")]
+    pub public: Option<String>,
+    /// `AKP` private key
+    #[serde(default, rename = "priv", skip_serializing_if = "Option::is_none")]
+    pub private: Option<String>,
+}
+
+impl std::fmt::Debug for Jwk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Show whether a private member is present, never its value
+        let redact = |member: Option<&String>| member.map(|_| "[REDACTED]");
+        f.debug_struct("Jwk")
+            .field("kty", &self.kty)
+            .field("kid", &self.kid)
+            .field("alg", &self.alg)
+            .field("key_use", &self.key_use)
+            .field("crv", &self.crv)
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("d", &redact(self.d.as_ref()))
+            .field("n", &self.n)
+            .field("e", &self.e)
+            .field("p", &redact(self.p.as_ref()))
+            .field("q", &redact(self.q.as_ref()))
+            .field("dp", &redact(self.dp.as_ref()))
+            .field("dq", &redact(self.dq.as_ref()))
+            .field("qi", &redact(self.qi.as_ref()))
+            .field("k", &redact(self.k.as_ref()))
+            .field("public", &self.public)
+            .field("private", &redact(self.private.as_ref()))
+            .finish()
+    }
+}
+
+impl Jwk {
+    /// Set the key identifier
+    #[must_use]
+    pub fn with_kid(mut self, kid: impl Into<String>) -> Self {
+        self.kid = Some(kid.into());
+        self
+    }
+
+    /// Set the algorithm the key is intended for
+    #[must_use]
+    pub fn with_alg(mut self, alg: impl Into<String>) -> Self {
+        self.alg = Some(alg.into());
+        self
+    }
+
+    /// Set the key identifier to the RFC 7638 thumbprint of this key
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if required members are missing
+    pub fn with_thumbprint_kid(self) -> JwtResult<Self> {
+        let kid = self.thumbprint()?;
+        Ok(self.with_kid(kid))
+    }
+
+    /// Whether this key carries private material
+    #[must_use]
+    pub fn is_private(&self) -> bool {
+        self.d.is_some() || self.k.is_some() || self.private.is_some()
+    }
+
+    /// Copy of this key with all private members removed
+    ///
+    /// Symmetric (`oct`) keys have no public part, so their `k` is removed too.
+    #[must_use]
+    pub fn to_public(&self) -> Self {
+        Self {
+            d: None,
+            p: None,
+            q: None,
+            dp: None,
+            dq: None,
+            qi: None,
+            k: None,
+            private: None,
+            ..self.clone()
+        }
+    }
+
+    /// Whether this key may verify tokens signed with `alg`
+    #[must_use]
+    pub fn supports_alg(&self, alg: &str) -> bool {
+        if self.alg.as_deref().is_some_and(|own| own != alg) {
+            return false;
+        }
+        if self.key_use.as_deref().is_some_and(|key_use| key_use != "sig") {
+            return false;
+        }
+
+        match alg {
+            "HS256" | "HS384" | "HS512" => self.kty == "oct",
+            "RS256" | "RS384" | "RS512" | "PS256" | "PS384" | "PS512" => self.kty == "RSA",
+            "ES256" => self.kty == "EC" && self.crv.as_deref() == Some("P-256"),
+            "ES384" => self.kty == "EC" && self.crv.as_deref() == Some("P-384"),
+            "EdDSA" => {
+                self.kty == "OKP" && matches!(self.crv.as_deref(), Some("Ed25519" | "Ed448"))
+            }
+            // AKP keys are bound to exactly one algorithm
+            alg => {
+                self.kty == "AKP"
+                    && self.alg.as_deref() == Some(alg)
+                    && crate::api::algorithms::pq::is_pq_algorithm(alg)
+            }
+        }
+    }
+}
+
+/// Decode a required base64url JWK member
+fn decode_member(value: Option<&str>, name: &str) -> JwtResult<Vec<u8>> {
+    let value =
+        value.ok_or_else(|| JwtError::invalid_key(&format!("JWK is missing member {name}")))?;
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|e| JwtError::invalid_key(&format!("Invalid base64url in JWK {name}: {e}")))
+}
+
+/// Encode a JWK member as base64url without padding
+fn encode_member(value: &[u8]) -> Option<String> {
+    Some(URL_SAFE_NO_PAD.encode(value))
+}
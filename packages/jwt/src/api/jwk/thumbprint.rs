@@ -0,0 +1,52 @@
+//! RFC 7638 JWK thumbprints
+//!
+//! The thumbprint is the base64url SHA-256 of the key's required members,
+//! serialized as JSON with lexicographically ordered names and no whitespace.
+
+use super::Jwk;
+use crate::error::{JwtError, JwtResult};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+impl Jwk {
+    /// Compute the RFC 7638 SHA-256 thumbprint of this key
+    ///
+    /// Private members never contribute, so a private key and its public half
+    /// share a thumbprint. `AKP` keys use the `alg`, `kty` and `pub` members.
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidKey` if a required member is missing
+    pub fn thumbprint(&self) -> JwtResult<String> {
+        let required: &[(&str, Option<&String>)] = match self.kty.as_str() {
+            "oct" => &[("k", self.k.as_ref())],
+            "RSA" => &[("e", self.e.as_ref()), ("n", self.n.as_ref())],
+            "EC" => &[
+                ("crv", self.crv.as_ref()),
+                ("x", self.x.as_ref()),
+                ("y", self.y.as_ref()),
+            ],
+            "OKP" => &[("crv", self.crv.as_ref()), ("x", self.x.as_ref())],
+            "AKP" => &[("alg", self.alg.as_ref()), ("pub", self.public.as_ref())],
+            kty => {
+                return Err(JwtError::invalid_key(&format!(
+                    "Cannot compute thumbprint for key type {kty}"
+                )));
+            }
+        };
+
+        let mut members = BTreeMap::new();
+        members.insert("kty", self.kty.as_str());
+        for &(name, value) in required {
+            let value = value.ok_or_else(|| {
+                JwtError::invalid_key(&format!("JWK is missing member {name}"))
+            })?;
+            members.insert(name, value.as_str());
+        }
+
+        // BTreeMap gives the lexicographic member order; serde_json emits no whitespace
+        let canonical =
+            serde_json::to_vec(&members).map_err(|e| JwtError::Serialization(e.to_string()))?;
+        Ok(URL_SAFE_NO_PAD.encode(Sha256::digest(&canonical)))
+    }
+}
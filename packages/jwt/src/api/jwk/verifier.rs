@@ -0,0 +1,134 @@
+//! JWT verification against a JWK Set, selecting the key by `kid` and `alg`
+
+use super::{Jwk, Jwks, JwksCache};
+use crate::api::algorithms::verify_jwt_for;
+use crate::error::{JwtError, JwtResult};
+use crate::types::JwtHeader;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use std::sync::Arc;
+
+/// Where the verifier takes its keys from
+#[derive(Debug, Clone)]
+enum KeySet {
+    Static(Arc<Jwks>),
+    Cache(JwksCache),
+}
+
+/// JWKS verifier - picks the verification key from the token's `kid` and `alg`
+#[derive(Debug, Clone)]
+pub struct JwksVerifier {
+    keys: KeySet,
+    algorithms: Vec<String>,
+}
+
+/// JWKS verifier with result handler
+#[derive(Debug)]
+pub struct JwksVerifierWithHandler<F> {
+    verifier: JwksVerifier,
+    handler: F,
+}
+
+impl JwksVerifier {
+    /// Verify against a fixed key set
+    #[must_use]
+    pub fn new(jwks: Jwks) -> Self {
+        Self {
+            keys: KeySet::Static(Arc::new(jwks)),
+            algorithms: Vec::new(),
+        }
+    }
+
+    /// Verify against a refreshing key set
+    #[must_use]
+    pub fn from_cache(cache: JwksCache) -> Self {
+        Self {
+            keys: KeySet::Cache(cache),
+            algorithms: Vec::new(),
+        }
+    }
+
+    /// Only accept tokens whose header `alg` is in `algorithms`
+    #[must_use]
+    pub fn with_algorithms(mut self, algorithms: &[&str]) -> Self {
+        self.algorithms = algorithms.iter().map(ToString::to_string).collect();
+        self
+    }
+
+    /// Set result handler for JWT verification
+    #[must_use]
+    pub fn on_result<F, R>(self, handler: F) -> JwksVerifierWithHandler<F>
+    where
+        F: FnOnce(JwtResult<serde_json::Value>) -> R,
+        R: cryypt_common::NotResult,
+    {
+        JwksVerifierWithHandler {
+            verifier: self,
+            handler,
+        }
+    }
+
+    /// Verify a JWT with the key selected from the set
+    ///
+    /// # Errors
+    /// Returns `JwtError` if no key matches or verification fails
+    pub async fn verify<S: AsRef<str>>(&self, token: S) -> JwtResult<serde_json::Value> {
+        let token = token.as_ref();
+        let header = decode_header(token)?;
+
+        if !self.algorithms.is_empty() && !self.algorithms.contains(&header.alg) {
+            return Err(JwtError::UnsupportedAlgorithm(header.alg));
+        }
+
+        let jwk = self.select(&header).await?;
+        let (secret, public_key) = if jwk.kty == "oct" {
+            (Some(jwk.hmac_secret()?), None)
+        } else {
+            (None, Some(jwk.public_key_bytes()?))
+        };
+
+        verify_jwt_for(&[header.alg.as_str()], token.to_string(), secret, public_key).await
+    }
+
+    /// Find the key for `header`, reloading a cached set once on a `kid` miss
+    async fn select(&self, header: &JwtHeader) -> JwtResult<Jwk> {
+        let kid = header.kid.as_deref();
+        match &self.keys {
+            KeySet::Static(jwks) => jwks.select(kid, &header.alg).cloned(),
+            KeySet::Cache(cache) => {
+                let jwks = cache.keys().await?;
+                match jwks.select(kid, &header.alg) {
+                    Ok(jwk) => Ok(jwk.clone()),
+                    Err(miss) => match cache.refresh_for_unknown_kid().await? {
+                        Some(jwks) => jwks.select(kid, &header.alg).cloned(),
+                        None => Err(miss),
+                    },
+                }
+            }
+        }
+    }
+}
+
+impl<F, R> JwksVerifierWithHandler<F>
+where
+    F: FnOnce(JwtResult<serde_json::Value>) -> R + Send + 'static,
+    R: cryypt_common::NotResult + Send + 'static,
+{
+    /// Verify a JWT with the key selected from the set and apply the result handler
+    pub async fn verify<S: AsRef<str>>(self, token: S) -> R {
+        let result = self.verifier.verify(token).await;
+        (self.handler)(result)
+    }
+}
+
+/// Decode the protected header of a compact JWT
+fn decode_header(token: &str) -> JwtResult<JwtHeader> {
+    let header_b64 = token
+        .split('.')
+        .next()
+        .ok_or_else(|| JwtError::InvalidToken("Invalid JWT format".to_string()))?;
+    let header_bytes = URL_SAFE_NO_PAD
+        .decode(header_b64)
+        .map_err(|_| JwtError::InvalidToken("Invalid header encoding".to_string()))?;
+    serde_json::from_slice(&header_bytes)
+        .map_err(|_| JwtError::InvalidToken("Invalid header JSON".to_string()))
+}
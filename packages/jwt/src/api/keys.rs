@@ -3,6 +3,7 @@
 //! Contains utilities for key validation, conversion, and management for JWT operations.
 
 use crate::error::JwtError;
+use crate::types::EdDsaCurve;
 
 /// Validate HMAC secret key length for security
 pub(crate) fn validate_hmac_key(secret: &[u8], algorithm: &str) -> Result<(), JwtError> {
@@ -87,16 +88,103 @@ pub(crate) fn validate_ec_public_key(key_der: &[u8], algorithm: &str) -> Result<
     Ok(())
 }
 
+/// Validate `EdDSA` private key format (PKCS#8 DER, Ed25519 or Ed448)
+pub(crate) fn validate_okp_private_key(key_der: &[u8]) -> Result<(), JwtError> {
+    let (curve, seed) = crate::api::algorithms::eddsa::decode_okp_private_key(key_der)?;
+    crate::api::algorithms::eddsa::okp_public_from_seed(curve, seed)
+        .map_err(|e| JwtError::invalid_key(&format!("Invalid {} private key: {e}", curve.crv())))?;
+
+    Ok(())
+}
+
+/// Validate `EdDSA` public key format (SPKI DER, Ed25519 or Ed448)
+pub(crate) fn validate_okp_public_key(key_der: &[u8]) -> Result<(), JwtError> {
+    let (curve, point) = crate::api::algorithms::eddsa::decode_okp_public_key(key_der)?;
+
+    let valid = match curve {
+        EdDsaCurve::Ed25519 => <[u8; 32]>::try_from(point)
+            .ok()
+            .is_some_and(|bytes| ed25519_dalek::VerifyingKey::from_bytes(&bytes).is_ok()),
+        EdDsaCurve::Ed448 => crate::api::algorithms::eddsa::ed448_verifying_key(point).is_ok(),
+    };
+
+    if !valid {
+        return Err(JwtError::invalid_key(&format!(
+            "Invalid {} public key",
+            curve.crv()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate post-quantum private key length for a PQ or composite `alg`
+pub(crate) fn validate_pq_private_key(key: &[u8], algorithm: &str) -> Result<(), JwtError> {
+    use crate::api::algorithms::pq;
+
+    let expected = match pq::composite_algorithm(algorithm) {
+        Some(composite) => composite.secret_key_size(),
+        None => pq::pq_signature_algorithm(algorithm)
+            .ok_or_else(|| JwtError::unsupported_algorithm(algorithm))?
+            .secret_key_size(),
+    };
+    if key.len() != expected {
+        return Err(JwtError::invalid_key(&format!(
+            "{algorithm} private key must be {expected} bytes, got {}",
+            key.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate post-quantum public key length for a PQ or composite `alg`
+pub(crate) fn validate_pq_public_key(key: &[u8], algorithm: &str) -> Result<(), JwtError> {
+    use crate::api::algorithms::pq;
+
+    let expected = match pq::composite_algorithm(algorithm) {
+        Some(composite) => composite.public_key_size(),
+        None => pq::pq_signature_algorithm(algorithm)
+            .ok_or_else(|| JwtError::unsupported_algorithm(algorithm))?
+            .public_key_size(),
+    };
+    if key.len() != expected {
+        return Err(JwtError::invalid_key(&format!(
+            "{algorithm} public key must be {expected} bytes, got {}",
+            key.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Convert a PEM-armoured key to DER, passing DER input through unchanged
+///
+/// # Errors
+/// Returns `JwtError` if the input looks like PEM but cannot be decoded
+pub fn key_to_der(key: &[u8]) -> Result<Vec<u8>, JwtError> {
+    if key.starts_with(b"-----BEGIN") {
+        let (_label, der) = pem_rfc7468::decode_vec(key)
+            .map_err(|e| JwtError::invalid_key(&format!("Invalid PEM key: {e}")))?;
+        Ok(der)
+    } else {
+        Ok(key.to_vec())
+    }
+}
+
 /// Get recommended key size for algorithm
 ///
 /// # Errors
 /// Returns `JwtError` if the algorithm is not supported
 pub fn get_recommended_key_size(algorithm: &str) -> Result<usize, JwtError> {
     match algorithm {
-        "HS256" | "ES256" => Ok(32),            // 256 bits / P-256 curve
-        "HS384" | "ES384" => Ok(48),            // 384 bits / P-384 curve
-        "HS512" => Ok(64),                      // 512 bits
-        "RS256" | "RS384" | "RS512" => Ok(256), // 2048 bits RSA minimum
-        _ => Err(JwtError::unsupported_algorithm(algorithm)),
+        "HS256" | "ES256" | "EdDSA" => Ok(32), // 256 bits / P-256 curve / Ed25519 seed
+        "HS384" | "ES384" => Ok(48),           // 384 bits / P-384 curve
+        "HS512" => Ok(64),                     // 512 bits
+        "RS256" | "RS384" | "RS512" | "PS256" | "PS384" | "PS512" => Ok(256), // 2048 bits RSA minimum
+        // Post-quantum keys have a fixed size per parameter set
+        alg => crate::api::algorithms::pq::pq_signature_algorithm(alg)
+            .map(|pq_algorithm| pq_algorithm.secret_key_size())
+            .ok_or_else(|| JwtError::unsupported_algorithm(alg)),
     }
 }
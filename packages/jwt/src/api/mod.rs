@@ -9,13 +9,20 @@ pub mod algorithms;
 pub mod builder;
 pub mod builders;
 pub mod claims;
+pub mod jwe;
+pub mod jwk;
 pub mod keys;
 pub mod operations;
 pub mod rotator_builder;
 pub mod validation;
 
 // Re-export main builder types - use builder.rs for ChunkHandler implementation
-pub use algorithm_builders::{HsJwtBuilder, RsJwtBuilder};
+pub use algorithm_builders::{
+    EdDsaJwtBuilder, HsJwtBuilder, PqJwtBuilder, PsJwtBuilder, RsJwtBuilder,
+};
+pub use algorithms::PQ_ALGORITHMS;
+pub use jwe::{JWE_ALGORITHMS, JweBuilder, JweHeader};
+pub use jwk::{Jwk, Jwks, JwksCache, JwksSource, JwksVerifier};
 pub use builder::{JwtBuilder, JwtMasterBuilder};
 pub use builders::Jwt;
 pub use validation::{AsyncJwtResult, AsyncJwtResultWithError};
@@ -4,9 +4,11 @@
 //! with zero-allocation patterns and elegant ergonomic APIs.
 
 use super::builders::{JwtBuilder, JwtBuilderWithChunkHandler, JwtBuilderWithResultHandler};
+use super::claims::Claims;
 use super::validation::AsyncJwtResult;
-use crate::error::JwtResult;
+use crate::error::{JwtError, JwtResult};
 use serde::Serialize;
+use serde::de::DeserializeOwned;
 use tokio::sync::oneshot;
 
 impl JwtBuilder {
@@ -16,13 +18,21 @@ impl JwtBuilder {
     #[must_use]
     pub fn sign<C: Serialize + Send + 'static>(self, claims: C) -> AsyncJwtResult<String> {
         let algorithm = self.get_algorithm();
+        let key_id = self.key_id;
         let secret = self.secret;
         let private_key = self.private_key;
 
         let (tx, rx) = oneshot::channel();
 
         tokio::spawn(async move {
-            let result = super::algorithms::sign_jwt(algorithm, claims, secret, private_key).await;
+            let result = super::algorithms::sign_jwt_with_kid(
+                algorithm,
+                key_id,
+                claims,
+                secret,
+                private_key,
+            )
+            .await;
             let _ = tx.send(result);
         });
 
@@ -31,17 +41,93 @@ impl JwtBuilder {
 
     /// Verify JWT without handler - returns `AsyncJwtResult` for Value
     /// Zero-allocation async operation with blazing-fast performance
+    ///
+    /// The token's `alg` header must match the algorithm set with
+    /// `with_algorithm`, which defaults to `HS256` as for signing.
     #[inline]
     #[must_use]
     pub fn verify<S: AsRef<str>>(self, token: S) -> AsyncJwtResult<serde_json::Value> {
         let token = token.as_ref().to_string();
+        let algorithm = self.get_algorithm();
         let secret = self.secret;
         let public_key = self.public_key;
+        let validator = self.validator;
 
         let (tx, rx) = oneshot::channel();
 
         tokio::spawn(async move {
-            let result = super::algorithms::verify_jwt(token, secret, public_key).await;
+            let result = super::algorithms::verify_jwt_validated(
+                &[algorithm.as_str()],
+                token,
+                secret,
+                public_key,
+                validator.as_deref(),
+            )
+            .await;
+            let _ = tx.send(result);
+        });
+
+        AsyncJwtResult::new(rx)
+    }
+
+    /// Verify JWT and deserialize its claims into [`Claims<T>`]
+    ///
+    /// Registered claims land in the [`Claims`] fields; every other claim is
+    /// deserialized into `T`, typically a struct of your custom claims (use
+    /// `#[serde(flatten)]` inside it to keep unknown extras). As with
+    /// [`verify`](Self::verify), the algorithm is pinned to the one set with
+    /// `with_algorithm`, defaulting to `HS256`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(token: &str) -> cryypt_jwt::JwtResult<()> {
+    /// use cryypt_jwt::{Jwt, claims::ClaimsValidator};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Session {
+    ///     role: String,
+    ///     #[serde(flatten)]
+    ///     other: std::collections::HashMap<String, serde_json::Value>,
+    /// }
+    ///
+    /// let claims = Jwt::builder()
+    ///     .with_algorithm("HS256")
+    ///     .with_secret(b"secret")
+    ///     .with_validator(ClaimsValidator::new().with_audience("api").with_leeway(30))
+    ///     .verify_as::<Session>(token)
+    ///     .await?;
+    /// println!("{} is {}", claims.sub, claims.extra.role);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn verify_as<T>(self, token: impl AsRef<str>) -> AsyncJwtResult<Claims<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let token = token.as_ref().to_string();
+        let algorithm = self.get_algorithm();
+        let secret = self.secret;
+        let public_key = self.public_key;
+        let validator = self.validator;
+
+        let (tx, rx) = oneshot::channel();
+
+        tokio::spawn(async move {
+            let result = super::algorithms::verify_jwt_validated(
+                &[algorithm.as_str()],
+                token,
+                secret,
+                public_key,
+                validator.as_deref(),
+            )
+            .await
+            .and_then(|claims| {
+                serde_json::from_value(claims).map_err(|e| JwtError::InvalidClaims(e.to_string()))
+            });
             let _ = tx.send(result);
         });
 
@@ -71,7 +157,9 @@ where
     #[must_use]
     pub async fn verify<S: AsRef<str>>(self, token: S) -> Vec<u8> {
         let token = token.as_ref().to_string();
-        let result = super::algorithms::verify_jwt(token, self.secret, self.public_key).await;
+        let algorithm = self.algorithm.as_deref().unwrap_or("HS256");
+        let result =
+            super::algorithms::verify_jwt(algorithm, token, self.secret, self.public_key).await;
 
         // Convert serde_json::Value result to Vec<u8> and apply handler
         let converted_result = result.map(|v| v.to_string().into_bytes());
@@ -135,13 +223,14 @@ where
         token: &S,
     ) -> impl futures::Stream<Item = Vec<u8>> + Send {
         let token = token.as_ref().to_string();
+        let algorithm = self.algorithm.unwrap_or_else(|| "HS256".to_string());
         let secret = self.secret;
         let public_key = self.public_key;
         let handler = self.chunk_handler;
 
         futures::stream::unfold(
-            (token, secret, public_key, handler, false),
-            move |(token, secret, public_key, handler, done)| async move {
+            (algorithm, token, secret, public_key, handler, false),
+            move |(algorithm, token, secret, public_key, handler, done)| async move {
                 if done {
                     return None;
                 }
@@ -152,13 +241,21 @@ where
                 let public_key_clone = public_key.clone();
 
                 // Verify the JWT with blazing-fast performance
-                let result = super::algorithms::verify_jwt(token, secret, public_key).await;
+                let result =
+                    super::algorithms::verify_jwt(&algorithm, token, secret, public_key).await;
                 let converted_result = result.map(|v| v.to_string().into_bytes());
                 let processed_chunk = handler(converted_result);
 
                 Some((
                     processed_chunk,
-                    (token_clone, secret_clone, public_key_clone, handler, true),
+                    (
+                        algorithm,
+                        token_clone,
+                        secret_clone,
+                        public_key_clone,
+                        handler,
+                        true,
+                    ),
                 ))
             },
         )
@@ -106,6 +106,16 @@ impl JwtRotator {
         self.current_key.as_ref()
     }
 
+    /// Get a key by id, including the current signing key
+    #[must_use]
+    pub fn get_key(&self, key_id: &str) -> Option<&[u8]> {
+        self.current_key
+            .as_ref()
+            .filter(|(current_id, _)| current_id == key_id)
+            .map(|(_, key)| key.as_slice())
+            .or_else(|| self.keys.get(key_id).map(Vec::as_slice))
+    }
+
     /// List all available keys  
     #[must_use]
     pub fn list_keys(&self) -> Vec<&String> {
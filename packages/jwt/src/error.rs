@@ -42,6 +42,8 @@ pub enum JwtError {
     TaskFailed,
     /// Invalid format (alias for `InvalidToken`)
     InvalidFormat,
+    /// Token has been revoked
+    Revoked(String),
 }
 
 impl fmt::Display for JwtError {
@@ -64,6 +66,7 @@ impl fmt::Display for JwtError {
             JwtError::Crypto => write!(f, "Cryptographic operation failed"),
             JwtError::TaskFailed => write!(f, "Background task failed"),
             JwtError::InvalidFormat => write!(f, "Invalid format"),
+            JwtError::Revoked(reason) => write!(f, "Token has been revoked: {reason}"),
         }
     }
 }
@@ -154,4 +157,11 @@ impl JwtError {
     pub fn invalid_claims(msg: &str) -> Self {
         JwtError::InvalidClaims(msg.to_string())
     }
+
+    /// Create a token revoked error
+    #[inline]
+    #[must_use]
+    pub fn revoked(reason: &str) -> Self {
+        JwtError::Revoked(reason.to_string())
+    }
 }
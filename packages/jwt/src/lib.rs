@@ -1,7 +1,12 @@
 //! JSON Web Token (JWT) implementation following README.md patterns
 //!
 //! This module provides JWT functionality with:
-//! - HS256 and ES256 algorithms  
+//! - HS*, RS*, PS*, ES* and EdDSA (Ed25519/Ed448) algorithms
+//! - Post-quantum ML-DSA and SLH-DSA algorithms, plus composite ML-DSA + ES256
+//! - JWK/JWKS export and import, RFC 7638 thumbprints and `kid`-driven verification
+//! - JWE encrypted tokens (RSA-OAEP, ECDH-ES, AES key wrap, direct and ML-KEM)
+//! - Token revocation by `jti`, digest, subject or issue time, with pluggable stores
+//! - Refresh token rotation with token families and reuse detection
 //! - Key rotation support
 //! - Standard claims handling
 //! - True async with channels using fast crypto operations
@@ -12,15 +17,27 @@ pub(crate) mod algorithms;
 pub mod api;
 pub(crate) mod crypto;
 mod error;
+pub mod refresh;
+pub mod revocation;
 mod rotation;
 mod types;
 
 // Public re-exports following README.md patterns
 pub use api::{
-    AsyncJwtResult, AsyncJwtResultWithError, JwtBuilder, JwtMasterBuilder, get_recommended_key_size,
+    AsyncJwtResult, AsyncJwtResultWithError, JWE_ALGORITHMS, JweBuilder, JweHeader, Jwk, Jwks,
+    JwksCache, JwksSource, JwksVerifier, JwtBuilder, JwtMasterBuilder, PQ_ALGORITHMS,
+    get_recommended_key_size,
 };
-pub use api::{builder, builders, claims, keys, operations, rotator_builder, validation};
+pub use api::{builder, builders, claims, jwe, jwk, keys, operations, rotator_builder, validation};
 pub use error::*;
+pub use refresh::{
+    MAX_USED_TOKENS, MemoryRefreshTokenStore, RefreshFamily, RefreshTokenManager,
+    RefreshTokenStore, TokenPair,
+};
+pub use revocation::{
+    FileRevocationStore, IssuedBefore, MemoryRevocationStore, Revocation, RevocationKey,
+    RevocationRecord, RevocationStore, RevokedToken,
+};
 pub use types::*;
 
 // Re-export common macros and handlers from cryypt_common
@@ -0,0 +1,73 @@
+//! In-memory refresh token store
+
+use super::store::{
+    RefreshFamily, RefreshTokenStore, RotationOutcome, RotationRequest, apply_rotation,
+};
+use crate::error::JwtResult;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+
+/// In-memory refresh token store
+///
+/// Rotation holds the family's shard lock, so concurrent use of one refresh
+/// token yields exactly one rotation.
+#[derive(Debug, Default)]
+pub struct MemoryRefreshTokenStore {
+    families: DashMap<String, RefreshFamily>,
+}
+
+impl MemoryRefreshTokenStore {
+    /// Create an empty store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored families, including revoked ones not yet purged
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.families.len()
+    }
+
+    /// Whether no families are stored
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.families.is_empty()
+    }
+}
+
+impl RefreshTokenStore for MemoryRefreshTokenStore {
+    async fn create(&self, family: RefreshFamily) -> JwtResult<()> {
+        self.families.insert(family.id.clone(), family);
+        Ok(())
+    }
+
+    async fn family(&self, id: &str) -> JwtResult<Option<RefreshFamily>> {
+        Ok(self.families.get(id).map(|entry| entry.value().clone()))
+    }
+
+    async fn rotate(
+        &self,
+        id: &str,
+        request: RotationRequest,
+        now: DateTime<Utc>,
+    ) -> JwtResult<RotationOutcome> {
+        Ok(match self.families.get_mut(id) {
+            Some(mut family) => apply_rotation(&mut family, request, now),
+            None => RotationOutcome::Unknown,
+        })
+    }
+
+    async fn revoke(&self, id: &str, reason: &str) -> JwtResult<Option<RefreshFamily>> {
+        Ok(self.families.get_mut(id).map(|mut family| {
+            family.revoked.get_or_insert_with(|| reason.to_string());
+            family.clone()
+        }))
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> JwtResult<usize> {
+        let before = self.families.len();
+        self.families.retain(|_, family| family.expires_at > now);
+        Ok(before.saturating_sub(self.families.len()))
+    }
+}
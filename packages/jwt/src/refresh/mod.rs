@@ -0,0 +1,488 @@
+//! Refresh token rotation with reuse detection
+//!
+//! Each login starts a token family. Every refresh rotates the family's
+//! refresh token and issues a new access token; the old refresh token can
+//! never be used again. Presenting an already-used refresh token means it was
+//! stolen or replayed, so the whole family is revoked - including, when a
+//! [`Revocation`] is attached, every access token it issued that has not
+//! expired yet.
+//!
+//! Refresh tokens are opaque (`<family>.<secret>`) by default, or HS256 JWTs
+//! signed with the current key of a [`JwtRotator`].
+
+mod memory;
+mod store;
+
+pub use memory::MemoryRefreshTokenStore;
+pub use store::{
+    IssuedAccessToken, MAX_USED_TOKENS, REUSE_DETECTED, RefreshFamily, RefreshTokenStore,
+    RotationOutcome, RotationRequest, apply_rotation,
+};
+
+use crate::api::algorithms::{sign_jwt_with_kid, verify_jwt_for};
+use crate::api::builders::JwtBuilder;
+use crate::api::claims::ClaimsBuilder;
+use crate::error::{JwtError, JwtResult};
+use crate::revocation::{MemoryRevocationStore, Revocation, RevocationStore};
+use crate::rotation::JwtRotator;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Claim carrying the family id in access and JWT refresh tokens
+pub const FAMILY_CLAIM: &str = "fid";
+
+/// Claim marking a JWT as a refresh token
+const TOKEN_USE_CLAIM: &str = "token_use";
+
+/// An access token and the refresh token that replaces it
+#[derive(Debug, Clone)]
+pub struct TokenPair {
+    /// Signed access token
+    pub access_token: String,
+    /// Refresh token for the next rotation
+    pub refresh_token: String,
+    /// Family both tokens belong to
+    pub family_id: String,
+    /// When the access token expires
+    pub access_expires_at: DateTime<Utc>,
+    /// When the refresh token expires
+    pub refresh_expires_at: DateTime<Utc>,
+}
+
+/// Issues and rotates refresh tokens with reuse detection
+///
+/// # Example
+///
+/// ```no_run
+/// use cryypt_jwt::{Jwt, RefreshTokenManager, Revocation, claims::ClaimsBuilder};
+///
+/// # async fn example() -> cryypt_jwt::JwtResult<()> {
+/// let signer = Jwt::builder().with_algorithm("HS256").with_secret(b"access-secret");
+/// let manager = RefreshTokenManager::new(signer).with_revocation(Revocation::new());
+///
+/// let pair = manager
+///     .issue("user123", ClaimsBuilder::new().with_issuer("auth.example.com"))
+///     .await?;
+/// let next = manager.refresh(&pair.refresh_token).await?;
+///
+/// // Replaying the first refresh token revokes the whole family
+/// assert!(manager.refresh(&pair.refresh_token).await.is_err());
+/// assert!(manager.refresh(&next.refresh_token).await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub struct RefreshTokenManager<
+    S: RefreshTokenStore = MemoryRefreshTokenStore,
+    R: RevocationStore = MemoryRevocationStore,
+> {
+    store: Arc<S>,
+    revocation: Option<Revocation<R>>,
+    access_signer: JwtBuilder,
+    rotator: Option<Arc<JwtRotator>>,
+    access_ttl: Duration,
+    refresh_ttl: Duration,
+}
+
+impl<S: RefreshTokenStore, R: RevocationStore> Clone for RefreshTokenManager<S, R> {
+    fn clone(&self) -> Self {
+        Self {
+            store: Arc::clone(&self.store),
+            revocation: self.revocation.clone(),
+            access_signer: self.access_signer.clone(),
+            rotator: self.rotator.clone(),
+            access_ttl: self.access_ttl,
+            refresh_ttl: self.refresh_ttl,
+        }
+    }
+}
+
+impl RefreshTokenManager {
+    /// Manager with an in-memory store, signing access tokens with `access_signer`
+    ///
+    /// Access tokens live 15 minutes and refresh tokens 30 days by default.
+    #[must_use]
+    pub fn new(access_signer: JwtBuilder) -> Self {
+        Self {
+            store: Arc::new(MemoryRefreshTokenStore::new()),
+            revocation: None,
+            access_signer,
+            rotator: None,
+            access_ttl: Duration::minutes(15),
+            refresh_ttl: Duration::days(30),
+        }
+    }
+}
+
+impl<S: RefreshTokenStore, R: RevocationStore> RefreshTokenManager<S, R> {
+    /// Keep families in `store`
+    #[must_use]
+    pub fn with_store<S2: RefreshTokenStore>(self, store: S2) -> RefreshTokenManager<S2, R> {
+        RefreshTokenManager {
+            store: Arc::new(store),
+            revocation: self.revocation,
+            access_signer: self.access_signer,
+            rotator: self.rotator,
+            access_ttl: self.access_ttl,
+            refresh_ttl: self.refresh_ttl,
+        }
+    }
+
+    /// Revoke a family's live access tokens in `revocation` when it is revoked
+    #[must_use]
+    pub fn with_revocation<R2: RevocationStore>(
+        self,
+        revocation: Revocation<R2>,
+    ) -> RefreshTokenManager<S, R2> {
+        RefreshTokenManager {
+            store: self.store,
+            revocation: Some(revocation),
+            access_signer: self.access_signer,
+            rotator: self.rotator,
+            access_ttl: self.access_ttl,
+            refresh_ttl: self.refresh_ttl,
+        }
+    }
+
+    /// Issue refresh tokens as HS256 JWTs signed with the rotator's current key
+    ///
+    /// Tokens signed with any key still in the rotator remain refreshable.
+    #[must_use]
+    pub fn with_rotator(mut self, rotator: JwtRotator) -> Self {
+        self.rotator = Some(Arc::new(rotator));
+        self
+    }
+
+    /// Set the access token lifetime
+    #[must_use]
+    pub fn with_access_ttl(mut self, ttl: Duration) -> Self {
+        self.access_ttl = ttl;
+        self
+    }
+
+    /// Set the refresh token lifetime, renewed on every rotation
+    #[must_use]
+    pub fn with_refresh_ttl(mut self, ttl: Duration) -> Self {
+        self.refresh_ttl = ttl;
+        self
+    }
+
+    /// The underlying store
+    #[must_use]
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// The attached revocation, if any
+    #[must_use]
+    pub fn revocation(&self) -> Option<&Revocation<R>> {
+        self.revocation.as_ref()
+    }
+
+    /// Start a new family for `subject` and issue its first token pair
+    ///
+    /// Claims set on `claims` are copied into every access token of the
+    /// family; `sub`, `iat`, `exp`, `jti` and `fid` are always set by the manager.
+    ///
+    /// # Errors
+    /// Returns any signing or store error
+    pub async fn issue(&self, subject: &str, claims: ClaimsBuilder) -> JwtResult<TokenPair> {
+        let now = Utc::now();
+        let mut template = claims.into_map();
+        for reserved in ["sub", "iat", "exp", "jti", FAMILY_CLAIM] {
+            template.remove(reserved);
+        }
+
+        let family_id = random_id();
+        let refresh_expires_at = now + self.refresh_ttl;
+        let (refresh_token, token_id) = self
+            .refresh_token(&family_id, subject, now, refresh_expires_at)
+            .await?;
+        let access = self.next_access_token(now);
+        let access_token = self
+            .sign_access_token(&family_id, subject, &template, now, &access)
+            .await?;
+
+        self.store
+            .create(RefreshFamily {
+                id: family_id.clone(),
+                subject: subject.to_string(),
+                claims: template,
+                current: digest(&token_id),
+                used: Vec::new(),
+                generation: 0,
+                access_tokens: vec![access.clone()],
+                created_at: now,
+                expires_at: refresh_expires_at,
+                revoked: None,
+            })
+            .await?;
+
+        Ok(TokenPair {
+            access_token,
+            refresh_token,
+            family_id,
+            access_expires_at: access.expires_at,
+            refresh_expires_at,
+        })
+    }
+
+    /// Rotate `refresh_token`, returning a new token pair for the same family
+    ///
+    /// The access token is signed before the rotation is stored, so a signing
+    /// failure leaves `refresh_token` usable for a retry.
+    ///
+    /// # Errors
+    /// Returns `JwtError::Revoked` if the family is revoked - including by this
+    /// call, when `refresh_token` was already used - `JwtError::TokenExpired`
+    /// if it expired, `JwtError::InvalidToken` if it was never issued, or any
+    /// signing or store error
+    pub async fn refresh(&self, refresh_token: &str) -> JwtResult<TokenPair> {
+        let now = Utc::now();
+        let (family_id, token_id) = self.parse_refresh_token(refresh_token).await?;
+        let Some(family) = self.store.family(&family_id).await? else {
+            return Err(JwtError::invalid_token("Unknown refresh token"));
+        };
+
+        let refresh_expires_at = now + self.refresh_ttl;
+        let (next_token, next_id) = self
+            .refresh_token(&family_id, &family.subject, now, refresh_expires_at)
+            .await?;
+        // The presented token is spent once the rotation commits, so nothing may fail after it
+        let access = self.next_access_token(now);
+        let access_token = self
+            .sign_access_token(&family.id, &family.subject, &family.claims, now, &access)
+            .await?;
+
+        let request = RotationRequest {
+            presented: digest(&token_id),
+            next: digest(&next_id),
+            expires_at: refresh_expires_at,
+            access_token: access.clone(),
+        };
+        match self.store.rotate(&family_id, request, now).await? {
+            RotationOutcome::Rotated(_) => Ok(TokenPair {
+                access_token,
+                refresh_token: next_token,
+                family_id,
+                access_expires_at: access.expires_at,
+                refresh_expires_at,
+            }),
+            RotationOutcome::Reused(family) => {
+                tracing::warn!(
+                    family = %family.id,
+                    subject = %family.subject,
+                    "Refresh token reuse detected; revoking token family"
+                );
+                self.revoke_access_tokens(&family, REUSE_DETECTED).await?;
+                Err(JwtError::revoked(REUSE_DETECTED))
+            }
+            RotationOutcome::Revoked(reason) => Err(JwtError::Revoked(reason)),
+            RotationOutcome::Expired => Err(JwtError::TokenExpired),
+            RotationOutcome::Unknown => Err(JwtError::invalid_token("Unknown refresh token")),
+        }
+    }
+
+    /// Revoke a family and its live access tokens, e.g. on logout
+    ///
+    /// Returns `false` if no such family exists.
+    ///
+    /// # Errors
+    /// Returns any store error
+    pub async fn revoke_family(&self, family_id: &str, reason: &str) -> JwtResult<bool> {
+        match self.store.revoke(family_id, reason).await? {
+            Some(family) => {
+                self.revoke_access_tokens(&family, reason).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Revoke the family `refresh_token` belongs to
+    ///
+    /// The token must be one the family issued: its current refresh token, or
+    /// a rotated-out one, which counts as reuse and revokes the family with
+    /// [`REUSE_DETECTED`]. Knowing the family id alone, e.g. from an access
+    /// token's `fid` claim, is not enough to log the family out.
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidToken` if the token cannot be parsed or was not
+    /// issued by the family, or any store error
+    pub async fn revoke(&self, refresh_token: &str, reason: &str) -> JwtResult<bool> {
+        let (family_id, token_id) = self.parse_refresh_token(refresh_token).await?;
+        let Some(family) = self.store.family(&family_id).await? else {
+            return Ok(false);
+        };
+
+        let presented = digest(&token_id);
+        if family.current == presented {
+            self.revoke_family(&family_id, reason).await
+        } else if family.used.contains(&presented) {
+            self.revoke_family(&family_id, REUSE_DETECTED).await
+        } else {
+            Err(JwtError::invalid_token("Unknown refresh token"))
+        }
+    }
+
+    /// Remove families whose refresh token has expired
+    ///
+    /// # Errors
+    /// Returns any store error
+    pub async fn cleanup_expired(&self) -> JwtResult<usize> {
+        self.store.purge_expired(Utc::now()).await
+    }
+
+    async fn revoke_access_tokens(&self, family: &RefreshFamily, reason: &str) -> JwtResult<()> {
+        let Some(revocation) = &self.revocation else {
+            return Ok(());
+        };
+        let now = Utc::now();
+        for token in family.access_tokens.iter().filter(|t| t.expires_at > now) {
+            revocation
+                .revoke_jti(&token.jti, reason, Some(token.expires_at))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn next_access_token(&self, now: DateTime<Utc>) -> IssuedAccessToken {
+        IssuedAccessToken {
+            jti: random_id(),
+            expires_at: now + self.access_ttl,
+        }
+    }
+
+    async fn sign_access_token(
+        &self,
+        family_id: &str,
+        subject: &str,
+        template: &serde_json::Map<String, Value>,
+        now: DateTime<Utc>,
+        access: &IssuedAccessToken,
+    ) -> JwtResult<String> {
+        let mut claims = template.clone();
+        claims.insert("sub".to_string(), json!(subject));
+        claims.insert("iat".to_string(), json!(now.timestamp()));
+        claims.insert("exp".to_string(), json!(access.expires_at.timestamp()));
+        claims.insert("jti".to_string(), json!(access.jti));
+        claims.insert(FAMILY_CLAIM.to_string(), json!(family_id));
+
+        let signer = self.access_signer.clone();
+        let algorithm = signer.get_algorithm();
+        sign_jwt_with_kid(
+            algorithm,
+            signer.key_id,
+            Value::Object(claims),
+            signer.secret,
+            signer.private_key,
+        )
+        .await
+    }
+
+    /// Create a refresh token, returning it and its token id
+    async fn refresh_token(
+        &self,
+        family_id: &str,
+        subject: &str,
+        now: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> JwtResult<(String, String)> {
+        let Some(rotator) = &self.rotator else {
+            let secret = random_secret();
+            return Ok((format!("{family_id}.{secret}"), secret));
+        };
+
+        let (kid, key) = rotator
+            .get_current_key()
+            .ok_or_else(|| JwtError::invalid_key("Rotator has no current key"))?;
+        let jti = random_id();
+        let claims = json!({
+            "sub": subject,
+            "iat": now.timestamp(),
+            "exp": expires_at.timestamp(),
+            "jti": jti,
+            FAMILY_CLAIM: family_id,
+            TOKEN_USE_CLAIM: "refresh",
+        });
+        let token = sign_jwt_with_kid(
+            "HS256".to_string(),
+            Some(kid.clone()),
+            claims,
+            Some(key.clone()),
+            None,
+        )
+        .await?;
+        Ok((token, jti))
+    }
+
+    /// Split a refresh token into its family id and token id
+    async fn parse_refresh_token(&self, token: &str) -> JwtResult<(String, String)> {
+        let Some(rotator) = &self.rotator else {
+            return token
+                .split_once('.')
+                .filter(|(family, secret)| !family.is_empty() && !secret.contains('.'))
+                .map(|(family, secret)| (family.to_string(), secret.to_string()))
+                .ok_or_else(|| JwtError::invalid_token("Malformed refresh token"));
+        };
+
+        let kid = header_kid(token)?;
+        let key = rotator
+            .get_key(&kid)
+            .ok_or_else(|| JwtError::invalid_token("Refresh token signed with an unknown key"))?;
+        let claims = verify_jwt_for(&["HS256"], token.to_string(), Some(key.to_vec()), None)
+            .await
+            .map_err(|e| match e {
+                JwtError::TokenExpired => e,
+                _ => JwtError::invalid_token("Invalid refresh token"),
+            })?;
+
+        if claims.get(TOKEN_USE_CLAIM).and_then(Value::as_str) != Some("refresh") {
+            return Err(JwtError::invalid_token("Not a refresh token"));
+        }
+        let claim = |name: &str| {
+            claims
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| JwtError::MissingClaim(name.to_string()))
+        };
+        Ok((claim(FAMILY_CLAIM)?, claim("jti")?))
+    }
+}
+
+/// Random 128-bit id, hex encoded
+fn random_id() -> String {
+    let mut id = [0u8; 16];
+    rand::rng().fill_bytes(&mut id);
+    hex::encode(id)
+}
+
+/// Random 256-bit secret, base64url encoded
+fn random_secret() -> String {
+    let mut secret = [0u8; 32];
+    rand::rng().fill_bytes(&mut secret);
+    URL_SAFE_NO_PAD.encode(secret)
+}
+
+fn digest(token_id: &str) -> String {
+    hex::encode(Sha256::digest(token_id.as_bytes()))
+}
+
+/// `kid` from the unverified header of a compact JWS
+fn header_kid(token: &str) -> JwtResult<String> {
+    let header = token
+        .split('.')
+        .next()
+        .and_then(|segment| URL_SAFE_NO_PAD.decode(segment).ok())
+        .and_then(|json| serde_json::from_slice::<Value>(&json).ok())
+        .ok_or_else(|| JwtError::invalid_token("Malformed refresh token"))?;
+    header
+        .get("kid")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| JwtError::invalid_token("Refresh token has no kid"))
+}
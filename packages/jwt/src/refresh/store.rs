@@ -0,0 +1,150 @@
+//! Refresh token families and the pluggable [`RefreshTokenStore`] trait
+
+use crate::error::JwtResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+/// Revocation reason recorded when a rotated-out refresh token is presented again
+pub const REUSE_DETECTED: &str = "refresh token reuse detected";
+
+/// Number of rotated-out refresh token digests a family remembers for reuse detection
+pub const MAX_USED_TOKENS: usize = 64;
+
+/// An access token issued alongside a refresh token, tracked for revocation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuedAccessToken {
+    /// Access token `jti`
+    pub jti: String,
+    /// Access token `exp`
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A chain of refresh tokens descending from one login
+///
+/// Token ids are stored only as SHA-256 digests.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RefreshFamily {
+    /// Family id, embedded in every refresh token of the family
+    pub id: String,
+    /// Subject the family was issued to
+    pub subject: String,
+    /// Extra claims copied into every access token of the family
+    pub claims: serde_json::Map<String, serde_json::Value>,
+    /// Digest of the only refresh token that may be used next
+    pub current: String,
+    /// Digests of refresh tokens already rotated out, oldest first
+    ///
+    /// Capped at [`MAX_USED_TOKENS`]; replaying a token older than that is
+    /// rejected as unknown instead of being detected as reuse.
+    pub used: Vec<String>,
+    /// Number of rotations so far
+    pub generation: u64,
+    /// Access tokens issued by the family that may not have expired yet
+    pub access_tokens: Vec<IssuedAccessToken>,
+    /// When the family was created
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub created_at: DateTime<Utc>,
+    /// When the current refresh token expires
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub expires_at: DateTime<Utc>,
+    /// Revocation reason, once the family is revoked
+    pub revoked: Option<String>,
+}
+
+/// A request to replace a family's current refresh token
+#[derive(Debug, Clone)]
+pub struct RotationRequest {
+    /// Digest of the refresh token being presented
+    pub presented: String,
+    /// Digest of the refresh token replacing it
+    pub next: String,
+    /// Expiry of the replacement refresh token
+    pub expires_at: DateTime<Utc>,
+    /// Access token issued with the replacement
+    pub access_token: IssuedAccessToken,
+}
+
+/// Result of [`RefreshTokenStore::rotate`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RotationOutcome {
+    /// The presented token was current and has been replaced
+    Rotated(RefreshFamily),
+    /// The presented token was already used; the family is now revoked
+    Reused(RefreshFamily),
+    /// The family was revoked earlier, with this reason
+    Revoked(String),
+    /// The presented token was current but has expired
+    Expired,
+    /// No such family, or a token the family never issued
+    Unknown,
+}
+
+/// Backing storage for [`RefreshTokenManager`](super::RefreshTokenManager)
+///
+/// [`rotate`](Self::rotate) must be atomic per family: of two concurrent
+/// rotations presenting the same token, exactly one may succeed and the other
+/// must observe reuse.
+pub trait RefreshTokenStore: Send + Sync + 'static {
+    /// Persist a new family
+    fn create(&self, family: RefreshFamily) -> impl Future<Output = JwtResult<()>> + Send;
+
+    /// Look up a family by id
+    fn family(&self, id: &str) -> impl Future<Output = JwtResult<Option<RefreshFamily>>> + Send;
+
+    /// Rotate the family's current token, revoking the family on reuse
+    ///
+    /// On success the presented digest moves to `used`, expired entries are
+    /// dropped from `access_tokens` and the new access token is recorded.
+    fn rotate(
+        &self,
+        id: &str,
+        request: RotationRequest,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = JwtResult<RotationOutcome>> + Send;
+
+    /// Mark a family revoked, returning it if it exists
+    fn revoke(
+        &self,
+        id: &str,
+        reason: &str,
+    ) -> impl Future<Output = JwtResult<Option<RefreshFamily>>> + Send;
+
+    /// Remove families whose refresh token expired at or before `now`
+    fn purge_expired(&self, now: DateTime<Utc>) -> impl Future<Output = JwtResult<usize>> + Send;
+}
+
+/// Apply `request` to `family` in place, for store implementations
+#[must_use]
+pub fn apply_rotation(
+    family: &mut RefreshFamily,
+    request: RotationRequest,
+    now: DateTime<Utc>,
+) -> RotationOutcome {
+    if let Some(reason) = &family.revoked {
+        return RotationOutcome::Revoked(reason.clone());
+    }
+
+    if family.used.contains(&request.presented) {
+        family.revoked = Some(REUSE_DETECTED.to_string());
+        return RotationOutcome::Reused(family.clone());
+    }
+
+    if family.current != request.presented {
+        return RotationOutcome::Unknown;
+    }
+    if family.expires_at <= now {
+        return RotationOutcome::Expired;
+    }
+
+    let presented = std::mem::replace(&mut family.current, request.next);
+    family.used.push(presented);
+    let excess = family.used.len().saturating_sub(MAX_USED_TOKENS);
+    family.used.drain(..excess);
+    family.generation += 1;
+    family.expires_at = request.expires_at;
+    family.access_tokens.retain(|token| token.expires_at > now);
+    family.access_tokens.push(request.access_token);
+    RotationOutcome::Rotated(family.clone())
+}
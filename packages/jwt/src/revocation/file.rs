@@ -0,0 +1,163 @@
+//! Append-only file revocation store
+
+use super::memory::MemoryRevocationStore;
+use super::store::{IssuedBefore, RevocationKey, RevocationRecord, RevocationStore, RevokedToken};
+use crate::error::{JwtError, JwtResult};
+use chrono::{DateTime, Utc};
+use std::path::{Path, PathBuf};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// Revocation store backed by an append-only JSON-lines log
+///
+/// Every insert is appended and synced before it returns; reads are served
+/// from an in-memory index rebuilt from the log on [`open`](Self::open).
+/// [`purge_expired`](RevocationStore::purge_expired) rewrites the log without
+/// expired entries.
+#[derive(Debug)]
+pub struct FileRevocationStore {
+    path: PathBuf,
+    index: MemoryRevocationStore,
+    log: Mutex<File>,
+}
+
+impl FileRevocationStore {
+    /// Open or create the log at `path` and replay it
+    ///
+    /// A truncated final line, left by a crash mid-write, is ignored and
+    /// dropped from the log.
+    ///
+    /// # Errors
+    /// Returns `JwtError::Internal` if the log cannot be read or opened, or a
+    /// line other than the last is corrupt
+    pub async fn open(path: impl AsRef<Path>) -> JwtResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let index = MemoryRevocationStore::new();
+        let mut unterminated = false;
+
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                unterminated = !contents.is_empty() && !contents.ends_with('\n');
+                let lines: Vec<&str> = contents.lines().filter(|l| !l.trim().is_empty()).collect();
+                for (number, line) in lines.iter().enumerate() {
+                    match serde_json::from_str::<RevocationRecord>(line) {
+                        Ok(record) => index.apply(record),
+                        Err(_) if number + 1 == lines.len() && unterminated => {}
+                        Err(e) => {
+                            return Err(JwtError::Internal(format!(
+                                "Corrupt revocation log line {}: {e}",
+                                number + 1
+                            )));
+                        }
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(io_error("read revocation log", &e)),
+        }
+
+        let log = open_append(&path).await?;
+        let store = Self {
+            path,
+            index,
+            log: Mutex::new(log),
+        };
+        if unterminated {
+            // Appending after a partial line would corrupt the next record
+            store.compact().await?;
+        }
+        Ok(store)
+    }
+
+    /// Path of the log file
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Rewrite the log with only the live entries
+    ///
+    /// # Errors
+    /// Returns `JwtError::Internal` if the replacement log cannot be written
+    pub async fn compact(&self) -> JwtResult<()> {
+        let mut log = self.log.lock().await;
+
+        let mut contents = String::new();
+        for record in self.index.records() {
+            contents.push_str(&encode(&record)?);
+        }
+
+        let tmp = self.path.with_extension("compact.tmp");
+        let mut file = File::create(&tmp)
+            .await
+            .map_err(|e| io_error("create compacted log", &e))?;
+        file.write_all(contents.as_bytes())
+            .await
+            .map_err(|e| io_error("write compacted log", &e))?;
+        file.sync_all()
+            .await
+            .map_err(|e| io_error("sync compacted log", &e))?;
+        tokio::fs::rename(&tmp, &self.path)
+            .await
+            .map_err(|e| io_error("replace revocation log", &e))?;
+
+        *log = open_append(&self.path).await?;
+        Ok(())
+    }
+}
+
+impl RevocationStore for FileRevocationStore {
+    async fn insert(&self, record: RevocationRecord) -> JwtResult<()> {
+        let line = encode(&record)?;
+        let mut log = self.log.lock().await;
+        log.write_all(line.as_bytes())
+            .await
+            .map_err(|e| io_error("append to revocation log", &e))?;
+        log.sync_data()
+            .await
+            .map_err(|e| io_error("sync revocation log", &e))?;
+        self.index.apply(record);
+        Ok(())
+    }
+
+    async fn token(&self, key: &RevocationKey) -> JwtResult<Option<RevokedToken>> {
+        self.index.token(key).await
+    }
+
+    async fn subject_cutoff(&self, subject: &str) -> JwtResult<Option<IssuedBefore>> {
+        self.index.subject_cutoff(subject).await
+    }
+
+    async fn global_cutoff(&self) -> JwtResult<Option<IssuedBefore>> {
+        self.index.global_cutoff().await
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> JwtResult<usize> {
+        let purged = self.index.purge(now);
+        if purged > 0 {
+            self.compact().await?;
+        }
+        Ok(purged)
+    }
+}
+
+fn encode(record: &RevocationRecord) -> JwtResult<String> {
+    let mut line =
+        serde_json::to_string(record).map_err(|e| JwtError::Serialization(e.to_string()))?;
+    line.push('\n');
+    Ok(line)
+}
+
+async fn open_append(path: &Path) -> JwtResult<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .map_err(|e| io_error("open revocation log", &e))
+}
+
+fn io_error(action: &str, error: &std::io::Error) -> JwtError {
+    JwtError::Internal(format!("Failed to {action}: {error}"))
+}
@@ -0,0 +1,113 @@
+//! In-memory revocation store
+
+use super::store::{IssuedBefore, RevocationKey, RevocationRecord, RevocationStore, RevokedToken};
+use crate::error::JwtResult;
+use arc_swap::ArcSwapOption;
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use std::sync::Arc;
+
+/// Lock-free in-memory revocation store
+///
+/// Entries are lost on restart; use
+/// [`FileRevocationStore`](super::FileRevocationStore) or an external store
+/// when revocations must survive the process.
+#[derive(Debug, Default)]
+pub struct MemoryRevocationStore {
+    tokens: DashMap<RevocationKey, RevokedToken>,
+    subjects: DashMap<String, IssuedBefore>,
+    global: ArcSwapOption<IssuedBefore>,
+}
+
+impl MemoryRevocationStore {
+    /// Create an empty store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of revoked token entries
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Whether no token entries are stored
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Apply a record without I/O
+    pub(crate) fn apply(&self, record: RevocationRecord) {
+        match record {
+            RevocationRecord::Token(token) => {
+                self.tokens.insert(token.key.clone(), token);
+            }
+            RevocationRecord::Subject { subject, cutoff } => {
+                self.subjects
+                    .entry(subject)
+                    .and_modify(|current| {
+                        if cutoff.cutoff > current.cutoff {
+                            *current = cutoff.clone();
+                        }
+                    })
+                    .or_insert(cutoff);
+            }
+            RevocationRecord::All(cutoff) => {
+                self.global.rcu(|current| match current {
+                    Some(current) if current.cutoff >= cutoff.cutoff => Some(Arc::clone(current)),
+                    _ => Some(Arc::new(cutoff.clone())),
+                });
+            }
+        }
+    }
+
+    /// Remove expired token entries without I/O
+    pub(crate) fn purge(&self, now: DateTime<Utc>) -> usize {
+        let before = self.tokens.len();
+        self.tokens
+            .retain(|_, token| token.expires_at.is_none_or(|expires_at| expires_at > now));
+        before.saturating_sub(self.tokens.len())
+    }
+
+    /// Every live entry as records, in a form that replays to the same state
+    pub(crate) fn records(&self) -> Vec<RevocationRecord> {
+        let mut records: Vec<RevocationRecord> = self
+            .tokens
+            .iter()
+            .map(|entry| RevocationRecord::Token(entry.value().clone()))
+            .collect();
+        records.extend(self.subjects.iter().map(|entry| RevocationRecord::Subject {
+            subject: entry.key().clone(),
+            cutoff: entry.value().clone(),
+        }));
+        if let Some(global) = self.global.load_full() {
+            records.push(RevocationRecord::All((*global).clone()));
+        }
+        records
+    }
+}
+
+impl RevocationStore for MemoryRevocationStore {
+    async fn insert(&self, record: RevocationRecord) -> JwtResult<()> {
+        self.apply(record);
+        Ok(())
+    }
+
+    async fn token(&self, key: &RevocationKey) -> JwtResult<Option<RevokedToken>> {
+        Ok(self.tokens.get(key).map(|entry| entry.value().clone()))
+    }
+
+    async fn subject_cutoff(&self, subject: &str) -> JwtResult<Option<IssuedBefore>> {
+        Ok(self.subjects.get(subject).map(|entry| entry.value().clone()))
+    }
+
+    async fn global_cutoff(&self) -> JwtResult<Option<IssuedBefore>> {
+        Ok(self.global.load_full().map(|cutoff| (*cutoff).clone()))
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> JwtResult<usize> {
+        Ok(self.purge(now))
+    }
+}
@@ -0,0 +1,325 @@
+//! JWT token revocation with pluggable storage
+//!
+//! Tokens are revoked individually, keyed by `jti` or - when the token has no
+//! `jti` - by a SHA-256 digest of the compact token. Whole subjects, or every
+//! token, can be revoked by an "issued before" cutoff compared against `iat`.
+//!
+//! Storage is a [`RevocationStore`]: [`MemoryRevocationStore`] for a single
+//! process, [`FileRevocationStore`] for an append-only log on disk, or any
+//! external implementation such as the vault's SurrealDB store.
+
+mod file;
+mod memory;
+mod store;
+
+pub use file::FileRevocationStore;
+pub use memory::MemoryRevocationStore;
+pub use store::{IssuedBefore, RevocationKey, RevocationRecord, RevocationStore, RevokedToken};
+
+use crate::api::algorithms::verify_jwt_for;
+use crate::api::builders::JwtBuilder;
+use crate::error::{JwtError, JwtResult};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Weak};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// Token revocation over a [`RevocationStore`]
+///
+/// Cloning is cheap; clones share the store and the cleanup task.
+///
+/// # Example
+///
+/// ```no_run
+/// use cryypt_jwt::{Jwt, revocation::Revocation};
+///
+/// # async fn example(token: String) -> cryypt_jwt::JwtResult<()> {
+/// let revocation = Revocation::new();
+/// revocation.start_cleanup(std::time::Duration::from_secs(300)).await;
+///
+/// let verifier = || Jwt::builder().with_algorithm("HS256").with_secret(b"secret");
+/// revocation.revoke(verifier(), &token, "user logged out").await?;
+///
+/// assert!(revocation.verify(verifier(), &token).await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub struct Revocation<St: RevocationStore = MemoryRevocationStore> {
+    store: Arc<St>,
+    cleanup: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl<St: RevocationStore> Clone for Revocation<St> {
+    fn clone(&self) -> Self {
+        Self {
+            store: Arc::clone(&self.store),
+            cleanup: Arc::clone(&self.cleanup),
+        }
+    }
+}
+
+impl Revocation<MemoryRevocationStore> {
+    /// Revocation backed by a fresh in-memory store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_store(MemoryRevocationStore::new())
+    }
+}
+
+impl Default for Revocation<MemoryRevocationStore> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<St: RevocationStore> Revocation<St> {
+    /// Revocation backed by `store`
+    #[must_use]
+    pub fn with_store(store: St) -> Self {
+        Self {
+            store: Arc::new(store),
+            cleanup: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The underlying store
+    #[must_use]
+    pub fn store(&self) -> &St {
+        &self.store
+    }
+
+    /// Revoke a single token until its `exp`, after verifying it with `verifier`
+    ///
+    /// The token is keyed by its `jti`, or by digest if it has none. Only a
+    /// token that verifies can be revoked: otherwise anyone could forge a
+    /// token carrying a victim's `jti`, or an `exp` that keeps the entry
+    /// forever. The verifier's algorithm is pinned.
+    ///
+    /// # Errors
+    /// Returns any verification error, or any store error
+    pub async fn revoke(
+        &self,
+        verifier: JwtBuilder,
+        token: &str,
+        reason: &str,
+    ) -> JwtResult<RevocationKey> {
+        let claims = verified_claims(verifier, token).await?;
+        let key = token_key(token, &claims);
+        let expires_at = claims
+            .get("exp")
+            .and_then(serde_json::Value::as_i64)
+            .and_then(|exp| DateTime::from_timestamp(exp, 0));
+
+        self.store
+            .insert(RevocationRecord::Token(RevokedToken {
+                key: key.clone(),
+                reason: reason.to_string(),
+                revoked_at: Utc::now(),
+                expires_at,
+            }))
+            .await?;
+        Ok(key)
+    }
+
+    /// Revoke the token with `jti`, keeping the entry until `expires_at` (or forever)
+    ///
+    /// # Errors
+    /// Returns any store error
+    pub async fn revoke_jti(
+        &self,
+        jti: &str,
+        reason: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> JwtResult<()> {
+        self.store
+            .insert(RevocationRecord::Token(RevokedToken {
+                key: RevocationKey::Jti(jti.to_string()),
+                reason: reason.to_string(),
+                revoked_at: Utc::now(),
+                expires_at,
+            }))
+            .await
+    }
+
+    /// Revoke every token already issued to `subject`
+    ///
+    /// # Errors
+    /// Returns any store error
+    pub async fn revoke_subject(&self, subject: &str, reason: &str) -> JwtResult<()> {
+        self.revoke_subject_issued_before(subject, Utc::now(), reason)
+            .await
+    }
+
+    /// Revoke every token issued to `subject` at or before `cutoff`
+    ///
+    /// # Errors
+    /// Returns any store error
+    pub async fn revoke_subject_issued_before(
+        &self,
+        subject: &str,
+        cutoff: DateTime<Utc>,
+        reason: &str,
+    ) -> JwtResult<()> {
+        self.store
+            .insert(RevocationRecord::Subject {
+                subject: subject.to_string(),
+                cutoff: issued_before(cutoff, reason),
+            })
+            .await
+    }
+
+    /// Revoke every token issued at or before `cutoff`, for any subject
+    ///
+    /// # Errors
+    /// Returns any store error
+    pub async fn revoke_all_issued_before(
+        &self,
+        cutoff: DateTime<Utc>,
+        reason: &str,
+    ) -> JwtResult<()> {
+        self.store
+            .insert(RevocationRecord::All(issued_before(cutoff, reason)))
+            .await
+    }
+
+    /// Check already-verified `claims` of `token` against every revocation
+    ///
+    /// A token without `iat` is treated as revoked whenever a cutoff applies to it.
+    ///
+    /// # Errors
+    /// Returns `JwtError::Revoked` with the recorded reason, or any store error
+    pub async fn check(&self, token: &str, claims: &serde_json::Value) -> JwtResult<()> {
+        if let Some(revoked) = self.store.token(&token_key(token, claims)).await? {
+            return Err(JwtError::Revoked(revoked.reason));
+        }
+
+        let iat = claims.get("iat").and_then(serde_json::Value::as_i64);
+        let mut cutoffs = vec![self.store.global_cutoff().await?];
+        if let Some(subject) = claims.get("sub").and_then(serde_json::Value::as_str) {
+            cutoffs.push(self.store.subject_cutoff(subject).await?);
+        }
+
+        for cutoff in cutoffs.into_iter().flatten() {
+            if iat.is_none_or(|iat| iat <= cutoff.cutoff.timestamp()) {
+                return Err(JwtError::Revoked(cutoff.reason));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `token` is revoked, without checking its signature
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidToken` if the token is malformed, or any store error
+    pub async fn is_revoked(&self, token: &str) -> JwtResult<bool> {
+        let claims = unverified_claims(token)?;
+        match self.check(token, &claims).await {
+            Ok(()) => Ok(false),
+            Err(JwtError::Revoked(_)) => Ok(true),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Revocation details for `token`, if it was revoked individually
+    ///
+    /// # Errors
+    /// Returns `JwtError::InvalidToken` if the token is malformed, or any store error
+    pub async fn get_revocation_info(&self, token: &str) -> JwtResult<Option<RevokedToken>> {
+        let claims = unverified_claims(token)?;
+        self.store.token(&token_key(token, &claims)).await
+    }
+
+    /// Verify `token` with `verifier`, then reject it if revoked
+    ///
+    /// The verifier's algorithm is pinned.
+    ///
+    /// # Errors
+    /// Returns any verification error, or `JwtError::Revoked`
+    pub async fn verify(&self, verifier: JwtBuilder, token: &str) -> JwtResult<serde_json::Value> {
+        let claims = verified_claims(verifier, token).await?;
+        self.check(token, &claims).await?;
+        Ok(claims)
+    }
+
+    /// Remove token entries whose tokens have expired
+    ///
+    /// # Errors
+    /// Returns any store error
+    pub async fn cleanup_expired(&self) -> JwtResult<usize> {
+        self.store.purge_expired(Utc::now()).await
+    }
+
+    /// Purge expired entries every `interval` in a background task
+    ///
+    /// Replaces any running cleanup task. The task stops on its own once every
+    /// clone of this `Revocation` is dropped.
+    pub async fn start_cleanup(&self, interval: std::time::Duration) {
+        let store: Weak<St> = Arc::downgrade(&self.store);
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(store) = store.upgrade() else { break };
+                if let Err(e) = store.purge_expired(Utc::now()).await {
+                    tracing::warn!("Revocation cleanup failed: {e}");
+                }
+            }
+        });
+
+        if let Some(previous) = self.cleanup.lock().await.replace(task) {
+            previous.abort();
+        }
+    }
+
+    /// Stop the background cleanup task, if running
+    pub async fn stop_cleanup(&self) {
+        if let Some(task) = self.cleanup.lock().await.take() {
+            task.abort();
+        }
+    }
+}
+
+fn issued_before(cutoff: DateTime<Utc>, reason: &str) -> IssuedBefore {
+    IssuedBefore {
+        cutoff,
+        reason: reason.to_string(),
+        revoked_at: Utc::now(),
+    }
+}
+
+/// Verify `token` with `verifier`, pinning the verifier's algorithm
+async fn verified_claims(verifier: JwtBuilder, token: &str) -> JwtResult<serde_json::Value> {
+    let algorithm = verifier.get_algorithm();
+    verify_jwt_for(
+        &[algorithm.as_str()],
+        token.to_string(),
+        verifier.secret,
+        verifier.public_key,
+    )
+    .await
+}
+
+/// Key for `token`: its `jti` if present, otherwise its digest
+fn token_key(token: &str, claims: &serde_json::Value) -> RevocationKey {
+    claims
+        .get("jti")
+        .and_then(serde_json::Value::as_str)
+        .map_or_else(
+            || RevocationKey::digest(token),
+            |jti| RevocationKey::Jti(jti.to_string()),
+        )
+}
+
+/// Decode the payload of a compact JWS without verifying it
+fn unverified_claims(token: &str) -> JwtResult<serde_json::Value> {
+    let payload = token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| JwtError::invalid_token("Invalid JWT format"))?;
+    let json = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| JwtError::invalid_token("Invalid JWT payload encoding"))?;
+    serde_json::from_slice(&json).map_err(|_| JwtError::invalid_token("Invalid JWT payload JSON"))
+}
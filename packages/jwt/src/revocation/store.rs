@@ -0,0 +1,107 @@
+//! Revocation records and the pluggable [`RevocationStore`] trait
+
+use crate::error::JwtResult;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+
+/// Key identifying a single revoked token
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum RevocationKey {
+    /// The token's `jti` claim
+    Jti(String),
+    /// Hex SHA-256 digest of the compact token, for tokens without a `jti`
+    Digest(String),
+}
+
+impl RevocationKey {
+    /// Key for `token` by SHA-256 digest
+    #[must_use]
+    pub fn digest(token: &str) -> Self {
+        Self::Digest(hex::encode(Sha256::digest(token.as_bytes())))
+    }
+
+    /// Stable string form, usable as a record id in external stores
+    #[must_use]
+    pub fn storage_id(&self) -> String {
+        match self {
+            Self::Jti(jti) => format!("jti:{jti}"),
+            Self::Digest(digest) => format!("sha256:{digest}"),
+        }
+    }
+}
+
+/// A single revoked token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevokedToken {
+    /// Token key
+    pub key: RevocationKey,
+    /// Reason for revocation
+    pub reason: String,
+    /// When the token was revoked
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub revoked_at: DateTime<Utc>,
+    /// When the token expires; the entry is purged after this. `None` keeps it forever.
+    #[serde(with = "chrono::serde::ts_seconds_option", default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Revocation of every token issued at or before `cutoff`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssuedBefore {
+    /// Tokens with `iat` at or before this instant are revoked
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub cutoff: DateTime<Utc>,
+    /// Reason for revocation
+    pub reason: String,
+    /// When the revocation was recorded
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// An entry written to a [`RevocationStore`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RevocationRecord {
+    /// One token by `jti` or digest
+    Token(RevokedToken),
+    /// Every token for `subject` issued at or before the cutoff
+    Subject {
+        /// Subject (`sub` claim)
+        subject: String,
+        /// Cutoff for the subject
+        #[serde(flatten)]
+        cutoff: IssuedBefore,
+    },
+    /// Every token issued at or before the cutoff
+    All(IssuedBefore),
+}
+
+/// Backing storage for [`Revocation`](super::Revocation)
+///
+/// Subject and global cutoffs only ever move forward: inserting an earlier
+/// cutoff than the one stored must keep the later one.
+pub trait RevocationStore: Send + Sync + 'static {
+    /// Persist a revocation record
+    fn insert(&self, record: RevocationRecord) -> impl Future<Output = JwtResult<()>> + Send;
+
+    /// Look up a revoked token by key
+    fn token(
+        &self,
+        key: &RevocationKey,
+    ) -> impl Future<Output = JwtResult<Option<RevokedToken>>> + Send;
+
+    /// Latest issued-before cutoff recorded for `subject`
+    fn subject_cutoff(
+        &self,
+        subject: &str,
+    ) -> impl Future<Output = JwtResult<Option<IssuedBefore>>> + Send;
+
+    /// Latest issued-before cutoff recorded for all tokens
+    fn global_cutoff(&self) -> impl Future<Output = JwtResult<Option<IssuedBefore>>> + Send;
+
+    /// Remove token entries that expired at or before `now`, returning how many were removed
+    fn purge_expired(&self, now: DateTime<Utc>) -> impl Future<Output = JwtResult<usize>> + Send;
+}
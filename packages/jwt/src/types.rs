@@ -10,7 +10,7 @@ pub struct JwtClaims {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sub: Option<String>, // Subject
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub aud: Option<String>, // Audience
+    pub aud: Option<Audience>, // Audience
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exp: Option<i64>, // Expiration time
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -49,10 +49,65 @@ impl Default for JwtClaims {
     }
 }
 
+/// Audience claim - a single string or a list of strings (RFC 7519 section 4.1.3)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Audience {
+    /// One audience, serialized as a string
+    Single(String),
+    /// Several audiences, serialized as an array
+    Multiple(Vec<String>),
+}
+
+impl Audience {
+    /// Whether `audience` is one of the intended audiences
+    #[must_use]
+    pub fn contains(&self, audience: &str) -> bool {
+        self.iter().any(|aud| aud == audience)
+    }
+
+    /// Iterate over the audiences
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        match self {
+            Self::Single(aud) => std::slice::from_ref(aud).iter(),
+            Self::Multiple(auds) => auds.iter(),
+        }
+        .map(String::as_str)
+    }
+
+    /// The audiences as a list
+    #[must_use]
+    pub fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::Single(aud) => vec![aud],
+            Self::Multiple(auds) => auds,
+        }
+    }
+}
+
+impl From<&str> for Audience {
+    fn from(audience: &str) -> Self {
+        Self::Single(audience.to_string())
+    }
+}
+
+impl From<String> for Audience {
+    fn from(audience: String) -> Self {
+        Self::Single(audience)
+    }
+}
+
+impl From<Vec<String>> for Audience {
+    fn from(audiences: Vec<String>) -> Self {
+        Self::Multiple(audiences)
+    }
+}
+
 /// JWT header structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwtHeader {
     pub alg: String,
+    #[serde(default)]
     pub typ: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kid: Option<String>,
@@ -82,6 +137,59 @@ pub struct Es256KeyPair {
     pub public_key: Vec<u8>,
 }
 
+/// Edwards curve used by the `EdDSA` algorithm (RFC 8037)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdDsaCurve {
+    /// Ed25519 (32-byte keys, 64-byte signatures)
+    Ed25519,
+    /// Ed448 (57-byte keys, 114-byte signatures)
+    Ed448,
+}
+
+impl EdDsaCurve {
+    /// JWK `crv` name for this curve
+    #[must_use]
+    pub fn crv(self) -> &'static str {
+        match self {
+            Self::Ed25519 => "Ed25519",
+            Self::Ed448 => "Ed448",
+        }
+    }
+
+    /// Raw private and public key size in bytes
+    #[must_use]
+    pub fn key_size(self) -> usize {
+        match self {
+            Self::Ed25519 => 32,
+            Self::Ed448 => 57,
+        }
+    }
+}
+
+/// Key pair for `EdDSA` - PKCS#8 DER private key and SPKI DER public key
+#[derive(Debug, Clone)]
+pub struct EdDsaKeyPair {
+    pub curve: EdDsaCurve,
+    pub private_key: Vec<u8>,
+    pub public_key: Vec<u8>,
+}
+
+/// Key pair for PS256/PS384/PS512 - PKCS#8 DER private key and SPKI DER public key
+#[derive(Debug, Clone)]
+pub struct RsaPssKeyPair {
+    pub private_key: Vec<u8>,
+    pub public_key: Vec<u8>,
+}
+
+/// Key pair for a post-quantum or composite `alg` - raw `cryypt_pqcrypto` keys,
+/// in the `cryypt_pqcrypto` composite encoding for composite algorithms
+#[derive(Debug, Clone)]
+pub struct PqKeyPair {
+    pub algorithm: String,
+    pub private_key: Vec<u8>,
+    pub public_key: Vec<u8>,
+}
+
 /// JWT token string wrapper
 #[derive(Debug, Clone)]
 pub struct JwtToken(pub String);
@@ -0,0 +1,160 @@
+//! Known-answer and round-trip tests for the ECDSA (ES256/ES384) JWT algorithms
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use cryypt_jwt::Jwt;
+use hex_literal::hex;
+use serde_json::json;
+
+/// PKCS#8 DER of the RFC 7515 appendix A.3 P-256 key
+const ES256_PRIVATE: [u8; 138] = hex!(
+    "308187020100301306072a8648ce3d020106082a8648ce3d030107046d306b0201010420"
+    "8e9b109e719098bf980487df1f5d77e9cb29606ebed2263b5f57c213df84f4b2a1440342"
+    "00047fcdce2770f6c45d4183cbee6fdb4b7b580733357be9ef13bacf6e3c7bd15445c7f1"
+    "44cd1bbd9b7e872cdfedb9eeb9f4b3695d6ea90b24ad8a4623288588e5ad"
+);
+
+/// SPKI DER of the RFC 7515 appendix A.3 P-256 key
+const ES256_PUBLIC: [u8; 91] = hex!(
+    "3059301306072a8648ce3d020106082a8648ce3d030107034200047fcdce2770f6c45d41"
+    "83cbee6fdb4b7b580733357be9ef13bacf6e3c7bd15445c7f144cd1bbd9b7e872cdfedb9"
+    "eeb9f4b3695d6ea90b24ad8a4623288588e5ad"
+);
+
+/// `{"sub":"es256-kat","exp":4102444800}` signed with `ES256_PRIVATE` by OpenSSL
+const ES256_TOKEN: &str = "eyJhbGciOiJFUzI1NiIsInR5cCI6IkpXVCJ9.\
+    eyJzdWIiOiJlczI1Ni1rYXQiLCJleHAiOjQxMDI0NDQ4MDB9.\
+    N70EeoBlJWj-e-dukrGkmHZHgj9A0DPNfcMnj5s9yJq02gWjFKlDKRw1TClp8tAyiqhQlqKOeVCUyJg-TFXOpw";
+
+/// PKCS#8 DER of an OpenSSL-generated P-384 key
+const ES384_PRIVATE: [u8; 185] = hex!(
+    "3081b6020100301006072a8648ce3d020106052b8104002204819e30819b020101043015"
+    "8beeb5959870b9b1bfaf36a72e2f4aaa5290d1e68d412bf772b23a8d47cec85879243e24"
+    "5831522074e449b4d00d2da16403620004f63863c643750b5d3a47a86ea6b19a09a36858"
+    "299e5c13baa93e23fa37680ac095f6be9ef9b8d0a9dc64522e2317b0aa8db550b984e997"
+    "7a79288e27ed8c3e577f8b6796d421a4dcea1fa1578fe07886380bfdc2df19a6a01503a7"
+    "c2cd4aecbd"
+);
+
+/// SPKI DER of the `ES384_PRIVATE` key
+const ES384_PUBLIC: [u8; 120] = hex!(
+    "3076301006072a8648ce3d020106052b8104002203620004f63863c643750b5d3a47a86e"
+    "a6b19a09a36858299e5c13baa93e23fa37680ac095f6be9ef9b8d0a9dc64522e2317b0aa"
+    "8db550b984e9977a79288e27ed8c3e577f8b6796d421a4dcea1fa1578fe07886380bfdc2"
+    "df19a6a01503a7c2cd4aecbd"
+);
+
+/// `{"sub":"es384-kat","exp":4102444800}` signed with `ES384_PRIVATE` by OpenSSL
+const ES384_TOKEN: &str = "eyJhbGciOiJFUzM4NCIsInR5cCI6IkpXVCJ9.\
+    eyJzdWIiOiJlczM4NC1rYXQiLCJleHAiOjQxMDI0NDQ4MDB9.\
+    8u7TDBjM0Q5B6C2I6vwxRo2gDsrTMBP5iKbd-I9CJwW5p2YI7FzSIex4u_GHR3C0T2AqzAIXPqlWc70zZU5eWGEIyJ\
+    zcKrtQaVtwpFkdstwf1DjdcBlR3rOLTniyq9J0";
+
+/// Sign `sub` with `alg` and return the token and its decoded signature
+async fn sign(alg: &str, private_key: &[u8], sub: &str) -> (String, Vec<u8>) {
+    let token = Jwt::builder()
+        .with_algorithm(alg)
+        .with_private_key(private_key)
+        .sign(json!({"sub": sub}))
+        .await
+        .expect("signing should succeed");
+    let signature = token.rsplit_once('.').expect("three parts").1;
+    let signature = URL_SAFE_NO_PAD.decode(signature).expect("signature");
+    (token, signature)
+}
+
+#[tokio::test]
+async fn test_es256_known_answer() {
+    // A token signed elsewhere verifies against the RFC 7515 public key
+    let claims = Jwt::builder()
+        .with_algorithm("ES256")
+        .with_public_key(&ES256_PUBLIC)
+        .verify(ES256_TOKEN)
+        .await
+        .expect("OpenSSL-signed ES256 token should verify");
+    assert_eq!(claims["sub"], "es256-kat");
+
+    // Our signature is the fixed-size RFC 7518 `r || s` form
+    let (token, signature) = sign("ES256", &ES256_PRIVATE, "es256-kat").await;
+    assert_eq!(signature.len(), 64);
+    Jwt::builder()
+        .with_algorithm("ES256")
+        .with_public_key(&ES256_PUBLIC)
+        .verify(&token)
+        .await
+        .expect("verification should succeed");
+}
+
+#[tokio::test]
+async fn test_es384_known_answer() {
+    let claims = Jwt::builder()
+        .with_algorithm("ES384")
+        .with_public_key(&ES384_PUBLIC)
+        .verify(ES384_TOKEN)
+        .await
+        .expect("OpenSSL-signed ES384 token should verify");
+    assert_eq!(claims["sub"], "es384-kat");
+
+    let (token, signature) = sign("ES384", &ES384_PRIVATE, "es384-kat").await;
+    assert_eq!(signature.len(), 96);
+    Jwt::builder()
+        .with_algorithm("ES384")
+        .with_public_key(&ES384_PUBLIC)
+        .verify(&token)
+        .await
+        .expect("verification should succeed");
+}
+
+#[tokio::test]
+async fn test_ecdsa_rejects_tampering_and_wrong_curve() {
+    let (token, mut signature) = sign("ES256", &ES256_PRIVATE, "user").await;
+    let signing_input = token.rsplit_once('.').expect("three parts").0;
+    signature[10] ^= 0x01;
+    let tampered = format!("{signing_input}.{}", URL_SAFE_NO_PAD.encode(&signature));
+    assert!(
+        Jwt::builder()
+            .with_algorithm("ES256")
+            .with_public_key(&ES256_PUBLIC)
+            .verify(&tampered)
+            .await
+            .is_err()
+    );
+
+    // A P-384 key cannot verify an ES256 token, nor sign one
+    assert!(
+        Jwt::builder()
+            .with_algorithm("ES256")
+            .with_public_key(&ES384_PUBLIC)
+            .verify(&token)
+            .await
+            .is_err()
+    );
+    assert!(
+        Jwt::builder()
+            .with_algorithm("ES256")
+            .with_private_key(&ES384_PRIVATE)
+            .sign(json!({"sub": "user"}))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_verify_pins_the_expected_algorithm() {
+    let (token, _) = sign("ES256", &ES256_PRIVATE, "user").await;
+
+    // The header never chooses the algorithm: a mismatch fails even with the right key
+    for verifier in [
+        Jwt::builder().with_public_key(&ES256_PUBLIC),
+        Jwt::builder()
+            .with_algorithm("ES384")
+            .with_public_key(&ES256_PUBLIC),
+    ] {
+        assert!(verifier.clone().verify(&token).await.is_err());
+        assert!(
+            verifier
+                .verify_as::<serde_json::Value>(&token)
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Round-trip tests for JWE compact serialization and nested sign-then-encrypt
+
+use cryypt_jwt::{JweBuilder, JweHeader, Jwt};
+use cryypt_pqcrypto::api::MlKemBuilder;
+use cryypt_pqcrypto::{KemAlgorithm, KemKeyPairBuilder};
+use serde_json::json;
+
+/// Encrypt `claims` with `builder` to `recipient_key`, returning the compact token
+async fn encrypt(builder: JweBuilder, recipient_key: &[u8], claims: serde_json::Value) -> String {
+    let token = builder
+        .with_recipient_key(recipient_key)
+        .with_claims(claims)
+        .on_result(|result| result.expect("encryption should succeed"))
+        .encrypt()
+        .await;
+    String::from_utf8(token).expect("token should be UTF-8")
+}
+
+/// Decrypt `token`, mapping failures to `None`
+async fn decrypt(builder: JweBuilder, key: &[u8], token: &str) -> Option<serde_json::Value> {
+    builder
+        .with_decryption_key(key)
+        .on_result(Result::ok)
+        .decrypt(token)
+        .await
+}
+
+fn header(token: &str) -> JweHeader {
+    use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+
+    let segment = token.split('.').next().expect("header segment");
+    let json = URL_SAFE_NO_PAD.decode(segment).expect("header should be base64url");
+    serde_json::from_slice(&json).expect("header JSON")
+}
+
+#[tokio::test]
+async fn test_dir_and_a256kw_round_trip() {
+    let key = [0x42u8; 32];
+
+    for builder in [JweBuilder::dir(), JweBuilder::a256kw()] {
+        let token = encrypt(builder.clone(), &key, json!({"ssn": "078-05-1120"})).await;
+        assert_eq!(token.split('.').count(), 5);
+        assert!(!token.contains("078-05-1120"));
+
+        let header = header(&token);
+        assert_eq!(header.alg, builder.algorithm());
+        assert_eq!(header.enc, "A256GCM");
+
+        let claims = decrypt(builder.clone(), &key, &token).await.expect("decrypt");
+        assert_eq!(claims["ssn"], "078-05-1120");
+
+        // Wrong key
+        assert!(decrypt(builder.clone(), &[0x43u8; 32], &token).await.is_none());
+
+        // Tampered ciphertext
+        let mut segments: Vec<String> = token.split('.').map(str::to_string).collect();
+        segments[3] = segments[3].chars().rev().collect();
+        assert!(decrypt(builder, &key, &segments.join(".")).await.is_none());
+    }
+
+    // A decryptor for one algorithm never accepts another
+    let token = encrypt(JweBuilder::a256kw(), &key, json!({"sub": "u"})).await;
+    assert!(decrypt(JweBuilder::dir(), &key, &token).await.is_none());
+}
+
+#[tokio::test]
+async fn test_rsa_oaep_256_round_trip() {
+    let keys = Jwt::ps256().generate_keys().await.expect("RSA keygen");
+    let builder = Jwt::jwe("RSA-OAEP-256").expect("RSA-OAEP-256 should be supported");
+
+    let token = encrypt(builder.clone(), &keys.public_key, json!({"sub": "rsa"})).await;
+    let claims = decrypt(builder, &keys.private_key, &token).await.expect("decrypt");
+    assert_eq!(claims["sub"], "rsa");
+}
+
+#[tokio::test]
+async fn test_ecdh_es_a256kw_round_trip() {
+    use p256::elliptic_curve::rand_core::OsRng;
+    use p256::pkcs8::{EncodePrivateKey, EncodePublicKey};
+
+    let secret = p256::SecretKey::random(&mut OsRng);
+    let private_key = secret.to_pkcs8_der().expect("PKCS#8");
+    let public_key = secret.public_key().to_public_key_der().expect("SPKI");
+
+    let token = encrypt(
+        JweBuilder::ecdh_es_a256kw(),
+        public_key.as_bytes(),
+        json!({"sub": "ecdh"}),
+    )
+    .await;
+    let epk = header(&token).epk.expect("epk header");
+    assert_eq!(epk.crv.as_deref(), Some("P-256"));
+    assert!(epk.d.is_none());
+
+    let claims = decrypt(JweBuilder::ecdh_es_a256kw(), private_key.as_bytes(), &token)
+        .await
+        .expect("decrypt");
+    assert_eq!(claims["sub"], "ecdh");
+}
+
+#[tokio::test]
+async fn test_ml_kem_round_trip() {
+    let keys = MlKemBuilder::new(KemAlgorithm::MlKem768)
+        .generate()
+        .await
+        .expect("ML-KEM keygen");
+    let public_key = keys.public_key().expect("public key");
+    let secret_key = keys.secret_key().expect("secret key");
+
+    let token = encrypt(JweBuilder::ml_kem_768(), public_key, json!({"sub": "pq"})).await;
+    assert_eq!(header(&token).alg, "MLKEM768");
+
+    let claims = decrypt(JweBuilder::ml_kem_768(), secret_key, &token)
+        .await
+        .expect("decrypt");
+    assert_eq!(claims["sub"], "pq");
+}
+
+#[tokio::test]
+async fn test_nested_sign_then_encrypt() {
+    let signing = Jwt::eddsa().generate_keys().await.expect("Ed25519 keygen");
+    let key = [0x24u8; 32];
+
+    let token = JweBuilder::a256kw()
+        .with_recipient_key(&key)
+        .with_key_id("enc-1")
+        .sign_with(
+            Jwt::builder()
+                .with_algorithm("EdDSA")
+                .with_private_key(&signing.private_key),
+        )
+        .with_claims(json!({"sub": "nested"}))
+        .on_result(|result| result.expect("encryption should succeed"))
+        .encrypt()
+        .await;
+    let token = String::from_utf8(token).expect("token should be UTF-8");
+
+    let header = header(&token);
+    assert!(header.is_nested());
+    assert_eq!(header.kid.as_deref(), Some("enc-1"));
+
+    let claims = JweBuilder::a256kw()
+        .with_decryption_key(&key)
+        .verify_with(
+            Jwt::builder()
+                .with_algorithm("EdDSA")
+                .with_public_key(&signing.public_key),
+        )
+        .on_result(|result| result.expect("decryption should succeed"))
+        .decrypt(&token)
+        .await;
+    assert_eq!(claims["sub"], "nested");
+
+    // Without a verifier the inner signature is never skipped
+    assert!(decrypt(JweBuilder::a256kw(), &key, &token).await.is_none());
+}
@@ -0,0 +1,270 @@
+//! JWK conversion, RFC 7638 thumbprint, JWKS verifier and JWKS cache tests
+
+use cryypt_jwt::{Jwk, Jwks, JwksCache, Jwt};
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+#[test]
+fn test_rfc7638_thumbprint_vector() {
+    let jwk: Jwk = serde_json::from_value(json!({
+        "kty": "RSA",
+        "n": "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+        "e": "AQAB",
+        "alg": "RS256",
+        "kid": "2011-04-29"
+    }))
+    .expect("JWK should parse");
+
+    assert_eq!(
+        jwk.thumbprint().expect("thumbprint"),
+        "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+    );
+}
+
+#[tokio::test]
+async fn test_rsa_and_okp_jwk_round_trip() {
+    let rsa = Jwt::ps256().generate_keys().await.expect("RSA keygen");
+    let private = Jwk::from_rsa_private_key(&rsa.private_key).expect("RSA private JWK");
+    let public = Jwk::from_rsa_public_key(&rsa.public_key).expect("RSA public JWK");
+
+    assert_eq!(private.to_public(), public);
+    assert_eq!(private.thumbprint().ok(), public.thumbprint().ok());
+    assert_eq!(private.private_key_bytes().expect("PKCS#8"), rsa.private_key);
+    assert_eq!(public.public_key_bytes().expect("SPKI"), rsa.public_key);
+
+    // Debug output never prints private members
+    let debug = format!("{private:?}");
+    assert!(debug.contains("[REDACTED]"));
+    for secret in [&private.d, &private.p, &private.q, &private.dp, &private.dq, &private.qi] {
+        assert!(!debug.contains(secret.as_deref().expect("private member")));
+    }
+
+    let ed = Jwt::eddsa().generate_keys().await.expect("Ed25519 keygen");
+    let okp = Jwk::from_okp_private_key(&ed.private_key).expect("OKP private JWK");
+    assert_eq!(okp.crv.as_deref(), Some("Ed25519"));
+    assert_eq!(okp.private_key_bytes().expect("PKCS#8"), ed.private_key);
+    assert_eq!(okp.public_key_bytes().expect("SPKI"), ed.public_key);
+}
+
+#[tokio::test]
+async fn test_jwks_verifier_selects_key_by_kid_and_alg() {
+    let first = [0x11u8; 32];
+    let second = [0x22u8; 32];
+    let jwks = Jwks::new()
+        .with_key(Jwk::from_hmac_secret("HS256", &first).expect("oct").with_kid("first"))
+        .with_key(Jwk::from_hmac_secret("HS256", &second).expect("oct").with_kid("second"));
+
+    let token = Jwt::builder()
+        .with_algorithm("HS256")
+        .with_key_id("second")
+        .with_secret(&second)
+        .sign(json!({"sub": "kid-user"}))
+        .await
+        .expect("signing should succeed");
+
+    let claims = Jwt::with_jwks(jwks.clone())
+        .verify(&token)
+        .await
+        .expect("verification should succeed");
+    assert_eq!(claims["sub"], "kid-user");
+
+    // Restricting the accepted algorithms rejects the token outright
+    let rejected = Jwt::with_jwks(jwks.clone())
+        .with_algorithms(&["EdDSA"])
+        .on_result(|result| result.is_err())
+        .verify(&token)
+        .await;
+    assert!(rejected);
+
+    // Symmetric keys never leave the publishable set
+    assert!(jwks.to_public().is_empty());
+}
+
+/// Serve `body` over HTTP on localhost, counting requests
+async fn serve_jwks(body: Arc<Mutex<String>>, hits: Arc<AtomicUsize>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let address = listener.local_addr().expect("address");
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            hits.fetch_add(1, Ordering::SeqCst);
+
+            let body = body.lock().await.clone();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        }
+    });
+
+    format!("http://{address}/.well-known/jwks.json")
+}
+
+#[tokio::test]
+async fn test_jwks_cache_refreshes_on_unknown_kid() {
+    let old_key = Jwt::eddsa().generate_keys().await.expect("keygen");
+    let new_key = Jwt::eddsa().generate_keys().await.expect("keygen");
+    let old_jwk = Jwk::from_okp_public_key(&old_key.public_key)
+        .expect("OKP")
+        .with_kid("old");
+    let new_jwk = Jwk::from_okp_public_key(&new_key.public_key)
+        .expect("OKP")
+        .with_kid("new");
+
+    let body = Arc::new(Mutex::new(
+        Jwks::new().with_key(old_jwk.clone()).to_json().expect("JWKS"),
+    ));
+    let hits = Arc::new(AtomicUsize::new(0));
+    let url = serve_jwks(Arc::clone(&body), Arc::clone(&hits)).await;
+
+    let cache = JwksCache::from_url(url)
+        .expect("cache")
+        .with_min_refresh_interval(Duration::ZERO);
+    assert_eq!(cache.refresh().await.expect("initial load").len(), 1);
+
+    // The issuer rotates to a new key the cache has not seen yet
+    *body.lock().await = Jwks::new()
+        .with_key(old_jwk)
+        .with_key(new_jwk)
+        .to_json()
+        .expect("JWKS");
+
+    let token = Jwt::builder()
+        .with_algorithm("EdDSA")
+        .with_key_id("new")
+        .with_private_key(&new_key.private_key)
+        .sign(json!({"sub": "rotated"}))
+        .await
+        .expect("signing should succeed");
+
+    let claims = Jwt::with_jwks_cache(cache.clone())
+        .verify(&token)
+        .await
+        .expect("unknown kid should trigger a reload");
+    assert_eq!(claims["sub"], "rotated");
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+    assert_eq!(cache.cached().expect("snapshot").len(), 2);
+}
+
+#[tokio::test]
+async fn test_jwks_cache_backs_off_after_failed_reload() {
+    let key = Jwt::eddsa().generate_keys().await.expect("keygen");
+    let jwk = Jwk::from_okp_public_key(&key.public_key)
+        .expect("OKP")
+        .with_kid("current");
+    let body = Arc::new(Mutex::new(
+        Jwks::new().with_key(jwk).to_json().expect("JWKS"),
+    ));
+    let hits = Arc::new(AtomicUsize::new(0));
+    let url = serve_jwks(Arc::clone(&body), Arc::clone(&hits)).await;
+
+    // Every snapshot is immediately stale, but failures back off for a minute
+    let cache = JwksCache::from_url(url)
+        .expect("cache")
+        .with_refresh_interval(Duration::ZERO)
+        .with_min_refresh_interval(Duration::from_secs(60));
+    assert_eq!(cache.keys().await.expect("initial load").len(), 1);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    // The key server breaks: the first stale read tries once and serves cached keys
+    *body.lock().await = "not a key set".to_string();
+    assert_eq!(cache.keys().await.expect("cached keys").len(), 1);
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+    // Later reads and unknown `kid` values wait out the backoff instead of retrying
+    for _ in 0..3 {
+        assert_eq!(cache.keys().await.expect("cached keys").len(), 1);
+    }
+    let token = Jwt::builder()
+        .with_algorithm("EdDSA")
+        .with_key_id("unknown")
+        .with_private_key(&key.private_key)
+        .sign(json!({"sub": "user"}))
+        .await
+        .expect("signing should succeed");
+    assert!(
+        Jwt::with_jwks_cache(cache.clone())
+            .verify(&token)
+            .await
+            .is_err()
+    );
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+    // An explicit refresh still goes to the source
+    assert!(cache.refresh().await.is_err());
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+}
+
+/// Serve one oversized body, declaring its length or leaving the client to read to EOF
+async fn serve_oversized(declare_length: bool) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let address = listener.local_addr().expect("address");
+
+    tokio::spawn(async move {
+        if let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let body = format!(
+                r#"{{"keys":[],"padding":"{}"}}"#,
+                "x".repeat(2 * 1024 * 1024)
+            );
+            let length = if declare_length {
+                format!("Content-Length: {}\r\n", body.len())
+            } else {
+                String::new()
+            };
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n{length}\
+                 Connection: close\r\n\r\n"
+            );
+            let _ = socket.write_all(head.as_bytes()).await;
+            let _ = socket.write_all(body.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        }
+    });
+
+    format!("http://{address}/.well-known/jwks.json")
+}
+
+#[tokio::test]
+async fn test_jwks_cache_rejects_oversized_response() {
+    for declare_length in [true, false] {
+        let cache = JwksCache::from_url(serve_oversized(declare_length).await).expect("cache");
+        let error = cache.refresh().await.expect_err("oversized JWKS");
+        assert!(error.to_string().contains("limit"), "{error}");
+        assert!(cache.cached().is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_jwks_cache_loads_from_file() {
+    let secret = [0x33u8; 32];
+    let jwks = Jwks::new().with_key(Jwk::from_hmac_secret("HS256", &secret).expect("oct"));
+    let path = std::env::temp_dir().join(format!("cryypt-jwks-{}.json", std::process::id()));
+    std::fs::write(&path, jwks.to_json().expect("JWKS")).expect("write JWKS file");
+
+    let token = Jwt::builder()
+        .with_algorithm("HS256")
+        .with_secret(&secret)
+        .sign(json!({"sub": "file-user"}))
+        .await
+        .expect("signing should succeed");
+
+    // A token without kid is accepted when exactly one key fits its alg
+    let claims = Jwt::with_jwks_cache(JwksCache::from_file(&path).expect("cache"))
+        .verify(&token)
+        .await
+        .expect("verification should succeed");
+    assert_eq!(claims["sub"], "file-user");
+
+    let _ = std::fs::remove_file(path);
+}
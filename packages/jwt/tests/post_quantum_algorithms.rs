@@ -0,0 +1,223 @@
+//! Round-trip tests for the post-quantum ML-DSA/SLH-DSA and composite JWT algorithms
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use cryypt_jwt::{Jwk, Jwt};
+use cryypt_pqcrypto::api::CompositeBuilder;
+use cryypt_pqcrypto::{
+    Backend, CompositeAlgorithm, MessageBuilder, SignatureAlgorithm, SignatureDataBuilder,
+    SignatureKeyPairBuilder, SigningOptions, VerifyBuilder,
+};
+use serde_json::json;
+
+/// Split a compact JWS into its signing input and decoded signature
+fn split_token(token: &str) -> (&str, Vec<u8>) {
+    let (signing_input, signature) = token.rsplit_once('.').expect("three parts");
+    let signature = URL_SAFE_NO_PAD.decode(signature).expect("signature");
+    (signing_input, signature)
+}
+
+#[tokio::test]
+async fn test_ml_dsa_65_sign_and_verify() {
+    let keys = Jwt::ml_dsa_65()
+        .generate_keys()
+        .await
+        .expect("ML-DSA-65 key generation should succeed");
+
+    let token = Jwt::ml_dsa_65()
+        .with_private_key(&keys.private_key)
+        .with_claims(json!({"sub": "pq-user"}))
+        .on_result(|result| result.expect("signing should succeed"))
+        .sign()
+        .await;
+    let token = String::from_utf8(token).expect("token should be UTF-8");
+
+    let header = URL_SAFE_NO_PAD
+        .decode(token.split('.').next().expect("header"))
+        .expect("header should be base64url");
+    let header: serde_json::Value = serde_json::from_slice(&header).expect("header JSON");
+    assert_eq!(header["alg"], "ML-DSA-65");
+
+    let claims = Jwt::ml_dsa_65()
+        .with_public_key(&keys.public_key)
+        .on_result(|result| result.expect("verification should succeed"))
+        .verify(&token)
+        .await;
+    assert_eq!(claims["sub"], "pq-user");
+
+    // An ML-DSA-87 verifier must not accept an ML-DSA-65 token
+    let accepted = Jwt::ml_dsa_87()
+        .with_public_key(&keys.public_key)
+        .on_result(|result| result.is_ok())
+        .verify(&token)
+        .await;
+    assert!(!accepted);
+}
+
+#[tokio::test]
+async fn test_slh_dsa_through_generic_builder() {
+    let keys = Jwt::post_quantum("SLH-DSA-SHA2-128f")
+        .expect("SLH-DSA-SHA2-128f should be supported")
+        .generate_keys()
+        .await
+        .expect("SLH-DSA key generation should succeed");
+
+    let token = Jwt::builder()
+        .with_algorithm("SLH-DSA-SHA2-128f")
+        .with_private_key(&keys.private_key)
+        .sign(json!({"sub": "hash-based"}))
+        .await
+        .expect("signing should succeed");
+
+    let claims = Jwt::builder()
+        .with_algorithm("SLH-DSA-SHA2-128f")
+        .with_public_key(&keys.public_key)
+        .verify(&token)
+        .await
+        .expect("verification should succeed");
+    assert_eq!(claims["sub"], "hash-based");
+
+    // The token is a FIPS 205 SLH-DSA signature with an empty context
+    let (signing_input, signature) = split_token(&token);
+    let valid = Backend::Native
+        .verify(
+            SignatureAlgorithm::SphincsShaSha256_128fSimple,
+            &keys.public_key,
+            signing_input.as_bytes(),
+            &signature,
+            &SigningOptions::new(),
+        )
+        .expect("native verification should run");
+    assert!(valid);
+
+    assert!(Jwt::post_quantum("SLH-DSA-SHAKE-128f").is_err());
+}
+
+#[tokio::test]
+async fn test_composite_requires_both_signatures() {
+    let keys = Jwt::ml_dsa_65_es256()
+        .generate_keys()
+        .await
+        .expect("composite key generation should succeed");
+
+    let token = Jwt::ml_dsa_65_es256()
+        .with_private_key(&keys.private_key)
+        .with_claims(json!({"sub": "hybrid"}))
+        .on_result(|result| result.expect("signing should succeed"))
+        .sign()
+        .await;
+    let token = String::from_utf8(token).expect("token should be UTF-8");
+
+    let claims = Jwt::ml_dsa_65_es256()
+        .with_public_key(&keys.public_key)
+        .on_result(|result| result.expect("verification should succeed"))
+        .verify(&token)
+        .await;
+    assert_eq!(claims["sub"], "hybrid");
+
+    // Corrupting only the trailing ECDSA component must fail verification
+    let (signing_input, mut signature) = split_token(&token);
+    let last = signature.len() - 1;
+    signature[last] ^= 0x01;
+    let tampered = format!("{signing_input}.{}", URL_SAFE_NO_PAD.encode(&signature));
+
+    let accepted = Jwt::ml_dsa_65_es256()
+        .with_public_key(&keys.public_key)
+        .on_result(|result| result.is_ok())
+        .verify(&tampered)
+        .await;
+    assert!(!accepted);
+}
+
+#[tokio::test]
+async fn test_composite_matches_pqcrypto_composite() {
+    let algorithm = CompositeAlgorithm::MlDsa65EcdsaP256;
+    let keypair = CompositeBuilder::new(algorithm)
+        .generate()
+        .await
+        .expect("composite key generation should succeed");
+    let private_key = keypair.secret_key_vec().expect("sk");
+    let public_key = keypair.public_key_vec().expect("pk");
+
+    let token = Jwt::builder()
+        .with_algorithm("ML-DSA-65-ES256")
+        .with_private_key(&private_key)
+        .sign(json!({"sub": "interop"}))
+        .await
+        .expect("signing should succeed");
+
+    // The JWS signature is an ordinary `cryypt_pqcrypto` composite signature
+    let (signing_input, signature) = split_token(&token);
+    let valid = CompositeBuilder::new(algorithm)
+        .with_public_key(public_key.clone())
+        .expect("public key")
+        .with_message(signing_input.as_bytes().to_vec())
+        .with_signature(signature)
+        .verify()
+        .await
+        .expect("composite verification should run")
+        .is_valid();
+    assert!(valid);
+
+    let keys = Jwt::ml_dsa_65_es256()
+        .generate_keys()
+        .await
+        .expect("keygen");
+    assert_eq!(keys.public_key.len(), algorithm.public_key_size());
+    assert_eq!(keys.private_key.len(), algorithm.secret_key_size());
+
+    // The draft defines no ML-DSA-44 + P-256 JOSE composite
+    assert!(Jwt::post_quantum("ML-DSA-44-ES256").is_err());
+}
+
+#[tokio::test]
+async fn test_composite_verifies_draft_vector() {
+    let vectors: serde_json::Value =
+        serde_json::from_str(include_str!("../../pqcrypto/tests/vectors/composite.json"))
+            .expect("vector file");
+    let test = vectors["testGroups"]
+        .as_array()
+        .expect("testGroups")
+        .iter()
+        .find(|group| group["parameterSet"] == "COMPSIG-MLDSA65-ECDSA-P256-SHA512")
+        .map(|group| &group["tests"][0])
+        .expect("ML-DSA-65 + P-256 vector");
+    let hex_field = |field: &str| hex::decode(test[field].as_str().expect(field)).expect("hex");
+
+    // The vector's message is a JWS signing input, so vector and token are the same bytes
+    let signing_input = String::from_utf8(hex_field("message")).expect("signing input");
+    let token = format!(
+        "{signing_input}.{}",
+        URL_SAFE_NO_PAD.encode(hex_field("signature"))
+    );
+    let claims = Jwt::ml_dsa_65_es256()
+        .with_public_key(&hex_field("pk"))
+        .on_result(|result| result.expect("verification should succeed"))
+        .verify(&token)
+        .await;
+    assert_eq!(claims["sub"], "composite-vector");
+}
+
+#[tokio::test]
+async fn test_akp_jwk_round_trip() {
+    let keys = Jwt::ml_dsa_44().generate_keys().await.expect("keygen");
+
+    let jwk = Jwk::from_pq_private_key("ML-DSA-44", &keys.private_key, &keys.public_key)
+        .expect("JWK should encode")
+        .with_kid("pq-1");
+    let json = serde_json::to_value(&jwk).expect("JWK should serialize");
+    assert_eq!(json["kty"], "AKP");
+    assert_eq!(json["alg"], "ML-DSA-44");
+    assert!(json.get("priv").is_some());
+
+    let public = jwk.to_public();
+    assert!(
+        serde_json::to_value(&public)
+            .expect("JWK")
+            .get("priv")
+            .is_none()
+    );
+
+    let parsed: Jwk = serde_json::from_value(json).expect("JWK should deserialize");
+    assert_eq!(parsed.pq_private_key().expect("priv"), keys.private_key);
+    assert_eq!(public.pq_public_key().expect("pub"), keys.public_key);
+}
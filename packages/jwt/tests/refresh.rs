@@ -0,0 +1,174 @@
+//! Refresh token rotation and reuse detection tests
+
+use chrono::{DateTime, Utc};
+use cryypt_jwt::claims::ClaimsBuilder;
+use cryypt_jwt::refresh::{RotationOutcome, RotationRequest};
+use cryypt_jwt::rotator_builder::JwtRotator;
+use cryypt_jwt::{
+    Jwt, JwtError, JwtResult, MAX_USED_TOKENS, MemoryRefreshTokenStore, RefreshFamily,
+    RefreshTokenManager, RefreshTokenStore, Revocation,
+};
+use std::sync::Arc;
+
+const ACCESS_SECRET: &[u8] = b"access-token-secret";
+
+fn manager() -> RefreshTokenManager {
+    RefreshTokenManager::new(Jwt::builder().with_algorithm("HS256").with_secret(ACCESS_SECRET))
+}
+
+fn access_verifier() -> cryypt_jwt::builders::JwtBuilder {
+    Jwt::builder().with_algorithm("HS256").with_secret(ACCESS_SECRET)
+}
+
+#[tokio::test]
+async fn test_rotation_and_reuse_revokes_family() {
+    let revocation = Revocation::new();
+    let manager = manager().with_revocation(revocation.clone());
+
+    let first = manager
+        .issue("alice", ClaimsBuilder::new().with_custom_string("role", "admin"))
+        .await
+        .expect("issue");
+    let claims = revocation
+        .verify(access_verifier(), &first.access_token)
+        .await
+        .expect("access token should verify");
+    assert_eq!(claims["sub"], "alice");
+    assert_eq!(claims["role"], "admin");
+    assert_eq!(claims["fid"], first.family_id.as_str());
+
+    let second = manager.refresh(&first.refresh_token).await.expect("rotate");
+    assert_eq!(second.family_id, first.family_id);
+    assert_ne!(second.refresh_token, first.refresh_token);
+    let claims = revocation
+        .verify(access_verifier(), &second.access_token)
+        .await
+        .expect("rotated access token should verify");
+    assert_eq!(claims["role"], "admin");
+
+    // Replaying the first refresh token is reuse: the family dies with it
+    match manager.refresh(&first.refresh_token).await {
+        Err(JwtError::Revoked(_)) => {}
+        other => panic!("expected reuse to be rejected, got {other:?}"),
+    }
+    assert!(manager.refresh(&second.refresh_token).await.is_err());
+    assert!(revocation.is_revoked(&first.access_token).await.expect("check"));
+    assert!(revocation.is_revoked(&second.access_token).await.expect("check"));
+
+    // Other families are untouched
+    let other = manager.issue("bob", ClaimsBuilder::new()).await.expect("issue");
+    assert!(manager.refresh(&other.refresh_token).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_forged_and_logged_out_tokens() {
+    let manager = manager();
+    let pair = manager.issue("carol", ClaimsBuilder::new()).await.expect("issue");
+
+    // A token the family never issued is rejected without revoking the family
+    let forged = format!("{}.not-a-real-secret", pair.family_id);
+    assert!(matches!(manager.refresh(&forged).await, Err(JwtError::InvalidToken(_))));
+
+    // Knowing the family id (the `fid` access token claim) is not enough to log it out
+    assert!(matches!(manager.revoke(&forged, "logout").await, Err(JwtError::InvalidToken(_))));
+    let pair = manager.refresh(&pair.refresh_token).await.expect("family still usable");
+
+    assert!(manager.revoke(&pair.refresh_token, "logout").await.expect("revoke"));
+    match manager.refresh(&pair.refresh_token).await {
+        Err(JwtError::Revoked(reason)) => assert_eq!(reason, "logout"),
+        other => panic!("expected logged-out family to be rejected, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_jwt_refresh_tokens_survive_key_rotation() {
+    let mut rotator = JwtRotator::new();
+    rotator.add_key("2024-01".to_string(), b"refresh-key-one".to_vec());
+    let manager = manager().with_rotator(rotator);
+
+    let pair = manager.issue("dave", ClaimsBuilder::new()).await.expect("issue");
+    assert_eq!(pair.refresh_token.split('.').count(), 3);
+
+    let next = manager.refresh(&pair.refresh_token).await.expect("rotate");
+    assert!(manager.refresh(&pair.refresh_token).await.is_err());
+    assert!(manager.refresh(&next.refresh_token).await.is_err());
+
+    // A refresh token signed by an unknown key is never accepted
+    let mut other = JwtRotator::new();
+    other.add_key("2024-01".to_string(), b"a-different-key".to_vec());
+    let foreign = manager().with_rotator(other);
+    let pair = manager.issue("erin", ClaimsBuilder::new()).await.expect("issue");
+    let result = foreign.refresh(&pair.refresh_token).await;
+    assert!(matches!(result, Err(JwtError::InvalidToken(_))));
+}
+
+#[tokio::test]
+async fn test_used_token_history_is_bounded() {
+    let manager = manager();
+    let first = manager.issue("frank", ClaimsBuilder::new()).await.expect("issue");
+
+    let mut pair = first.clone();
+    for _ in 0..MAX_USED_TOKENS {
+        pair = manager.refresh(&pair.refresh_token).await.expect("rotate");
+    }
+    let recent = pair.refresh_token.clone();
+    let pair = manager.refresh(&recent).await.expect("rotate");
+
+    // The oldest token fell out of the history: rejected, but no longer seen as reuse
+    let result = manager.refresh(&first.refresh_token).await;
+    assert!(matches!(result, Err(JwtError::InvalidToken(_))));
+
+    // Tokens still in the history keep triggering reuse detection
+    assert!(matches!(manager.refresh(&recent).await, Err(JwtError::Revoked(_))));
+    assert!(manager.refresh(&pair.refresh_token).await.is_err());
+}
+
+/// One store behind managers with different access token signers
+struct SharedStore(Arc<MemoryRefreshTokenStore>);
+
+impl RefreshTokenStore for SharedStore {
+    async fn create(&self, family: RefreshFamily) -> JwtResult<()> {
+        self.0.create(family).await
+    }
+
+    async fn family(&self, id: &str) -> JwtResult<Option<RefreshFamily>> {
+        self.0.family(id).await
+    }
+
+    async fn rotate(
+        &self,
+        id: &str,
+        request: RotationRequest,
+        now: DateTime<Utc>,
+    ) -> JwtResult<RotationOutcome> {
+        self.0.rotate(id, request, now).await
+    }
+
+    async fn revoke(&self, id: &str, reason: &str) -> JwtResult<Option<RefreshFamily>> {
+        self.0.revoke(id, reason).await
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> JwtResult<usize> {
+        self.0.purge_expired(now).await
+    }
+}
+
+#[tokio::test]
+async fn test_signing_failure_leaves_refresh_token_unspent() {
+    let store = Arc::new(MemoryRefreshTokenStore::new());
+    let manager = manager().with_store(SharedStore(Arc::clone(&store)));
+    // HS256 without a secret fails to sign every access token
+    let failing = RefreshTokenManager::new(Jwt::builder().with_algorithm("HS256"))
+        .with_store(SharedStore(Arc::clone(&store)));
+
+    let pair = manager.issue("dave", ClaimsBuilder::new()).await.expect("issue");
+    assert!(failing.refresh(&pair.refresh_token).await.is_err());
+
+    // The retry is a first use, not a replay
+    let next = manager
+        .refresh(&pair.refresh_token)
+        .await
+        .expect("token should survive the failed refresh");
+    assert_eq!(next.family_id, pair.family_id);
+    assert!(manager.refresh(&next.refresh_token).await.is_ok());
+}
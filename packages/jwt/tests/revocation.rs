@@ -0,0 +1,153 @@
+//! Token revocation tests for the in-memory and append-only file stores
+
+use chrono::{DateTime, Duration, Utc};
+use cryypt_jwt::{FileRevocationStore, Jwt, JwtError, Revocation, RevocationKey};
+use serde_json::json;
+use tokio::io::AsyncWriteExt;
+
+const SECRET: &[u8] = b"revocation-test-secret";
+
+async fn sign(claims: serde_json::Value) -> String {
+    Jwt::builder()
+        .with_algorithm("HS256")
+        .with_secret(SECRET)
+        .sign(claims)
+        .await
+        .expect("signing should succeed")
+}
+
+fn verifier() -> cryypt_jwt::builders::JwtBuilder {
+    Jwt::builder().with_algorithm("HS256").with_secret(SECRET)
+}
+
+#[tokio::test]
+async fn test_revoke_by_jti_and_digest() {
+    let now = Utc::now().timestamp();
+    let with_jti = sign(json!({"sub": "u1", "jti": "abc", "iat": now, "exp": now + 60})).await;
+    let without_jti = sign(json!({"sub": "u1", "iat": now, "exp": now + 60})).await;
+
+    let revocation = Revocation::new();
+    assert!(revocation.verify(verifier(), &with_jti).await.is_ok());
+
+    let key = revocation.revoke(verifier(), &with_jti, "logout").await.expect("revoke");
+    assert_eq!(key, RevocationKey::Jti("abc".to_string()));
+    let key = revocation.revoke(verifier(), &without_jti, "leaked").await.expect("revoke");
+    assert_eq!(key, RevocationKey::digest(&without_jti));
+
+    match revocation.verify(verifier(), &with_jti).await {
+        Err(JwtError::Revoked(reason)) => assert_eq!(reason, "logout"),
+        other => panic!("expected revoked token, got {other:?}"),
+    }
+    assert!(revocation.is_revoked(&without_jti).await.expect("check"));
+
+    let info = revocation.get_revocation_info(&with_jti).await.expect("lookup");
+    assert_eq!(info.expect("entry").expires_at.map(|t| t.timestamp()), Some(now + 60));
+}
+
+#[tokio::test]
+async fn test_forged_token_cannot_revoke_victim() {
+    let now = Utc::now().timestamp();
+    let victim = sign(json!({"sub": "victim", "jti": "victim-1", "exp": now + 60})).await;
+
+    // Same `jti` and a far-future `exp`, signed with someone else's secret
+    let forged = Jwt::builder()
+        .with_algorithm("HS256")
+        .with_secret(b"attacker-secret")
+        .sign(json!({"sub": "victim", "jti": "victim-1", "exp": now + 1_000_000_000}))
+        .await
+        .expect("signing should succeed");
+
+    let revocation = Revocation::new();
+    assert!(revocation.revoke(verifier(), &forged, "forged").await.is_err());
+    assert!(revocation.verify(verifier(), &victim).await.is_ok());
+    assert!(
+        revocation
+            .get_revocation_info(&victim)
+            .await
+            .expect("lookup")
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_subject_and_global_issued_before() {
+    let now = Utc::now();
+    let old = sign(json!({"sub": "alice", "iat": (now - Duration::hours(2)).timestamp()})).await;
+    let fresh = sign(json!({"sub": "alice", "iat": (now + Duration::minutes(1)).timestamp()}))
+        .await;
+    let bob = sign(json!({"sub": "bob", "iat": (now - Duration::hours(2)).timestamp()})).await;
+    let no_iat = sign(json!({"sub": "alice"})).await;
+
+    let revocation = Revocation::new();
+    revocation
+        .revoke_subject_issued_before("alice", now - Duration::hours(1), "password reset")
+        .await
+        .expect("revoke subject");
+
+    assert!(revocation.is_revoked(&old).await.expect("check"));
+    assert!(!revocation.is_revoked(&fresh).await.expect("check"));
+    assert!(!revocation.is_revoked(&bob).await.expect("check"));
+    assert!(revocation.is_revoked(&no_iat).await.expect("check"));
+
+    // An earlier cutoff never rolls back a later one
+    revocation
+        .revoke_subject_issued_before("alice", now - Duration::days(1), "stale")
+        .await
+        .expect("revoke subject");
+    assert!(revocation.is_revoked(&old).await.expect("check"));
+
+    revocation
+        .revoke_all_issued_before(now, "signing key compromised")
+        .await
+        .expect("revoke all");
+    assert!(revocation.is_revoked(&bob).await.expect("check"));
+    assert!(!revocation.is_revoked(&fresh).await.expect("check"));
+}
+
+#[tokio::test]
+async fn test_file_store_survives_reopen_and_purges() {
+    let path =
+        std::env::temp_dir().join(format!("cryypt-revocation-{}.log", uuid::Uuid::new_v4()));
+    let now = Utc::now().timestamp();
+    let live = sign(json!({"sub": "u", "jti": "live", "iat": now, "exp": now + 3600})).await;
+    let expired =
+        sign(json!({"sub": "u", "jti": "gone", "iat": now - 120, "exp": now - 60})).await;
+
+    {
+        let store = FileRevocationStore::open(&path).await.expect("open");
+        let revocation = Revocation::with_store(store);
+        revocation.revoke(verifier(), &live, "logout").await.expect("revoke");
+        let gone = DateTime::from_timestamp(now - 60, 0);
+        revocation.revoke_jti("gone", "logout", gone).await.expect("revoke");
+        revocation.revoke_subject("mallory", "banned").await.expect("revoke subject");
+    }
+
+    // Simulate a crash mid-append
+    tokio::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .await
+        .expect("open log")
+        .write_all(b"{\"type\":\"tok")
+        .await
+        .expect("write torn line");
+
+    let store = FileRevocationStore::open(&path).await.expect("reopen");
+    let revocation = Revocation::with_store(store);
+    assert!(revocation.is_revoked(&live).await.expect("check"));
+    assert!(revocation.is_revoked(&expired).await.expect("check"));
+
+    assert_eq!(revocation.cleanup_expired().await.expect("purge"), 1);
+    assert!(!revocation.is_revoked(&expired).await.expect("check"));
+
+    let log = tokio::fs::read_to_string(&path).await.expect("read log");
+    assert_eq!(log.lines().count(), 2);
+
+    let store = FileRevocationStore::open(&path).await.expect("reopen");
+    let reopened = Revocation::with_store(store);
+    assert!(reopened.is_revoked(&live).await.expect("check"));
+    let mallory = sign(json!({"sub": "mallory", "iat": now - 10})).await;
+    assert!(reopened.is_revoked(&mallory).await.expect("check"));
+
+    let _ = tokio::fs::remove_file(&path).await;
+}
@@ -0,0 +1,123 @@
+//! Audience lists, leeway, `typ` / `crit` header checks and typed claims
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::Utc;
+use cryypt_jwt::claims::ClaimsValidator;
+use cryypt_jwt::{Audience, Jwt, JwtError};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use sha2::Sha256;
+use std::collections::HashMap;
+
+const SECRET: &[u8] = b"typed-claims-secret";
+
+/// Hand-roll an HS256 token so tests control every header parameter
+fn token(header: &Value, claims: &Value) -> String {
+    let signing_input = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(header.to_string()),
+        URL_SAFE_NO_PAD.encode(claims.to_string())
+    );
+    let mut mac = Hmac::<Sha256>::new_from_slice(SECRET).expect("HMAC key");
+    mac.update(signing_input.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+    format!("{signing_input}.{signature}")
+}
+
+async fn verify(token: &str, validator: ClaimsValidator) -> Result<Value, JwtError> {
+    Jwt::builder()
+        .with_algorithm("HS256")
+        .with_secret(SECRET)
+        .with_validator(validator)
+        .verify(token)
+        .await
+}
+
+#[derive(Debug, Deserialize)]
+struct Session {
+    role: String,
+    tenant: u32,
+    #[serde(flatten)]
+    other: HashMap<String, Value>,
+}
+
+#[tokio::test]
+async fn test_verify_as_typed_claims() {
+    let now = Utc::now().timestamp();
+    let jwt = token(
+        &json!({"alg": "HS256", "typ": "JWT"}),
+        &json!({
+            "sub": "user-1", "iat": now, "exp": now + 60, "aud": "api",
+            "role": "admin", "tenant": 7, "theme": "dark"
+        }),
+    );
+
+    let claims = Jwt::builder()
+        .with_algorithm("HS256")
+        .with_secret(SECRET)
+        .verify_as::<Session>(&jwt)
+        .await
+        .expect("typed verification should succeed");
+    assert_eq!(claims.sub, "user-1");
+    assert_eq!(claims.aud, Some(vec!["api".to_string()]));
+    assert_eq!(claims.extra.role, "admin");
+    assert_eq!(claims.extra.tenant, 7);
+    assert_eq!(claims.extra.other["theme"], "dark");
+
+    // Missing custom claims are a claims error, not a panic
+    let jwt = token(
+        &json!({"alg": "HS256"}),
+        &json!({"sub": "user-1", "iat": now, "exp": now + 60}),
+    );
+    let result = Jwt::builder().with_secret(SECRET).verify_as::<Session>(&jwt).await;
+    assert!(matches!(result, Err(JwtError::InvalidClaims(_))));
+}
+
+#[tokio::test]
+async fn test_audience_string_or_list_and_leeway() {
+    let now = Utc::now().timestamp();
+    let listed = token(
+        &json!({"alg": "HS256"}),
+        &json!({"sub": "u", "aud": ["web", "api"], "exp": now + 60}),
+    );
+    assert!(verify(&listed, ClaimsValidator::new().with_audience("api")).await.is_ok());
+    assert!(verify(&listed, ClaimsValidator::new().with_audiences(&["cli", "web"])).await.is_ok());
+    assert!(verify(&listed, ClaimsValidator::new().with_audience("cli")).await.is_err());
+
+    let audience: Audience = serde_json::from_value(json!("api")).expect("string audience");
+    assert!(audience.contains("api"));
+    let audience: Audience = serde_json::from_value(json!(["a", "b"])).expect("list audience");
+    assert_eq!(audience.iter().collect::<Vec<_>>(), ["a", "b"]);
+
+    let expired = token(&json!({"alg": "HS256"}), &json!({"sub": "u", "exp": now - 10}));
+    let result = verify(&expired, ClaimsValidator::new()).await;
+    assert!(matches!(result, Err(JwtError::TokenExpired)));
+    assert!(verify(&expired, ClaimsValidator::new().with_leeway(30)).await.is_ok());
+
+    let early = token(&json!({"alg": "HS256"}), &json!({"sub": "u", "nbf": now + 10}));
+    let result = verify(&early, ClaimsValidator::new()).await;
+    assert!(matches!(result, Err(JwtError::TokenNotYetValid)));
+    assert!(verify(&early, ClaimsValidator::new().with_leeway(30)).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_typ_and_crit_headers() {
+    let claims = json!({"sub": "u"});
+
+    let access = token(&json!({"alg": "HS256", "typ": "application/at+jwt"}), &claims);
+    assert!(verify(&access, ClaimsValidator::new().with_type("at+JWT")).await.is_ok());
+    assert!(verify(&access, ClaimsValidator::new().with_type("JWT")).await.is_err());
+
+    let critical = token(&json!({"alg": "HS256", "crit": ["b64x"], "b64x": true}), &claims);
+    let plain = Jwt::builder().with_secret(SECRET).verify(&critical).await;
+    assert!(matches!(plain, Err(JwtError::InvalidToken(_))));
+    assert!(verify(&critical, ClaimsValidator::new()).await.is_err());
+    assert!(verify(&critical, ClaimsValidator::new().with_critical(&["b64x"])).await.is_ok());
+
+    // Listed but absent, or a registered parameter, is always rejected
+    let absent = token(&json!({"alg": "HS256", "crit": ["b64x"]}), &claims);
+    assert!(verify(&absent, ClaimsValidator::new().with_critical(&["b64x"])).await.is_err());
+    let registered = token(&json!({"alg": "HS256", "crit": ["kid"], "kid": "k"}), &claims);
+    assert!(verify(&registered, ClaimsValidator::new().with_critical(&["kid"])).await.is_err());
+}
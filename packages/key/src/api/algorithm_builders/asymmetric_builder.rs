@@ -0,0 +1,113 @@
+//! Ed25519, X25519 and NIST EC key builders - polymorphic pattern
+//!
+//! Each builder produces an [`AsymmetricKey`], which exports to PKCS#8 / SPKI, JWK and
+//! OpenSSH encodings.
+
+use crate::asymmetric::{AsymmetricAlgorithm, AsymmetricKey, EcCurve};
+
+/// Ed25519 signing key builder
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ed25519KeyBuilder;
+
+/// X25519 key agreement key builder
+#[derive(Debug, Clone, Copy, Default)]
+pub struct X25519KeyBuilder;
+
+/// NIST elliptic curve key builder - initial state
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EcKeyBuilder;
+
+/// NIST elliptic curve key builder with curve configured
+#[derive(Debug, Clone, Copy)]
+pub struct EcKeyWithCurve {
+    curve: EcCurve,
+}
+
+/// Asymmetric key builder with algorithm and result handler
+#[derive(Debug)]
+pub struct AsymmetricKeyWithHandler<F> {
+    algorithm: AsymmetricAlgorithm,
+    handler: F,
+}
+
+impl Ed25519KeyBuilder {
+    /// Create new Ed25519 key builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Set result handler for key generation
+    pub fn on_result<F, T>(self, handler: F) -> AsymmetricKeyWithHandler<F>
+    where
+        F: FnOnce(crate::Result<AsymmetricKey>) -> T,
+        T: cryypt_common::NotResult,
+    {
+        AsymmetricKeyWithHandler {
+            algorithm: AsymmetricAlgorithm::Ed25519,
+            handler,
+        }
+    }
+}
+
+impl X25519KeyBuilder {
+    /// Create new X25519 key builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Set result handler for key generation
+    pub fn on_result<F, T>(self, handler: F) -> AsymmetricKeyWithHandler<F>
+    where
+        F: FnOnce(crate::Result<AsymmetricKey>) -> T,
+        T: cryypt_common::NotResult,
+    {
+        AsymmetricKeyWithHandler {
+            algorithm: AsymmetricAlgorithm::X25519,
+            handler,
+        }
+    }
+}
+
+impl EcKeyBuilder {
+    /// Create new EC key builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Set the curve (P-256 or P-384)
+    #[must_use]
+    pub fn with_curve(self, curve: EcCurve) -> EcKeyWithCurve {
+        EcKeyWithCurve { curve }
+    }
+}
+
+impl EcKeyWithCurve {
+    /// Set result handler for key generation
+    pub fn on_result<F, T>(self, handler: F) -> AsymmetricKeyWithHandler<F>
+    where
+        F: FnOnce(crate::Result<AsymmetricKey>) -> T,
+        T: cryypt_common::NotResult,
+    {
+        AsymmetricKeyWithHandler {
+            algorithm: self.curve.into(),
+            handler,
+        }
+    }
+}
+
+impl<F, T> AsymmetricKeyWithHandler<F>
+where
+    F: FnOnce(crate::Result<AsymmetricKey>) -> T + Send + 'static,
+    T: cryypt_common::NotResult + Send + 'static,
+{
+    /// Generate single key pair
+    pub async fn generate(self) -> T {
+        let result = AsymmetricKey::generate(self.algorithm);
+
+        // Apply result handler
+        (self.handler)(result)
+    }
+}
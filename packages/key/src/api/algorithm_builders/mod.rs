@@ -4,11 +4,15 @@
 //! `KeyGenerator` system while following the polymorphic pattern from cipher module.
 
 pub mod aes_builder;
+pub mod asymmetric_builder;
 pub mod rsa_builder;
 
 pub use aes_builder::{
     AesKeyBuilder, AesKeyWithSize, AesKeyWithSizeAndChunkHandler, AesKeyWithSizeAndHandler,
 };
+pub use asymmetric_builder::{
+    AsymmetricKeyWithHandler, EcKeyBuilder, EcKeyWithCurve, Ed25519KeyBuilder, X25519KeyBuilder,
+};
 pub use rsa_builder::{
     RsaKeyBuilder, RsaKeyWithSize, RsaKeyWithSizeAndChunkHandler, RsaKeyWithSizeAndHandler,
 };
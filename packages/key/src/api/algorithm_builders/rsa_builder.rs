@@ -86,6 +86,11 @@ where
     T: cryypt_common::NotResult + Send + 'static,
 {
     /// Generate single RSA keypair
+    ///
+    /// The keypair is the private and public PKCS#1 DER, each prefixed with its length as
+    /// a little-endian `u32`. For PKCS#8, SPKI, JWK or OpenSSH, load the private half with
+    /// [`AsymmetricKey::from_secret_bytes`](crate::AsymmetricKey::from_secret_bytes) and
+    /// [`AsymmetricAlgorithm::Rsa`](crate::AsymmetricAlgorithm::Rsa).
     pub async fn generate(self) -> T {
         let result = async {
             // Validate RSA key size
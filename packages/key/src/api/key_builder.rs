@@ -3,6 +3,7 @@
 use crate::KeyError;
 use crate::KeyResult;
 use crate::api::ActualKey;
+use crate::policy::{KeyPolicy, KeyUsage};
 use crate::result_macro::KeyProducer;
 
 /// Key builder for creating and retrieving keys
@@ -29,6 +30,8 @@ pub struct KeyBuilderReady {
     store: Box<dyn KeyStore>,
     namespace: String,
     version: u32,
+    usage: Option<KeyUsage>,
+    policy: Option<KeyPolicy>,
     result_handler: Option<Box<dyn Fn(KeyResult) -> KeyResult + Send + Sync>>,
 }
 
@@ -38,6 +41,8 @@ pub struct KeyBuilderReadyWithHandler<F> {
     store: Box<dyn KeyStore>,
     namespace: String,
     version: u32,
+    usage: Option<KeyUsage>,
+    policy: Option<KeyPolicy>,
     result_handler: F,
 }
 
@@ -47,6 +52,8 @@ pub struct KeyBuilderReadyWithChunkHandler<F> {
     store: Box<dyn KeyStore>,
     namespace: String,
     version: u32,
+    usage: Option<KeyUsage>,
+    policy: Option<KeyPolicy>,
     chunk_handler: F,
 }
 
@@ -57,6 +64,30 @@ pub trait KeyStore: Send + Sync {
 
     /// Retrieve an existing key
     fn retrieve_key(&self, namespace: &str, version: u32) -> KeyResult;
+
+    /// Retrieve an existing key for a declared usage
+    ///
+    /// Stores that keep key policies check `usage` against them; the default ignores it.
+    fn retrieve_key_for(&self, namespace: &str, version: u32, usage: &KeyUsage) -> KeyResult {
+        let _ = usage;
+        self.retrieve_key(namespace, version)
+    }
+
+    /// Generate a new key and attach `policy` to it
+    ///
+    /// The default refuses, since a store that cannot persist the policy cannot enforce it.
+    fn generate_key_with_policy(
+        &self,
+        size_bits: u32,
+        namespace: &str,
+        version: u32,
+        policy: &KeyPolicy,
+    ) -> KeyResult {
+        let _ = (size_bits, version, policy);
+        KeyResult::error(KeyError::KeyStorage(format!(
+            "Store for '{namespace}' does not support key policies"
+        )))
+    }
 }
 
 impl KeyBuilder {
@@ -95,12 +126,29 @@ impl KeyBuilderWithStoreAndNamespace {
             store: self.store,
             namespace: self.namespace,
             version,
+            usage: None,
+            policy: None,
             result_handler: None,
         }
     }
 }
 
 impl KeyBuilderReady {
+    /// Declare what the retrieved key will be used for, so the store can check it
+    /// against the key's policy
+    #[must_use]
+    pub fn for_usage(mut self, usage: KeyUsage) -> Self {
+        self.usage = Some(usage);
+        self
+    }
+
+    /// Attach a usage policy to generated keys
+    #[must_use]
+    pub fn with_policy(mut self, policy: KeyPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
     /// Add `on_result` handler - transforms pattern matching internally
     pub fn on_result<F>(self, handler: F) -> KeyBuilderReadyWithHandler<F>
     where
@@ -112,6 +160,8 @@ impl KeyBuilderReady {
             store: self.store,
             namespace: self.namespace,
             version: self.version,
+            usage: self.usage,
+            policy: self.policy,
             result_handler: handler,
         }
     }
@@ -127,6 +177,8 @@ impl KeyBuilderReady {
             store: self.store,
             namespace: self.namespace,
             version: self.version,
+            usage: self.usage,
+            policy: self.policy,
             chunk_handler: handler,
         }
     }
@@ -134,9 +186,13 @@ impl KeyBuilderReady {
     /// Generate a new key - action method per README.md
     #[must_use]
     pub fn generate(self) -> KeyResult {
-        let result = self
-            .store
-            .generate_key(self.size_bits, &self.namespace, self.version);
+        let result = generate_with(
+            self.store.as_ref(),
+            self.size_bits,
+            &self.namespace,
+            self.version,
+            self.policy.as_ref(),
+        );
 
         if let Some(handler) = self.result_handler {
             handler(result)
@@ -148,7 +204,12 @@ impl KeyBuilderReady {
     /// Retrieve an existing key - action method per README.md
     #[must_use]
     pub fn retrieve(self) -> KeyResult {
-        let result = self.store.retrieve_key(&self.namespace, self.version);
+        let result = retrieve_with(
+            self.store.as_ref(),
+            &self.namespace,
+            self.version,
+            self.usage.as_ref(),
+        );
 
         if let Some(handler) = self.result_handler {
             handler(result)
@@ -164,9 +225,13 @@ where
 {
     /// Generate a new key - action method per README.md
     pub async fn generate(self) -> Vec<u8> {
-        let key_result = self
-            .store
-            .generate_key(self.size_bits, &self.namespace, self.version);
+        let key_result = generate_with(
+            self.store.as_ref(),
+            self.size_bits,
+            &self.namespace,
+            self.version,
+            self.policy.as_ref(),
+        );
 
         // KeyResult is a Future that resolves to Result<Vec<u8>>
         let result = key_result.await;
@@ -175,7 +240,12 @@ where
 
     /// Retrieve an existing key - action method per README.md
     pub async fn retrieve(self) -> Vec<u8> {
-        let key_result = self.store.retrieve_key(&self.namespace, self.version);
+        let key_result = retrieve_with(
+            self.store.as_ref(),
+            &self.namespace,
+            self.version,
+            self.usage.as_ref(),
+        );
 
         // KeyResult is a Future that resolves to Result<Vec<u8>>
         let result = key_result.await;
@@ -193,24 +263,34 @@ where
         let namespace = self.namespace;
         let version = self.version;
         let size_bits = self.size_bits;
+        let policy = self.policy;
         let handler = self.chunk_handler;
 
         futures::stream::unfold(
             (store, namespace, version, size_bits, handler, false),
-            move |(store, namespace, version, size_bits, handler, done)| async move {
-                if done {
-                    return None;
+            move |(store, namespace, version, size_bits, handler, done)| {
+                let policy = policy.clone();
+                async move {
+                    if done {
+                        return None;
+                    }
+
+                    // Generate the key
+                    let key_result = generate_with(
+                        store.as_ref(),
+                        size_bits,
+                        &namespace,
+                        version,
+                        policy.as_ref(),
+                    );
+                    let result = key_result.await;
+                    let processed_chunk = handler(result);
+
+                    Some((
+                        processed_chunk,
+                        (store, namespace, version, size_bits, handler, true),
+                    ))
                 }
-
-                // Generate the key
-                let key_result = store.generate_key(size_bits, &namespace, version);
-                let result = key_result.await;
-                let processed_chunk = handler(result);
-
-                Some((
-                    processed_chunk,
-                    (store, namespace, version, size_bits, handler, true),
-                ))
             },
         )
     }
@@ -220,26 +300,58 @@ where
         let store = self.store;
         let namespace = self.namespace;
         let version = self.version;
+        let usage = self.usage;
         let handler = self.chunk_handler;
 
         futures::stream::unfold(
             (store, namespace, version, handler, false),
-            move |(store, namespace, version, handler, done)| async move {
-                if done {
-                    return None;
+            move |(store, namespace, version, handler, done)| {
+                let usage = usage.clone();
+                async move {
+                    if done {
+                        return None;
+                    }
+
+                    // Retrieve the key
+                    let key_result =
+                        retrieve_with(store.as_ref(), &namespace, version, usage.as_ref());
+                    let result = key_result.await;
+                    let processed_chunk = handler(result);
+
+                    Some((processed_chunk, (store, namespace, version, handler, true)))
                 }
-
-                // Retrieve the key
-                let key_result = store.retrieve_key(&namespace, version);
-                let result = key_result.await;
-                let processed_chunk = handler(result);
-
-                Some((processed_chunk, (store, namespace, version, handler, true)))
             },
         )
     }
 }
 
+/// Generate through the store, attaching `policy` when one was configured
+fn generate_with(
+    store: &dyn KeyStore,
+    size_bits: u32,
+    namespace: &str,
+    version: u32,
+    policy: Option<&KeyPolicy>,
+) -> KeyResult {
+    match policy {
+        Some(policy) => store.generate_key_with_policy(size_bits, namespace, version, policy),
+        None => store.generate_key(size_bits, namespace, version),
+    }
+}
+
+/// Retrieve through the store, declaring `usage` when one was configured
+fn retrieve_with(
+    store: &dyn KeyStore,
+    namespace: &str,
+    version: u32,
+    usage: Option<&KeyUsage>,
+) -> KeyResult {
+    match usage {
+        Some(usage) => store.retrieve_key_for(namespace, version, usage),
+        None => store.retrieve_key(namespace, version),
+    }
+}
+
 // KeyProducer implementations for different builder states
 impl KeyProducer for KeyBuilder {
     async fn produce_key(self) -> Result<ActualKey, KeyError> {
@@ -260,9 +372,13 @@ impl KeyProducer for KeyBuilderWithStore {
 impl KeyProducer for KeyBuilderReady {
     async fn produce_key(self) -> Result<ActualKey, KeyError> {
         // Use the store with proper namespace and version
-        let result = self
-            .store
-            .generate_key(self.size_bits, &self.namespace, self.version);
+        let result = generate_with(
+            self.store.as_ref(),
+            self.size_bits,
+            &self.namespace,
+            self.version,
+            self.policy.as_ref(),
+        );
         result.await.map(ActualKey::from_bytes)
     }
 }
@@ -0,0 +1,174 @@
+//! Persisted key-derivation profiles for passphrase master keys
+//!
+//! A profile records the random salt and Argon2id costs a passphrase was stretched with,
+//! so equal passphrases in different installations yield unrelated keys and costs can be
+//! raised later without guessing what an existing key was derived with. Profiles are not
+//! secret; they are stored in plain form next to the key material they protect.
+
+use crate::{KeyError, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{Engine, engine::general_purpose::STANDARD_NO_PAD};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use zeroize::Zeroizing;
+
+/// Current profile format version
+pub const KDF_PROFILE_VERSION: u32 = 1;
+
+/// Only key-derivation function currently supported by profiles
+pub const KDF_ARGON2ID: &str = "argon2id";
+
+/// Length of freshly generated salts
+const SALT_LEN: usize = 16;
+
+/// Random salt and Argon2id parameters used to derive a master key from a passphrase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfProfile {
+    version: u32,
+    algorithm: String,
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+impl KdfProfile {
+    /// New profile with a random salt and the Argon2 default costs
+    #[must_use]
+    pub fn generate() -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        rand::rng().fill_bytes(&mut salt);
+
+        Self {
+            version: KDF_PROFILE_VERSION,
+            algorithm: KDF_ARGON2ID.to_string(),
+            salt: STANDARD_NO_PAD.encode(salt),
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+
+    /// Override the Argon2id memory (KiB), iteration and parallelism costs
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyDerivation` if Argon2 rejects the parameters.
+    pub fn with_costs(
+        mut self,
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    ) -> Result<Self> {
+        Params::new(memory_kib, iterations, parallelism, Some(32))
+            .map_err(|e| KeyError::KeyDerivation(format!("Invalid Argon2 parameters: {e}")))?;
+        self.memory_kib = memory_kib;
+        self.iterations = iterations;
+        self.parallelism = parallelism;
+        Ok(self)
+    }
+
+    /// Profile format version
+    #[must_use]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Argon2id memory cost in KiB
+    #[must_use]
+    pub fn memory_kib(&self) -> u32 {
+        self.memory_kib
+    }
+
+    /// Argon2id iteration count
+    #[must_use]
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Argon2id degree of parallelism
+    #[must_use]
+    pub fn parallelism(&self) -> u32 {
+        self.parallelism
+    }
+
+    /// Derive a 32-byte master key from `passphrase`
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyDerivation` if the profile has an unsupported version or
+    /// algorithm, a malformed salt, invalid costs, or Argon2 fails.
+    pub fn derive(&self, passphrase: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+        if self.version != KDF_PROFILE_VERSION || self.algorithm != KDF_ARGON2ID {
+            return Err(KeyError::KeyDerivation(format!(
+                "Unsupported KDF profile {} v{}",
+                self.algorithm, self.version
+            )));
+        }
+
+        let salt = STANDARD_NO_PAD
+            .decode(&self.salt)
+            .map_err(|e| KeyError::KeyDerivation(format!("Invalid KDF salt: {e}")))?;
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| KeyError::KeyDerivation(format!("Invalid Argon2 parameters: {e}")))?;
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase, &salt, key.as_mut_slice())
+            .map_err(|e| KeyError::KeyDerivation(format!("Argon2id derivation failed: {e}")))?;
+        Ok(key)
+    }
+
+    /// Encode as JSON for storage
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::Internal` if serialization fails.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| KeyError::Internal(format!("KDF profile serialization failed: {e}")))
+    }
+
+    /// Decode a stored JSON profile
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the JSON is not a KDF profile.
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| KeyError::InvalidKeyFormat(format!("Invalid KDF profile: {e}")))
+    }
+}
+
+/// Storage for KDF profiles, keyed by namespace
+///
+/// Implemented by [`FileKeyStore`](crate::store::FileKeyStore), which writes
+/// `{namespace}.kdf.json` next to its key files, and by
+/// [`KeychainStore`](crate::store::KeychainStore), which keeps the profile as a
+/// keychain entry beside the keys.
+pub trait KdfProfileStore: Send + Sync {
+    /// Load the profile for `namespace`, or `None` if none has been saved
+    fn load_profile(
+        &self,
+        namespace: &str,
+    ) -> impl Future<Output = Result<Option<KdfProfile>>> + Send;
+
+    /// Persist the profile for `namespace`, replacing any previous one
+    fn save_profile(
+        &self,
+        namespace: &str,
+        profile: &KdfProfile,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Persist `profile` only if `namespace` has none yet
+    ///
+    /// Returns `None` when `profile` was saved, or the profile that was already stored.
+    /// Of two callers racing to create the first profile exactly one may see `None`, so
+    /// both end up deriving with the same salt.
+    fn save_profile_if_absent(
+        &self,
+        namespace: &str,
+        profile: &KdfProfile,
+    ) -> impl Future<Output = Result<Option<KdfProfile>>> + Send;
+}
@@ -4,8 +4,10 @@
 
 use zeroize::Zeroizing;
 
+pub mod kdf_profile;
 pub mod providers;
 
+pub use kdf_profile::{KDF_PROFILE_VERSION, KdfProfile, KdfProfileStore};
 pub use providers::*;
 
 /// Builder for master key
@@ -17,6 +19,13 @@ impl MasterKeyBuilder {
     pub fn from_passphrase(passphrase: &str) -> PassphraseMasterKey {
         PassphraseMasterKey {
             passphrase: Zeroizing::new(passphrase.to_string()),
+            profile: None,
         }
     }
+
+    /// Create master key from Shamir shares
+    #[must_use]
+    pub fn from_shares(shares: Vec<crate::sharing::Share>) -> crate::sharing::SharesMasterKey {
+        crate::sharing::SharesMasterKey::new(shares)
+    }
 }
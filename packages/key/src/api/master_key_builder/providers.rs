@@ -9,6 +9,7 @@ use base64::{
 };
 use hex;
 use sha2::{Digest, Sha256};
+use super::kdf_profile::{KdfProfile, KdfProfileStore};
 use zeroize::Zeroizing;
 
 /// Trait for types that can provide a master key
@@ -24,20 +25,113 @@ pub trait MasterKeyProvider: Send + Sync {
     fn resolve(&self) -> crate::Result<[u8; 32]>;
 }
 
-/// Master key derived from a user passphrase
+/// Passphrase for a master key, before its KDF profile is known
+///
+/// The passphrase is stretched with Argon2id using a [`KdfProfile`] - a random salt and
+/// cost parameters persisted next to the keys it protects. Without a profile there is no
+/// key to derive, so this type is deliberately not a [`MasterKeyProvider`]:
+///
+/// - [`PassphraseMasterKey::resolve_with_store`] loads the profile from a
+///   [`KdfProfileStore`], creating it on first use.
+/// - [`PassphraseMasterKey::with_profile`] attaches a known profile and yields a
+///   [`ProfiledPassphrase`], which is a [`MasterKeyProvider`].
+/// - [`PassphraseMasterKey::resolve_legacy`] recomputes the pre-profile key, to read and
+///   re-wrap material written before profiles existed.
+///
+/// Code that called `MasterKeyProvider::resolve` on a bare passphrase used the legacy
+/// derivation; it now fails to compile instead of silently switching keys.
 pub struct PassphraseMasterKey {
     pub(crate) passphrase: Zeroizing<String>,
+    pub(crate) profile: Option<KdfProfile>,
 }
 
-impl MasterKeyProvider for PassphraseMasterKey {
-    fn resolve(&self) -> crate::Result<[u8; 32]> {
-        // Use Argon2 for secure key derivation from passphrase
+/// Passphrase with its KDF profile attached
+///
+/// Created by [`PassphraseMasterKey::with_profile`]. When handed to
+/// [`PassphraseMasterKey::resolve_with_store`] or a store opened with a passphrase, the
+/// attached profile is only used if the store has none yet.
+pub struct ProfiledPassphrase {
+    inner: PassphraseMasterKey,
+    profile: KdfProfile,
+}
+
+/// Result of resolving a passphrase against a profile store
+pub struct ProfiledMasterKey {
+    /// Master key derived with the stored profile
+    pub master_key: Zeroizing<[u8; 32]>,
+    /// Profile the key was derived with
+    pub profile: KdfProfile,
+    /// Key from the legacy deterministic-salt derivation when the profile was created
+    /// by this call; material wrapped under it must be re-wrapped under `master_key`
+    pub legacy_key: Option<Zeroizing<[u8; 32]>>,
+}
+
+impl PassphraseMasterKey {
+    /// Derive with a known KDF profile
+    #[must_use]
+    pub fn with_profile(mut self, profile: KdfProfile) -> ProfiledPassphrase {
+        self.profile = Some(profile.clone());
+        ProfiledPassphrase {
+            inner: self,
+            profile,
+        }
+    }
+
+    /// Resolve using the profile stored for `namespace`, creating one on first use
+    ///
+    /// When no profile exists yet a fresh one is saved unless another caller saves one
+    /// first, in which case that profile is used. Only the caller whose profile was saved
+    /// gets the key from the legacy derivation, so it can re-wrap anything protected by
+    /// it exactly once. The profile is saved before returning, so if re-wrapping is
+    /// interrupted the legacy key can be recomputed with
+    /// [`PassphraseMasterKey::resolve_legacy`] to finish the job.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile store fails or key derivation fails.
+    pub async fn resolve_with_store<S: KdfProfileStore>(
+        &self,
+        store: &S,
+        namespace: &str,
+    ) -> crate::Result<ProfiledMasterKey> {
+        if let Some(profile) = store.load_profile(namespace).await? {
+            return self.profiled(profile, None);
+        }
+
+        let profile = self.profile.clone().unwrap_or_else(KdfProfile::generate);
+        if let Some(existing) = store.save_profile_if_absent(namespace, &profile).await? {
+            return self.profiled(existing, None);
+        }
+        let legacy_key = Zeroizing::new(self.resolve_legacy()?);
+        self.profiled(profile, Some(legacy_key))
+    }
+
+    fn profiled(
+        &self,
+        profile: KdfProfile,
+        legacy_key: Option<Zeroizing<[u8; 32]>>,
+    ) -> crate::Result<ProfiledMasterKey> {
+        Ok(ProfiledMasterKey {
+            master_key: profile.derive(self.passphrase.as_bytes())?,
+            profile,
+            legacy_key,
+        })
+    }
+
+    /// Derive with the pre-profile scheme (Argon2 over a salt hashed from the passphrase)
+    ///
+    /// Equal passphrases always produce equal keys under this scheme. It is kept only so
+    /// material protected by it can be read once and re-wrapped under a profiled key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Argon2 fails.
+    pub fn resolve_legacy(&self) -> crate::Result<[u8; 32]> {
         let argon2 = Argon2::default();
 
-        // Generate deterministic salt from passphrase hash for security
-        // This ensures same passphrase = same key, but different passphrases = different salts
+        // Salt derived from the passphrase itself - the reason this scheme is legacy
         let mut hasher = Sha256::new();
-        hasher.update("passphrasesaltv1:".as_bytes()); // Version prefix for future compatibility (no underscores)
+        hasher.update("passphrasesaltv1:".as_bytes());
         hasher.update(self.passphrase.as_bytes());
         let salt_bytes = hasher.finalize();
 
@@ -65,6 +159,54 @@ impl MasterKeyProvider for PassphraseMasterKey {
     }
 }
 
+impl AsRef<PassphraseMasterKey> for PassphraseMasterKey {
+    fn as_ref(&self) -> &PassphraseMasterKey {
+        self
+    }
+}
+
+impl ProfiledPassphrase {
+    /// KDF profile attached to this passphrase
+    #[must_use]
+    pub fn profile(&self) -> &KdfProfile {
+        &self.profile
+    }
+
+    /// See [`PassphraseMasterKey::resolve_with_store`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile store fails or key derivation fails.
+    pub async fn resolve_with_store<S: KdfProfileStore>(
+        &self,
+        store: &S,
+        namespace: &str,
+    ) -> crate::Result<ProfiledMasterKey> {
+        self.inner.resolve_with_store(store, namespace).await
+    }
+
+    /// See [`PassphraseMasterKey::resolve_legacy`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Argon2 fails.
+    pub fn resolve_legacy(&self) -> crate::Result<[u8; 32]> {
+        self.inner.resolve_legacy()
+    }
+}
+
+impl AsRef<PassphraseMasterKey> for ProfiledPassphrase {
+    fn as_ref(&self) -> &PassphraseMasterKey {
+        &self.inner
+    }
+}
+
+impl MasterKeyProvider for ProfiledPassphrase {
+    fn resolve(&self) -> crate::Result<[u8; 32]> {
+        Ok(*self.profile.derive(self.inner.passphrase.as_bytes())?)
+    }
+}
+
 /// Master key using raw key material directly
 pub struct RawMasterKey {
     pub(crate) key: [u8; 32],
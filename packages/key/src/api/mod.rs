@@ -8,7 +8,9 @@ pub mod key_retriever;
 pub mod master_key_builder;
 
 pub use actual_key::ActualKey;
-pub use algorithm_builders::{AesKeyBuilder, RsaKeyBuilder};
+pub use algorithm_builders::{
+    AesKeyBuilder, EcKeyBuilder, Ed25519KeyBuilder, RsaKeyBuilder, X25519KeyBuilder,
+};
 pub use key_builder::{
     KeyBuilder, KeyBuilderReady, KeyBuilderWithStore, KeyBuilderWithStoreAndNamespace, KeyStore,
 };
@@ -18,4 +20,7 @@ pub use key_generator::KeyGenerator;
 pub use key_retriever::{KeyRetriever, SecureRetrievedKey};
 
 // Export MasterKeyBuilder for vault module
-pub use master_key_builder::{MasterKeyBuilder, MasterKeyProvider, PassphraseMasterKey};
+pub use master_key_builder::{
+    KdfProfile, KdfProfileStore, MasterKeyBuilder, MasterKeyProvider, PassphraseMasterKey,
+    ProfiledMasterKey, ProfiledPassphrase,
+};
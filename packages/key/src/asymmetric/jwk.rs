@@ -0,0 +1,260 @@
+//! JSON Web Key encoding (RFC 7517, RFC 7518 sections 6.2 and 6.3, RFC 8037)
+
+use super::AsymmetricAlgorithm;
+use super::key::{AsymmetricKey, AsymmetricPublicKey, PublicKey, SecretKey, exact_len};
+use super::rsa_key::{RsaPrivateParts, RsaPublic, RsaSecret};
+use crate::{KeyError, Result};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Wire form of an OKP, EC or RSA JWK
+#[derive(Serialize, Deserialize)]
+struct Jwk {
+    kty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crv: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    x: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    y: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    n: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    e: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    d: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    p: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    q: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dq: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    qi: Option<String>,
+}
+
+impl Jwk {
+    fn new(kty: &str, crv: Option<&str>) -> Self {
+        Self {
+            kty: kty.to_string(),
+            crv: crv.map(str::to_string),
+            x: None,
+            y: None,
+            n: None,
+            e: None,
+            d: None,
+            p: None,
+            q: None,
+            dp: None,
+            dq: None,
+            qi: None,
+        }
+    }
+}
+
+impl Drop for Jwk {
+    fn drop(&mut self) {
+        for value in [
+            &mut self.d,
+            &mut self.p,
+            &mut self.q,
+            &mut self.dp,
+            &mut self.dq,
+            &mut self.qi,
+        ] {
+            value.zeroize();
+        }
+    }
+}
+
+impl AsymmetricKey {
+    /// Encode as a private JWK, including the public members
+    ///
+    /// RSA keys carry the CRT members `p`, `q`, `dp`, `dq` and `qi` besides `d`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::Internal` if serialization fails.
+    pub fn to_jwk(&self) -> Result<Zeroizing<String>> {
+        let mut jwk = self.public_key().jwk_members()?;
+        if let SecretKey::Rsa(key) = &self.secret {
+            let parts = key.parts()?;
+            jwk.d = Some(URL_SAFE_NO_PAD.encode(parts.d.as_slice()));
+            jwk.p = Some(URL_SAFE_NO_PAD.encode(parts.p.as_slice()));
+            jwk.q = Some(URL_SAFE_NO_PAD.encode(parts.q.as_slice()));
+            jwk.dp = Some(URL_SAFE_NO_PAD.encode(parts.dp.as_slice()));
+            jwk.dq = Some(URL_SAFE_NO_PAD.encode(parts.dq.as_slice()));
+            jwk.qi = Some(URL_SAFE_NO_PAD.encode(parts.qi.as_slice()));
+        } else {
+            jwk.d = Some(URL_SAFE_NO_PAD.encode(self.secret_bytes().as_slice()));
+        }
+        serde_json::to_string(&jwk)
+            .map(Zeroizing::new)
+            .map_err(|e| KeyError::Internal(format!("JWK serialization failed: {e}")))
+    }
+
+    /// Decode a private JWK
+    ///
+    /// RSA JWKs must include the CRT members; RFC 7518 makes them optional, but every
+    /// common producer writes them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed or public-only JWKs and
+    /// `KeyError::InvalidKey` if the private and public members do not match.
+    pub fn from_jwk(json: &str) -> Result<Self> {
+        let jwk = parse(json)?;
+        let algorithm = jwk_algorithm(&jwk)?;
+        let encoded = jwk
+            .d
+            .as_deref()
+            .ok_or_else(|| KeyError::InvalidKeyFormat("JWK has no private member 'd'".into()))?;
+        let secret = Zeroizing::new(decode_member(encoded, "d")?);
+        let key = if algorithm == AsymmetricAlgorithm::Rsa {
+            rsa_from_jwk(&jwk, secret)?
+        } else {
+            Self::from_secret_bytes(algorithm, &secret)?
+        };
+
+        if key.public_key() != public_from_jwk(&jwk, algorithm)? {
+            return Err(KeyError::InvalidKey(
+                "JWK private key does not match its public members".into(),
+            ));
+        }
+        Ok(key)
+    }
+}
+
+impl AsymmetricPublicKey {
+    /// Encode as a public JWK
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::Internal` if serialization fails.
+    pub fn to_jwk(&self) -> Result<String> {
+        serde_json::to_string(&self.jwk_members()?)
+            .map_err(|e| KeyError::Internal(format!("JWK serialization failed: {e}")))
+    }
+
+    /// Decode a public JWK; private members, if present, are ignored
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed JWKs and
+    /// `KeyError::InvalidKey` for invalid points.
+    pub fn from_jwk(json: &str) -> Result<Self> {
+        let jwk = parse(json)?;
+        public_from_jwk(&jwk, jwk_algorithm(&jwk)?)
+    }
+
+    fn jwk_members(&self) -> Result<Jwk> {
+        let crv = self.algorithm().jwk_curve();
+        let (kty, x, y) = match &self.public {
+            PublicKey::Ed25519(key) => ("OKP", key.as_bytes().to_vec(), None),
+            PublicKey::X25519(key) => ("OKP", key.as_bytes().to_vec(), None),
+            PublicKey::P256(key) => {
+                let point = key.to_encoded_point(false);
+                let (x, y) = point.x().zip(point.y()).ok_or_else(identity_point)?;
+                ("EC", x.to_vec(), Some(y.to_vec()))
+            }
+            PublicKey::P384(key) => {
+                let point = key.to_encoded_point(false);
+                let (x, y) = point.x().zip(point.y()).ok_or_else(identity_point)?;
+                ("EC", x.to_vec(), Some(y.to_vec()))
+            }
+            PublicKey::Rsa(key) => {
+                let (n, e) = key.parts()?;
+                let mut jwk = Jwk::new("RSA", crv);
+                jwk.n = Some(URL_SAFE_NO_PAD.encode(n));
+                jwk.e = Some(URL_SAFE_NO_PAD.encode(e));
+                return Ok(jwk);
+            }
+        };
+
+        let mut jwk = Jwk::new(kty, crv);
+        jwk.x = Some(URL_SAFE_NO_PAD.encode(x));
+        jwk.y = y.map(|y| URL_SAFE_NO_PAD.encode(y));
+        Ok(jwk)
+    }
+}
+
+fn parse(json: &str) -> Result<Jwk> {
+    serde_json::from_str(json).map_err(|e| KeyError::InvalidKeyFormat(format!("Invalid JWK: {e}")))
+}
+
+fn jwk_algorithm(jwk: &Jwk) -> Result<AsymmetricAlgorithm> {
+    match (jwk.kty.as_str(), jwk.crv.as_deref()) {
+        ("OKP", Some("Ed25519")) => Ok(AsymmetricAlgorithm::Ed25519),
+        ("OKP", Some("X25519")) => Ok(AsymmetricAlgorithm::X25519),
+        ("EC", Some("P-256")) => Ok(AsymmetricAlgorithm::P256),
+        ("EC", Some("P-384")) => Ok(AsymmetricAlgorithm::P384),
+        ("RSA", _) => Ok(AsymmetricAlgorithm::Rsa),
+        (kty, crv) => Err(KeyError::InvalidKeyFormat(format!(
+            "Unsupported JWK key type {kty} with curve {}",
+            crv.unwrap_or("(none)")
+        ))),
+    }
+}
+
+fn public_from_jwk(jwk: &Jwk, algorithm: AsymmetricAlgorithm) -> Result<AsymmetricPublicKey> {
+    let field_len = match algorithm {
+        AsymmetricAlgorithm::Ed25519 | AsymmetricAlgorithm::X25519 => {
+            return AsymmetricPublicKey::from_bytes(algorithm, &member(jwk.x.as_deref(), "x")?);
+        }
+        AsymmetricAlgorithm::Rsa => {
+            let n = member(jwk.n.as_deref(), "n")?;
+            let e = member(jwk.e.as_deref(), "e")?;
+            let public = PublicKey::Rsa(RsaPublic::from_parts(&n, &e)?);
+            return Ok(AsymmetricPublicKey { public });
+        }
+        AsymmetricAlgorithm::P256 => 32,
+        AsymmetricAlgorithm::P384 => 48,
+    };
+    let x = member(jwk.x.as_deref(), "x")?;
+    let y = member(jwk.y.as_deref(), "y")?;
+
+    // RFC 7518 requires full-length coordinates; rebuild the uncompressed SEC1 point
+    let mut point = Vec::with_capacity(1 + 2 * field_len);
+    point.push(0x04);
+    point.extend_from_slice(exact_len(&x, field_len)?);
+    point.extend_from_slice(exact_len(&y, field_len)?);
+    AsymmetricPublicKey::from_bytes(algorithm, &point)
+}
+
+fn rsa_from_jwk(jwk: &Jwk, d: Zeroizing<Vec<u8>>) -> Result<AsymmetricKey> {
+    let secret = |value: Option<&str>, name| member(value, name).map(Zeroizing::new);
+    let parts = RsaPrivateParts {
+        n: member(jwk.n.as_deref(), "n")?,
+        e: member(jwk.e.as_deref(), "e")?,
+        d,
+        p: secret(jwk.p.as_deref(), "p")?,
+        q: secret(jwk.q.as_deref(), "q")?,
+        dp: secret(jwk.dp.as_deref(), "dp")?,
+        dq: secret(jwk.dq.as_deref(), "dq")?,
+        qi: secret(jwk.qi.as_deref(), "qi")?,
+    };
+    Ok(AsymmetricKey {
+        secret: SecretKey::Rsa(RsaSecret::from_parts(&parts)?),
+    })
+}
+
+/// Decode a required member
+fn member(value: Option<&str>, name: &str) -> Result<Vec<u8>> {
+    let value =
+        value.ok_or_else(|| KeyError::InvalidKeyFormat(format!("JWK has no member '{name}'")))?;
+    decode_member(value, name)
+}
+
+fn decode_member(value: &str, name: &str) -> Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|e| KeyError::InvalidKeyFormat(format!("Invalid JWK member '{name}': {e}")))
+}
+
+fn identity_point() -> KeyError {
+    KeyError::InvalidKey("Public key is the point at infinity".into())
+}
@@ -0,0 +1,283 @@
+//! Asymmetric private and public key types
+
+use super::AsymmetricAlgorithm;
+use super::rsa_key::{RSA_DEFAULT_BITS, RsaPublic, RsaSecret};
+use crate::traits::{KeyImport, KeyRetrieval};
+use crate::{KeyError, KeyId, Result};
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+/// Private key material for one of the supported algorithms
+#[derive(Clone)]
+pub(super) enum SecretKey {
+    Ed25519(ed25519_dalek::SigningKey),
+    X25519(x25519_dalek::StaticSecret),
+    P256(p256::SecretKey),
+    P384(p384::SecretKey),
+    Rsa(RsaSecret),
+}
+
+/// Public key material for one of the supported algorithms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum PublicKey {
+    Ed25519(ed25519_dalek::VerifyingKey),
+    X25519(x25519_dalek::PublicKey),
+    P256(p256::PublicKey),
+    P384(p384::PublicKey),
+    Rsa(RsaPublic),
+}
+
+/// Asymmetric private key
+///
+/// The key zeroizes its material on drop. `Debug` output shows only the algorithm and
+/// public key.
+#[derive(Clone)]
+pub struct AsymmetricKey {
+    pub(super) secret: SecretKey,
+}
+
+/// Public half of an [`AsymmetricKey`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsymmetricPublicKey {
+    pub(super) public: PublicKey,
+}
+
+impl cryypt_common::NotResult for AsymmetricKey {}
+impl cryypt_common::NotResult for AsymmetricPublicKey {}
+
+impl std::fmt::Debug for AsymmetricKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsymmetricKey")
+            .field("algorithm", &self.algorithm())
+            .field("public_key", &self.public_key())
+            .finish_non_exhaustive()
+    }
+}
+
+impl AsymmetricKey {
+    /// Generate a new random key pair; RSA keys have a
+    /// [`RSA_DEFAULT_BITS`](crate::RSA_DEFAULT_BITS)-bit modulus
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyGeneration` if no valid key could be drawn, which only
+    /// happens with a broken random source.
+    pub fn generate(algorithm: AsymmetricAlgorithm) -> Result<Self> {
+        let mut rng = rand::rng();
+        let secret = match algorithm {
+            AsymmetricAlgorithm::Ed25519 => {
+                let mut seed = Zeroizing::new([0u8; 32]);
+                rng.fill_bytes(seed.as_mut_slice());
+                SecretKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&seed))
+            }
+            AsymmetricAlgorithm::X25519 => {
+                let mut bytes = Zeroizing::new([0u8; 32]);
+                rng.fill_bytes(bytes.as_mut_slice());
+                SecretKey::X25519(x25519_dalek::StaticSecret::from(*bytes))
+            }
+            AsymmetricAlgorithm::P256 => {
+                SecretKey::P256(random_scalar(&mut rng, 32, p256::SecretKey::from_slice)?)
+            }
+            AsymmetricAlgorithm::P384 => {
+                SecretKey::P384(random_scalar(&mut rng, 48, p384::SecretKey::from_slice)?)
+            }
+            AsymmetricAlgorithm::Rsa => SecretKey::Rsa(RsaSecret::generate(RSA_DEFAULT_BITS)?),
+        };
+        Ok(Self { secret })
+    }
+
+    /// Generate an RSA key pair with a modulus of `bits`, one of
+    /// [`RSA_GENERATE_BITS`](crate::RSA_GENERATE_BITS)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeySize` for other sizes and `KeyError::KeyGeneration` if
+    /// generation fails.
+    pub fn generate_rsa(bits: usize) -> Result<Self> {
+        Ok(Self {
+            secret: SecretKey::Rsa(RsaSecret::generate(bits)?),
+        })
+    }
+
+    /// Rebuild a key from its raw secret: the Ed25519 seed, the X25519 scalar, the
+    /// big-endian EC scalar, or the PKCS#1 `RSAPrivateKey` DER
+    ///
+    /// The private half of [`RsaKeyBuilder`](crate::api::RsaKeyBuilder) output is PKCS#1
+    /// DER, so this also brings builder keys to the other encodings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` if the bytes are the wrong length, not a valid
+    /// scalar for the curve, or not a consistent RSA key of at least 2048 bits.
+    pub fn from_secret_bytes(algorithm: AsymmetricAlgorithm, bytes: &[u8]) -> Result<Self> {
+        let secret = match algorithm {
+            AsymmetricAlgorithm::Ed25519 => {
+                SecretKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&fixed_32(bytes)?))
+            }
+            AsymmetricAlgorithm::X25519 => {
+                SecretKey::X25519(x25519_dalek::StaticSecret::from(*fixed_32(bytes)?))
+            }
+            AsymmetricAlgorithm::P256 => SecretKey::P256(
+                p256::SecretKey::from_slice(exact_len(bytes, 32)?)
+                    .map_err(|_| KeyError::InvalidKey("Invalid P-256 scalar".into()))?,
+            ),
+            AsymmetricAlgorithm::P384 => SecretKey::P384(
+                p384::SecretKey::from_slice(exact_len(bytes, 48)?)
+                    .map_err(|_| KeyError::InvalidKey("Invalid P-384 scalar".into()))?,
+            ),
+            AsymmetricAlgorithm::Rsa => SecretKey::Rsa(RsaSecret::from_pkcs1_der(bytes)?),
+        };
+        Ok(Self { secret })
+    }
+
+    /// Algorithm of this key
+    #[must_use]
+    pub fn algorithm(&self) -> AsymmetricAlgorithm {
+        match self.secret {
+            SecretKey::Ed25519(_) => AsymmetricAlgorithm::Ed25519,
+            SecretKey::X25519(_) => AsymmetricAlgorithm::X25519,
+            SecretKey::P256(_) => AsymmetricAlgorithm::P256,
+            SecretKey::P384(_) => AsymmetricAlgorithm::P384,
+            SecretKey::Rsa(_) => AsymmetricAlgorithm::Rsa,
+        }
+    }
+
+    /// Public half of the key pair
+    #[must_use]
+    pub fn public_key(&self) -> AsymmetricPublicKey {
+        let public = match &self.secret {
+            SecretKey::Ed25519(key) => PublicKey::Ed25519(key.verifying_key()),
+            SecretKey::X25519(key) => PublicKey::X25519(x25519_dalek::PublicKey::from(key)),
+            SecretKey::P256(key) => PublicKey::P256(key.public_key()),
+            SecretKey::P384(key) => PublicKey::P384(key.public_key()),
+            SecretKey::Rsa(key) => PublicKey::Rsa(key.public().clone()),
+        };
+        AsymmetricPublicKey { public }
+    }
+
+    /// Raw secret in the form accepted by [`AsymmetricKey::from_secret_bytes`]
+    #[must_use]
+    pub fn secret_bytes(&self) -> Zeroizing<Vec<u8>> {
+        Zeroizing::new(match &self.secret {
+            SecretKey::Ed25519(key) => key.to_bytes().to_vec(),
+            SecretKey::X25519(key) => key.to_bytes().to_vec(),
+            SecretKey::P256(key) => key.to_bytes().to_vec(),
+            SecretKey::P384(key) => key.to_bytes().to_vec(),
+            SecretKey::Rsa(key) => key.pkcs1_der().to_vec(),
+        })
+    }
+
+    /// Store the key as PKCS#8 DER under `key_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if encoding fails or the store rejects the key.
+    pub async fn save<S: KeyImport>(&self, store: &S, key_id: &dyn KeyId) -> Result<()> {
+        let der = self.to_pkcs8_der()?;
+        store.store(key_id, &der).recv().await
+    }
+
+    /// Load a key previously written with [`AsymmetricKey::save`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is missing or is not a supported PKCS#8 key.
+    pub async fn load<S: KeyRetrieval>(store: &S, key_id: &dyn KeyId) -> Result<Self> {
+        let der = Zeroizing::new(store.retrieve(key_id).recv().await?);
+        Self::from_pkcs8_der(&der)
+    }
+}
+
+impl AsymmetricPublicKey {
+    /// Rebuild a public key from its raw form: 32 bytes for Ed25519 and X25519, a SEC1
+    /// point (compressed or uncompressed) for the NIST curves, PKCS#1 `RSAPublicKey` DER
+    /// for RSA
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` if the bytes do not encode a valid public key.
+    pub fn from_bytes(algorithm: AsymmetricAlgorithm, bytes: &[u8]) -> Result<Self> {
+        let public = match algorithm {
+            AsymmetricAlgorithm::Ed25519 => PublicKey::Ed25519(
+                ed25519_dalek::VerifyingKey::from_bytes(&fixed_32(bytes)?)
+                    .map_err(|_| KeyError::InvalidKey("Invalid Ed25519 public key".into()))?,
+            ),
+            AsymmetricAlgorithm::X25519 => {
+                PublicKey::X25519(x25519_dalek::PublicKey::from(*fixed_32(bytes)?))
+            }
+            AsymmetricAlgorithm::P256 => PublicKey::P256(
+                p256::PublicKey::from_sec1_bytes(bytes)
+                    .map_err(|_| KeyError::InvalidKey("Invalid P-256 public key".into()))?,
+            ),
+            AsymmetricAlgorithm::P384 => PublicKey::P384(
+                p384::PublicKey::from_sec1_bytes(bytes)
+                    .map_err(|_| KeyError::InvalidKey("Invalid P-384 public key".into()))?,
+            ),
+            AsymmetricAlgorithm::Rsa => PublicKey::Rsa(RsaPublic::from_pkcs1_der(bytes)?),
+        };
+        Ok(Self { public })
+    }
+
+    /// Algorithm of this key
+    #[must_use]
+    pub fn algorithm(&self) -> AsymmetricAlgorithm {
+        match self.public {
+            PublicKey::Ed25519(_) => AsymmetricAlgorithm::Ed25519,
+            PublicKey::X25519(_) => AsymmetricAlgorithm::X25519,
+            PublicKey::P256(_) => AsymmetricAlgorithm::P256,
+            PublicKey::P384(_) => AsymmetricAlgorithm::P384,
+            PublicKey::Rsa(_) => AsymmetricAlgorithm::Rsa,
+        }
+    }
+
+    /// Raw public key: 32 bytes for Ed25519 and X25519, an uncompressed SEC1 point for
+    /// the NIST curves, PKCS#1 DER for RSA
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+        match &self.public {
+            PublicKey::Ed25519(key) => key.to_bytes().to_vec(),
+            PublicKey::X25519(key) => key.to_bytes().to_vec(),
+            PublicKey::P256(key) => key.to_encoded_point(false).as_bytes().to_vec(),
+            PublicKey::P384(key) => key.to_encoded_point(false).as_bytes().to_vec(),
+            PublicKey::Rsa(key) => key.pkcs1_der().to_vec(),
+        }
+    }
+}
+
+/// Draw random scalars until one is in range for the curve
+fn random_scalar<K, E>(
+    rng: &mut impl RngCore,
+    len: usize,
+    parse: impl Fn(&[u8]) -> std::result::Result<K, E>,
+) -> Result<K> {
+    // Out-of-range draws are astronomically rare; the bound only guards a broken RNG
+    for _ in 0..64 {
+        let mut bytes = Zeroizing::new(vec![0u8; len]);
+        rng.fill_bytes(&mut bytes);
+        if let Ok(key) = parse(&bytes) {
+            return Ok(key);
+        }
+    }
+    Err(KeyError::KeyGeneration(
+        "Random source produced no valid EC scalar".into(),
+    ))
+}
+
+pub(super) fn exact_len(bytes: &[u8], len: usize) -> Result<&[u8]> {
+    if bytes.len() == len {
+        Ok(bytes)
+    } else {
+        Err(KeyError::InvalidKeySize {
+            expected: len,
+            actual: bytes.len(),
+        })
+    }
+}
+
+pub(super) fn fixed_32(bytes: &[u8]) -> Result<Zeroizing<[u8; 32]>> {
+    let mut out = Zeroizing::new([0u8; 32]);
+    out.copy_from_slice(exact_len(bytes, 32)?);
+    Ok(out)
+}
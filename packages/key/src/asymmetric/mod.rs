@@ -0,0 +1,68 @@
+//! Asymmetric key pairs and their standard encodings
+//!
+//! [`AsymmetricKey`] holds an Ed25519, X25519, P-256, P-384 or RSA private key; its
+//! [`AsymmetricPublicKey`] half can be shared freely. Both convert to and from:
+//!
+//! - PKCS#8 (private) and SPKI (public), in DER and PEM
+//! - encrypted PKCS#8 (PBES2 with PBKDF2-SHA256 and AES-256-CBC)
+//! - JWK (RFC 7517, with RFC 8037 for the Edwards/Montgomery curves)
+//! - OpenSSH private and public key formats (Ed25519, P-256, P-384 and RSA)
+//!
+//! Keys are kept in a key store as unencrypted PKCS#8 DER, relying on the store's own
+//! master-key encryption; see [`AsymmetricKey::save`] and [`AsymmetricKey::load`].
+
+mod jwk;
+mod key;
+mod openssh;
+mod pkcs;
+mod rsa_key;
+
+pub use key::{AsymmetricKey, AsymmetricPublicKey};
+pub use rsa_key::{RSA_DEFAULT_BITS, RSA_GENERATE_BITS};
+
+/// Asymmetric key algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsymmetricAlgorithm {
+    /// Ed25519 signing keys (RFC 8032)
+    Ed25519,
+    /// X25519 key agreement keys (RFC 7748)
+    X25519,
+    /// ECDSA / ECDH keys on NIST P-256
+    P256,
+    /// ECDSA / ECDH keys on NIST P-384
+    P384,
+    /// RSA keys (RFC 8017) with a 2048- to 4096-bit modulus
+    Rsa,
+}
+
+impl AsymmetricAlgorithm {
+    /// Curve name as used in JWK `crv`; RSA keys have none
+    #[must_use]
+    pub fn jwk_curve(self) -> Option<&'static str> {
+        match self {
+            Self::Ed25519 => Some("Ed25519"),
+            Self::X25519 => Some("X25519"),
+            Self::P256 => Some("P-256"),
+            Self::P384 => Some("P-384"),
+            Self::Rsa => None,
+        }
+    }
+}
+
+/// NIST curve for [`EcKeyBuilder`](crate::api::EcKeyBuilder)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EcCurve {
+    /// NIST P-256 (secp256r1)
+    P256,
+    /// NIST P-384 (secp384r1)
+    P384,
+}
+
+impl From<EcCurve> for AsymmetricAlgorithm {
+    fn from(curve: EcCurve) -> Self {
+        match curve {
+            EcCurve::P256 => Self::P256,
+            EcCurve::P384 => Self::P384,
+        }
+    }
+}
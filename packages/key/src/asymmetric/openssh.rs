@@ -0,0 +1,191 @@
+//! OpenSSH private (`openssh-key-v1`) and public (`authorized_keys`) key formats
+
+use super::AsymmetricAlgorithm;
+use super::key::{AsymmetricKey, AsymmetricPublicKey, PublicKey, SecretKey};
+use super::rsa_key::{RsaPublic, RsaSecret};
+use crate::{KeyError, Result};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use ssh_key::private::{
+    EcdsaKeypair, EcdsaPrivateKey, Ed25519Keypair, KeypairData, RsaKeypair, RsaPrivateKey,
+};
+use ssh_key::public::{EcdsaPublicKey, Ed25519PublicKey, KeyData, RsaPublicKey};
+use ssh_key::{LineEnding, Mpint};
+use zeroize::Zeroizing;
+
+impl AsymmetricKey {
+    /// Encode as an unencrypted OpenSSH private key (`OPENSSH PRIVATE KEY`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for X25519 keys, which SSH does not use, or
+    /// if encoding fails.
+    pub fn to_openssh(&self, comment: &str) -> Result<Zeroizing<String>> {
+        let keypair = match &self.secret {
+            SecretKey::Ed25519(key) => {
+                KeypairData::Ed25519(Ed25519Keypair::from_seed(&Zeroizing::new(key.to_bytes())))
+            }
+            SecretKey::P256(key) => {
+                let mut private = Zeroizing::new([0u8; 32]);
+                private.copy_from_slice(&key.to_bytes());
+                KeypairData::Ecdsa(EcdsaKeypair::NistP256 {
+                    public: key.public_key().to_encoded_point(false),
+                    private: EcdsaPrivateKey::from(*private),
+                })
+            }
+            SecretKey::P384(key) => {
+                let mut private = Zeroizing::new([0u8; 48]);
+                private.copy_from_slice(&key.to_bytes());
+                KeypairData::Ecdsa(EcdsaKeypair::NistP384 {
+                    public: key.public_key().to_encoded_point(false),
+                    private: EcdsaPrivateKey::from(*private),
+                })
+            }
+            SecretKey::Rsa(key) => {
+                let parts = key.parts()?;
+                KeypairData::Rsa(RsaKeypair {
+                    public: RsaPublicKey {
+                        e: mpint(&parts.e)?,
+                        n: mpint(&parts.n)?,
+                    },
+                    private: RsaPrivateKey {
+                        d: mpint(&parts.d)?,
+                        iqmp: mpint(&parts.qi)?,
+                        p: mpint(&parts.p)?,
+                        q: mpint(&parts.q)?,
+                    },
+                })
+            }
+            SecretKey::X25519(_) => return Err(unsupported(AsymmetricAlgorithm::X25519)),
+        };
+
+        ssh_key::PrivateKey::new(keypair, comment)
+            .and_then(|key| key.to_openssh(LineEnding::LF))
+            .map_err(format_error)
+    }
+
+    /// Decode an unencrypted OpenSSH private key
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed input, passphrase-protected
+    /// keys, and key types other than Ed25519, ECDSA P-256, ECDSA P-384 and RSA.
+    pub fn from_openssh(openssh: &str) -> Result<Self> {
+        let key = ssh_key::PrivateKey::from_openssh(openssh).map_err(format_error)?;
+        if key.is_encrypted() {
+            return Err(KeyError::InvalidKeyFormat(
+                "Passphrase-protected OpenSSH keys are not supported".into(),
+            ));
+        }
+
+        match key.key_data() {
+            KeypairData::Ed25519(keypair) => Self::from_secret_bytes(
+                AsymmetricAlgorithm::Ed25519,
+                Zeroizing::new(keypair.private.to_bytes()).as_slice(),
+            ),
+            KeypairData::Ecdsa(EcdsaKeypair::NistP256 { private, .. }) => {
+                Self::from_secret_bytes(AsymmetricAlgorithm::P256, private.as_slice())
+            }
+            KeypairData::Ecdsa(EcdsaKeypair::NistP384 { private, .. }) => {
+                Self::from_secret_bytes(AsymmetricAlgorithm::P384, private.as_slice())
+            }
+            KeypairData::Rsa(keypair) => {
+                let secret = RsaSecret::from_ssh_parts(
+                    positive(&keypair.public.n)?,
+                    positive(&keypair.public.e)?,
+                    positive(&keypair.private.d)?,
+                    positive(&keypair.private.p)?,
+                    positive(&keypair.private.q)?,
+                    positive(&keypair.private.iqmp)?,
+                )?;
+                Ok(Self {
+                    secret: SecretKey::Rsa(secret),
+                })
+            }
+            other => Err(KeyError::InvalidKeyFormat(format!(
+                "Unsupported OpenSSH key type {}",
+                other
+                    .algorithm()
+                    .map_or_else(|_| "unknown".into(), |a| a.to_string())
+            ))),
+        }
+    }
+}
+
+impl AsymmetricPublicKey {
+    /// Encode as an OpenSSH public key line, as found in `authorized_keys`
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for X25519 keys or if encoding fails.
+    pub fn to_openssh(&self, comment: &str) -> Result<String> {
+        let key_data = match &self.public {
+            PublicKey::Ed25519(key) => KeyData::Ed25519(Ed25519PublicKey(key.to_bytes())),
+            PublicKey::P256(key) => {
+                KeyData::Ecdsa(EcdsaPublicKey::NistP256(key.to_encoded_point(false)))
+            }
+            PublicKey::P384(key) => {
+                KeyData::Ecdsa(EcdsaPublicKey::NistP384(key.to_encoded_point(false)))
+            }
+            PublicKey::Rsa(key) => {
+                let (n, e) = key.parts()?;
+                KeyData::Rsa(RsaPublicKey {
+                    e: mpint(&e)?,
+                    n: mpint(&n)?,
+                })
+            }
+            PublicKey::X25519(_) => return Err(unsupported(AsymmetricAlgorithm::X25519)),
+        };
+
+        ssh_key::PublicKey::new(key_data, comment)
+            .to_openssh()
+            .map_err(format_error)
+    }
+
+    /// Decode an OpenSSH public key line; the comment is discarded
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed input and unsupported key
+    /// types, and `KeyError::InvalidKey` for invalid points.
+    pub fn from_openssh(openssh: &str) -> Result<Self> {
+        let key = ssh_key::PublicKey::from_openssh(openssh.trim()).map_err(format_error)?;
+        match key.key_data() {
+            KeyData::Ed25519(key) => Self::from_bytes(AsymmetricAlgorithm::Ed25519, &key.0),
+            KeyData::Ecdsa(EcdsaPublicKey::NistP256(point)) => {
+                Self::from_bytes(AsymmetricAlgorithm::P256, point.as_bytes())
+            }
+            KeyData::Ecdsa(EcdsaPublicKey::NistP384(point)) => {
+                Self::from_bytes(AsymmetricAlgorithm::P384, point.as_bytes())
+            }
+            KeyData::Rsa(key) => Ok(Self {
+                public: PublicKey::Rsa(RsaPublic::from_parts(
+                    positive(&key.n)?,
+                    positive(&key.e)?,
+                )?),
+            }),
+            other => Err(KeyError::InvalidKeyFormat(format!(
+                "Unsupported OpenSSH key type {}",
+                other.algorithm()
+            ))),
+        }
+    }
+}
+
+fn unsupported(algorithm: AsymmetricAlgorithm) -> KeyError {
+    KeyError::InvalidKeyFormat(format!("{algorithm:?} keys have no OpenSSH encoding"))
+}
+
+fn mpint(bytes: &[u8]) -> Result<Mpint> {
+    Mpint::from_positive_bytes(bytes)
+        .map_err(|e| KeyError::InvalidKeyFormat(format!("OpenSSH key error: {e}")))
+}
+
+fn positive(value: &Mpint) -> Result<&[u8]> {
+    value
+        .as_positive_bytes()
+        .ok_or_else(|| KeyError::InvalidKey("Negative RSA parameter in OpenSSH key".into()))
+}
+
+fn format_error(error: ssh_key::Error) -> KeyError {
+    KeyError::InvalidKeyFormat(format!("OpenSSH key error: {error}"))
+}
@@ -0,0 +1,329 @@
+//! PKCS#8 and SPKI encodings, plain and password-encrypted
+
+use super::AsymmetricAlgorithm;
+use super::key::{AsymmetricKey, AsymmetricPublicKey, PublicKey, SecretKey, fixed_32};
+use super::rsa_key::RsaSecret;
+use crate::{KeyError, Result};
+use pkcs8::der::asn1::{AnyRef, BitStringRef, OctetStringRef};
+use pkcs8::der::{Decode, Encode};
+use pkcs8::pkcs5::pbes2;
+use pkcs8::spki::{AlgorithmIdentifierRef, SubjectPublicKeyInfoRef};
+use pkcs8::{
+    DecodePrivateKey, DecodePublicKey, Document, EncodePrivateKey, EncodePublicKey,
+    EncryptedPrivateKeyInfo, LineEnding, ObjectIdentifier, PrivateKeyInfo, SecretDocument,
+};
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+const OID_ED25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const OID_X25519: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.110");
+const OID_EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const OID_P256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+const OID_P384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.34");
+const OID_RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+
+/// `rsaEncryption` with the NULL parameters RFC 8017 requires
+const RSA_ALGORITHM: AlgorithmIdentifierRef<'static> = AlgorithmIdentifierRef {
+    oid: OID_RSA_ENCRYPTION,
+    parameters: Some(AnyRef::NULL),
+};
+
+const PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+const ENCRYPTED_PRIVATE_KEY_LABEL: &str = "ENCRYPTED PRIVATE KEY";
+const PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+
+/// PBKDF2-SHA256 iterations for encrypted PKCS#8 (OWASP 2023 recommendation)
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
+impl AsymmetricKey {
+    /// Encode as unencrypted PKCS#8 DER
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if encoding fails.
+    pub fn to_pkcs8_der(&self) -> Result<Zeroizing<Vec<u8>>> {
+        let document = self.pkcs8_document()?;
+        Ok(Zeroizing::new(document.as_bytes().to_vec()))
+    }
+
+    /// Encode as unencrypted PKCS#8 PEM (`PRIVATE KEY`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if encoding fails.
+    pub fn to_pkcs8_pem(&self) -> Result<Zeroizing<String>> {
+        self.pkcs8_document()?
+            .to_pem(PRIVATE_KEY_LABEL, LineEnding::LF)
+            .map_err(format_error)
+    }
+
+    /// Decode unencrypted PKCS#8 DER, detecting the algorithm from the key itself
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed input and unsupported
+    /// algorithms or curves.
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self> {
+        let info = PrivateKeyInfo::from_der(der).map_err(format_error)?;
+        let secret = match key_algorithm(info.algorithm)? {
+            AsymmetricAlgorithm::Ed25519 => {
+                let seed = fixed_32(curve_private_key(info.private_key)?)?;
+                SecretKey::Ed25519(ed25519_dalek::SigningKey::from_bytes(&seed))
+            }
+            AsymmetricAlgorithm::X25519 => {
+                let scalar = fixed_32(curve_private_key(info.private_key)?)?;
+                SecretKey::X25519(x25519_dalek::StaticSecret::from(*scalar))
+            }
+            AsymmetricAlgorithm::P256 => {
+                SecretKey::P256(p256::SecretKey::from_pkcs8_der(der).map_err(format_error)?)
+            }
+            AsymmetricAlgorithm::P384 => {
+                SecretKey::P384(p384::SecretKey::from_pkcs8_der(der).map_err(format_error)?)
+            }
+            AsymmetricAlgorithm::Rsa => {
+                SecretKey::Rsa(RsaSecret::from_pkcs1_der(info.private_key)?)
+            }
+        };
+        Ok(Self { secret })
+    }
+
+    /// Decode unencrypted PKCS#8 PEM
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed input, a PEM label other than
+    /// `PRIVATE KEY`, and unsupported algorithms.
+    pub fn from_pkcs8_pem(pem: &str) -> Result<Self> {
+        let (label, document) = SecretDocument::from_pem(pem).map_err(format_error)?;
+        expect_label(label, PRIVATE_KEY_LABEL)?;
+        Self::from_pkcs8_der(document.as_bytes())
+    }
+
+    /// Encode as password-encrypted PKCS#8 DER
+    ///
+    /// Uses PBES2 with PBKDF2-SHA256 (600 000 iterations, random 16-byte salt) and
+    /// AES-256-CBC, which OpenSSL and other PKCS#8 readers accept.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::EncryptionFailed` if encryption fails.
+    pub fn to_encrypted_pkcs8_der(&self, password: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let document = self.encrypted_pkcs8_document(password)?;
+        Ok(Zeroizing::new(document.as_bytes().to_vec()))
+    }
+
+    /// Encode as password-encrypted PKCS#8 PEM (`ENCRYPTED PRIVATE KEY`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::EncryptionFailed` if encryption fails.
+    pub fn to_encrypted_pkcs8_pem(&self, password: &[u8]) -> Result<Zeroizing<String>> {
+        self.encrypted_pkcs8_document(password)?
+            .to_pem(ENCRYPTED_PRIVATE_KEY_LABEL, LineEnding::LF)
+            .map_err(format_error)
+    }
+
+    /// Decode password-encrypted PKCS#8 DER
+    ///
+    /// Any PBES2 scheme supported by the `pkcs5` crate is accepted, including
+    /// scrypt-based files written by other tools.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::DecryptionFailed` for a wrong password and
+    /// `KeyError::InvalidKeyFormat` for malformed input.
+    pub fn from_encrypted_pkcs8_der(der: &[u8], password: &[u8]) -> Result<Self> {
+        let info = EncryptedPrivateKeyInfo::from_der(der).map_err(format_error)?;
+        let document = info.decrypt(password).map_err(|e| {
+            KeyError::DecryptionFailed(format!("Encrypted PKCS#8 decryption failed: {e}"))
+        })?;
+        Self::from_pkcs8_der(document.as_bytes())
+    }
+
+    /// Decode password-encrypted PKCS#8 PEM
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::DecryptionFailed` for a wrong password and
+    /// `KeyError::InvalidKeyFormat` for malformed input or an unexpected PEM label.
+    pub fn from_encrypted_pkcs8_pem(pem: &str, password: &[u8]) -> Result<Self> {
+        let (label, document) = Document::from_pem(pem).map_err(format_error)?;
+        expect_label(label, ENCRYPTED_PRIVATE_KEY_LABEL)?;
+        Self::from_encrypted_pkcs8_der(document.as_bytes(), password)
+    }
+
+    fn pkcs8_document(&self) -> Result<SecretDocument> {
+        match &self.secret {
+            SecretKey::Ed25519(key) => curve_pkcs8(OID_ED25519, &key.to_bytes()),
+            SecretKey::X25519(key) => curve_pkcs8(OID_X25519, &key.to_bytes()),
+            SecretKey::P256(key) => key.to_pkcs8_der().map_err(format_error),
+            SecretKey::P384(key) => key.to_pkcs8_der().map_err(format_error),
+            SecretKey::Rsa(key) => {
+                SecretDocument::encode_msg(&PrivateKeyInfo::new(RSA_ALGORITHM, key.pkcs1_der()))
+                    .map_err(format_error)
+            }
+        }
+    }
+
+    fn encrypted_pkcs8_document(&self, password: &[u8]) -> Result<SecretDocument> {
+        let mut salt = [0u8; 16];
+        let mut iv = [0u8; 16];
+        rand::rng().fill_bytes(&mut salt);
+        rand::rng().fill_bytes(&mut iv);
+
+        let params = pbes2::Parameters::pbkdf2_sha256_aes256cbc(PBKDF2_ITERATIONS, &salt, &iv)
+            .map_err(|e| KeyError::EncryptionFailed(format!("Invalid PBES2 parameters: {e}")))?;
+
+        let document = self.pkcs8_document()?;
+        PrivateKeyInfo::from_der(document.as_bytes())
+            .map_err(format_error)?
+            .encrypt_with_params(params, password)
+            .map_err(|e| KeyError::EncryptionFailed(format!("PKCS#8 encryption failed: {e}")))
+    }
+}
+
+impl AsymmetricPublicKey {
+    /// Encode as SPKI DER
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if encoding fails.
+    pub fn to_spki_der(&self) -> Result<Vec<u8>> {
+        Ok(self.spki_document()?.as_bytes().to_vec())
+    }
+
+    /// Encode as SPKI PEM (`PUBLIC KEY`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if encoding fails.
+    pub fn to_spki_pem(&self) -> Result<String> {
+        self.spki_document()?
+            .to_pem(PUBLIC_KEY_LABEL, LineEnding::LF)
+            .map_err(format_error)
+    }
+
+    /// Decode SPKI DER, detecting the algorithm from the key itself
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed input and unsupported
+    /// algorithms, and `KeyError::InvalidKey` for invalid points.
+    pub fn from_spki_der(der: &[u8]) -> Result<Self> {
+        let info = SubjectPublicKeyInfoRef::from_der(der).map_err(format_error)?;
+        let public = match key_algorithm(info.algorithm)? {
+            algorithm @ (AsymmetricAlgorithm::Ed25519
+            | AsymmetricAlgorithm::X25519
+            | AsymmetricAlgorithm::Rsa) => {
+                let bytes = info
+                    .subject_public_key
+                    .as_bytes()
+                    .ok_or_else(|| KeyError::InvalidKeyFormat("SPKI key has unused bits".into()))?;
+                return Self::from_bytes(algorithm, bytes);
+            }
+            AsymmetricAlgorithm::P256 => {
+                PublicKey::P256(p256::PublicKey::from_public_key_der(der).map_err(format_error)?)
+            }
+            AsymmetricAlgorithm::P384 => {
+                PublicKey::P384(p384::PublicKey::from_public_key_der(der).map_err(format_error)?)
+            }
+        };
+        Ok(Self { public })
+    }
+
+    /// Decode SPKI PEM
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed input, a PEM label other than
+    /// `PUBLIC KEY`, and unsupported algorithms.
+    pub fn from_spki_pem(pem: &str) -> Result<Self> {
+        let (label, document) = Document::from_pem(pem).map_err(format_error)?;
+        expect_label(label, PUBLIC_KEY_LABEL)?;
+        Self::from_spki_der(document.as_bytes())
+    }
+
+    fn spki_document(&self) -> Result<Document> {
+        match &self.public {
+            PublicKey::Ed25519(key) => curve_spki(OID_ED25519, key.as_bytes()),
+            PublicKey::X25519(key) => curve_spki(OID_X25519, key.as_bytes()),
+            PublicKey::P256(key) => key.to_public_key_der().map_err(format_error),
+            PublicKey::P384(key) => key.to_public_key_der().map_err(format_error),
+            PublicKey::Rsa(key) => encode_spki(RSA_ALGORITHM, key.pkcs1_der()),
+        }
+    }
+}
+
+/// Identify the supported algorithm named by a PKCS#8 or SPKI algorithm identifier
+fn key_algorithm(algorithm: AlgorithmIdentifierRef<'_>) -> Result<AsymmetricAlgorithm> {
+    if algorithm.oid == OID_ED25519 {
+        Ok(AsymmetricAlgorithm::Ed25519)
+    } else if algorithm.oid == OID_X25519 {
+        Ok(AsymmetricAlgorithm::X25519)
+    } else if algorithm.oid == OID_RSA_ENCRYPTION {
+        Ok(AsymmetricAlgorithm::Rsa)
+    } else if algorithm.oid == OID_EC_PUBLIC_KEY {
+        let curve = algorithm.parameters_oid().map_err(format_error)?;
+        if curve == OID_P256 {
+            Ok(AsymmetricAlgorithm::P256)
+        } else if curve == OID_P384 {
+            Ok(AsymmetricAlgorithm::P384)
+        } else {
+            Err(KeyError::InvalidKeyFormat(format!(
+                "Unsupported elliptic curve {curve}"
+            )))
+        }
+    } else {
+        Err(KeyError::InvalidKeyFormat(format!(
+            "Unsupported key algorithm {}",
+            algorithm.oid
+        )))
+    }
+}
+
+/// RFC 8410 private key: the `CurvePrivateKey` octet string nested in the PKCS#8 field
+fn curve_pkcs8(oid: ObjectIdentifier, secret: &[u8]) -> Result<SecretDocument> {
+    let octets = OctetStringRef::new(secret).map_err(format_error)?;
+    let inner = Zeroizing::new(octets.to_der().map_err(format_error)?);
+    let algorithm = AlgorithmIdentifierRef {
+        oid,
+        parameters: None,
+    };
+    SecretDocument::encode_msg(&PrivateKeyInfo::new(algorithm, &inner)).map_err(format_error)
+}
+
+fn curve_private_key(private_key: &[u8]) -> Result<&[u8]> {
+    let octets = OctetStringRef::from_der(private_key).map_err(format_error)?;
+    Ok(octets.as_bytes())
+}
+
+/// RFC 8410 public key: the raw key as the SPKI bit string, with absent parameters
+fn curve_spki(oid: ObjectIdentifier, public: &[u8]) -> Result<Document> {
+    let algorithm = AlgorithmIdentifierRef {
+        oid,
+        parameters: None,
+    };
+    encode_spki(algorithm, public)
+}
+
+fn encode_spki(algorithm: AlgorithmIdentifierRef<'_>, public: &[u8]) -> Result<Document> {
+    let info = SubjectPublicKeyInfoRef {
+        algorithm,
+        subject_public_key: BitStringRef::from_bytes(public).map_err(format_error)?,
+    };
+    Document::encode_msg(&info).map_err(format_error)
+}
+
+fn expect_label(label: &str, expected: &str) -> Result<()> {
+    if label == expected {
+        Ok(())
+    } else {
+        Err(KeyError::InvalidKeyFormat(format!(
+            "Expected PEM label {expected}, found {label}"
+        )))
+    }
+}
+
+fn format_error(error: impl std::fmt::Display) -> KeyError {
+    KeyError::InvalidKeyFormat(error.to_string())
+}
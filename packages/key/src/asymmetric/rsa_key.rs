@@ -0,0 +1,238 @@
+//! RSA keys (RFC 8017), held as canonical PKCS#1 DER
+
+use crate::{KeyError, Result};
+use rsa::pkcs1::der::asn1::UintRef;
+use rsa::pkcs1::der::{Decode, Encode};
+use rsa::pkcs1::{
+    DecodeRsaPrivateKey, DecodeRsaPublicKey, EncodeRsaPrivateKey, EncodeRsaPublicKey,
+};
+use subtle::{Choice, ConditionallySelectable};
+use zeroize::Zeroizing;
+
+/// Modulus size in bits of RSA keys from
+/// [`AsymmetricKey::generate`](super::AsymmetricKey::generate)
+pub const RSA_DEFAULT_BITS: usize = 3072;
+
+/// Modulus sizes accepted by [`AsymmetricKey::generate_rsa`](super::AsymmetricKey::generate_rsa)
+pub const RSA_GENERATE_BITS: [usize; 3] = [2048, 3072, 4096];
+
+/// Smallest modulus accepted on import, as in OpenSSH
+const RSA_MIN_BITS: usize = 2048;
+
+/// RSA private key as PKCS#1 `RSAPrivateKey` DER, with its public half
+#[derive(Clone)]
+pub(super) struct RsaSecret {
+    pkcs1: Zeroizing<Vec<u8>>,
+    public: RsaPublic,
+}
+
+/// RSA public key as PKCS#1 `RSAPublicKey` DER
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct RsaPublic {
+    pkcs1: Vec<u8>,
+}
+
+/// Private key members as unsigned big-endian integers without leading zeros
+pub(super) struct RsaPrivateParts {
+    pub n: Vec<u8>,
+    pub e: Vec<u8>,
+    pub d: Zeroizing<Vec<u8>>,
+    pub p: Zeroizing<Vec<u8>>,
+    pub q: Zeroizing<Vec<u8>>,
+    pub dp: Zeroizing<Vec<u8>>,
+    pub dq: Zeroizing<Vec<u8>>,
+    pub qi: Zeroizing<Vec<u8>>,
+}
+
+impl RsaSecret {
+    pub(super) fn generate(bits: usize) -> Result<Self> {
+        if !RSA_GENERATE_BITS.contains(&bits) {
+            return Err(KeyError::InvalidKeySize {
+                expected: RSA_DEFAULT_BITS,
+                actual: bits,
+            });
+        }
+        let key = rsa::RsaPrivateKey::new(&mut rand::rng(), bits)
+            .map_err(|e| KeyError::KeyGeneration(format!("RSA key generation failed: {e}")))?;
+        Self::from_key(&key)
+    }
+
+    /// Parse PKCS#1 DER, checking the key and re-encoding it with recomputed CRT values
+    pub(super) fn from_pkcs1_der(der: &[u8]) -> Result<Self> {
+        let key = rsa::RsaPrivateKey::from_pkcs1_der(der)
+            .map_err(|e| KeyError::InvalidKey(format!("Invalid RSA private key: {e}")))?;
+        Self::from_key(&key)
+    }
+
+    pub(super) fn from_parts(parts: &RsaPrivateParts) -> Result<Self> {
+        let key = rsa::pkcs1::RsaPrivateKey {
+            modulus: uint(&parts.n)?,
+            public_exponent: uint(&parts.e)?,
+            private_exponent: uint(&parts.d)?,
+            prime1: uint(&parts.p)?,
+            prime2: uint(&parts.q)?,
+            exponent1: uint(&parts.dp)?,
+            exponent2: uint(&parts.dq)?,
+            coefficient: uint(&parts.qi)?,
+            other_prime_infos: None,
+        };
+        let der = Zeroizing::new(key.to_der().map_err(format_error)?);
+        Self::from_pkcs1_der(&der)
+    }
+
+    /// Rebuild from the members OpenSSH stores, which omit the CRT exponents
+    pub(super) fn from_ssh_parts(
+        n: &[u8],
+        e: &[u8],
+        d: &[u8],
+        p: &[u8],
+        q: &[u8],
+        iqmp: &[u8],
+    ) -> Result<Self> {
+        let secret = |bytes: &[u8]| Zeroizing::new(bytes.to_vec());
+        Self::from_parts(&RsaPrivateParts {
+            n: n.to_vec(),
+            e: e.to_vec(),
+            d: secret(d),
+            p: secret(p),
+            q: secret(q),
+            dp: reduce(d, &minus_one(p)?),
+            dq: reduce(d, &minus_one(q)?),
+            qi: secret(iqmp),
+        })
+    }
+
+    fn from_key(key: &rsa::RsaPrivateKey) -> Result<Self> {
+        let pkcs1 = key.to_pkcs1_der().map_err(format_error)?;
+        let public = RsaPublic::from_key(&rsa::RsaPublicKey::from(key))?;
+        Ok(Self {
+            pkcs1: Zeroizing::new(pkcs1.as_bytes().to_vec()),
+            public,
+        })
+    }
+
+    pub(super) fn pkcs1_der(&self) -> &[u8] {
+        &self.pkcs1
+    }
+
+    pub(super) fn public(&self) -> &RsaPublic {
+        &self.public
+    }
+
+    pub(super) fn parts(&self) -> Result<RsaPrivateParts> {
+        let key = rsa::pkcs1::RsaPrivateKey::from_der(&self.pkcs1).map_err(format_error)?;
+        let secret = |value: UintRef<'_>| Zeroizing::new(value.as_bytes().to_vec());
+        Ok(RsaPrivateParts {
+            n: key.modulus.as_bytes().to_vec(),
+            e: key.public_exponent.as_bytes().to_vec(),
+            d: secret(key.private_exponent),
+            p: secret(key.prime1),
+            q: secret(key.prime2),
+            dp: secret(key.exponent1),
+            dq: secret(key.exponent2),
+            qi: secret(key.coefficient),
+        })
+    }
+}
+
+impl RsaPublic {
+    pub(super) fn from_pkcs1_der(der: &[u8]) -> Result<Self> {
+        let key = rsa::RsaPublicKey::from_pkcs1_der(der)
+            .map_err(|e| KeyError::InvalidKey(format!("Invalid RSA public key: {e}")))?;
+        Self::from_key(&key)
+    }
+
+    pub(super) fn from_parts(n: &[u8], e: &[u8]) -> Result<Self> {
+        let key = rsa::pkcs1::RsaPublicKey {
+            modulus: uint(n)?,
+            public_exponent: uint(e)?,
+        };
+        Self::from_pkcs1_der(&key.to_der().map_err(format_error)?)
+    }
+
+    fn from_key(key: &rsa::RsaPublicKey) -> Result<Self> {
+        let pkcs1 = key.to_pkcs1_der().map_err(format_error)?;
+        let public = Self {
+            pkcs1: pkcs1.as_bytes().to_vec(),
+        };
+
+        let (n, _) = public.parts()?;
+        let leading = n.first().map_or(8, |byte| byte.leading_zeros() as usize);
+        let bits = (n.len() * 8).saturating_sub(leading);
+        if bits < RSA_MIN_BITS {
+            return Err(KeyError::InvalidKey(format!(
+                "RSA modulus of {bits} bits is below the {RSA_MIN_BITS}-bit minimum"
+            )));
+        }
+        Ok(public)
+    }
+
+    pub(super) fn pkcs1_der(&self) -> &[u8] {
+        &self.pkcs1
+    }
+
+    /// Modulus and public exponent
+    pub(super) fn parts(&self) -> Result<(Vec<u8>, Vec<u8>)> {
+        let key = rsa::pkcs1::RsaPublicKey::from_der(&self.pkcs1).map_err(format_error)?;
+        Ok((
+            key.modulus.as_bytes().to_vec(),
+            key.public_exponent.as_bytes().to_vec(),
+        ))
+    }
+}
+
+fn uint(bytes: &[u8]) -> Result<UintRef<'_>> {
+    UintRef::new(bytes).map_err(format_error)
+}
+
+/// `prime - 1` for an odd prime, which only touches the last byte
+fn minus_one(prime: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+    let mut out = Zeroizing::new(prime.to_vec());
+    match out.last_mut() {
+        Some(last) if *last & 1 == 1 => *last -= 1,
+        _ => return Err(KeyError::InvalidKey("RSA prime is not odd".into())),
+    }
+    Ok(out)
+}
+
+/// `value mod modulus` over unsigned big-endian integers
+///
+/// `value` is a private exponent, so the running time depends only on the input lengths:
+/// each bit shifts into the remainder and the modulus is subtracted under a mask.
+#[allow(clippy::cast_possible_truncation)] // Byte arithmetic keeps the low 8 bits
+fn reduce(value: &[u8], modulus: &[u8]) -> Zeroizing<Vec<u8>> {
+    // The spare leading byte holds the doubled remainder before the subtraction
+    let mut remainder = Zeroizing::new(vec![0u8; modulus.len() + 1]);
+    let mut difference = Zeroizing::new(vec![0u8; modulus.len() + 1]);
+
+    for byte in value {
+        for shift in (0..8).rev() {
+            let mut carry = (*byte >> shift) & 1;
+            for limb in remainder.iter_mut().rev() {
+                let high = *limb >> 7;
+                *limb = (*limb << 1) | carry;
+                carry = high;
+            }
+
+            let mut borrow = 0u16;
+            for (index, out) in difference.iter_mut().enumerate().rev() {
+                let subtrahend = if index == 0 { 0 } else { modulus[index - 1] };
+                let wide = u16::from(remainder[index])
+                    .wrapping_sub(u16::from(subtrahend))
+                    .wrapping_sub(borrow);
+                *out = wide as u8;
+                borrow = (wide >> 8) & 1;
+            }
+
+            let fits = Choice::from((borrow as u8) ^ 1);
+            for (limb, reduced) in remainder.iter_mut().zip(difference.iter()) {
+                limb.conditional_assign(reduced, fits);
+            }
+        }
+    }
+    Zeroizing::new(remainder[1..].to_vec())
+}
+
+fn format_error(error: impl std::fmt::Display) -> KeyError {
+    KeyError::InvalidKeyFormat(error.to_string())
+}
@@ -99,6 +99,15 @@ pub enum KeyError {
         /// Connection failure details
         details: String,
     },
+
+    /// Key usage rejected by the key's policy
+    #[error("Key policy violation for {key_id}: {violation}")]
+    PolicyViolation {
+        /// Key identifier
+        key_id: String,
+        /// The rule that was broken
+        violation: crate::policy::PolicyViolation,
+    },
 }
 
 impl KeyError {
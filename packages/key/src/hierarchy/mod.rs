@@ -0,0 +1,288 @@
+//! Hierarchical deterministic key derivation
+//!
+//! A [`KeyHierarchy`] derives child keys from one root key along labelled paths such as
+//! `root/tenant:42/purpose:db-encryption/v3`. Every node holds a 64-byte chain key; each
+//! path segment advances the chain key through a one-way KDF step (HKDF-SHA512 or BLAKE3
+//! `derive_key`) with the segment length-prefixed and domain-separated. Exported keys are
+//! derived from a node's chain key by a separate export step, so a leaked leaf key reveals
+//! neither its parent nor its siblings, and a delegated subtree reveals nothing above it.
+//!
+//! ```rust,ignore
+//! use cryypt_key::hierarchy::{DerivationAlgorithm, KeyHierarchy};
+//!
+//! let hierarchy = KeyHierarchy::new(&master_key, DerivationAlgorithm::HkdfSha512)?;
+//! let db_key = hierarchy.derive("root/tenant:42/purpose:db-encryption/v3")?;
+//!
+//! // Hand one tenant its own subtree without exposing the others
+//! let tenant = hierarchy.subtree("tenant:42")?;
+//! assert_eq!(tenant.derive("purpose:db-encryption/v3")?.bytes(), db_key.bytes());
+//! ```
+
+mod path;
+
+pub use path::KeyPath;
+
+use crate::api::ActualKey;
+use crate::{KeyError, Result};
+use hkdf::Hkdf;
+use sha2::Sha512;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use zeroize::Zeroizing;
+
+/// Length of the chain key held by every node
+const CHAIN_KEY_LEN: usize = 64;
+
+/// Minimum accepted root key length in bytes
+const MIN_ROOT_KEY_LEN: usize = 32;
+
+/// Default length of exported keys in bytes
+const DEFAULT_KEY_LEN: usize = 32;
+
+/// Supported exported key lengths in bytes
+const KEY_LEN_RANGE: std::ops::RangeInclusive<usize> = 16..=64;
+
+/// HKDF salt binding derivations to this construction
+const HKDF_SALT: &[u8] = b"cryypt_key hierarchy v1";
+
+/// BLAKE3 `derive_key` contexts, one per derivation step
+const BLAKE3_ROOT_CONTEXT: &str = "cryypt_key 2026-10-18 key hierarchy root";
+const BLAKE3_CHILD_CONTEXT: &str = "cryypt_key 2026-10-18 key hierarchy child";
+const BLAKE3_EXPORT_CONTEXT: &str = "cryypt_key 2026-10-18 key hierarchy export";
+
+type ChainKey = Zeroizing<[u8; CHAIN_KEY_LEN]>;
+
+/// KDF used for each derivation step
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DerivationAlgorithm {
+    /// HKDF with SHA-512 (RFC 5869)
+    #[default]
+    HkdfSha512,
+    /// BLAKE3 in `derive_key` mode
+    Blake3,
+}
+
+impl DerivationAlgorithm {
+    fn root(self, root_key: &[u8]) -> Result<ChainKey> {
+        let mut chain = Zeroizing::new([0u8; CHAIN_KEY_LEN]);
+        match self {
+            Self::HkdfSha512 => hkdf_expand(root_key, &[b"root"], chain.as_mut_slice())?,
+            Self::Blake3 => blake3::Hasher::new_derive_key(BLAKE3_ROOT_CONTEXT)
+                .update(root_key)
+                .finalize_xof()
+                .fill(chain.as_mut_slice()),
+        }
+        Ok(chain)
+    }
+
+    fn child(self, parent: &ChainKey, segment: &str) -> Result<ChainKey> {
+        // Segments are at most 255 bytes (see `KeyPath`), so the prefix is unambiguous
+        let len = [u8::try_from(segment.len()).unwrap_or(u8::MAX)];
+        let mut chain = Zeroizing::new([0u8; CHAIN_KEY_LEN]);
+        match self {
+            Self::HkdfSha512 => hkdf_expand(
+                parent.as_slice(),
+                &[b"child", &len, segment.as_bytes()],
+                chain.as_mut_slice(),
+            )?,
+            Self::Blake3 => blake3::Hasher::new_derive_key(BLAKE3_CHILD_CONTEXT)
+                .update(parent.as_slice())
+                .update(&len)
+                .update(segment.as_bytes())
+                .finalize_xof()
+                .fill(chain.as_mut_slice()),
+        }
+        Ok(chain)
+    }
+
+    fn export(self, node: &ChainKey, key_len: usize) -> Result<ActualKey> {
+        // The length is bound in so shorter keys are not prefixes of longer ones
+        let len = [u8::try_from(key_len).unwrap_or(u8::MAX)];
+        let mut key = vec![0u8; key_len];
+        match self {
+            Self::HkdfSha512 => hkdf_expand(node.as_slice(), &[b"export", &len], &mut key)?,
+            Self::Blake3 => blake3::Hasher::new_derive_key(BLAKE3_EXPORT_CONTEXT)
+                .update(node.as_slice())
+                .update(&len)
+                .finalize_xof()
+                .fill(&mut key),
+        }
+        Ok(ActualKey::from_bytes(key))
+    }
+}
+
+fn hkdf_expand(ikm: &[u8], info: &[&[u8]], out: &mut [u8]) -> Result<()> {
+    Hkdf::<Sha512>::new(Some(HKDF_SALT), ikm)
+        .expand_multi_info(info, out)
+        .map_err(|e| KeyError::KeyDerivation(format!("HKDF-SHA512 expansion failed: {e}")))
+}
+
+/// Deterministic derivation tree rooted at one key
+///
+/// Derived chain keys are cached per node and zeroized when evicted with
+/// [`clear_cache`](Self::clear_cache) or when the hierarchy is dropped.
+pub struct KeyHierarchy {
+    algorithm: DerivationAlgorithm,
+    key_len: usize,
+    base: KeyPath,
+    cache: Mutex<HashMap<KeyPath, ChainKey>>,
+}
+
+impl KeyHierarchy {
+    /// Hierarchy rooted at a generated or retrieved key
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeySize` if the key is shorter than 32 bytes.
+    pub fn new(root_key: &ActualKey, algorithm: DerivationAlgorithm) -> Result<Self> {
+        Self::from_bytes(root_key.bytes(), algorithm)
+    }
+
+    /// Hierarchy rooted at raw key material
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeySize` if the key is shorter than 32 bytes.
+    pub fn from_bytes(root_key: &[u8], algorithm: DerivationAlgorithm) -> Result<Self> {
+        if root_key.len() < MIN_ROOT_KEY_LEN {
+            return Err(KeyError::InvalidKeySize {
+                expected: MIN_ROOT_KEY_LEN,
+                actual: root_key.len(),
+            });
+        }
+        Ok(Self::with_root(
+            algorithm,
+            DEFAULT_KEY_LEN,
+            KeyPath::root(),
+            algorithm.root(root_key)?,
+        ))
+    }
+
+    fn with_root(
+        algorithm: DerivationAlgorithm,
+        key_len: usize,
+        base: KeyPath,
+        root: ChainKey,
+    ) -> Self {
+        Self {
+            algorithm,
+            key_len,
+            base,
+            cache: Mutex::new(HashMap::from([(KeyPath::root(), root)])),
+        }
+    }
+
+    /// Set the length of exported keys (16 to 64 bytes, default 32)
+    ///
+    /// The length is part of the derivation, so keys of different lengths at the same path
+    /// are unrelated.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeySize` if the length is out of range.
+    pub fn with_key_len(mut self, key_len: usize) -> Result<Self> {
+        if !KEY_LEN_RANGE.contains(&key_len) {
+            return Err(KeyError::InvalidKeySize {
+                expected: DEFAULT_KEY_LEN,
+                actual: key_len,
+            });
+        }
+        self.key_len = key_len;
+        Ok(self)
+    }
+
+    /// KDF used for each derivation step
+    #[must_use]
+    pub fn algorithm(&self) -> DerivationAlgorithm {
+        self.algorithm
+    }
+
+    /// Path of this hierarchy's root within the hierarchy it was delegated from
+    #[must_use]
+    pub fn base(&self) -> &KeyPath {
+        &self.base
+    }
+
+    /// Derive the key at `path`, e.g. `root/tenant:42/purpose:db-encryption/v3`
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` if the path is malformed.
+    pub fn derive(&self, path: &str) -> Result<ActualKey> {
+        self.derive_path(&KeyPath::parse(path)?)
+    }
+
+    /// Derive the key at a parsed path
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::Internal` if the cache lock is poisoned.
+    pub fn derive_path(&self, path: &KeyPath) -> Result<ActualKey> {
+        let node = self.chain_key(path)?;
+        self.algorithm.export(&node, self.key_len)
+    }
+
+    /// Hierarchy rooted at the node `path`, for delegating a subtree
+    ///
+    /// Keys derived from the subtree equal those derived from this hierarchy at the joined
+    /// path, while nothing outside the subtree can be derived from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` if the path is malformed.
+    pub fn subtree(&self, path: &str) -> Result<Self> {
+        let path = KeyPath::parse(path)?;
+        let node = self.chain_key(&path)?;
+        Ok(Self::with_root(
+            self.algorithm,
+            self.key_len,
+            self.base.join(&path),
+            node,
+        ))
+    }
+
+    /// Zeroize and drop all cached chain keys except the root
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::Internal` if the cache lock is poisoned.
+    pub fn clear_cache(&self) -> Result<()> {
+        self.lock_cache()?.retain(|path, _| path.is_root());
+        Ok(())
+    }
+
+    /// Chain key of `path`, walking down from the deepest cached ancestor
+    fn chain_key(&self, path: &KeyPath) -> Result<ChainKey> {
+        let mut cache = self.lock_cache()?;
+        let segments = path.segments();
+
+        // The root is always cached, so the search stops at depth zero at the latest
+        let (mut depth, mut node) = (0..=segments.len())
+            .rev()
+            .find_map(|depth| Some((depth, cache.get(&path.prefix(depth))?.clone())))
+            .ok_or_else(|| KeyError::Internal("Key hierarchy root missing".into()))?;
+
+        while depth < segments.len() {
+            node = self.algorithm.child(&node, &segments[depth])?;
+            depth += 1;
+            cache.insert(path.prefix(depth), node.clone());
+        }
+        Ok(node)
+    }
+
+    fn lock_cache(&self) -> Result<std::sync::MutexGuard<'_, HashMap<KeyPath, ChainKey>>> {
+        self.cache
+            .lock()
+            .map_err(|_| KeyError::Internal("Key hierarchy cache lock poisoned".into()))
+    }
+}
+
+impl std::fmt::Debug for KeyHierarchy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyHierarchy")
+            .field("algorithm", &self.algorithm)
+            .field("key_len", &self.key_len)
+            .field("base", &self.base.to_string())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,112 @@
+//! Labelled derivation paths
+
+use crate::{KeyError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// Maximum length of a single path segment in bytes
+const MAX_SEGMENT_LEN: usize = 255;
+
+/// Path from the hierarchy root to a node, e.g. `root/tenant:42/purpose:db-encryption/v3`
+///
+/// Segments are opaque labels; the `name:value` form is a convention, not a requirement.
+/// A leading `root` segment is optional and refers to the hierarchy's own root.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct KeyPath {
+    segments: Vec<String>,
+}
+
+impl KeyPath {
+    /// The root of the hierarchy
+    #[must_use]
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Parse a `/`-separated path
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` if a segment is empty, too long, or contains control
+    /// characters.
+    pub fn parse(path: &str) -> Result<Self> {
+        let mut segments = path.split('/').peekable();
+        if segments.peek() == Some(&"root") {
+            segments.next();
+        }
+        segments.try_fold(Self::root(), |parent, segment| parent.child(segment))
+    }
+
+    /// Path of the child `segment` below this one
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` if the segment is empty, too long, contains `/` or
+    /// control characters.
+    pub fn child(mut self, segment: &str) -> Result<Self> {
+        validate_segment(segment)?;
+        self.segments.push(segment.to_string());
+        Ok(self)
+    }
+
+    /// Segments below the root
+    #[must_use]
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// This path followed by `other`
+    #[must_use]
+    pub fn join(&self, other: &KeyPath) -> Self {
+        let mut joined = self.clone();
+        joined.segments.extend_from_slice(&other.segments);
+        joined
+    }
+
+    /// The first `depth` segments of this path
+    pub(super) fn prefix(&self, depth: usize) -> Self {
+        Self {
+            segments: self.segments[..depth].to_vec(),
+        }
+    }
+
+    /// Whether this is the root path
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+impl fmt::Display for KeyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("root")?;
+        for segment in &self.segments {
+            write!(f, "/{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for KeyPath {
+    type Err = KeyError;
+
+    fn from_str(path: &str) -> Result<Self> {
+        Self::parse(path)
+    }
+}
+
+fn validate_segment(segment: &str) -> Result<()> {
+    if segment.is_empty() || segment.len() > MAX_SEGMENT_LEN {
+        return Err(KeyError::InvalidKey(format!(
+            "Key path segments must be 1 to {MAX_SEGMENT_LEN} bytes, got {}",
+            segment.len()
+        )));
+    }
+    if segment.contains('/') || segment.chars().any(char::is_control) {
+        return Err(KeyError::InvalidKey(format!(
+            "Invalid character in key path segment '{}'",
+            segment.escape_debug()
+        )));
+    }
+    Ok(())
+}
@@ -5,8 +5,15 @@
 //! ## Features
 //!
 //! - **Key Generation**: Secure key generation with entropy sources
-//! - **Key Storage**: File-based and keychain storage backends
+//! - **Key Storage**: File-based, keychain and PKCS#11 (HSM) storage backends
 //! - **Key Retrieval**: Version-based key retrieval
+//! - **Secret Sharing**: Shamir k-of-n splitting and recovery of master keys
+//! - **Key Wrapping**: AES Key Wrap (RFC 3394/5649) and KEK-versioned envelope keys
+//! - **Asymmetric Keys**: Ed25519, X25519, P-256/P-384 with PEM/DER/JWK/OpenSSH encodings
+//! - **Typed Key Pairs**: ML-KEM, ML-DSA and classical key pairs stored with their algorithm
+//! - **Key Hierarchies**: Deterministic per-tenant and per-purpose subkeys along labelled paths
+//! - **Key Rotation**: Policy-driven version rotation with lifecycle states
+//! - **Usage Policies**: Per-key operation, algorithm, expiry, use and caller restrictions
 //! - **True Async**: Channel-based async operations
 //!
 //! ## Quick Start
@@ -37,15 +44,22 @@
 mod result_macro;
 
 pub mod api;
+pub mod asymmetric;
 pub mod bits_macro;
 pub mod entropy;
 pub mod error;
+pub mod hierarchy;
 pub mod key_id;
 pub mod key_result;
+pub mod policy;
+pub mod rotation;
+pub mod sharing;
 pub mod storage_status;
 pub mod store;
 pub mod store_results;
+pub mod stored_key;
 pub mod traits;
+pub mod wrap;
 
 // Re-export core types
 pub use error::{KeyError, Result};
@@ -65,6 +79,15 @@ pub use store::FileKeyStore;
 pub use api::{KeyGenerator, KeyRetriever, key_retriever::SecureRetrievedKey};
 pub use storage_status::{StorageOperationStatus, StorageStatusTracking};
 pub use traits::KeyStorage;
+pub use asymmetric::{
+    AsymmetricAlgorithm, AsymmetricKey, AsymmetricPublicKey, EcCurve, RSA_DEFAULT_BITS,
+    RSA_GENERATE_BITS,
+};
+pub use hierarchy::{DerivationAlgorithm, KeyHierarchy, KeyPath};
+pub use policy::{KeyOperation, KeyPolicy, KeyUsage, PolicyViolation};
+pub use rotation::{KeyRotation, KeyState, RotationPolicy, VersionedKey};
+pub use stored_key::{StoredKeyKind, StoredKeyPair};
+pub use wrap::{DataKey, EnvelopeKey, KeyWrap, WrappedKey};
 
 /// Main entry point - README.md pattern: Key operations
 pub struct Key;
@@ -0,0 +1,168 @@
+//! Key usage policies
+//!
+//! A [`KeyPolicy`] restricts what a stored key may be used for: operations, algorithms,
+//! an expiry time, a use budget and the namespaces of callers allowed to retrieve it.
+//! Stores that support policies check the caller's declared [`KeyUsage`] on every
+//! retrieval and reject mismatches with [`KeyError::PolicyViolation`](crate::KeyError).
+
+mod usage;
+
+pub use usage::{KeyOperation, KeyUsage};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// Restrictions attached to a stored key
+///
+/// Every limit is optional. A policy that restricts operations, algorithms or callers
+/// can only be satisfied by a retrieval that declares a matching [`KeyUsage`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyPolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    operations: Option<BTreeSet<KeyOperation>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    algorithms: Option<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_uses: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    callers: Option<BTreeSet<String>>,
+}
+
+/// Why a retrieval was refused
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PolicyViolation {
+    /// The policy restricts usage but the caller did not declare it
+    #[error("usage must be declared ({0} is restricted)")]
+    UsageNotDeclared(&'static str),
+
+    /// The declared operation is not allowed
+    #[error("operation '{0}' is not allowed")]
+    OperationNotAllowed(KeyOperation),
+
+    /// The declared algorithm is not allowed
+    #[error("algorithm '{0}' is not allowed")]
+    AlgorithmNotAllowed(String),
+
+    /// The calling namespace is not allowed
+    #[error("caller '{0}' is not allowed")]
+    CallerNotAllowed(String),
+
+    /// The key expired
+    #[error("key expired at {0}")]
+    Expired(DateTime<Utc>),
+
+    /// The key's use budget is spent
+    #[error("key has been used {0} times, the maximum allowed")]
+    UsesExhausted(u64),
+}
+
+impl KeyPolicy {
+    /// Policy with no restrictions
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow only the given operations
+    #[must_use]
+    pub fn allow_operations(mut self, operations: impl IntoIterator<Item = KeyOperation>) -> Self {
+        self.operations = Some(operations.into_iter().collect());
+        self
+    }
+
+    /// Allow only the given algorithms (compared case-insensitively)
+    #[must_use]
+    pub fn allow_algorithms<S: AsRef<str>>(
+        mut self,
+        algorithms: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.algorithms = Some(
+            algorithms
+                .into_iter()
+                .map(|a| a.as_ref().to_ascii_lowercase())
+                .collect(),
+        );
+        self
+    }
+
+    /// Allow only callers declaring one of the given namespaces
+    #[must_use]
+    pub fn allow_callers<S: Into<String>>(
+        mut self,
+        namespaces: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.callers = Some(namespaces.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Refuse retrieval from `expires_at` onwards
+    #[must_use]
+    pub fn expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Allow at most `max_uses` retrievals
+    #[must_use]
+    pub fn max_uses(mut self, max_uses: u64) -> Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Whether retrievals must be counted to enforce this policy
+    #[must_use]
+    pub fn counts_uses(&self) -> bool {
+        self.max_uses.is_some()
+    }
+
+    /// Check a retrieval against the policy, given how often the key was already used
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`PolicyViolation`] found.
+    pub fn check(
+        &self,
+        usage: Option<&KeyUsage>,
+        uses: u64,
+        now: DateTime<Utc>,
+    ) -> std::result::Result<(), PolicyViolation> {
+        if let Some(expires_at) = self.expires_at
+            && now >= expires_at
+        {
+            return Err(PolicyViolation::Expired(expires_at));
+        }
+        if let Some(max_uses) = self.max_uses
+            && uses >= max_uses
+        {
+            return Err(PolicyViolation::UsesExhausted(max_uses));
+        }
+
+        if let Some(operations) = &self.operations {
+            let usage = usage.ok_or(PolicyViolation::UsageNotDeclared("operation"))?;
+            if !operations.contains(&usage.operation) {
+                return Err(PolicyViolation::OperationNotAllowed(usage.operation));
+            }
+        }
+        if let Some(algorithms) = &self.algorithms {
+            let algorithm = usage
+                .and_then(|u| u.algorithm.as_ref())
+                .ok_or(PolicyViolation::UsageNotDeclared("algorithm"))?;
+            if !algorithms.contains(algorithm) {
+                return Err(PolicyViolation::AlgorithmNotAllowed(algorithm.clone()));
+            }
+        }
+        if let Some(callers) = &self.callers {
+            let caller = usage
+                .and_then(|u| u.caller.as_ref())
+                .ok_or(PolicyViolation::UsageNotDeclared("caller"))?;
+            if !callers.contains(caller) {
+                return Err(PolicyViolation::CallerNotAllowed(caller.clone()));
+            }
+        }
+        Ok(())
+    }
+}
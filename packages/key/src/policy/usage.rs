@@ -0,0 +1,77 @@
+//! Declared key usage presented by callers when retrieving a key
+
+use serde::{Deserialize, Serialize};
+
+/// Operation a key may be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyOperation {
+    /// Encrypt data
+    Encrypt,
+    /// Decrypt data
+    Decrypt,
+    /// Produce signatures or MACs
+    Sign,
+    /// Wrap other keys
+    Wrap,
+    /// Unwrap other keys
+    Unwrap,
+    /// Derive further keys
+    Derive,
+}
+
+impl std::fmt::Display for KeyOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Encrypt => "encrypt",
+            Self::Decrypt => "decrypt",
+            Self::Sign => "sign",
+            Self::Wrap => "wrap",
+            Self::Unwrap => "unwrap",
+            Self::Derive => "derive",
+        })
+    }
+}
+
+/// What the caller intends to do with a retrieved key
+///
+/// Checked against the key's [`KeyPolicy`](super::KeyPolicy) before any material is
+/// released.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyUsage {
+    pub(crate) operation: KeyOperation,
+    pub(crate) algorithm: Option<String>,
+    pub(crate) caller: Option<String>,
+}
+
+impl KeyUsage {
+    /// Usage for `operation` with no algorithm or caller declared
+    #[must_use]
+    pub fn new(operation: KeyOperation) -> Self {
+        Self {
+            operation,
+            algorithm: None,
+            caller: None,
+        }
+    }
+
+    /// Declare the algorithm the key will be used with (compared case-insensitively)
+    #[must_use]
+    pub fn with_algorithm(mut self, algorithm: impl Into<String>) -> Self {
+        self.algorithm = Some(algorithm.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Declare the namespace of the calling component
+    #[must_use]
+    pub fn with_caller(mut self, namespace: impl Into<String>) -> Self {
+        self.caller = Some(namespace.into());
+        self
+    }
+
+    /// Declared operation
+    #[must_use]
+    pub fn operation(&self) -> KeyOperation {
+        self.operation
+    }
+}
@@ -0,0 +1,96 @@
+//! Per-version lifecycle metadata
+
+use crate::{KeyError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle state of a key version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyState {
+    /// Used for new encryptions and for decryption
+    Active,
+    /// Retired by a rotation; still usable for decryption
+    DecryptOnly,
+    /// Not usable, but the material is kept so it can be re-enabled
+    Disabled,
+    /// Material deleted from the store; terminal
+    Destroyed,
+}
+
+impl KeyState {
+    /// Whether data encrypted under a version in this state can still be decrypted
+    #[must_use]
+    pub fn can_decrypt(self) -> bool {
+        matches!(self, Self::Active | Self::DecryptOnly)
+    }
+
+    /// Check a manual transition
+    ///
+    /// Versions only move forward through the lifecycle, except that a disabled version
+    /// may be re-enabled for decryption. `Active` is only entered through a rotation.
+    pub(crate) fn check_transition(self, next: Self) -> Result<()> {
+        let allowed = matches!(
+            (self, next),
+            (Self::Active, Self::DecryptOnly | Self::Disabled)
+                | (Self::DecryptOnly, Self::Disabled)
+                | (Self::Disabled, Self::DecryptOnly | Self::Destroyed)
+        );
+        if allowed || self == next {
+            Ok(())
+        } else {
+            Err(KeyError::InvalidKey(format!(
+                "Cannot move key version from {self:?} to {next:?}"
+            )))
+        }
+    }
+}
+
+/// Metadata for one version of a rotating key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyVersionMetadata {
+    /// Key version number
+    pub version: u32,
+    /// Current lifecycle state
+    pub state: KeyState,
+    /// When the version was generated
+    pub created_at: DateTime<Utc>,
+    /// When the version entered its current state
+    pub state_changed_at: DateTime<Utc>,
+    /// Number of times the version was handed out as the active key
+    pub uses: u64,
+}
+
+/// Persisted metadata for all versions of a namespace
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct KeyRing {
+    pub(crate) versions: Vec<KeyVersionMetadata>,
+}
+
+impl KeyRing {
+    pub(crate) fn active(&self) -> Option<&KeyVersionMetadata> {
+        self.versions
+            .iter()
+            .rev()
+            .find(|entry| entry.state == KeyState::Active)
+    }
+
+    pub(crate) fn active_mut(&mut self) -> Option<&mut KeyVersionMetadata> {
+        self.versions
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.state == KeyState::Active)
+    }
+
+    pub(crate) fn get(&self, version: u32) -> Option<&KeyVersionMetadata> {
+        self.versions.iter().find(|entry| entry.version == version)
+    }
+
+    pub(crate) fn get_mut(&mut self, version: u32) -> Option<&mut KeyVersionMetadata> {
+        self.versions.iter_mut().find(|entry| entry.version == version)
+    }
+
+    pub(crate) fn next_version(&self) -> u32 {
+        self.versions.iter().map(|entry| entry.version).max().unwrap_or(0) + 1
+    }
+}
@@ -0,0 +1,444 @@
+//! Key rotation policies and version lifecycle management
+//!
+//! A [`KeyRotation`] owns one namespace in a key store. Key material lives under the
+//! usual `{namespace}:v{version}` ids; lifecycle metadata for every version is stored
+//! beside it under `{namespace}:meta` in the same store. Exactly one version is active
+//! at a time. Rotating generates the next version and moves the previous one to
+//! decrypt-only; the [`RotationPolicy`] then ages retired versions through disabled to
+//! destroyed.
+//!
+//! ```rust,ignore
+//! use cryypt_key::rotation::{KeyRotation, RotationPolicy};
+//! use chrono::Duration;
+//!
+//! let rotation = KeyRotation::new(store, "orders")
+//!     .with_policy(
+//!         RotationPolicy::new()
+//!             .max_age(Duration::days(30))
+//!             .decrypt_window(Duration::days(90))
+//!             .destroy_after(Duration::days(30)),
+//!     )
+//!     .open()
+//!     .await?;
+//! rotation.start_scheduler(std::time::Duration::from_secs(3600)).await;
+//!
+//! let key = rotation.current().await?;              // encrypt with key.key, record key.version
+//! let old = rotation.for_decrypt(stored_version).await?;
+//! ```
+
+mod metadata;
+mod policy;
+
+pub use metadata::{KeyState, KeyVersionMetadata};
+pub use policy::RotationPolicy;
+
+use crate::api::ActualKey;
+use crate::traits::{KeyImport, KeyRetrieval};
+use crate::{KeyError, Result, SimpleKeyId};
+use chrono::{DateTime, Utc};
+use metadata::KeyRing;
+use rand::RngCore;
+use std::sync::{Arc, Weak};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use zeroize::Zeroizing;
+
+/// Default size of generated key versions in bytes
+const DEFAULT_KEY_SIZE_BYTES: usize = 32;
+
+/// A key version handed out by [`KeyRotation`]
+#[derive(Debug, Clone)]
+pub struct VersionedKey {
+    /// Version to record alongside anything encrypted with this key
+    pub version: u32,
+    /// Key material
+    pub key: ActualKey,
+}
+
+/// Changes applied by [`KeyRotation::enforce`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RotationReport {
+    /// New active version, if the active one had expired
+    pub rotated_to: Option<u32>,
+    /// Versions moved from decrypt-only to disabled
+    pub disabled: Vec<u32>,
+    /// Versions whose material was destroyed
+    pub destroyed: Vec<u32>,
+}
+
+/// Configuration for a [`KeyRotation`] before its metadata is loaded
+pub struct KeyRotationBuilder<S> {
+    store: S,
+    namespace: String,
+    policy: RotationPolicy,
+    key_size_bytes: usize,
+}
+
+impl<S> KeyRotationBuilder<S>
+where
+    S: KeyImport + KeyRetrieval + Send + Sync + 'static,
+{
+    /// Set the rotation policy
+    #[must_use]
+    pub fn with_policy(mut self, policy: RotationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the size of generated key versions in bytes (default 32)
+    #[must_use]
+    pub fn with_key_size(mut self, key_size_bytes: usize) -> Self {
+        self.key_size_bytes = key_size_bytes;
+        self
+    }
+
+    /// Load the namespace metadata, generating version 1 if the namespace is new
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key size is zero or the store cannot be read or written.
+    pub async fn open(self) -> Result<KeyRotation<S>> {
+        if self.key_size_bytes == 0 {
+            return Err(KeyError::InvalidKeySize {
+                expected: DEFAULT_KEY_SIZE_BYTES,
+                actual: 0,
+            });
+        }
+
+        let inner = Inner {
+            store: self.store,
+            namespace: self.namespace,
+            policy: self.policy,
+            key_size_bytes: self.key_size_bytes,
+            ring: Mutex::new(KeyRing::default()),
+        };
+
+        {
+            let mut ring = inner.ring.lock().await;
+            *ring = inner.load_ring().await?;
+            if ring.versions.is_empty() {
+                inner.rotate_locked(&mut ring, Utc::now()).await?;
+            }
+        }
+
+        Ok(KeyRotation {
+            inner: Arc::new(inner),
+            scheduler: Arc::new(Mutex::new(None)),
+        })
+    }
+}
+
+/// Versioned key with policy-driven rotation for one namespace
+///
+/// Cloning is cheap; clones share metadata and the background scheduler.
+pub struct KeyRotation<S> {
+    inner: Arc<Inner<S>>,
+    scheduler: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl<S> Clone for KeyRotation<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            scheduler: Arc::clone(&self.scheduler),
+        }
+    }
+}
+
+struct Inner<S> {
+    store: S,
+    namespace: String,
+    policy: RotationPolicy,
+    key_size_bytes: usize,
+    ring: Mutex<KeyRing>,
+}
+
+impl<S> KeyRotation<S>
+where
+    S: KeyImport + KeyRetrieval + Send + Sync + 'static,
+{
+    /// Manage rotating versions of `namespace` in `store`
+    pub fn new(store: S, namespace: impl Into<String>) -> KeyRotationBuilder<S> {
+        KeyRotationBuilder {
+            store,
+            namespace: namespace.into(),
+            policy: RotationPolicy::default(),
+            key_size_bytes: DEFAULT_KEY_SIZE_BYTES,
+        }
+    }
+
+    /// Namespace managed by this rotation
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.inner.namespace
+    }
+
+    /// The active key version for new encryptions
+    ///
+    /// Counts one use. If the active version has exceeded the policy's age or use limit
+    /// it is rotated first, so limits hold even without the background scheduler.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no version is active (all were disabled manually) or the
+    /// store fails.
+    pub async fn current(&self) -> Result<VersionedKey> {
+        let inner = &self.inner;
+        let mut ring = inner.ring.lock().await;
+        let now = Utc::now();
+
+        let active = ring.active().ok_or_else(|| {
+            KeyError::InvalidKey(format!("No active key version for '{}'", inner.namespace))
+        })?;
+        if inner.rotation_due(active, now) {
+            inner.rotate_locked(&mut ring, now).await?;
+        }
+
+        let active = ring.active_mut().ok_or_else(|| {
+            KeyError::Internal("Active key version disappeared during rotation".into())
+        })?;
+        active.uses += 1;
+        let version = active.version;
+
+        // Use counts only need to survive restarts when a use limit depends on them
+        if inner.policy.max_uses.is_some() {
+            inner.save_ring(&ring).await?;
+        }
+
+        inner.load_key(version).await
+    }
+
+    /// A specific version for decrypting existing data
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyNotFound` for unknown versions and `KeyError::InvalidKey`
+    /// for versions that are disabled or destroyed.
+    pub async fn for_decrypt(&self, version: u32) -> Result<VersionedKey> {
+        let state = {
+            let ring = self.inner.ring.lock().await;
+            ring.get(version)
+                .ok_or_else(|| KeyError::KeyNotFound {
+                    id: self.inner.namespace.clone(),
+                    version,
+                })?
+                .state
+        };
+
+        if !state.can_decrypt() {
+            return Err(KeyError::InvalidKey(format!(
+                "Key version {version} of '{}' is {state:?}",
+                self.inner.namespace
+            )));
+        }
+        self.inner.load_key(version).await
+    }
+
+    /// Generate the next version now and retire the active one to decrypt-only
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails.
+    pub async fn rotate(&self) -> Result<u32> {
+        let mut ring = self.inner.ring.lock().await;
+        self.inner.rotate_locked(&mut ring, Utc::now()).await
+    }
+
+    /// Move a version to another lifecycle state
+    ///
+    /// Destroying a version deletes its material from the store. A disabled version may
+    /// be re-enabled as decrypt-only; `Active` is only reachable through a rotation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown versions, disallowed transitions, or store failures.
+    pub async fn set_state(&self, version: u32, state: KeyState) -> Result<()> {
+        let mut ring = self.inner.ring.lock().await;
+        let entry = ring.get_mut(version).ok_or_else(|| KeyError::KeyNotFound {
+            id: self.inner.namespace.clone(),
+            version,
+        })?;
+        entry.state.check_transition(state)?;
+        if entry.state == state {
+            return Ok(());
+        }
+
+        if state == KeyState::Destroyed {
+            self.inner.destroy_material(version).await?;
+        }
+        entry.state = state;
+        entry.state_changed_at = Utc::now();
+        self.inner.save_ring(&ring).await
+    }
+
+    /// Apply the policy: rotate an expired active version, disable versions past the
+    /// decrypt window, and destroy versions disabled for longer than `destroy_after`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails; changes applied before the failure are kept.
+    pub async fn enforce(&self) -> Result<RotationReport> {
+        let inner = &self.inner;
+        let mut ring = inner.ring.lock().await;
+        let now = Utc::now();
+        let mut report = RotationReport::default();
+
+        if ring.active().is_some_and(|active| inner.rotation_due(active, now)) {
+            report.rotated_to = Some(inner.rotate_locked(&mut ring, now).await?);
+        }
+
+        let mut changed = false;
+        for index in 0..ring.versions.len() {
+            let entry = &ring.versions[index];
+            let age = now - entry.state_changed_at;
+            let (version, state) = (entry.version, entry.state);
+
+            match state {
+                KeyState::DecryptOnly
+                    if inner.policy.decrypt_window.is_some_and(|window| age >= window) =>
+                {
+                    ring.versions[index].state = KeyState::Disabled;
+                    ring.versions[index].state_changed_at = now;
+                    report.disabled.push(version);
+                    changed = true;
+                }
+                KeyState::Disabled
+                    if inner.policy.destroy_after.is_some_and(|after| age >= after) =>
+                {
+                    inner.destroy_material(version).await?;
+                    ring.versions[index].state = KeyState::Destroyed;
+                    ring.versions[index].state_changed_at = now;
+                    report.destroyed.push(version);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if changed {
+            inner.save_ring(&ring).await?;
+        }
+        Ok(report)
+    }
+
+    /// Metadata for every known version, oldest first
+    pub async fn versions(&self) -> Vec<KeyVersionMetadata> {
+        self.inner.ring.lock().await.versions.clone()
+    }
+
+    /// Run [`KeyRotation::enforce`] every `interval` in the background
+    ///
+    /// The task holds only a weak reference and stops once every clone of this rotation
+    /// has been dropped. Starting again replaces the previous task.
+    pub async fn start_scheduler(&self, interval: std::time::Duration) {
+        let inner: Weak<Inner<S>> = Arc::downgrade(&self.inner);
+        let scheduler = Arc::downgrade(&self.scheduler);
+        let task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let (Some(inner), Some(scheduler)) = (inner.upgrade(), scheduler.upgrade()) else {
+                    break;
+                };
+                let rotation = KeyRotation { inner, scheduler };
+                if let Err(e) = rotation.enforce().await {
+                    tracing::warn!("Key rotation for '{}' failed: {e}", rotation.namespace());
+                }
+            }
+        });
+
+        if let Some(previous) = self.scheduler.lock().await.replace(task) {
+            previous.abort();
+        }
+    }
+
+    /// Stop the background scheduler, if running
+    pub async fn stop_scheduler(&self) {
+        if let Some(task) = self.scheduler.lock().await.take() {
+            task.abort();
+        }
+    }
+}
+
+impl<S> Inner<S>
+where
+    S: KeyImport + KeyRetrieval + Send + Sync + 'static,
+{
+    fn key_id(&self, version: u32) -> SimpleKeyId {
+        SimpleKeyId::new(format!("{}:v{version}", self.namespace))
+    }
+
+    fn metadata_id(&self) -> SimpleKeyId {
+        SimpleKeyId::new(format!("{}:meta", self.namespace))
+    }
+
+    fn rotation_due(&self, active: &KeyVersionMetadata, now: DateTime<Utc>) -> bool {
+        let too_old = self
+            .policy
+            .max_age
+            .is_some_and(|max_age| now - active.created_at >= max_age);
+        let overused = self.policy.max_uses.is_some_and(|max| active.uses >= max);
+        too_old || overused
+    }
+
+    async fn rotate_locked(&self, ring: &mut KeyRing, now: DateTime<Utc>) -> Result<u32> {
+        let version = ring.next_version();
+
+        let mut material = Zeroizing::new(vec![0u8; self.key_size_bytes]);
+        rand::rng().fill_bytes(&mut material);
+        self.store
+            .store(&self.key_id(version), &material)
+            .recv()
+            .await?;
+
+        if let Some(previous) = ring.active_mut() {
+            previous.state = KeyState::DecryptOnly;
+            previous.state_changed_at = now;
+        }
+        ring.versions.push(KeyVersionMetadata {
+            version,
+            state: KeyState::Active,
+            created_at: now,
+            state_changed_at: now,
+            uses: 0,
+        });
+
+        self.save_ring(ring).await?;
+        tracing::info!("Rotated '{}' to key version {version}", self.namespace);
+        Ok(version)
+    }
+
+    async fn load_key(&self, version: u32) -> Result<VersionedKey> {
+        let material = self.store.retrieve(&self.key_id(version)).recv().await?;
+        Ok(VersionedKey {
+            version,
+            key: ActualKey::from_bytes(material),
+        })
+    }
+
+    async fn destroy_material(&self, version: u32) -> Result<()> {
+        let key_id = self.key_id(version);
+        if self.store.exists(&key_id).recv().await? {
+            self.store.delete(&key_id).recv().await?;
+        }
+        Ok(())
+    }
+
+    async fn load_ring(&self) -> Result<KeyRing> {
+        let metadata_id = self.metadata_id();
+        if !self.store.exists(&metadata_id).recv().await? {
+            return Ok(KeyRing::default());
+        }
+
+        let json = self.store.retrieve(&metadata_id).recv().await?;
+        serde_json::from_slice(&json)
+            .map_err(|e| KeyError::InvalidKeyFormat(format!("Invalid rotation metadata: {e}")))
+    }
+
+    async fn save_ring(&self, ring: &KeyRing) -> Result<()> {
+        let json = serde_json::to_vec(ring)
+            .map_err(|e| KeyError::Internal(format!("Rotation metadata encoding failed: {e}")))?;
+        self.store.store(&self.metadata_id(), &json).recv().await
+    }
+}
@@ -0,0 +1,51 @@
+//! Rotation policy configuration
+
+use chrono::Duration;
+
+/// When to rotate the active key version and how long retired versions linger
+///
+/// All limits are optional; an empty policy never rotates on its own and keeps retired
+/// versions decryptable indefinitely.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RotationPolicy {
+    pub(crate) max_age: Option<Duration>,
+    pub(crate) max_uses: Option<u64>,
+    pub(crate) decrypt_window: Option<Duration>,
+    pub(crate) destroy_after: Option<Duration>,
+}
+
+impl RotationPolicy {
+    /// Policy with no limits
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rotate once the active version is older than `max_age`
+    #[must_use]
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Rotate once the active version has been handed out `max_uses` times
+    #[must_use]
+    pub fn max_uses(mut self, max_uses: u64) -> Self {
+        self.max_uses = Some(max_uses);
+        self
+    }
+
+    /// Keep retired versions decryptable for `window`, then disable them
+    #[must_use]
+    pub fn decrypt_window(mut self, window: Duration) -> Self {
+        self.decrypt_window = Some(window);
+        self
+    }
+
+    /// Destroy key material once a version has been disabled for `after`
+    #[must_use]
+    pub fn destroy_after(mut self, after: Duration) -> Self {
+        self.destroy_after = Some(after);
+        self
+    }
+}
@@ -0,0 +1,55 @@
+//! Arithmetic in GF(2^8) with the AES reduction polynomial x^8 + x^4 + x^3 + x + 1
+//!
+//! Multiplication is branch-free and table-free so that share evaluation and
+//! interpolation do not leak secret bytes through cache timing.
+
+/// Multiply two field elements
+pub(super) fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1B & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse (`a^254`); the inverse of zero is defined as zero
+pub(super) fn inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Evaluate a polynomial with `coefficients[0]` as the constant term at `x`
+pub(super) fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0u8, |acc, &coefficient| mul(acc, x) ^ coefficient)
+}
+
+/// Lagrange basis weights for interpolating at zero from the given x coordinates
+///
+/// In characteristic 2 subtraction is XOR, so `x_j / (x_j - x_i)` becomes
+/// `x_j / (x_j ^ x_i)`. Coordinates must be non-zero and distinct.
+pub(super) fn lagrange_weights_at_zero(xs: &[u8]) -> Vec<u8> {
+    xs.iter()
+        .enumerate()
+        .map(|(i, &xi)| {
+            xs.iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .fold(1u8, |weight, (_, &xj)| mul(weight, mul(xj, inv(xj ^ xi))))
+        })
+        .collect()
+}
@@ -0,0 +1,210 @@
+//! Shamir secret sharing over GF(256)
+//!
+//! Splits a secret into `n` shares so that any `k` of them recover it and fewer reveal
+//! nothing. Each byte of the secret is the constant term of an independent random
+//! polynomial of degree `k - 1`; share `i` holds the evaluations at `x = i`.
+//!
+//! A four-byte SHA-256 digest of the secret is appended before splitting, so recovery
+//! from shares of different splits or corrupted payloads fails instead of returning the
+//! wrong key. Individual shares additionally carry their own checksum (see [`Share`]).
+//!
+//! ```rust,ignore
+//! use cryypt_key::sharing::{SecretSharing, SharesMasterKey};
+//!
+//! let shares = SecretSharing::new(3, 5)?.split_master_key(&master_key)?;
+//! let phrases: Vec<_> = shares.iter().map(|share| share.to_mnemonic()).collect();
+//!
+//! // Later, any three custodians bring their shares back
+//! let master_key = SharesMasterKey::from_mnemonics(&[&custodian_a, &custodian_c, &custodian_e])?;
+//! ```
+
+mod gf256;
+mod provider;
+mod share;
+
+pub use provider::SharesMasterKey;
+pub use share::{SHARE_FORMAT_VERSION, Share};
+
+use crate::api::{ActualKey, MasterKeyProvider};
+use crate::{KeyError, Result};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+/// Length of the secret digest appended before splitting
+const DIGEST_LEN: usize = 4;
+
+/// k-of-n Shamir secret sharing parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecretSharing {
+    threshold: u8,
+    shares: u8,
+}
+
+impl SecretSharing {
+    /// Split into `shares` shares, any `threshold` of which recover the secret
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` unless `2 <= threshold <= shares`.
+    pub fn new(threshold: u8, shares: u8) -> Result<Self> {
+        if threshold < 2 || threshold > shares {
+            return Err(KeyError::InvalidKey(format!(
+                "Threshold must be between 2 and the share count, got {threshold} of {shares}"
+            )));
+        }
+        Ok(Self { threshold, shares })
+    }
+
+    /// Number of shares required to recover the secret
+    #[must_use]
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Number of shares produced
+    #[must_use]
+    pub fn shares(&self) -> u8 {
+        self.shares
+    }
+
+    /// Split arbitrary secret bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` if the secret is empty.
+    pub fn split(&self, secret: &[u8]) -> Result<Vec<Share>> {
+        if secret.is_empty() {
+            return Err(KeyError::InvalidKey("Cannot split an empty secret".into()));
+        }
+
+        let mut payload = Zeroizing::new(secret.to_vec());
+        payload.extend_from_slice(&secret_digest(secret));
+
+        let mut rng = rand::rng();
+        let mut group_id = [0u8; 2];
+        rng.fill_bytes(&mut group_id);
+        let group_id = u16::from_be_bytes(group_id);
+
+        let mut shares: Vec<Share> = (1..=self.shares)
+            .map(|index| Share {
+                group_id,
+                threshold: self.threshold,
+                index,
+                payload: Zeroizing::new(Vec::with_capacity(payload.len())),
+            })
+            .collect();
+
+        let mut coefficients = Zeroizing::new(vec![0u8; usize::from(self.threshold)]);
+        for &byte in payload.iter() {
+            coefficients[0] = byte;
+            rng.fill_bytes(&mut coefficients[1..]);
+            for share in &mut shares {
+                share.payload.push(gf256::evaluate(&coefficients, share.index));
+            }
+        }
+
+        Ok(shares)
+    }
+
+    /// Split the bytes of a generated or retrieved key
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` if the key is empty.
+    pub fn split_key(&self, key: &ActualKey) -> Result<Vec<Share>> {
+        self.split(key.bytes())
+    }
+
+    /// Resolve a master key provider and split its output
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider fails to resolve.
+    pub fn split_master_key<P: MasterKeyProvider + ?Sized>(
+        &self,
+        provider: &P,
+    ) -> Result<Vec<Share>> {
+        let master_key = Zeroizing::new(provider.resolve()?);
+        self.split(&master_key[..])
+    }
+}
+
+/// Recover a secret from at least `threshold` shares of the same split
+///
+/// Extra shares beyond the threshold are ignored.
+///
+/// # Errors
+///
+/// Returns an error if fewer shares than the threshold are supplied, the shares come
+/// from different splits or repeat an index, or the recovered secret fails its digest.
+pub fn combine(shares: &[Share]) -> Result<Zeroizing<Vec<u8>>> {
+    let first = shares
+        .first()
+        .ok_or_else(|| KeyError::InvalidKey("No shares supplied".into()))?;
+
+    if shares.iter().any(|share| {
+        share.group_id != first.group_id
+            || share.threshold != first.threshold
+            || share.payload.len() != first.payload.len()
+    }) {
+        return Err(KeyError::InvalidKey("Shares belong to different splits".into()));
+    }
+
+    let mut selected: Vec<&Share> = Vec::with_capacity(usize::from(first.threshold));
+    for share in shares {
+        if selected.iter().any(|chosen| chosen.index == share.index) {
+            return Err(KeyError::InvalidKey(format!(
+                "Share {} was supplied more than once",
+                share.index
+            )));
+        }
+        if selected.len() < usize::from(first.threshold) {
+            selected.push(share);
+        }
+    }
+
+    if selected.len() < usize::from(first.threshold) {
+        return Err(KeyError::InvalidKey(format!(
+            "Need {} shares to recover the secret, got {}",
+            first.threshold,
+            selected.len()
+        )));
+    }
+    if first.payload.len() <= DIGEST_LEN {
+        return Err(KeyError::InvalidKeyFormat("Share payload is too short".into()));
+    }
+
+    let xs: Vec<u8> = selected.iter().map(|share| share.index).collect();
+    let weights = gf256::lagrange_weights_at_zero(&xs);
+
+    let mut payload = Zeroizing::new(vec![0u8; first.payload.len()]);
+    for (position, byte) in payload.iter_mut().enumerate() {
+        *byte = selected.iter().zip(&weights).fold(0u8, |acc, (share, &weight)| {
+            acc ^ gf256::mul(share.payload[position], weight)
+        });
+    }
+
+    let secret_len = payload.len() - DIGEST_LEN;
+    let (secret, digest) = payload.split_at(secret_len);
+    if !bool::from(secret_digest(secret).as_slice().ct_eq(digest)) {
+        return Err(KeyError::DecryptionFailed(
+            "Recovered secret failed its integrity check".into(),
+        ));
+    }
+
+    payload.truncate(secret_len);
+    Ok(payload)
+}
+
+fn secret_digest(secret: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"cryypt-shamir-v1:");
+    hasher.update(secret);
+    let digest = hasher.finalize();
+
+    let mut truncated = [0u8; DIGEST_LEN];
+    truncated.copy_from_slice(&digest[..DIGEST_LEN]);
+    truncated
+}
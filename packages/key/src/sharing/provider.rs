@@ -0,0 +1,82 @@
+//! Master key provider backed by Shamir shares
+
+use super::{Share, combine};
+use crate::api::MasterKeyProvider;
+use crate::{KeyError, Result};
+
+/// Master key rebuilt from a threshold of Shamir shares
+///
+/// Shares can be collected one at a time as custodians present them; resolving fails
+/// until enough shares of the same split have been added.
+#[derive(Debug, Clone, Default)]
+pub struct SharesMasterKey {
+    shares: Vec<Share>,
+}
+
+impl SharesMasterKey {
+    /// Start from the given shares
+    #[must_use]
+    pub fn new(shares: Vec<Share>) -> Self {
+        Self { shares }
+    }
+
+    /// Decode shares from their hex form
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if any share fails to decode.
+    pub fn from_hex(shares: &[&str]) -> Result<Self> {
+        Self::decode(shares, Share::from_hex)
+    }
+
+    /// Decode shares from their base64 form
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if any share fails to decode.
+    pub fn from_base64(shares: &[&str]) -> Result<Self> {
+        Self::decode(shares, Share::from_base64)
+    }
+
+    /// Decode shares from their mnemonic form
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if any share fails to decode.
+    pub fn from_mnemonics(shares: &[&str]) -> Result<Self> {
+        Self::decode(shares, Share::from_mnemonic)
+    }
+
+    /// Add another custodian's share
+    #[must_use]
+    pub fn with_share(mut self, share: Share) -> Self {
+        self.shares.push(share);
+        self
+    }
+
+    /// Whether enough shares have been supplied to attempt recovery
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.shares
+            .first()
+            .is_some_and(|first| self.shares.len() >= usize::from(first.threshold()))
+    }
+
+    fn decode(shares: &[&str], decode: fn(&str) -> Result<Share>) -> Result<Self> {
+        shares
+            .iter()
+            .map(|share| decode(share))
+            .collect::<Result<Vec<_>>>()
+            .map(Self::new)
+    }
+}
+
+impl MasterKeyProvider for SharesMasterKey {
+    fn resolve(&self) -> Result<[u8; 32]> {
+        let secret = combine(&self.shares)?;
+        <[u8; 32]>::try_from(secret.as_slice()).map_err(|_| KeyError::InvalidKeySize {
+            expected: 32,
+            actual: secret.len(),
+        })
+    }
+}
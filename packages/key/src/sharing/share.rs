@@ -0,0 +1,247 @@
+//! Share type and its versioned encodings
+//!
+//! Binary layout (format version 1):
+//!
+//! ```text
+//! format (1) || group id (2) || threshold (1) || index (1) || payload (n) || checksum (4)
+//! ```
+//!
+//! The checksum is the first four bytes of SHA-256 over everything before it, so a
+//! mistyped or truncated share is rejected before it reaches interpolation. Text forms
+//! are lowercase hex, unpadded URL-safe base64, and a mnemonic-friendly Crockford base32
+//! split into dash-separated groups that tolerates case and the usual look-alikes.
+
+use crate::{KeyError, Result};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Share encoding version written by [`Share::to_bytes`]
+pub const SHARE_FORMAT_VERSION: u8 = 1;
+
+const HEADER_LEN: usize = 5;
+const CHECKSUM_LEN: usize = 4;
+
+/// Crockford base32 alphabet (no I, L, O or U)
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Characters per dash-separated group in the mnemonic form
+const MNEMONIC_GROUP: usize = 4;
+
+/// One share of a secret split with [`SecretSharing`](super::SecretSharing)
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share {
+    pub(super) group_id: u16,
+    pub(super) threshold: u8,
+    pub(super) index: u8,
+    pub(super) payload: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    /// Identifier shared by every share from the same split
+    #[must_use]
+    pub fn group_id(&self) -> u16 {
+        self.group_id
+    }
+
+    /// Number of shares required to recover the secret
+    #[must_use]
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Share index (the non-zero x coordinate)
+    #[must_use]
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Encode with header and checksum
+    #[must_use]
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(
+            HEADER_LEN + self.payload.len() + CHECKSUM_LEN,
+        ));
+        bytes.push(SHARE_FORMAT_VERSION);
+        bytes.extend_from_slice(&self.group_id.to_be_bytes());
+        bytes.push(self.threshold);
+        bytes.push(self.index);
+        bytes.extend_from_slice(&self.payload);
+
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum);
+        bytes
+    }
+
+    /// Decode bytes produced by [`Share::to_bytes`], verifying the checksum
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the encoding is truncated, uses an
+    /// unknown format version, carries invalid parameters, or fails its checksum.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() <= HEADER_LEN + CHECKSUM_LEN {
+            return Err(KeyError::InvalidKeyFormat("Share is truncated".into()));
+        }
+        if bytes[0] != SHARE_FORMAT_VERSION {
+            return Err(KeyError::InvalidKeyFormat(format!(
+                "Unsupported share format version {}",
+                bytes[0]
+            )));
+        }
+
+        let (body, expected) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if checksum(body) != expected {
+            return Err(KeyError::InvalidKeyFormat("Share checksum mismatch".into()));
+        }
+
+        let share = Self {
+            group_id: u16::from_be_bytes([body[1], body[2]]),
+            threshold: body[3],
+            index: body[4],
+            payload: Zeroizing::new(body[HEADER_LEN..].to_vec()),
+        };
+        if share.index == 0 || share.threshold < 2 {
+            return Err(KeyError::InvalidKeyFormat("Invalid share parameters".into()));
+        }
+        Ok(share)
+    }
+
+    /// Lowercase hex encoding
+    #[must_use]
+    pub fn to_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(hex::encode(self.to_bytes().as_slice()))
+    }
+
+    /// Decode a hex share
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the input is not hex or not a valid share.
+    pub fn from_hex(encoded: &str) -> Result<Self> {
+        let bytes = Zeroizing::new(
+            hex::decode(encoded.trim())
+                .map_err(|e| KeyError::InvalidKeyFormat(format!("Invalid hex share: {e}")))?,
+        );
+        Self::from_bytes(&bytes)
+    }
+
+    /// Unpadded URL-safe base64 encoding
+    #[must_use]
+    pub fn to_base64(&self) -> Zeroizing<String> {
+        Zeroizing::new(URL_SAFE_NO_PAD.encode(self.to_bytes().as_slice()))
+    }
+
+    /// Decode a base64 share
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the input is not base64 or not a valid share.
+    pub fn from_base64(encoded: &str) -> Result<Self> {
+        let bytes = Zeroizing::new(
+            URL_SAFE_NO_PAD
+                .decode(encoded.trim())
+                .map_err(|e| KeyError::InvalidKeyFormat(format!("Invalid base64 share: {e}")))?,
+        );
+        Self::from_bytes(&bytes)
+    }
+
+    /// Mnemonic-friendly form: Crockford base32 in dash-separated groups of four
+    ///
+    /// Suited to reading aloud or writing down; decoding ignores case, whitespace and
+    /// dashes, and maps `O` to `0` and `I`/`L` to `1`.
+    #[must_use]
+    pub fn to_mnemonic(&self) -> Zeroizing<String> {
+        let symbols = crockford_encode(&self.to_bytes());
+        let groups: Vec<&str> = symbols
+            .as_bytes()
+            .chunks(MNEMONIC_GROUP)
+            .filter_map(|group| std::str::from_utf8(group).ok())
+            .collect();
+        Zeroizing::new(groups.join("-"))
+    }
+
+    /// Decode a share from its mnemonic form
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the input contains characters outside the
+    /// Crockford alphabet or is not a valid share.
+    pub fn from_mnemonic(encoded: &str) -> Result<Self> {
+        let bytes = crockford_decode(encoded)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("group_id", &self.group_id)
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("payload", &"[REDACTED]")
+            .finish()
+    }
+}
+
+fn checksum(body: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let digest = Sha256::digest(body);
+    let mut checksum = [0u8; CHECKSUM_LEN];
+    checksum.copy_from_slice(&digest[..CHECKSUM_LEN]);
+    checksum
+}
+
+fn crockford_encode(bytes: &[u8]) -> Zeroizing<String> {
+    let mut symbols = Zeroizing::new(String::with_capacity(bytes.len() * 8 / 5 + 1));
+    let mut buffer = 0u16;
+    let mut bits = 0u32;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            symbols.push(char::from(CROCKFORD[usize::from((buffer >> bits) & 0x1F)]));
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        symbols.push(char::from(CROCKFORD[usize::from((buffer << (5 - bits)) & 0x1F)]));
+    }
+    symbols
+}
+
+fn crockford_decode(encoded: &str) -> Result<Zeroizing<Vec<u8>>> {
+    let mut bytes = Zeroizing::new(Vec::with_capacity(encoded.len() * 5 / 8));
+    let mut buffer = 0u16;
+    let mut bits = 0u32;
+
+    for symbol in encoded.chars() {
+        if symbol == '-' || symbol.is_whitespace() {
+            continue;
+        }
+        let normalized = match symbol.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            other => other,
+        };
+        let value = (0u16..)
+            .zip(CROCKFORD)
+            .find_map(|(value, &c)| (char::from(c) == normalized).then_some(value))
+            .ok_or_else(|| {
+                KeyError::InvalidKeyFormat(format!("Invalid mnemonic character '{symbol}'"))
+            })?;
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            // The buffer never holds more than `bits + 8` significant bits here
+            #[allow(clippy::cast_possible_truncation)]
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(bytes)
+}
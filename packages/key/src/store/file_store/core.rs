@@ -3,19 +3,24 @@
 //! This module provides the fundamental types and builder pattern for creating
 //! file-based key stores with master key encryption.
 
+use crate::{KeyError, Result};
+use rand::RngCore;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tokio::fs;
 
 /// File-based key store that encrypts keys with a master key
 #[derive(Clone)]
 pub struct FileKeyStore {
     pub(super) base_path: PathBuf,
     pub(super) master_key: Arc<[u8; 32]>,
+    /// Serializes read-modify-write of key policy records across clones
+    pub(super) policy_lock: Arc<tokio::sync::Mutex<()>>,
 }
 
 /// Builder for file-based key store
 pub struct FileKeyStoreBuilder {
-    base_path: PathBuf,
+    pub(super) base_path: PathBuf,
 }
 
 impl FileKeyStore {
@@ -31,6 +36,7 @@ impl FileKeyStore {
         Self {
             base_path: base_path.as_ref().to_path_buf(),
             master_key: Arc::new(master_key),
+            policy_lock: Arc::default(),
         }
     }
 
@@ -39,6 +45,19 @@ impl FileKeyStore {
         let safe_id = format!("{}_{version}", namespace.replace(['/', ':'], "_"));
         self.base_path.join(format!("{safe_id}.key"))
     }
+
+    /// Derive a file path from a `KeyId`, honouring the `{namespace}:v{version}` scheme
+    /// used by the key retriever so both APIs address the same files
+    pub(super) fn key_id_path(&self, key_id: &dyn crate::KeyId) -> PathBuf {
+        let id = key_id.id();
+        match id
+            .rsplit_once(":v")
+            .and_then(|(namespace, version)| Some((namespace, version.parse().ok()?)))
+        {
+            Some((namespace, version)) => self.key_path(namespace, version),
+            None => self.key_path(id, key_id.version()),
+        }
+    }
 }
 
 impl FileKeyStoreBuilder {
@@ -48,6 +67,29 @@ impl FileKeyStoreBuilder {
         FileKeyStore {
             base_path: self.base_path,
             master_key: Arc::new(master_key),
+            policy_lock: Arc::default(),
         }
     }
 }
+
+/// Sibling of `path` with a random suffix, for staging a write
+pub(super) fn temp_path(path: &Path) -> PathBuf {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{:016x}.tmp", rand::rng().next_u64()));
+    PathBuf::from(tmp)
+}
+
+/// Write to a sibling temporary file and rename it over `path`
+///
+/// Readers see either the old contents or the new ones, never a partial file.
+pub(super) async fn write_replacing(path: &Path, contents: &[u8]) -> Result<()> {
+    let tmp = temp_path(path);
+    let written = match fs::write(&tmp, contents).await {
+        Ok(()) => fs::rename(&tmp, path).await,
+        Err(e) => Err(e),
+    };
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp).await;
+    }
+    written.map_err(KeyError::Io)
+}
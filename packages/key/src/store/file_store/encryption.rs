@@ -6,12 +6,21 @@
 use crate::{KeyError, Result};
 use aes_gcm::{
     Aes256Gcm,
-    aead::{Aead, KeyInit, generic_array::GenericArray},
+    aead::{Aead, KeyInit, Payload, generic_array::GenericArray},
 };
 use zeroize::Zeroizing;
 
 /// Encrypt key material using AES-GCM with the provided master key
 pub(super) fn encrypt_key_material(key_material: &[u8], master_key: &[u8; 32]) -> Result<Vec<u8>> {
+    encrypt_with_aad(key_material, &[], master_key)
+}
+
+/// Encrypt like [`encrypt_key_material`], authenticating `aad` alongside the material
+pub(super) fn encrypt_with_aad(
+    key_material: &[u8],
+    aad: &[u8],
+    master_key: &[u8; 32],
+) -> Result<Vec<u8>> {
     use rand::RngCore;
 
     // Generate random nonce
@@ -25,7 +34,13 @@ pub(super) fn encrypt_key_material(key_material: &[u8], master_key: &[u8; 32]) -
 
     // Encrypt key material
     let ciphertext = cipher
-        .encrypt(nonce_array, key_material)
+        .encrypt(
+            nonce_array,
+            Payload {
+                msg: key_material,
+                aad,
+            },
+        )
         .map_err(|_| KeyError::EncryptionFailed("Key encryption failed".into()))?;
 
     // Combine nonce + ciphertext
@@ -39,6 +54,15 @@ pub(super) fn encrypt_key_material(key_material: &[u8], master_key: &[u8; 32]) -
 pub(super) fn decrypt_key_material(
     encrypted_data: &[u8],
     master_key: &[u8; 32],
+) -> Result<Vec<u8>> {
+    decrypt_with_aad(encrypted_data, &[], master_key)
+}
+
+/// Decrypt data from [`encrypt_with_aad`], which fails unless `aad` matches
+pub(super) fn decrypt_with_aad(
+    encrypted_data: &[u8],
+    aad: &[u8],
+    master_key: &[u8; 32],
 ) -> Result<Vec<u8>> {
     // Validate minimum size (12 bytes nonce + at least 16 bytes ciphertext)
     if encrypted_data.len() < 28 {
@@ -58,7 +82,13 @@ pub(super) fn decrypt_key_material(
 
     // Decrypt key material
     let decrypted = cipher
-        .decrypt(nonce_array, ciphertext)
+        .decrypt(
+            nonce_array,
+            Payload {
+                msg: ciphertext,
+                aad,
+            },
+        )
         .map_err(|_| KeyError::DecryptionFailed("Key decryption failed".into()))?;
 
     Ok(decrypted)
@@ -0,0 +1,181 @@
+//! Passphrase-derived master keys for file-based key storage
+//!
+//! KDF profiles live next to the key files as `{namespace}.kdf.json`. Opening a store
+//! with a passphrase for the first time migrates key files written under the legacy
+//! deterministic-salt derivation: a pending profile is persisted first, every key file
+//! is re-wrapped under the new master key, and only then is the profile committed. An
+//! interrupted migration resumes from the pending profile on the next open.
+
+use super::core::{FileKeyStore, FileKeyStoreBuilder, temp_path, write_replacing};
+use super::encryption::{decrypt_key_material, encrypt_key_material};
+use super::policy::{open_key_file, seal_key_file};
+use crate::api::master_key_builder::{KdfProfile, KdfProfileStore, PassphraseMasterKey};
+use crate::{KeyError, Result};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use zeroize::Zeroizing;
+
+/// Profile namespace for the store's own master key
+const MASTER_PROFILE: &str = "master";
+
+impl KdfProfileStore for FileKeyStore {
+    async fn load_profile(&self, namespace: &str) -> Result<Option<KdfProfile>> {
+        read_profile(&profile_path(&self.base_path, namespace)).await
+    }
+
+    async fn save_profile(&self, namespace: &str, profile: &KdfProfile) -> Result<()> {
+        write_profile(&profile_path(&self.base_path, namespace), profile).await
+    }
+
+    async fn save_profile_if_absent(
+        &self,
+        namespace: &str,
+        profile: &KdfProfile,
+    ) -> Result<Option<KdfProfile>> {
+        write_profile_if_absent(&profile_path(&self.base_path, namespace), profile).await
+    }
+}
+
+impl FileKeyStoreBuilder {
+    /// Derive the master key from a passphrase and the store's persisted KDF profile
+    ///
+    /// On first use a profile is created - from the passphrase's attached profile if it
+    /// has one, otherwise with a random salt and default costs - and existing key files
+    /// are re-wrapped from the legacy derivation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile cannot be read or written, key derivation fails,
+    /// or an existing key file cannot be decrypted with either the new or legacy key.
+    pub async fn with_passphrase(
+        self,
+        passphrase: &impl AsRef<PassphraseMasterKey>,
+    ) -> Result<FileKeyStore> {
+        let passphrase = passphrase.as_ref();
+        let committed = profile_path(&self.base_path, MASTER_PROFILE);
+        if let Some(profile) = read_profile(&committed).await? {
+            let master_key = profile.derive(passphrase.passphrase.as_bytes())?;
+            return Ok(self.with_master_key(*master_key));
+        }
+
+        let pending = committed.with_extension("json.pending");
+        let profile = if let Some(profile) = read_profile(&pending).await? {
+            profile
+        } else {
+            let profile = passphrase.profile.clone().unwrap_or_else(KdfProfile::generate);
+            write_profile_if_absent(&pending, &profile)
+                .await?
+                .unwrap_or(profile)
+        };
+
+        let master_key = profile.derive(passphrase.passphrase.as_bytes())?;
+        let legacy_key = Zeroizing::new(passphrase.resolve_legacy()?);
+        let migrated = rewrap_key_files(&self.base_path, &legacy_key, &master_key).await?;
+        match fs::rename(&pending, &committed).await {
+            // A concurrent open finished the same migration and committed first
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && committed.exists() => {}
+            result => result.map_err(KeyError::Io)?,
+        }
+
+        if migrated > 0 {
+            log::info!("Re-wrapped {migrated} key files under the profiled master key");
+        }
+        Ok(self.with_master_key(*master_key))
+    }
+}
+
+fn profile_path(base_path: &Path, namespace: &str) -> PathBuf {
+    base_path.join(format!("{}.kdf.json", namespace.replace(['/', ':'], "_")))
+}
+
+async fn read_profile(path: &Path) -> Result<Option<KdfProfile>> {
+    match fs::read_to_string(path).await {
+        Ok(json) => KdfProfile::from_json(&json).map(Some),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(KeyError::Io(e)),
+    }
+}
+
+async fn write_profile(path: &Path, profile: &KdfProfile) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await.map_err(KeyError::Io)?;
+    }
+    write_replacing(path, profile.to_json()?.as_bytes()).await
+}
+
+/// Create the profile at `path` unless one exists, returning the existing one
+///
+/// The profile is written to a temporary file and hard-linked into place, which fails
+/// atomically if `path` already exists, so a reader never sees a partial profile.
+async fn write_profile_if_absent(path: &Path, profile: &KdfProfile) -> Result<Option<KdfProfile>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await.map_err(KeyError::Io)?;
+    }
+    let tmp = temp_path(path);
+    fs::write(&tmp, profile.to_json()?.as_bytes())
+        .await
+        .map_err(KeyError::Io)?;
+    let linked = fs::hard_link(&tmp, path).await;
+    let _ = fs::remove_file(&tmp).await;
+
+    match linked {
+        Ok(()) => Ok(None),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            let existing = read_profile(path).await?;
+            existing.map(Some).ok_or_else(|| {
+                KeyError::Internal(format!("{} vanished while being read", path.display()))
+            })
+        }
+        Err(e) => Err(KeyError::Io(e)),
+    }
+}
+
+/// Re-encrypt every key file and key policy record under `master_key`, skipping files
+/// already migrated
+async fn rewrap_key_files(
+    base_path: &Path,
+    legacy_key: &[u8; 32],
+    master_key: &[u8; 32],
+) -> Result<usize> {
+    let mut entries = match fs::read_dir(base_path).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(KeyError::Io(e)),
+    };
+
+    let mut migrated = 0;
+    while let Some(entry) = entries.next_entry().await.map_err(KeyError::Io)? {
+        let path = entry.path();
+        let Some(extension) = path
+            .extension()
+            .filter(|ext| *ext == "key" || *ext == "policy")
+        else {
+            continue;
+        };
+        let unreadable = |_| {
+            KeyError::DecryptionFailed(format!(
+                "{} is not readable with the legacy or profiled master key",
+                path.display()
+            ))
+        };
+
+        let encrypted = fs::read(&path).await.map_err(KeyError::Io)?;
+        if extension == "policy" {
+            if decrypt_key_material(&encrypted, master_key).is_ok() {
+                continue;
+            }
+            let record = decrypt_key_material(&encrypted, legacy_key).map_err(unreadable)?;
+            write_replacing(&path, &encrypt_key_material(&record, master_key)?).await?;
+            continue;
+        }
+
+        if open_key_file(&encrypted, master_key).is_ok() {
+            continue;
+        }
+        let (material, policy) = open_key_file(&encrypted, legacy_key).map_err(unreadable)?;
+        let sealed = seal_key_file(&material, policy.as_ref(), master_key)?;
+        write_replacing(&path, &sealed).await?;
+        migrated += 1;
+    }
+    Ok(migrated)
+}
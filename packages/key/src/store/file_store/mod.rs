@@ -5,7 +5,9 @@
 
 mod core;
 mod encryption;
+mod kdf_profile;
 mod legacy_api;
+mod policy;
 mod storage_traits;
 
 // Re-export public types
@@ -0,0 +1,279 @@
+//! Key Policy Records for File-based Key Storage
+//!
+//! A key's policy is sealed into its encrypted key file, so the material never comes out
+//! without it. The number of retrievals so far lives next to the key in a `.policy`
+//! record, encrypted with the master key and naming the key file it belongs to, so it
+//! can be neither edited nor copied onto another key. A policy-bound key whose record is
+//! missing is refused rather than treated as unrestricted.
+//!
+//! Records written before policies were sealed into key files carry the policy
+//! themselves; it is still enforced for the unbound key file next to them, and sealed in
+//! the next time the key or its policy is written.
+
+use super::core::{FileKeyStore, write_replacing};
+use super::encryption::{
+    decrypt_key_material, decrypt_with_aad, encrypt_key_material, encrypt_with_aad,
+};
+use crate::policy::{KeyPolicy, KeyUsage};
+use crate::{KeyError, KeyId, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::Mutex;
+use zeroize::Zeroizing;
+
+/// Associated data of a key file whose plaintext starts with its policy
+const POLICY_BOUND_AAD: &[u8] = b"cryypt-key-policy-v1";
+
+/// Persisted use counter
+#[derive(Serialize, Deserialize)]
+struct PolicyRecord {
+    key_file: String,
+    /// Only present in records written before policies were sealed into key files
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    policy: Option<KeyPolicy>,
+    #[serde(default)]
+    uses: u64,
+}
+
+/// Everything needed to read and write a key under its policy from inside a spawned task
+pub(super) struct PolicyGuard {
+    key_path: PathBuf,
+    path: PathBuf,
+    key_file: String,
+    key_id: String,
+    master_key: Arc<[u8; 32]>,
+    lock: Arc<Mutex<()>>,
+}
+
+impl FileKeyStore {
+    /// Attach `policy` to a stored key, replacing any previous policy
+    ///
+    /// The use count carries over, so tightening `max_uses` takes earlier retrievals into
+    /// account.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyNotFound` if the key does not exist, or an I/O or encryption
+    /// error if the key or its record cannot be written.
+    pub async fn set_policy(&self, key_id: &dyn KeyId, policy: KeyPolicy) -> Result<()> {
+        let key_path = self.key_id_path(key_id);
+        let guard = self.policy_guard(&key_path, key_id.full_id());
+        let _lock = guard.lock.lock().await;
+        let Some((material, _)) = guard.read_key().await? else {
+            return Err(KeyError::KeyNotFound {
+                id: key_id.id().to_string(),
+                version: key_id.version(),
+            });
+        };
+
+        // The record goes first so a sealed key is never left without one
+        let uses = guard.read().await?.map_or(0, |record| record.uses);
+        guard.write(None, uses).await?;
+        guard.write_key(&material, Some(&policy)).await
+    }
+
+    /// The policy attached to a stored key, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the key or its record cannot be read, or
+    /// `KeyError::DecryptionFailed` if either was tampered with.
+    pub async fn policy(&self, key_id: &dyn KeyId) -> Result<Option<KeyPolicy>> {
+        let key_path = self.key_id_path(key_id);
+        let guard = self.policy_guard(&key_path, key_id.full_id());
+        let _lock = guard.lock.lock().await;
+        guard.current_policy().await
+    }
+
+    pub(super) fn policy_guard(&self, key_path: &Path, key_id: String) -> PolicyGuard {
+        PolicyGuard {
+            key_path: key_path.to_path_buf(),
+            path: key_path.with_extension("policy"),
+            key_file: key_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            key_id,
+            master_key: Arc::clone(&self.master_key),
+            lock: Arc::clone(&self.policy_lock),
+        }
+    }
+}
+
+impl PolicyGuard {
+    /// Read the key, checking the retrieval against its policy and counting it
+    ///
+    /// Keys without a policy are always allowed.
+    pub(super) async fn retrieve(&self, usage: Option<&KeyUsage>) -> Result<Vec<u8>> {
+        let _lock = self.lock.lock().await;
+        let Some((material, sealed)) = self.read_key().await? else {
+            return Err(KeyError::Io(std::io::ErrorKind::NotFound.into()));
+        };
+
+        let (policy, uses, legacy) = match (sealed, self.read().await?) {
+            (Some(policy), Some(record)) => (policy, record.uses, false),
+            (Some(_), None) => {
+                return Err(KeyError::InvalidKeyFormat(format!(
+                    "Key policy record for '{}' is missing",
+                    self.key_file
+                )));
+            }
+            (
+                None,
+                Some(PolicyRecord {
+                    policy: Some(policy),
+                    uses,
+                    ..
+                }),
+            ) => (policy, uses, true),
+            (None, _) => return Ok(material.to_vec()),
+        };
+
+        policy
+            .check(usage, uses, chrono::Utc::now())
+            .map_err(|violation| KeyError::PolicyViolation {
+                key_id: self.key_id.clone(),
+                violation,
+            })?;
+
+        if policy.counts_uses() {
+            self.write(legacy.then_some(&policy), uses + 1).await?;
+        }
+        Ok(material.to_vec())
+    }
+
+    /// Write `material` as the key, sealed with `policy` or else with the policy the key
+    /// already has, so replacing a key never lifts its restrictions
+    ///
+    /// A new policy starts a fresh use count.
+    pub(super) async fn store_key(&self, material: &[u8], policy: Option<KeyPolicy>) -> Result<()> {
+        let _lock = self.lock.lock().await;
+        let policy = match policy {
+            Some(policy) => {
+                self.write(None, 0).await?;
+                Some(policy)
+            }
+            None => self.current_policy().await?,
+        };
+        self.write_key(material, policy.as_ref()).await
+    }
+
+    /// Remove the record along with its key
+    pub(super) async fn remove(&self) -> Result<()> {
+        let _lock = self.lock.lock().await;
+        match fs::remove_file(&self.path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(KeyError::Io(e)),
+        }
+    }
+
+    async fn current_policy(&self) -> Result<Option<KeyPolicy>> {
+        if let Some((_, Some(policy))) = self.read_key().await? {
+            return Ok(Some(policy));
+        }
+        Ok(self.read().await?.and_then(|record| record.policy))
+    }
+
+    async fn read_key(&self) -> Result<Option<(Zeroizing<Vec<u8>>, Option<KeyPolicy>)>> {
+        let encrypted = match fs::read(&self.key_path).await {
+            Ok(data) => Zeroizing::new(data),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(KeyError::Io(e)),
+        };
+        open_key_file(&encrypted, &self.master_key).map(Some)
+    }
+
+    async fn write_key(&self, material: &[u8], policy: Option<&KeyPolicy>) -> Result<()> {
+        let sealed = seal_key_file(material, policy, &self.master_key)?;
+        if let Some(parent) = self.key_path.parent() {
+            fs::create_dir_all(parent).await.map_err(KeyError::Io)?;
+        }
+        write_replacing(&self.key_path, &sealed).await
+    }
+
+    async fn read(&self) -> Result<Option<PolicyRecord>> {
+        let encrypted = match fs::read(&self.path).await {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(KeyError::Io(e)),
+        };
+
+        let json = decrypt_key_material(&encrypted, &self.master_key)?;
+        let record: PolicyRecord = serde_json::from_slice(&json)
+            .map_err(|e| KeyError::InvalidKeyFormat(format!("Invalid key policy record: {e}")))?;
+        if record.key_file != self.key_file {
+            return Err(KeyError::InvalidKeyFormat(format!(
+                "Key policy record belongs to '{}', not '{}'",
+                record.key_file, self.key_file
+            )));
+        }
+        Ok(Some(record))
+    }
+
+    async fn write(&self, policy: Option<&KeyPolicy>, uses: u64) -> Result<()> {
+        let record = PolicyRecord {
+            key_file: self.key_file.clone(),
+            policy: policy.cloned(),
+            uses,
+        };
+        let json = serde_json::to_vec(&record)
+            .map_err(|e| KeyError::Internal(format!("Serializing key policy failed: {e}")))?;
+        let encrypted = encrypt_key_material(&json, &self.master_key)?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await.map_err(KeyError::Io)?;
+        }
+        write_replacing(&self.path, &encrypted).await
+    }
+}
+
+/// Encrypt key material for a key file, sealing `policy` in with it
+///
+/// A sealed file holds the policy's JSON length (u32, big-endian), the JSON and the
+/// material, encrypted with [`POLICY_BOUND_AAD`]. The associated data keeps it from
+/// decrypting as an unbound key, so the policy cannot be stripped.
+pub(super) fn seal_key_file(
+    material: &[u8],
+    policy: Option<&KeyPolicy>,
+    master_key: &[u8; 32],
+) -> Result<Vec<u8>> {
+    let Some(policy) = policy else {
+        return encrypt_key_material(material, master_key);
+    };
+
+    let json = serde_json::to_vec(policy)
+        .map_err(|e| KeyError::Internal(format!("Serializing key policy failed: {e}")))?;
+    let length = u32::try_from(json.len())
+        .map_err(|_| KeyError::Internal("Key policy is too large".into()))?;
+    let mut plaintext = Zeroizing::new(Vec::with_capacity(4 + json.len() + material.len()));
+    plaintext.extend_from_slice(&length.to_be_bytes());
+    plaintext.extend_from_slice(&json);
+    plaintext.extend_from_slice(material);
+    encrypt_with_aad(&plaintext, POLICY_BOUND_AAD, master_key)
+}
+
+/// Decrypt a key file from [`seal_key_file`] into its material and sealed policy
+pub(super) fn open_key_file(
+    encrypted: &[u8],
+    master_key: &[u8; 32],
+) -> Result<(Zeroizing<Vec<u8>>, Option<KeyPolicy>)> {
+    let Ok(plaintext) = decrypt_with_aad(encrypted, POLICY_BOUND_AAD, master_key) else {
+        let material = decrypt_key_material(encrypted, master_key)?;
+        return Ok((Zeroizing::new(material), None));
+    };
+
+    let plaintext = Zeroizing::new(plaintext);
+    let truncated = || KeyError::InvalidKeyFormat("Policy-bound key file is truncated".into());
+    let (length, rest) = plaintext.split_first_chunk::<4>().ok_or_else(truncated)?;
+    let length = usize::try_from(u32::from_be_bytes(*length)).map_err(|_| truncated())?;
+    if rest.len() < length {
+        return Err(truncated());
+    }
+    let (json, material) = rest.split_at(length);
+    let policy = serde_json::from_slice(json)
+        .map_err(|e| KeyError::InvalidKeyFormat(format!("Invalid sealed key policy: {e}")))?;
+    Ok((Zeroizing::new(material.to_vec()), Some(policy)))
+}
@@ -4,84 +4,158 @@
 //! including key import, retrieval, generation, and enumeration operations.
 
 use super::core::FileKeyStore;
-use super::encryption::{decrypt_key_material, encrypt_key_material};
 use crate::api::KeyStore;
-use crate::{KeyError, KeyResult};
+use crate::policy::{KeyPolicy, KeyUsage};
+use crate::store_results::{DeleteResult, ExistsResult, RetrieveResult, StoreResult};
+use crate::traits::{KeyImport, KeyRetrieval, KeyStorage};
+use crate::{KeyError, KeyId, KeyResult};
+use std::path::PathBuf;
 use tokio::fs;
-use tokio::io::AsyncReadExt;
+use tokio::sync::oneshot;
 use zeroize::Zeroizing;
 
 impl KeyStore for FileKeyStore {
     /// Generate a new key
     fn generate_key(&self, size_bits: u32, namespace: &str, version: u32) -> KeyResult {
-        let key_size_bytes = (size_bits / 8) as usize;
+        KeyResult::new(self.spawn_generate(size_bits, namespace, version, None))
+    }
+
+    /// Retrieve an existing key
+    fn retrieve_key(&self, namespace: &str, version: u32) -> KeyResult {
         let path = self.key_path(namespace, version);
-        let master_key = self.master_key.clone();
+        KeyResult::new(self.spawn_retrieve(path, format!("{namespace}:v{version}"), None))
+    }
 
-        let (tx, rx) = tokio::sync::oneshot::channel();
+    /// Retrieve an existing key, checking `usage` against its policy
+    fn retrieve_key_for(&self, namespace: &str, version: u32, usage: &KeyUsage) -> KeyResult {
+        let path = self.key_path(namespace, version);
+        let key_id = format!("{namespace}:v{version}");
+        KeyResult::new(self.spawn_retrieve(path, key_id, Some(usage.clone())))
+    }
 
-        tokio::spawn(async move {
-            let result = async move {
-                // Direct async implementation - RNG and encryption are fast, no blocking needed
-                let (key_material, encrypted_data) = {
-                    use rand::RngCore;
+    /// Generate a new key with `policy` attached
+    fn generate_key_with_policy(
+        &self,
+        size_bits: u32,
+        namespace: &str,
+        version: u32,
+        policy: &KeyPolicy,
+    ) -> KeyResult {
+        KeyResult::new(self.spawn_generate(size_bits, namespace, version, Some(policy.clone())))
+    }
+}
 
-                    // Generate random key material
-                    let mut key_material = Zeroizing::new(vec![0u8; key_size_bytes]);
-                    rand::rng().fill_bytes(&mut key_material);
+impl FileKeyStore {
+    fn spawn_generate(
+        &self,
+        size_bits: u32,
+        namespace: &str,
+        version: u32,
+        policy: Option<KeyPolicy>,
+    ) -> oneshot::Receiver<crate::Result<Vec<u8>>> {
+        let key_size_bytes = (size_bits / 8) as usize;
+        let path = self.key_path(namespace, version);
+        let guard = self.policy_guard(&path, format!("{namespace}:v{version}"));
 
-                    // Encrypt the key material
-                    let encrypted_data = encrypt_key_material(&key_material, &master_key)?;
+        let (tx, rx) = oneshot::channel();
 
-                    (key_material.to_vec(), encrypted_data)
-                };
+        tokio::spawn(async move {
+            let result = async move {
+                use rand::RngCore;
 
-                // Ensure directory exists
-                if let Some(parent) = path.parent() {
-                    fs::create_dir_all(parent).await.map_err(KeyError::Io)?;
-                }
+                // Generate random key material
+                let mut key_material = Zeroizing::new(vec![0u8; key_size_bytes]);
+                rand::rng().fill_bytes(&mut key_material);
 
-                // Write encrypted data to file
-                fs::write(&path, &encrypted_data)
-                    .await
-                    .map_err(KeyError::Io)?;
+                // Seal the policy into the key file; without one, a policy already attached
+                // to this id stays in force
+                guard.store_key(&key_material, policy).await?;
 
-                Ok(key_material)
+                Ok(key_material.to_vec())
             }
             .await;
 
             let _ = tx.send(result);
         });
 
-        KeyResult::new(rx)
+        rx
     }
 
-    /// Retrieve an existing key
-    fn retrieve_key(&self, namespace: &str, version: u32) -> KeyResult {
-        let path = self.key_path(namespace, version);
-        let master_key = self.master_key.clone();
+    fn spawn_retrieve(
+        &self,
+        path: PathBuf,
+        key_id: String,
+        usage: Option<KeyUsage>,
+    ) -> oneshot::Receiver<crate::Result<Vec<u8>>> {
+        let guard = self.policy_guard(&path, key_id);
+
+        let (tx, rx) = oneshot::channel();
 
+        tokio::spawn(async move {
+            let result = guard.retrieve(usage.as_ref()).await;
+            let _ = tx.send(result);
+        });
+
+        rx
+    }
+}
+
+impl KeyStorage for FileKeyStore {
+    fn exists(&self, key_id: &dyn KeyId) -> ExistsResult {
+        let path = self.key_id_path(key_id);
         let (tx, rx) = tokio::sync::oneshot::channel();
 
         tokio::spawn(async move {
-            let result = async move {
-                // Read encrypted data
-                let mut file = fs::File::open(&path).await.map_err(KeyError::Io)?;
+            let result = fs::try_exists(&path).await.map_err(KeyError::Io);
+            let _ = tx.send(result);
+        });
+
+        ExistsResult::new(rx)
+    }
 
-                let mut encrypted_data = Zeroizing::new(Vec::new());
-                file.read_to_end(&mut encrypted_data)
-                    .await
-                    .map_err(KeyError::Io)?;
+    fn delete(&self, key_id: &dyn KeyId) -> DeleteResult {
+        let path = self.key_id_path(key_id);
+        let guard = self.policy_guard(&path, key_id.full_id());
+        let (tx, rx) = tokio::sync::oneshot::channel();
 
-                // Direct async implementation - decryption is fast, no blocking needed
-                decrypt_key_material(&encrypted_data, &master_key)
+        tokio::spawn(async move {
+            let result = async move {
+                fs::remove_file(&path).await.map_err(KeyError::Io)?;
+                guard.remove().await
             }
             .await;
+            let _ = tx.send(result);
+        });
+
+        DeleteResult::new(rx)
+    }
+}
+
+impl KeyRetrieval for FileKeyStore {
+    fn retrieve(&self, key_id: &dyn KeyId) -> RetrieveResult {
+        let path = self.key_id_path(key_id);
+        RetrieveResult::new(self.spawn_retrieve(path, key_id.full_id(), None))
+    }
+
+    fn retrieve_for(&self, key_id: &dyn KeyId, usage: &KeyUsage) -> RetrieveResult {
+        let path = self.key_id_path(key_id);
+        RetrieveResult::new(self.spawn_retrieve(path, key_id.full_id(), Some(usage.clone())))
+    }
+}
+
+impl KeyImport for FileKeyStore {
+    fn store(&self, key_id: &dyn KeyId, key_material: &[u8]) -> StoreResult {
+        let path = self.key_id_path(key_id);
+        let guard = self.policy_guard(&path, key_id.full_id());
+        let key_data = Zeroizing::new(key_material.to_vec());
+        let (tx, rx) = tokio::sync::oneshot::channel();
 
+        tokio::spawn(async move {
+            let result = guard.store_key(&key_data, None).await;
             let _ = tx.send(result);
         });
 
-        KeyResult::new(rx)
+        StoreResult::new(rx)
     }
 }
 
@@ -90,27 +164,15 @@ impl FileKeyStore {
     #[must_use]
     pub fn import_key(&self, key_material: &[u8], namespace: &str, version: u32) -> KeyResult {
         let path = self.key_path(namespace, version);
-        let master_key = self.master_key.clone();
+        let guard = self.policy_guard(&path, format!("{namespace}:v{version}"));
         let key_data = key_material.to_vec();
 
         let (tx, rx) = tokio::sync::oneshot::channel();
 
         tokio::spawn(async move {
             let result = async move {
-                let key_data_for_encryption = key_data.clone();
-                // Direct async implementation - encryption is fast, no blocking needed
-                let encrypted_data = encrypt_key_material(&key_data_for_encryption, &master_key)?;
-
-                // Ensure directory exists
-                if let Some(parent) = path.parent() {
-                    fs::create_dir_all(parent).await.map_err(KeyError::Io)?;
-                }
-
-                // Write encrypted data to file
-                fs::write(&path, &encrypted_data)
-                    .await
-                    .map_err(KeyError::Io)?;
-
+                // A policy already attached to this id stays in force
+                guard.store_key(&key_data, None).await?;
                 Ok(key_data)
             }
             .await;
@@ -5,6 +5,7 @@
 use super::keychain_service::get_keychain_service;
 use crate::{
     KeyId,
+    api::master_key_builder::{KdfProfile, KdfProfileStore},
     store_results::{DeleteResult, ExistsResult, ListResult, RetrieveResult, StoreResult},
     traits::{KeyEnumeration, KeyImport, KeyRetrieval, KeyStorage},
 };
@@ -125,3 +126,53 @@ impl KeyEnumeration for KeychainStore {
         ListResult::new(rx)
     }
 }
+
+impl KdfProfileStore for KeychainStore {
+    async fn load_profile(&self, namespace: &str) -> crate::Result<Option<KdfProfile>> {
+        let service = get_keychain_service().await?;
+        let account = kdf_profile_account(namespace);
+
+        if !service.exists(self.service_name.clone(), account.clone()).await? {
+            return Ok(None);
+        }
+        let json = service.retrieve(self.service_name.clone(), account).await?;
+        let json = String::from_utf8(json)
+            .map_err(|_| crate::KeyError::InvalidKeyFormat("KDF profile is not UTF-8".into()))?;
+        KdfProfile::from_json(&json).map(Some)
+    }
+
+    async fn save_profile(&self, namespace: &str, profile: &KdfProfile) -> crate::Result<()> {
+        let json = profile.to_json()?;
+        get_keychain_service()
+            .await?
+            .store(
+                self.service_name.clone(),
+                kdf_profile_account(namespace),
+                json.into_bytes(),
+            )
+            .await
+    }
+
+    /// Keychains have no create-if-absent primitive, so this is atomic only between
+    /// callers in one process; separate processes creating the first profile for the
+    /// same namespace at once can still race.
+    async fn save_profile_if_absent(
+        &self,
+        namespace: &str,
+        profile: &KdfProfile,
+    ) -> crate::Result<Option<KdfProfile>> {
+        static CREATE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+        let _guard = CREATE_LOCK.lock().await;
+
+        if let Some(existing) = self.load_profile(namespace).await? {
+            return Ok(Some(existing));
+        }
+        self.save_profile(namespace, profile).await?;
+        Ok(None)
+    }
+}
+
+/// Keychain account holding the KDF profile for `namespace`
+fn kdf_profile_account(namespace: &str) -> String {
+    format!("kdf-profile:{namespace}")
+}
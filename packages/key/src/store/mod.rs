@@ -3,6 +3,8 @@
 mod file_store;
 mod keychain_service;
 mod keychain_store;
+mod pkcs11_store;
 
 pub use file_store::{FileKeyStore, FileKeyStoreBuilder};
 pub use keychain_store::KeychainStore;
+pub use pkcs11_store::{Pkcs11Config, Pkcs11Store};
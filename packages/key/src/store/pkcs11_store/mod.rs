@@ -0,0 +1,72 @@
+//! PKCS#11 Key Storage Implementation
+//!
+//! Keys are generated and used inside a PKCS#11 token (an HSM, or SoftHSMv2 for
+//! development). Two kinds of objects live in the token:
+//!
+//! - Native keys created with [`Pkcs11Store::generate_aes_key`] and
+//!   [`Pkcs11Store::generate_ec_key`]. They are sensitive and non-extractable; encrypt,
+//!   sign, wrap and unwrap operations run on the device.
+//! - Key material handled through the storage traits (and `Key::size(..).with_store`),
+//!   kept as data objects sealed with AES-GCM under the store's key-encryption key. The
+//!   KEK itself is a non-extractable token key, so the sealed objects are useless
+//!   outside the device. The material is not: generating or retrieving it returns the
+//!   plaintext to the host, where it is only as safe as the process holding it.
+
+mod operations;
+mod session;
+mod storage_traits;
+
+pub use session::Pkcs11Config;
+
+use cryptoki::object::ObjectHandle;
+use cryptoki::session::Session;
+use session::Pkcs11Session;
+use std::sync::Arc;
+
+/// Key store backed by a PKCS#11 token
+///
+/// Cloning is cheap; clones share one logged-in session. PKCS#11 calls block, so every
+/// operation runs on tokio's blocking pool.
+#[derive(Clone)]
+pub struct Pkcs11Store {
+    session: Arc<Pkcs11Session>,
+}
+
+impl Pkcs11Store {
+    /// Load the module, log in to the token and find or create the key-encryption key
+    ///
+    /// This blocks while the module initializes; call it from `spawn_blocking` when
+    /// opening stores on a busy runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::StorageConnectionFailed` if the module cannot be loaded, no
+    /// token carries the configured label, or login fails.
+    pub fn connect(config: Pkcs11Config) -> crate::Result<Self> {
+        Ok(Self {
+            session: Arc::new(Pkcs11Session::open(config)?),
+        })
+    }
+
+    /// Label of the token this store is logged in to
+    #[must_use]
+    pub fn token_label(&self) -> &str {
+        self.session.token_label()
+    }
+
+    /// Run a blocking PKCS#11 operation on the blocking pool; the closure also receives
+    /// the key-encryption key handle
+    async fn run<T, F>(&self, op: F) -> crate::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Session, ObjectHandle) -> crate::Result<T> + Send + 'static,
+    {
+        let session = Arc::clone(&self.session);
+        tokio::task::spawn_blocking(move || {
+            let kek = session.kek();
+            session.with_session(|s| op(s, kek))
+        })
+        .await
+        .map_err(|e| crate::KeyError::Internal(format!("PKCS#11 task failed: {e}")))?
+    }
+}
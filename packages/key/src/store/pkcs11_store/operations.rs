@@ -0,0 +1,376 @@
+//! Cryptographic operations performed inside the token
+
+use super::Pkcs11Store;
+use super::session::{hsm_error, read_attribute};
+use crate::asymmetric::{AsymmetricPublicKey, EcCurve};
+use crate::{KeyError, Result};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::mechanism::aead::GcmParams;
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::Session;
+use sha2::{Digest, Sha256, Sha384};
+use zeroize::Zeroizing;
+
+/// DER-encoded `namedCurve` OIDs for `CKA_EC_PARAMS`
+const EC_PARAMS_P256: &[u8] = &[0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+const EC_PARAMS_P384: &[u8] = &[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x22];
+
+const GCM_IV_LEN: u32 = 12;
+const GCM_TAG_BITS: u64 = 128;
+
+impl Pkcs11Store {
+    /// Generate a non-extractable AES key in the token
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeySize` for sizes other than 128, 192 or 256 bits, or
+    /// a storage backend error if a key with this label exists or generation fails.
+    pub async fn generate_aes_key(&self, label: &str, size_bits: u32) -> Result<()> {
+        self.generate_secret_key(label, size_bits, false).await
+    }
+
+    /// Generate an AES key that can leave the token only wrapped under another token key
+    ///
+    /// The key is sensitive, so its plaintext value is never readable; see
+    /// [`Pkcs11Store::wrap_key`].
+    ///
+    /// # Errors
+    ///
+    /// As for [`Pkcs11Store::generate_aes_key`].
+    pub async fn generate_wrappable_aes_key(&self, label: &str, size_bits: u32) -> Result<()> {
+        self.generate_secret_key(label, size_bits, true).await
+    }
+
+    /// Generate a non-extractable EC signing key pair in the token
+    ///
+    /// Returns the public key; the private key stays on the device.
+    ///
+    /// # Errors
+    ///
+    /// Returns a storage backend error if a key with this label exists or generation
+    /// fails.
+    pub async fn generate_ec_key(
+        &self,
+        label: &str,
+        curve: EcCurve,
+    ) -> Result<AsymmetricPublicKey> {
+        let label = label.to_string();
+        self.run(move |session, _| {
+            ensure_absent(session, ObjectClass::PRIVATE_KEY, &label)?;
+            let params = match curve {
+                EcCurve::P256 => EC_PARAMS_P256,
+                EcCurve::P384 => EC_PARAMS_P384,
+            };
+
+            let (public, _) = session
+                .generate_key_pair(
+                    &Mechanism::EccKeyPairGen,
+                    &[
+                        Attribute::Token(true),
+                        Attribute::Verify(true),
+                        Attribute::EcParams(params.to_vec()),
+                        Attribute::Label(label.as_bytes().to_vec()),
+                    ],
+                    &[
+                        Attribute::Token(true),
+                        Attribute::Private(true),
+                        Attribute::Sensitive(true),
+                        Attribute::Extractable(false),
+                        Attribute::Sign(true),
+                        Attribute::Label(label.as_bytes().to_vec()),
+                    ],
+                )
+                .map_err(hsm_error("generate EC key pair"))?;
+            ec_public_key(session, public, curve)
+        })
+        .await
+    }
+
+    /// Public half of an EC key pair generated in the token
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyRetrieval` if no EC public key has this label.
+    pub async fn ec_public_key(&self, label: &str) -> Result<AsymmetricPublicKey> {
+        let label = label.to_string();
+        self.run(move |session, _| {
+            let public = find_object(session, ObjectClass::PUBLIC_KEY, &label)?;
+            let curve = ec_curve(session, public)?;
+            ec_public_key(session, public, curve)
+        })
+        .await
+    }
+
+    /// Sign `message` with the token's EC private key labelled `label`
+    ///
+    /// The message is hashed with SHA-256 (P-256) or SHA-384 (P-384) before the device
+    /// computes raw ECDSA, so the signature matches `ES256` / `ES384`: the fixed-length
+    /// `r || s` concatenation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyRetrieval` if the key is missing, or a storage backend
+    /// error if signing fails.
+    pub async fn sign(&self, label: &str, message: &[u8]) -> Result<Vec<u8>> {
+        let label = label.to_string();
+        let message = message.to_vec();
+        self.run(move |session, _| {
+            let private = find_object(session, ObjectClass::PRIVATE_KEY, &label)?;
+            let digest = match ec_curve(session, private)? {
+                EcCurve::P256 => Sha256::digest(&message).to_vec(),
+                EcCurve::P384 => Sha384::digest(&message).to_vec(),
+            };
+            session
+                .sign(&Mechanism::Ecdsa, private, &digest)
+                .map_err(hsm_error("sign"))
+        })
+        .await
+    }
+
+    /// Encrypt with the token's AES key labelled `label` using AES-GCM
+    ///
+    /// The output is the 12-byte IV followed by the ciphertext and 16-byte tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyRetrieval` if the key is missing, or a storage backend
+    /// error if encryption fails.
+    pub async fn encrypt(&self, label: &str, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+        let label = label.to_string();
+        let plaintext = Zeroizing::new(plaintext.to_vec());
+        let aad = aad.to_vec();
+        self.run(move |session, _| {
+            let key = find_object(session, ObjectClass::SECRET_KEY, &label)?;
+            seal(session, key, &plaintext, &aad)
+        })
+        .await
+    }
+
+    /// Decrypt output of [`Pkcs11Store::encrypt`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::DecryptionFailed` if the ciphertext or AAD was altered, and
+    /// `KeyError::KeyRetrieval` if the key is missing.
+    pub async fn decrypt(
+        &self,
+        label: &str,
+        ciphertext: &[u8],
+        aad: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>> {
+        let label = label.to_string();
+        let ciphertext = ciphertext.to_vec();
+        let aad = aad.to_vec();
+        self.run(move |session, _| {
+            let key = find_object(session, ObjectClass::SECRET_KEY, &label)?;
+            open(session, key, &ciphertext, &aad)
+        })
+        .await
+    }
+
+    /// Wrap the token key `key_label` under the token key `wrapping_label` with AES Key
+    /// Wrap with Padding (RFC 5649)
+    ///
+    /// Only keys created with [`Pkcs11Store::generate_wrappable_aes_key`] can be wrapped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyRetrieval` if either key is missing, or a storage backend
+    /// error if the token refuses to wrap the key.
+    pub async fn wrap_key(&self, wrapping_label: &str, key_label: &str) -> Result<Vec<u8>> {
+        let wrapping_label = wrapping_label.to_string();
+        let key_label = key_label.to_string();
+        self.run(move |session, _| {
+            let wrapping = find_object(session, ObjectClass::SECRET_KEY, &wrapping_label)?;
+            let key = find_object(session, ObjectClass::SECRET_KEY, &key_label)?;
+            session
+                .wrap_key(&Mechanism::AesKeyWrapPad, wrapping, key)
+                .map_err(hsm_error("wrap key"))
+        })
+        .await
+    }
+
+    /// Import a key wrapped with [`Pkcs11Store::wrap_key`] as a non-extractable AES key
+    /// labelled `new_label`
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::KeyRetrieval` if the wrapping key is missing, or a storage
+    /// backend error if the label is taken or unwrapping fails.
+    pub async fn unwrap_key(
+        &self,
+        wrapping_label: &str,
+        wrapped: &[u8],
+        new_label: &str,
+    ) -> Result<()> {
+        let wrapping_label = wrapping_label.to_string();
+        let wrapped = wrapped.to_vec();
+        let new_label = new_label.to_string();
+        self.run(move |session, _| {
+            ensure_absent(session, ObjectClass::SECRET_KEY, &new_label)?;
+            let wrapping = find_object(session, ObjectClass::SECRET_KEY, &wrapping_label)?;
+            session
+                .unwrap_key(
+                    &Mechanism::AesKeyWrapPad,
+                    wrapping,
+                    &wrapped,
+                    &[
+                        Attribute::Class(ObjectClass::SECRET_KEY),
+                        Attribute::KeyType(KeyType::AES),
+                        Attribute::Token(true),
+                        Attribute::Private(true),
+                        Attribute::Sensitive(true),
+                        Attribute::Extractable(false),
+                        Attribute::Encrypt(true),
+                        Attribute::Decrypt(true),
+                        Attribute::Label(new_label.as_bytes().to_vec()),
+                    ],
+                )
+                .map(|_| ())
+                .map_err(hsm_error("unwrap key"))
+        })
+        .await
+    }
+
+    async fn generate_secret_key(
+        &self,
+        label: &str,
+        size_bits: u32,
+        wrappable: bool,
+    ) -> Result<()> {
+        if !matches!(size_bits, 128 | 192 | 256) {
+            return Err(KeyError::InvalidKeySize {
+                expected: 256,
+                actual: size_bits as usize,
+            });
+        }
+
+        let label = label.to_string();
+        self.run(move |session, _| {
+            ensure_absent(session, ObjectClass::SECRET_KEY, &label)?;
+            session
+                .generate_key(
+                    &Mechanism::AesKeyGen,
+                    &[
+                        Attribute::KeyType(KeyType::AES),
+                        Attribute::ValueLen(u64::from(size_bits / 8).into()),
+                        Attribute::Token(true),
+                        Attribute::Private(true),
+                        Attribute::Sensitive(true),
+                        Attribute::Extractable(wrappable),
+                        Attribute::Encrypt(true),
+                        Attribute::Decrypt(true),
+                        Attribute::Wrap(true),
+                        Attribute::Unwrap(true),
+                        Attribute::Label(label.as_bytes().to_vec()),
+                    ],
+                )
+                .map(|_| ())
+                .map_err(hsm_error("generate AES key"))
+        })
+        .await
+    }
+}
+
+/// AES-GCM encrypt inside the token, returning `iv || ciphertext || tag`
+pub(super) fn seal(
+    session: &Session,
+    key: ObjectHandle,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>> {
+    let mut iv = session
+        .generate_random_vec(GCM_IV_LEN)
+        .map_err(hsm_error("generate IV"))?;
+    let params = GcmParams::new(&mut iv, aad, GCM_TAG_BITS.into())
+        .map_err(hsm_error("prepare AES-GCM"))?;
+    let ciphertext = session
+        .encrypt(&Mechanism::AesGcm(params), key, plaintext)
+        .map_err(hsm_error("encrypt"))?;
+
+    let mut sealed = iv;
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// Reverse [`seal`]
+pub(super) fn open(
+    session: &Session,
+    key: ObjectHandle,
+    sealed: &[u8],
+    aad: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    let iv_len = GCM_IV_LEN as usize;
+    if sealed.len() < iv_len + 16 {
+        return Err(KeyError::DecryptionFailed("Sealed data too short".into()));
+    }
+    let (iv, ciphertext) = sealed.split_at(iv_len);
+    let mut iv = iv.to_vec();
+    let params = GcmParams::new(&mut iv, aad, GCM_TAG_BITS.into())
+        .map_err(hsm_error("prepare AES-GCM"))?;
+    session
+        .decrypt(&Mechanism::AesGcm(params), key, ciphertext)
+        .map(Zeroizing::new)
+        .map_err(|e| KeyError::DecryptionFailed(format!("Token AES-GCM decryption failed: {e}")))
+}
+
+/// Find the single object of `class` labelled `label`
+pub(super) fn find_object(
+    session: &Session,
+    class: ObjectClass,
+    label: &str,
+) -> Result<ObjectHandle> {
+    session
+        .find_objects(&[
+            Attribute::Class(class),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])
+        .map_err(hsm_error("find object"))?
+        .first()
+        .copied()
+        .ok_or_else(|| KeyError::KeyRetrieval(format!("No {class} object labelled '{label}'")))
+}
+
+fn ensure_absent(session: &Session, class: ObjectClass, label: &str) -> Result<()> {
+    match find_object(session, class, label) {
+        Ok(_) => Err(KeyError::StorageBackendError {
+            operation: "generate key".into(),
+            details: format!("A {class} object labelled '{label}' already exists"),
+        }),
+        Err(KeyError::KeyRetrieval(_)) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn ec_curve(session: &Session, key: ObjectHandle) -> Result<EcCurve> {
+    match read_attribute(session, key, AttributeType::EcParams)? {
+        Attribute::EcParams(params) if params == EC_PARAMS_P256 => Ok(EcCurve::P256),
+        Attribute::EcParams(params) if params == EC_PARAMS_P384 => Ok(EcCurve::P384),
+        _ => Err(KeyError::InvalidKeyFormat(
+            "Token key is not on a supported EC curve".into(),
+        )),
+    }
+}
+
+fn ec_public_key(
+    session: &Session,
+    public: ObjectHandle,
+    curve: EcCurve,
+) -> Result<AsymmetricPublicKey> {
+    let Attribute::EcPoint(point) = read_attribute(session, public, AttributeType::EcPoint)?
+    else {
+        return Err(KeyError::InvalidKeyFormat("Token returned no EC point".into()));
+    };
+
+    // CKA_EC_POINT is a DER OCTET STRING around the SEC1 point, though some modules
+    // return the bare point
+    let der_wrapped = {
+        use pkcs8::der::{Decode, asn1::OctetStringRef};
+        OctetStringRef::from_der(&point).map(|octets| octets.as_bytes().to_vec())
+    };
+    match der_wrapped {
+        Ok(inner) => AsymmetricPublicKey::from_bytes(curve.into(), &inner)
+            .or_else(|_| AsymmetricPublicKey::from_bytes(curve.into(), &point)),
+        Err(_) => AsymmetricPublicKey::from_bytes(curve.into(), &point),
+    }
+}
@@ -0,0 +1,202 @@
+//! Module loading, login and the shared token session
+
+use crate::{KeyError, Result};
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::error::{Error as CryptokiError, RvError};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use zeroize::Zeroizing;
+
+/// Default label of the store's key-encryption key
+const DEFAULT_KEK_LABEL: &str = "cryypt-kek";
+
+/// Connection settings for [`Pkcs11Store`](super::Pkcs11Store)
+pub struct Pkcs11Config {
+    module: PathBuf,
+    token_label: String,
+    pin: Zeroizing<String>,
+    kek_label: String,
+}
+
+impl Pkcs11Config {
+    /// Settings for the token labelled `token_label` in the PKCS#11 `module`
+    /// (for example `/usr/lib/softhsm/libsofthsm2.so`), logging in with the user PIN
+    pub fn new(
+        module: impl Into<PathBuf>,
+        token_label: impl Into<String>,
+        pin: impl Into<String>,
+    ) -> Self {
+        Self {
+            module: module.into(),
+            token_label: token_label.into(),
+            pin: Zeroizing::new(pin.into()),
+            kek_label: DEFAULT_KEK_LABEL.to_string(),
+        }
+    }
+
+    /// Label of the AES key that seals stored key material (default `cryypt-kek`)
+    #[must_use]
+    pub fn with_kek_label(mut self, kek_label: impl Into<String>) -> Self {
+        self.kek_label = kek_label.into();
+        self
+    }
+}
+
+impl std::fmt::Debug for Pkcs11Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pkcs11Config")
+            .field("module", &self.module)
+            .field("token_label", &self.token_label)
+            .field("kek_label", &self.kek_label)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Logged-in session plus the handle of the key-encryption key
+pub(super) struct Pkcs11Session {
+    session: Mutex<Session>,
+    kek: ObjectHandle,
+    token_label: String,
+}
+
+impl Pkcs11Session {
+    pub(super) fn open(config: Pkcs11Config) -> Result<Self> {
+        let pkcs11 = load_module(&config.module)?;
+        let slot = pkcs11
+            .get_slots_with_token()
+            .map_err(|e| connection_error(format!("Listing slots failed: {e}")))?
+            .into_iter()
+            .find(|slot| {
+                pkcs11
+                    .get_token_info(*slot)
+                    .is_ok_and(|info| info.label().trim() == config.token_label)
+            })
+            .ok_or_else(|| {
+                connection_error(format!("No token labelled '{}'", config.token_label))
+            })?;
+
+        let session = pkcs11
+            .open_rw_session(slot)
+            .map_err(|e| connection_error(format!("Opening session failed: {e}")))?;
+        let pin = AuthPin::new(config.pin.as_str().into());
+        match session.login(UserType::User, Some(&pin)) {
+            // Login state is shared by all sessions of the application
+            Ok(()) | Err(CryptokiError::Pkcs11(RvError::UserAlreadyLoggedIn, _)) => {}
+            Err(e) => return Err(connection_error(format!("Login failed: {e}"))),
+        }
+
+        let kek = find_or_create_kek(&session, &config.kek_label)?;
+        Ok(Self {
+            session: Mutex::new(session),
+            kek,
+            token_label: config.token_label,
+        })
+    }
+
+    pub(super) fn token_label(&self) -> &str {
+        &self.token_label
+    }
+
+    pub(super) fn kek(&self) -> ObjectHandle {
+        self.kek
+    }
+
+    /// Run a blocking operation against the session
+    pub(super) fn with_session<T>(&self, op: impl FnOnce(&Session) -> Result<T>) -> Result<T> {
+        let session = self
+            .session
+            .lock()
+            .map_err(|_| KeyError::Internal("PKCS#11 session lock poisoned".into()))?;
+        op(&session)
+    }
+}
+
+/// Modules may only be initialized once per process, so loaded contexts are shared
+fn load_module(path: &Path) -> Result<Pkcs11> {
+    static MODULES: OnceLock<Mutex<HashMap<PathBuf, Pkcs11>>> = OnceLock::new();
+
+    let mut modules = MODULES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map_err(|_| KeyError::Internal("PKCS#11 module cache poisoned".into()))?;
+    if let Some(pkcs11) = modules.get(path) {
+        return Ok(pkcs11.clone());
+    }
+
+    let pkcs11 = Pkcs11::new(path)
+        .map_err(|e| connection_error(format!("Loading {} failed: {e}", path.display())))?;
+    match pkcs11.initialize(CInitializeArgs::OsThreads) {
+        Ok(()) | Err(CryptokiError::Pkcs11(RvError::CryptokiAlreadyInitialized, _)) => {}
+        Err(e) => return Err(connection_error(format!("C_Initialize failed: {e}"))),
+    }
+
+    modules.insert(path.to_path_buf(), pkcs11.clone());
+    Ok(pkcs11)
+}
+
+fn find_or_create_kek(session: &Session, label: &str) -> Result<ObjectHandle> {
+    let existing = session
+        .find_objects(&[
+            Attribute::Class(ObjectClass::SECRET_KEY),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])
+        .map_err(hsm_error("find key-encryption key"))?;
+    if let Some(kek) = existing.first() {
+        return Ok(*kek);
+    }
+
+    tracing::info!("Creating PKCS#11 key-encryption key '{label}'");
+    session
+        .generate_key(
+            &Mechanism::AesKeyGen,
+            &[
+                Attribute::KeyType(KeyType::AES),
+                Attribute::ValueLen(32.into()),
+                Attribute::Token(true),
+                Attribute::Private(true),
+                Attribute::Sensitive(true),
+                Attribute::Extractable(false),
+                Attribute::Encrypt(true),
+                Attribute::Decrypt(true),
+                Attribute::Label(label.as_bytes().to_vec()),
+            ],
+        )
+        .map_err(hsm_error("generate key-encryption key"))
+}
+
+/// Read one attribute value of an object
+pub(super) fn read_attribute(
+    session: &Session,
+    object: ObjectHandle,
+    attribute: AttributeType,
+) -> Result<Attribute> {
+    session
+        .get_attributes(object, &[attribute])
+        .map_err(hsm_error("read attribute"))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| KeyError::StorageBackendError {
+            operation: "read attribute".into(),
+            details: format!("Token did not return {attribute:?}"),
+        })
+}
+
+fn connection_error(details: String) -> KeyError {
+    KeyError::StorageConnectionFailed {
+        backend_type: "pkcs11".into(),
+        details,
+    }
+}
+
+/// Map a cryptoki error to a storage backend error for `operation`
+pub(super) fn hsm_error(operation: &'static str) -> impl Fn(CryptokiError) -> KeyError {
+    move |e| KeyError::StorageBackendError {
+        operation: operation.into(),
+        details: e.to_string(),
+    }
+}
@@ -0,0 +1,206 @@
+//! Storage Trait Implementations
+//!
+//! Key material handled through the storage traits is sealed with AES-GCM under the
+//! token's key-encryption key and kept as a private `CKO_DATA` object labelled with the
+//! key's full id. The label doubles as AAD, so sealed values cannot be swapped between ids.
+//!
+//! Sealing protects the stored object, not the material: `generate` and `retrieve` open it
+//! on the token and return the plaintext to the caller, as the traits require.
+
+use super::Pkcs11Store;
+use super::operations::{find_object, open, seal};
+use super::session::{hsm_error, read_attribute};
+use crate::api::KeyStore;
+use crate::store_results::{DeleteResult, ExistsResult, RetrieveResult, StoreResult};
+use crate::traits::{KeyGeneration, KeyImport, KeyRetrieval, KeyStorage};
+use crate::{KeyError, KeyId, KeyResult, SimpleKeyId};
+use cryptoki::object::{Attribute, AttributeType, ObjectClass, ObjectHandle};
+use cryptoki::session::Session;
+use zeroize::Zeroizing;
+
+impl KeyStorage for Pkcs11Store {
+    fn exists(&self, key_id: &dyn KeyId) -> ExistsResult {
+        let store = self.clone();
+        let label = key_id.full_id();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        tokio::spawn(async move {
+            let result = store
+                .run(move |session, _| Ok(!find_data_objects(session, &label)?.is_empty()))
+                .await;
+            let _ = tx.send(result);
+        });
+
+        ExistsResult::new(rx)
+    }
+
+    fn delete(&self, key_id: &dyn KeyId) -> DeleteResult {
+        let store = self.clone();
+        let label = key_id.full_id();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        tokio::spawn(async move {
+            let result = store
+                .run(move |session, _| destroy_data_objects(session, &label))
+                .await;
+            let _ = tx.send(result);
+        });
+
+        DeleteResult::new(rx)
+    }
+}
+
+impl KeyImport for Pkcs11Store {
+    fn store(&self, key_id: &dyn KeyId, key_material: &[u8]) -> StoreResult {
+        let store = self.clone();
+        let label = key_id.full_id();
+        let key_material = Zeroizing::new(key_material.to_vec());
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        tokio::spawn(async move {
+            let result = store
+                .run(move |session, kek| store_sealed(session, kek, &label, &key_material))
+                .await;
+            let _ = tx.send(result);
+        });
+
+        StoreResult::new(rx)
+    }
+}
+
+impl KeyRetrieval for Pkcs11Store {
+    fn retrieve(&self, key_id: &dyn KeyId) -> RetrieveResult {
+        let store = self.clone();
+        let label = key_id.full_id();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        tokio::spawn(async move {
+            let result = store
+                .run(move |session, kek| retrieve_sealed(session, kek, &label))
+                .await;
+            let _ = tx.send(result);
+        });
+
+        RetrieveResult::new(rx)
+    }
+}
+
+impl KeyGeneration for Pkcs11Store {
+    fn generate(&self, key_id: &dyn KeyId, key_size_bytes: usize) -> RetrieveResult {
+        let store = self.clone();
+        let label = key_id.full_id();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        tokio::spawn(async move {
+            let result = store
+                .run(move |session, kek| generate_sealed(session, kek, &label, key_size_bytes))
+                .await;
+            let _ = tx.send(result);
+        });
+
+        RetrieveResult::new(rx)
+    }
+}
+
+impl KeyStore for Pkcs11Store {
+    /// Generate key material with the token's RNG and store it sealed
+    fn generate_key(&self, size_bits: u32, namespace: &str, version: u32) -> KeyResult {
+        let store = self.clone();
+        let label = SimpleKeyId::new(format!("{namespace}:v{version}")).full_id();
+        let key_size_bytes = (size_bits / 8) as usize;
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        tokio::spawn(async move {
+            let result = store
+                .run(move |session, kek| generate_sealed(session, kek, &label, key_size_bytes))
+                .await;
+            let _ = tx.send(result);
+        });
+
+        KeyResult::new(rx)
+    }
+
+    /// Retrieve sealed key material
+    fn retrieve_key(&self, namespace: &str, version: u32) -> KeyResult {
+        let store = self.clone();
+        let label = SimpleKeyId::new(format!("{namespace}:v{version}")).full_id();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        tokio::spawn(async move {
+            let result = store
+                .run(move |session, kek| retrieve_sealed(session, kek, &label))
+                .await;
+            let _ = tx.send(result);
+        });
+
+        KeyResult::new(rx)
+    }
+}
+
+fn find_data_objects(session: &Session, label: &str) -> crate::Result<Vec<ObjectHandle>> {
+    session
+        .find_objects(&[
+            Attribute::Class(ObjectClass::DATA),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])
+        .map_err(hsm_error("find data object"))
+}
+
+fn destroy_data_objects(session: &Session, label: &str) -> crate::Result<()> {
+    for object in find_data_objects(session, label)? {
+        session
+            .destroy_object(object)
+            .map_err(hsm_error("destroy data object"))?;
+    }
+    Ok(())
+}
+
+fn store_sealed(
+    session: &Session,
+    kek: ObjectHandle,
+    label: &str,
+    key_material: &[u8],
+) -> crate::Result<()> {
+    let sealed = seal(session, kek, key_material, label.as_bytes())?;
+    destroy_data_objects(session, label)?;
+    session
+        .create_object(&[
+            Attribute::Class(ObjectClass::DATA),
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Label(label.as_bytes().to_vec()),
+            Attribute::Value(sealed),
+        ])
+        .map(|_| ())
+        .map_err(hsm_error("create data object"))
+}
+
+fn retrieve_sealed(session: &Session, kek: ObjectHandle, label: &str) -> crate::Result<Vec<u8>> {
+    let object = find_object(session, ObjectClass::DATA, label)?;
+    let Attribute::Value(sealed) = read_attribute(session, object, AttributeType::Value)? else {
+        return Err(KeyError::InvalidKeyFormat(format!(
+            "Data object '{label}' has no value"
+        )));
+    };
+    open(session, kek, &sealed, label.as_bytes()).map(|material| material.to_vec())
+}
+
+fn generate_sealed(
+    session: &Session,
+    kek: ObjectHandle,
+    label: &str,
+    key_size_bytes: usize,
+) -> crate::Result<Vec<u8>> {
+    let length = u32::try_from(key_size_bytes).map_err(|_| {
+        KeyError::KeyGeneration(format!(
+            "{key_size_bytes}-byte key exceeds a PKCS#11 random request"
+        ))
+    })?;
+    let material = Zeroizing::new(
+        session
+            .generate_random_vec(length)
+            .map_err(hsm_error("generate random"))?,
+    );
+    store_sealed(session, kek, label, &material)?;
+    Ok(material.to_vec())
+}
@@ -17,6 +17,13 @@ impl ExistsResult {
         Self { receiver }
     }
 
+    /// Await the raw result for crate-internal callers that propagate errors
+    pub(crate) async fn recv(self) -> Result<bool> {
+        self.receiver
+            .await
+            .unwrap_or_else(|_| Err(KeyError::internal("Exists check task dropped")))
+    }
+
     /// Add a result handler following README.md pattern
     pub fn on_result<F, T>(self, handler: F) -> ExistsResultWithHandler<F>
     where
@@ -101,6 +108,13 @@ impl DeleteResult {
         Self { receiver }
     }
 
+    /// Await the raw result for crate-internal callers that propagate errors
+    pub(crate) async fn recv(self) -> Result<()> {
+        self.receiver
+            .await
+            .unwrap_or_else(|_| Err(KeyError::internal("Delete task dropped")))
+    }
+
     /// Add a result handler following README.md pattern
     pub fn on_result<F, T>(self, handler: F) -> DeleteResultWithHandler<F>
     where
@@ -183,6 +197,13 @@ impl StoreResult {
         Self { receiver }
     }
 
+    /// Await the raw result for crate-internal callers that propagate errors
+    pub(crate) async fn recv(self) -> Result<()> {
+        self.receiver
+            .await
+            .unwrap_or_else(|_| Err(KeyError::internal("Store task dropped")))
+    }
+
     /// Add a result handler following README.md pattern
     pub fn on_result<F, T>(self, handler: F) -> StoreResultWithHandler<F>
     where
@@ -265,6 +286,13 @@ impl RetrieveResult {
         Self { receiver }
     }
 
+    /// Await the raw result for crate-internal callers that propagate errors
+    pub(crate) async fn recv(self) -> Result<Vec<u8>> {
+        self.receiver
+            .await
+            .unwrap_or_else(|_| Err(KeyError::internal("Retrieve task dropped")))
+    }
+
     /// Add a result handler following README.md pattern
     pub fn on_result<F, T>(self, handler: F) -> RetrieveResultWithHandler<F>
     where
@@ -0,0 +1,359 @@
+//! Typed asymmetric key pairs in a key store
+//!
+//! [`StoredKeyPair`] keeps a public and secret key together with a [`StoredKeyKind`] naming
+//! the algorithm, so post-quantum (ML-KEM, ML-DSA) and classical key pairs share the file,
+//! keychain and PKCS#11 stores with symmetric keys. Loading names the expected kind, and a
+//! key of another kind is refused instead of being handed to the wrong algorithm.
+//!
+//! Stored keys use a small binary layout, left to the store's own master-key encryption:
+//!
+//! ```text
+//! "CKP1" || kind (1 byte) || public key length (u32, big-endian) || public key || secret key
+//! ```
+
+use crate::asymmetric::{AsymmetricAlgorithm, AsymmetricKey, AsymmetricPublicKey};
+use crate::traits::{KeyImport, KeyRetrieval};
+use crate::{KeyError, KeyId, Result};
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Magic prefix of an encoded [`StoredKeyPair`]
+const MAGIC: &[u8; 4] = b"CKP1";
+
+/// Length of the fixed header: magic, kind and public key length
+const HEADER_LEN: usize = MAGIC.len() + 1 + 4;
+
+/// Algorithm of a stored key pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StoredKeyKind {
+    /// Ed25519 signing keys (RFC 8032)
+    Ed25519,
+    /// X25519 key agreement keys (RFC 7748)
+    X25519,
+    /// ECDSA / ECDH keys on NIST P-256
+    P256,
+    /// ECDSA / ECDH keys on NIST P-384
+    P384,
+    /// RSA keys (RFC 8017), stored as PKCS#1 DER
+    Rsa,
+    /// ML-KEM-512 (FIPS 203)
+    MlKem512,
+    /// ML-KEM-768 (FIPS 203)
+    MlKem768,
+    /// ML-KEM-1024 (FIPS 203)
+    MlKem1024,
+    /// ML-DSA-44 (FIPS 204)
+    MlDsa44,
+    /// ML-DSA-65 (FIPS 204)
+    MlDsa65,
+    /// ML-DSA-87 (FIPS 204)
+    MlDsa87,
+}
+
+impl StoredKeyKind {
+    /// Every kind, in tag order
+    pub const ALL: [Self; 11] = [
+        Self::Ed25519,
+        Self::X25519,
+        Self::P256,
+        Self::P384,
+        Self::Rsa,
+        Self::MlKem512,
+        Self::MlKem768,
+        Self::MlKem1024,
+        Self::MlDsa44,
+        Self::MlDsa65,
+        Self::MlDsa87,
+    ];
+
+    /// Algorithm name
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ed25519 => "Ed25519",
+            Self::X25519 => "X25519",
+            Self::P256 => "P-256",
+            Self::P384 => "P-384",
+            Self::Rsa => "RSA",
+            Self::MlKem512 => "ML-KEM-512",
+            Self::MlKem768 => "ML-KEM-768",
+            Self::MlKem1024 => "ML-KEM-1024",
+            Self::MlDsa44 => "ML-DSA-44",
+            Self::MlDsa65 => "ML-DSA-65",
+            Self::MlDsa87 => "ML-DSA-87",
+        }
+    }
+
+    /// Whether this is a post-quantum algorithm
+    #[must_use]
+    pub fn is_post_quantum(self) -> bool {
+        self.pq_key_sizes().is_some()
+    }
+
+    /// The classical algorithm, for kinds that [`AsymmetricKey`] can hold
+    #[must_use]
+    pub fn asymmetric_algorithm(self) -> Option<AsymmetricAlgorithm> {
+        match self {
+            Self::Ed25519 => Some(AsymmetricAlgorithm::Ed25519),
+            Self::X25519 => Some(AsymmetricAlgorithm::X25519),
+            Self::P256 => Some(AsymmetricAlgorithm::P256),
+            Self::P384 => Some(AsymmetricAlgorithm::P384),
+            Self::Rsa => Some(AsymmetricAlgorithm::Rsa),
+            _ => None,
+        }
+    }
+
+    /// Public and secret key sizes in bytes of the post-quantum kinds
+    fn pq_key_sizes(self) -> Option<(usize, usize)> {
+        match self {
+            Self::MlKem512 => Some((800, 1632)),
+            Self::MlKem768 => Some((1184, 2400)),
+            Self::MlKem1024 => Some((1568, 3168)),
+            Self::MlDsa44 => Some((1312, 2560)),
+            Self::MlDsa65 => Some((1952, 4032)),
+            Self::MlDsa87 => Some((2592, 4896)),
+            Self::Ed25519 | Self::X25519 | Self::P256 | Self::P384 | Self::Rsa => None,
+        }
+    }
+
+    /// Stable one-byte tag used in the stored encoding
+    fn tag(self) -> u8 {
+        match self {
+            Self::Ed25519 => 1,
+            Self::X25519 => 2,
+            Self::P256 => 3,
+            Self::P384 => 4,
+            Self::Rsa => 5,
+            Self::MlKem512 => 16,
+            Self::MlKem768 => 17,
+            Self::MlKem1024 => 18,
+            Self::MlDsa44 => 32,
+            Self::MlDsa65 => 33,
+            Self::MlDsa87 => 34,
+        }
+    }
+
+    fn from_tag(tag: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.tag() == tag)
+    }
+}
+
+impl fmt::Display for StoredKeyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<AsymmetricAlgorithm> for StoredKeyKind {
+    fn from(algorithm: AsymmetricAlgorithm) -> Self {
+        match algorithm {
+            AsymmetricAlgorithm::Ed25519 => Self::Ed25519,
+            AsymmetricAlgorithm::X25519 => Self::X25519,
+            AsymmetricAlgorithm::P256 => Self::P256,
+            AsymmetricAlgorithm::P384 => Self::P384,
+            AsymmetricAlgorithm::Rsa => Self::Rsa,
+        }
+    }
+}
+
+/// An asymmetric key pair tagged with its algorithm, as kept in a key store
+///
+/// The secret key zeroizes on drop. `Debug` output shows only the kind and public key length.
+#[derive(Clone)]
+pub struct StoredKeyPair {
+    kind: StoredKeyKind,
+    public_key: Vec<u8>,
+    secret_key: Zeroizing<Vec<u8>>,
+}
+
+impl StoredKeyPair {
+    /// Pair raw public and secret key bytes
+    ///
+    /// Post-quantum keys are checked against the algorithm's key sizes; classical keys must
+    /// decode as [`AsymmetricPublicKey::from_bytes`] and
+    /// [`AsymmetricKey::from_secret_bytes`] accept them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeySize` or `KeyError::InvalidKey` if either key does not
+    /// fit `kind`.
+    pub fn new(
+        kind: StoredKeyKind,
+        public_key: impl Into<Vec<u8>>,
+        secret_key: impl Into<Vec<u8>>,
+    ) -> Result<Self> {
+        let pair = Self {
+            kind,
+            public_key: public_key.into(),
+            secret_key: Zeroizing::new(secret_key.into()),
+        };
+        pair.validate()?;
+        Ok(pair)
+    }
+
+    /// Store a classical key pair
+    #[must_use]
+    pub fn from_asymmetric(key: &AsymmetricKey) -> Self {
+        Self {
+            kind: key.algorithm().into(),
+            public_key: key.public_key().to_bytes(),
+            secret_key: key.secret_bytes(),
+        }
+    }
+
+    /// Rebuild the classical key pair
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for post-quantum kinds.
+    pub fn to_asymmetric(&self) -> Result<AsymmetricKey> {
+        let algorithm = self.kind.asymmetric_algorithm().ok_or_else(|| {
+            KeyError::InvalidKeyFormat(format!("{} is not a classical key", self.kind))
+        })?;
+        AsymmetricKey::from_secret_bytes(algorithm, &self.secret_key)
+    }
+
+    /// Algorithm of this key pair
+    #[must_use]
+    pub fn kind(&self) -> StoredKeyKind {
+        self.kind
+    }
+
+    /// Public key bytes
+    #[must_use]
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// Secret key bytes
+    #[must_use]
+    pub fn secret_key(&self) -> &[u8] {
+        &self.secret_key
+    }
+
+    /// Encode for storage
+    #[must_use]
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(
+            HEADER_LEN + self.public_key.len() + self.secret_key.len(),
+        ));
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(self.kind.tag());
+        #[allow(clippy::cast_possible_truncation)] // Validated keys are far below 4 GiB
+        bytes.extend_from_slice(&(self.public_key.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&self.public_key);
+        bytes.extend_from_slice(&self.secret_key);
+        bytes
+    }
+
+    /// Decode a key pair written by [`StoredKeyPair::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` for malformed input or an unknown kind, or an
+    /// error as [`StoredKeyPair::new`] if the keys do not fit their kind.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(KeyError::InvalidKeyFormat(
+                "Not a stored key pair".to_string(),
+            ));
+        }
+        let kind = StoredKeyKind::from_tag(bytes[MAGIC.len()]).ok_or_else(|| {
+            KeyError::InvalidKeyFormat(format!("Unknown stored key kind {}", bytes[MAGIC.len()]))
+        })?;
+        let mut length = [0u8; 4];
+        length.copy_from_slice(&bytes[MAGIC.len() + 1..HEADER_LEN]);
+        let public_len = usize::try_from(u32::from_be_bytes(length))
+            .map_err(|_| KeyError::InvalidKeyFormat("Public key too long".to_string()))?;
+        let body = &bytes[HEADER_LEN..];
+        if public_len > body.len() {
+            return Err(KeyError::InvalidKeyFormat(
+                "Stored key pair is truncated".to_string(),
+            ));
+        }
+        let (public_key, secret_key) = body.split_at(public_len);
+        Self::new(kind, public_key, secret_key)
+    }
+
+    /// Write the key pair to `store` under `key_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store rejects the key.
+    pub async fn save<S: KeyImport>(&self, store: &S, key_id: &dyn KeyId) -> Result<()> {
+        store.store(key_id, &self.to_bytes()).recv().await
+    }
+
+    /// Load a key pair of kind `expected` written with [`StoredKeyPair::save`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is missing or malformed, and `KeyError::InvalidKey` if
+    /// it is a key pair of another kind.
+    pub async fn load<S: KeyRetrieval>(
+        store: &S,
+        key_id: &dyn KeyId,
+        expected: StoredKeyKind,
+    ) -> Result<Self> {
+        let pair = Self::load_any(store, key_id).await?;
+        if pair.kind != expected {
+            return Err(KeyError::InvalidKey(format!(
+                "Key {} is {}, expected {expected}",
+                key_id.full_id(),
+                pair.kind
+            )));
+        }
+        Ok(pair)
+    }
+
+    /// Load a key pair of whatever kind is stored under `key_id`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is missing or is not a stored key pair.
+    pub async fn load_any<S: KeyRetrieval>(store: &S, key_id: &dyn KeyId) -> Result<Self> {
+        let bytes = Zeroizing::new(store.retrieve(key_id).recv().await?);
+        Self::from_bytes(&bytes)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Some((public_size, secret_size)) = self.kind.pq_key_sizes() {
+            check_size(public_size, self.public_key.len())?;
+            return check_size(secret_size, self.secret_key.len());
+        }
+        let algorithm = self
+            .kind
+            .asymmetric_algorithm()
+            .ok_or_else(|| KeyError::Internal(format!("No key layout for {}", self.kind)))?;
+        AsymmetricPublicKey::from_bytes(algorithm, &self.public_key)?;
+        AsymmetricKey::from_secret_bytes(algorithm, &self.secret_key).map(|_| ())
+    }
+}
+
+impl fmt::Debug for StoredKeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoredKeyPair")
+            .field("kind", &self.kind)
+            .field("public_key_len", &self.public_key.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for StoredKeyPair {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.kind == other.kind
+            && self.public_key == other.public_key
+            && bool::from(self.secret_key.ct_eq(&other.secret_key))
+    }
+}
+
+impl Eq for StoredKeyPair {}
+
+fn check_size(expected: usize, actual: usize) -> Result<()> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(KeyError::InvalidKeySize { expected, actual })
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::{
     KeyId, KeyResult,
+    policy::KeyUsage,
     store_results::{DeleteResult, ExistsResult, ListResult, RetrieveResult, StoreResult},
 };
 use std::future::Future;
@@ -19,6 +20,15 @@ pub trait KeyStorage: Send + Sync {
 pub trait KeyRetrieval: KeyStorage {
     /// Retrieve a key by ID
     fn retrieve(&self, key_id: &dyn KeyId) -> RetrieveResult;
+
+    /// Retrieve a key by ID for a declared usage
+    ///
+    /// Stores that keep [`KeyPolicy`](crate::policy::KeyPolicy) records check `usage`
+    /// against them; the default ignores it.
+    fn retrieve_for(&self, key_id: &dyn KeyId, usage: &KeyUsage) -> RetrieveResult {
+        let _ = usage;
+        self.retrieve(key_id)
+    }
 }
 
 /// Trait for key stores that support importing key material
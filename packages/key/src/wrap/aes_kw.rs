@@ -0,0 +1,253 @@
+//! AES Key Wrap (RFC 3394) and AES Key Wrap with Padding (RFC 5649)
+//!
+//! Both modes run the same six-round Feistel-like wrapping function `W` over 64-bit
+//! semiblocks. RFC 3394 uses a fixed integrity check value and only accepts key data in
+//! multiples of 8 bytes; RFC 5649 carries the message length in an alternative initial
+//! value so that key data of any length can be wrapped.
+
+use crate::{KeyError, Result};
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit, generic_array::GenericArray};
+use aes::{Aes128, Aes192, Aes256};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+/// Default initial value from RFC 3394 section 2.2.3.1
+const DEFAULT_IV: [u8; 8] = [0xA6; 8];
+
+/// Constant half of the alternative initial value from RFC 5649 section 3
+const PADDED_IV_PREFIX: [u8; 4] = [0xA6, 0x59, 0x59, 0xA6];
+
+/// Semiblock size used by both wrapping modes
+const SEMIBLOCK: usize = 8;
+
+#[derive(Clone)]
+enum Kek {
+    Aes128(Aes128),
+    Aes192(Aes192),
+    Aes256(Aes256),
+}
+
+impl Kek {
+    fn encrypt(&self, block: &mut [u8; 16]) {
+        let block = GenericArray::from_mut_slice(block);
+        match self {
+            Self::Aes128(cipher) => cipher.encrypt_block(block),
+            Self::Aes192(cipher) => cipher.encrypt_block(block),
+            Self::Aes256(cipher) => cipher.encrypt_block(block),
+        }
+    }
+
+    fn decrypt(&self, block: &mut [u8; 16]) {
+        let block = GenericArray::from_mut_slice(block);
+        match self {
+            Self::Aes128(cipher) => cipher.decrypt_block(block),
+            Self::Aes192(cipher) => cipher.decrypt_block(block),
+            Self::Aes256(cipher) => cipher.decrypt_block(block),
+        }
+    }
+}
+
+/// AES key-encryption key for wrapping and unwrapping key material
+///
+/// Accepts 128, 192 and 256-bit KEKs. Unwrapped material is returned in a
+/// [`Zeroizing`] buffer so it is wiped once the caller drops it.
+#[derive(Clone)]
+pub struct KeyWrap {
+    kek: Kek,
+}
+
+impl KeyWrap {
+    /// Create a key wrapper from raw KEK bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKey` unless the KEK is 16, 24 or 32 bytes long.
+    pub fn new(kek: &[u8]) -> Result<Self> {
+        let invalid = |_| KeyError::InvalidKey("Invalid AES key-encryption key".into());
+        let kek = match kek.len() {
+            16 => Kek::Aes128(Aes128::new_from_slice(kek).map_err(invalid)?),
+            24 => Kek::Aes192(Aes192::new_from_slice(kek).map_err(invalid)?),
+            32 => Kek::Aes256(Aes256::new_from_slice(kek).map_err(invalid)?),
+            len => {
+                return Err(KeyError::InvalidKey(format!(
+                    "Key-encryption key must be 16, 24 or 32 bytes, got {len}"
+                )));
+            }
+        };
+        Ok(Self { kek })
+    }
+
+    /// Wrap key data with AES Key Wrap (RFC 3394)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the key data is shorter than 16 bytes or
+    /// not a multiple of 8 bytes. Use [`KeyWrap::wrap_with_padding`] for other lengths.
+    pub fn wrap(&self, key_data: &[u8]) -> Result<Vec<u8>> {
+        if key_data.len() < 2 * SEMIBLOCK || key_data.len() % SEMIBLOCK != 0 {
+            return Err(KeyError::InvalidKeyFormat(format!(
+                "AES key wrap requires a multiple of 8 bytes and at least 16, got {}",
+                key_data.len()
+            )));
+        }
+        Ok(self.wrap_semiblocks(DEFAULT_IV, key_data))
+    }
+
+    /// Unwrap key data produced by AES Key Wrap (RFC 3394)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::DecryptionFailed` if the input is malformed or the integrity
+    /// check fails (wrong KEK or tampered ciphertext).
+    pub fn unwrap(&self, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        if wrapped.len() < 3 * SEMIBLOCK || wrapped.len() % SEMIBLOCK != 0 {
+            return Err(KeyError::DecryptionFailed("Invalid wrapped key length".into()));
+        }
+
+        let (check, key_data) = self.unwrap_semiblocks(wrapped);
+        if bool::from(check.as_slice().ct_eq(&DEFAULT_IV)) {
+            Ok(key_data)
+        } else {
+            Err(integrity_failure())
+        }
+    }
+
+    /// Wrap key data of any non-zero length with AES Key Wrap with Padding (RFC 5649)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the key data is empty or longer than
+    /// `u32::MAX` bytes.
+    pub fn wrap_with_padding(&self, key_data: &[u8]) -> Result<Vec<u8>> {
+        let length = u32::try_from(key_data.len())
+            .ok()
+            .filter(|&length| length > 0)
+            .ok_or_else(|| {
+                KeyError::InvalidKeyFormat(format!(
+                    "AES key wrap with padding requires 1 to 2^32-1 bytes, got {}",
+                    key_data.len()
+                ))
+            })?;
+
+        let mut iv = [0u8; 8];
+        iv[..4].copy_from_slice(&PADDED_IV_PREFIX);
+        iv[4..].copy_from_slice(&length.to_be_bytes());
+
+        let padded_len = key_data.len().div_ceil(SEMIBLOCK) * SEMIBLOCK;
+        let mut padded = Zeroizing::new(vec![0u8; padded_len]);
+        padded[..key_data.len()].copy_from_slice(key_data);
+
+        if padded_len == SEMIBLOCK {
+            // A single semiblock is encrypted directly as one AES block
+            let mut block = Zeroizing::new([0u8; 16]);
+            block[..8].copy_from_slice(&iv);
+            block[8..].copy_from_slice(&padded);
+            self.kek.encrypt(&mut block);
+            return Ok(block.to_vec());
+        }
+
+        Ok(self.wrap_semiblocks(iv, &padded))
+    }
+
+    /// Unwrap key data produced by AES Key Wrap with Padding (RFC 5649)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::DecryptionFailed` if the input is malformed, the integrity
+    /// check fails, or the encoded length and padding are inconsistent.
+    pub fn unwrap_with_padding(&self, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        if wrapped.len() < 2 * SEMIBLOCK || wrapped.len() % SEMIBLOCK != 0 {
+            return Err(KeyError::DecryptionFailed("Invalid wrapped key length".into()));
+        }
+
+        let (check, mut padded) = if wrapped.len() == 2 * SEMIBLOCK {
+            let mut block = Zeroizing::new([0u8; 16]);
+            block.copy_from_slice(wrapped);
+            self.kek.decrypt(&mut block);
+
+            let mut check = [0u8; 8];
+            check.copy_from_slice(&block[..8]);
+            (check, Zeroizing::new(block[8..].to_vec()))
+        } else {
+            self.unwrap_semiblocks(wrapped)
+        };
+
+        if !bool::from(check[..4].ct_eq(&PADDED_IV_PREFIX)) {
+            return Err(integrity_failure());
+        }
+
+        let length = u32::from_be_bytes([check[4], check[5], check[6], check[7]]) as usize;
+        if length > padded.len() || length + SEMIBLOCK <= padded.len() {
+            return Err(integrity_failure());
+        }
+
+        let padding = &padded[length..];
+        if !bool::from(padding.ct_eq(&vec![0u8; padding.len()])) {
+            return Err(integrity_failure());
+        }
+
+        padded.truncate(length);
+        Ok(padded)
+    }
+
+    /// Wrapping function `W` from RFC 3394 section 2.2.1 (index based)
+    fn wrap_semiblocks(&self, iv: [u8; 8], plaintext: &[u8]) -> Vec<u8> {
+        let n = plaintext.len() / SEMIBLOCK;
+        let mut a = iv;
+        let mut r = Zeroizing::new(plaintext.to_vec());
+        let mut block = Zeroizing::new([0u8; 16]);
+
+        for j in 0..6 {
+            for (i, semiblock) in r.chunks_exact_mut(SEMIBLOCK).enumerate() {
+                block[..8].copy_from_slice(&a);
+                block[8..].copy_from_slice(semiblock);
+                self.kek.encrypt(&mut block);
+
+                let t = (n * j + i + 1) as u64;
+                a.copy_from_slice(&block[..8]);
+                xor_counter(&mut a, t);
+                semiblock.copy_from_slice(&block[8..]);
+            }
+        }
+
+        let mut wrapped = Vec::with_capacity(plaintext.len() + SEMIBLOCK);
+        wrapped.extend_from_slice(&a);
+        wrapped.extend_from_slice(&r);
+        wrapped
+    }
+
+    /// Unwrapping function `W^-1` from RFC 3394 section 2.2.2, returning the recovered
+    /// integrity check value alongside the plaintext semiblocks
+    fn unwrap_semiblocks(&self, wrapped: &[u8]) -> ([u8; 8], Zeroizing<Vec<u8>>) {
+        let n = wrapped.len() / SEMIBLOCK - 1;
+        let mut a = [0u8; 8];
+        a.copy_from_slice(&wrapped[..SEMIBLOCK]);
+        let mut r = Zeroizing::new(wrapped[SEMIBLOCK..].to_vec());
+        let mut block = Zeroizing::new([0u8; 16]);
+
+        for j in (0..6).rev() {
+            for (i, semiblock) in r.chunks_exact_mut(SEMIBLOCK).enumerate().rev() {
+                let t = (n * j + i + 1) as u64;
+                xor_counter(&mut a, t);
+                block[..8].copy_from_slice(&a);
+                block[8..].copy_from_slice(semiblock);
+                self.kek.decrypt(&mut block);
+
+                a.copy_from_slice(&block[..8]);
+                semiblock.copy_from_slice(&block[8..]);
+            }
+        }
+
+        (a, r)
+    }
+}
+
+fn xor_counter(a: &mut [u8; 8], t: u64) {
+    for (byte, counter) in a.iter_mut().zip(t.to_be_bytes()) {
+        *byte ^= counter;
+    }
+}
+
+fn integrity_failure() -> KeyError {
+    KeyError::DecryptionFailed("Key unwrap integrity check failed".into())
+}
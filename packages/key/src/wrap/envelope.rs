@@ -0,0 +1,232 @@
+//! Envelope encryption of data keys under versioned key-encryption keys
+//!
+//! A data-encryption key (DEK) is generated locally and wrapped with AES Key Wrap with
+//! Padding under a key-encryption key (KEK) held in any [`KeyRetrieval`] store. The
+//! wrapped DEK records the KEK id and version it was wrapped under, so unwrapping keeps
+//! working after the KEK is rotated as long as the old version remains in the store.
+
+use super::KeyWrap;
+use crate::{KeyError, Result, SimpleKeyId, traits::KeyRetrieval};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Serialization format version written by [`WrappedKey::to_bytes`]
+const WRAPPED_KEY_FORMAT: u8 = 1;
+
+/// A data key wrapped under a specific KEK version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrappedKey {
+    /// Namespace of the KEK in the key store
+    pub kek_id: String,
+    /// Version of the KEK the data key was wrapped under
+    pub kek_version: u32,
+    /// RFC 5649 wrapped data key
+    pub wrapped: Vec<u8>,
+}
+
+impl WrappedKey {
+    /// Encode as `format || id length (u16) || id || version (u32) || wrapped key`
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the KEK id is longer than 65535 bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let id_len = u16::try_from(self.kek_id.len())
+            .map_err(|_| KeyError::InvalidKeyFormat("KEK id is too long".into()))?;
+
+        let mut bytes = Vec::with_capacity(7 + self.kek_id.len() + self.wrapped.len());
+        bytes.push(WRAPPED_KEY_FORMAT);
+        bytes.extend_from_slice(&id_len.to_be_bytes());
+        bytes.extend_from_slice(self.kek_id.as_bytes());
+        bytes.extend_from_slice(&self.kek_version.to_be_bytes());
+        bytes.extend_from_slice(&self.wrapped);
+        Ok(bytes)
+    }
+
+    /// Decode bytes produced by [`WrappedKey::to_bytes`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `KeyError::InvalidKeyFormat` if the encoding is truncated or uses an
+    /// unknown format version.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let malformed = || KeyError::InvalidKeyFormat("Malformed wrapped key".into());
+
+        let (&format, rest) = bytes.split_first().ok_or_else(malformed)?;
+        if format != WRAPPED_KEY_FORMAT {
+            return Err(KeyError::InvalidKeyFormat(format!(
+                "Unsupported wrapped key format {format}"
+            )));
+        }
+
+        let (id_len, rest) = rest.split_first_chunk::<2>().ok_or_else(malformed)?;
+        let id_len = usize::from(u16::from_be_bytes(*id_len));
+        if rest.len() < id_len {
+            return Err(malformed());
+        }
+        let (kek_id, rest) = rest.split_at(id_len);
+        let kek_id = String::from_utf8(kek_id.to_vec()).map_err(|_| malformed())?;
+
+        let (kek_version, wrapped) = rest.split_first_chunk::<4>().ok_or_else(malformed)?;
+        if wrapped.is_empty() {
+            return Err(malformed());
+        }
+
+        Ok(Self {
+            kek_id,
+            kek_version: u32::from_be_bytes(*kek_version),
+            wrapped: wrapped.to_vec(),
+        })
+    }
+}
+
+/// A freshly generated data key together with its wrapped form
+pub struct DataKey {
+    key: Zeroizing<Vec<u8>>,
+    wrapped: WrappedKey,
+}
+
+impl DataKey {
+    /// Plaintext data key - use it, then drop it
+    #[must_use]
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Wrapped data key to persist alongside the ciphertext
+    #[must_use]
+    pub fn wrapped(&self) -> &WrappedKey {
+        &self.wrapped
+    }
+
+    /// Discard the plaintext key and keep only the wrapped form
+    #[must_use]
+    pub fn into_wrapped(self) -> WrappedKey {
+        self.wrapped
+    }
+}
+
+/// Envelope key management bound to a KEK namespace in a key store
+///
+/// KEKs are looked up as `{kek_id}:v{version}`, the same id scheme used by
+/// [`KeyRetriever`](crate::KeyRetriever). New data keys are wrapped under the current
+/// version; unwrapping always uses the version recorded in the [`WrappedKey`].
+pub struct EnvelopeKey<S> {
+    store: S,
+    kek_id: String,
+    kek_version: u32,
+}
+
+impl<S: KeyRetrieval> EnvelopeKey<S> {
+    /// Wrap data keys under `kek_id` at `kek_version`
+    pub fn new(store: S, kek_id: impl Into<String>, kek_version: u32) -> Self {
+        Self {
+            store,
+            kek_id: kek_id.into(),
+            kek_version,
+        }
+    }
+
+    /// Switch to a rotated KEK version for new data keys
+    #[must_use]
+    pub fn with_kek_version(mut self, kek_version: u32) -> Self {
+        self.kek_version = kek_version;
+        self
+    }
+
+    /// KEK namespace used for wrapping
+    #[must_use]
+    pub fn kek_id(&self) -> &str {
+        &self.kek_id
+    }
+
+    /// KEK version used for new data keys
+    #[must_use]
+    pub fn kek_version(&self) -> u32 {
+        self.kek_version
+    }
+
+    /// Generate a random data key of `size_bytes` and wrap it under the current KEK
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size_bytes` is zero or the KEK cannot be retrieved.
+    pub async fn generate_data_key(&self, size_bytes: usize) -> Result<DataKey> {
+        if size_bytes == 0 {
+            return Err(KeyError::InvalidKeySize {
+                expected: 32,
+                actual: 0,
+            });
+        }
+
+        let mut key = Zeroizing::new(vec![0u8; size_bytes]);
+        rand::rng().fill_bytes(&mut key);
+        let wrapped = self.wrap_data_key(&key).await?;
+
+        Ok(DataKey { key, wrapped })
+    }
+
+    /// Wrap an existing data key under the current KEK
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the KEK cannot be retrieved or is not a valid AES key.
+    pub async fn wrap_data_key(&self, data_key: &[u8]) -> Result<WrappedKey> {
+        let kek = self.kek(self.kek_version).await?;
+        Ok(WrappedKey {
+            kek_id: self.kek_id.clone(),
+            kek_version: self.kek_version,
+            wrapped: kek.wrap_with_padding(data_key)?,
+        })
+    }
+
+    /// Unwrap a data key using the KEK version it was wrapped under
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the wrapped key belongs to another KEK namespace, its KEK
+    /// version is no longer in the store, or the integrity check fails.
+    pub async fn unwrap_data_key(&self, wrapped: &WrappedKey) -> Result<Zeroizing<Vec<u8>>> {
+        if wrapped.kek_id != self.kek_id {
+            return Err(KeyError::InvalidKey(format!(
+                "Data key was wrapped under KEK '{}', not '{}'",
+                wrapped.kek_id, self.kek_id
+            )));
+        }
+
+        let kek = self.kek(wrapped.kek_version).await?;
+        kek.unwrap_with_padding(&wrapped.wrapped)
+    }
+
+    /// Re-wrap a data key under the current KEK version
+    ///
+    /// Used after a KEK rotation so the old version can eventually be retired. Keys
+    /// already wrapped under the current version are returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either KEK version cannot be retrieved or unwrapping fails.
+    pub async fn rewrap(&self, wrapped: &WrappedKey) -> Result<WrappedKey> {
+        if wrapped.kek_id == self.kek_id && wrapped.kek_version == self.kek_version {
+            return Ok(wrapped.clone());
+        }
+
+        let data_key = self.unwrap_data_key(wrapped).await?;
+        self.wrap_data_key(&data_key).await
+    }
+
+    async fn kek(&self, version: u32) -> Result<KeyWrap> {
+        let key_id = SimpleKeyId::new(format!("{}:v{version}", self.kek_id));
+        let material = Zeroizing::new(self.store.retrieve(&key_id).recv().await?);
+
+        if material.is_empty() {
+            return Err(KeyError::KeyNotFound {
+                id: self.kek_id.clone(),
+                version,
+            });
+        }
+
+        KeyWrap::new(&material)
+    }
+}
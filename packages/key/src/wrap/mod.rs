@@ -0,0 +1,10 @@
+//! Key wrapping and envelope encryption
+//!
+//! - [`KeyWrap`]: AES Key Wrap (RFC 3394) and AES Key Wrap with Padding (RFC 5649)
+//! - [`EnvelopeKey`]: data keys wrapped under versioned KEKs from a key store
+
+mod aes_kw;
+mod envelope;
+
+pub use aes_kw::KeyWrap;
+pub use envelope::{DataKey, EnvelopeKey, WrappedKey};
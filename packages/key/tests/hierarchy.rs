@@ -0,0 +1,90 @@
+//! Hierarchical key derivation tests
+
+use cryypt_key::KeyError;
+use cryypt_key::api::ActualKey;
+use cryypt_key::hierarchy::{DerivationAlgorithm, KeyHierarchy, KeyPath};
+
+const ROOT: [u8; 32] = [0x42; 32];
+
+#[test]
+fn test_derivation_is_deterministic_and_separated() {
+    for algorithm in [DerivationAlgorithm::HkdfSha512, DerivationAlgorithm::Blake3] {
+        let hierarchy = KeyHierarchy::from_bytes(&ROOT, algorithm).expect("hierarchy");
+        let again =
+            KeyHierarchy::new(&ActualKey::from_bytes(ROOT.to_vec()), algorithm).expect("hierarchy");
+
+        let path = "root/tenant:42/purpose:db-encryption/v3";
+        let key = hierarchy.derive(path).expect("derive");
+        assert_eq!(key.bytes().len(), 32);
+        assert_eq!(again.derive(path).expect("derive").bytes(), key.bytes());
+        // The leading `root` segment is optional
+        let unrooted = hierarchy.derive("tenant:42/purpose:db-encryption/v3");
+        assert_eq!(unrooted.expect("derive").bytes(), key.bytes());
+
+        let sibling = hierarchy.derive("tenant:42/purpose:db-encryption/v4");
+        assert_ne!(sibling.expect("derive").bytes(), key.bytes());
+        let other_tenant = hierarchy.derive("tenant:43/purpose:db-encryption/v3");
+        assert_ne!(other_tenant.expect("derive").bytes(), key.bytes());
+        // Segment boundaries are part of the derivation
+        let merged = hierarchy.derive("tenant:42purpose:db-encryption/v3");
+        assert_ne!(merged.expect("derive").bytes(), key.bytes());
+    }
+
+    let hkdf = KeyHierarchy::from_bytes(&ROOT, DerivationAlgorithm::HkdfSha512).expect("hkdf");
+    let blake3 = KeyHierarchy::from_bytes(&ROOT, DerivationAlgorithm::Blake3).expect("blake3");
+    assert_ne!(
+        hkdf.derive("tenant:1").expect("derive").bytes(),
+        blake3.derive("tenant:1").expect("derive").bytes()
+    );
+}
+
+#[test]
+fn test_subtree_matches_full_path_derivation() {
+    let hierarchy = KeyHierarchy::from_bytes(&ROOT, DerivationAlgorithm::Blake3)
+        .expect("hierarchy")
+        .with_key_len(64)
+        .expect("key length");
+    let full = hierarchy
+        .derive("tenant:7/purpose:signing/v1")
+        .expect("derive");
+    assert_eq!(full.bytes().len(), 64);
+
+    let tenant = hierarchy.subtree("tenant:7").expect("subtree");
+    assert_eq!(tenant.base(), &KeyPath::parse("tenant:7").expect("path"));
+    assert_eq!(tenant.base().to_string(), "root/tenant:7");
+    let delegated = tenant.derive("purpose:signing/v1").expect("derive");
+    assert_eq!(delegated.bytes(), full.bytes());
+
+    // Cached and freshly derived chain keys agree
+    hierarchy.clear_cache().expect("clear");
+    let rederived = hierarchy
+        .derive("tenant:7/purpose:signing/v1")
+        .expect("derive");
+    assert_eq!(rederived.bytes(), full.bytes());
+}
+
+#[test]
+fn test_rejects_weak_roots_and_malformed_paths() {
+    assert!(matches!(
+        KeyHierarchy::from_bytes(&[1u8; 16], DerivationAlgorithm::HkdfSha512),
+        Err(KeyError::InvalidKeySize {
+            expected: 32,
+            actual: 16
+        })
+    ));
+
+    let hierarchy =
+        KeyHierarchy::from_bytes(&ROOT, DerivationAlgorithm::HkdfSha512).expect("hierarchy");
+    for path in ["tenant:1//v1", "tenant:1/", "tenant:\n1"] {
+        assert!(
+            matches!(hierarchy.derive(path), Err(KeyError::InvalidKey(_))),
+            "{path:?} should be rejected"
+        );
+    }
+    assert!(matches!(
+        KeyHierarchy::from_bytes(&ROOT, DerivationAlgorithm::Blake3)
+            .expect("hierarchy")
+            .with_key_len(8),
+        Err(KeyError::InvalidKeySize { .. })
+    ));
+}
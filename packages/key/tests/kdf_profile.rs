@@ -0,0 +1,125 @@
+//! Passphrase KDF profile and legacy migration tests
+
+use cryypt_key::api::{KdfProfile, MasterKeyBuilder, MasterKeyProvider};
+use cryypt_key::traits::{KeyImport, KeyRetrieval};
+use cryypt_key::{FileKeyStore, SimpleKeyId};
+
+fn cheap_profile() -> KdfProfile {
+    KdfProfile::generate()
+        .with_costs(1024, 1, 1)
+        .expect("valid Argon2 parameters")
+}
+
+#[test]
+fn test_profiles_salt_equal_passphrases_differently() {
+    let first = cheap_profile();
+    let second = cheap_profile();
+
+    let key = |profile: &KdfProfile| {
+        MasterKeyBuilder::from_passphrase("correct horse")
+            .with_profile(profile.clone())
+            .resolve()
+            .expect("derive")
+    };
+    assert_eq!(key(&first), key(&first));
+    assert_ne!(key(&first), key(&second));
+
+    let restored = KdfProfile::from_json(&first.to_json().expect("encode")).expect("decode");
+    assert_eq!(restored, first);
+    assert_eq!(key(&restored), key(&first));
+}
+
+#[tokio::test]
+async fn test_resolve_with_store_creates_profile_once() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([0u8; 32]);
+    let passphrase = MasterKeyBuilder::from_passphrase("tr0ub4dor").with_profile(cheap_profile());
+
+    let first = passphrase
+        .resolve_with_store(&store, "app")
+        .await
+        .expect("first resolve");
+    assert_eq!(
+        first.legacy_key.as_deref(),
+        Some(&passphrase.resolve_legacy().expect("legacy"))
+    );
+
+    let second = MasterKeyBuilder::from_passphrase("tr0ub4dor")
+        .resolve_with_store(&store, "app")
+        .await
+        .expect("second resolve");
+    assert!(second.legacy_key.is_none());
+    assert_eq!(second.profile, first.profile);
+    assert_eq!(*second.master_key, *first.master_key);
+}
+
+#[tokio::test]
+async fn test_racing_first_resolves_agree_on_one_profile() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([0u8; 32]);
+
+    let resolve = |store: FileKeyStore| async move {
+        MasterKeyBuilder::from_passphrase("shared secret")
+            .with_profile(cheap_profile())
+            .resolve_with_store(&store, "app")
+            .await
+            .expect("resolve")
+    };
+    let resolved = futures::future::join_all((0..8).map(|_| resolve(store.clone()))).await;
+
+    // Every caller derives under the one stored profile; only its creator migrates
+    let stored = resolve(store).await.profile;
+    for result in &resolved {
+        assert_eq!(result.profile, stored);
+        assert_eq!(*result.master_key, *resolved[0].master_key);
+    }
+    let creators = resolved.iter().filter(|r| r.legacy_key.is_some()).count();
+    assert_eq!(creators, 1);
+}
+
+#[tokio::test]
+async fn test_file_store_migrates_legacy_key_files() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let passphrase =
+        MasterKeyBuilder::from_passphrase("legacy vault").with_profile(cheap_profile());
+    let key_id = SimpleKeyId::new("app:v1");
+
+    // A store written before profiles existed
+    let legacy_key = passphrase.resolve_legacy().expect("legacy");
+    let legacy_store = FileKeyStore::at(&dir).with_master_key(legacy_key);
+    assert!(
+        legacy_store
+            .store(&key_id, &[0x5Au8; 32])
+            .on_result(|result| result.is_ok())
+            .await
+    );
+
+    let store = FileKeyStore::at(&dir)
+        .with_passphrase(&passphrase)
+        .await
+        .expect("migrating open");
+    let material = store
+        .retrieve(&key_id)
+        .on_result(|result| result.unwrap_or_default())
+        .await;
+    assert_eq!(material, vec![0x5Au8; 32]);
+    assert!(dir.path().join("master.kdf.json").exists());
+
+    // The file is now wrapped under the profiled key only
+    let stale = legacy_store
+        .retrieve(&key_id)
+        .on_result(|result| result.is_err())
+        .await;
+    assert!(stale);
+
+    // Reopening uses the committed profile without migrating again
+    let reopened = FileKeyStore::at(&dir)
+        .with_passphrase(&MasterKeyBuilder::from_passphrase("legacy vault"))
+        .await
+        .expect("reopen");
+    let material = reopened
+        .retrieve(&key_id)
+        .on_result(|result| result.unwrap_or_default())
+        .await;
+    assert_eq!(material, vec![0x5Au8; 32]);
+}
@@ -0,0 +1,220 @@
+//! Key usage policy enforcement tests
+
+use chrono::{Duration, Utc};
+use cryypt_key::policy::{KeyOperation, KeyPolicy, KeyUsage, PolicyViolation};
+use cryypt_key::traits::{KeyImport, KeyRetrieval};
+use cryypt_key::{FileKeyStore, Key, KeyError, SimpleKeyId};
+
+fn violation(result: cryypt_key::Result<Vec<u8>>) -> PolicyViolation {
+    match result {
+        Err(KeyError::PolicyViolation { violation, .. }) => violation,
+        other => panic!("expected policy violation, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_signing_key_rejects_encryption() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([3u8; 32]);
+    let key_id = SimpleKeyId::new("signer:v1");
+    assert!(
+        store
+            .store(&key_id, &[9u8; 32])
+            .on_result(|r| r.is_ok())
+            .await
+    );
+    store
+        .set_policy(
+            &key_id,
+            KeyPolicy::new().allow_operations([KeyOperation::Sign]),
+        )
+        .await
+        .expect("set policy");
+
+    let encrypt = store.retrieve_for(&key_id, &KeyUsage::new(KeyOperation::Encrypt));
+    assert_eq!(
+        violation(encrypt.on_result(|r| r).await),
+        PolicyViolation::OperationNotAllowed(KeyOperation::Encrypt)
+    );
+    assert_eq!(
+        violation(store.retrieve(&key_id).on_result(|r| r).await),
+        PolicyViolation::UsageNotDeclared("operation")
+    );
+
+    let sign = store.retrieve_for(&key_id, &KeyUsage::new(KeyOperation::Sign));
+    assert_eq!(
+        sign.on_result(|r| r).await.expect("sign usage"),
+        vec![9u8; 32]
+    );
+}
+
+#[tokio::test]
+async fn test_builder_policy_limits_uses_and_expiry() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([4u8; 32]);
+    let usage = KeyUsage::new(KeyOperation::Decrypt);
+
+    let key = Key::size(256u32)
+        .with_store(store.clone())
+        .with_namespace("archive")
+        .version(1)
+        .with_policy(KeyPolicy::new().max_uses(2))
+        .generate()
+        .await
+        .expect("generate");
+
+    for _ in 0..2 {
+        let retrieved = Key::size(256u32)
+            .with_store(store.clone())
+            .with_namespace("archive")
+            .version(1)
+            .for_usage(usage.clone())
+            .retrieve()
+            .await
+            .expect("within budget");
+        assert_eq!(retrieved, key);
+    }
+    let exhausted = Key::size(256u32)
+        .with_store(store.clone())
+        .with_namespace("archive")
+        .version(1)
+        .retrieve()
+        .await;
+    assert_eq!(violation(exhausted), PolicyViolation::UsesExhausted(2));
+
+    let key_id = SimpleKeyId::new("archive:v1");
+    let expired_at = Utc::now() - Duration::minutes(1);
+    store
+        .set_policy(&key_id, KeyPolicy::new().expires_at(expired_at))
+        .await
+        .expect("set policy");
+    assert_eq!(
+        violation(store.retrieve(&key_id).on_result(|r| r).await),
+        PolicyViolation::Expired(expired_at)
+    );
+}
+
+#[tokio::test]
+async fn test_caller_and_algorithm_restrictions() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([5u8; 32]);
+    let billing = SimpleKeyId::new("billing:v1");
+    let reports = SimpleKeyId::new("reports:v1");
+    assert!(
+        store
+            .store(&billing, &[1u8; 32])
+            .on_result(|r| r.is_ok())
+            .await
+    );
+    assert!(
+        store
+            .store(&reports, &[2u8; 32])
+            .on_result(|r| r.is_ok())
+            .await
+    );
+    store
+        .set_policy(
+            &billing,
+            KeyPolicy::new()
+                .allow_algorithms(["AES-256-GCM"])
+                .allow_callers(["billing"]),
+        )
+        .await
+        .expect("set policy");
+
+    let usage = KeyUsage::new(KeyOperation::Encrypt).with_algorithm("aes-256-gcm");
+    let wrong_caller = store.retrieve_for(&billing, &usage.clone().with_caller("reports"));
+    assert_eq!(
+        violation(wrong_caller.on_result(|r| r).await),
+        PolicyViolation::CallerNotAllowed("reports".into())
+    );
+    let wrong_algorithm = KeyUsage::new(KeyOperation::Encrypt)
+        .with_algorithm("chacha20-poly1305")
+        .with_caller("billing");
+    assert_eq!(
+        violation(
+            store
+                .retrieve_for(&billing, &wrong_algorithm)
+                .on_result(|r| r)
+                .await
+        ),
+        PolicyViolation::AlgorithmNotAllowed("chacha20-poly1305".into())
+    );
+    let allowed = store.retrieve_for(&billing, &usage.with_caller("billing"));
+    assert_eq!(
+        allowed.on_result(|r| r).await.expect("allowed"),
+        vec![1u8; 32]
+    );
+
+    // A policy record copied onto another key is rejected rather than trusted
+    std::fs::copy(
+        dir.path().join("billing_1.policy"),
+        dir.path().join("reports_1.policy"),
+    )
+    .expect("copy");
+    assert!(matches!(
+        store.retrieve(&reports).on_result(|r| r).await,
+        Err(KeyError::InvalidKeyFormat(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_sealed_policy_fails_closed() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([6u8; 32]);
+    let key_id = SimpleKeyId::new("ledger:v1");
+    let sign = KeyUsage::new(KeyOperation::Sign);
+    let policy = KeyPolicy::new()
+        .allow_operations([KeyOperation::Sign])
+        .max_uses(5);
+    assert!(
+        store
+            .store(&key_id, &[7u8; 32])
+            .on_result(|r| r.is_ok())
+            .await
+    );
+    store
+        .set_policy(&key_id, policy.clone())
+        .await
+        .expect("set policy");
+
+    // Replacing the material keeps the policy and its use count
+    assert!(
+        store
+            .store(&key_id, &[8u8; 32])
+            .on_result(|r| r.is_ok())
+            .await
+    );
+    assert_eq!(
+        store.policy(&key_id).await.expect("policy"),
+        Some(policy.clone())
+    );
+    assert_eq!(
+        violation(store.retrieve(&key_id).on_result(|r| r).await),
+        PolicyViolation::UsageNotDeclared("operation")
+    );
+    assert_eq!(
+        store
+            .retrieve_for(&key_id, &sign)
+            .on_result(|r| r)
+            .await
+            .expect("sign usage"),
+        vec![8u8; 32]
+    );
+
+    // Writes are staged in temporary files that never outlive the rename
+    let leftovers: Vec<_> = std::fs::read_dir(&dir)
+        .expect("read dir")
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty(), "{leftovers:?}");
+
+    // Deleting the record does not lift the policy sealed into the key file
+    std::fs::remove_file(dir.path().join("ledger_1.policy")).expect("remove record");
+    assert!(matches!(
+        store.retrieve_for(&key_id, &sign).on_result(|r| r).await,
+        Err(KeyError::InvalidKeyFormat(_))
+    ));
+    assert_eq!(store.policy(&key_id).await.expect("policy"), Some(policy));
+}
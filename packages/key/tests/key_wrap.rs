@@ -0,0 +1,108 @@
+//! AES key wrap (RFC 3394 / RFC 5649) and envelope key tests
+
+use cryypt_key::traits::KeyImport;
+use cryypt_key::{EnvelopeKey, FileKeyStore, KeyWrap, SimpleKeyId, WrappedKey};
+use hex_literal::hex;
+
+#[test]
+fn test_rfc3394_and_rfc5649_vectors() {
+    // RFC 3394 section 4.1: 128-bit key data with a 128-bit KEK
+    let kek = KeyWrap::new(&hex!("000102030405060708090A0B0C0D0E0F")).expect("KEK");
+    let wrapped = kek
+        .wrap(&hex!("00112233445566778899AABBCCDDEEFF"))
+        .expect("wrap");
+    assert_eq!(wrapped, hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5"));
+    assert_eq!(
+        kek.unwrap(&wrapped).expect("unwrap").as_slice(),
+        hex!("00112233445566778899AABBCCDDEEFF")
+    );
+
+    // RFC 3394 section 4.6: 256-bit key data with a 256-bit KEK
+    let kek = KeyWrap::new(&hex!(
+        "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F"
+    ))
+    .expect("KEK");
+    let key_data = hex!("00112233445566778899AABBCCDDEEFF000102030405060708090A0B0C0D0E0F");
+    assert_eq!(
+        kek.wrap(&key_data).expect("wrap"),
+        hex!("28C9F404C4B810F4CBCCB35CFB87F8263F5786E2D80ED326CBC7F0E71A99F43BFB988B9B7A02DD21")
+    );
+
+    // RFC 5649 section 6: 20-byte and 7-byte key data with a 192-bit KEK
+    let kek = KeyWrap::new(&hex!("5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8")).expect("KEK");
+    let wrapped = kek
+        .wrap_with_padding(&hex!("c37b7e6492584340bed12207808941155068f738"))
+        .expect("wrap");
+    assert_eq!(
+        wrapped,
+        hex!("138bdeaa9b8fa7fc61f97742e72248ee5ae6ae5360d1ae6a5f54f373fa543b6a")
+    );
+    assert_eq!(
+        kek.unwrap_with_padding(&wrapped).expect("unwrap").as_slice(),
+        hex!("c37b7e6492584340bed12207808941155068f738")
+    );
+
+    let wrapped = kek.wrap_with_padding(&hex!("466f7250617369")).expect("wrap");
+    assert_eq!(wrapped, hex!("afbeb0f07dfbf5419200f2ccb50bb24f"));
+    assert_eq!(
+        kek.unwrap_with_padding(&wrapped).expect("unwrap").as_slice(),
+        hex!("466f7250617369")
+    );
+}
+
+#[test]
+fn test_unwrap_rejects_tampering_and_wrong_kek() {
+    let kek = KeyWrap::new(&[0x42u8; 32]).expect("KEK");
+    let other = KeyWrap::new(&[0x24u8; 32]).expect("KEK");
+
+    let mut wrapped = kek.wrap(&[0x07u8; 32]).expect("wrap");
+    assert!(other.unwrap(&wrapped).is_err());
+    wrapped[10] ^= 0x01;
+    assert!(kek.unwrap(&wrapped).is_err());
+
+    let padded = kek.wrap_with_padding(&[0x07u8; 13]).expect("wrap");
+    assert!(other.unwrap_with_padding(&padded).is_err());
+    // Padded and unpadded outputs are not interchangeable
+    assert!(kek.unwrap(&padded).is_err());
+
+    assert!(KeyWrap::new(&[0u8; 20]).is_err());
+    assert!(kek.wrap(&[0u8; 12]).is_err());
+    assert!(kek.wrap_with_padding(&[]).is_err());
+}
+
+#[tokio::test]
+async fn test_envelope_key_survives_kek_rotation() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([0x11u8; 32]);
+
+    assert!(
+        store
+            .store(&SimpleKeyId::new("orders-kek:v1"), &[0xA1u8; 32])
+            .on_result(|result| result.is_ok())
+            .await
+    );
+
+    let envelope = EnvelopeKey::new(store.clone(), "orders-kek", 1);
+    let data_key = envelope.generate_data_key(32).await.expect("generate DEK");
+    let stored = WrappedKey::from_bytes(&data_key.wrapped().to_bytes().expect("encode"))
+        .expect("decode");
+    assert_eq!(stored.kek_version, 1);
+
+    // Rotate the KEK: new data keys use v2, v1 envelopes still unwrap
+    assert!(
+        store
+            .store(&SimpleKeyId::new("orders-kek:v2"), &[0xB2u8; 32])
+            .on_result(|result| result.is_ok())
+            .await
+    );
+    let envelope = envelope.with_kek_version(2);
+
+    let unwrapped = envelope.unwrap_data_key(&stored).await.expect("unwrap v1");
+    assert_eq!(unwrapped.as_slice(), data_key.key());
+
+    let rewrapped = envelope.rewrap(&stored).await.expect("rewrap");
+    assert_eq!(rewrapped.kek_version, 2);
+    assert_ne!(rewrapped.wrapped, stored.wrapped);
+    let unwrapped = envelope.unwrap_data_key(&rewrapped).await.expect("unwrap v2");
+    assert_eq!(unwrapped.as_slice(), data_key.key());
+}
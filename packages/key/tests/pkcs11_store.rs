@@ -0,0 +1,103 @@
+//! PKCS#11 store tests against SoftHSMv2
+//!
+//! Requires an initialized token, for example:
+//!
+//! ```text
+//! softhsm2-util --init-token --free --label cryypt-test --pin 1234 --so-pin 5678
+//! ```
+//!
+//! The module path, token label and PIN default to the values above and can be overridden
+//! with `CRYYPT_PKCS11_MODULE`, `CRYYPT_PKCS11_TOKEN` and `CRYYPT_PKCS11_PIN`. The tests are
+//! ignored by default and fail, rather than pass, without a token; `just test-pkcs11` sets
+//! up a throwaway SoftHSMv2 token and runs them.
+
+use cryypt_key::store::{Pkcs11Config, Pkcs11Store};
+use cryypt_key::traits::{KeyImport, KeyRetrieval, KeyStorage};
+use cryypt_key::{EcCurve, Key, SimpleKeyId};
+
+fn connect() -> Pkcs11Store {
+    let module = std::env::var("CRYYPT_PKCS11_MODULE")
+        .unwrap_or_else(|_| "/usr/lib/softhsm/libsofthsm2.so".into());
+    assert!(
+        std::path::Path::new(&module).exists(),
+        "PKCS#11 module {module} is not installed"
+    );
+    let token = std::env::var("CRYYPT_PKCS11_TOKEN").unwrap_or_else(|_| "cryypt-test".into());
+    let pin = std::env::var("CRYYPT_PKCS11_PIN").unwrap_or_else(|_| "1234".into());
+    Pkcs11Store::connect(Pkcs11Config::new(module, token, pin)).expect("connect")
+}
+
+fn unique(label: &str) -> String {
+    format!("{label}-{}-{}", std::process::id(), rand::random::<u32>())
+}
+
+#[tokio::test]
+#[ignore = "needs a SoftHSMv2 token; run with `just test-pkcs11`"]
+async fn test_sealed_key_material_round_trip() {
+    let store = connect();
+    let key_id = SimpleKeyId::new(unique("sealed"));
+
+    assert!(store.store(&key_id, &[0x42; 32]).on_result(|r| r.is_ok()).await);
+    assert!(store.exists(&key_id).on_result(|r| r.unwrap_or(false)).await);
+    let material = store.retrieve(&key_id).on_result(|r| r.unwrap_or_default()).await;
+    assert_eq!(material, vec![0x42; 32]);
+
+    assert!(store.delete(&key_id).on_result(|r| r.is_ok()).await);
+    assert!(!store.exists(&key_id).on_result(|r| r.unwrap_or(true)).await);
+
+    // Plugs into the key builder like the other stores
+    let namespace = unique("builder");
+    let generated = Key::size(256u32)
+        .with_store(store.clone())
+        .with_namespace(namespace.clone())
+        .version(1)
+        .generate()
+        .await
+        .expect("generate");
+    let retrieved = Key::size(256u32)
+        .with_store(store)
+        .with_namespace(namespace)
+        .version(1)
+        .retrieve()
+        .await
+        .expect("retrieve");
+    assert_eq!(generated, retrieved);
+}
+
+#[tokio::test]
+#[ignore = "needs a SoftHSMv2 token; run with `just test-pkcs11`"]
+async fn test_native_aes_encrypt_and_wrap() {
+    let store = connect();
+    let (kek, data_key, imported) = (unique("kek"), unique("dek"), unique("imported"));
+
+    store.generate_aes_key(&kek, 256).await.expect("kek");
+    store.generate_wrappable_aes_key(&data_key, 256).await.expect("dek");
+
+    let ciphertext = store.encrypt(&data_key, b"secret", b"aad").await.expect("encrypt");
+    assert!(store.decrypt(&data_key, &ciphertext, b"other").await.is_err());
+
+    let wrapped = store.wrap_key(&kek, &data_key).await.expect("wrap");
+    store.unwrap_key(&kek, &wrapped, &imported).await.expect("unwrap");
+    let plaintext = store.decrypt(&imported, &ciphertext, b"aad").await.expect("decrypt");
+    assert_eq!(plaintext.as_slice(), b"secret");
+
+    // Non-extractable keys cannot be wrapped out of the token
+    assert!(store.wrap_key(&kek, &imported).await.is_err());
+}
+
+#[tokio::test]
+#[ignore = "needs a SoftHSMv2 token; run with `just test-pkcs11`"]
+async fn test_ec_signatures_verify_with_exported_public_key() {
+    use p256::ecdsa::{Signature, VerifyingKey, signature::Verifier};
+
+    let store = connect();
+    let label = unique("signer");
+
+    let public = store.generate_ec_key(&label, EcCurve::P256).await.expect("keypair");
+    assert_eq!(store.ec_public_key(&label).await.expect("public"), public);
+
+    let signature = store.sign(&label, b"message").await.expect("sign");
+    let verifying = VerifyingKey::from_sec1_bytes(&public.to_bytes()).expect("verifying key");
+    let signature = Signature::from_slice(&signature).expect("r || s");
+    assert!(verifying.verify(b"message", &signature).is_ok());
+}
@@ -0,0 +1,75 @@
+//! Key rotation policy and lifecycle tests
+
+use chrono::Duration;
+use cryypt_key::FileKeyStore;
+use cryypt_key::rotation::{KeyRotation, KeyState, RotationPolicy};
+
+#[tokio::test]
+async fn test_rotate_retires_previous_version() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([7u8; 32]);
+    let rotation = KeyRotation::new(store.clone(), "orders")
+        .open()
+        .await
+        .expect("open");
+
+    let first = rotation.current().await.expect("current");
+    assert_eq!(first.version, 1);
+
+    assert_eq!(rotation.rotate().await.expect("rotate"), 2);
+    let second = rotation.current().await.expect("current");
+    assert_eq!(second.version, 2);
+    assert_ne!(second.key.bytes(), first.key.bytes());
+
+    let old = rotation.for_decrypt(1).await.expect("decrypt-only version");
+    assert_eq!(old.key.bytes(), first.key.bytes());
+
+    // Metadata survives reopening the namespace
+    let reopened = KeyRotation::new(store, "orders").open().await.expect("reopen");
+    let states: Vec<_> = reopened.versions().await.iter().map(|v| v.state).collect();
+    assert_eq!(states, vec![KeyState::DecryptOnly, KeyState::Active]);
+}
+
+#[tokio::test]
+async fn test_max_uses_rotates_on_current() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([7u8; 32]);
+    let rotation = KeyRotation::new(store, "tokens")
+        .with_policy(RotationPolicy::new().max_uses(2))
+        .open()
+        .await
+        .expect("open");
+
+    let mut versions = Vec::new();
+    for _ in 0..3 {
+        versions.push(rotation.current().await.expect("current").version);
+    }
+    assert_eq!(versions, vec![1, 1, 2]);
+}
+
+#[tokio::test]
+async fn test_enforce_disables_and_destroys_retired_versions() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([7u8; 32]);
+    let rotation = KeyRotation::new(store, "sessions")
+        .with_policy(
+            RotationPolicy::new()
+                .decrypt_window(Duration::zero())
+                .destroy_after(Duration::zero()),
+        )
+        .open()
+        .await
+        .expect("open");
+    rotation.rotate().await.expect("rotate");
+
+    let report = rotation.enforce().await.expect("first pass");
+    assert_eq!(report.rotated_to, None);
+    assert_eq!(report.disabled, vec![1]);
+    assert!(rotation.for_decrypt(1).await.is_err());
+
+    let report = rotation.enforce().await.expect("second pass");
+    assert_eq!(report.destroyed, vec![1]);
+    let revived = rotation.set_state(1, KeyState::DecryptOnly).await;
+    assert!(revived.is_err(), "destroyed versions cannot be revived");
+    assert_eq!(rotation.current().await.expect("active").version, 2);
+}
@@ -0,0 +1,84 @@
+//! Shamir secret sharing tests
+
+use cryypt_key::ActualKey;
+use cryypt_key::api::{KdfProfile, MasterKeyBuilder, MasterKeyProvider};
+use cryypt_key::sharing::{SecretSharing, Share, SharesMasterKey, combine};
+
+#[test]
+fn test_any_threshold_subset_recovers_secret() {
+    let key = ActualKey::from_bytes((0u8..48).collect());
+    let shares = SecretSharing::new(3, 5)
+        .expect("parameters")
+        .split_key(&key)
+        .expect("split");
+    assert_eq!(shares.len(), 5);
+
+    for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1], [1, 2, 3]] {
+        let chosen: Vec<Share> = subset.iter().map(|&i| shares[i].clone()).collect();
+        assert_eq!(combine(&chosen).expect("combine").as_slice(), key.bytes());
+    }
+
+    // Below the threshold nothing is recovered
+    assert!(combine(&shares[..2]).is_err());
+    // Repeating a share does not count twice
+    assert!(combine(&[shares[0].clone(), shares[0].clone(), shares[1].clone()]).is_err());
+    // Mixing shares from a different split is detected
+    let other = SecretSharing::new(3, 5)
+        .expect("parameters")
+        .split_key(&key)
+        .expect("split");
+    assert!(combine(&[shares[0].clone(), shares[1].clone(), other[2].clone()]).is_err());
+}
+
+#[test]
+fn test_share_encodings_round_trip_and_detect_corruption() {
+    let shares = SecretSharing::new(2, 3)
+        .expect("parameters")
+        .split(b"correct horse battery staple")
+        .expect("split");
+    let share = &shares[1];
+
+    assert_eq!(&Share::from_hex(&share.to_hex()).expect("hex"), share);
+    assert_eq!(&Share::from_base64(&share.to_base64()).expect("base64"), share);
+
+    let mnemonic = share.to_mnemonic();
+    assert!(mnemonic.split('-').all(|group| group.len() <= 4));
+    assert_eq!(&Share::from_mnemonic(&mnemonic).expect("mnemonic"), share);
+    assert_eq!(
+        &Share::from_mnemonic(&mnemonic.to_lowercase().replace('-', " ")).expect("lenient"),
+        share
+    );
+
+    // A single transcription error fails the per-share checksum
+    let mut bytes = share.to_bytes();
+    bytes[7] ^= 0x10;
+    assert!(Share::from_bytes(&bytes).is_err());
+    assert!(Share::from_hex("00").is_err());
+}
+
+#[test]
+fn test_shares_master_key_rebuilds_master_key() {
+    let passphrase = MasterKeyBuilder::from_passphrase("vault recovery drill")
+        .with_profile(KdfProfile::generate());
+    let master_key = passphrase.resolve().expect("master key");
+
+    let shares = SecretSharing::new(2, 3)
+        .expect("parameters")
+        .split_master_key(&passphrase)
+        .expect("split");
+    let phrases: Vec<_> = shares.iter().map(Share::to_mnemonic).collect();
+
+    let partial = SharesMasterKey::from_mnemonics(&[phrases[2].as_str()]).expect("decode");
+    assert!(!partial.is_complete());
+    assert!(partial.resolve().is_err());
+
+    let rebuilt = partial.with_share(shares[0].clone());
+    assert!(rebuilt.is_complete());
+    assert_eq!(rebuilt.resolve().expect("resolve"), master_key);
+    assert_eq!(
+        MasterKeyBuilder::from_shares(shares[1..].to_vec())
+            .resolve()
+            .expect("resolve"),
+        master_key
+    );
+}
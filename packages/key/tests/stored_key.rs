@@ -0,0 +1,84 @@
+//! Typed key pair storage tests
+
+use cryypt_key::{
+    AsymmetricAlgorithm, AsymmetricKey, FileKeyStore, KeyError, SimpleKeyId, StoredKeyKind,
+    StoredKeyPair,
+};
+
+#[test]
+fn test_encoding_round_trip_and_sizes() {
+    let pair = StoredKeyPair::new(StoredKeyKind::MlKem768, vec![1u8; 1184], vec![2u8; 2400])
+        .expect("ML-KEM-768 pair");
+    let bytes = pair.to_bytes();
+    assert_eq!(&bytes[..4], b"CKP1");
+    assert_eq!(StoredKeyPair::from_bytes(&bytes).expect("decode"), pair);
+
+    assert!(matches!(
+        StoredKeyPair::new(StoredKeyKind::MlDsa65, vec![0u8; 1952], vec![0u8; 4000]),
+        Err(KeyError::InvalidKeySize {
+            expected: 4032,
+            actual: 4000
+        })
+    ));
+    assert!(StoredKeyPair::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(StoredKeyPair::from_bytes(b"CKP1\xff\0\0\0\0").is_err());
+
+    let debug = format!("{pair:?}");
+    assert!(debug.contains("MlKem768") && !debug.contains("2, 2"));
+}
+
+#[tokio::test]
+async fn test_typed_load_checks_kind() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let store = FileKeyStore::at(&dir).with_master_key([8u8; 32]);
+    let key_id = SimpleKeyId::new("signing:v1");
+
+    let pair = StoredKeyPair::new(StoredKeyKind::MlDsa44, vec![3u8; 1312], vec![4u8; 2560])
+        .expect("ML-DSA-44 pair");
+    pair.save(&store, &key_id).await.expect("save");
+
+    let loaded = StoredKeyPair::load(&store, &key_id, StoredKeyKind::MlDsa44)
+        .await
+        .expect("load");
+    assert_eq!(loaded, pair);
+    assert!(matches!(
+        StoredKeyPair::load(&store, &key_id, StoredKeyKind::MlKem512).await,
+        Err(KeyError::InvalidKey(_))
+    ));
+    assert_eq!(
+        StoredKeyPair::load_any(&store, &key_id)
+            .await
+            .expect("load any")
+            .kind(),
+        StoredKeyKind::MlDsa44
+    );
+}
+
+#[test]
+fn test_classical_keys_share_the_format() {
+    let mut keys: Vec<AsymmetricKey> = [
+        AsymmetricAlgorithm::Ed25519,
+        AsymmetricAlgorithm::X25519,
+        AsymmetricAlgorithm::P256,
+        AsymmetricAlgorithm::P384,
+    ]
+    .into_iter()
+    .map(|algorithm| AsymmetricKey::generate(algorithm).expect("generate"))
+    .collect();
+    keys.push(AsymmetricKey::from_pkcs8_pem(include_str!("vectors/rsa2048.pem")).expect("RSA"));
+
+    for key in keys {
+        let pair = StoredKeyPair::from_asymmetric(&key);
+        assert_eq!(pair.kind(), StoredKeyKind::from(key.algorithm()));
+        assert!(!pair.kind().is_post_quantum());
+
+        let decoded = StoredKeyPair::from_bytes(&pair.to_bytes()).expect("decode");
+        let restored = decoded.to_asymmetric().expect("classical key");
+        assert_eq!(restored.secret_bytes(), key.secret_bytes());
+        assert_eq!(restored.public_key(), key.public_key());
+    }
+
+    let pq = StoredKeyPair::new(StoredKeyKind::MlKem512, vec![0u8; 800], vec![0u8; 1632])
+        .expect("ML-KEM-512 pair");
+    assert!(pq.to_asymmetric().is_err());
+}
@@ -0,0 +1,193 @@
+//! Benchmarks for batch signing and verification
+//!
+//! Compares `SignatureBatch` against awaiting one builder call per item, for the same
+//! batch of messages under ML-DSA, FALCON and SPHINCS+.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use cryypt_pqcrypto::api::{
+    MessageBuilder, MlDsaBuilder, SignBuilder, SignatureDataBuilder, SignatureKeyPairBuilder,
+    SphincsBuilder, VerifyBuilder,
+};
+use cryypt_pqcrypto::{Backend, SignatureAlgorithm, SignatureBuilder, SigningOptions};
+use tokio::runtime::Runtime;
+
+/// Messages per batch
+const BATCH_SIZE: usize = 64;
+
+type Item = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+fn messages() -> Vec<Vec<u8>> {
+    (0..BATCH_SIZE)
+        .map(|i| format!("audit record {i:04}").into_bytes())
+        .collect()
+}
+
+/// Sign `messages` and pair each with its public key and signature
+fn signed_items(algorithm: SignatureAlgorithm, messages: &[Vec<u8>]) -> Vec<Item> {
+    let (public_key, secret_key) = Backend::PqClean
+        .signature_keypair(algorithm)
+        .expect("keypair should generate");
+    messages
+        .iter()
+        .map(|message| {
+            let signature = Backend::PqClean
+                .sign(algorithm, &secret_key, message, &SigningOptions::new())
+                .expect("signing should succeed");
+            (public_key.clone(), message.clone(), signature)
+        })
+        .collect()
+}
+
+/// Verify one item through the algorithm's builder, as callers without batching do
+async fn verify_one(
+    algorithm: SignatureAlgorithm,
+    (public_key, message, signature): &Item,
+) -> bool {
+    let result = match algorithm {
+        SignatureAlgorithm::Falcon512 => {
+            SignatureBuilder::falcon_512()
+                .with_public_key(public_key.clone())
+                .expect("public key")
+                .with_message(message.clone())
+                .with_signature(signature.clone())
+                .verify()
+                .await
+        }
+        SignatureAlgorithm::SphincsShaSha256_128fSimple => {
+            SphincsBuilder::new(algorithm)
+                .expect("builder")
+                .with_public_key(public_key.clone())
+                .expect("public key")
+                .with_message(message.clone())
+                .with_signature(signature.clone())
+                .verify()
+                .await
+        }
+        _ => {
+            MlDsaBuilder::new(algorithm)
+                .expect("builder")
+                .with_public_key(public_key.clone())
+                .expect("public key")
+                .with_message(message.clone())
+                .with_signature(signature.clone())
+                .verify()
+                .await
+        }
+    };
+    result.expect("verification should run").is_valid()
+}
+
+/// Sign one message through the algorithm's builder
+async fn sign_one(algorithm: SignatureAlgorithm, secret_key: &[u8], message: &[u8]) -> Vec<u8> {
+    let result = match algorithm {
+        SignatureAlgorithm::Falcon512 => {
+            SignatureBuilder::falcon_512()
+                .with_secret_key(secret_key.to_vec())
+                .expect("secret key")
+                .with_message(message.to_vec())
+                .sign()
+                .await
+        }
+        _ => {
+            MlDsaBuilder::new(algorithm)
+                .expect("builder")
+                .with_secret_key(secret_key.to_vec())
+                .expect("secret key")
+                .with_message(message.to_vec())
+                .sign()
+                .await
+        }
+    };
+    result.expect("signing should succeed").signature_vec()
+}
+
+/// Benchmark verifying a batch sequentially and with `verify_batch`
+fn benchmark_verify_batch(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("verify_batch");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.sample_size(20);
+
+    let messages = messages();
+    for algorithm in [
+        SignatureAlgorithm::MlDsa65,
+        SignatureAlgorithm::Falcon512,
+        SignatureAlgorithm::SphincsShaSha256_128fSimple,
+    ] {
+        let items = signed_items(algorithm, &messages);
+
+        group.bench_with_input(
+            BenchmarkId::new("sequential", algorithm),
+            &items,
+            |b, items| {
+                b.iter(|| {
+                    rt.block_on(async {
+                        for item in items {
+                            std::hint::black_box(verify_one(algorithm, item).await);
+                        }
+                    });
+                });
+            },
+        );
+
+        let batch = SignatureBuilder::batch(algorithm);
+        group.bench_with_input(BenchmarkId::new("batch", algorithm), &items, |b, items| {
+            b.iter(|| {
+                rt.block_on(async {
+                    let results = batch.verify_batch(items.clone()).await;
+                    std::hint::black_box(results);
+                });
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Benchmark signing a batch sequentially and with `sign_batch`
+fn benchmark_sign_batch(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("sign_batch");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.sample_size(20);
+
+    let messages = messages();
+    for algorithm in [SignatureAlgorithm::MlDsa65, SignatureAlgorithm::Falcon512] {
+        let (_, secret_key) = Backend::PqClean
+            .signature_keypair(algorithm)
+            .expect("keypair should generate");
+
+        group.bench_with_input(
+            BenchmarkId::new("sequential", algorithm),
+            &messages,
+            |b, messages| {
+                b.iter(|| {
+                    rt.block_on(async {
+                        for message in messages {
+                            std::hint::black_box(sign_one(algorithm, &secret_key, message).await);
+                        }
+                    });
+                });
+            },
+        );
+
+        let batch = SignatureBuilder::batch(algorithm);
+        group.bench_with_input(
+            BenchmarkId::new("batch", algorithm),
+            &messages,
+            |b, messages| {
+                b.iter(|| {
+                    rt.block_on(async {
+                        let results = batch
+                            .sign_batch(secret_key.to_vec(), messages.clone())
+                            .await;
+                        std::hint::black_box(results);
+                    });
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_verify_batch, benchmark_sign_batch);
+criterion_main!(benches);
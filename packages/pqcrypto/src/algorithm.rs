@@ -1,5 +1,7 @@
 //! Algorithm enums for post-quantum cryptography
 
+use crate::backend::PreHash;
+use crate::{PqCryptoError, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -21,6 +23,18 @@ pub enum KemAlgorithm {
     /// Formerly known as Kyber1024
     #[serde(rename = "ml-kem-1024")]
     MlKem1024,
+
+    /// X25519 + ML-KEM-768 hybrid (X-Wing)
+    #[serde(rename = "x25519-ml-kem-768")]
+    X25519MlKem768,
+
+    /// P-256 ECDH + ML-KEM-768 hybrid with a cryypt-specific combiner
+    #[serde(rename = "p256-ml-kem-768")]
+    P256MlKem768,
+
+    /// P-384 ECDH + ML-KEM-1024 hybrid with a cryypt-specific combiner
+    #[serde(rename = "p384-ml-kem-1024")]
+    P384MlKem1024,
 }
 
 impl KemAlgorithm {
@@ -29,11 +43,20 @@ impl KemAlgorithm {
     pub fn security_level(&self) -> u8 {
         match self {
             Self::MlKem512 => 1,
-            Self::MlKem768 => 3,
-            Self::MlKem1024 => 5,
+            Self::MlKem768 | Self::X25519MlKem768 | Self::P256MlKem768 => 3,
+            Self::MlKem1024 | Self::P384MlKem1024 => 5,
         }
     }
 
+    /// Check if this combines ML-KEM with a classical ECDH exchange
+    #[must_use]
+    pub fn is_hybrid(&self) -> bool {
+        matches!(
+            self,
+            Self::X25519MlKem768 | Self::P256MlKem768 | Self::P384MlKem1024
+        )
+    }
+
     /// Get the public key size in bytes
     #[must_use]
     pub fn public_key_size(&self) -> usize {
@@ -41,6 +64,9 @@ impl KemAlgorithm {
             Self::MlKem512 => 800,
             Self::MlKem768 => 1184,
             Self::MlKem1024 => 1568,
+            Self::X25519MlKem768 => 1184 + 32,
+            Self::P256MlKem768 => 65 + 1184,
+            Self::P384MlKem1024 => 97 + 1568,
         }
     }
 
@@ -51,6 +77,8 @@ impl KemAlgorithm {
             Self::MlKem512 => 1632,
             Self::MlKem768 => 2400,
             Self::MlKem1024 => 3168,
+            Self::X25519MlKem768 | Self::P256MlKem768 => 2400 + 32,
+            Self::P384MlKem1024 => 48 + 3168,
         }
     }
 
@@ -61,10 +89,13 @@ impl KemAlgorithm {
             Self::MlKem512 => 768,
             Self::MlKem768 => 1088,
             Self::MlKem1024 => 1568,
+            Self::X25519MlKem768 => 1088 + 32,
+            Self::P256MlKem768 => 65 + 1088,
+            Self::P384MlKem1024 => 97 + 1568,
         }
     }
 
-    /// Get the shared secret size in bytes (always 32 for ML-KEM)
+    /// Get the shared secret size in bytes (always 32 for ML-KEM and the hybrids)
     #[must_use]
     pub fn shared_secret_size(&self) -> usize {
         32
@@ -77,6 +108,9 @@ impl fmt::Display for KemAlgorithm {
             Self::MlKem512 => write!(f, "ML-KEM-512"),
             Self::MlKem768 => write!(f, "ML-KEM-768"),
             Self::MlKem1024 => write!(f, "ML-KEM-1024"),
+            Self::X25519MlKem768 => write!(f, "X-Wing"),
+            Self::P256MlKem768 => write!(f, "cryypt-P256-MLKEM768"),
+            Self::P384MlKem1024 => write!(f, "cryypt-P384-MLKEM1024"),
         }
     }
 }
@@ -187,8 +221,8 @@ impl SignatureAlgorithm {
     pub fn signature_size(&self) -> usize {
         match self {
             Self::MlDsa44 => 2420,
-            Self::MlDsa65 => 3293,
-            Self::MlDsa87 => 4595,
+            Self::MlDsa65 => 3309,
+            Self::MlDsa87 => 4627,
             Self::Falcon512 => 666,
             Self::Falcon1024 => 1280,
             Self::SphincsShaSha256_128fSimple => 17088,
@@ -240,3 +274,200 @@ impl fmt::Display for SignatureAlgorithm {
         }
     }
 }
+
+/// Classical signature algorithms paired with ML-DSA in composite signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ClassicalAlgorithm {
+    /// Ed25519 (RFC 8032)
+    #[serde(rename = "ed25519")]
+    Ed25519,
+
+    /// ECDSA over P-256 with SHA-256
+    #[serde(rename = "ecdsa-p256")]
+    EcdsaP256,
+
+    /// ECDSA over P-384 with SHA-384
+    #[serde(rename = "ecdsa-p384")]
+    EcdsaP384,
+}
+
+impl ClassicalAlgorithm {
+    /// Get the public key size in bytes (uncompressed SEC1 points for ECDSA)
+    #[must_use]
+    pub fn public_key_size(&self) -> usize {
+        match self {
+            Self::Ed25519 => 32,
+            Self::EcdsaP256 => 65,
+            Self::EcdsaP384 => 97,
+        }
+    }
+
+    /// Get the secret key size in bytes
+    ///
+    /// ECDSA secret keys are RFC 5915 `ECPrivateKey` structures carrying the curve and the
+    /// public key.
+    #[must_use]
+    pub fn secret_key_size(&self) -> usize {
+        match self {
+            Self::Ed25519 => 32,
+            Self::EcdsaP256 => 121,
+            Self::EcdsaP384 => 167,
+        }
+    }
+
+    /// Get the maximum signature size in bytes (ECDSA signatures are DER and vary in length)
+    #[must_use]
+    pub fn max_signature_size(&self) -> usize {
+        match self {
+            Self::Ed25519 => 64,
+            Self::EcdsaP256 => 72,
+            Self::EcdsaP384 => 104,
+        }
+    }
+}
+
+impl fmt::Display for ClassicalAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ed25519 => write!(f, "Ed25519"),
+            Self::EcdsaP256 => write!(f, "ECDSA-P256"),
+            Self::EcdsaP384 => write!(f, "ECDSA-P384"),
+        }
+    }
+}
+
+/// Composite ML-DSA + classical signature algorithms (draft-ietf-lamps-pq-composite-sigs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CompositeAlgorithm {
+    /// ML-DSA-44 + Ed25519, SHA-512 pre-hash
+    #[serde(rename = "mldsa44-ed25519")]
+    MlDsa44Ed25519,
+
+    /// ML-DSA-44 + ECDSA P-256, SHA-256 pre-hash
+    #[serde(rename = "mldsa44-ecdsa-p256")]
+    MlDsa44EcdsaP256,
+
+    /// ML-DSA-65 + Ed25519, SHA-512 pre-hash
+    #[serde(rename = "mldsa65-ed25519")]
+    MlDsa65Ed25519,
+
+    /// ML-DSA-65 + ECDSA P-256, SHA-512 pre-hash
+    #[serde(rename = "mldsa65-ecdsa-p256")]
+    MlDsa65EcdsaP256,
+
+    /// ML-DSA-65 + ECDSA P-384, SHA-512 pre-hash
+    #[serde(rename = "mldsa65-ecdsa-p384")]
+    MlDsa65EcdsaP384,
+
+    /// ML-DSA-87 + ECDSA P-384, SHA-512 pre-hash
+    #[serde(rename = "mldsa87-ecdsa-p384")]
+    MlDsa87EcdsaP384,
+}
+
+impl CompositeAlgorithm {
+    /// Every composite algorithm, in draft order
+    pub const ALL: [Self; 6] = [
+        Self::MlDsa44Ed25519,
+        Self::MlDsa44EcdsaP256,
+        Self::MlDsa65Ed25519,
+        Self::MlDsa65EcdsaP256,
+        Self::MlDsa65EcdsaP384,
+        Self::MlDsa87EcdsaP384,
+    ];
+
+    /// Look up the composite of an ML-DSA parameter set and a classical algorithm
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if the draft defines no such pairing.
+    pub fn new(ml_dsa: SignatureAlgorithm, classical: ClassicalAlgorithm) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|composite| composite.ml_dsa() == ml_dsa && composite.classical() == classical)
+            .ok_or_else(|| {
+                PqCryptoError::UnsupportedAlgorithm(format!(
+                    "No composite signature pairs {ml_dsa} with {classical}"
+                ))
+            })
+    }
+
+    /// The ML-DSA component
+    #[must_use]
+    pub fn ml_dsa(&self) -> SignatureAlgorithm {
+        match self {
+            Self::MlDsa44Ed25519 | Self::MlDsa44EcdsaP256 => SignatureAlgorithm::MlDsa44,
+            Self::MlDsa65Ed25519 | Self::MlDsa65EcdsaP256 | Self::MlDsa65EcdsaP384 => {
+                SignatureAlgorithm::MlDsa65
+            }
+            Self::MlDsa87EcdsaP384 => SignatureAlgorithm::MlDsa87,
+        }
+    }
+
+    /// The classical component
+    #[must_use]
+    pub fn classical(&self) -> ClassicalAlgorithm {
+        match self {
+            Self::MlDsa44Ed25519 | Self::MlDsa65Ed25519 => ClassicalAlgorithm::Ed25519,
+            Self::MlDsa44EcdsaP256 | Self::MlDsa65EcdsaP256 => ClassicalAlgorithm::EcdsaP256,
+            Self::MlDsa65EcdsaP384 | Self::MlDsa87EcdsaP384 => ClassicalAlgorithm::EcdsaP384,
+        }
+    }
+
+    /// Get the security level of the algorithm (that of its ML-DSA component)
+    #[must_use]
+    pub fn security_level(&self) -> u8 {
+        self.ml_dsa().security_level()
+    }
+
+    /// Get the public key size in bytes: the ML-DSA public key followed by the classical one
+    #[must_use]
+    pub fn public_key_size(&self) -> usize {
+        self.ml_dsa().public_key_size() + self.classical().public_key_size()
+    }
+
+    /// Get the secret key size in bytes: the 32-byte ML-DSA seed followed by the classical
+    /// secret key
+    #[must_use]
+    pub fn secret_key_size(&self) -> usize {
+        32 + self.classical().secret_key_size()
+    }
+
+    /// Get the maximum signature size in bytes
+    #[must_use]
+    pub fn max_signature_size(&self) -> usize {
+        self.ml_dsa().signature_size() + self.classical().max_signature_size()
+    }
+
+    /// Domain separation label, also the ML-DSA context string
+    pub(crate) fn label(self) -> &'static [u8] {
+        match self {
+            Self::MlDsa44Ed25519 => b"COMPSIG-MLDSA44-Ed25519-SHA512",
+            Self::MlDsa44EcdsaP256 => b"COMPSIG-MLDSA44-ECDSA-P256-SHA256",
+            Self::MlDsa65Ed25519 => b"COMPSIG-MLDSA65-Ed25519-SHA512",
+            Self::MlDsa65EcdsaP256 => b"COMPSIG-MLDSA65-ECDSA-P256-SHA512",
+            Self::MlDsa65EcdsaP384 => b"COMPSIG-MLDSA65-ECDSA-P384-SHA512",
+            Self::MlDsa87EcdsaP384 => b"COMPSIG-MLDSA87-ECDSA-P384-SHA512",
+        }
+    }
+
+    /// Hash applied to the message before both components sign it
+    pub(crate) fn pre_hash(self) -> PreHash {
+        match self {
+            Self::MlDsa44EcdsaP256 => PreHash::Sha256,
+            _ => PreHash::Sha512,
+        }
+    }
+}
+
+impl fmt::Display for CompositeAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MlDsa44Ed25519 => write!(f, "MLDSA44-Ed25519-SHA512"),
+            Self::MlDsa44EcdsaP256 => write!(f, "MLDSA44-ECDSA-P256-SHA256"),
+            Self::MlDsa65Ed25519 => write!(f, "MLDSA65-Ed25519-SHA512"),
+            Self::MlDsa65EcdsaP256 => write!(f, "MLDSA65-ECDSA-P256-SHA512"),
+            Self::MlDsa65EcdsaP384 => write!(f, "MLDSA65-ECDSA-P384-SHA512"),
+            Self::MlDsa87EcdsaP384 => write!(f, "MLDSA87-ECDSA-P384-SHA512"),
+        }
+    }
+}
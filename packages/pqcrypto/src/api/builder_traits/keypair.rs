@@ -20,7 +20,10 @@ pub trait KemKeyPairBuilder {
     where
         Self: Sized + Send;
 
-    /// Load key pair from bytes
+    /// Load key pair from raw bytes, or from SPKI and PKCS#8 in DER or PEM
+    ///
+    /// Encoded keys select the parameter set named by their OID; see
+    /// [`encoding`](crate::encoding) for the supported algorithms.
     ///
     /// # Errors
     ///
@@ -29,7 +32,7 @@ pub trait KemKeyPairBuilder {
     where
         Self: Sized;
 
-    /// Load public key from bytes
+    /// Load public key from raw bytes, SPKI DER or SPKI PEM
     ///
     /// # Errors
     ///
@@ -38,7 +41,7 @@ pub trait KemKeyPairBuilder {
     where
         Self: Sized;
 
-    /// Load secret key from bytes
+    /// Load secret key from raw bytes, PKCS#8 DER or PKCS#8 PEM
     ///
     /// # Errors
     ///
@@ -142,7 +145,10 @@ pub trait SignatureKeyPairBuilder {
     where
         Self: Sized + Send;
 
-    /// Load key pair from bytes
+    /// Load key pair from raw bytes, or from SPKI and PKCS#8 in DER or PEM
+    ///
+    /// Encoded keys select the parameter set named by their OID; see
+    /// [`encoding`](crate::encoding) for the supported algorithms.
     ///
     /// # Errors
     ///
@@ -151,7 +157,7 @@ pub trait SignatureKeyPairBuilder {
     where
         Self: Sized;
 
-    /// Load public key from bytes
+    /// Load public key from raw bytes, SPKI DER or SPKI PEM
     ///
     /// # Errors
     ///
@@ -160,7 +166,7 @@ pub trait SignatureKeyPairBuilder {
     where
         Self: Sized;
 
-    /// Load secret key from bytes
+    /// Load secret key from raw bytes, PKCS#8 DER or PKCS#8 PEM
     ///
     /// # Errors
     ///
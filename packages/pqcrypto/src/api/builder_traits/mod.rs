@@ -9,11 +9,13 @@ use std::future::Future;
 pub mod execution;
 pub mod keypair;
 pub mod operations;
+pub mod streaming;
 
 // Re-export all traits from submodules for external use
 pub use execution::*;
 pub use keypair::*;
 pub use operations::*;
+pub use streaming::*;
 
 /// Async result type for encapsulation operations
 pub trait AsyncEncapsulationResult:
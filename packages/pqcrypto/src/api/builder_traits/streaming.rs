@@ -0,0 +1,154 @@
+//! Streaming signature builder traits
+//!
+//! Contains traits for signing and verifying messages that are hashed as they are read, so
+//! they never need to fit in memory.
+
+use crate::{PqCryptoError, Result, SignatureFile, StreamDigest, VerificationResult};
+use futures::{Stream, StreamExt};
+use std::future::Future;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Bytes read from an `AsyncRead` per hash update
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Builder that can sign a streamed message, producing a detached [`SignatureFile`]
+pub trait StreamSignBuilder: Sized + Send {
+    /// How messages are digested for this builder's algorithm
+    fn stream_digest(&self) -> StreamDigest;
+
+    /// Sign a digest of the message computed with [`Self::stream_digest`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the digest has the wrong length or the secret key is invalid.
+    fn sign_digest(self, digest: &[u8]) -> Result<SignatureFile>;
+
+    /// Sign everything read from `reader`
+    fn sign_stream<R>(self, reader: R) -> impl Future<Output = Result<SignatureFile>> + Send
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        async move {
+            let digest = digest_reader(self.stream_digest(), reader).await?;
+            self.sign_digest(&digest)
+        }
+    }
+
+    /// Sign the concatenation of the chunks of `stream`
+    fn sign_chunks<S, B>(self, stream: S) -> impl Future<Output = Result<SignatureFile>> + Send
+    where
+        S: Stream<Item = B> + Unpin + Send,
+        B: AsRef<[u8]> + Send,
+    {
+        async move {
+            let digest = digest_chunks(self.stream_digest(), stream).await;
+            self.sign_digest(&digest)
+        }
+    }
+
+    /// Sign the file at `path` and write the signature next to it, as
+    /// [`SignatureFile::path_for`] names it
+    fn sign_file<P: AsRef<Path> + Send>(
+        self,
+        path: P,
+    ) -> impl Future<Output = Result<SignatureFile>> + Send {
+        async move {
+            let file = tokio::fs::File::open(path.as_ref())
+                .await
+                .map_err(|e| PqCryptoError::Io(format!("Failed to open file to sign: {e}")))?;
+            let signature = self.sign_stream(file).await?;
+            signature.write(SignatureFile::path_for(path)).await?;
+            Ok(signature)
+        }
+    }
+}
+
+/// Builder that can verify a [`SignatureFile`] over a streamed message
+pub trait StreamVerifyBuilder: Sized + Send {
+    /// How messages are digested for this builder's algorithm
+    fn stream_digest(&self) -> StreamDigest;
+
+    /// Verify `signature` over a digest of the message computed with [`Self::stream_digest`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `signature` is for another algorithm or the public key is invalid.
+    fn verify_digest(self, digest: &[u8], signature: &SignatureFile) -> Result<VerificationResult>;
+
+    /// Verify `signature` over everything read from `reader`
+    fn verify_stream<R>(
+        self,
+        reader: R,
+        signature: SignatureFile,
+    ) -> impl Future<Output = Result<VerificationResult>> + Send
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        async move {
+            let digest = digest_reader(self.stream_digest(), reader).await?;
+            self.verify_digest(&digest, &signature)
+        }
+    }
+
+    /// Verify `signature` over the concatenation of the chunks of `stream`
+    fn verify_chunks<S, B>(
+        self,
+        stream: S,
+        signature: SignatureFile,
+    ) -> impl Future<Output = Result<VerificationResult>> + Send
+    where
+        S: Stream<Item = B> + Unpin + Send,
+        B: AsRef<[u8]> + Send,
+    {
+        async move {
+            let digest = digest_chunks(self.stream_digest(), stream).await;
+            self.verify_digest(&digest, &signature)
+        }
+    }
+
+    /// Verify the file at `path` against the signature file next to it, as
+    /// [`SignatureFile::path_for`] names it
+    fn verify_file<P: AsRef<Path> + Send>(
+        self,
+        path: P,
+    ) -> impl Future<Output = Result<VerificationResult>> + Send {
+        async move {
+            let signature = SignatureFile::read(SignatureFile::path_for(path.as_ref())).await?;
+            let file = tokio::fs::File::open(path.as_ref())
+                .await
+                .map_err(|e| PqCryptoError::Io(format!("Failed to open file to verify: {e}")))?;
+            self.verify_stream(file, signature).await
+        }
+    }
+}
+
+async fn digest_reader<R: AsyncRead + Unpin>(
+    digest: StreamDigest,
+    mut reader: R,
+) -> Result<Vec<u8>> {
+    let mut hasher = digest.hasher();
+    let mut buffer = vec![0u8; READ_CHUNK_SIZE];
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .await
+            .map_err(|e| PqCryptoError::Io(format!("Failed to read message: {e}")))?;
+        if read == 0 {
+            return Ok(hasher.finalize());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+async fn digest_chunks<S, B>(digest: StreamDigest, mut stream: S) -> Vec<u8>
+where
+    S: Stream<Item = B> + Unpin,
+    B: AsRef<[u8]>,
+{
+    let mut hasher = digest.hasher();
+    while let Some(chunk) = stream.next().await {
+        hasher.update(chunk.as_ref());
+    }
+    hasher.finalize()
+}
@@ -104,6 +104,13 @@ impl DecapsulateBuilder for MlKemBuilder<HasCiphertext> {
                     let ss = decapsulate(&ct, &sk);
                     PqSharedSecret::as_bytes(&ss).to_vec()
                 }
+                KemAlgorithm::X25519MlKem768
+                | KemAlgorithm::P256MlKem768
+                | KemAlgorithm::P384MlKem1024 => {
+                    let mut ss =
+                        super::hybrid::decapsulate(algorithm, &secret_key, &ciphertext)?;
+                    std::mem::take(&mut *ss)
+                }
             };
 
             let shared_secret = SharedSecret::new(algorithm, shared_secret_bytes);
@@ -56,6 +56,12 @@ impl EncapsulateBuilder for MlKemBuilder<HasPublicKey> {
                         PqCiphertext::as_bytes(&ct).to_vec(),
                     )
                 }
+                KemAlgorithm::X25519MlKem768
+                | KemAlgorithm::P256MlKem768
+                | KemAlgorithm::P384MlKem1024 => {
+                    let (ct, mut ss) = super::hybrid::encapsulate(self.algorithm, &public_key)?;
+                    (std::mem::take(&mut *ss), ct)
+                }
             };
 
             let shared_secret = SharedSecret::new(self.algorithm, shared_secret_bytes);
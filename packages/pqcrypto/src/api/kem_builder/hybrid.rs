@@ -0,0 +1,377 @@
+//! Hybrid KEMs combining ML-KEM with X25519 or NIST-curve ECDH
+//!
+//! The two shared secrets are combined with the X-Wing combiner
+//!
+//! ```text
+//! ss = SHA3-256(ss_mlkem || ss_ecdh || ct_ecdh || pk_ecdh || label)
+//! ```
+//!
+//! so the result stays secret as long as either component does.
+//!
+//! `X25519MlKem768` is X-Wing (draft-connolly-cfrg-xwing-kem): ML-KEM-768 first, then
+//! X25519, with the X-Wing label. Public keys, ciphertexts and shared secrets match the
+//! draft, checked by `tests/vectors/x_wing.json`; the secret key is the expanded ML-KEM
+//! decapsulation key followed by the X25519 scalar instead of the draft's 32-byte seed.
+//!
+//! The NIST-curve variants are cryypt-specific. Their keys and ciphertexts put ECDH first,
+//! like the `SecP256r1MLKEM768` / `SecP384r1MLKEM1024` groups of draft-ietf-tls-ecdhe-mlkem,
+//! but TLS concatenates the raw shared secrets where these hash them under a cryypt label,
+//! so they do not interoperate with TLS. A secret key is the ML-KEM and ECDH secret keys in
+//! the same order as the public key; the ECDH public key is recomputed when needed.
+
+use super::super::super::KemAlgorithm;
+use crate::{PqCryptoError, Result};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use pqcrypto_mlkem::{mlkem512, mlkem768, mlkem1024};
+use pqcrypto_traits::kem::{
+    Ciphertext as PqCiphertext, PublicKey as PqPublicKey, SecretKey as PqSecretKey,
+    SharedSecret as PqSharedSecret,
+};
+use rand::RngCore;
+use sha3::digest::generic_array::GenericArray;
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroizing;
+
+/// X-Wing combiner label (`\.//^\`)
+const XWING_LABEL: &[u8] = b"\\.//^\\";
+
+/// Classical half of a hybrid KEM, shared with the HPKE DHKEMs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Ecdh {
+    X25519,
+    P256,
+    P384,
+}
+
+/// How a hybrid algorithm is assembled
+struct Layout {
+    ecdh: Ecdh,
+    mlkem: KemAlgorithm,
+    ecdh_first: bool,
+    label: &'static [u8],
+}
+
+fn layout(algorithm: KemAlgorithm) -> Result<Layout> {
+    match algorithm {
+        KemAlgorithm::X25519MlKem768 => Ok(Layout {
+            ecdh: Ecdh::X25519,
+            mlkem: KemAlgorithm::MlKem768,
+            ecdh_first: false,
+            label: XWING_LABEL,
+        }),
+        KemAlgorithm::P256MlKem768 => Ok(Layout {
+            ecdh: Ecdh::P256,
+            mlkem: KemAlgorithm::MlKem768,
+            ecdh_first: true,
+            label: b"cryypt-P256-MLKEM768",
+        }),
+        KemAlgorithm::P384MlKem1024 => Ok(Layout {
+            ecdh: Ecdh::P384,
+            mlkem: KemAlgorithm::MlKem1024,
+            ecdh_first: true,
+            label: b"cryypt-P384-MLKEM1024",
+        }),
+        other => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+            "{other} is not a hybrid KEM"
+        ))),
+    }
+}
+
+impl Layout {
+    /// Split `bytes` into its (ECDH, ML-KEM) parts
+    fn split<'a>(
+        &self,
+        bytes: &'a [u8],
+        ecdh_len: usize,
+        mlkem_len: usize,
+    ) -> Result<(&'a [u8], &'a [u8])> {
+        if bytes.len() != ecdh_len + mlkem_len {
+            return Err(PqCryptoError::InvalidKeySize {
+                expected: ecdh_len + mlkem_len,
+                actual: bytes.len(),
+            });
+        }
+        Ok(if self.ecdh_first {
+            bytes.split_at(ecdh_len)
+        } else {
+            let (mlkem, ecdh) = bytes.split_at(mlkem_len);
+            (ecdh, mlkem)
+        })
+    }
+
+    fn join(&self, ecdh: &[u8], mlkem: &[u8]) -> Vec<u8> {
+        if self.ecdh_first {
+            [ecdh, mlkem].concat()
+        } else {
+            [mlkem, ecdh].concat()
+        }
+    }
+
+    fn combine(
+        &self,
+        mlkem_shared: &[u8],
+        ecdh_shared: &[u8],
+        ecdh_ciphertext: &[u8],
+        ecdh_public: &[u8],
+    ) -> Zeroizing<Vec<u8>> {
+        let mut shared = Zeroizing::new(vec![0u8; Sha3_256::output_size()]);
+        Sha3_256::new()
+            .chain_update(mlkem_shared)
+            .chain_update(ecdh_shared)
+            .chain_update(ecdh_ciphertext)
+            .chain_update(ecdh_public)
+            .chain_update(self.label)
+            .finalize_into(GenericArray::from_mut_slice(&mut shared));
+        shared
+    }
+}
+
+impl Ecdh {
+    pub(crate) fn public_len(self) -> usize {
+        match self {
+            Self::X25519 => 32,
+            Self::P256 => 65,
+            Self::P384 => 97,
+        }
+    }
+
+    pub(crate) fn secret_len(self) -> usize {
+        match self {
+            Self::X25519 | Self::P256 => 32,
+            Self::P384 => 48,
+        }
+    }
+
+    /// Draw a random secret key
+    pub(crate) fn generate(self) -> Result<Zeroizing<Vec<u8>>> {
+        let mut rng = rand::rng();
+        // Out-of-range scalars are astronomically rare; the bound only guards a broken RNG
+        for _ in 0..64 {
+            let mut secret = Zeroizing::new(vec![0u8; self.secret_len()]);
+            rng.fill_bytes(&mut secret);
+            if self.public_key(&secret).is_ok() {
+                return Ok(secret);
+            }
+        }
+        Err(PqCryptoError::KeyGenerationFailed(
+            "Random source produced no valid ECDH scalar".to_string(),
+        ))
+    }
+
+    /// Public key of a secret key (uncompressed SEC1 for the NIST curves)
+    pub(crate) fn public_key(self, secret: &[u8]) -> Result<Vec<u8>> {
+        Ok(match self {
+            Self::X25519 => {
+                let secret = x25519_secret(secret)?;
+                x25519_dalek::PublicKey::from(&secret).as_bytes().to_vec()
+            }
+            Self::P256 => p256::SecretKey::from_slice(secret)
+                .map_err(|_| PqCryptoError::InvalidKey("Invalid P-256 scalar".to_string()))?
+                .public_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .to_vec(),
+            Self::P384 => p384::SecretKey::from_slice(secret)
+                .map_err(|_| PqCryptoError::InvalidKey("Invalid P-384 scalar".to_string()))?
+                .public_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .to_vec(),
+        })
+    }
+
+    /// ECDH between a secret key and a peer public key
+    pub(crate) fn agree(self, secret: &[u8], peer: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let shared = match self {
+            Self::X25519 => {
+                let peer: [u8; 32] =
+                    peer.try_into().map_err(|_| PqCryptoError::InvalidKeySize {
+                        expected: 32,
+                        actual: peer.len(),
+                    })?;
+                let shared = x25519_secret(secret)?.diffie_hellman(&peer.into());
+                // Low-order peer points yield an all-zero secret
+                if !shared.was_contributory() {
+                    return Err(PqCryptoError::InvalidKey(
+                        "X25519 public key is a low-order point".to_string(),
+                    ));
+                }
+                shared.as_bytes().to_vec()
+            }
+            Self::P256 => {
+                let secret = p256::SecretKey::from_slice(secret)
+                    .map_err(|_| PqCryptoError::InvalidKey("Invalid P-256 scalar".to_string()))?;
+                let peer = p256::PublicKey::from_sec1_bytes(peer)
+                    .map_err(|_| PqCryptoError::InvalidKey("Invalid P-256 point".to_string()))?;
+                p256::ecdh::diffie_hellman(secret.to_nonzero_scalar(), peer.as_affine())
+                    .raw_secret_bytes()
+                    .to_vec()
+            }
+            Self::P384 => {
+                let secret = p384::SecretKey::from_slice(secret)
+                    .map_err(|_| PqCryptoError::InvalidKey("Invalid P-384 scalar".to_string()))?;
+                let peer = p384::PublicKey::from_sec1_bytes(peer)
+                    .map_err(|_| PqCryptoError::InvalidKey("Invalid P-384 point".to_string()))?;
+                p384::ecdh::diffie_hellman(secret.to_nonzero_scalar(), peer.as_affine())
+                    .raw_secret_bytes()
+                    .to_vec()
+            }
+        };
+        Ok(Zeroizing::new(shared))
+    }
+}
+
+fn x25519_secret(secret: &[u8]) -> Result<x25519_dalek::StaticSecret> {
+    let bytes: Zeroizing<[u8; 32]> =
+        Zeroizing::new(
+            secret
+                .try_into()
+                .map_err(|_| PqCryptoError::InvalidKeySize {
+                    expected: 32,
+                    actual: secret.len(),
+                })?,
+        );
+    Ok(x25519_dalek::StaticSecret::from(*bytes))
+}
+
+/// Generate an ML-KEM key pair, returning `(public_key, secret_key)`
+pub(crate) fn mlkem_keypair(algorithm: KemAlgorithm) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    let (pk, sk) = match algorithm {
+        KemAlgorithm::MlKem512 => {
+            let (pk, sk) = mlkem512::keypair();
+            (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+        }
+        KemAlgorithm::MlKem768 => {
+            let (pk, sk) = mlkem768::keypair();
+            (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+        }
+        KemAlgorithm::MlKem1024 => {
+            let (pk, sk) = mlkem1024::keypair();
+            (pk.as_bytes().to_vec(), sk.as_bytes().to_vec())
+        }
+        other => return Err(not_mlkem(other)),
+    };
+    Ok((pk, Zeroizing::new(sk)))
+}
+
+/// Encapsulate to an ML-KEM public key, returning `(ciphertext, shared_secret)`
+pub(crate) fn mlkem_encapsulate(
+    algorithm: KemAlgorithm,
+    public_key: &[u8],
+) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    let invalid = |_| PqCryptoError::InvalidKey(format!("Invalid {algorithm} public key"));
+    let (ss, ct) = match algorithm {
+        KemAlgorithm::MlKem512 => {
+            let pk = mlkem512::PublicKey::from_bytes(public_key).map_err(invalid)?;
+            let (ss, ct) = mlkem512::encapsulate(&pk);
+            (ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
+        }
+        KemAlgorithm::MlKem768 => {
+            let pk = mlkem768::PublicKey::from_bytes(public_key).map_err(invalid)?;
+            let (ss, ct) = mlkem768::encapsulate(&pk);
+            (ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
+        }
+        KemAlgorithm::MlKem1024 => {
+            let pk = mlkem1024::PublicKey::from_bytes(public_key).map_err(invalid)?;
+            let (ss, ct) = mlkem1024::encapsulate(&pk);
+            (ss.as_bytes().to_vec(), ct.as_bytes().to_vec())
+        }
+        other => return Err(not_mlkem(other)),
+    };
+    Ok((ct, Zeroizing::new(ss)))
+}
+
+/// Decapsulate an ML-KEM ciphertext, returning the shared secret
+pub(crate) fn mlkem_decapsulate(
+    algorithm: KemAlgorithm,
+    secret_key: &[u8],
+    ciphertext: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    let invalid_key = |_| PqCryptoError::InvalidKey(format!("Invalid {algorithm} secret key"));
+    let invalid_ct =
+        |_| PqCryptoError::InvalidEncryptedData(format!("Invalid {algorithm} ciphertext"));
+    let ss = match algorithm {
+        KemAlgorithm::MlKem512 => {
+            let sk = mlkem512::SecretKey::from_bytes(secret_key).map_err(invalid_key)?;
+            let ct = mlkem512::Ciphertext::from_bytes(ciphertext).map_err(invalid_ct)?;
+            mlkem512::decapsulate(&ct, &sk).as_bytes().to_vec()
+        }
+        KemAlgorithm::MlKem768 => {
+            let sk = mlkem768::SecretKey::from_bytes(secret_key).map_err(invalid_key)?;
+            let ct = mlkem768::Ciphertext::from_bytes(ciphertext).map_err(invalid_ct)?;
+            mlkem768::decapsulate(&ct, &sk).as_bytes().to_vec()
+        }
+        KemAlgorithm::MlKem1024 => {
+            let sk = mlkem1024::SecretKey::from_bytes(secret_key).map_err(invalid_key)?;
+            let ct = mlkem1024::Ciphertext::from_bytes(ciphertext).map_err(invalid_ct)?;
+            mlkem1024::decapsulate(&ct, &sk).as_bytes().to_vec()
+        }
+        other => return Err(not_mlkem(other)),
+    };
+    Ok(Zeroizing::new(ss))
+}
+
+fn not_mlkem(algorithm: KemAlgorithm) -> PqCryptoError {
+    PqCryptoError::UnsupportedAlgorithm(format!("{algorithm} is not a plain ML-KEM parameter set"))
+}
+
+/// Generate a hybrid key pair, returning `(public_key, secret_key)`
+pub(crate) fn keypair(algorithm: KemAlgorithm) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    let layout = layout(algorithm)?;
+    let (mlkem_public, mlkem_secret) = mlkem_keypair(layout.mlkem)?;
+    let ecdh_secret = layout.ecdh.generate()?;
+    let ecdh_public = layout.ecdh.public_key(&ecdh_secret)?;
+    Ok((
+        layout.join(&ecdh_public, &mlkem_public),
+        Zeroizing::new(layout.join(&ecdh_secret, &mlkem_secret)),
+    ))
+}
+
+/// Encapsulate to a hybrid public key, returning `(ciphertext, shared_secret)`
+pub(crate) fn encapsulate(
+    algorithm: KemAlgorithm,
+    public_key: &[u8],
+) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    let layout = layout(algorithm)?;
+    let (ecdh_public, mlkem_public) = layout.split(
+        public_key,
+        layout.ecdh.public_len(),
+        layout.mlkem.public_key_size(),
+    )?;
+
+    let (mlkem_ciphertext, mlkem_shared) = mlkem_encapsulate(layout.mlkem, mlkem_public)?;
+    let ephemeral = layout.ecdh.generate()?;
+    let ecdh_ciphertext = layout.ecdh.public_key(&ephemeral)?;
+    let ecdh_shared = layout.ecdh.agree(&ephemeral, ecdh_public)?;
+
+    let shared_secret = layout.combine(&mlkem_shared, &ecdh_shared, &ecdh_ciphertext, ecdh_public);
+    Ok((
+        layout.join(&ecdh_ciphertext, &mlkem_ciphertext),
+        shared_secret,
+    ))
+}
+
+/// Decapsulate a hybrid ciphertext, returning the shared secret
+pub(crate) fn decapsulate(
+    algorithm: KemAlgorithm,
+    secret_key: &[u8],
+    ciphertext: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    let layout = layout(algorithm)?;
+    let (ecdh_secret, mlkem_secret) = layout.split(
+        secret_key,
+        layout.ecdh.secret_len(),
+        layout.mlkem.secret_key_size(),
+    )?;
+    let (ecdh_ciphertext, mlkem_ciphertext) = layout.split(
+        ciphertext,
+        layout.ecdh.public_len(),
+        layout.mlkem.ciphertext_size(),
+    )?;
+
+    let mlkem_shared = mlkem_decapsulate(layout.mlkem, mlkem_secret, mlkem_ciphertext)?;
+    let ecdh_shared = layout.ecdh.agree(ecdh_secret, ecdh_ciphertext)?;
+    let ecdh_public = layout.ecdh.public_key(ecdh_secret)?;
+
+    Ok(layout.combine(&mlkem_shared, &ecdh_shared, ecdh_ciphertext, &ecdh_public))
+}
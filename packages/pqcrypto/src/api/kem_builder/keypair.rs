@@ -8,10 +8,46 @@ use super::super::{
     states::{HasKeyPair, HasPublicKey, HasSecretKey, NeedKeyPair},
 };
 use super::MlKemBuilder;
-use crate::Result;
+use crate::backend::Backend;
+use crate::{Result, encoding};
 use pqcrypto_traits::kem::{PublicKey as PqPublicKey, SecretKey as PqSecretKey};
 
 use std::marker::PhantomData;
+use zeroize::Zeroizing;
+
+impl MlKemBuilder<NeedKeyPair> {
+    /// Create a builder for the given ML-KEM parameter set
+    #[must_use]
+    pub fn new(algorithm: KemAlgorithm) -> Self {
+        Self {
+            algorithm,
+            state: PhantomData,
+            public_key: None,
+            secret_key: None,
+            ciphertext: None,
+        }
+    }
+
+    /// Derive the key pair from a 64-byte FIPS 203 seed `d || z`
+    ///
+    /// Uses the [native backend](crate::backend); the same seed always yields the same keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` for the hybrid KEMs and
+    /// `PqCryptoError::InvalidParameters` for a seed of the wrong length.
+    pub fn keypair_from_seed(self, seed: &[u8]) -> Result<MlKemBuilder<HasKeyPair>> {
+        let (pk, sk) = Backend::Native.kem_keypair_from_seed(self.algorithm, seed)?;
+
+        Ok(MlKemBuilder {
+            algorithm: self.algorithm,
+            state: PhantomData,
+            public_key: Some(pk),
+            secret_key: Some(sk),
+            ciphertext: None,
+        })
+    }
+}
 
 // Implementation for NeedKeyPair state
 impl KemKeyPairBuilder for MlKemBuilder<NeedKeyPair> {
@@ -25,23 +61,26 @@ impl KemKeyPairBuilder for MlKemBuilder<NeedKeyPair> {
                 let (pk, sk) = pqcrypto_mlkem::mlkem512::keypair();
                 (
                     PqPublicKey::as_bytes(&pk).to_vec(),
-                    PqSecretKey::as_bytes(&sk).to_vec(),
+                    Zeroizing::new(PqSecretKey::as_bytes(&sk).to_vec()),
                 )
             }
             KemAlgorithm::MlKem768 => {
                 let (pk, sk) = pqcrypto_mlkem::mlkem768::keypair();
                 (
                     PqPublicKey::as_bytes(&pk).to_vec(),
-                    PqSecretKey::as_bytes(&sk).to_vec(),
+                    Zeroizing::new(PqSecretKey::as_bytes(&sk).to_vec()),
                 )
             }
             KemAlgorithm::MlKem1024 => {
                 let (pk, sk) = pqcrypto_mlkem::mlkem1024::keypair();
                 (
                     PqPublicKey::as_bytes(&pk).to_vec(),
-                    PqSecretKey::as_bytes(&sk).to_vec(),
+                    Zeroizing::new(PqSecretKey::as_bytes(&sk).to_vec()),
                 )
             }
+            KemAlgorithm::X25519MlKem768
+            | KemAlgorithm::P256MlKem768
+            | KemAlgorithm::P384MlKem1024 => super::hybrid::keypair(self.algorithm)?,
         };
 
         Ok(MlKemBuilder {
@@ -54,17 +93,14 @@ impl KemKeyPairBuilder for MlKemBuilder<NeedKeyPair> {
     }
 
     fn with_keypair<T: Into<Vec<u8>>>(self, public_key: T, secret_key: T) -> Result<Self::Output> {
-        let pk = public_key.into();
-        let sk = secret_key.into();
-
-        self.validate_public_key(&pk)?;
-        self.validate_secret_key(&sk)?;
+        let (algorithm, pk, sk) =
+            encoding::import_keypair(self.algorithm, public_key.into(), secret_key.into())?;
 
         Ok(MlKemBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: Some(pk),
-            secret_key: Some(sk),
+            secret_key: Some(Zeroizing::new(sk)),
             ciphertext: None,
         })
     }
@@ -73,11 +109,10 @@ impl KemKeyPairBuilder for MlKemBuilder<NeedKeyPair> {
         self,
         public_key: T,
     ) -> Result<MlKemBuilder<HasPublicKey>> {
-        let pk = public_key.into();
-        self.validate_public_key(&pk)?;
+        let (algorithm, pk) = encoding::import_public_key(self.algorithm, public_key.into())?;
 
         Ok(MlKemBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: Some(pk),
             secret_key: None,
@@ -89,14 +124,13 @@ impl KemKeyPairBuilder for MlKemBuilder<NeedKeyPair> {
         self,
         secret_key: T,
     ) -> Result<MlKemBuilder<HasSecretKey>> {
-        let sk = secret_key.into();
-        self.validate_secret_key(&sk)?;
+        let (algorithm, sk) = encoding::import_secret_key(self.algorithm, secret_key.into())?;
 
         Ok(MlKemBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: None,
-            secret_key: Some(sk),
+            secret_key: Some(Zeroizing::new(sk)),
             ciphertext: None,
         })
     }
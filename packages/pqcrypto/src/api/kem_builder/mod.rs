@@ -2,278 +2,334 @@
 //!
 //! Contains the main ML-KEM builder patterns and core types for post-quantum key encapsulation.
 
-use super::super::KemAlgorithm;
+use super::super::{
+    DecapsulationResult, EncapsulationResult, KemAlgorithm, KemPublicKey, KemSecretKey,
+    SharedSecret,
+};
+use super::builder_traits::{DecapsulateBuilder, EncapsulateBuilder, KemKeyPairBuilder};
 use super::states::{HasCiphertext, HasKeyPair, HasPublicKey, HasSecretKey, NeedKeyPair};
 use crate::{PqCryptoError, Result};
+use std::fmt;
 use std::marker::PhantomData;
+use zeroize::Zeroizing;
 
 // Declare submodules
 pub mod decapsulation;
 pub mod encapsulation;
+pub(crate) mod hybrid;
 pub mod keypair;
 
 /// Main entry point for KEM operations
-pub struct KemBuilder;
+///
+/// Keys and ciphertexts are checked against one algorithm: the one chosen with
+/// [`KemBuilder::with_algorithm`], or else the algorithm of the key supplied. Key generation
+/// without a chosen algorithm uses ML-KEM-768.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KemBuilder {
+    algorithm: Option<KemAlgorithm>,
+}
 
-/// KEM builder with result handler for keypair/encapsulation operations (returns tuple)
+/// KEM builder with result handler for key generation
 pub struct KemBuilderWithHandler<F, T> {
+    algorithm: KemAlgorithm,
     result_handler: F,
-    _phantom: std::marker::PhantomData<T>,
+    _phantom: PhantomData<T>,
 }
 
-/// KEM builder with result handler for decapsulation operations (returns Vec<u8>)
-pub struct KemBuilderWithDecapHandler<F, T> {
+/// KEM builder with public key for encapsulation
+#[derive(Debug, Clone)]
+pub struct KemBuilderWithPublicKey {
+    public_key: KemPublicKey,
+}
+
+/// KEM builder with public key and result handler for encapsulation
+pub struct KemBuilderWithEncapHandler<F, T> {
+    public_key: KemPublicKey,
     result_handler: F,
-    _phantom: std::marker::PhantomData<T>,
+    _phantom: PhantomData<T>,
 }
 
 /// KEM builder with secret key for decapsulation
+#[derive(Debug, Clone)]
 pub struct KemBuilderWithSecretKey {
-    _phantom: std::marker::PhantomData<()>,
+    secret_key: KemSecretKey,
+}
+
+/// KEM builder with secret key and ciphertext ready for decapsulation
+#[derive(Debug, Clone)]
+pub struct KemBuilderWithCiphertext {
+    secret_key: KemSecretKey,
+    ciphertext: Vec<u8>,
+}
+
+/// KEM builder with result handler for decapsulation
+pub struct KemBuilderWithDecapHandler<F, T> {
+    secret_key: KemSecretKey,
+    ciphertext: Vec<u8>,
+    result_handler: F,
+    _phantom: PhantomData<T>,
 }
 
 impl KemBuilder {
-    /// Add `on_result` handler - `README.md` pattern
+    /// Create a KEM builder
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `algorithm` for key generation and require it of every key
+    #[must_use]
+    pub fn with_algorithm(self, algorithm: KemAlgorithm) -> Self {
+        Self {
+            algorithm: Some(algorithm),
+        }
+    }
+
+    /// Add `on_result` handler for key generation - `README.md` pattern
     pub fn on_result<F, T>(self, handler: F) -> KemBuilderWithHandler<F, T>
     where
-        F: FnOnce(crate::Result<(Vec<u8>, Vec<u8>)>) -> T + Send + 'static,
+        F: FnOnce(Result<(KemPublicKey, KemSecretKey)>) -> T + Send + 'static,
         T: Send + 'static,
     {
         KemBuilderWithHandler {
+            algorithm: self.algorithm.unwrap_or(KemAlgorithm::MlKem768),
             result_handler: handler,
-            _phantom: std::marker::PhantomData,
+            _phantom: PhantomData,
         }
     }
 
+    /// Set public key for encapsulation operations
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if the key is for another algorithm than
+    /// the one chosen with [`KemBuilder::with_algorithm`].
+    pub fn with_public_key(self, key: KemPublicKey) -> Result<KemBuilderWithPublicKey> {
+        self.check_algorithm(key.algorithm())?;
+        Ok(KemBuilderWithPublicKey { public_key: key })
+    }
+
     /// Set secret key for decapsulation operations
-    #[must_use]
-    pub fn with_secret_key(self, _key: Vec<u8>) -> KemBuilderWithSecretKey {
-        KemBuilderWithSecretKey {
-            _phantom: std::marker::PhantomData,
-        }
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if the key is for another algorithm than
+    /// the one chosen with [`KemBuilder::with_algorithm`].
+    pub fn with_secret_key(self, key: KemSecretKey) -> Result<KemBuilderWithSecretKey> {
+        self.check_algorithm(key.algorithm())?;
+        Ok(KemBuilderWithSecretKey { secret_key: key })
     }
-}
 
-impl KemBuilderWithSecretKey {
-    /// Add `on_result` handler for decapsulation - `README.md` pattern
-    #[allow(clippy::unused_self)] // self consumed for builder pattern state transition
-    pub fn on_result<F, T>(self, handler: F) -> KemBuilderWithDecapHandler<F, T>
-    where
-        F: FnOnce(crate::Result<Vec<u8>>) -> T + Send + 'static,
-        T: Send + 'static,
-    {
-        KemBuilderWithDecapHandler {
-            result_handler: handler,
-            _phantom: std::marker::PhantomData,
+    fn check_algorithm(self, key_algorithm: KemAlgorithm) -> Result<()> {
+        match self.algorithm {
+            Some(algorithm) if algorithm != key_algorithm => {
+                Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                    "{key_algorithm} key given to a {algorithm} builder"
+                )))
+            }
+            _ => Ok(()),
         }
     }
-}
 
-impl<F, T> KemBuilderWithDecapHandler<F, T>
-where
-    F: FnOnce(crate::Result<Vec<u8>>) -> T + Send + 'static,
-    T: Send + 'static,
-{
-    /// Decapsulate using real ML-KEM cryptography  
-    pub fn decapsulate(self, secret_key: &[u8], ciphertext: &[u8]) -> T {
-        let handler = self.result_handler;
+    /// Create a new ML-KEM builder with the specified security level
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the security level is not 512, 768 or 1024.
+    pub fn ml_kem(security_level: u16) -> Result<MlKemBuilder<NeedKeyPair>> {
+        let algorithm = match security_level {
+            512 => KemAlgorithm::MlKem512,
+            768 => KemAlgorithm::MlKem768,
+            1024 => KemAlgorithm::MlKem1024,
+            _ => {
+                return Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                    "ML-KEM-{security_level} is not supported. Use 512, 768, or 1024"
+                )));
+            }
+        };
 
-        // Perform real ML-KEM decapsulation using production cryptography
-        let result = Self::perform_mlkem_decapsulation(secret_key, ciphertext);
-        handler(result)
+        Ok(MlKemBuilder::new(algorithm))
     }
 
-    /// Internal ML-KEM decapsulation implementation using real cryptography
-    #[inline]
-    fn perform_mlkem_decapsulation(secret_key: &[u8], ciphertext: &[u8]) -> crate::Result<Vec<u8>> {
-        // Use ML-KEM-768 for decapsulation with actual cryptographic implementation
-        use pqcrypto_mlkem::mlkem768;
-        use pqcrypto_traits::kem::{Ciphertext, SecretKey, SharedSecret};
-
-        // Validate secret key size for ML-KEM-768
-        if secret_key.len() != mlkem768::secret_key_bytes() {
-            return Err(crate::PqCryptoError::InvalidKeySize {
-                expected: mlkem768::secret_key_bytes(),
-                actual: secret_key.len(),
-            });
-        }
+    /// Create ML-KEM-512 builder (NIST security level 1)
+    #[must_use]
+    pub fn ml_kem_512() -> MlKemBuilder<NeedKeyPair> {
+        MlKemBuilder::new(KemAlgorithm::MlKem512)
+    }
 
-        // Validate ciphertext size for ML-KEM-768
-        if ciphertext.len() != mlkem768::ciphertext_bytes() {
-            return Err(crate::PqCryptoError::InvalidParameters(format!(
-                "Invalid ML-KEM-768 ciphertext size: expected {}, got {}",
-                mlkem768::ciphertext_bytes(),
-                ciphertext.len()
-            )));
-        }
+    /// Create ML-KEM-768 builder (NIST security level 3)
+    #[must_use]
+    pub fn ml_kem_768() -> MlKemBuilder<NeedKeyPair> {
+        MlKemBuilder::new(KemAlgorithm::MlKem768)
+    }
 
-        // Convert bytes to secret key and ciphertext types
-        let sk = mlkem768::SecretKey::from_bytes(secret_key).map_err(|_| {
-            crate::PqCryptoError::InvalidKey("Invalid ML-KEM secret key format".to_string())
-        })?;
+    /// Create ML-KEM-1024 builder (NIST security level 5)
+    #[must_use]
+    pub fn ml_kem_1024() -> MlKemBuilder<NeedKeyPair> {
+        MlKemBuilder::new(KemAlgorithm::MlKem1024)
+    }
 
-        let ct = mlkem768::Ciphertext::from_bytes(ciphertext).map_err(|_| {
-            crate::PqCryptoError::InvalidParameters("Invalid ML-KEM ciphertext format".to_string())
-        })?;
+    /// Create X25519 + ML-KEM-768 hybrid builder (X-Wing)
+    #[must_use]
+    pub fn x25519_ml_kem_768() -> MlKemBuilder<NeedKeyPair> {
+        MlKemBuilder::new(KemAlgorithm::X25519MlKem768)
+    }
 
-        // Perform decapsulation
-        let shared_secret = mlkem768::decapsulate(&ct, &sk);
-        Ok(shared_secret.as_bytes().to_vec())
+    /// Create P-256 + ML-KEM-768 hybrid builder (cryypt-specific combiner)
+    #[must_use]
+    pub fn p256_ml_kem_768() -> MlKemBuilder<NeedKeyPair> {
+        MlKemBuilder::new(KemAlgorithm::P256MlKem768)
+    }
+
+    /// Create P-384 + ML-KEM-1024 hybrid builder (cryypt-specific combiner)
+    #[must_use]
+    pub fn p384_ml_kem_1024() -> MlKemBuilder<NeedKeyPair> {
+        MlKemBuilder::new(KemAlgorithm::P384MlKem1024)
     }
 }
 
 impl<F, T> KemBuilderWithHandler<F, T>
 where
-    F: FnOnce(crate::Result<(Vec<u8>, Vec<u8>)>) -> T + Send + 'static,
+    F: FnOnce(Result<(KemPublicKey, KemSecretKey)>) -> T + Send + 'static,
     T: Send + 'static,
 {
     /// Generate keypair and apply result handler
     pub async fn generate_keypair(self) -> T {
         let handler = self.result_handler;
-
-        // Generate real ML-KEM keypair using production cryptography
         let result = async {
-            // Use ML-KEM-768 as default security level for keypair generation
-            use pqcrypto_mlkem::mlkem768;
-            use pqcrypto_traits::kem::{PublicKey, SecretKey};
-
-            let (public_key, secret_key) = mlkem768::keypair();
-            Ok((
-                public_key.as_bytes().to_vec(),
-                secret_key.as_bytes().to_vec(),
-            ))
+            let keypair = MlKemBuilder::new(self.algorithm).generate().await?;
+            keypair.into_keys()
         }
         .await;
 
         handler(result)
     }
+}
 
-    /// Encapsulate and apply result handler
-    pub async fn encapsulate(self, public_key: Vec<u8>) -> T {
-        let handler = self.result_handler;
-
-        // Perform real ML-KEM encapsulation using production cryptography
-        let result = async {
-            // Use ML-KEM-768 for encapsulation with actual cryptographic implementation
-            use pqcrypto_mlkem::mlkem768;
-            use pqcrypto_traits::kem::{Ciphertext, PublicKey, SharedSecret};
-
-            // Validate public key size for ML-KEM-768
-            if public_key.len() != mlkem768::public_key_bytes() {
-                return Err(crate::PqCryptoError::InvalidKeySize {
-                    expected: mlkem768::public_key_bytes(),
-                    actual: public_key.len(),
-                });
-            }
-
-            // Convert bytes to public key type
-            let pk = mlkem768::PublicKey::from_bytes(&public_key).map_err(|_| {
-                crate::PqCryptoError::InvalidKey("Invalid ML-KEM public key format".to_string())
-            })?;
-
-            // Perform encapsulation
-            let (shared_secret, ciphertext) = mlkem768::encapsulate(&pk);
-            Ok((
-                ciphertext.as_bytes().to_vec(),
-                shared_secret.as_bytes().to_vec(),
-            ))
+impl KemBuilderWithPublicKey {
+    /// Add `on_result` handler for encapsulation - `README.md` pattern
+    pub fn on_result<F, T>(self, handler: F) -> KemBuilderWithEncapHandler<F, T>
+    where
+        F: FnOnce(Result<EncapsulationResult>) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        KemBuilderWithEncapHandler {
+            public_key: self.public_key,
+            result_handler: handler,
+            _phantom: PhantomData,
         }
-        .await;
-
-        handler(result)
     }
+}
 
-    /// Create a new ML-KEM builder with the specified security level
-    pub fn ml_kem(security_level: u16) -> Result<MlKemBuilder<NeedKeyPair>> {
-        let algorithm = match security_level {
-            512 => KemAlgorithm::MlKem512,
-            768 => KemAlgorithm::MlKem768,
-            1024 => KemAlgorithm::MlKem1024,
-            _ => {
-                return Err(PqCryptoError::UnsupportedAlgorithm(format!(
-                    "ML-KEM-{security_level} is not supported. Use 512, 768, or 1024"
-                )));
-            }
-        };
-
-        Ok(MlKemBuilder {
-            algorithm,
+impl<F, T> KemBuilderWithEncapHandler<F, T>
+where
+    F: FnOnce(Result<EncapsulationResult>) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    /// Encapsulate a fresh shared secret to the public key and apply result handler
+    pub async fn encapsulate(self) -> T {
+        let handler = self.result_handler;
+        let builder = MlKemBuilder::<HasPublicKey> {
+            algorithm: self.public_key.algorithm(),
             state: PhantomData,
-            public_key: None,
+            public_key: Some(self.public_key.to_vec()),
             secret_key: None,
             ciphertext: None,
-        })
+        };
+
+        handler(builder.encapsulate().await)
     }
+}
 
-    /// Create ML-KEM-512 builder (NIST security level 1)
-    pub fn ml_kem_512() -> MlKemBuilder<NeedKeyPair> {
-        MlKemBuilder {
-            algorithm: KemAlgorithm::MlKem512,
-            state: PhantomData,
-            public_key: None,
-            secret_key: None,
-            ciphertext: None,
+impl KemBuilderWithSecretKey {
+    /// Set the ciphertext to decapsulate
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidCiphertext` if the ciphertext is not the secret key's
+    /// algorithm's ciphertext size.
+    pub fn with_ciphertext<C: Into<Vec<u8>>>(
+        self,
+        ciphertext: C,
+    ) -> Result<KemBuilderWithCiphertext> {
+        let ciphertext = ciphertext.into();
+        let algorithm = self.secret_key.algorithm();
+        if ciphertext.len() != algorithm.ciphertext_size() {
+            return Err(PqCryptoError::InvalidCiphertext(format!(
+                "{algorithm} ciphertext must be {} bytes, got {}",
+                algorithm.ciphertext_size(),
+                ciphertext.len()
+            )));
         }
+        Ok(KemBuilderWithCiphertext {
+            secret_key: self.secret_key,
+            ciphertext,
+        })
     }
+}
 
-    /// Create ML-KEM-768 builder (NIST security level 3)
-    pub fn ml_kem_768() -> MlKemBuilder<NeedKeyPair> {
-        MlKemBuilder {
-            algorithm: KemAlgorithm::MlKem768,
-            state: PhantomData,
-            public_key: None,
-            secret_key: None,
-            ciphertext: None,
+impl KemBuilderWithCiphertext {
+    /// Add `on_result` handler for decapsulation - `README.md` pattern
+    pub fn on_result<F, T>(self, handler: F) -> KemBuilderWithDecapHandler<F, T>
+    where
+        F: FnOnce(Result<SharedSecret>) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        KemBuilderWithDecapHandler {
+            secret_key: self.secret_key,
+            ciphertext: self.ciphertext,
+            result_handler: handler,
+            _phantom: PhantomData,
         }
     }
+}
 
-    /// Create ML-KEM-1024 builder (NIST security level 5)
-    pub fn ml_kem_1024() -> MlKemBuilder<NeedKeyPair> {
-        MlKemBuilder {
-            algorithm: KemAlgorithm::MlKem1024,
+impl<F, T> KemBuilderWithDecapHandler<F, T>
+where
+    F: FnOnce(Result<SharedSecret>) -> T + Send + 'static,
+    T: Send + 'static,
+{
+    /// Decapsulate the ciphertext with the secret key and apply result handler
+    pub async fn decapsulate(self) -> T {
+        let handler = self.result_handler;
+        let builder = MlKemBuilder::<HasCiphertext> {
+            algorithm: self.secret_key.algorithm(),
             state: PhantomData,
             public_key: None,
-            secret_key: None,
-            ciphertext: None,
-        }
+            secret_key: Some(Zeroizing::new(self.secret_key.as_bytes().to_vec())),
+            ciphertext: Some(self.ciphertext),
+        };
+
+        handler(
+            builder
+                .decapsulate()
+                .await
+                .map(DecapsulationResult::into_shared_secret),
+        )
     }
 }
 
 /// ML-KEM builder type with type-state pattern
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct MlKemBuilder<State> {
     pub(crate) algorithm: KemAlgorithm,
     pub(crate) state: PhantomData<State>,
     pub(crate) public_key: Option<Vec<u8>>,
-    pub(crate) secret_key: Option<Vec<u8>>,
+    pub(crate) secret_key: Option<Zeroizing<Vec<u8>>>,
     pub(crate) ciphertext: Option<Vec<u8>>,
 }
 
-impl<State> MlKemBuilder<State> {
-    /// Validate public key size for the algorithm
-    pub(crate) fn validate_public_key(&self, key: &[u8]) -> Result<()> {
-        let expected = self.algorithm.public_key_size();
-        if key.len() != expected {
-            return Err(PqCryptoError::InvalidKeySize {
-                expected,
-                actual: key.len(),
-            });
-        }
-        Ok(())
-    }
-
-    /// Validate secret key size for the algorithm
-    pub(crate) fn validate_secret_key(&self, key: &[u8]) -> Result<()> {
-        let expected = self.algorithm.secret_key_size();
-        if key.len() != expected {
-            return Err(PqCryptoError::InvalidKeySize {
-                expected,
-                actual: key.len(),
-            });
-        }
-        Ok(())
+impl<State> fmt::Debug for MlKemBuilder<State> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MlKemBuilder")
+            .field("algorithm", &self.algorithm)
+            .field("public_key", &self.public_key)
+            .field("ciphertext", &self.ciphertext)
+            .finish_non_exhaustive()
     }
+}
 
+impl<State> MlKemBuilder<State> {
     /// Get the algorithm used by this builder
     #[must_use]
     pub fn algorithm(&self) -> KemAlgorithm {
@@ -333,7 +389,26 @@ impl MlKemBuilder<HasKeyPair> {
     /// Returns an error if the secret key is not available in the current state.
     pub fn secret_key_vec(&self) -> Result<Vec<u8>> {
         self.secret_key
-            .clone()
+            .as_deref()
+            .map(<[u8]>::to_vec)
             .ok_or_else(|| PqCryptoError::internal("Secret key not available in HasKeyPair state"))
     }
+
+    /// Take the key pair as typed keys
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either key is missing or not the algorithm's key size.
+    pub fn into_keys(self) -> Result<(KemPublicKey, KemSecretKey)> {
+        let public_key = self.public_key.ok_or_else(|| {
+            PqCryptoError::internal("Public key not available in HasKeyPair state")
+        })?;
+        let mut secret_key = self.secret_key.ok_or_else(|| {
+            PqCryptoError::internal("Secret key not available in HasKeyPair state")
+        })?;
+        Ok((
+            KemPublicKey::new(self.algorithm, public_key)?,
+            KemSecretKey::new(self.algorithm, std::mem::take(&mut *secret_key))?,
+        ))
+    }
 }
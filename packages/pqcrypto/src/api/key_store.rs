@@ -0,0 +1,113 @@
+//! ML-KEM and ML-DSA key pairs in `cryypt_key` stores
+//!
+//! Key pairs are kept as [`StoredKeyPair`]s, so a [`FileKeyStore`](cryypt_key::FileKeyStore)
+//! or [`KeychainStore`](cryypt_key::store::KeychainStore) holds them next to symmetric and
+//! classical keys. [`PqCryptoMasterBuilder`](super::PqCryptoMasterBuilder) loads them back
+//! into ready builders.
+
+use super::kem_builder::MlKemBuilder;
+use super::signature_builder::MlDsaBuilder;
+use super::states::HasKeyPair;
+use crate::{KemAlgorithm, PqCryptoError, Result, SignatureAlgorithm};
+use cryypt_key::traits::KeyImport;
+use cryypt_key::{KeyId, StoredKeyKind, StoredKeyPair};
+
+impl TryFrom<KemAlgorithm> for StoredKeyKind {
+    type Error = PqCryptoError;
+
+    fn try_from(algorithm: KemAlgorithm) -> Result<Self> {
+        match algorithm {
+            KemAlgorithm::MlKem512 => Ok(Self::MlKem512),
+            KemAlgorithm::MlKem768 => Ok(Self::MlKem768),
+            KemAlgorithm::MlKem1024 => Ok(Self::MlKem1024),
+            other => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{other} key pairs cannot be kept in a key store"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<StoredKeyKind> for KemAlgorithm {
+    type Error = PqCryptoError;
+
+    fn try_from(kind: StoredKeyKind) -> Result<Self> {
+        match kind {
+            StoredKeyKind::MlKem512 => Ok(Self::MlKem512),
+            StoredKeyKind::MlKem768 => Ok(Self::MlKem768),
+            StoredKeyKind::MlKem1024 => Ok(Self::MlKem1024),
+            other => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{other} is not an ML-KEM key"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<SignatureAlgorithm> for StoredKeyKind {
+    type Error = PqCryptoError;
+
+    fn try_from(algorithm: SignatureAlgorithm) -> Result<Self> {
+        match algorithm {
+            SignatureAlgorithm::MlDsa44 => Ok(Self::MlDsa44),
+            SignatureAlgorithm::MlDsa65 => Ok(Self::MlDsa65),
+            SignatureAlgorithm::MlDsa87 => Ok(Self::MlDsa87),
+            other => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{other} key pairs cannot be kept in a key store"
+            ))),
+        }
+    }
+}
+
+impl TryFrom<StoredKeyKind> for SignatureAlgorithm {
+    type Error = PqCryptoError;
+
+    fn try_from(kind: StoredKeyKind) -> Result<Self> {
+        match kind {
+            StoredKeyKind::MlDsa44 => Ok(Self::MlDsa44),
+            StoredKeyKind::MlDsa65 => Ok(Self::MlDsa65),
+            StoredKeyKind::MlDsa87 => Ok(Self::MlDsa87),
+            other => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{other} is not an ML-DSA key"
+            ))),
+        }
+    }
+}
+
+impl MlKemBuilder<HasKeyPair> {
+    /// Write the key pair to `store` under `key_id`
+    ///
+    /// Load it again with `PqCryptoMasterBuilder::ml_kem_from_store`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` for the hybrid KEMs, or an error if the
+    /// store rejects the key.
+    pub async fn save<S: KeyImport>(&self, store: &S, key_id: &dyn KeyId) -> Result<()> {
+        let kind = StoredKeyKind::try_from(self.algorithm)?;
+        save_pair(kind, self.public_key()?, self.secret_key()?, store, key_id).await
+    }
+}
+
+impl MlDsaBuilder<HasKeyPair> {
+    /// Write the key pair to `store` under `key_id`
+    ///
+    /// Load it again with `PqCryptoMasterBuilder::ml_dsa_from_store`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store rejects the key.
+    pub async fn save<S: KeyImport>(&self, store: &S, key_id: &dyn KeyId) -> Result<()> {
+        let kind = StoredKeyKind::try_from(self.algorithm)?;
+        save_pair(kind, self.public_key()?, self.secret_key()?, store, key_id).await
+    }
+}
+
+async fn save_pair<S: KeyImport>(
+    kind: StoredKeyKind,
+    public_key: &[u8],
+    secret_key: &[u8],
+    store: &S,
+    key_id: &dyn KeyId,
+) -> Result<()> {
+    let pair = StoredKeyPair::new(kind, public_key, secret_key)?;
+    Ok(pair.save(store, key_id).await?)
+}
@@ -2,7 +2,8 @@
 
 mod builder_traits;
 mod dilithium_builder;
-mod kem_builder;
+pub(crate) mod kem_builder;
+mod key_store;
 mod kyber_builder;
 mod pqcrypto_master_builder;
 mod signature_builder;
@@ -13,19 +14,22 @@ pub use self::builder_traits::{
     AsyncDecapsulationResult, AsyncEncapsulationResult, AsyncSignatureResult,
     AsyncVerificationResult, CiphertextBuilder, DecapsulateBuilder, EncapsulateBuilder,
     KemKeyPairBuilder, MessageBuilder, SignBuilder, SignatureDataBuilder, SignatureKeyPairBuilder,
-    VerifyBuilder,
+    StreamSignBuilder, StreamVerifyBuilder, VerifyBuilder,
 };
 
 pub use self::kem_builder::{
-    KemBuilder, MlKemBuilder, MlKemWithCiphertext, MlKemWithKeyPair, MlKemWithPublicKey,
-    MlKemWithSecretKey,
+    KemBuilder, KemBuilderWithCiphertext, KemBuilderWithDecapHandler, KemBuilderWithEncapHandler,
+    KemBuilderWithHandler, KemBuilderWithPublicKey, KemBuilderWithSecretKey, MlKemBuilder,
+    MlKemWithCiphertext, MlKemWithKeyPair, MlKemWithPublicKey, MlKemWithSecretKey,
 };
 
 pub use self::signature_builder::{
-    FalconBuilder, FalconWithKeyPair, FalconWithMessage, FalconWithPublicKey, FalconWithSecretKey,
-    FalconWithSignature, MlDsaBuilder, MlDsaWithKeyPair, MlDsaWithMessage, MlDsaWithPublicKey,
-    MlDsaWithSecretKey, MlDsaWithSignature, SignatureBuilder, SphincsBuilder, SphincsWithKeyPair,
-    SphincsWithMessage, SphincsWithPublicKey, SphincsWithSecretKey, SphincsWithSignature,
+    CompositeBuilder, CompositeWithKeyPair, CompositeWithMessage, CompositeWithPublicKey,
+    CompositeWithSecretKey, CompositeWithSignature, FalconBuilder, FalconWithKeyPair,
+    FalconWithMessage, FalconWithPublicKey, FalconWithSecretKey, FalconWithSignature, MlDsaBuilder,
+    MlDsaWithKeyPair, MlDsaWithMessage, MlDsaWithPublicKey, MlDsaWithSecretKey, MlDsaWithSignature,
+    SignatureBatch, SignatureBuilder, SphincsBuilder, SphincsWithKeyPair, SphincsWithMessage,
+    SphincsWithPublicKey, SphincsWithSecretKey, SphincsWithSignature,
 };
 
 pub use self::states::{
@@ -1,7 +1,14 @@
 //! `PQCrypto` master builder for polymorphic API
 
+use super::builder_traits::{KemKeyPairBuilder, SignatureKeyPairBuilder};
 use super::dilithium_builder::DilithiumBuilder;
+use super::kem_builder::MlKemBuilder;
 use super::kyber_builder::KyberBuilder;
+use super::signature_builder::MlDsaBuilder;
+use super::states::HasKeyPair;
+use crate::{KemAlgorithm, Result, SignatureAlgorithm};
+use cryypt_key::traits::KeyRetrieval;
+use cryypt_key::{KeyId, StoredKeyPair};
 
 /// Master builder for post-quantum cryptography operations
 pub struct PqCryptoMasterBuilder;
@@ -30,4 +37,44 @@ impl PqCryptoMasterBuilder {
     pub fn dilithium(self) -> DilithiumBuilder<super::dilithium_builder::NoSecurityLevel> {
         DilithiumBuilder::new()
     }
+
+    /// Load an ML-KEM key pair saved with [`MlKemBuilder::save`] from a key store such as
+    /// [`FileKeyStore`](cryypt_key::FileKeyStore) or
+    /// [`KeychainStore`](cryypt_key::store::KeychainStore)
+    ///
+    /// The parameter set is the one recorded with the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is missing or malformed, and
+    /// `PqCryptoError::UnsupportedAlgorithm` if `key_id` holds a key pair that is not ML-KEM.
+    pub async fn ml_kem_from_store<S: KeyRetrieval>(
+        self,
+        store: &S,
+        key_id: &dyn KeyId,
+    ) -> Result<MlKemBuilder<HasKeyPair>> {
+        let pair = StoredKeyPair::load_any(store, key_id).await?;
+        let algorithm = KemAlgorithm::try_from(pair.kind())?;
+        MlKemBuilder::new(algorithm).with_keypair(pair.public_key(), pair.secret_key())
+    }
+
+    /// Load an ML-DSA key pair saved with [`MlDsaBuilder::save`] from a key store such as
+    /// [`FileKeyStore`](cryypt_key::FileKeyStore) or
+    /// [`KeychainStore`](cryypt_key::store::KeychainStore)
+    ///
+    /// The parameter set is the one recorded with the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is missing or malformed, and
+    /// `PqCryptoError::UnsupportedAlgorithm` if `key_id` holds a key pair that is not ML-DSA.
+    pub async fn ml_dsa_from_store<S: KeyRetrieval>(
+        self,
+        store: &S,
+        key_id: &dyn KeyId,
+    ) -> Result<MlDsaBuilder<HasKeyPair>> {
+        let pair = StoredKeyPair::load_any(store, key_id).await?;
+        let algorithm = SignatureAlgorithm::try_from(pair.kind())?;
+        MlDsaBuilder::new(algorithm)?.with_keypair(pair.public_key(), pair.secret_key())
+    }
 }
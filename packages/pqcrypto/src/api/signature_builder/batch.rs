@@ -0,0 +1,199 @@
+//! Batch signing and verification over a bounded pool of blocking workers
+
+use super::core::SignatureBuilder;
+use crate::backend::{Backend, SigningOptions};
+use crate::result::{SignatureResult, VerificationResult};
+use crate::{PqCryptoError, Result, SignatureAlgorithm};
+use std::any::Any;
+use std::num::NonZeroUsize;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use zeroize::Zeroizing;
+
+/// Signs or verifies many messages with one ML-DSA, FALCON or SPHINCS+ algorithm in parallel
+///
+/// Items are split into at most `workers` chunks, each run on a `spawn_blocking` thread.
+/// The bound holds across concurrent calls: clones share one pool of `workers` permits.
+/// Results come back in input order, one per item, so a malformed key or signature, or a
+/// panic while handling it, only fails its own entry; a well-formed but wrong signature
+/// verifies as invalid.
+#[derive(Debug, Clone)]
+pub struct SignatureBatch {
+    algorithm: SignatureAlgorithm,
+    backend: Backend,
+    options: SigningOptions,
+    workers: NonZeroUsize,
+    permits: Arc<Semaphore>,
+}
+
+impl SignatureBuilder {
+    /// Create a batch signer and verifier for `algorithm`
+    #[must_use]
+    pub fn batch(algorithm: SignatureAlgorithm) -> SignatureBatch {
+        SignatureBatch::new(algorithm)
+    }
+}
+
+impl SignatureBatch {
+    /// Use the `PQClean` backend, default signing options and one worker per available core
+    #[must_use]
+    pub fn new(algorithm: SignatureAlgorithm) -> Self {
+        let workers = std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+        Self {
+            algorithm,
+            backend: Backend::PqClean,
+            options: SigningOptions::new(),
+            workers,
+            permits: Arc::new(Semaphore::new(workers.get())),
+        }
+    }
+
+    /// Sign and verify with `backend`
+    #[must_use]
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Apply `options` to every item
+    #[must_use]
+    pub fn with_options(mut self, options: SigningOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Run at most `workers` blocking tasks at once; zero is treated as one
+    ///
+    /// The returned batch and its clones share a new pool, separate from this one's.
+    #[must_use]
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = NonZeroUsize::new(workers).unwrap_or(NonZeroUsize::MIN);
+        self.permits = Arc::new(Semaphore::new(self.workers.get()));
+        self
+    }
+
+    /// Signature algorithm of every item
+    #[must_use]
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    /// Maximum number of concurrent workers
+    #[must_use]
+    pub fn workers(&self) -> usize {
+        self.workers.get()
+    }
+
+    /// Verify each `(public_key, message, signature)`
+    ///
+    /// Entry `i` of the result is `Ok` with the verdict for item `i`, or the error that kept
+    /// it from being checked, such as a malformed public key.
+    pub async fn verify_batch<I, P, M, S>(&self, items: I) -> Vec<Result<VerificationResult>>
+    where
+        I: IntoIterator<Item = (P, M, S)>,
+        P: Into<Vec<u8>>,
+        M: Into<Vec<u8>>,
+        S: Into<Vec<u8>>,
+    {
+        let items: Vec<(Vec<u8>, Vec<u8>, Vec<u8>)> = items
+            .into_iter()
+            .map(|(public_key, message, signature)| {
+                (public_key.into(), message.into(), signature.into())
+            })
+            .collect();
+        let (algorithm, backend, options) = (self.algorithm, self.backend, self.options.clone());
+        self.run(items, move |(public_key, message, signature)| {
+            backend
+                .verify(algorithm, &public_key, &message, &signature, &options)
+                .map(|is_valid| VerificationResult::new(algorithm, is_valid, None))
+        })
+        .await
+    }
+
+    /// Sign each message with `secret_key`
+    ///
+    /// Entry `i` of the result is the signature over message `i`, or the error that kept it
+    /// from being signed.
+    pub async fn sign_batch<I, M>(
+        &self,
+        secret_key: impl Into<Vec<u8>>,
+        messages: I,
+    ) -> Vec<Result<SignatureResult>>
+    where
+        I: IntoIterator<Item = M>,
+        M: Into<Vec<u8>>,
+    {
+        let messages: Vec<Vec<u8>> = messages.into_iter().map(Into::into).collect();
+        let secret_key = Zeroizing::new(secret_key.into());
+        let (algorithm, backend, options) = (self.algorithm, self.backend, self.options.clone());
+        self.run(messages, move |message| {
+            backend
+                .sign(algorithm, &secret_key, &message, &options)
+                .map(|signature| SignatureResult::new(algorithm, signature, None))
+        })
+        .await
+    }
+
+    /// Apply `work` to every item on up to `workers` blocking tasks, keeping input order
+    async fn run<T, R, F>(&self, items: Vec<T>, work: F) -> Vec<Result<R>>
+    where
+        T: Send + 'static,
+        R: Send + 'static,
+        F: Fn(T) -> Result<R> + Send + Sync + 'static,
+    {
+        let total = items.len();
+        let chunk_len = total.div_ceil(self.workers.get()).max(1);
+        let work = Arc::new(work);
+
+        let mut items = items.into_iter();
+        let mut tasks = Vec::with_capacity(self.workers.get());
+        loop {
+            let chunk: Vec<T> = items.by_ref().take(chunk_len).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            let len = chunk.len();
+            let work = Arc::clone(&work);
+            let permits = Arc::clone(&self.permits);
+            let task = tokio::spawn(async move {
+                // The pool is never closed, so acquiring only waits
+                let permit = permits.acquire_owned().await.ok();
+                tokio::task::spawn_blocking(move || {
+                    let _permit = permit;
+                    chunk
+                        .into_iter()
+                        .map(|item| {
+                            catch_unwind(AssertUnwindSafe(|| work(item)))
+                                .unwrap_or_else(|panic| Err(worker_panicked(&*panic)))
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await
+            });
+            tasks.push((len, task));
+        }
+
+        let mut results = Vec::with_capacity(total);
+        for (len, task) in tasks {
+            match task.await {
+                Ok(Ok(chunk)) => results.extend(chunk),
+                Ok(Err(e)) | Err(e) => results.extend((0..len).map(|_| {
+                    Err(PqCryptoError::internal(format!(
+                        "Batch signature worker failed: {e}"
+                    )))
+                })),
+            }
+        }
+        results
+    }
+}
+
+fn worker_panicked(panic: &(dyn Any + Send)) -> PqCryptoError {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+    PqCryptoError::internal(format!("Batch signature item panicked: {message}"))
+}
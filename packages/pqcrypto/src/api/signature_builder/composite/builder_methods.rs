@@ -0,0 +1,40 @@
+//! Composite builder pattern methods for message and signature data
+
+use super::super::super::{
+    builder_traits::{MessageBuilder, SignatureDataBuilder},
+    states::{HasMessage, HasSignature},
+};
+use super::types::CompositeBuilder;
+use std::marker::PhantomData;
+
+// Message builder implementations for composite signatures
+impl<State> MessageBuilder for CompositeBuilder<State> {
+    type Output = CompositeBuilder<HasMessage>;
+
+    fn with_message<T: Into<Vec<u8>>>(self, message: T) -> Self::Output {
+        CompositeBuilder {
+            algorithm: self.algorithm,
+            state: PhantomData,
+            public_key: self.public_key,
+            secret_key: self.secret_key,
+            message: Some(message.into()),
+            signature: self.signature,
+        }
+    }
+}
+
+// Signature data builder implementations for composite signatures
+impl<State> SignatureDataBuilder for CompositeBuilder<State> {
+    type Output = CompositeBuilder<HasSignature>;
+
+    fn with_signature<T: Into<Vec<u8>>>(self, signature: T) -> Self::Output {
+        CompositeBuilder {
+            algorithm: self.algorithm,
+            state: PhantomData,
+            public_key: self.public_key,
+            secret_key: self.secret_key,
+            message: self.message,
+            signature: Some(signature.into()),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Classical components of composite signatures
+//!
+//! Ed25519 secret keys are the 32-byte RFC 8032 seed; ECDSA secret keys are RFC 5915
+//! `ECPrivateKey` DER and public keys uncompressed SEC1 points. ECDSA signatures are DER
+//! `Ecdsa-Sig-Value`s over the curve's own hash (SHA-256 for P-256, SHA-384 for P-384).
+
+use crate::algorithm::ClassicalAlgorithm;
+use crate::{PqCryptoError, Result};
+use p256::elliptic_curve::rand_core::OsRng;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+/// Draw a random secret key
+pub(super) fn generate(algorithm: ClassicalAlgorithm) -> Result<Zeroizing<Vec<u8>>> {
+    match algorithm {
+        ClassicalAlgorithm::Ed25519 => {
+            let mut seed = Zeroizing::new(vec![0u8; 32]);
+            rand::rng().fill_bytes(&mut seed);
+            Ok(seed)
+        }
+        ClassicalAlgorithm::EcdsaP256 => p256::SecretKey::random(&mut OsRng)
+            .to_sec1_der()
+            .map_err(PqCryptoError::serialization_error),
+        ClassicalAlgorithm::EcdsaP384 => p384::SecretKey::random(&mut OsRng)
+            .to_sec1_der()
+            .map_err(PqCryptoError::serialization_error),
+    }
+}
+
+/// Public key of a secret key
+pub(super) fn public_key(algorithm: ClassicalAlgorithm, secret: &[u8]) -> Result<Vec<u8>> {
+    Ok(match algorithm {
+        ClassicalAlgorithm::Ed25519 => ed25519_signing_key(secret)?
+            .verifying_key()
+            .to_bytes()
+            .to_vec(),
+        ClassicalAlgorithm::EcdsaP256 => p256_secret(secret)?
+            .public_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec(),
+        ClassicalAlgorithm::EcdsaP384 => p384_secret(secret)?
+            .public_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec(),
+    })
+}
+
+pub(super) fn sign(
+    algorithm: ClassicalAlgorithm,
+    secret: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    Ok(match algorithm {
+        ClassicalAlgorithm::Ed25519 => {
+            use ed25519_dalek::Signer;
+            ed25519_signing_key(secret)?
+                .sign(message)
+                .to_bytes()
+                .to_vec()
+        }
+        ClassicalAlgorithm::EcdsaP256 => {
+            use p256::ecdsa::signature::Signer;
+            let key = p256::ecdsa::SigningKey::from(p256_secret(secret)?);
+            let signature: p256::ecdsa::Signature = key.sign(message);
+            signature.to_der().as_bytes().to_vec()
+        }
+        ClassicalAlgorithm::EcdsaP384 => {
+            use p384::ecdsa::signature::Signer;
+            let key = p384::ecdsa::SigningKey::from(p384_secret(secret)?);
+            let signature: p384::ecdsa::Signature = key.sign(message);
+            signature.to_der().as_bytes().to_vec()
+        }
+    })
+}
+
+/// Verify a signature; a malformed signature is reported as invalid
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidKey` for a malformed public key.
+pub(super) fn verify(
+    algorithm: ClassicalAlgorithm,
+    public: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool> {
+    let invalid_key = || PqCryptoError::InvalidKey(format!("Invalid {algorithm} public key"));
+    Ok(match algorithm {
+        ClassicalAlgorithm::Ed25519 => {
+            let public: [u8; 32] = public.try_into().map_err(|_| invalid_key())?;
+            let key =
+                ed25519_dalek::VerifyingKey::from_bytes(&public).map_err(|_| invalid_key())?;
+            ed25519_dalek::Signature::from_slice(signature)
+                .is_ok_and(|signature| key.verify_strict(message, &signature).is_ok())
+        }
+        ClassicalAlgorithm::EcdsaP256 => {
+            use p256::ecdsa::signature::Verifier;
+            let key =
+                p256::ecdsa::VerifyingKey::from_sec1_bytes(public).map_err(|_| invalid_key())?;
+            p256::ecdsa::Signature::from_der(signature)
+                .is_ok_and(|signature| key.verify(message, &signature).is_ok())
+        }
+        ClassicalAlgorithm::EcdsaP384 => {
+            use p384::ecdsa::signature::Verifier;
+            let key =
+                p384::ecdsa::VerifyingKey::from_sec1_bytes(public).map_err(|_| invalid_key())?;
+            p384::ecdsa::Signature::from_der(signature)
+                .is_ok_and(|signature| key.verify(message, &signature).is_ok())
+        }
+    })
+}
+
+fn ed25519_signing_key(secret: &[u8]) -> Result<ed25519_dalek::SigningKey> {
+    let seed: Zeroizing<[u8; 32]> =
+        Zeroizing::new(
+            secret
+                .try_into()
+                .map_err(|_| PqCryptoError::InvalidKeySize {
+                    expected: 32,
+                    actual: secret.len(),
+                })?,
+        );
+    Ok(ed25519_dalek::SigningKey::from_bytes(&seed))
+}
+
+fn p256_secret(secret: &[u8]) -> Result<p256::SecretKey> {
+    p256::SecretKey::from_sec1_der(secret)
+        .map_err(|_| PqCryptoError::InvalidKey("Invalid P-256 ECPrivateKey".to_string()))
+}
+
+fn p384_secret(secret: &[u8]) -> Result<p384::SecretKey> {
+    p384::SecretKey::from_sec1_der(secret)
+        .map_err(|_| PqCryptoError::InvalidKey("Invalid P-384 ECPrivateKey".to_string()))
+}
@@ -0,0 +1,129 @@
+//! Composite key, message and signature layouts
+//!
+//! Both components sign the message representative
+//!
+//! ```text
+//! M' = Prefix || Label || len(ctx) || ctx || PH(M)
+//! ```
+//!
+//! with ML-DSA using `Label` as its context string, so neither component verifies outside
+//! the composite. Keys and signatures are the ML-DSA part followed by the classical part.
+
+use super::classical;
+use crate::algorithm::CompositeAlgorithm;
+use crate::backend::{Backend, SigningOptions};
+use crate::{PqCryptoError, Result};
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+/// `Prefix` of every composite message representative
+const PREFIX: &[u8] = b"CompositeAlgorithmSignatures2025";
+
+/// Length of the ML-DSA seed at the start of a composite secret key
+const ML_DSA_SEED_LEN: usize = 32;
+
+/// Generate a key pair, returning `(public_key, secret_key)`
+pub(super) fn keypair(algorithm: CompositeAlgorithm) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    let mut seed = Zeroizing::new([0u8; ML_DSA_SEED_LEN]);
+    rand::rng().fill_bytes(seed.as_mut_slice());
+    let (ml_dsa_public, _) =
+        Backend::Native.signature_keypair_from_seed(algorithm.ml_dsa(), seed.as_slice())?;
+    let classical_secret = classical::generate(algorithm.classical())?;
+    let classical_public = classical::public_key(algorithm.classical(), &classical_secret)?;
+
+    Ok((
+        [ml_dsa_public, classical_public].concat(),
+        Zeroizing::new([seed.as_slice(), &classical_secret].concat()),
+    ))
+}
+
+pub(super) fn sign(
+    algorithm: CompositeAlgorithm,
+    secret_key: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    if secret_key.len() <= ML_DSA_SEED_LEN {
+        return Err(PqCryptoError::InvalidKeySize {
+            expected: algorithm.secret_key_size(),
+            actual: secret_key.len(),
+        });
+    }
+    let (seed, classical_secret) = secret_key.split_at(ML_DSA_SEED_LEN);
+    let (_, ml_dsa_secret) =
+        Backend::Native.signature_keypair_from_seed(algorithm.ml_dsa(), seed)?;
+    let representative = message_representative(algorithm, message);
+
+    let mut signature = Backend::Native.sign(
+        algorithm.ml_dsa(),
+        &ml_dsa_secret,
+        &representative,
+        &ml_dsa_options(algorithm),
+    )?;
+    signature.extend(classical::sign(
+        algorithm.classical(),
+        classical_secret,
+        &representative,
+    )?);
+    Ok(signature)
+}
+
+/// Verify both components; the signature is valid only if both are
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidKeySize` for a public key of the wrong length and
+/// `PqCryptoError::InvalidParameters` for a signature too short to hold both components.
+pub(super) fn verify(
+    algorithm: CompositeAlgorithm,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool> {
+    if public_key.len() != algorithm.public_key_size() {
+        return Err(PqCryptoError::InvalidKeySize {
+            expected: algorithm.public_key_size(),
+            actual: public_key.len(),
+        });
+    }
+    let ml_dsa_len = algorithm.ml_dsa().signature_size();
+    if signature.len() <= ml_dsa_len {
+        return Err(PqCryptoError::InvalidParameters(format!(
+            "{algorithm} signature is too short to hold both components"
+        )));
+    }
+    let (ml_dsa_public, classical_public) =
+        public_key.split_at(algorithm.ml_dsa().public_key_size());
+    let (ml_dsa_signature, classical_signature) = signature.split_at(ml_dsa_len);
+    let representative = message_representative(algorithm, message);
+
+    let ml_dsa_valid = Backend::Native.verify(
+        algorithm.ml_dsa(),
+        ml_dsa_public,
+        &representative,
+        ml_dsa_signature,
+        &ml_dsa_options(algorithm),
+    )?;
+    let classical_valid = classical::verify(
+        algorithm.classical(),
+        classical_public,
+        &representative,
+        classical_signature,
+    )?;
+    Ok(ml_dsa_valid && classical_valid)
+}
+
+/// `M'` for an empty application context
+fn message_representative(algorithm: CompositeAlgorithm, message: &[u8]) -> Vec<u8> {
+    let label = algorithm.label();
+    let digest = algorithm.pre_hash().digest(message);
+    let mut representative = Vec::with_capacity(PREFIX.len() + label.len() + 1 + digest.len());
+    representative.extend_from_slice(PREFIX);
+    representative.extend_from_slice(label);
+    representative.push(0);
+    representative.extend_from_slice(&digest);
+    representative
+}
+
+fn ml_dsa_options(algorithm: CompositeAlgorithm) -> SigningOptions {
+    SigningOptions::new().with_context(algorithm.label())
+}
@@ -0,0 +1,123 @@
+//! Composite key generation and management operations
+
+use super::super::super::{
+    builder_traits::SignatureKeyPairBuilder,
+    states::{HasKeyPair, HasPublicKey, HasSecretKey, NeedKeyPair},
+};
+use super::combiner;
+use super::types::CompositeBuilder;
+use crate::{PqCryptoError, Result, encoding};
+
+use std::marker::PhantomData;
+
+impl SignatureKeyPairBuilder for CompositeBuilder<NeedKeyPair> {
+    type Output = CompositeBuilder<HasKeyPair>;
+    type PublicKeyOutput = CompositeBuilder<HasPublicKey>;
+    type SecretKeyOutput = CompositeBuilder<HasSecretKey>;
+
+    async fn generate(self) -> Result<Self::Output> {
+        let (pk, sk) = combiner::keypair(self.algorithm)?;
+
+        Ok(CompositeBuilder {
+            algorithm: self.algorithm,
+            state: PhantomData,
+            public_key: Some(pk),
+            secret_key: Some(sk.to_vec()),
+            message: None,
+            signature: None,
+        })
+    }
+
+    fn with_keypair<T: Into<Vec<u8>>>(self, public_key: T, secret_key: T) -> Result<Self::Output> {
+        let (algorithm, pk, sk) =
+            encoding::import_keypair(self.algorithm, public_key.into(), secret_key.into())?;
+
+        Ok(CompositeBuilder {
+            algorithm,
+            state: PhantomData,
+            public_key: Some(pk),
+            secret_key: Some(sk),
+            message: None,
+            signature: None,
+        })
+    }
+
+    fn with_public_key<T: Into<Vec<u8>>>(
+        self,
+        public_key: T,
+    ) -> Result<CompositeBuilder<HasPublicKey>> {
+        let (algorithm, pk) = encoding::import_public_key(self.algorithm, public_key.into())?;
+
+        Ok(CompositeBuilder {
+            algorithm,
+            state: PhantomData,
+            public_key: Some(pk),
+            secret_key: None,
+            message: None,
+            signature: None,
+        })
+    }
+
+    fn with_secret_key<T: Into<Vec<u8>>>(
+        self,
+        secret_key: T,
+    ) -> Result<CompositeBuilder<HasSecretKey>> {
+        let (algorithm, sk) = encoding::import_secret_key(self.algorithm, secret_key.into())?;
+
+        Ok(CompositeBuilder {
+            algorithm,
+            state: PhantomData,
+            public_key: None,
+            secret_key: Some(sk),
+            message: None,
+            signature: None,
+        })
+    }
+}
+
+// Key access methods for composite HasKeyPair state
+impl CompositeBuilder<HasKeyPair> {
+    /// Get the public key bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the public key is not available in the current state.
+    pub fn public_key(&self) -> Result<&[u8]> {
+        self.public_key
+            .as_deref()
+            .ok_or_else(|| PqCryptoError::internal("Public key not available in HasKeyPair state"))
+    }
+
+    /// Get the secret key bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret key is not available in the current state.
+    pub fn secret_key(&self) -> Result<&[u8]> {
+        self.secret_key
+            .as_deref()
+            .ok_or_else(|| PqCryptoError::internal("Secret key not available in HasKeyPair state"))
+    }
+
+    /// Get the public key as a vector
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the public key is not available in the current state.
+    pub fn public_key_vec(&self) -> Result<Vec<u8>> {
+        self.public_key
+            .clone()
+            .ok_or_else(|| PqCryptoError::internal("Public key not available in HasKeyPair state"))
+    }
+
+    /// Get the secret key as a vector
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret key is not available in the current state.
+    pub fn secret_key_vec(&self) -> Result<Vec<u8>> {
+        self.secret_key
+            .clone()
+            .ok_or_else(|| PqCryptoError::internal("Secret key not available in HasKeyPair state"))
+    }
+}
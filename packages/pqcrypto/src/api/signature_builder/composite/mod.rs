@@ -0,0 +1,20 @@
+//! Composite ML-DSA + classical signatures (draft-ietf-lamps-pq-composite-sigs)
+//!
+//! A composite signature holds an ML-DSA signature and an Ed25519 or ECDSA signature over
+//! the same message, and verifies only if both do, so it stays secure while either
+//! algorithm does. Keys are the ML-DSA seed or public key followed by the classical key,
+//! and encode to SPKI and PKCS#8 under the draft's OIDs.
+
+pub mod builder_methods;
+mod classical;
+mod combiner;
+pub mod key_management;
+pub mod signing;
+pub mod types;
+pub mod verification;
+
+// Re-export main types and type aliases
+pub use types::{
+    CompositeBuilder, CompositeWithKeyPair, CompositeWithMessage, CompositeWithPublicKey,
+    CompositeWithSecretKey, CompositeWithSignature,
+};
@@ -0,0 +1,34 @@
+//! Composite signing
+
+use super::super::super::{
+    builder_traits::{AsyncSignatureResult, SignBuilder},
+    states::HasMessage,
+};
+use super::combiner;
+use super::types::CompositeBuilder;
+use crate::PqCryptoError;
+use crate::result::SignatureResult;
+
+// Sign builder implementation for composite signatures with message
+//
+// The result reports the ML-DSA component as its algorithm.
+impl SignBuilder for CompositeBuilder<HasMessage> {
+    fn sign(self) -> impl AsyncSignatureResult {
+        async move {
+            let secret_key = self.secret_key.ok_or_else(|| {
+                PqCryptoError::InvalidKey("Secret key required for signing".to_string())
+            })?;
+            let message = self
+                .message
+                .ok_or_else(|| PqCryptoError::InternalError("Message not set".to_string()))?;
+
+            let signature = combiner::sign(self.algorithm, &secret_key, &message)?;
+
+            Ok(SignatureResult::new(
+                self.algorithm.ml_dsa(),
+                signature,
+                None,
+            ))
+        }
+    }
+}
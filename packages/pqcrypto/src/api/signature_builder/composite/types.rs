@@ -0,0 +1,69 @@
+//! Composite signature type definitions and aliases
+
+use super::super::super::states::{
+    HasKeyPair, HasMessage, HasPublicKey, HasSecretKey, HasSignature, NeedKeyPair,
+};
+use super::super::SignatureBuilder;
+use crate::Result;
+use crate::algorithm::{ClassicalAlgorithm, CompositeAlgorithm, SignatureAlgorithm};
+use std::marker::PhantomData;
+
+/// Composite ML-DSA + classical signature builder
+pub struct CompositeBuilder<State> {
+    pub(crate) algorithm: CompositeAlgorithm,
+    pub(crate) state: PhantomData<State>,
+    pub(crate) public_key: Option<Vec<u8>>,
+    pub(crate) secret_key: Option<Vec<u8>>,
+    pub(crate) message: Option<Vec<u8>>,
+    pub(crate) signature: Option<Vec<u8>>,
+}
+
+impl CompositeBuilder<NeedKeyPair> {
+    /// Create a composite builder for the given algorithm
+    #[must_use]
+    pub fn new(algorithm: CompositeAlgorithm) -> Self {
+        Self {
+            algorithm,
+            state: PhantomData,
+            public_key: None,
+            secret_key: None,
+            message: None,
+            signature: None,
+        }
+    }
+}
+
+impl<State> CompositeBuilder<State> {
+    /// Get the composite algorithm
+    #[must_use]
+    pub fn algorithm(&self) -> CompositeAlgorithm {
+        self.algorithm
+    }
+}
+
+impl SignatureBuilder {
+    /// Create a composite builder signing with both `ml_dsa` and `classical`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if no composite algorithm pairs the two.
+    pub fn composite(
+        ml_dsa: SignatureAlgorithm,
+        classical: ClassicalAlgorithm,
+    ) -> Result<CompositeBuilder<NeedKeyPair>> {
+        Ok(CompositeBuilder::new(CompositeAlgorithm::new(
+            ml_dsa, classical,
+        )?))
+    }
+}
+
+/// Composite builder with a complete key pair (public and secret keys)
+pub type CompositeWithKeyPair = CompositeBuilder<HasKeyPair>;
+/// Composite builder with only the secret key for signing
+pub type CompositeWithSecretKey = CompositeBuilder<HasSecretKey>;
+/// Composite builder with only the public key for verification
+pub type CompositeWithPublicKey = CompositeBuilder<HasPublicKey>;
+/// Composite builder with message ready for signing
+pub type CompositeWithMessage = CompositeBuilder<HasMessage>;
+/// Composite builder with signature ready for verification
+pub type CompositeWithSignature = CompositeBuilder<HasSignature>;
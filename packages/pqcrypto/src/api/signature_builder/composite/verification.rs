@@ -0,0 +1,35 @@
+//! Composite verification
+
+use super::super::super::{
+    builder_traits::{AsyncVerificationResult, VerifyBuilder},
+    states::HasSignature,
+};
+use super::combiner;
+use super::types::CompositeBuilder;
+use crate::PqCryptoError;
+use crate::result::VerificationResult;
+
+// Verify builder implementation for composite signatures; both components must verify
+impl VerifyBuilder for CompositeBuilder<HasSignature> {
+    fn verify(self) -> impl AsyncVerificationResult {
+        async move {
+            let public_key = self.public_key.ok_or_else(|| {
+                PqCryptoError::InvalidKey("Public key required for verification".to_string())
+            })?;
+            let message = self.message.ok_or_else(|| {
+                PqCryptoError::InvalidParameters("Message required for verification".to_string())
+            })?;
+            let signature = self
+                .signature
+                .ok_or_else(|| PqCryptoError::InternalError("Signature not set".to_string()))?;
+
+            let is_valid = combiner::verify(self.algorithm, &public_key, &message, &signature)?;
+
+            Ok(VerificationResult::new(
+                self.algorithm.ml_dsa(),
+                is_valid,
+                None,
+            ))
+        }
+    }
+}
@@ -1,6 +1,11 @@
 //! Core signature builder types and basic implementations
 
 // Re-export builder types
+pub use super::batch::SignatureBatch;
+pub use super::composite::{
+    CompositeBuilder, CompositeWithKeyPair, CompositeWithMessage, CompositeWithPublicKey,
+    CompositeWithSecretKey, CompositeWithSignature,
+};
 pub use super::falcon::{
     FalconBuilder, FalconWithKeyPair, FalconWithMessage, FalconWithPublicKey, FalconWithSecretKey,
     FalconWithSignature,
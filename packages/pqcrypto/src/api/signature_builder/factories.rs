@@ -1,16 +1,12 @@
 //! Algorithm factory methods for creating specific signature builders
 
 use super::super::states::NeedKeyPair;
-use super::core::SignatureBuilderWithHandler;
+use super::core::SignatureBuilder;
 use super::{FalconBuilder, MlDsaBuilder, SphincsBuilder};
 use crate::{PqCryptoError, Result, SignatureAlgorithm};
 use std::marker::PhantomData;
 
-impl<F, T> SignatureBuilderWithHandler<F, T>
-where
-    F: FnOnce(crate::Result<(Vec<u8>, Vec<u8>)>) -> T + Send + 'static,
-    T: Send + 'static,
-{
+impl SignatureBuilder {
     /// Create a new ML-DSA builder with the specified security level
     pub fn ml_dsa(security_level: u16) -> Result<MlDsaBuilder<NeedKeyPair>> {
         let algorithm = match security_level {
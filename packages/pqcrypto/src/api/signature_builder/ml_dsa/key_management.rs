@@ -4,10 +4,10 @@ use super::super::super::{
     builder_traits::SignatureKeyPairBuilder,
     states::{HasKeyPair, HasPublicKey, HasSecretKey, NeedKeyPair},
 };
-use super::super::common::BaseSignatureBuilder;
 use super::types::MlDsaBuilder;
 use crate::algorithm::SignatureAlgorithm;
-use crate::{PqCryptoError, Result};
+use crate::backend::Backend;
+use crate::{PqCryptoError, Result, encoding};
 use pqcrypto_traits::sign::{PublicKey as PqPublicKey, SecretKey as PqSecretKey};
 
 use std::marker::PhantomData;
@@ -58,14 +58,11 @@ impl SignatureKeyPairBuilder for MlDsaBuilder<NeedKeyPair> {
     }
 
     fn with_keypair<T: Into<Vec<u8>>>(self, public_key: T, secret_key: T) -> Result<Self::Output> {
-        let pk = public_key.into();
-        let sk = secret_key.into();
-
-        self.validate_public_key(&pk)?;
-        self.validate_secret_key(&sk)?;
+        let (algorithm, pk, sk) =
+            encoding::import_keypair(self.algorithm, public_key.into(), secret_key.into())?;
 
         Ok(MlDsaBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: Some(pk),
             secret_key: Some(sk),
@@ -78,11 +75,10 @@ impl SignatureKeyPairBuilder for MlDsaBuilder<NeedKeyPair> {
         self,
         public_key: T,
     ) -> Result<MlDsaBuilder<HasPublicKey>> {
-        let pk = public_key.into();
-        self.validate_public_key(&pk)?;
+        let (algorithm, pk) = encoding::import_public_key(self.algorithm, public_key.into())?;
 
         Ok(MlDsaBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: Some(pk),
             secret_key: None,
@@ -95,11 +91,10 @@ impl SignatureKeyPairBuilder for MlDsaBuilder<NeedKeyPair> {
         self,
         secret_key: T,
     ) -> Result<MlDsaBuilder<HasSecretKey>> {
-        let sk = secret_key.into();
-        self.validate_secret_key(&sk)?;
+        let (algorithm, sk) = encoding::import_secret_key(self.algorithm, secret_key.into())?;
 
         Ok(MlDsaBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: None,
             secret_key: Some(sk),
@@ -109,6 +104,28 @@ impl SignatureKeyPairBuilder for MlDsaBuilder<NeedKeyPair> {
     }
 }
 
+impl MlDsaBuilder<NeedKeyPair> {
+    /// Derive the key pair from the 32-byte FIPS 204 seed `ξ`
+    ///
+    /// Uses the [native backend](crate::backend); the same seed always yields the same keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidParameters` for a seed of the wrong length.
+    pub fn keypair_from_seed(self, seed: &[u8]) -> Result<MlDsaBuilder<HasKeyPair>> {
+        let (pk, sk) = Backend::Native.signature_keypair_from_seed(self.algorithm, seed)?;
+
+        Ok(MlDsaBuilder {
+            algorithm: self.algorithm,
+            state: PhantomData,
+            public_key: Some(pk),
+            secret_key: Some(sk.to_vec()),
+            message: None,
+            signature: None,
+        })
+    }
+}
+
 // Public key access methods for ML-DSA HasKeyPair state
 impl MlDsaBuilder<HasKeyPair> {
     /// Get the public key bytes
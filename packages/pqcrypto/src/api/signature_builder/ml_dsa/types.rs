@@ -1,9 +1,10 @@
 //! ML-DSA type definitions and aliases
 
 use super::super::super::states::{
-    HasKeyPair, HasMessage, HasPublicKey, HasSecretKey, HasSignature,
+    HasKeyPair, HasMessage, HasPublicKey, HasSecretKey, HasSignature, NeedKeyPair,
 };
 use crate::algorithm::SignatureAlgorithm;
+use crate::{PqCryptoError, Result};
 use std::marker::PhantomData;
 
 /// ML-DSA builder type
@@ -16,6 +17,31 @@ pub struct MlDsaBuilder<State> {
     pub(crate) signature: Option<Vec<u8>>,
 }
 
+impl MlDsaBuilder<NeedKeyPair> {
+    /// Create an ML-DSA builder for the given parameter set
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `algorithm` is not an ML-DSA parameter set.
+    pub fn new(algorithm: SignatureAlgorithm) -> Result<Self> {
+        match algorithm {
+            SignatureAlgorithm::MlDsa44
+            | SignatureAlgorithm::MlDsa65
+            | SignatureAlgorithm::MlDsa87 => Ok(Self {
+                algorithm,
+                state: PhantomData,
+                public_key: None,
+                secret_key: None,
+                message: None,
+                signature: None,
+            }),
+            _ => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{algorithm:?} is not an ML-DSA algorithm"
+            ))),
+        }
+    }
+}
+
 /// ML-DSA builder with a complete key pair (public and secret keys)
 pub type MlDsaWithKeyPair = MlDsaBuilder<HasKeyPair>;
 /// ML-DSA builder with only the secret key for signing
@@ -1,12 +1,15 @@
 //! Digital signature builder implementations
 
+mod batch;
 mod common;
+pub mod composite;
 mod core;
 mod factories;
 mod falcon;
 mod handlers;
 pub mod ml_dsa;
 pub mod sphincs;
+mod streaming;
 
 // Re-export all public types and functions
 pub use core::*;
@@ -1,6 +1,8 @@
 //! Core SPHINCS+ builder struct and base implementation
 
 use super::super::super::super::SignatureAlgorithm;
+use super::super::super::states::NeedKeyPair;
+use crate::{PqCryptoError, Result};
 use std::marker::PhantomData;
 
 /// SPHINCS+ builder type
@@ -20,3 +22,31 @@ impl<State> SphincsBuilder<State> {
         self.algorithm
     }
 }
+
+impl SphincsBuilder<NeedKeyPair> {
+    /// Create a SPHINCS+ builder for the given parameter set
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `algorithm` is not a SPHINCS+ parameter set.
+    pub fn new(algorithm: SignatureAlgorithm) -> Result<Self> {
+        match algorithm {
+            SignatureAlgorithm::SphincsShaSha256_128fSimple
+            | SignatureAlgorithm::SphincsShaSha256_128sSimple
+            | SignatureAlgorithm::SphincsShaSha256_192fSimple
+            | SignatureAlgorithm::SphincsShaSha256_192sSimple
+            | SignatureAlgorithm::SphincsShaSha256_256fSimple
+            | SignatureAlgorithm::SphincsShaSha256_256sSimple => Ok(Self {
+                algorithm,
+                state: PhantomData,
+                public_key: None,
+                secret_key: None,
+                message: None,
+                signature: None,
+            }),
+            _ => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{algorithm:?} is not a SPHINCS+ algorithm"
+            ))),
+        }
+    }
+}
@@ -6,7 +6,8 @@ use super::super::super::{
     states::{HasKeyPair, HasPublicKey, HasSecretKey, NeedKeyPair},
 };
 use super::core::SphincsBuilder;
-use crate::{PqCryptoError, Result};
+use crate::backend::Backend;
+use crate::{PqCryptoError, Result, encoding};
 use pqcrypto_traits::sign::{PublicKey as PqPublicKey, SecretKey as PqSecretKey};
 
 use std::marker::PhantomData;
@@ -79,14 +80,11 @@ impl SignatureKeyPairBuilder for SphincsBuilder<NeedKeyPair> {
     }
 
     fn with_keypair<T: Into<Vec<u8>>>(self, public_key: T, secret_key: T) -> Result<Self::Output> {
-        let pk = public_key.into();
-        let sk = secret_key.into();
-
-        self.validate_public_key(&pk)?;
-        self.validate_secret_key(&sk)?;
+        let (algorithm, pk, sk) =
+            encoding::import_keypair(self.algorithm, public_key.into(), secret_key.into())?;
 
         Ok(SphincsBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: Some(pk),
             secret_key: Some(sk),
@@ -99,11 +97,10 @@ impl SignatureKeyPairBuilder for SphincsBuilder<NeedKeyPair> {
         self,
         public_key: T,
     ) -> Result<SphincsBuilder<HasPublicKey>> {
-        let pk = public_key.into();
-        self.validate_public_key(&pk)?;
+        let (algorithm, pk) = encoding::import_public_key(self.algorithm, public_key.into())?;
 
         Ok(SphincsBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: Some(pk),
             secret_key: None,
@@ -116,11 +113,10 @@ impl SignatureKeyPairBuilder for SphincsBuilder<NeedKeyPair> {
         self,
         secret_key: T,
     ) -> Result<SphincsBuilder<HasSecretKey>> {
-        let sk = secret_key.into();
-        self.validate_secret_key(&sk)?;
+        let (algorithm, sk) = encoding::import_secret_key(self.algorithm, secret_key.into())?;
 
         Ok(SphincsBuilder {
-            algorithm: self.algorithm,
+            algorithm,
             state: PhantomData,
             public_key: None,
             secret_key: Some(sk),
@@ -130,28 +126,25 @@ impl SignatureKeyPairBuilder for SphincsBuilder<NeedKeyPair> {
     }
 }
 
-impl<State> SphincsBuilder<State> {
-    /// Validate public key size for the algorithm
-    pub(crate) fn validate_public_key(&self, key: &[u8]) -> Result<()> {
-        let expected = self.algorithm.public_key_size();
-        if key.len() != expected {
-            return Err(PqCryptoError::InvalidKeySize {
-                expected,
-                actual: key.len(),
-            });
-        }
-        Ok(())
-    }
+impl SphincsBuilder<NeedKeyPair> {
+    /// Derive the key pair from the FIPS 205 seed `SK.seed || SK.prf || PK.seed` (`3n` bytes)
+    ///
+    /// Uses the [native backend](crate::backend). The keys are valid for both SLH-DSA and
+    /// SPHINCS+, since the two share key generation and key formats.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidParameters` for a seed of the wrong length.
+    pub fn keypair_from_seed(self, seed: &[u8]) -> Result<SphincsBuilder<HasKeyPair>> {
+        let (pk, sk) = Backend::Native.signature_keypair_from_seed(self.algorithm, seed)?;
 
-    /// Validate secret key size for the algorithm
-    pub(crate) fn validate_secret_key(&self, key: &[u8]) -> Result<()> {
-        let expected = self.algorithm.secret_key_size();
-        if key.len() != expected {
-            return Err(PqCryptoError::InvalidKeySize {
-                expected,
-                actual: key.len(),
-            });
-        }
-        Ok(())
+        Ok(SphincsBuilder {
+            algorithm: self.algorithm,
+            state: PhantomData,
+            public_key: Some(pk),
+            secret_key: Some(sk.to_vec()),
+            message: None,
+            signature: None,
+        })
     }
 }
@@ -0,0 +1,160 @@
+//! Streaming sign and verify for the ML-DSA, SPHINCS+ and FALCON builders
+//!
+//! ML-DSA and SPHINCS+ keys sign streamed messages as HashML-DSA and HashSLH-DSA (FIPS 205,
+//! not the round 3.1 SPHINCS+ of [`SignBuilder`](super::super::builder_traits::SignBuilder))
+//! through the native backend; FALCON signs a domain-separated SHA3-512 digest.
+
+use super::super::builder_traits::{StreamSignBuilder, StreamVerifyBuilder};
+use super::super::states::{HasKeyPair, HasPublicKey, HasSecretKey};
+use super::falcon::FalconBuilder;
+use super::ml_dsa::MlDsaBuilder;
+use super::sphincs::SphincsBuilder;
+use crate::{
+    PqCryptoError, Result, SignatureAlgorithm, SignatureFile, StreamDigest, VerificationResult,
+};
+
+impl StreamSignBuilder for MlDsaBuilder<HasKeyPair> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn sign_digest(self, digest: &[u8]) -> Result<SignatureFile> {
+        sign(self.algorithm, self.secret_key, self.public_key, digest)
+    }
+}
+
+impl StreamSignBuilder for MlDsaBuilder<HasSecretKey> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn sign_digest(self, digest: &[u8]) -> Result<SignatureFile> {
+        sign(self.algorithm, self.secret_key, self.public_key, digest)
+    }
+}
+
+impl StreamVerifyBuilder for MlDsaBuilder<HasKeyPair> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn verify_digest(self, digest: &[u8], signature: &SignatureFile) -> Result<VerificationResult> {
+        verify(self.algorithm, self.public_key, digest, signature)
+    }
+}
+
+impl StreamVerifyBuilder for MlDsaBuilder<HasPublicKey> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn verify_digest(self, digest: &[u8], signature: &SignatureFile) -> Result<VerificationResult> {
+        verify(self.algorithm, self.public_key, digest, signature)
+    }
+}
+
+impl StreamSignBuilder for SphincsBuilder<HasKeyPair> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn sign_digest(self, digest: &[u8]) -> Result<SignatureFile> {
+        sign(self.algorithm, self.secret_key, self.public_key, digest)
+    }
+}
+
+impl StreamSignBuilder for SphincsBuilder<HasSecretKey> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn sign_digest(self, digest: &[u8]) -> Result<SignatureFile> {
+        sign(self.algorithm, self.secret_key, self.public_key, digest)
+    }
+}
+
+impl StreamVerifyBuilder for SphincsBuilder<HasKeyPair> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn verify_digest(self, digest: &[u8], signature: &SignatureFile) -> Result<VerificationResult> {
+        verify(self.algorithm, self.public_key, digest, signature)
+    }
+}
+
+impl StreamVerifyBuilder for SphincsBuilder<HasPublicKey> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn verify_digest(self, digest: &[u8], signature: &SignatureFile) -> Result<VerificationResult> {
+        verify(self.algorithm, self.public_key, digest, signature)
+    }
+}
+
+impl StreamSignBuilder for FalconBuilder<HasKeyPair> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn sign_digest(self, digest: &[u8]) -> Result<SignatureFile> {
+        sign(self.algorithm, self.secret_key, self.public_key, digest)
+    }
+}
+
+impl StreamSignBuilder for FalconBuilder<HasSecretKey> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn sign_digest(self, digest: &[u8]) -> Result<SignatureFile> {
+        sign(self.algorithm, self.secret_key, self.public_key, digest)
+    }
+}
+
+impl StreamVerifyBuilder for FalconBuilder<HasKeyPair> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn verify_digest(self, digest: &[u8], signature: &SignatureFile) -> Result<VerificationResult> {
+        verify(self.algorithm, self.public_key, digest, signature)
+    }
+}
+
+impl StreamVerifyBuilder for FalconBuilder<HasPublicKey> {
+    fn stream_digest(&self) -> StreamDigest {
+        StreamDigest::for_algorithm(self.algorithm)
+    }
+
+    fn verify_digest(self, digest: &[u8], signature: &SignatureFile) -> Result<VerificationResult> {
+        verify(self.algorithm, self.public_key, digest, signature)
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)] // Takes the builder's key fields as they are moved out
+fn sign(
+    algorithm: SignatureAlgorithm,
+    secret_key: Option<Vec<u8>>,
+    public_key: Option<Vec<u8>>,
+    digest: &[u8],
+) -> Result<SignatureFile> {
+    let secret_key = secret_key
+        .ok_or_else(|| PqCryptoError::InvalidKey("Secret key required for signing".to_string()))?;
+    SignatureFile::sign(algorithm, &secret_key, public_key.as_deref(), digest)
+}
+
+#[allow(clippy::needless_pass_by_value)] // Takes the builder's key field as it is moved out
+fn verify(
+    algorithm: SignatureAlgorithm,
+    public_key: Option<Vec<u8>>,
+    digest: &[u8],
+    signature: &SignatureFile,
+) -> Result<VerificationResult> {
+    let public_key = public_key.ok_or_else(|| {
+        PqCryptoError::InvalidKey("Public key required for verification".to_string())
+    })?;
+    let is_valid = signature.verify(algorithm, &public_key, digest)?;
+    Ok(VerificationResult::new(algorithm, is_valid, None))
+}
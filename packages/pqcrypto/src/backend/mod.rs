@@ -0,0 +1,539 @@
+//! Interchangeable implementations of the post-quantum primitives
+//!
+//! [`Backend::PqClean`] wraps the `PQClean` C code from the `pqcrypto` crates and is what
+//! the builders use. [`Backend::Native`] is a pure-Rust implementation of FIPS 203 (ML-KEM),
+//! FIPS 204 (ML-DSA) and FIPS 205 (SLH-DSA, SHA2 parameter sets) that adds what `PQClean`
+//! does not expose:
+//!
+//! - key generation from a seed, for reproducible keys and known-answer tests
+//! - ML-DSA and SLH-DSA context strings and pre-hash (HashML-DSA, HashSLH-DSA) signing
+//! - deterministic as well as hedged signing
+//! - signing a pre-hash digest computed incrementally with a [`PreHasher`], for messages too
+//!   large to hold in memory
+//!
+//! ```rust,ignore
+//! let (public_key, secret_key) =
+//!     Backend::Native.signature_keypair_from_seed(SignatureAlgorithm::MlDsa65, &seed)?;
+//! let options = SigningOptions::new()
+//!     .with_context(b"invoice-v1".to_vec())
+//!     .with_pre_hash(PreHash::Sha512);
+//! let signature = Backend::Native.sign(algorithm, &secret_key, &message, &options)?;
+//! ```
+//!
+//! ML-KEM and ML-DSA keys, ciphertexts and pure signatures with an empty context are
+//! interchangeable between the backends. `PQClean`'s SPHINCS+ is round 3.1 rather than
+//! FIPS 205, so for the `Sphincs*` algorithms the two backends share key formats but produce
+//! incompatible signatures. FALCON and the hybrid KEMs are only available through
+//! `PQClean`.
+
+mod native;
+mod pqclean;
+mod prehash;
+
+pub use self::prehash::{MAX_CONTEXT_LEN, PreHash, PreHasher};
+
+use self::native::{ml_dsa, ml_kem, slh_dsa};
+use self::prehash::FormattedMessage;
+use crate::{KemAlgorithm, PqAlgorithm, PqCryptoError, Result, SignatureAlgorithm};
+use zeroize::Zeroizing;
+
+/// Implementation of the post-quantum primitives
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// `PQClean` via the `pqcrypto` crates
+    #[default]
+    PqClean,
+    /// Pure-Rust FIPS 203, 204 and 205
+    Native,
+}
+
+/// How a signature's per-message randomness is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SigningMode {
+    /// Fresh randomness mixed into every signature (the FIPS 204 and 205 default)
+    #[default]
+    Hedged,
+    /// The same message and key always produce the same signature
+    Deterministic,
+}
+
+/// Signature variant parameters beyond the message and key
+///
+/// Verification uses the context and pre-hash, which must match those used for signing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SigningOptions {
+    context: Vec<u8>,
+    pre_hash: Option<PreHash>,
+    mode: SigningMode,
+    randomness: Option<Vec<u8>>,
+}
+
+impl SigningOptions {
+    /// Pure, hedged signing with an empty context
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind signatures to an application context of at most [`MAX_CONTEXT_LEN`] bytes
+    #[must_use]
+    pub fn with_context(mut self, context: impl Into<Vec<u8>>) -> Self {
+        self.context = context.into();
+        self
+    }
+
+    /// Sign a digest of the message rather than the message itself
+    #[must_use]
+    pub fn with_pre_hash(mut self, pre_hash: PreHash) -> Self {
+        self.pre_hash = Some(pre_hash);
+        self
+    }
+
+    /// Choose hedged or deterministic signing
+    #[must_use]
+    pub fn with_mode(mut self, mode: SigningMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Fix the hedged signing randomness, for known-answer tests only
+    #[doc(hidden)]
+    #[must_use]
+    pub fn with_test_randomness(mut self, randomness: impl Into<Vec<u8>>) -> Self {
+        self.randomness = Some(randomness.into());
+        self
+    }
+
+    /// Application context
+    #[must_use]
+    pub fn context(&self) -> &[u8] {
+        &self.context
+    }
+
+    /// Pre-hash function, if any
+    #[must_use]
+    pub fn pre_hash(&self) -> Option<PreHash> {
+        self.pre_hash
+    }
+
+    /// Hedged or deterministic signing
+    #[must_use]
+    pub fn mode(&self) -> SigningMode {
+        self.mode
+    }
+
+    fn is_plain(&self) -> bool {
+        self.context.is_empty()
+            && self.pre_hash.is_none()
+            && self.mode == SigningMode::Hedged
+            && self.randomness.is_none()
+    }
+
+    /// Per-message randomness of `len` bytes, or `None` for deterministic signing
+    fn randomness(&self, len: usize) -> Result<Option<Zeroizing<Vec<u8>>>> {
+        match (&self.randomness, self.mode) {
+            (Some(randomness), _) if randomness.len() == len => {
+                Ok(Some(Zeroizing::new(randomness.clone())))
+            }
+            (Some(randomness), _) => Err(PqCryptoError::InvalidParameters(format!(
+                "Signing randomness must be {len} bytes, got {}",
+                randomness.len()
+            ))),
+            (None, SigningMode::Hedged) => {
+                let mut randomness = Zeroizing::new(vec![0u8; len]);
+                native::random_bytes(&mut randomness);
+                Ok(Some(randomness))
+            }
+            (None, SigningMode::Deterministic) => Ok(None),
+        }
+    }
+}
+
+/// Native signature scheme and parameter set
+enum NativeScheme {
+    MlDsa(ml_dsa::Params),
+    SlhDsa(slh_dsa::Params),
+}
+
+impl Backend {
+    /// Whether this backend implements `algorithm`
+    #[must_use]
+    pub fn supports(self, algorithm: impl Into<PqAlgorithm>) -> bool {
+        match (self, algorithm.into()) {
+            // Assembled from both backends by the composite signature builder
+            (_, PqAlgorithm::Composite(_)) => false,
+            (Self::PqClean, _) => true,
+            (Self::Native, PqAlgorithm::Kem(algorithm)) => native_kem(algorithm).is_ok(),
+            (Self::Native, PqAlgorithm::Signature(algorithm)) => {
+                native_signature(algorithm).is_ok()
+            }
+        }
+    }
+
+    /// Generate a KEM key pair, returning `(public_key, secret_key)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if this backend lacks `algorithm`.
+    pub fn kem_keypair(self, algorithm: KemAlgorithm) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+        match self {
+            Self::PqClean => pqclean::kem_keypair(algorithm),
+            Self::Native => {
+                let params = native_kem(algorithm)?;
+                let (public_key, secret_key) = ml_kem::keypair(params);
+                Ok((public_key, secret_key))
+            }
+        }
+    }
+
+    /// Derive an ML-KEM key pair from the 64-byte seed `d || z` of FIPS 203
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` unless this is [`Backend::Native`] and
+    /// `algorithm` is plain ML-KEM, and `PqCryptoError::InvalidParameters` for a seed of the
+    /// wrong length.
+    pub fn kem_keypair_from_seed(
+        self,
+        algorithm: KemAlgorithm,
+        seed: &[u8],
+    ) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+        self.require_native("Seeded key generation")?;
+        let params = native_kem(algorithm)?;
+        let seed = <&[u8; ml_kem::SEED_LEN]>::try_from(seed)
+            .map_err(|_| seed_length_error(algorithm, ml_kem::SEED_LEN, seed.len()))?;
+        Ok(ml_kem::keypair_from_seed(params, seed))
+    }
+
+    /// Encapsulate to `public_key`, returning `(ciphertext, shared_secret)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if this backend lacks `algorithm`, or an
+    /// error if the public key is malformed.
+    pub fn encapsulate(
+        self,
+        algorithm: KemAlgorithm,
+        public_key: &[u8],
+    ) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+        match self {
+            Self::PqClean => pqclean::encapsulate(algorithm, public_key),
+            Self::Native => {
+                let (ciphertext, shared_secret) =
+                    ml_kem::encapsulate(native_kem(algorithm)?, public_key)?;
+                Ok((ciphertext, Zeroizing::new(shared_secret.to_vec())))
+            }
+        }
+    }
+
+    /// Encapsulate with fixed randomness `m`, for known-answer tests only
+    ///
+    /// # Errors
+    ///
+    /// As [`Backend::encapsulate`], and only [`Backend::Native`] supports it.
+    #[doc(hidden)]
+    pub fn encapsulate_deterministic(
+        self,
+        algorithm: KemAlgorithm,
+        public_key: &[u8],
+        randomness: &[u8],
+    ) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+        self.require_native("Deterministic encapsulation")?;
+        let params = native_kem(algorithm)?;
+        let randomness = <&[u8; ml_kem::MESSAGE_LEN]>::try_from(randomness).map_err(|_| {
+            PqCryptoError::InvalidParameters(format!(
+                "Encapsulation randomness must be {} bytes, got {}",
+                ml_kem::MESSAGE_LEN,
+                randomness.len()
+            ))
+        })?;
+        let (ciphertext, shared_secret) =
+            ml_kem::encapsulate_deterministic(params, public_key, randomness)?;
+        Ok((ciphertext, Zeroizing::new(shared_secret.to_vec())))
+    }
+
+    /// Decapsulate `ciphertext`, returning the shared secret
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if this backend lacks `algorithm`, or an
+    /// error if the key or ciphertext is malformed.
+    pub fn decapsulate(
+        self,
+        algorithm: KemAlgorithm,
+        secret_key: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Zeroizing<Vec<u8>>> {
+        match self {
+            Self::PqClean => pqclean::decapsulate(algorithm, secret_key, ciphertext),
+            Self::Native => {
+                let shared_secret =
+                    ml_kem::decapsulate(native_kem(algorithm)?, secret_key, ciphertext)?;
+                Ok(Zeroizing::new(shared_secret.to_vec()))
+            }
+        }
+    }
+
+    /// Generate a signature key pair, returning `(public_key, secret_key)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if this backend lacks `algorithm`.
+    pub fn signature_keypair(
+        self,
+        algorithm: SignatureAlgorithm,
+    ) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+        match self {
+            Self::PqClean => Ok(pqclean::signature_keypair(algorithm)),
+            Self::Native => {
+                let seed_len = match native_signature(algorithm)? {
+                    NativeScheme::MlDsa(_) => ml_dsa::SEED_LEN,
+                    NativeScheme::SlhDsa(params) => params.seed_len(),
+                };
+                let mut seed = Zeroizing::new(vec![0u8; seed_len]);
+                native::random_bytes(&mut seed);
+                self.signature_keypair_from_seed(algorithm, &seed)
+            }
+        }
+    }
+
+    /// Derive a signature key pair from a seed: the 32-byte `ξ` of FIPS 204 for ML-DSA, or
+    /// `SK.seed || SK.prf || PK.seed` (`3n` bytes) of FIPS 205 for SLH-DSA
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` unless this is [`Backend::Native`] and
+    /// `algorithm` is ML-DSA or SLH-DSA, and `PqCryptoError::InvalidParameters` for a seed of
+    /// the wrong length.
+    pub fn signature_keypair_from_seed(
+        self,
+        algorithm: SignatureAlgorithm,
+        seed: &[u8],
+    ) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+        self.require_native("Seeded key generation")?;
+        match native_signature(algorithm)? {
+            NativeScheme::MlDsa(params) => {
+                let seed = <&[u8; ml_dsa::SEED_LEN]>::try_from(seed)
+                    .map_err(|_| seed_length_error(algorithm, ml_dsa::SEED_LEN, seed.len()))?;
+                Ok(ml_dsa::keypair_from_seed(params, seed))
+            }
+            NativeScheme::SlhDsa(params) => slh_dsa::keypair_from_seed(params, seed)
+                .map_err(|_| seed_length_error(algorithm, params.seed_len(), seed.len())),
+        }
+    }
+
+    /// Sign `message`
+    ///
+    /// [`Backend::PqClean`] only signs with the default [`SigningOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if this backend lacks `algorithm` or the
+    /// options, `PqCryptoError::InvalidParameters` for an over-long context, or an error if the
+    /// secret key is malformed.
+    pub fn sign(
+        self,
+        algorithm: SignatureAlgorithm,
+        secret_key: &[u8],
+        message: &[u8],
+        options: &SigningOptions,
+    ) -> Result<Vec<u8>> {
+        match self {
+            Self::PqClean if options.is_plain() => pqclean::sign(algorithm, secret_key, message),
+            Self::PqClean => Err(plain_only()),
+            Self::Native => {
+                let formatted = FormattedMessage::new(message, &options.context, options.pre_hash)?;
+                sign_native(algorithm, secret_key, &formatted, options)
+            }
+        }
+    }
+
+    /// Verify `signature` over `message`, with the context and pre-hash of `options`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if this backend lacks `algorithm` or the
+    /// options, `PqCryptoError::InvalidParameters` for an over-long context, or an error if the
+    /// public key is malformed. A well-formed key with a bad signature yields `Ok(false)`.
+    pub fn verify(
+        self,
+        algorithm: SignatureAlgorithm,
+        public_key: &[u8],
+        message: &[u8],
+        signature: &[u8],
+        options: &SigningOptions,
+    ) -> Result<bool> {
+        match self {
+            Self::PqClean if options.context.is_empty() && options.pre_hash.is_none() => {
+                pqclean::verify(algorithm, public_key, message, signature)
+            }
+            Self::PqClean => Err(plain_only()),
+            Self::Native => {
+                let formatted = FormattedMessage::new(message, &options.context, options.pre_hash)?;
+                verify_native(algorithm, public_key, &formatted, signature)
+            }
+        }
+    }
+
+    /// Sign a message given only its digest under `options.pre_hash`, as HashML-DSA or
+    /// HashSLH-DSA
+    ///
+    /// The signature is identical to [`Backend::sign`] over the full message with the same
+    /// options, so a [`PreHasher`] can digest messages too large to hold in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` unless this is [`Backend::Native`] and
+    /// `algorithm` is ML-DSA or SLH-DSA, `PqCryptoError::InvalidParameters` if `options` has
+    /// no pre-hash or `digest` is the wrong length, or an error if the secret key is malformed.
+    pub fn sign_digest(
+        self,
+        algorithm: SignatureAlgorithm,
+        secret_key: &[u8],
+        digest: &[u8],
+        options: &SigningOptions,
+    ) -> Result<Vec<u8>> {
+        self.require_native("Signing a digest")?;
+        let formatted = digest_message(digest, options)?;
+        sign_native(algorithm, secret_key, &formatted, options)
+    }
+
+    /// Verify `signature` over a message given only its digest under `options.pre_hash`
+    ///
+    /// # Errors
+    ///
+    /// As [`Backend::sign_digest`], with a malformed public key in place of the secret key.
+    /// A well-formed key with a bad signature yields `Ok(false)`.
+    pub fn verify_digest(
+        self,
+        algorithm: SignatureAlgorithm,
+        public_key: &[u8],
+        digest: &[u8],
+        signature: &[u8],
+        options: &SigningOptions,
+    ) -> Result<bool> {
+        self.require_native("Verifying a digest")?;
+        let formatted = digest_message(digest, options)?;
+        verify_native(algorithm, public_key, &formatted, signature)
+    }
+
+    fn require_native(self, operation: &str) -> Result<()> {
+        match self {
+            Self::Native => Ok(()),
+            Self::PqClean => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{operation} requires the native backend"
+            ))),
+        }
+    }
+}
+
+fn sign_native(
+    algorithm: SignatureAlgorithm,
+    secret_key: &[u8],
+    formatted: &FormattedMessage<'_>,
+    options: &SigningOptions,
+) -> Result<Vec<u8>> {
+    match native_signature(algorithm)? {
+        NativeScheme::MlDsa(params) => {
+            let mut rnd = Zeroizing::new([0u8; ml_dsa::SEED_LEN]);
+            if let Some(randomness) = options.randomness(ml_dsa::SEED_LEN)? {
+                rnd.copy_from_slice(&randomness);
+            }
+            ml_dsa::sign_internal(params, secret_key, &formatted.parts(), &rnd)
+        }
+        NativeScheme::SlhDsa(params) => {
+            let addrnd = options.randomness(params.randomness_len())?;
+            slh_dsa::sign_internal(
+                params,
+                secret_key,
+                &formatted.parts(),
+                addrnd.as_deref().map(Vec::as_slice),
+            )
+        }
+    }
+}
+
+fn verify_native(
+    algorithm: SignatureAlgorithm,
+    public_key: &[u8],
+    formatted: &FormattedMessage<'_>,
+    signature: &[u8],
+) -> Result<bool> {
+    match native_signature(algorithm)? {
+        NativeScheme::MlDsa(params) => {
+            ml_dsa::verify_internal(params, public_key, &formatted.parts(), signature)
+        }
+        NativeScheme::SlhDsa(params) => {
+            slh_dsa::verify_internal(params, public_key, &formatted.parts(), signature)
+        }
+    }
+}
+
+fn digest_message(digest: &[u8], options: &SigningOptions) -> Result<FormattedMessage<'static>> {
+    let pre_hash = options.pre_hash.ok_or_else(|| {
+        PqCryptoError::InvalidParameters("Signing a digest requires a pre-hash".to_string())
+    })?;
+    FormattedMessage::from_digest(digest.to_vec(), &options.context, pre_hash)
+}
+
+fn native_kem(algorithm: KemAlgorithm) -> Result<ml_kem::Params> {
+    match algorithm {
+        KemAlgorithm::MlKem512 => Ok(ml_kem::ML_KEM_512),
+        KemAlgorithm::MlKem768 => Ok(ml_kem::ML_KEM_768),
+        KemAlgorithm::MlKem1024 => Ok(ml_kem::ML_KEM_1024),
+        KemAlgorithm::X25519MlKem768 | KemAlgorithm::P256MlKem768 | KemAlgorithm::P384MlKem1024 => {
+            Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{algorithm} is not available in the native backend"
+            )))
+        }
+    }
+}
+
+fn native_signature(algorithm: SignatureAlgorithm) -> Result<NativeScheme> {
+    Ok(match algorithm {
+        SignatureAlgorithm::MlDsa44 => NativeScheme::MlDsa(ml_dsa::ML_DSA_44),
+        SignatureAlgorithm::MlDsa65 => NativeScheme::MlDsa(ml_dsa::ML_DSA_65),
+        SignatureAlgorithm::MlDsa87 => NativeScheme::MlDsa(ml_dsa::ML_DSA_87),
+        SignatureAlgorithm::SphincsShaSha256_128sSimple => {
+            NativeScheme::SlhDsa(slh_dsa::SLH_DSA_SHA2_128S)
+        }
+        SignatureAlgorithm::SphincsShaSha256_128fSimple => {
+            NativeScheme::SlhDsa(slh_dsa::SLH_DSA_SHA2_128F)
+        }
+        SignatureAlgorithm::SphincsShaSha256_192sSimple => {
+            NativeScheme::SlhDsa(slh_dsa::SLH_DSA_SHA2_192S)
+        }
+        SignatureAlgorithm::SphincsShaSha256_192fSimple => {
+            NativeScheme::SlhDsa(slh_dsa::SLH_DSA_SHA2_192F)
+        }
+        SignatureAlgorithm::SphincsShaSha256_256sSimple => {
+            NativeScheme::SlhDsa(slh_dsa::SLH_DSA_SHA2_256S)
+        }
+        SignatureAlgorithm::SphincsShaSha256_256fSimple => {
+            NativeScheme::SlhDsa(slh_dsa::SLH_DSA_SHA2_256F)
+        }
+        SignatureAlgorithm::Falcon512 | SignatureAlgorithm::Falcon1024 => {
+            return Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{algorithm} is not available in the native backend"
+            )));
+        }
+    })
+}
+
+fn seed_length_error(
+    algorithm: impl Into<PqAlgorithm>,
+    expected: usize,
+    actual: usize,
+) -> PqCryptoError {
+    PqCryptoError::InvalidParameters(format!(
+        "{} seed must be {expected} bytes, got {actual}",
+        algorithm.into()
+    ))
+}
+
+fn plain_only() -> PqCryptoError {
+    PqCryptoError::UnsupportedAlgorithm(
+        "Context strings, pre-hashing and deterministic signing require the native backend"
+            .to_string(),
+    )
+}
@@ -0,0 +1,719 @@
+//! ML-DSA (FIPS 204)
+//!
+//! Coefficients are kept in `[0, q)` as `u32`; products are reduced from `u64`. Signed
+//! quantities (secrets, masks, hints) are recentred only where the standard compares norms.
+//! Secret coefficients are never divided: reductions use Barrett multiplication and masked
+//! subtraction, and `Decompose` a fixed-point reciprocal of `2γ2`, so the running time does
+//! not depend on them.
+//! Callers supply the formatted message `M'` so that pure and pre-hash signing share this
+//! code.
+
+// Every narrowing cast below is of a value already reduced modulo q or to a bit width;
+// single-letter names follow the notation of FIPS 204
+#![allow(
+    clippy::cast_possible_truncation,
+    clippy::many_single_char_names,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+
+use super::{shake128, shake256, shake256_into};
+use crate::{PqCryptoError, Result};
+use sha3::digest::XofReader;
+use sha3::digest::{ExtendableOutput, Update};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+const N: usize = 256;
+const Q: u32 = 8_380_417;
+
+/// Dropped bits of `t`
+const D: u32 = 13;
+
+/// `256^-1 mod q`, scaling the inverse NTT
+const INVERSE_256: u64 = 8_347_681;
+
+/// `⌊2^64 / q⌋`, the Barrett reduction multiplier
+const BARRETT_MULTIPLIER: u128 = (1 << 64) / Q as u128;
+
+type Poly = [u32; N];
+
+/// `ζ^BitRev8(i) mod q` for `ζ = 1753`
+const ZETAS: [u32; N] = {
+    let mut zetas = [0u32; N];
+    let mut i = 0;
+    while i < N {
+        let mut reversed = 0;
+        let mut bit = 0;
+        while bit < 8 {
+            reversed |= ((i >> bit) & 1) << (7 - bit);
+            bit += 1;
+        }
+        let mut power = 1u64;
+        let mut j = 0;
+        while j < reversed {
+            power = power * 1753 % Q as u64;
+            j += 1;
+        }
+        zetas[i] = power as u32;
+        i += 1;
+    }
+    zetas
+};
+
+/// ML-DSA parameter set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Params {
+    k: usize,
+    l: usize,
+    eta: u32,
+    tau: usize,
+    /// Length of the commitment hash `c̃` in bytes
+    lambda_bytes: usize,
+    gamma1: u32,
+    gamma2: u32,
+    omega: usize,
+}
+
+pub(crate) const ML_DSA_44: Params = Params {
+    k: 4,
+    l: 4,
+    eta: 2,
+    tau: 39,
+    lambda_bytes: 32,
+    gamma1: 1 << 17,
+    gamma2: (Q - 1) / 88,
+    omega: 80,
+};
+
+pub(crate) const ML_DSA_65: Params = Params {
+    k: 6,
+    l: 5,
+    eta: 4,
+    tau: 49,
+    lambda_bytes: 48,
+    gamma1: 1 << 19,
+    gamma2: (Q - 1) / 32,
+    omega: 55,
+};
+
+pub(crate) const ML_DSA_87: Params = Params {
+    k: 8,
+    l: 7,
+    eta: 2,
+    tau: 60,
+    lambda_bytes: 64,
+    gamma1: 1 << 19,
+    gamma2: (Q - 1) / 32,
+    omega: 75,
+};
+
+/// Key generation seed `ξ` and signing randomness `rnd` length
+pub(crate) const SEED_LEN: usize = 32;
+
+impl Params {
+    pub(crate) fn public_key_len(self) -> usize {
+        32 + 320 * self.k
+    }
+
+    pub(crate) fn secret_key_len(self) -> usize {
+        128 + 32 * ((self.k + self.l) * self.eta_bits() as usize + D as usize * self.k)
+    }
+
+    pub(crate) fn signature_len(self) -> usize {
+        self.lambda_bytes + 32 * self.l * self.z_bits() as usize + self.omega + self.k
+    }
+
+    fn beta(self) -> u32 {
+        self.tau as u32 * self.eta
+    }
+
+    fn eta_bits(self) -> u32 {
+        if self.eta == 2 { 3 } else { 4 }
+    }
+
+    fn z_bits(self) -> u32 {
+        if self.gamma1 == 1 << 17 { 18 } else { 20 }
+    }
+
+    fn w1_bits(self) -> u32 {
+        if self.gamma2 == (Q - 1) / 88 { 6 } else { 4 }
+    }
+}
+
+/// `ML-DSA.KeyGen_internal(ξ)`, returning `(public_key, secret_key)`
+pub(crate) fn keypair_from_seed(
+    params: Params,
+    seed: &[u8; SEED_LEN],
+) -> (Vec<u8>, Zeroizing<Vec<u8>>) {
+    let mut expanded = Zeroizing::new([0u8; 128]);
+    shake256_into(
+        &[seed, &[params.k as u8, params.l as u8]],
+        expanded.as_mut_slice(),
+    );
+    let (rho, rest) = expanded.split_at(32);
+    let (rho_prime, key) = rest.split_at(64);
+
+    let matrix = expand_a(params, rho);
+    let (s1, s2) = expand_s(params, rho_prime);
+    let s1_hat = Zeroizing::new(s1.iter().map(ntt).collect::<Vec<_>>());
+    let t = matrix_multiply(params, &matrix, &s1_hat);
+
+    let mut t1 = vec![[0u32; N]; params.k];
+    let mut t0 = Zeroizing::new(vec![[0u32; N]; params.k]);
+    for i in 0..params.k {
+        let mut ti = ntt_inverse(&t[i]);
+        add_assign(&mut ti, &s2[i]);
+        for j in 0..N {
+            (t1[i][j], t0[i][j]) = power2round(ti[j]);
+        }
+    }
+
+    let mut public_key = Vec::with_capacity(params.public_key_len());
+    public_key.extend_from_slice(rho);
+    for poly in &t1 {
+        simple_bit_pack(poly, 10, &mut public_key);
+    }
+    let mut tr = [0u8; 64];
+    shake256_into(&[&public_key], &mut tr);
+
+    let mut secret_key = Zeroizing::new(Vec::with_capacity(params.secret_key_len()));
+    secret_key.extend_from_slice(rho);
+    secret_key.extend_from_slice(key);
+    secret_key.extend_from_slice(&tr);
+    for poly in s1.iter().chain(s2.iter()) {
+        bit_pack(poly, params.eta, params.eta_bits(), &mut secret_key);
+    }
+    for poly in t0.iter() {
+        bit_pack(poly, 1 << (D - 1), D, &mut secret_key);
+    }
+    (public_key, secret_key)
+}
+
+/// `ML-DSA.Sign_internal(sk, M', rnd)` with `M'` given in parts
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidKeySize` if the secret key has the wrong length.
+pub(crate) fn sign_internal(
+    params: Params,
+    secret_key: &[u8],
+    message: &[&[u8]],
+    rnd: &[u8; SEED_LEN],
+) -> Result<Vec<u8>> {
+    check_len(params.secret_key_len(), secret_key.len())?;
+    let (rho, rest) = secret_key.split_at(32);
+    let (key, rest) = rest.split_at(32);
+    let (tr, rest) = rest.split_at(64);
+    let eta_len = 32 * params.eta_bits() as usize;
+    let (s1_bytes, rest) = rest.split_at(eta_len * params.l);
+    let (s2_bytes, t0_bytes) = rest.split_at(eta_len * params.k);
+
+    let decode_ntt = |bytes: &[u8], chunk: usize, a: u32, bits: u32| {
+        Zeroizing::new(
+            bytes
+                .chunks(chunk)
+                .map(|b| ntt(&bit_unpack(b, a, bits)))
+                .collect::<Vec<_>>(),
+        )
+    };
+    let s1_hat = decode_ntt(s1_bytes, eta_len, params.eta, params.eta_bits());
+    let s2_hat = decode_ntt(s2_bytes, eta_len, params.eta, params.eta_bits());
+    let t0_hat = decode_ntt(t0_bytes, 32 * D as usize, 1 << (D - 1), D);
+    let matrix = expand_a(params, rho);
+
+    let mu = message_representative(tr, message);
+    let mut rho_second = Zeroizing::new([0u8; 64]);
+    shake256_into(&[key, rnd, &mu], rho_second.as_mut_slice());
+
+    let z_len = 32 * params.z_bits() as usize;
+    let mut kappa = 0u16;
+    loop {
+        let y = Zeroizing::new(
+            (0..params.l)
+                .map(|r| {
+                    let mut bytes = Zeroizing::new(vec![0u8; z_len]);
+                    let nonce = (kappa + r as u16).to_le_bytes();
+                    shake256_into(&[rho_second.as_slice(), &nonce], &mut bytes);
+                    bit_unpack(&bytes, params.gamma1, params.z_bits())
+                })
+                .collect::<Vec<_>>(),
+        );
+        kappa += params.l as u16;
+
+        let y_hat = Zeroizing::new(y.iter().map(ntt).collect::<Vec<_>>());
+        let w = Zeroizing::new(
+            matrix_multiply(params, &matrix, &y_hat)
+                .iter()
+                .map(ntt_inverse)
+                .collect::<Vec<_>>(),
+        );
+        let mut commitment = [0u8; 64];
+        let c_tilde = &mut commitment[..params.lambda_bytes];
+        commitment_hash(params, &mu, &w, |r| decompose(params, r).0, c_tilde);
+        let c_hat = ntt(&sample_in_ball(params, c_tilde));
+
+        let mut z = Zeroizing::new(vec![[0u32; N]; params.l]);
+        let mut rejected = false;
+        for i in 0..params.l {
+            z[i] = ntt_inverse(&pointwise(&c_hat, &s1_hat[i]));
+            add_assign(&mut z[i], &y[i]);
+            rejected |= infinity_norm(&z[i]) >= params.gamma1 - params.beta();
+        }
+        if rejected {
+            continue;
+        }
+
+        let mut hint = vec![[0u32; N]; params.k];
+        let mut ones = 0;
+        for i in 0..params.k {
+            let cs2 = ntt_inverse(&pointwise(&c_hat, &s2_hat[i]));
+            let mut r = w[i];
+            sub_assign(&mut r, &cs2);
+            let ct0 = ntt_inverse(&pointwise(&c_hat, &t0_hat[i]));
+            rejected |= r
+                .iter()
+                .any(|&x| centered(decompose(params, x).1) >= params.gamma2 - params.beta());
+            rejected |= infinity_norm(&ct0) >= params.gamma2;
+            for j in 0..N {
+                // MakeHint(-ct0, w - cs2 + ct0): does adding ct0 change the high bits?
+                let plus_ct0 = subtract_q(r[j] + ct0[j]);
+                let differs = decompose(params, plus_ct0).0 != decompose(params, r[j]).0;
+                hint[i][j] = u32::from(differs);
+                ones += usize::from(differs);
+            }
+        }
+        if rejected || ones > params.omega {
+            continue;
+        }
+
+        let mut signature = Vec::with_capacity(params.signature_len());
+        signature.extend_from_slice(c_tilde);
+        for poly in z.iter() {
+            bit_pack(poly, params.gamma1, params.z_bits(), &mut signature);
+        }
+        hint_bit_pack(params, &hint, &mut signature);
+        return Ok(signature);
+    }
+}
+
+/// `ML-DSA.Verify_internal(pk, M', σ)` with `M'` given in parts
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidKeySize` if the public key has the wrong length.
+pub(crate) fn verify_internal(
+    params: Params,
+    public_key: &[u8],
+    message: &[&[u8]],
+    signature: &[u8],
+) -> Result<bool> {
+    check_len(params.public_key_len(), public_key.len())?;
+    if signature.len() != params.signature_len() {
+        return Ok(false);
+    }
+    let (rho, t1_bytes) = public_key.split_at(32);
+    let (c_tilde, rest) = signature.split_at(params.lambda_bytes);
+    let (z_bytes, hint_bytes) = rest.split_at(32 * params.z_bits() as usize * params.l);
+    let Some(hint) = hint_bit_unpack(params, hint_bytes) else {
+        return Ok(false);
+    };
+    let z: Vec<Poly> = z_bytes
+        .chunks(32 * params.z_bits() as usize)
+        .map(|b| bit_unpack(b, params.gamma1, params.z_bits()))
+        .collect();
+    if z.iter()
+        .any(|poly| infinity_norm(poly) >= params.gamma1 - params.beta())
+    {
+        return Ok(false);
+    }
+
+    let matrix = expand_a(params, rho);
+    let mut tr = [0u8; 64];
+    shake256_into(&[public_key], &mut tr);
+    let mu = message_representative(&tr, message);
+    let c_hat = ntt(&sample_in_ball(params, c_tilde));
+
+    let z_hat: Vec<Poly> = z.iter().map(ntt).collect();
+    let az = matrix_multiply(params, &matrix, &z_hat);
+    let w_approx: Vec<Poly> = t1_bytes
+        .chunks(320)
+        .zip(&az)
+        .map(|(bytes, az)| {
+            let mut t1 = simple_bit_unpack(bytes, 10);
+            for coefficient in &mut t1 {
+                *coefficient <<= D;
+            }
+            let mut w = *az;
+            sub_assign(&mut w, &pointwise(&c_hat, &ntt(&t1)));
+            ntt_inverse(&w)
+        })
+        .collect();
+
+    let mut index = 0;
+    let mut expected = [0u8; 64];
+    let expected = &mut expected[..params.lambda_bytes];
+    commitment_hash(
+        params,
+        &mu,
+        &w_approx,
+        |r| {
+            let h = hint[index / N][index % N];
+            index += 1;
+            use_hint(params, h, r)
+        },
+        expected,
+    );
+    Ok(bool::from(expected.ct_eq(c_tilde)))
+}
+
+/// `μ = H(tr || M', 64)`
+fn message_representative(tr: &[u8], message: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = sha3::Shake256::default();
+    hasher.update(tr);
+    for part in message {
+        hasher.update(part);
+    }
+    let mut mu = [0u8; 64];
+    hasher.finalize_xof().read(&mut mu);
+    mu
+}
+
+/// `c̃ = H(μ || w1Encode(w1))`, with `w1` computed coefficient by coefficient from `w`
+fn commitment_hash(
+    params: Params,
+    mu: &[u8],
+    w: &[Poly],
+    mut high_bits: impl FnMut(u32) -> u32,
+    out: &mut [u8],
+) {
+    let mut w1_encoded = Vec::with_capacity(32 * params.k * params.w1_bits() as usize);
+    for poly in w {
+        let mut w1 = [0u32; N];
+        for (out, &r) in w1.iter_mut().zip(poly) {
+            *out = high_bits(r);
+        }
+        simple_bit_pack(&w1, params.w1_bits(), &mut w1_encoded);
+    }
+    shake256_into(&[mu, &w1_encoded], out);
+}
+
+/// `ExpandA(ρ)`: `Â[r][s] = RejNTTPoly(ρ || s || r)`, stored row-major
+fn expand_a(params: Params, rho: &[u8]) -> Vec<Poly> {
+    let mut matrix = Vec::with_capacity(params.k * params.l);
+    for r in 0..params.k {
+        for s in 0..params.l {
+            let mut xof = shake128(&[rho, &[s as u8, r as u8]]);
+            let mut poly = [0u32; N];
+            let mut filled = 0;
+            let mut bytes = [0u8; 3];
+            while filled < N {
+                xof.read(&mut bytes);
+                let candidate = u32::from_le_bytes([bytes[0], bytes[1], bytes[2] & 0x7F, 0]);
+                if candidate < Q {
+                    poly[filled] = candidate;
+                    filled += 1;
+                }
+            }
+            matrix.push(poly);
+        }
+    }
+    matrix
+}
+
+/// `ExpandS(ρ')`: secrets with coefficients in `[-η, η]`
+fn expand_s(params: Params, rho_prime: &[u8]) -> (Zeroizing<Vec<Poly>>, Zeroizing<Vec<Poly>>) {
+    let sample = |r: usize| {
+        let mut xof = shake256(&[rho_prime, &(r as u16).to_le_bytes()]);
+        let mut poly = [0u32; N];
+        let mut filled = 0;
+        let mut byte = [0u8; 1];
+        while filled < N {
+            xof.read(&mut byte);
+            for half in [byte[0] & 0x0F, byte[0] >> 4] {
+                let half = u32::from(half);
+                let value = match params.eta {
+                    // `half mod 5` as `half - 5⌊13·half / 64⌋`, exact for `half < 15`
+                    2 if half < 15 => Some(Q + 2 - (half - 5 * ((half * 13) >> 6))),
+                    4 if half < 9 => Some(Q + 4 - half),
+                    _ => None,
+                };
+                if let Some(value) = value.filter(|_| filled < N) {
+                    poly[filled] = subtract_q(value);
+                    filled += 1;
+                }
+            }
+        }
+        poly
+    };
+    let s1 = Zeroizing::new((0..params.l).map(sample).collect());
+    let s2 = Zeroizing::new((params.l..params.l + params.k).map(sample).collect());
+    (s1, s2)
+}
+
+/// `SampleInBall(c̃)`: `τ` coefficients of `±1`
+fn sample_in_ball(params: Params, c_tilde: &[u8]) -> Poly {
+    let mut xof = shake256(&[c_tilde]);
+    let mut signs = [0u8; 8];
+    xof.read(&mut signs);
+    let signs = u64::from_le_bytes(signs);
+
+    let mut c = [0u32; N];
+    let mut byte = [0u8; 1];
+    for i in N - params.tau..N {
+        let j = loop {
+            xof.read(&mut byte);
+            if usize::from(byte[0]) <= i {
+                break usize::from(byte[0]);
+            }
+        };
+        c[i] = c[j];
+        c[j] = if (signs >> (i + params.tau - N)) & 1 == 1 {
+            Q - 1
+        } else {
+            1
+        };
+    }
+    c
+}
+
+/// `Â ∘ v̂` for an NTT-domain vector of length `l`
+fn matrix_multiply(params: Params, matrix: &[Poly], vector: &[Poly]) -> Vec<Poly> {
+    (0..params.k)
+        .map(|i| {
+            let mut sum = [0u32; N];
+            for (a, v) in matrix[i * params.l..(i + 1) * params.l].iter().zip(vector) {
+                add_assign(&mut sum, &pointwise(a, v));
+            }
+            sum
+        })
+        .collect()
+}
+
+fn ntt(f: &Poly) -> Poly {
+    let mut w = *f;
+    let mut m = 0;
+    let mut len = 128;
+    while len >= 1 {
+        for start in (0..N).step_by(2 * len) {
+            m += 1;
+            let zeta = u64::from(ZETAS[m]);
+            for j in start..start + len {
+                let t = reduce(zeta * u64::from(w[j + len]));
+                w[j + len] = subtract_q(w[j] + Q - t);
+                w[j] = subtract_q(w[j] + t);
+            }
+        }
+        len /= 2;
+    }
+    w
+}
+
+fn ntt_inverse(f: &Poly) -> Poly {
+    let mut w = *f;
+    let mut m = N;
+    let mut len = 1;
+    while len < N {
+        for start in (0..N).step_by(2 * len) {
+            m -= 1;
+            let zeta = u64::from(ZETAS[m]);
+            for j in start..start + len {
+                let t = w[j];
+                let u = w[j + len];
+                w[j] = subtract_q(t + u);
+                w[j + len] = reduce(zeta * u64::from(subtract_q(u + Q - t)));
+            }
+        }
+        len *= 2;
+    }
+    for coefficient in &mut w {
+        *coefficient = reduce(u64::from(*coefficient) * INVERSE_256);
+    }
+    w
+}
+
+fn pointwise(f: &Poly, g: &Poly) -> Poly {
+    let mut h = [0u32; N];
+    for ((out, &a), &b) in h.iter_mut().zip(f).zip(g) {
+        *out = reduce(u64::from(a) * u64::from(b));
+    }
+    h
+}
+
+fn add_assign(f: &mut Poly, g: &Poly) {
+    for (a, &b) in f.iter_mut().zip(g) {
+        *a = subtract_q(*a + b);
+    }
+}
+
+fn sub_assign(f: &mut Poly, g: &Poly) {
+    for (a, &b) in f.iter_mut().zip(g) {
+        *a = subtract_q(*a + Q - b);
+    }
+}
+
+/// Absolute value of the representative of `x` in `(-q/2, q/2]`
+fn centered(x: u32) -> u32 {
+    if x > (Q - 1) / 2 { Q - x } else { x }
+}
+
+fn infinity_norm(f: &Poly) -> u32 {
+    f.iter().map(|&x| centered(x)).max().unwrap_or(0)
+}
+
+/// `x mod q` for `x < 2^64`
+fn reduce(x: u64) -> u32 {
+    let quotient = ((u128::from(x) * BARRETT_MULTIPLIER) >> 64) as u64;
+    subtract_q((x - quotient * u64::from(Q)) as u32)
+}
+
+/// `x mod q` for `x < 2q`
+fn subtract_q(x: u32) -> u32 {
+    let difference = x.wrapping_sub(Q);
+    // All ones when `x < q` and the subtraction wrapped
+    let wrapped = 0u32.wrapping_sub(difference >> 31);
+    difference.wrapping_add(Q & wrapped)
+}
+
+/// `x mod q` for `-q < x < q`
+fn from_centered(x: i32) -> u32 {
+    (x + ((x >> 31) & Q as i32)) as u32
+}
+
+/// `Power2Round(r)`, returning `(r1, r0 mod q)`
+fn power2round(r: u32) -> (u32, u32) {
+    let r1 = (r + (1 << (D - 1)) - 1) >> D;
+    (r1, from_centered(r as i32 - (r1 << D) as i32))
+}
+
+/// `Decompose(r)`, returning `(r1, r0 mod q)`
+///
+/// `r1` is `⌈r / 128⌉` scaled by a fixed-point reciprocal of `2γ2 / 128`, as in the reference
+/// implementation; the top value `(q - 1) / 2γ2` wraps to 0, which with the final
+/// subtraction of `q` from `r0` is the standard's `r - r0 = q - 1` case.
+fn decompose(params: Params, r: u32) -> (u32, u32) {
+    let r1 = (r + 127) >> 7;
+    let r1 = if params.gamma2 == (Q - 1) / 32 {
+        ((r1 * 1025 + (1 << 21)) >> 22) & 15
+    } else {
+        let r1 = (r1 * 11275 + (1 << 23)) >> 24;
+        // Clear `r1 = 44`, the only value above 43
+        r1 & !0u32.wrapping_sub(43u32.wrapping_sub(r1) >> 31)
+    };
+    let r0 = r as i32 - (r1 * 2 * params.gamma2) as i32;
+    // Subtract q when r0 > (q - 1) / 2
+    let r0 = r0 - ((((Q as i32 - 1) / 2 - r0) >> 31) & Q as i32);
+    (r1, from_centered(r0))
+}
+
+/// `UseHint(h, r)`, on public verification inputs
+fn use_hint(params: Params, h: u32, r: u32) -> u32 {
+    let m = (Q - 1) / (2 * params.gamma2);
+    let (r1, r0) = decompose(params, r);
+    if h == 0 {
+        r1
+    } else if r0 != 0 && r0 <= (Q - 1) / 2 {
+        if r1 + 1 == m { 0 } else { r1 + 1 }
+    } else if r1 == 0 {
+        m - 1
+    } else {
+        r1 - 1
+    }
+}
+
+/// `SimpleBitPack`: little-endian packing of `bits`-bit coefficients
+fn simple_bit_pack(f: &Poly, bits: u32, out: &mut Vec<u8>) {
+    let mut buffer = 0u64;
+    let mut filled = 0;
+    for &coefficient in f {
+        buffer |= u64::from(coefficient) << filled;
+        filled += bits;
+        while filled >= 8 {
+            out.push(buffer as u8);
+            buffer >>= 8;
+            filled -= 8;
+        }
+    }
+}
+
+fn simple_bit_unpack(bytes: &[u8], bits: u32) -> Poly {
+    let mut poly = [0u32; N];
+    let mut buffer = 0u64;
+    let mut filled = 0;
+    let mut index = 0;
+    for &byte in bytes {
+        buffer |= u64::from(byte) << filled;
+        filled += 8;
+        while filled >= bits && index < N {
+            poly[index] = (buffer & ((1 << bits) - 1)) as u32;
+            index += 1;
+            buffer >>= bits;
+            filled -= bits;
+        }
+    }
+    poly
+}
+
+/// `BitPack(w, a - 1 or a, b)`: packs `b - w` for coefficients in `[-(a - 1) or -a, b]`
+fn bit_pack(f: &Poly, b: u32, bits: u32, out: &mut Vec<u8>) {
+    let mut shifted = [0u32; N];
+    for (out, &x) in shifted.iter_mut().zip(f) {
+        *out = subtract_q(b + Q - x);
+    }
+    simple_bit_pack(&shifted, bits, out);
+}
+
+fn bit_unpack(bytes: &[u8], b: u32, bits: u32) -> Poly {
+    let mut poly = simple_bit_unpack(bytes, bits);
+    for x in &mut poly {
+        *x = subtract_q(b + Q - *x);
+    }
+    poly
+}
+
+fn hint_bit_pack(params: Params, hint: &[Poly], out: &mut Vec<u8>) {
+    let start = out.len();
+    out.resize(start + params.omega + params.k, 0);
+    let mut index = 0;
+    for (i, poly) in hint.iter().enumerate() {
+        for (j, &h) in poly.iter().enumerate() {
+            if h != 0 {
+                out[start + index] = j as u8;
+                index += 1;
+            }
+        }
+        out[start + params.omega + i] = index as u8;
+    }
+}
+
+/// `HintBitUnpack`, rejecting non-canonical encodings
+fn hint_bit_unpack(params: Params, bytes: &[u8]) -> Option<Vec<Poly>> {
+    let mut hint = vec![[0u32; N]; params.k];
+    let mut index = 0;
+    for (i, poly) in hint.iter_mut().enumerate() {
+        let end = usize::from(bytes[params.omega + i]);
+        if end < index || end > params.omega {
+            return None;
+        }
+        let first = index;
+        while index < end {
+            if index > first && bytes[index - 1] >= bytes[index] {
+                return None;
+            }
+            poly[usize::from(bytes[index])] = 1;
+            index += 1;
+        }
+    }
+    bytes[index..params.omega]
+        .iter()
+        .all(|&b| b == 0)
+        .then_some(hint)
+}
+
+fn check_len(expected: usize, actual: usize) -> Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(PqCryptoError::InvalidKeySize { expected, actual })
+    }
+}
@@ -0,0 +1,529 @@
+//! ML-KEM (FIPS 203)
+//!
+//! Coefficients are kept fully reduced in `[0, q)` as `u16`; products are formed in `u32`,
+//! which cannot overflow for `q = 3329`. Secret coefficients are never divided: reductions
+//! use Barrett multiplication and masked subtraction, and `Compress` a fixed-point
+//! reciprocal, so the running time does not depend on them.
+
+// Every narrowing cast below is of a value already reduced modulo q or 2^d; single-letter
+// names follow the notation of FIPS 203
+#![allow(clippy::cast_possible_truncation, clippy::many_single_char_names)]
+
+use super::{random_bytes, sha3_256, sha3_512, shake128, shake256_into};
+use crate::{PqCryptoError, Result};
+use sha3::digest::XofReader;
+use subtle::{ConditionallySelectable, ConstantTimeEq};
+use zeroize::Zeroizing;
+
+const N: usize = 256;
+const Q: u32 = 3329;
+
+/// `128^-1 mod q`, scaling the inverse NTT
+const INVERSE_128: u32 = 3303;
+
+/// `⌊2^36 / q⌋`, the Barrett reduction multiplier
+const BARRETT_MULTIPLIER: u64 = (1 << 36) / Q as u64;
+
+/// `⌈2^39 / 2q⌉`, for which `⌊n·M / 2^39⌋ = ⌊n / 2q⌋` for every `n < 2^25`
+const COMPRESS_MULTIPLIER: u64 = (1u64 << 39).div_ceil(2 * Q as u64);
+
+type Poly = [u16; N];
+
+/// `ζ^BitRev7(i) mod q` for `ζ = 17`
+const ZETAS: [u16; 128] = {
+    let mut zetas = [0u16; 128];
+    let mut i = 0;
+    while i < 128 {
+        zetas[i] = pow_mod(17, bit_rev7(i)) as u16;
+        i += 1;
+    }
+    zetas
+};
+
+/// `ζ^(2·BitRev7(i)+1) mod q`, the moduli of the degree-two base case products
+const GAMMAS: [u16; 128] = {
+    let mut gammas = [0u16; 128];
+    let mut i = 0;
+    while i < 128 {
+        gammas[i] = pow_mod(17, 2 * bit_rev7(i) + 1) as u16;
+        i += 1;
+    }
+    gammas
+};
+
+const fn bit_rev7(i: usize) -> usize {
+    let mut reversed = 0;
+    let mut bit = 0;
+    while bit < 7 {
+        reversed |= ((i >> bit) & 1) << (6 - bit);
+        bit += 1;
+    }
+    reversed
+}
+
+const fn pow_mod(base: u32, exponent: usize) -> u32 {
+    let mut result = 1;
+    let mut i = 0;
+    while i < exponent {
+        result = result * base % Q;
+        i += 1;
+    }
+    result
+}
+
+/// ML-KEM parameter set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Params {
+    k: usize,
+    eta1: usize,
+    eta2: usize,
+    du: u32,
+    dv: u32,
+}
+
+pub(crate) const ML_KEM_512: Params = Params {
+    k: 2,
+    eta1: 3,
+    eta2: 2,
+    du: 10,
+    dv: 4,
+};
+
+pub(crate) const ML_KEM_768: Params = Params {
+    k: 3,
+    eta1: 2,
+    eta2: 2,
+    du: 10,
+    dv: 4,
+};
+
+pub(crate) const ML_KEM_1024: Params = Params {
+    k: 4,
+    eta1: 2,
+    eta2: 2,
+    du: 11,
+    dv: 5,
+};
+
+/// Seed length: `d || z`
+pub(crate) const SEED_LEN: usize = 64;
+
+/// Encapsulation randomness `m` and shared secret length
+pub(crate) const MESSAGE_LEN: usize = 32;
+
+impl Params {
+    pub(crate) fn encapsulation_key_len(self) -> usize {
+        384 * self.k + 32
+    }
+
+    pub(crate) fn decapsulation_key_len(self) -> usize {
+        768 * self.k + 96
+    }
+
+    pub(crate) fn ciphertext_len(self) -> usize {
+        32 * (self.du as usize * self.k + self.dv as usize)
+    }
+}
+
+/// `ML-KEM.KeyGen`, returning `(encapsulation_key, decapsulation_key)`
+pub(crate) fn keypair(params: Params) -> (Vec<u8>, Zeroizing<Vec<u8>>) {
+    let mut seed = Zeroizing::new([0u8; SEED_LEN]);
+    random_bytes(seed.as_mut_slice());
+    keypair_from_seed(params, &seed)
+}
+
+/// `ML-KEM.KeyGen_internal(d, z)` from the 64-byte seed `d || z`
+pub(crate) fn keypair_from_seed(
+    params: Params,
+    seed: &[u8; SEED_LEN],
+) -> (Vec<u8>, Zeroizing<Vec<u8>>) {
+    let (d, z) = seed.split_at(32);
+    let (ek, dk_pke) = pke_keygen(params, d);
+    let mut dk = Zeroizing::new(Vec::with_capacity(params.decapsulation_key_len()));
+    dk.extend_from_slice(&dk_pke);
+    dk.extend_from_slice(&ek);
+    dk.extend_from_slice(&sha3_256(&[&ek]));
+    dk.extend_from_slice(z);
+    (ek, dk)
+}
+
+/// `ML-KEM.Encaps`, returning `(ciphertext, shared_secret)`
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidKeySize` or `PqCryptoError::InvalidKey` if the
+/// encapsulation key fails the FIPS 203 input checks.
+pub(crate) fn encapsulate(
+    params: Params,
+    ek: &[u8],
+) -> Result<(Vec<u8>, Zeroizing<[u8; MESSAGE_LEN]>)> {
+    let mut m = Zeroizing::new([0u8; MESSAGE_LEN]);
+    random_bytes(m.as_mut_slice());
+    encapsulate_deterministic(params, ek, &m)
+}
+
+/// `ML-KEM.Encaps_internal(ek, m)`
+///
+/// # Errors
+///
+/// As [`encapsulate`].
+pub(crate) fn encapsulate_deterministic(
+    params: Params,
+    ek: &[u8],
+    m: &[u8; MESSAGE_LEN],
+) -> Result<(Vec<u8>, Zeroizing<[u8; MESSAGE_LEN]>)> {
+    check_len(params.encapsulation_key_len(), ek.len())?;
+    check_modulus(params, ek)?;
+
+    let g = Zeroizing::new(sha3_512(&[m, &sha3_256(&[ek])]));
+    let (shared, r) = g.split_at(32);
+    let ciphertext = pke_encrypt(params, ek, m, r);
+    let mut shared_secret = Zeroizing::new([0u8; MESSAGE_LEN]);
+    shared_secret.copy_from_slice(shared);
+    Ok((ciphertext, shared_secret))
+}
+
+/// `ML-KEM.Decaps`, with implicit rejection of invalid ciphertexts
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidKeySize` for wrong key lengths,
+/// `PqCryptoError::InvalidKey` if the key fails the hash check and
+/// `PqCryptoError::InvalidCiphertext` for a wrong ciphertext length.
+pub(crate) fn decapsulate(
+    params: Params,
+    dk: &[u8],
+    ciphertext: &[u8],
+) -> Result<Zeroizing<[u8; MESSAGE_LEN]>> {
+    check_len(params.decapsulation_key_len(), dk.len())?;
+    if ciphertext.len() != params.ciphertext_len() {
+        return Err(PqCryptoError::InvalidCiphertext(format!(
+            "ML-KEM ciphertext must be {} bytes, got {}",
+            params.ciphertext_len(),
+            ciphertext.len()
+        )));
+    }
+
+    let (dk_pke, rest) = dk.split_at(384 * params.k);
+    let (ek, rest) = rest.split_at(params.encapsulation_key_len());
+    let (h, z) = rest.split_at(32);
+    if sha3_256(&[ek]).ct_eq(h).unwrap_u8() == 0 {
+        return Err(PqCryptoError::InvalidKey(
+            "ML-KEM decapsulation key fails its hash check".to_string(),
+        ));
+    }
+
+    let m = pke_decrypt(params, dk_pke, ciphertext);
+    let g = Zeroizing::new(sha3_512(&[m.as_slice(), h]));
+    let (shared, r) = g.split_at(32);
+    let mut rejected = Zeroizing::new([0u8; MESSAGE_LEN]);
+    shake256_into(&[z, ciphertext], rejected.as_mut_slice());
+
+    let reencrypted = pke_encrypt(params, ek, m.as_slice(), r);
+    let valid = reencrypted.ct_eq(ciphertext);
+    let mut shared_secret = Zeroizing::new([0u8; MESSAGE_LEN]);
+    for ((out, &accepted), &rejected) in shared_secret.iter_mut().zip(shared).zip(rejected.iter()) {
+        *out = u8::conditional_select(&rejected, &accepted, valid);
+    }
+    Ok(shared_secret)
+}
+
+/// `K-PKE.KeyGen(d)`, returning `(ek, dk_pke)`
+fn pke_keygen(params: Params, d: &[u8]) -> (Vec<u8>, Zeroizing<Vec<u8>>) {
+    let k = params.k;
+    let g = Zeroizing::new(sha3_512(&[d, &[k as u8]]));
+    let (rho, sigma) = g.split_at(32);
+    let matrix = sample_matrix(rho, k);
+
+    let mut nonce = 0u8;
+    let mut s = Zeroizing::new(vec![[0u16; N]; k]);
+    for poly in s.iter_mut() {
+        *poly = sample_cbd(sigma, &mut nonce, params.eta1);
+        ntt(poly);
+    }
+    let mut e = Zeroizing::new(vec![[0u16; N]; k]);
+    for poly in e.iter_mut() {
+        *poly = sample_cbd(sigma, &mut nonce, params.eta1);
+        ntt(poly);
+    }
+
+    let mut ek = Vec::with_capacity(params.encapsulation_key_len());
+    for i in 0..k {
+        let mut t = e[i];
+        for j in 0..k {
+            add_assign(&mut t, &multiply_ntts(&matrix[i * k + j], &s[j]));
+        }
+        byte_encode(&t, 12, &mut ek);
+    }
+    ek.extend_from_slice(rho);
+
+    let mut dk = Zeroizing::new(Vec::with_capacity(384 * k));
+    for poly in s.iter() {
+        byte_encode(poly, 12, &mut dk);
+    }
+    (ek, dk)
+}
+
+/// `K-PKE.Encrypt(ek, m, r)`
+fn pke_encrypt(params: Params, ek: &[u8], m: &[u8], r: &[u8]) -> Vec<u8> {
+    let k = params.k;
+    let (t_bytes, rho) = ek.split_at(384 * k);
+    let t: Vec<Poly> = t_bytes.chunks(384).map(|b| byte_decode(b, 12)).collect();
+    let matrix = sample_matrix(rho, k);
+
+    let mut nonce = 0u8;
+    let mut y = Zeroizing::new(vec![[0u16; N]; k]);
+    for poly in y.iter_mut() {
+        *poly = sample_cbd(r, &mut nonce, params.eta1);
+        ntt(poly);
+    }
+    let mut ciphertext = Vec::with_capacity(params.ciphertext_len());
+    for i in 0..k {
+        let mut u = [0u16; N];
+        for j in 0..k {
+            add_assign(&mut u, &multiply_ntts(&matrix[j * k + i], &y[j]));
+        }
+        ntt_inverse(&mut u);
+        add_assign(&mut u, &sample_cbd(r, &mut nonce, params.eta2));
+        compress(&mut u, params.du);
+        byte_encode(&u, params.du, &mut ciphertext);
+    }
+
+    let mut v = [0u16; N];
+    for (t, y) in t.iter().zip(y.iter()) {
+        add_assign(&mut v, &multiply_ntts(t, y));
+    }
+    ntt_inverse(&mut v);
+    add_assign(&mut v, &sample_cbd(r, &mut nonce, params.eta2));
+    let mut mu = byte_decode(m, 1);
+    decompress(&mut mu, 1);
+    add_assign(&mut v, &mu);
+    compress(&mut v, params.dv);
+    byte_encode(&v, params.dv, &mut ciphertext);
+    ciphertext
+}
+
+/// `K-PKE.Decrypt(dk_pke, c)`
+fn pke_decrypt(params: Params, dk_pke: &[u8], ciphertext: &[u8]) -> Zeroizing<[u8; 32]> {
+    let (c1, c2) = ciphertext.split_at(32 * params.du as usize * params.k);
+    let mut w = [0u16; N];
+    for (u_bytes, s_bytes) in c1.chunks(32 * params.du as usize).zip(dk_pke.chunks(384)) {
+        let mut u = byte_decode(u_bytes, params.du);
+        decompress(&mut u, params.du);
+        ntt(&mut u);
+        let s = Zeroizing::new(byte_decode(s_bytes, 12));
+        add_assign(&mut w, &multiply_ntts(&s, &u));
+    }
+    ntt_inverse(&mut w);
+
+    let mut v = byte_decode(c2, params.dv);
+    decompress(&mut v, params.dv);
+    for (v, w) in v.iter_mut().zip(w) {
+        *v = subtract_q(u32::from(*v) + Q - u32::from(w));
+    }
+    compress(&mut v, 1);
+    let mut encoded = Vec::with_capacity(32);
+    byte_encode(&v, 1, &mut encoded);
+    let mut m = Zeroizing::new([0u8; 32]);
+    m.copy_from_slice(&encoded);
+    m
+}
+
+/// `Â[i][j] = SampleNTT(ρ || j || i)`, stored row-major
+fn sample_matrix(rho: &[u8], k: usize) -> Vec<Poly> {
+    let mut matrix = Vec::with_capacity(k * k);
+    for i in 0..k {
+        for j in 0..k {
+            matrix.push(sample_ntt(rho, j as u8, i as u8));
+        }
+    }
+    matrix
+}
+
+/// `SampleNTT`: rejection sampling of 12-bit values from SHAKE128
+fn sample_ntt(rho: &[u8], j: u8, i: u8) -> Poly {
+    let mut xof = shake128(&[rho, &[j, i]]);
+    let mut poly = [0u16; N];
+    let mut filled = 0;
+    let mut bytes = [0u8; 3];
+    while filled < N {
+        xof.read(&mut bytes);
+        let d1 = u16::from(bytes[0]) | (u16::from(bytes[1] & 0x0F) << 8);
+        let d2 = u16::from(bytes[1] >> 4) | (u16::from(bytes[2]) << 4);
+        for candidate in [d1, d2] {
+            if u32::from(candidate) < Q && filled < N {
+                poly[filled] = candidate;
+                filled += 1;
+            }
+        }
+    }
+    poly
+}
+
+/// `SamplePolyCBD_η(PRF_η(seed, nonce))`, advancing the nonce
+fn sample_cbd(seed: &[u8], nonce: &mut u8, eta: usize) -> Poly {
+    let mut bytes = Zeroizing::new(vec![0u8; 64 * eta]);
+    shake256_into(&[seed, &[*nonce]], &mut bytes);
+    *nonce += 1;
+
+    let bit = |index: usize| u32::from((bytes[index / 8] >> (index % 8)) & 1);
+    let mut poly = [0u16; N];
+    for (i, coefficient) in poly.iter_mut().enumerate() {
+        let (mut x, mut y) = (0, 0);
+        for j in 0..eta {
+            x += bit(2 * i * eta + j);
+            y += bit(2 * i * eta + eta + j);
+        }
+        *coefficient = subtract_q(x + Q - y);
+    }
+    poly
+}
+
+fn ntt(f: &mut Poly) {
+    let mut i = 1;
+    let mut len = 128;
+    while len >= 2 {
+        for start in (0..N).step_by(2 * len) {
+            let zeta = u32::from(ZETAS[i]);
+            i += 1;
+            for j in start..start + len {
+                let t = u32::from(reduce(zeta * u32::from(f[j + len])));
+                f[j + len] = subtract_q(u32::from(f[j]) + Q - t);
+                f[j] = subtract_q(u32::from(f[j]) + t);
+            }
+        }
+        len /= 2;
+    }
+}
+
+fn ntt_inverse(f: &mut Poly) {
+    let mut i = 127;
+    let mut len = 2;
+    while len <= 128 {
+        for start in (0..N).step_by(2 * len) {
+            let zeta = u32::from(ZETAS[i]);
+            i -= 1;
+            for j in start..start + len {
+                let t = u32::from(f[j]);
+                let u = u32::from(f[j + len]);
+                f[j] = subtract_q(t + u);
+                f[j + len] = reduce(zeta * u32::from(subtract_q(u + Q - t)));
+            }
+        }
+        len *= 2;
+    }
+    for coefficient in f.iter_mut() {
+        *coefficient = reduce(u32::from(*coefficient) * INVERSE_128);
+    }
+}
+
+/// `MultiplyNTTs`: 128 products in `Z_q[X]/(X^2 - γ)`
+fn multiply_ntts(f: &Poly, g: &Poly) -> Poly {
+    let mut h = [0u16; N];
+    for (i, &gamma) in GAMMAS.iter().enumerate() {
+        let (a0, a1) = (u32::from(f[2 * i]), u32::from(f[2 * i + 1]));
+        let (b0, b1) = (u32::from(g[2 * i]), u32::from(g[2 * i + 1]));
+        h[2 * i] = reduce(a0 * b0 + u32::from(reduce(a1 * b1)) * u32::from(gamma));
+        h[2 * i + 1] = reduce(a0 * b1 + a1 * b0);
+    }
+    h
+}
+
+fn add_assign(f: &mut Poly, g: &Poly) {
+    for (a, &b) in f.iter_mut().zip(g) {
+        *a = subtract_q(u32::from(*a) + u32::from(b));
+    }
+}
+
+/// `x mod q` for any `u32`
+///
+/// The estimated quotient is `⌊x / q⌋` or one less, so one masked subtraction finishes.
+fn reduce(x: u32) -> u16 {
+    let quotient = ((u64::from(x) * BARRETT_MULTIPLIER) >> 36) as u32;
+    subtract_q(x - quotient * Q)
+}
+
+/// `x mod q` for `x < 2q`
+fn subtract_q(x: u32) -> u16 {
+    let difference = x.wrapping_sub(Q);
+    // All ones when `x < q` and the subtraction wrapped
+    let wrapped = 0u32.wrapping_sub(difference >> 31);
+    difference.wrapping_add(Q & wrapped) as u16
+}
+
+/// `Compress_d`: `round(2^d / q · x) mod 2^d`, as `⌊(2^(d+1)·x + q) / 2q⌋ mod 2^d`
+fn compress(f: &mut Poly, d: u32) {
+    for x in f.iter_mut() {
+        let numerator = (u64::from(*x) << (d + 1)) + u64::from(Q);
+        *x = (((numerator * COMPRESS_MULTIPLIER) >> 39) & ((1 << d) - 1)) as u16;
+    }
+}
+
+/// `Decompress_d`: `round(q / 2^d · y)`
+fn decompress(f: &mut Poly, d: u32) {
+    for y in f.iter_mut() {
+        *y = ((u32::from(*y) * Q + (1 << (d - 1))) >> d) as u16;
+    }
+}
+
+/// `ByteEncode_d`: little-endian packing of `d`-bit coefficients
+fn byte_encode(f: &Poly, d: u32, out: &mut Vec<u8>) {
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &coefficient in f {
+        buffer |= u32::from(coefficient) << bits;
+        bits += d;
+        while bits >= 8 {
+            out.push(buffer as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    }
+}
+
+/// `ByteDecode_d`, reducing 12-bit values modulo q
+fn byte_decode(bytes: &[u8], d: u32) -> Poly {
+    let mut poly = [0u16; N];
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    let mut index = 0;
+    for &byte in bytes {
+        buffer |= u32::from(byte) << bits;
+        bits += 8;
+        while bits >= d && index < N {
+            let value = buffer & ((1 << d) - 1);
+            poly[index] = if d == 12 {
+                subtract_q(value)
+            } else {
+                value as u16
+            };
+            index += 1;
+            buffer >>= d;
+            bits -= d;
+        }
+    }
+    poly
+}
+
+/// Encapsulation key modulus check: every coefficient must already be reduced
+fn check_modulus(params: Params, ek: &[u8]) -> Result<()> {
+    let mut reencoded = Vec::with_capacity(384);
+    for chunk in ek[..384 * params.k].chunks(384) {
+        reencoded.clear();
+        byte_encode(&byte_decode(chunk, 12), 12, &mut reencoded);
+        if reencoded != chunk {
+            return Err(PqCryptoError::InvalidKey(
+                "ML-KEM encapsulation key has unreduced coefficients".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_len(expected: usize, actual: usize) -> Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(PqCryptoError::InvalidKeySize { expected, actual })
+    }
+}
@@ -0,0 +1,56 @@
+//! Pure-Rust FIPS 203 (ML-KEM), FIPS 204 (ML-DSA) and FIPS 205 (SLH-DSA)
+//!
+//! Each parameter set is a `Params` constant; the functions here take raw byte encodings so
+//! the backend layer only maps crate algorithms to parameter sets.
+
+pub(crate) mod ml_dsa;
+pub(crate) mod ml_kem;
+pub(crate) mod slh_dsa;
+
+use sha3::digest::{Digest, ExtendableOutput, Update, XofReader};
+use sha3::{Sha3_256, Sha3_512, Shake128, Shake128Reader, Shake256, Shake256Reader};
+
+/// SHAKE128 over the concatenation of `parts`, as a stream
+fn shake128(parts: &[&[u8]]) -> Shake128Reader {
+    let mut hasher = Shake128::default();
+    for part in parts {
+        Update::update(&mut hasher, part);
+    }
+    hasher.finalize_xof()
+}
+
+/// SHAKE256 over the concatenation of `parts`, as a stream
+fn shake256(parts: &[&[u8]]) -> Shake256Reader {
+    let mut hasher = Shake256::default();
+    for part in parts {
+        Update::update(&mut hasher, part);
+    }
+    hasher.finalize_xof()
+}
+
+/// SHAKE256 over the concatenation of `parts`, filling `out`
+fn shake256_into(parts: &[&[u8]], out: &mut [u8]) {
+    shake256(parts).read(out);
+}
+
+fn sha3_256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    for part in parts {
+        Digest::update(&mut hasher, part);
+    }
+    hasher.finalize().into()
+}
+
+fn sha3_512(parts: &[&[u8]]) -> [u8; 64] {
+    let mut hasher = Sha3_512::new();
+    for part in parts {
+        Digest::update(&mut hasher, part);
+    }
+    hasher.finalize().into()
+}
+
+/// Fill `out` from the operating system's random number generator
+pub(super) fn random_bytes(out: &mut [u8]) {
+    use rand::RngCore;
+    rand::rng().fill_bytes(out);
+}
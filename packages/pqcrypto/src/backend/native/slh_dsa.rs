@@ -0,0 +1,716 @@
+//! SLH-DSA (FIPS 205), SHA2 parameter sets
+//!
+//! Tree nodes are recomputed on demand rather than cached, following the recursive
+//! definitions in the standard. `F` and `PRF` always use SHA-256; security categories 3 and 5
+//! use SHA-512 for `H`, `T`, `H_msg` and `PRF_msg`.
+
+// Every narrowing cast below is of a tree index or count bounded by the parameter set
+#![allow(clippy::cast_possible_truncation)]
+
+use crate::{PqCryptoError, Result};
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use sha2::digest::Digest;
+use sha2::{Sha256, Sha512};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+/// Winternitz parameter `lg_w`
+const LG_W: u32 = 4;
+const W: u32 = 1 << LG_W;
+
+/// Checksum length in base-`w` digits
+const LEN2: usize = 3;
+
+/// SLH-DSA parameter set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Params {
+    n: usize,
+    h: usize,
+    d: usize,
+    /// Height of each XMSS tree, `h / d`
+    hp: usize,
+    a: usize,
+    k: usize,
+    m: usize,
+}
+
+pub(crate) const SLH_DSA_SHA2_128S: Params = Params {
+    n: 16,
+    h: 63,
+    d: 7,
+    hp: 9,
+    a: 12,
+    k: 14,
+    m: 30,
+};
+
+pub(crate) const SLH_DSA_SHA2_128F: Params = Params {
+    n: 16,
+    h: 66,
+    d: 22,
+    hp: 3,
+    a: 6,
+    k: 33,
+    m: 34,
+};
+
+pub(crate) const SLH_DSA_SHA2_192S: Params = Params {
+    n: 24,
+    h: 63,
+    d: 7,
+    hp: 9,
+    a: 14,
+    k: 17,
+    m: 39,
+};
+
+pub(crate) const SLH_DSA_SHA2_192F: Params = Params {
+    n: 24,
+    h: 66,
+    d: 22,
+    hp: 3,
+    a: 8,
+    k: 33,
+    m: 42,
+};
+
+pub(crate) const SLH_DSA_SHA2_256S: Params = Params {
+    n: 32,
+    h: 64,
+    d: 8,
+    hp: 8,
+    a: 14,
+    k: 22,
+    m: 47,
+};
+
+pub(crate) const SLH_DSA_SHA2_256F: Params = Params {
+    n: 32,
+    h: 68,
+    d: 17,
+    hp: 4,
+    a: 9,
+    k: 35,
+    m: 49,
+};
+
+impl Params {
+    /// Key generation seed length: `SK.seed || SK.prf || PK.seed`
+    pub(crate) fn seed_len(self) -> usize {
+        3 * self.n
+    }
+
+    /// Length of the additional randomness `addrnd` for hedged signing
+    pub(crate) fn randomness_len(self) -> usize {
+        self.n
+    }
+
+    pub(crate) fn public_key_len(self) -> usize {
+        2 * self.n
+    }
+
+    pub(crate) fn secret_key_len(self) -> usize {
+        4 * self.n
+    }
+
+    pub(crate) fn signature_len(self) -> usize {
+        (1 + self.k * (1 + self.a) + self.h + self.d * self.len()) * self.n
+    }
+
+    /// WOTS+ chains per key: `len1 + len2`
+    fn len(self) -> usize {
+        2 * self.n + LEN2
+    }
+
+    fn wots_len(self) -> usize {
+        self.len() * self.n
+    }
+
+    fn xmss_len(self) -> usize {
+        (self.len() + self.hp) * self.n
+    }
+
+    fn fors_len(self) -> usize {
+        self.k * (1 + self.a) * self.n
+    }
+}
+
+/// Address types
+const WOTS_HASH: u8 = 0;
+const WOTS_PK: u8 = 1;
+const TREE: u8 = 2;
+const FORS_TREE: u8 = 3;
+const FORS_ROOTS: u8 = 4;
+const WOTS_PRF: u8 = 5;
+const FORS_PRF: u8 = 6;
+
+/// Hash function address `ADRS`, 32 bytes uncompressed
+#[derive(Clone, Copy, Default)]
+struct Address([u8; 32]);
+
+impl Address {
+    fn set_layer(&mut self, layer: u32) {
+        self.0[0..4].copy_from_slice(&layer.to_be_bytes());
+    }
+
+    fn set_tree(&mut self, tree: u64) {
+        self.0[4..8].fill(0);
+        self.0[8..16].copy_from_slice(&tree.to_be_bytes());
+    }
+
+    /// `setTypeAndClear`
+    fn set_type(&mut self, address_type: u8) {
+        self.0[16..19].fill(0);
+        self.0[19] = address_type;
+        self.0[20..32].fill(0);
+    }
+
+    fn set_keypair(&mut self, keypair: u32) {
+        self.0[20..24].copy_from_slice(&keypair.to_be_bytes());
+    }
+
+    fn keypair(self) -> u32 {
+        u32::from_be_bytes([self.0[20], self.0[21], self.0[22], self.0[23]])
+    }
+
+    /// Chain address, or tree height
+    fn set_chain(&mut self, chain: u32) {
+        self.0[24..28].copy_from_slice(&chain.to_be_bytes());
+    }
+
+    /// Hash address, or tree index
+    fn set_hash(&mut self, hash: u32) {
+        self.0[28..32].copy_from_slice(&hash.to_be_bytes());
+    }
+
+    fn hash(self) -> u32 {
+        u32::from_be_bytes([self.0[28], self.0[29], self.0[30], self.0[31]])
+    }
+
+    /// `ADRSc`, the 22-byte compressed address used with SHA2
+    fn compressed(&self) -> [u8; 22] {
+        let mut compressed = [0u8; 22];
+        compressed[0] = self.0[3];
+        compressed[1..9].copy_from_slice(&self.0[8..16]);
+        compressed[9] = self.0[19];
+        compressed[10..22].copy_from_slice(&self.0[20..32]);
+        compressed
+    }
+
+    /// A copy with type `address_type` and the same key pair address
+    fn with_type_keeping_keypair(self, address_type: u8) -> Self {
+        let mut address = self;
+        address.set_type(address_type);
+        address.set_keypair(self.keypair());
+        address
+    }
+}
+
+/// Tweakable hash functions keyed by `PK.seed`, with the padded seed block pre-absorbed
+struct Hashes<'a> {
+    params: Params,
+    sk_seed: &'a [u8],
+    sha256: Sha256,
+    sha512: Sha512,
+}
+
+impl<'a> Hashes<'a> {
+    fn new(params: Params, pk_seed: &[u8], sk_seed: &'a [u8]) -> Self {
+        let mut sha256 = Sha256::new();
+        sha256.update(pk_seed);
+        sha256.update(&[0u8; 64][..64 - params.n]);
+        let mut sha512 = Sha512::new();
+        sha512.update(pk_seed);
+        sha512.update(&[0u8; 128][..128 - params.n]);
+        Self {
+            params,
+            sk_seed,
+            sha256,
+            sha512,
+        }
+    }
+
+    /// `PRF(PK.seed, SK.seed, ADRS)`
+    fn prf(&self, address: &Address) -> Zeroizing<Vec<u8>> {
+        let mut hasher = self.sha256.clone();
+        hasher.update(address.compressed());
+        hasher.update(self.sk_seed);
+        Zeroizing::new(hasher.finalize()[..self.params.n].to_vec())
+    }
+
+    /// `F(PK.seed, ADRS, M1)`
+    fn f(&self, address: &Address, input: &[u8]) -> Vec<u8> {
+        let mut hasher = self.sha256.clone();
+        hasher.update(address.compressed());
+        hasher.update(input);
+        hasher.finalize()[..self.params.n].to_vec()
+    }
+
+    /// `H(PK.seed, ADRS, M2)` and `T_l(PK.seed, ADRS, M)`
+    fn t(&self, address: &Address, inputs: &[&[u8]]) -> Vec<u8> {
+        if self.params.n == 16 {
+            let mut hasher = self.sha256.clone();
+            hasher.update(address.compressed());
+            for input in inputs {
+                hasher.update(input);
+            }
+            hasher.finalize()[..self.params.n].to_vec()
+        } else {
+            let mut hasher = self.sha512.clone();
+            hasher.update(address.compressed());
+            for input in inputs {
+                hasher.update(input);
+            }
+            hasher.finalize()[..self.params.n].to_vec()
+        }
+    }
+
+    /// `chain(X, i, s, PK.seed, ADRS)`
+    fn chain(&self, input: &[u8], start: u32, steps: u32, address: &mut Address) -> Vec<u8> {
+        let mut value = input.to_vec();
+        for j in start..start + steps {
+            address.set_hash(j);
+            value = self.f(address, &value);
+        }
+        value
+    }
+}
+
+/// `PRF_msg(SK.prf, opt_rand, M)`
+fn prf_msg(params: Params, sk_prf: &[u8], opt_rand: &[u8], message: &[&[u8]]) -> Result<Vec<u8>> {
+    fn mac<M: Mac + KeyInit>(key: &[u8], inputs: &[&[u8]], message: &[&[u8]]) -> Result<Vec<u8>> {
+        let mut mac = <M as KeyInit>::new_from_slice(key)
+            .map_err(|e| PqCryptoError::internal(format!("PRF_msg key: {e}")))?;
+        for part in inputs.iter().chain(message) {
+            mac.update(part);
+        }
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+    let mut randomizer = if params.n == 16 {
+        mac::<Hmac<Sha256>>(sk_prf, &[opt_rand], message)?
+    } else {
+        mac::<Hmac<Sha512>>(sk_prf, &[opt_rand], message)?
+    };
+    randomizer.truncate(params.n);
+    Ok(randomizer)
+}
+
+/// `H_msg(R, PK.seed, PK.root, M)`: MGF1 over `R || PK.seed || Hash(R || PK.seed || PK.root || M)`
+fn h_msg(params: Params, randomizer: &[u8], public_key: &[u8], message: &[&[u8]]) -> Vec<u8> {
+    fn expand<D: Digest>(seed_prefix: &[&[u8]], message: &[&[u8]], m: usize) -> Vec<u8> {
+        let mut inner = D::new();
+        for part in seed_prefix.iter().chain(message) {
+            inner.update(part);
+        }
+        let inner = inner.finalize();
+        let mut out = Vec::with_capacity(m + 64);
+        let mut counter = 0u32;
+        while out.len() < m {
+            let mut block = D::new();
+            block.update(seed_prefix[0]);
+            block.update(seed_prefix[1]);
+            block.update(&inner);
+            block.update(counter.to_be_bytes());
+            out.extend_from_slice(&block.finalize());
+            counter += 1;
+        }
+        out.truncate(m);
+        out
+    }
+    let (pk_seed, pk_root) = public_key.split_at(params.n);
+    let prefix = [randomizer, pk_seed, pk_root];
+    if params.n == 16 {
+        expand::<Sha256>(&prefix, message, params.m)
+    } else {
+        expand::<Sha512>(&prefix, message, params.m)
+    }
+}
+
+/// `base_2b(X, b, out_len)`
+fn base_2b(input: &[u8], b: usize, out_len: usize) -> Vec<u32> {
+    let mut out = Vec::with_capacity(out_len);
+    let mut bytes = input.iter();
+    let mut total = 0u64;
+    let mut bits = 0;
+    while out.len() < out_len {
+        while bits < b {
+            total = (total << 8) | u64::from(bytes.next().copied().unwrap_or(0));
+            bits += 8;
+        }
+        bits -= b;
+        out.push(((total >> bits) & ((1 << b) - 1)) as u32);
+    }
+    out
+}
+
+/// Message digits of `M` followed by its checksum digits
+fn wots_digits(params: Params, message: &[u8]) -> Vec<u32> {
+    let mut digits = base_2b(message, LG_W as usize, 2 * params.n);
+    let checksum: u32 = digits.iter().map(|&digit| W - 1 - digit).sum();
+    // Left-align the 12-bit checksum in two bytes
+    let checksum = (checksum << 4) as u16;
+    digits.extend(base_2b(&checksum.to_be_bytes(), LG_W as usize, LEN2));
+    digits
+}
+
+fn wots_pk_gen(hashes: &Hashes<'_>, address: &mut Address) -> Vec<u8> {
+    let mut sk_address = address.with_type_keeping_keypair(WOTS_PRF);
+    let mut chains = Vec::with_capacity(hashes.params.wots_len());
+    for i in 0..hashes.params.len() as u32 {
+        sk_address.set_chain(i);
+        let sk = hashes.prf(&sk_address);
+        address.set_chain(i);
+        chains.extend(hashes.chain(&sk, 0, W - 1, address));
+    }
+    let pk_address = address.with_type_keeping_keypair(WOTS_PK);
+    hashes.t(&pk_address, &[&chains])
+}
+
+fn wots_sign(hashes: &Hashes<'_>, message: &[u8], address: &mut Address, out: &mut Vec<u8>) {
+    let mut sk_address = address.with_type_keeping_keypair(WOTS_PRF);
+    for (i, digit) in wots_digits(hashes.params, message).into_iter().enumerate() {
+        sk_address.set_chain(i as u32);
+        let sk = hashes.prf(&sk_address);
+        address.set_chain(i as u32);
+        out.extend(hashes.chain(&sk, 0, digit, address));
+    }
+}
+
+fn wots_pk_from_sig(
+    hashes: &Hashes<'_>,
+    signature: &[u8],
+    message: &[u8],
+    address: &mut Address,
+) -> Vec<u8> {
+    let n = hashes.params.n;
+    let mut chains = Vec::with_capacity(hashes.params.wots_len());
+    for (i, digit) in wots_digits(hashes.params, message).into_iter().enumerate() {
+        address.set_chain(i as u32);
+        chains.extend(hashes.chain(
+            &signature[i * n..(i + 1) * n],
+            digit,
+            W - 1 - digit,
+            address,
+        ));
+    }
+    let pk_address = address.with_type_keeping_keypair(WOTS_PK);
+    hashes.t(&pk_address, &[&chains])
+}
+
+/// `xmss_node(SK.seed, i, z, PK.seed, ADRS)`
+fn xmss_node(hashes: &Hashes<'_>, i: u32, z: u32, address: &mut Address) -> Vec<u8> {
+    if z == 0 {
+        address.set_type(WOTS_HASH);
+        address.set_keypair(i);
+        wots_pk_gen(hashes, address)
+    } else {
+        let left = xmss_node(hashes, 2 * i, z - 1, address);
+        let right = xmss_node(hashes, 2 * i + 1, z - 1, address);
+        address.set_type(TREE);
+        address.set_chain(z);
+        address.set_hash(i);
+        hashes.t(address, &[&left, &right])
+    }
+}
+
+fn xmss_sign(
+    hashes: &Hashes<'_>,
+    message: &[u8],
+    index: u32,
+    address: &mut Address,
+    out: &mut Vec<u8>,
+) {
+    let mut auth = Vec::with_capacity(hashes.params.hp * hashes.params.n);
+    for j in 0..hashes.params.hp as u32 {
+        auth.extend(xmss_node(hashes, (index >> j) ^ 1, j, address));
+    }
+    address.set_type(WOTS_HASH);
+    address.set_keypair(index);
+    wots_sign(hashes, message, address, out);
+    out.extend(auth);
+}
+
+fn xmss_pk_from_sig(
+    hashes: &Hashes<'_>,
+    index: u32,
+    signature: &[u8],
+    message: &[u8],
+    address: &mut Address,
+) -> Vec<u8> {
+    let n = hashes.params.n;
+    let (wots_signature, auth) = signature.split_at(hashes.params.wots_len());
+    address.set_type(WOTS_HASH);
+    address.set_keypair(index);
+    let mut node = wots_pk_from_sig(hashes, wots_signature, message, address);
+
+    address.set_type(TREE);
+    address.set_hash(index);
+    for (k, sibling) in auth.chunks(n).enumerate() {
+        address.set_chain(k as u32 + 1);
+        let tree_index = address.hash();
+        if (index >> k) & 1 == 0 {
+            address.set_hash(tree_index / 2);
+            node = hashes.t(address, &[&node, sibling]);
+        } else {
+            address.set_hash((tree_index - 1) / 2);
+            node = hashes.t(address, &[sibling, &node]);
+        }
+    }
+    node
+}
+
+/// Split a hypertree index into the next layer's `(tree, leaf)`
+fn split_index(params: Params, tree: u64) -> (u64, u32) {
+    let leaf = (tree & ((1 << params.hp) - 1)) as u32;
+    (tree >> params.hp, leaf)
+}
+
+fn ht_sign(hashes: &Hashes<'_>, message: &[u8], mut tree: u64, mut leaf: u32, out: &mut Vec<u8>) {
+    let mut address = Address::default();
+    let mut root = message.to_vec();
+    for layer in 0..hashes.params.d as u32 {
+        if layer > 0 {
+            (tree, leaf) = split_index(hashes.params, tree);
+        }
+        address.set_layer(layer);
+        address.set_tree(tree);
+        let start = out.len();
+        xmss_sign(hashes, &root, leaf, &mut address, out);
+        if layer + 1 < hashes.params.d as u32 {
+            root = xmss_pk_from_sig(hashes, leaf, &out[start..], &root, &mut address);
+        }
+    }
+}
+
+fn ht_verify(
+    hashes: &Hashes<'_>,
+    message: &[u8],
+    signature: &[u8],
+    mut tree: u64,
+    mut leaf: u32,
+    pk_root: &[u8],
+) -> bool {
+    let mut address = Address::default();
+    let mut node = message.to_vec();
+    for (layer, xmss_signature) in signature.chunks(hashes.params.xmss_len()).enumerate() {
+        if layer > 0 {
+            (tree, leaf) = split_index(hashes.params, tree);
+        }
+        address.set_layer(layer as u32);
+        address.set_tree(tree);
+        node = xmss_pk_from_sig(hashes, leaf, xmss_signature, &node, &mut address);
+    }
+    bool::from(node.ct_eq(pk_root))
+}
+
+fn fors_sk_gen(hashes: &Hashes<'_>, address: &Address, index: u32) -> Zeroizing<Vec<u8>> {
+    let mut sk_address = address.with_type_keeping_keypair(FORS_PRF);
+    sk_address.set_hash(index);
+    hashes.prf(&sk_address)
+}
+
+/// `fors_node(SK.seed, i, z, PK.seed, ADRS)`
+fn fors_node(hashes: &Hashes<'_>, i: u32, z: u32, address: &mut Address) -> Vec<u8> {
+    if z == 0 {
+        let sk = fors_sk_gen(hashes, address, i);
+        address.set_chain(0);
+        address.set_hash(i);
+        hashes.f(address, &sk)
+    } else {
+        let left = fors_node(hashes, 2 * i, z - 1, address);
+        let right = fors_node(hashes, 2 * i + 1, z - 1, address);
+        address.set_chain(z);
+        address.set_hash(i);
+        hashes.t(address, &[&left, &right])
+    }
+}
+
+fn fors_sign(hashes: &Hashes<'_>, digest: &[u8], address: &mut Address, out: &mut Vec<u8>) {
+    let a = hashes.params.a;
+    for (i, index) in base_2b(digest, a, hashes.params.k).into_iter().enumerate() {
+        let i = i as u32;
+        out.extend_from_slice(&fors_sk_gen(hashes, address, (i << a) + index));
+        for j in 0..a as u32 {
+            let sibling = (index >> j) ^ 1;
+            out.extend(fors_node(
+                hashes,
+                (i << (a as u32 - j)) + sibling,
+                j,
+                address,
+            ));
+        }
+    }
+}
+
+fn fors_pk_from_sig(
+    hashes: &Hashes<'_>,
+    signature: &[u8],
+    digest: &[u8],
+    address: &mut Address,
+) -> Vec<u8> {
+    let (n, a) = (hashes.params.n, hashes.params.a);
+    let mut roots = Vec::with_capacity(hashes.params.k * n);
+    let indices = base_2b(digest, a, hashes.params.k);
+    for (i, (tree, index)) in signature.chunks((1 + a) * n).zip(indices).enumerate() {
+        let (sk, auth) = tree.split_at(n);
+        address.set_chain(0);
+        address.set_hash(((i as u32) << a) + index);
+        let mut node = hashes.f(address, sk);
+        for (j, sibling) in auth.chunks(n).enumerate() {
+            address.set_chain(j as u32 + 1);
+            let tree_index = address.hash();
+            if (index >> j) & 1 == 0 {
+                address.set_hash(tree_index / 2);
+                node = hashes.t(address, &[&node, sibling]);
+            } else {
+                address.set_hash((tree_index - 1) / 2);
+                node = hashes.t(address, &[sibling, &node]);
+            }
+        }
+        roots.extend(node);
+    }
+    let pk_address = address.with_type_keeping_keypair(FORS_ROOTS);
+    hashes.t(&pk_address, &[&roots])
+}
+
+/// Split `H_msg` output into the FORS message digest and hypertree `(tree, leaf)` indices
+fn split_digest(params: Params, digest: &[u8]) -> (&[u8], u64, u32) {
+    let md_len = (params.k * params.a).div_ceil(8);
+    let tree_bits = params.h - params.hp;
+    let tree_len = tree_bits.div_ceil(8);
+    let (md, rest) = digest.split_at(md_len);
+    let (tree_bytes, leaf_bytes) = rest.split_at(tree_len);
+    let to_int = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .fold(0u64, |total, &byte| (total << 8) | u64::from(byte))
+    };
+    let tree = to_int(tree_bytes) & (u64::MAX >> (64 - tree_bits));
+    let leaf = to_int(&leaf_bytes[..params.hp.div_ceil(8)]) & ((1 << params.hp) - 1);
+    (md, tree, leaf as u32)
+}
+
+/// `slh_keygen_internal(SK.seed, SK.prf, PK.seed)`, returning `(public_key, secret_key)`
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidParameters` if the seed is not `3n` bytes.
+pub(crate) fn keypair_from_seed(
+    params: Params,
+    seed: &[u8],
+) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    if seed.len() != params.seed_len() {
+        return Err(PqCryptoError::InvalidParameters(format!(
+            "SLH-DSA seed must be {} bytes, got {}",
+            params.seed_len(),
+            seed.len()
+        )));
+    }
+    let n = params.n;
+    let (sk_seed, rest) = seed.split_at(n);
+    let pk_seed = &rest[n..];
+    let hashes = Hashes::new(params, pk_seed, sk_seed);
+    let mut address = Address::default();
+    address.set_layer(params.d as u32 - 1);
+    let pk_root = xmss_node(&hashes, 0, params.hp as u32, &mut address);
+
+    let mut public_key = pk_seed.to_vec();
+    public_key.extend_from_slice(&pk_root);
+    let mut secret_key = Zeroizing::new(seed.to_vec());
+    secret_key.extend_from_slice(&pk_root);
+    Ok((public_key, secret_key))
+}
+
+/// `slh_sign_internal(M', SK, addrnd)` with `M'` given in parts; `None` signs
+/// deterministically
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidKeySize` if the secret key has the wrong length and
+/// `PqCryptoError::InvalidParameters` if `addrnd` is not `n` bytes.
+pub(crate) fn sign_internal(
+    params: Params,
+    secret_key: &[u8],
+    message: &[&[u8]],
+    addrnd: Option<&[u8]>,
+) -> Result<Vec<u8>> {
+    if secret_key.len() != params.secret_key_len() {
+        return Err(PqCryptoError::InvalidKeySize {
+            expected: params.secret_key_len(),
+            actual: secret_key.len(),
+        });
+    }
+    let n = params.n;
+    let (sk_seed, rest) = secret_key.split_at(n);
+    let (sk_prf, public_key) = rest.split_at(n);
+    let pk_seed = &public_key[..n];
+    let opt_rand = addrnd.unwrap_or(pk_seed);
+    if opt_rand.len() != n {
+        return Err(PqCryptoError::InvalidParameters(format!(
+            "SLH-DSA signing randomness must be {n} bytes, got {}",
+            opt_rand.len()
+        )));
+    }
+
+    let hashes = Hashes::new(params, pk_seed, sk_seed);
+    let mut signature = Vec::with_capacity(params.signature_len());
+    let randomizer = prf_msg(params, sk_prf, opt_rand, message)?;
+    signature.extend_from_slice(&randomizer);
+    let digest = h_msg(params, &randomizer, public_key, message);
+    let (md, tree, leaf) = split_digest(params, &digest);
+
+    let mut address = Address::default();
+    address.set_tree(tree);
+    address.set_type(FORS_TREE);
+    address.set_keypair(leaf);
+    let fors_start = signature.len();
+    fors_sign(&hashes, md, &mut address, &mut signature);
+    let fors_pk = fors_pk_from_sig(&hashes, &signature[fors_start..], md, &mut address);
+    ht_sign(&hashes, &fors_pk, tree, leaf, &mut signature);
+    Ok(signature)
+}
+
+/// `slh_verify_internal(M', SIG, PK)` with `M'` given in parts
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidKeySize` if the public key has the wrong length.
+pub(crate) fn verify_internal(
+    params: Params,
+    public_key: &[u8],
+    message: &[&[u8]],
+    signature: &[u8],
+) -> Result<bool> {
+    if public_key.len() != params.public_key_len() {
+        return Err(PqCryptoError::InvalidKeySize {
+            expected: params.public_key_len(),
+            actual: public_key.len(),
+        });
+    }
+    if signature.len() != params.signature_len() {
+        return Ok(false);
+    }
+    let n = params.n;
+    let (pk_seed, pk_root) = public_key.split_at(n);
+    let (randomizer, rest) = signature.split_at(n);
+    let (fors_signature, ht_signature) = rest.split_at(params.fors_len());
+
+    let hashes = Hashes::new(params, pk_seed, &[]);
+    let digest = h_msg(params, randomizer, public_key, message);
+    let (md, tree, leaf) = split_digest(params, &digest);
+
+    let mut address = Address::default();
+    address.set_tree(tree);
+    address.set_type(FORS_TREE);
+    address.set_keypair(leaf);
+    let fors_pk = fors_pk_from_sig(&hashes, fors_signature, md, &mut address);
+    Ok(ht_verify(
+        &hashes,
+        &fors_pk,
+        ht_signature,
+        tree,
+        leaf,
+        pk_root,
+    ))
+}
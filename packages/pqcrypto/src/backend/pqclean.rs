@@ -0,0 +1,127 @@
+//! `PQClean` implementations through the `pqcrypto` crates
+
+use crate::api::kem_builder::hybrid;
+use crate::{KemAlgorithm, PqCryptoError, Result, SignatureAlgorithm};
+use pqcrypto_traits::sign::{DetachedSignature, PublicKey, SecretKey};
+use zeroize::Zeroizing;
+
+/// Run `$body` with `$scheme` bound to the `pqcrypto` module for `$algorithm`
+macro_rules! with_scheme {
+    ($algorithm:expr, $scheme:ident => $body:expr) => {
+        match $algorithm {
+            SignatureAlgorithm::MlDsa44 => {
+                use pqcrypto_mldsa::mldsa44 as $scheme;
+                $body
+            }
+            SignatureAlgorithm::MlDsa65 => {
+                use pqcrypto_mldsa::mldsa65 as $scheme;
+                $body
+            }
+            SignatureAlgorithm::MlDsa87 => {
+                use pqcrypto_mldsa::mldsa87 as $scheme;
+                $body
+            }
+            SignatureAlgorithm::Falcon512 => {
+                use pqcrypto_falcon::falcon512 as $scheme;
+                $body
+            }
+            SignatureAlgorithm::Falcon1024 => {
+                use pqcrypto_falcon::falcon1024 as $scheme;
+                $body
+            }
+            SignatureAlgorithm::SphincsShaSha256_128fSimple => {
+                use pqcrypto_sphincsplus::sphincssha2128fsimple as $scheme;
+                $body
+            }
+            SignatureAlgorithm::SphincsShaSha256_128sSimple => {
+                use pqcrypto_sphincsplus::sphincssha2128ssimple as $scheme;
+                $body
+            }
+            SignatureAlgorithm::SphincsShaSha256_192fSimple => {
+                use pqcrypto_sphincsplus::sphincssha2192fsimple as $scheme;
+                $body
+            }
+            SignatureAlgorithm::SphincsShaSha256_192sSimple => {
+                use pqcrypto_sphincsplus::sphincssha2192ssimple as $scheme;
+                $body
+            }
+            SignatureAlgorithm::SphincsShaSha256_256fSimple => {
+                use pqcrypto_sphincsplus::sphincssha2256fsimple as $scheme;
+                $body
+            }
+            SignatureAlgorithm::SphincsShaSha256_256sSimple => {
+                use pqcrypto_sphincsplus::sphincssha2256ssimple as $scheme;
+                $body
+            }
+        }
+    };
+}
+
+pub(crate) fn kem_keypair(algorithm: KemAlgorithm) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    if algorithm.is_hybrid() {
+        hybrid::keypair(algorithm)
+    } else {
+        hybrid::mlkem_keypair(algorithm)
+    }
+}
+
+pub(crate) fn encapsulate(
+    algorithm: KemAlgorithm,
+    public_key: &[u8],
+) -> Result<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    if algorithm.is_hybrid() {
+        hybrid::encapsulate(algorithm, public_key)
+    } else {
+        hybrid::mlkem_encapsulate(algorithm, public_key)
+    }
+}
+
+pub(crate) fn decapsulate(
+    algorithm: KemAlgorithm,
+    secret_key: &[u8],
+    ciphertext: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    if algorithm.is_hybrid() {
+        hybrid::decapsulate(algorithm, secret_key, ciphertext)
+    } else {
+        hybrid::mlkem_decapsulate(algorithm, secret_key, ciphertext)
+    }
+}
+
+pub(crate) fn signature_keypair(algorithm: SignatureAlgorithm) -> (Vec<u8>, Zeroizing<Vec<u8>>) {
+    with_scheme!(algorithm, scheme => {
+        let (public_key, secret_key) = scheme::keypair();
+        (
+            public_key.as_bytes().to_vec(),
+            Zeroizing::new(secret_key.as_bytes().to_vec()),
+        )
+    })
+}
+
+pub(crate) fn sign(
+    algorithm: SignatureAlgorithm,
+    secret_key: &[u8],
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    with_scheme!(algorithm, scheme => {
+        let secret_key = scheme::SecretKey::from_bytes(secret_key)
+            .map_err(|_| PqCryptoError::InvalidKey(format!("Invalid {algorithm} secret key")))?;
+        Ok(scheme::detached_sign(message, &secret_key).as_bytes().to_vec())
+    })
+}
+
+pub(crate) fn verify(
+    algorithm: SignatureAlgorithm,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool> {
+    with_scheme!(algorithm, scheme => {
+        let public_key = scheme::PublicKey::from_bytes(public_key)
+            .map_err(|_| PqCryptoError::InvalidKey(format!("Invalid {algorithm} public key")))?;
+        let Ok(signature) = scheme::DetachedSignature::from_bytes(signature) else {
+            return Ok(false);
+        };
+        Ok(scheme::verify_detached_signature(&signature, message, &public_key).is_ok())
+    })
+}
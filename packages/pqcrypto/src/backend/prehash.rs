@@ -0,0 +1,234 @@
+//! Pre-hash functions and the `M'` message encoding shared by ML-DSA and SLH-DSA
+
+use crate::{PqCryptoError, Result};
+use sha2::{Sha256, Sha384, Sha512};
+use sha3::digest::{Digest, ExtendableOutput, Update, XofReader};
+use sha3::{Sha3_256, Sha3_512, Shake128, Shake256};
+
+/// Longest context string FIPS 204 and FIPS 205 allow
+pub const MAX_CONTEXT_LEN: usize = 255;
+
+/// Hash applied to the message by HashML-DSA and HashSLH-DSA
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreHash {
+    /// SHA-256
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512
+    Sha512,
+    /// SHA3-256
+    Sha3_256,
+    /// SHA3-512
+    Sha3_512,
+    /// SHAKE128 with 256-bit output
+    Shake128,
+    /// SHAKE256 with 512-bit output
+    Shake256,
+}
+
+impl PreHash {
+    /// DER encoding of the hash function's OID, as embedded in `M'`
+    #[must_use]
+    pub fn oid(self) -> [u8; 11] {
+        let last = match self {
+            Self::Sha256 => 0x01,
+            Self::Sha384 => 0x02,
+            Self::Sha512 => 0x03,
+            Self::Sha3_256 => 0x08,
+            Self::Sha3_512 => 0x0A,
+            Self::Shake128 => 0x0B,
+            Self::Shake256 => 0x0C,
+        };
+        [
+            0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, last,
+        ]
+    }
+
+    /// Digest length in bytes
+    #[must_use]
+    pub fn output_size(self) -> usize {
+        match self {
+            Self::Sha256 | Self::Sha3_256 | Self::Shake128 => 32,
+            Self::Sha384 => 48,
+            Self::Sha512 | Self::Sha3_512 | Self::Shake256 => 64,
+        }
+    }
+
+    /// Hash `message`
+    #[must_use]
+    pub fn digest(self, message: &[u8]) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(message);
+        hasher.finalize()
+    }
+
+    /// Start an incremental hash, for messages too large to hold in memory
+    #[must_use]
+    pub fn hasher(self) -> PreHasher {
+        let state = match self {
+            Self::Sha256 => HasherState::Sha256(Sha256::default()),
+            Self::Sha384 => HasherState::Sha384(Sha384::default()),
+            Self::Sha512 => HasherState::Sha512(Sha512::default()),
+            Self::Sha3_256 => HasherState::Sha3_256(Sha3_256::default()),
+            Self::Sha3_512 => HasherState::Sha3_512(Sha3_512::default()),
+            Self::Shake128 => HasherState::Shake128(Shake128::default()),
+            Self::Shake256 => HasherState::Shake256(Shake256::default()),
+        };
+        PreHasher {
+            pre_hash: self,
+            state,
+        }
+    }
+}
+
+/// Incremental [`PreHash`] computation
+///
+/// Feeding a message in any number of pieces yields the same digest as
+/// [`PreHash::digest`] over the whole message.
+#[derive(Clone)]
+pub struct PreHasher {
+    pre_hash: PreHash,
+    state: HasherState,
+}
+
+#[derive(Clone)]
+enum HasherState {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Sha3_256(Sha3_256),
+    Sha3_512(Sha3_512),
+    Shake128(Shake128),
+    Shake256(Shake256),
+}
+
+impl PreHasher {
+    /// The hash being computed
+    #[must_use]
+    pub fn pre_hash(&self) -> PreHash {
+        self.pre_hash
+    }
+
+    /// Absorb the next piece of the message
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            HasherState::Sha256(hasher) => Digest::update(hasher, data),
+            HasherState::Sha384(hasher) => Digest::update(hasher, data),
+            HasherState::Sha512(hasher) => Digest::update(hasher, data),
+            HasherState::Sha3_256(hasher) => Digest::update(hasher, data),
+            HasherState::Sha3_512(hasher) => Digest::update(hasher, data),
+            HasherState::Shake128(hasher) => Update::update(hasher, data),
+            HasherState::Shake256(hasher) => Update::update(hasher, data),
+        }
+    }
+
+    /// The digest of everything absorbed, [`PreHash::output_size`] bytes long
+    #[must_use]
+    pub fn finalize(self) -> Vec<u8> {
+        match self.state {
+            HasherState::Sha256(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha384(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha512(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha3_256(hasher) => hasher.finalize().to_vec(),
+            HasherState::Sha3_512(hasher) => hasher.finalize().to_vec(),
+            HasherState::Shake128(hasher) => xof(hasher, 32),
+            HasherState::Shake256(hasher) => xof(hasher, 64),
+        }
+    }
+}
+
+impl std::fmt::Debug for PreHasher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreHasher")
+            .field("pre_hash", &self.pre_hash)
+            .finish_non_exhaustive()
+    }
+}
+
+fn xof(hasher: impl ExtendableOutput, len: usize) -> Vec<u8> {
+    let mut out = vec![0u8; len];
+    hasher.finalize_xof().read(&mut out);
+    out
+}
+
+/// `M'` split into a short header and the message (or its digest), so large messages are
+/// never copied
+pub(crate) struct FormattedMessage<'a> {
+    header: Vec<u8>,
+    body: FormattedBody<'a>,
+}
+
+enum FormattedBody<'a> {
+    Message(&'a [u8]),
+    Digest(Vec<u8>),
+}
+
+impl<'a> FormattedMessage<'a> {
+    /// `0 || |ctx| || ctx || M` for pure signing, or
+    /// `1 || |ctx| || ctx || OID || PH(M)` for pre-hash signing
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidParameters` if the context exceeds
+    /// [`MAX_CONTEXT_LEN`] bytes.
+    pub(crate) fn new(
+        message: &'a [u8],
+        context: &[u8],
+        pre_hash: Option<PreHash>,
+    ) -> Result<Self> {
+        match pre_hash {
+            Some(pre_hash) => Self::from_digest(pre_hash.digest(message), context, pre_hash),
+            None => Ok(Self {
+                header: header(context, None)?,
+                body: FormattedBody::Message(message),
+            }),
+        }
+    }
+
+    /// `1 || |ctx| || ctx || OID || PH(M)` from an already computed `PH(M)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidParameters` if the context exceeds
+    /// [`MAX_CONTEXT_LEN`] bytes or the digest is not `pre_hash`'s output size.
+    pub(crate) fn from_digest(digest: Vec<u8>, context: &[u8], pre_hash: PreHash) -> Result<Self> {
+        if digest.len() != pre_hash.output_size() {
+            return Err(PqCryptoError::InvalidParameters(format!(
+                "{pre_hash:?} digest must be {} bytes, got {}",
+                pre_hash.output_size(),
+                digest.len()
+            )));
+        }
+        Ok(Self {
+            header: header(context, Some(pre_hash))?,
+            body: FormattedBody::Digest(digest),
+        })
+    }
+
+    /// The two parts of `M'`, in order
+    pub(crate) fn parts(&self) -> [&[u8]; 2] {
+        let body = match &self.body {
+            FormattedBody::Message(message) => message,
+            FormattedBody::Digest(digest) => digest.as_slice(),
+        };
+        [&self.header, body]
+    }
+}
+
+fn header(context: &[u8], pre_hash: Option<PreHash>) -> Result<Vec<u8>> {
+    let context_len = u8::try_from(context.len()).map_err(|_| {
+        PqCryptoError::InvalidParameters(format!(
+            "Context string must be at most {MAX_CONTEXT_LEN} bytes, got {}",
+            context.len()
+        ))
+    })?;
+    let mut header = Vec::with_capacity(2 + context.len() + 11);
+    header.push(u8::from(pre_hash.is_some()));
+    header.push(context_len);
+    header.extend_from_slice(context);
+    if let Some(pre_hash) = pre_hash {
+        header.extend_from_slice(&pre_hash.oid());
+    }
+    Ok(header)
+}
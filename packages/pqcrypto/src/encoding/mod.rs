@@ -0,0 +1,379 @@
+//! Standard encodings for post-quantum keys
+//!
+//! [`PqPublicKey`] and [`PqSecretKey`] pair raw key bytes with their algorithm and convert
+//! to and from SubjectPublicKeyInfo and PKCS#8, in DER and PEM, using the NIST OIDs:
+//!
+//! - ML-KEM (FIPS 203): `expandedKey` private keys, draft-ietf-lamps-kyber-certificates
+//! - ML-DSA (FIPS 204): `expandedKey` private keys, draft-ietf-lamps-dilithium-certificates
+//! - SLH-DSA (FIPS 205): raw keys, draft-ietf-lamps-x509-slhdsa
+//! - Composite ML-DSA signatures: raw composite keys, draft-ietf-lamps-pq-composite-sigs
+//!
+//! The algorithm is detected from the encoding on import, and the ML-KEM, ML-DSA and
+//! composite builders accept these encodings wherever they accept raw keys:
+//!
+//! ```rust,ignore
+//! // Picks ML-KEM-1024 from the SPKI even though the builder started as ML-KEM-768
+//! let builder = KemBuilder::ml_kem_768().with_public_key(pem.as_bytes())?;
+//! assert_eq!(builder.algorithm(), KemAlgorithm::MlKem1024);
+//! ```
+//!
+//! Seed-only private keys (`seed [0]`) are rejected because the underlying implementations
+//! cannot expand seeds; FALCON and the hybrid KEMs have no standard OIDs yet.
+//!
+//! SPHINCS+ round 3.1 and SLH-DSA share key formats but not signatures, so the `Sphincs*`
+//! algorithms take an SLH-DSA OID only for keys marked as [`Backend::Native`], which
+//! implements FIPS 205. SLH-DSA keys decode as native keys, and the SPHINCS+ builders, which
+//! sign with `PQClean`'s round 3.1, refuse them:
+//!
+//! ```rust,ignore
+//! let (public_key, _) = Backend::Native.signature_keypair(algorithm)?;
+//! let pem = PqPublicKey::new(algorithm, public_key)?
+//!     .with_backend(Backend::Native)
+//!     .to_spki_pem()?;
+//! ```
+
+mod pkcs;
+
+use crate::backend::Backend;
+use crate::{CompositeAlgorithm, KemAlgorithm, PqCryptoError, Result, SignatureAlgorithm};
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Any post-quantum algorithm with a key pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PqAlgorithm {
+    /// Key encapsulation
+    Kem(KemAlgorithm),
+    /// Digital signatures
+    Signature(SignatureAlgorithm),
+    /// Composite ML-DSA + classical signatures
+    Composite(CompositeAlgorithm),
+}
+
+/// Algorithms whose keys can be told apart by encoding, and replaced by one another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Family {
+    MlKem,
+    MlDsa,
+    SlhDsa,
+    Falcon,
+    Hybrid(KemAlgorithm),
+    Composite,
+}
+
+impl PqAlgorithm {
+    /// Raw public key size in bytes
+    #[must_use]
+    pub fn public_key_size(self) -> usize {
+        match self {
+            Self::Kem(algorithm) => algorithm.public_key_size(),
+            Self::Signature(algorithm) => algorithm.public_key_size(),
+            Self::Composite(algorithm) => algorithm.public_key_size(),
+        }
+    }
+
+    /// Raw secret key size in bytes
+    #[must_use]
+    pub fn secret_key_size(self) -> usize {
+        match self {
+            Self::Kem(algorithm) => algorithm.secret_key_size(),
+            Self::Signature(algorithm) => algorithm.secret_key_size(),
+            Self::Composite(algorithm) => algorithm.secret_key_size(),
+        }
+    }
+
+    fn family(self) -> Family {
+        match self {
+            Self::Kem(algorithm) if algorithm.is_hybrid() => Family::Hybrid(algorithm),
+            Self::Kem(_) => Family::MlKem,
+            Self::Signature(
+                SignatureAlgorithm::MlDsa44
+                | SignatureAlgorithm::MlDsa65
+                | SignatureAlgorithm::MlDsa87,
+            ) => Family::MlDsa,
+            Self::Signature(SignatureAlgorithm::Falcon512 | SignatureAlgorithm::Falcon1024) => {
+                Family::Falcon
+            }
+            Self::Signature(_) => Family::SlhDsa,
+            Self::Composite(_) => Family::Composite,
+        }
+    }
+}
+
+impl fmt::Display for PqAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Kem(algorithm) => write!(f, "{algorithm}"),
+            Self::Signature(algorithm) => write!(f, "{algorithm}"),
+            Self::Composite(algorithm) => write!(f, "{algorithm}"),
+        }
+    }
+}
+
+impl From<KemAlgorithm> for PqAlgorithm {
+    fn from(algorithm: KemAlgorithm) -> Self {
+        Self::Kem(algorithm)
+    }
+}
+
+impl From<SignatureAlgorithm> for PqAlgorithm {
+    fn from(algorithm: SignatureAlgorithm) -> Self {
+        Self::Signature(algorithm)
+    }
+}
+
+impl From<CompositeAlgorithm> for PqAlgorithm {
+    fn from(algorithm: CompositeAlgorithm) -> Self {
+        Self::Composite(algorithm)
+    }
+}
+
+impl TryFrom<PqAlgorithm> for KemAlgorithm {
+    type Error = PqCryptoError;
+
+    fn try_from(algorithm: PqAlgorithm) -> Result<Self> {
+        match algorithm {
+            PqAlgorithm::Kem(algorithm) => Ok(algorithm),
+            PqAlgorithm::Signature(_) | PqAlgorithm::Composite(_) => Err(
+                PqCryptoError::UnsupportedAlgorithm(format!("{algorithm} is not a KEM")),
+            ),
+        }
+    }
+}
+
+impl TryFrom<PqAlgorithm> for SignatureAlgorithm {
+    type Error = PqCryptoError;
+
+    fn try_from(algorithm: PqAlgorithm) -> Result<Self> {
+        match algorithm {
+            PqAlgorithm::Signature(algorithm) => Ok(algorithm),
+            PqAlgorithm::Kem(_) | PqAlgorithm::Composite(_) => {
+                Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                    "{algorithm} is not a single-algorithm signature scheme"
+                )))
+            }
+        }
+    }
+}
+
+impl TryFrom<PqAlgorithm> for CompositeAlgorithm {
+    type Error = PqCryptoError;
+
+    fn try_from(algorithm: PqAlgorithm) -> Result<Self> {
+        match algorithm {
+            PqAlgorithm::Composite(algorithm) => Ok(algorithm),
+            PqAlgorithm::Kem(_) | PqAlgorithm::Signature(_) => {
+                Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                    "{algorithm} is not a composite signature algorithm"
+                )))
+            }
+        }
+    }
+}
+
+/// Public key with its algorithm
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PqPublicKey {
+    algorithm: PqAlgorithm,
+    backend: Backend,
+    key: Vec<u8>,
+}
+
+impl PqPublicKey {
+    /// Wrap raw public key bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidKeySize` if the length does not match the algorithm.
+    pub fn new(algorithm: impl Into<PqAlgorithm>, key: impl Into<Vec<u8>>) -> Result<Self> {
+        let algorithm = algorithm.into();
+        let key = key.into();
+        check_size(algorithm.public_key_size(), key.len())?;
+        Ok(Self {
+            algorithm,
+            backend: Backend::default(),
+            key,
+        })
+    }
+
+    /// Mark the key as used with `backend`, see [`backend`](Self::backend)
+    #[must_use]
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Algorithm of this key
+    #[must_use]
+    pub fn algorithm(&self) -> PqAlgorithm {
+        self.algorithm
+    }
+
+    /// Backend the key is used with, [`Backend::PqClean`] unless set
+    ///
+    /// Only matters for the `Sphincs*` algorithms, whose keys encode as SLH-DSA with
+    /// [`Backend::Native`] and not at all with `PQClean`'s round 3.1 SPHINCS+.
+    #[must_use]
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Raw key bytes
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Consume into the raw key bytes
+    #[must_use]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.key
+    }
+}
+
+/// Secret key with its algorithm, zeroized on drop
+#[derive(Clone)]
+pub struct PqSecretKey {
+    algorithm: PqAlgorithm,
+    backend: Backend,
+    key: Zeroizing<Vec<u8>>,
+}
+
+impl PqSecretKey {
+    /// Wrap raw secret key bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidKeySize` if the length does not match the algorithm.
+    pub fn new(algorithm: impl Into<PqAlgorithm>, key: impl Into<Vec<u8>>) -> Result<Self> {
+        let algorithm = algorithm.into();
+        let key = Zeroizing::new(key.into());
+        check_size(algorithm.secret_key_size(), key.len())?;
+        Ok(Self {
+            algorithm,
+            backend: Backend::default(),
+            key,
+        })
+    }
+
+    /// Mark the key as used with `backend`, see [`PqPublicKey::backend`]
+    #[must_use]
+    pub fn with_backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Algorithm of this key
+    #[must_use]
+    pub fn algorithm(&self) -> PqAlgorithm {
+        self.algorithm
+    }
+
+    /// Backend the key is used with, [`Backend::PqClean`] unless set
+    #[must_use]
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Raw key bytes
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl fmt::Debug for PqSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PqSecretKey")
+            .field("algorithm", &self.algorithm)
+            .field("backend", &self.backend)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Import a builder's public key given raw, SPKI DER or SPKI PEM, returning the detected
+/// algorithm and the raw key
+///
+/// Raw input must match `algorithm`; encoded input may name any parameter set of the same
+/// family, which then replaces `algorithm`.
+pub(crate) fn import_public_key<A>(algorithm: A, input: Vec<u8>) -> Result<(A, Vec<u8>)>
+where
+    A: Copy + Into<PqAlgorithm> + TryFrom<PqAlgorithm, Error = PqCryptoError>,
+{
+    let current = algorithm.into();
+    let expected = current.public_key_size();
+    if !pkcs::is_pem(&input) && input.len() == expected {
+        return Ok((algorithm, input));
+    }
+    let key = pkcs::decode_public_key(&input, expected)?;
+    same_family(current, key.algorithm)?;
+    builder_backend(key.algorithm, key.backend)?;
+    Ok((A::try_from(key.algorithm)?, key.key))
+}
+
+/// Import a builder's secret key given raw, PKCS#8 DER or PKCS#8 PEM
+pub(crate) fn import_secret_key<A>(algorithm: A, input: Vec<u8>) -> Result<(A, Vec<u8>)>
+where
+    A: Copy + Into<PqAlgorithm> + TryFrom<PqAlgorithm, Error = PqCryptoError>,
+{
+    let input = Zeroizing::new(input);
+    let current = algorithm.into();
+    let expected = current.secret_key_size();
+    if !pkcs::is_pem(&input) && input.len() == expected {
+        return Ok((algorithm, input.to_vec()));
+    }
+    let key = pkcs::decode_secret_key(&input, expected)?;
+    same_family(current, key.algorithm)?;
+    builder_backend(key.algorithm, key.backend)?;
+    Ok((A::try_from(key.algorithm)?, key.key.to_vec()))
+}
+
+/// Import a key pair, which must resolve to a single algorithm
+pub(crate) fn import_keypair<A>(
+    algorithm: A,
+    public_key: Vec<u8>,
+    secret_key: Vec<u8>,
+) -> Result<(A, Vec<u8>, Vec<u8>)>
+where
+    A: Copy + PartialEq + fmt::Display,
+    A: Into<PqAlgorithm> + TryFrom<PqAlgorithm, Error = PqCryptoError>,
+{
+    let (public_algorithm, public_key) = import_public_key(algorithm, public_key)?;
+    let (secret_algorithm, secret_key) = import_secret_key(algorithm, secret_key)?;
+    if public_algorithm != secret_algorithm {
+        return Err(PqCryptoError::InvalidKey(format!(
+            "Key pair mixes a {public_algorithm} public key with a {secret_algorithm} secret key"
+        )));
+    }
+    Ok((public_algorithm, public_key, secret_key))
+}
+
+fn same_family(expected: PqAlgorithm, found: PqAlgorithm) -> Result<()> {
+    if expected.family() == found.family() {
+        Ok(())
+    } else {
+        Err(PqCryptoError::UnsupportedAlgorithm(format!(
+            "Expected a {expected} key, found {found}"
+        )))
+    }
+}
+
+/// The builders sign SPHINCS+ with `PQClean`, so they cannot take SLH-DSA keys
+fn builder_backend(algorithm: PqAlgorithm, backend: Backend) -> Result<()> {
+    if algorithm.family() == Family::SlhDsa && backend == Backend::Native {
+        Err(PqCryptoError::UnsupportedAlgorithm(format!(
+            "{algorithm} key is SLH-DSA, but the SPHINCS+ builders implement round 3.1; \
+             use it with Backend::Native"
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn check_size(expected: usize, actual: usize) -> Result<()> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(PqCryptoError::InvalidKeySize { expected, actual })
+    }
+}
@@ -0,0 +1,372 @@
+//! PKCS#8 and SPKI encodings with the NIST algorithm OIDs
+
+use super::{Family, PqAlgorithm, PqPublicKey, PqSecretKey};
+use crate::backend::Backend;
+use crate::{CompositeAlgorithm, KemAlgorithm, PqCryptoError, Result, SignatureAlgorithm};
+use pkcs8::der::asn1::{BitStringRef, OctetStringRef};
+use pkcs8::der::{Decode, Encode, Reader, SliceReader};
+use pkcs8::spki::{AlgorithmIdentifierRef, SubjectPublicKeyInfoRef};
+use pkcs8::{Document, LineEnding, ObjectIdentifier, PrivateKeyInfo, SecretDocument};
+use zeroize::Zeroizing;
+
+/// `(algorithm, OID)` for every algorithm with a registered identifier
+///
+/// Composite keys use the OIDs of draft-ietf-lamps-pq-composite-sigs. The `Sphincs*`
+/// algorithms take the SLH-DSA OIDs only for keys used with [`Backend::Native`].
+const OIDS: [(PqAlgorithm, ObjectIdentifier); 18] = [
+    (
+        PqAlgorithm::Kem(KemAlgorithm::MlKem512),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.1"),
+    ),
+    (
+        PqAlgorithm::Kem(KemAlgorithm::MlKem768),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.2"),
+    ),
+    (
+        PqAlgorithm::Kem(KemAlgorithm::MlKem1024),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.3"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::MlDsa44),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.17"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::MlDsa65),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.18"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::MlDsa87),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.19"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::SphincsShaSha256_128sSimple),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.20"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::SphincsShaSha256_128fSimple),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.21"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::SphincsShaSha256_192sSimple),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.22"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::SphincsShaSha256_192fSimple),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.23"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::SphincsShaSha256_256sSimple),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.24"),
+    ),
+    (
+        PqAlgorithm::Signature(SignatureAlgorithm::SphincsShaSha256_256fSimple),
+        ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.25"),
+    ),
+    (
+        PqAlgorithm::Composite(CompositeAlgorithm::MlDsa44Ed25519),
+        ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.6.39"),
+    ),
+    (
+        PqAlgorithm::Composite(CompositeAlgorithm::MlDsa44EcdsaP256),
+        ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.6.40"),
+    ),
+    (
+        PqAlgorithm::Composite(CompositeAlgorithm::MlDsa65EcdsaP256),
+        ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.6.45"),
+    ),
+    (
+        PqAlgorithm::Composite(CompositeAlgorithm::MlDsa65EcdsaP384),
+        ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.6.46"),
+    ),
+    (
+        PqAlgorithm::Composite(CompositeAlgorithm::MlDsa65Ed25519),
+        ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.6.48"),
+    ),
+    (
+        PqAlgorithm::Composite(CompositeAlgorithm::MlDsa87EcdsaP384),
+        ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.6.49"),
+    ),
+];
+
+const PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+const PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+
+/// DER tags of the ML-KEM and ML-DSA private key `CHOICE`
+const TAG_SEED: u8 = 0x80;
+const TAG_EXPANDED_KEY: u8 = 0x04;
+const TAG_BOTH: u8 = 0x30;
+
+impl PqPublicKey {
+    /// Encode as SPKI DER
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` for algorithms without an OID.
+    pub fn to_spki_der(&self) -> Result<Vec<u8>> {
+        Ok(self.spki_document()?.as_bytes().to_vec())
+    }
+
+    /// Encode as SPKI PEM (`PUBLIC KEY`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` for algorithms without an OID.
+    pub fn to_spki_pem(&self) -> Result<String> {
+        self.spki_document()?
+            .to_pem(PUBLIC_KEY_LABEL, LineEnding::LF)
+            .map_err(PqCryptoError::serialization_error)
+    }
+
+    /// Decode SPKI DER, detecting the algorithm from its OID
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidKey` for malformed input,
+    /// `PqCryptoError::UnsupportedAlgorithm` for unknown OIDs and
+    /// `PqCryptoError::InvalidKeySize` if the key does not fit the algorithm.
+    pub fn from_spki_der(der: &[u8]) -> Result<Self> {
+        let info = SubjectPublicKeyInfoRef::from_der(der).map_err(format_error)?;
+        let algorithm = key_algorithm(info.algorithm)?;
+        let key = info
+            .subject_public_key
+            .as_bytes()
+            .ok_or_else(|| PqCryptoError::invalid_key("SPKI key has unused bits"))?;
+        Ok(Self::new(algorithm, key)?.with_backend(key_backend(algorithm)))
+    }
+
+    /// Decode SPKI PEM
+    ///
+    /// # Errors
+    ///
+    /// As [`from_spki_der`](Self::from_spki_der), and `PqCryptoError::InvalidKey` for a
+    /// PEM label other than `PUBLIC KEY`.
+    pub fn from_spki_pem(pem: &str) -> Result<Self> {
+        let (label, document) = Document::from_pem(pem).map_err(format_error)?;
+        expect_label(label, PUBLIC_KEY_LABEL)?;
+        Self::from_spki_der(document.as_bytes())
+    }
+
+    fn spki_document(&self) -> Result<Document> {
+        let info = SubjectPublicKeyInfoRef {
+            algorithm: AlgorithmIdentifierRef {
+                oid: algorithm_oid(self.algorithm, self.backend)?,
+                parameters: None,
+            },
+            subject_public_key: BitStringRef::from_bytes(&self.key)
+                .map_err(PqCryptoError::serialization_error)?,
+        };
+        Document::encode_msg(&info).map_err(PqCryptoError::serialization_error)
+    }
+}
+
+impl PqSecretKey {
+    /// Encode as unencrypted PKCS#8 DER
+    ///
+    /// ML-KEM and ML-DSA keys are written in the `expandedKey` form, composite keys as the
+    /// raw private key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` for algorithms without an OID.
+    pub fn to_pkcs8_der(&self) -> Result<Zeroizing<Vec<u8>>> {
+        Ok(Zeroizing::new(self.pkcs8_document()?.as_bytes().to_vec()))
+    }
+
+    /// Encode as unencrypted PKCS#8 PEM (`PRIVATE KEY`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` for algorithms without an OID.
+    pub fn to_pkcs8_pem(&self) -> Result<Zeroizing<String>> {
+        self.pkcs8_document()?
+            .to_pem(PRIVATE_KEY_LABEL, LineEnding::LF)
+            .map_err(PqCryptoError::serialization_error)
+    }
+
+    /// Decode unencrypted PKCS#8 DER, detecting the algorithm from its OID
+    ///
+    /// ML-KEM and ML-DSA keys may be in the `expandedKey` or `both` form; seed-only keys
+    /// are unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidKey` for malformed input,
+    /// `PqCryptoError::UnsupportedAlgorithm` for unknown OIDs and seed-only keys, and
+    /// `PqCryptoError::InvalidKeySize` if the key does not fit the algorithm.
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self> {
+        let info = PrivateKeyInfo::from_der(der).map_err(format_error)?;
+        let algorithm = key_algorithm(info.algorithm)?;
+        let key = if has_expanded_form(algorithm) {
+            expanded_key(info.private_key)?
+        } else {
+            info.private_key
+        };
+        Ok(Self::new(algorithm, key)?.with_backend(key_backend(algorithm)))
+    }
+
+    /// Decode unencrypted PKCS#8 PEM
+    ///
+    /// # Errors
+    ///
+    /// As [`from_pkcs8_der`](Self::from_pkcs8_der), and `PqCryptoError::InvalidKey` for a
+    /// PEM label other than `PRIVATE KEY`.
+    pub fn from_pkcs8_pem(pem: &str) -> Result<Self> {
+        let (label, document) = SecretDocument::from_pem(pem).map_err(format_error)?;
+        expect_label(label, PRIVATE_KEY_LABEL)?;
+        Self::from_pkcs8_der(document.as_bytes())
+    }
+
+    fn pkcs8_document(&self) -> Result<SecretDocument> {
+        let algorithm = AlgorithmIdentifierRef {
+            oid: algorithm_oid(self.algorithm, self.backend)?,
+            parameters: None,
+        };
+        let private_key = if has_expanded_form(self.algorithm) {
+            let octets =
+                OctetStringRef::new(&self.key).map_err(PqCryptoError::serialization_error)?;
+            Zeroizing::new(
+                octets
+                    .to_der()
+                    .map_err(PqCryptoError::serialization_error)?,
+            )
+        } else {
+            self.key.clone()
+        };
+        SecretDocument::encode_msg(&PrivateKeyInfo::new(algorithm, &private_key))
+            .map_err(PqCryptoError::serialization_error)
+    }
+}
+
+pub(super) fn is_pem(input: &[u8]) -> bool {
+    input.trim_ascii_start().starts_with(b"-----BEGIN")
+}
+
+/// Decode SPKI PEM or DER; input that is neither is reported as a raw key of the wrong size
+pub(super) fn decode_public_key(input: &[u8], raw_size: usize) -> Result<PqPublicKey> {
+    if is_pem(input) {
+        PqPublicKey::from_spki_pem(&pem_text(input)?)
+    } else {
+        PqPublicKey::from_spki_der(input).map_err(|e| not_encoded(e, raw_size, input.len()))
+    }
+}
+
+/// Decode PKCS#8 PEM or DER; input that is neither is reported as a raw key of the wrong size
+pub(super) fn decode_secret_key(input: &[u8], raw_size: usize) -> Result<PqSecretKey> {
+    if is_pem(input) {
+        PqSecretKey::from_pkcs8_pem(&pem_text(input)?)
+    } else {
+        PqSecretKey::from_pkcs8_der(input).map_err(|e| not_encoded(e, raw_size, input.len()))
+    }
+}
+
+fn pem_text(input: &[u8]) -> Result<Zeroizing<String>> {
+    std::str::from_utf8(input)
+        .map(|pem| Zeroizing::new(pem.to_string()))
+        .map_err(|_| PqCryptoError::invalid_key("PEM input is not valid UTF-8"))
+}
+
+/// Keep errors about well-formed but unusable encodings; anything else was likely raw
+fn not_encoded(error: PqCryptoError, raw_size: usize, actual: usize) -> PqCryptoError {
+    match error {
+        PqCryptoError::InvalidKey(_) => PqCryptoError::InvalidKeySize {
+            expected: raw_size,
+            actual,
+        },
+        error => error,
+    }
+}
+
+fn algorithm_oid(algorithm: PqAlgorithm, backend: Backend) -> Result<ObjectIdentifier> {
+    if algorithm.family() == Family::SlhDsa && backend != Backend::Native {
+        return Err(PqCryptoError::UnsupportedAlgorithm(format!(
+            "{algorithm} key is for SPHINCS+ round 3.1, which has no standard OID; \
+             SLH-DSA keys must be marked as Backend::Native"
+        )));
+    }
+    OIDS.iter()
+        .find(|(known, _)| *known == algorithm)
+        .map(|(_, oid)| *oid)
+        .ok_or_else(|| {
+            PqCryptoError::UnsupportedAlgorithm(format!("{algorithm} has no standard OID"))
+        })
+}
+
+/// Identify the algorithm named by a PKCS#8 or SPKI algorithm identifier
+fn key_algorithm(identifier: AlgorithmIdentifierRef<'_>) -> Result<PqAlgorithm> {
+    if identifier.parameters.is_some() {
+        return Err(PqCryptoError::invalid_key(
+            "Post-quantum algorithm identifiers take no parameters",
+        ));
+    }
+    OIDS.iter()
+        .find(|(_, oid)| *oid == identifier.oid)
+        .map(|(algorithm, _)| *algorithm)
+        .ok_or_else(|| {
+            PqCryptoError::UnsupportedAlgorithm(format!(
+                "Unsupported key algorithm {}",
+                identifier.oid
+            ))
+        })
+}
+
+/// Keys under an SLH-DSA OID are FIPS 205 keys, which only the native backend implements
+fn key_backend(algorithm: PqAlgorithm) -> Backend {
+    if algorithm.family() == Family::SlhDsa {
+        Backend::Native
+    } else {
+        Backend::default()
+    }
+}
+
+/// ML-KEM and ML-DSA private keys are a `CHOICE` of seed, expanded key or both
+fn has_expanded_form(algorithm: PqAlgorithm) -> bool {
+    matches!(
+        algorithm,
+        PqAlgorithm::Kem(_)
+            | PqAlgorithm::Signature(
+                SignatureAlgorithm::MlDsa44
+                    | SignatureAlgorithm::MlDsa65
+                    | SignatureAlgorithm::MlDsa87
+            )
+    )
+}
+
+/// `expandedKey` from the `CHOICE`, discarding the seed of the `both` form
+fn expanded_key(private_key: &[u8]) -> Result<&[u8]> {
+    match private_key.first() {
+        Some(&TAG_EXPANDED_KEY) => Ok(OctetStringRef::from_der(private_key)
+            .map_err(format_error)?
+            .as_bytes()),
+        Some(&TAG_BOTH) => {
+            let mut reader = SliceReader::new(private_key).map_err(format_error)?;
+            let expanded = reader
+                .sequence(|both| {
+                    OctetStringRef::decode(both)?;
+                    OctetStringRef::decode(both)
+                })
+                .map_err(format_error)?;
+            Ok(reader.finish(expanded).map_err(format_error)?.as_bytes())
+        }
+        Some(&TAG_SEED) => Err(PqCryptoError::UnsupportedAlgorithm(
+            "Seed-only private keys cannot be expanded; use the expandedKey or both form"
+                .to_string(),
+        )),
+        _ => Err(PqCryptoError::invalid_key(
+            "Unrecognised ML-KEM/ML-DSA private key form",
+        )),
+    }
+}
+
+fn expect_label(label: &str, expected: &str) -> Result<()> {
+    if label == expected {
+        Ok(())
+    } else {
+        Err(PqCryptoError::invalid_key(format!(
+            "Expected PEM label {expected}, found {label}"
+        )))
+    }
+}
+
+fn format_error(error: impl std::fmt::Display) -> PqCryptoError {
+    PqCryptoError::invalid_key(error)
+}
@@ -163,3 +163,15 @@ impl From<hex::FromHexError> for PqCryptoError {
         Self::InvalidEncryptedData(format!("Hex decode error: {err}"))
     }
 }
+
+impl From<cryypt_key::KeyError> for PqCryptoError {
+    fn from(err: cryypt_key::KeyError) -> Self {
+        match err {
+            cryypt_key::KeyError::Io(e) => Self::Io(e.to_string()),
+            cryypt_key::KeyError::InvalidKeySize { expected, actual } => {
+                Self::InvalidKeySize { expected, actual }
+            }
+            other => Self::InvalidKey(format!("Key store error: {other}")),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Handshake message encoding
+
+use super::HandshakeSuite;
+use crate::{PqCryptoError, Result};
+
+/// Random nonce length of each peer
+pub(super) const NONCE_LEN: usize = 32;
+
+/// Finished MAC length (HMAC-SHA-512)
+pub(super) const FINISHED_LEN: usize = 64;
+
+const CLIENT_HELLO: u8 = 1;
+const SERVER_HELLO: u8 = 2;
+const CLIENT_FINISH: u8 = 3;
+
+/// First message: the initiator's suite, nonce and ephemeral KEM public key
+pub(super) struct ClientHello {
+    pub suite: [u8; 2],
+    pub nonce: [u8; NONCE_LEN],
+    pub ephemeral_public_key: Vec<u8>,
+}
+
+/// Second message: the responder's encapsulation, identity, signature and MAC
+pub(super) struct ServerHello {
+    pub nonce: [u8; NONCE_LEN],
+    pub ciphertext: Vec<u8>,
+    pub identity: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub finished: Vec<u8>,
+}
+
+/// Third message: the initiator's identity, signature and MAC
+pub(super) struct ClientFinish {
+    pub identity: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub finished: Vec<u8>,
+}
+
+impl ClientHello {
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        encode(
+            CLIENT_HELLO,
+            &[&self.suite, &self.nonce, &self.ephemeral_public_key],
+        )
+    }
+
+    /// Decode and check the fields against the responder's `suite`
+    pub fn decode(bytes: &[u8], suite: HandshakeSuite) -> Result<Self> {
+        let [offered, nonce, ephemeral_public_key] = decode(CLIENT_HELLO, bytes)?;
+        if offered != suite.encode()? {
+            return Err(PqCryptoError::InvalidParameters(format!(
+                "Peer offered another handshake suite than {} with {}",
+                suite.kem(),
+                suite.signature()
+            )));
+        }
+        check_len(
+            "ephemeral public key",
+            suite.kem().public_key_size(),
+            &ephemeral_public_key,
+        )?;
+        Ok(Self {
+            suite: suite.encode()?,
+            nonce: nonce_from(&nonce)?,
+            ephemeral_public_key,
+        })
+    }
+}
+
+impl ServerHello {
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        encode(
+            SERVER_HELLO,
+            &[
+                &self.nonce,
+                &self.ciphertext,
+                &self.identity,
+                &self.signature,
+                &self.finished,
+            ],
+        )
+    }
+
+    pub fn decode(bytes: &[u8], suite: HandshakeSuite) -> Result<Self> {
+        let [nonce, ciphertext, identity, signature, finished] = decode(SERVER_HELLO, bytes)?;
+        check_len("KEM ciphertext", suite.kem().ciphertext_size(), &ciphertext)?;
+        check_identity(suite, &identity, &signature, &finished)?;
+        Ok(Self {
+            nonce: nonce_from(&nonce)?,
+            ciphertext,
+            identity,
+            signature,
+            finished,
+        })
+    }
+}
+
+impl ClientFinish {
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        encode(
+            CLIENT_FINISH,
+            &[&self.identity, &self.signature, &self.finished],
+        )
+    }
+
+    pub fn decode(bytes: &[u8], suite: HandshakeSuite) -> Result<Self> {
+        let [identity, signature, finished] = decode(CLIENT_FINISH, bytes)?;
+        check_identity(suite, &identity, &signature, &finished)?;
+        Ok(Self {
+            identity,
+            signature,
+            finished,
+        })
+    }
+}
+
+fn encode(message_type: u8, fields: &[&[u8]]) -> Result<Vec<u8>> {
+    let mut bytes = vec![message_type];
+    for field in fields {
+        let len = u32::try_from(field.len())
+            .map_err(|_| PqCryptoError::InvalidParameters("Handshake field too long".into()))?;
+        bytes.extend_from_slice(&len.to_be_bytes());
+        bytes.extend_from_slice(field);
+    }
+    Ok(bytes)
+}
+
+fn decode<const N: usize>(message_type: u8, bytes: &[u8]) -> Result<[Vec<u8>; N]> {
+    let malformed =
+        |what: &str| PqCryptoError::SerializationError(format!("{what} handshake message"));
+    let (&found, mut rest) = bytes.split_first().ok_or_else(|| malformed("Empty"))?;
+    if found != message_type {
+        return Err(PqCryptoError::InvalidParameters(format!(
+            "Expected handshake message {message_type}, got {found}"
+        )));
+    }
+
+    let mut fields = Vec::with_capacity(N);
+    for _ in 0..N {
+        let (len, body) = rest
+            .split_first_chunk::<4>()
+            .ok_or_else(|| malformed("Truncated"))?;
+        let len = usize::try_from(u32::from_be_bytes(*len)).map_err(|_| malformed("Oversized"))?;
+        if body.len() < len {
+            return Err(malformed("Truncated"));
+        }
+        let (field, remaining) = body.split_at(len);
+        fields.push(field.to_vec());
+        rest = remaining;
+    }
+    if !rest.is_empty() {
+        return Err(malformed("Over-long"));
+    }
+    fields.try_into().map_err(|_| malformed("Malformed"))
+}
+
+fn check_identity(
+    suite: HandshakeSuite,
+    identity: &[u8],
+    signature: &[u8],
+    finished: &[u8],
+) -> Result<()> {
+    let algorithm = suite.signature();
+    check_len("identity", algorithm.public_key_size(), identity)?;
+    check_len("finished MAC", FINISHED_LEN, finished)?;
+    if signature.is_empty() || signature.len() > algorithm.signature_size() {
+        return Err(PqCryptoError::SerializationError(format!(
+            "{algorithm} signature must be at most {} bytes, got {}",
+            algorithm.signature_size(),
+            signature.len()
+        )));
+    }
+    Ok(())
+}
+
+fn check_len(what: &str, expected: usize, field: &[u8]) -> Result<()> {
+    if field.len() == expected {
+        Ok(())
+    } else {
+        Err(PqCryptoError::SerializationError(format!(
+            "Handshake {what} must be {expected} bytes, got {}",
+            field.len()
+        )))
+    }
+}
+
+fn nonce_from(field: &[u8]) -> Result<[u8; NONCE_LEN]> {
+    field.try_into().map_err(|_| {
+        PqCryptoError::SerializationError(format!(
+            "Handshake nonce must be {NONCE_LEN} bytes, got {}",
+            field.len()
+        ))
+    })
+}
@@ -0,0 +1,227 @@
+//! Authenticated post-quantum key exchange for peer-to-peer sessions
+//!
+//! A three-message, SIGMA-style handshake: the initiator sends an ephemeral ML-KEM (or hybrid)
+//! public key, the responder encapsulates to it, and each peer signs the transcript with its
+//! long-term ML-DSA [`Identity`] and proves knowledge of the shared secret with a MAC. Both
+//! sides then derive one AES-256-GCM key per direction and talk through a [`Transport`] that
+//! numbers its records, so nonces are never reused and replayed, dropped or reordered records
+//! fail to open.
+//!
+//! ```text
+//! Initiator                                                    Responder
+//! ClientHello  = suite, nonce_i, ephemeral KEM public key     ─────────▶
+//!              ◀─────────  ServerHello = nonce_r, KEM ciphertext, identity_r,
+//!                                        signature_r, finished_r
+//! ClientFinish = identity_i, signature_i, finished_i          ─────────▶
+//! ```
+//!
+//! The ephemeral KEM key is used once and dropped, so recorded sessions stay secret if an
+//! identity key later leaks. Identities travel in the clear; pin the expected peer with
+//! `with_peer_key`, or check [`Transport::peer_public_key`] before trusting the session.
+//!
+//! # Wire format
+//!
+//! Each handshake message is a type byte (1, 2 or 3) followed by its fields, each a 4-byte
+//! big-endian length and the field bytes. The suite field is two bytes, the KEM and signature
+//! codes of [`HandshakeSuite`]; nonces are 32 random bytes and MACs 64 bytes. Over a byte
+//! stream, every handshake message and transport record is sent as a frame: a 4-byte
+//! big-endian length and the payload.
+//!
+//! # Transcript
+//!
+//! The transcript hash is a running SHA-512 over length-prefixed items (4-byte big-endian
+//! length, then the bytes), starting with the protocol name `cryypt-handshake/1`:
+//!
+//! 1. suite, `nonce_i`, ephemeral public key
+//! 2. `nonce_r`, KEM ciphertext, responder identity → `TH1`;
+//!    `signature_r` = ML-DSA over `"cryypt-handshake/1 responder signature" || TH1`
+//! 3. `signature_r` → `TH2`; `finished_r` = HMAC-SHA-512(`fk_r`, `TH2`)
+//! 4. `finished_r`, initiator identity → `TH3`;
+//!    `signature_i` = ML-DSA over `"cryypt-handshake/1 initiator signature" || TH3`
+//! 5. `signature_i` → `TH4`; `finished_i` = HMAC-SHA-512(`fk_i`, `TH4`)
+//! 6. `finished_i` → `TH5`, the session's [`Transport::transcript_hash`]
+//!
+//! # Key schedule
+//!
+//! With `PRK = HKDF-Extract-SHA-512(salt = nonce_i || nonce_r, ikm = KEM shared secret)`,
+//! `fk_r` and `fk_i` are 64-byte `HKDF-Expand(PRK, label)` outputs with the labels
+//! `cryypt-handshake/1 responder finished` and `cryypt-handshake/1 initiator finished`. The
+//! traffic key and IV of each direction are expanded with the info
+//! `"cryypt-handshake/1 " || direction || " key" || TH5` (32 bytes) and `... " iv" || TH5`
+//! (12 bytes), where the direction is `initiator to responder` or `responder to initiator`.
+//! Record `n` of a direction is sealed under the nonce `IV XOR (0^32 || n as u64 big-endian)`
+//! with empty AAD and carries the ciphertext followed by the 16-byte tag.
+//!
+//! ```rust,ignore
+//! use cryypt_pqcrypto::handshake::{HandshakeSuite, Initiator};
+//!
+//! let suite = HandshakeSuite::default(); // X-Wing + ML-DSA-65
+//! let mut transport = Initiator::new(suite, client_identity)?
+//!     .with_peer_key(server_public_key)
+//!     .connect(&mut stream)
+//!     .await?;
+//! transport.send(&mut stream, b"hello").await?;
+//! let reply = transport.recv(&mut stream).await?;
+//! ```
+
+mod messages;
+mod peer;
+mod schedule;
+mod transport;
+
+pub use peer::{AwaitingFinish, AwaitingResponse, Initiator, Responder};
+pub use transport::{MAX_FRAME_LEN, Transport, read_frame, write_frame};
+
+use crate::backend::Backend;
+use crate::{KemAlgorithm, PqCryptoError, Result, SignatureAlgorithm};
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// Protocol name, the first transcript item and the prefix of every label
+const PROTOCOL: &[u8] = b"cryypt-handshake/1";
+
+/// KEM and identity signature algorithms of a handshake
+///
+/// Both peers must be configured with the same suite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandshakeSuite {
+    kem: KemAlgorithm,
+    signature: SignatureAlgorithm,
+}
+
+impl HandshakeSuite {
+    /// Combine a KEM with an ML-DSA identity algorithm
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if `signature` is not ML-DSA.
+    pub fn new(kem: KemAlgorithm, signature: SignatureAlgorithm) -> Result<Self> {
+        signature_code(signature)?;
+        Ok(Self { kem, signature })
+    }
+
+    /// Key encapsulation mechanism for the ephemeral key exchange
+    #[must_use]
+    pub fn kem(self) -> KemAlgorithm {
+        self.kem
+    }
+
+    /// Signature algorithm of both peers' identities
+    #[must_use]
+    pub fn signature(self) -> SignatureAlgorithm {
+        self.signature
+    }
+
+    /// Two-byte wire encoding: KEM code, then signature code
+    fn encode(self) -> Result<[u8; 2]> {
+        Ok([kem_code(self.kem), signature_code(self.signature)?])
+    }
+}
+
+impl Default for HandshakeSuite {
+    /// X-Wing with ML-DSA-65 identities
+    fn default() -> Self {
+        Self {
+            kem: KemAlgorithm::X25519MlKem768,
+            signature: SignatureAlgorithm::MlDsa65,
+        }
+    }
+}
+
+/// A peer's long-term ML-DSA signing key pair
+#[derive(Clone)]
+pub struct Identity {
+    algorithm: SignatureAlgorithm,
+    public_key: Vec<u8>,
+    secret_key: Zeroizing<Vec<u8>>,
+}
+
+impl Identity {
+    /// Generate a new identity
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if `algorithm` is not ML-DSA.
+    pub fn generate(algorithm: SignatureAlgorithm) -> Result<Self> {
+        signature_code(algorithm)?;
+        let (public_key, secret_key) = Backend::PqClean.signature_keypair(algorithm)?;
+        Ok(Self {
+            algorithm,
+            public_key,
+            secret_key,
+        })
+    }
+
+    /// Use an existing key pair, such as one loaded from a key store
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if `algorithm` is not ML-DSA and
+    /// `PqCryptoError::InvalidKeySize` if either key has the wrong length.
+    pub fn new(
+        algorithm: SignatureAlgorithm,
+        public_key: impl Into<Vec<u8>>,
+        secret_key: impl Into<Vec<u8>>,
+    ) -> Result<Self> {
+        signature_code(algorithm)?;
+        let identity = Self {
+            algorithm,
+            public_key: public_key.into(),
+            secret_key: Zeroizing::new(secret_key.into()),
+        };
+        check_size(algorithm.public_key_size(), identity.public_key.len())?;
+        check_size(algorithm.secret_key_size(), identity.secret_key.len())?;
+        Ok(identity)
+    }
+
+    /// Signature algorithm
+    #[must_use]
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    /// Public key, for the peer to pin
+    #[must_use]
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+}
+
+impl fmt::Debug for Identity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Identity")
+            .field("algorithm", &self.algorithm)
+            .field("public_key_len", &self.public_key.len())
+            .finish_non_exhaustive()
+    }
+}
+
+fn kem_code(kem: KemAlgorithm) -> u8 {
+    match kem {
+        KemAlgorithm::MlKem512 => 1,
+        KemAlgorithm::MlKem768 => 2,
+        KemAlgorithm::MlKem1024 => 3,
+        KemAlgorithm::X25519MlKem768 => 16,
+        KemAlgorithm::P256MlKem768 => 17,
+        KemAlgorithm::P384MlKem1024 => 18,
+    }
+}
+
+fn signature_code(signature: SignatureAlgorithm) -> Result<u8> {
+    match signature {
+        SignatureAlgorithm::MlDsa44 => Ok(1),
+        SignatureAlgorithm::MlDsa65 => Ok(2),
+        SignatureAlgorithm::MlDsa87 => Ok(3),
+        other => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+            "Handshake identities must be ML-DSA, not {other}"
+        ))),
+    }
+}
+
+fn check_size(expected: usize, actual: usize) -> Result<()> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(PqCryptoError::InvalidKeySize { expected, actual })
+    }
+}
@@ -0,0 +1,332 @@
+//! Initiator and responder state machines
+
+use super::messages::{ClientFinish, ClientHello, NONCE_LEN, ServerHello};
+use super::schedule::{self, KeySchedule, Role, Transcript};
+use super::transport::{Transport, read_frame, write_frame};
+use super::{HandshakeSuite, Identity};
+use crate::backend::Backend;
+use crate::{PqCryptoError, Result};
+use rand::RngCore;
+use tokio::io::{AsyncRead, AsyncWrite};
+use zeroize::Zeroizing;
+
+/// The peer that opens the handshake
+#[derive(Debug, Clone)]
+pub struct Initiator {
+    suite: HandshakeSuite,
+    identity: Identity,
+    peer_key: Option<Vec<u8>>,
+}
+
+/// An initiator that has sent its `ClientHello` and waits for the `ServerHello`
+pub struct AwaitingResponse {
+    suite: HandshakeSuite,
+    identity: Identity,
+    peer_key: Option<Vec<u8>>,
+    nonce: [u8; NONCE_LEN],
+    ephemeral_secret_key: Zeroizing<Vec<u8>>,
+    transcript: Transcript,
+}
+
+/// The peer that answers a handshake
+#[derive(Debug, Clone)]
+pub struct Responder {
+    suite: HandshakeSuite,
+    identity: Identity,
+    peer_key: Option<Vec<u8>>,
+}
+
+/// A responder that has sent its `ServerHello` and waits for the `ClientFinish`
+pub struct AwaitingFinish {
+    suite: HandshakeSuite,
+    peer_key: Option<Vec<u8>>,
+    schedule: KeySchedule,
+    transcript: Transcript,
+}
+
+impl Initiator {
+    /// Start a handshake as `identity`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if `identity` is not for the suite's
+    /// signature algorithm.
+    pub fn new(suite: HandshakeSuite, identity: Identity) -> Result<Self> {
+        check_identity(suite, &identity)?;
+        Ok(Self {
+            suite,
+            identity,
+            peer_key: None,
+        })
+    }
+
+    /// Only accept a responder with this ML-DSA public key
+    #[must_use]
+    pub fn with_peer_key(mut self, public_key: impl Into<Vec<u8>>) -> Self {
+        self.peer_key = Some(public_key.into());
+        self
+    }
+
+    /// Create the `ClientHello`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ephemeral KEM key pair cannot be generated.
+    pub fn start(self) -> Result<(AwaitingResponse, Vec<u8>)> {
+        let (ephemeral_public_key, ephemeral_secret_key) =
+            Backend::PqClean.kem_keypair(self.suite.kem())?;
+        let hello = ClientHello {
+            suite: self.suite.encode()?,
+            nonce: random_nonce(),
+            ephemeral_public_key,
+        };
+
+        let mut transcript = Transcript::new();
+        transcript.absorb(&hello.suite);
+        transcript.absorb(&hello.nonce);
+        transcript.absorb(&hello.ephemeral_public_key);
+
+        let message = hello.encode()?;
+        Ok((
+            AwaitingResponse {
+                suite: self.suite,
+                identity: self.identity,
+                peer_key: self.peer_key,
+                nonce: hello.nonce,
+                ephemeral_secret_key,
+                transcript,
+            },
+            message,
+        ))
+    }
+
+    /// Run the whole handshake over `stream`, one frame per message
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::AuthenticationFailed` if the responder's signature or MAC is
+    /// wrong or it is not the pinned peer, or an error if the stream fails.
+    pub async fn connect<S: AsyncRead + AsyncWrite + Unpin>(
+        self,
+        stream: &mut S,
+    ) -> Result<Transport> {
+        let (waiting, hello) = self.start()?;
+        write_frame(stream, &hello).await?;
+        let response = read_frame(stream).await?;
+        let (transport, finish) = waiting.finish(&response)?;
+        write_frame(stream, &finish).await?;
+        Ok(transport)
+    }
+}
+
+impl AwaitingResponse {
+    /// Authenticate the `ServerHello` and create the `ClientFinish`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::AuthenticationFailed` if the responder's signature or MAC is
+    /// wrong or it is not the pinned peer, and an error if the message is malformed.
+    pub fn finish(mut self, response: &[u8]) -> Result<(Transport, Vec<u8>)> {
+        let hello = ServerHello::decode(response, self.suite)?;
+        check_peer(self.peer_key.as_deref(), &hello.identity)?;
+
+        let shared_secret = Backend::PqClean.decapsulate(
+            self.suite.kem(),
+            &self.ephemeral_secret_key,
+            &hello.ciphertext,
+        )?;
+        let schedule = KeySchedule::new(&shared_secret, &self.nonce, &hello.nonce);
+
+        self.transcript.absorb(&hello.nonce);
+        self.transcript.absorb(&hello.ciphertext);
+        self.transcript.absorb(&hello.identity);
+        schedule::verify(
+            Role::Responder,
+            self.suite.signature(),
+            &hello.identity,
+            &self.transcript.hash(),
+            &hello.signature,
+        )?;
+        self.transcript.absorb(&hello.signature);
+        schedule.verify_finished(Role::Responder, &self.transcript.hash(), &hello.finished)?;
+        self.transcript.absorb(&hello.finished);
+
+        self.transcript.absorb(self.identity.public_key());
+        let signature = schedule::sign(
+            Role::Initiator,
+            self.suite.signature(),
+            &self.identity.secret_key,
+            &self.transcript.hash(),
+        )?;
+        self.transcript.absorb(&signature);
+        let finished = schedule.finished(Role::Initiator, &self.transcript.hash())?;
+        self.transcript.absorb(&finished);
+
+        let finish = ClientFinish {
+            identity: self.identity.public_key().to_vec(),
+            signature,
+            finished,
+        };
+        let hash = self.transcript.hash();
+        let transport = Transport::new(
+            schedule.traffic(Role::Initiator, &hash)?,
+            schedule.traffic(Role::Responder, &hash)?,
+            hello.identity,
+            hash,
+        );
+        Ok((transport, finish.encode()?))
+    }
+}
+
+impl Responder {
+    /// Answer handshakes as `identity`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` if `identity` is not for the suite's
+    /// signature algorithm.
+    pub fn new(suite: HandshakeSuite, identity: Identity) -> Result<Self> {
+        check_identity(suite, &identity)?;
+        Ok(Self {
+            suite,
+            identity,
+            peer_key: None,
+        })
+    }
+
+    /// Only accept an initiator with this ML-DSA public key
+    #[must_use]
+    pub fn with_peer_key(mut self, public_key: impl Into<Vec<u8>>) -> Self {
+        self.peer_key = Some(public_key.into());
+        self
+    }
+
+    /// Answer a `ClientHello` with the `ServerHello`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidParameters` if the initiator offered another suite, or
+    /// an error if the message is malformed.
+    pub fn respond(self, hello: &[u8]) -> Result<(AwaitingFinish, Vec<u8>)> {
+        let hello = ClientHello::decode(hello, self.suite)?;
+        let (ciphertext, shared_secret) =
+            Backend::PqClean.encapsulate(self.suite.kem(), &hello.ephemeral_public_key)?;
+        let nonce = random_nonce();
+        let schedule = KeySchedule::new(&shared_secret, &hello.nonce, &nonce);
+
+        let mut transcript = Transcript::new();
+        transcript.absorb(&hello.suite);
+        transcript.absorb(&hello.nonce);
+        transcript.absorb(&hello.ephemeral_public_key);
+        transcript.absorb(&nonce);
+        transcript.absorb(&ciphertext);
+        transcript.absorb(self.identity.public_key());
+        let signature = schedule::sign(
+            Role::Responder,
+            self.suite.signature(),
+            &self.identity.secret_key,
+            &transcript.hash(),
+        )?;
+        transcript.absorb(&signature);
+        let finished = schedule.finished(Role::Responder, &transcript.hash())?;
+        transcript.absorb(&finished);
+
+        let response = ServerHello {
+            nonce,
+            ciphertext,
+            identity: self.identity.public_key().to_vec(),
+            signature,
+            finished,
+        };
+        Ok((
+            AwaitingFinish {
+                suite: self.suite,
+                peer_key: self.peer_key,
+                schedule,
+                transcript,
+            },
+            response.encode()?,
+        ))
+    }
+
+    /// Run the whole handshake over `stream`, one frame per message
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::AuthenticationFailed` if the initiator's signature or MAC is
+    /// wrong or it is not the pinned peer, or an error if the stream fails.
+    pub async fn accept<S: AsyncRead + AsyncWrite + Unpin>(
+        self,
+        stream: &mut S,
+    ) -> Result<Transport> {
+        let hello = read_frame(stream).await?;
+        let (waiting, response) = self.respond(&hello)?;
+        write_frame(stream, &response).await?;
+        let finish = read_frame(stream).await?;
+        waiting.finish(&finish)
+    }
+}
+
+impl AwaitingFinish {
+    /// Authenticate the `ClientFinish` and open the session
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::AuthenticationFailed` if the initiator's signature or MAC is
+    /// wrong or it is not the pinned peer, and an error if the message is malformed.
+    pub fn finish(mut self, finish: &[u8]) -> Result<Transport> {
+        let finish = ClientFinish::decode(finish, self.suite)?;
+        check_peer(self.peer_key.as_deref(), &finish.identity)?;
+
+        self.transcript.absorb(&finish.identity);
+        schedule::verify(
+            Role::Initiator,
+            self.suite.signature(),
+            &finish.identity,
+            &self.transcript.hash(),
+            &finish.signature,
+        )?;
+        self.transcript.absorb(&finish.signature);
+        self.schedule.verify_finished(
+            Role::Initiator,
+            &self.transcript.hash(),
+            &finish.finished,
+        )?;
+        self.transcript.absorb(&finish.finished);
+
+        let hash = self.transcript.hash();
+        Ok(Transport::new(
+            self.schedule.traffic(Role::Responder, &hash)?,
+            self.schedule.traffic(Role::Initiator, &hash)?,
+            finish.identity,
+            hash,
+        ))
+    }
+}
+
+fn check_identity(suite: HandshakeSuite, identity: &Identity) -> Result<()> {
+    if identity.algorithm() == suite.signature() {
+        Ok(())
+    } else {
+        Err(PqCryptoError::UnsupportedAlgorithm(format!(
+            "Identity is {}, but the suite uses {}",
+            identity.algorithm(),
+            suite.signature()
+        )))
+    }
+}
+
+fn check_peer(pinned: Option<&[u8]>, identity: &[u8]) -> Result<()> {
+    match pinned {
+        Some(expected) if expected != identity => Err(PqCryptoError::auth_failed(
+            "Peer identity does not match the pinned public key",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn random_nonce() -> [u8; NONCE_LEN] {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut nonce);
+    nonce
+}
@@ -0,0 +1,190 @@
+//! Transcript hash, key schedule and handshake signatures
+
+use super::PROTOCOL;
+use super::messages::{FINISHED_LEN, NONCE_LEN};
+use super::transport::{IV_LEN, KEY_LEN};
+use crate::backend::Backend;
+use crate::{PqCryptoError, Result, SignatureAlgorithm, SigningOptions};
+use hkdf::Hkdf;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha512};
+use zeroize::Zeroizing;
+
+/// Transcript hash length
+pub(super) const HASH_LEN: usize = 64;
+
+/// Which side of the handshake a key, signature or MAC belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Role {
+    Initiator,
+    Responder,
+}
+
+impl Role {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Initiator => "initiator",
+            Self::Responder => "responder",
+        }
+    }
+
+    fn direction(self) -> &'static str {
+        match self {
+            Self::Initiator => "initiator to responder",
+            Self::Responder => "responder to initiator",
+        }
+    }
+}
+
+/// Running SHA-512 over length-prefixed transcript items
+#[derive(Clone)]
+pub(super) struct Transcript(Sha512);
+
+impl Transcript {
+    pub fn new() -> Self {
+        let mut transcript = Self(Sha512::new());
+        transcript.absorb(PROTOCOL);
+        transcript
+    }
+
+    pub fn absorb(&mut self, item: &[u8]) {
+        // Handshake fields are far below 4 GiB; messages checked their lengths on decode
+        #[allow(clippy::cast_possible_truncation)]
+        self.0.update((item.len() as u32).to_be_bytes());
+        self.0.update(item);
+    }
+
+    pub fn hash(&self) -> [u8; HASH_LEN] {
+        let mut hash = [0u8; HASH_LEN];
+        hash.copy_from_slice(&self.0.clone().finalize());
+        hash
+    }
+}
+
+/// Secrets derived from the KEM shared secret
+pub(super) struct KeySchedule {
+    prk: Zeroizing<Vec<u8>>,
+}
+
+/// Traffic key and IV of one direction
+pub(super) struct DirectionKeys {
+    pub key: Zeroizing<Vec<u8>>,
+    pub iv: [u8; IV_LEN],
+}
+
+impl KeySchedule {
+    pub fn new(
+        shared_secret: &[u8],
+        initiator_nonce: &[u8; NONCE_LEN],
+        responder_nonce: &[u8; NONCE_LEN],
+    ) -> Self {
+        let salt = [initiator_nonce.as_slice(), responder_nonce.as_slice()].concat();
+        let (prk, _) = Hkdf::<Sha512>::extract(Some(&salt), shared_secret);
+        Self {
+            prk: Zeroizing::new(prk.to_vec()),
+        }
+    }
+
+    /// MAC proving `role` holds the shared secret, over transcript hash `hash`
+    pub fn finished(&self, role: Role, hash: &[u8; HASH_LEN]) -> Result<Vec<u8>> {
+        Ok(self
+            .finished_mac(role, hash)?
+            .finalize()
+            .into_bytes()
+            .to_vec())
+    }
+
+    /// Check the peer's finished MAC in constant time
+    pub fn verify_finished(&self, role: Role, hash: &[u8; HASH_LEN], mac: &[u8]) -> Result<()> {
+        self.finished_mac(role, hash)?
+            .verify_slice(mac)
+            .map_err(|_| PqCryptoError::auth_failed(format!("Bad {} finished MAC", role.name())))
+    }
+
+    /// Traffic keys for records sent by `role`, bound to the final transcript hash
+    pub fn traffic(&self, role: Role, hash: &[u8; HASH_LEN]) -> Result<DirectionKeys> {
+        let mut key = Zeroizing::new(vec![0u8; KEY_LEN]);
+        self.expand(
+            &[label(role.direction(), " key").as_slice(), hash.as_slice()],
+            &mut key,
+        )?;
+        let mut iv = [0u8; IV_LEN];
+        self.expand(
+            &[label(role.direction(), " iv").as_slice(), hash.as_slice()],
+            &mut iv,
+        )?;
+        Ok(DirectionKeys { key, iv })
+    }
+
+    fn finished_mac(&self, role: Role, hash: &[u8; HASH_LEN]) -> Result<Hmac<Sha512>> {
+        let mut key = Zeroizing::new([0u8; FINISHED_LEN]);
+        self.expand(
+            &[label(role.name(), " finished").as_slice()],
+            key.as_mut_slice(),
+        )?;
+        let mut mac = <Hmac<Sha512> as KeyInit>::new_from_slice(key.as_slice())
+            .map_err(|e| PqCryptoError::internal(format!("HMAC key: {e}")))?;
+        mac.update(hash);
+        Ok(mac)
+    }
+
+    fn expand(&self, info: &[&[u8]], okm: &mut [u8]) -> Result<()> {
+        Hkdf::<Sha512>::from_prk(&self.prk)
+            .map_err(|_| PqCryptoError::internal("Invalid handshake PRK length"))?
+            .expand_multi_info(info, okm)
+            .map_err(|_| PqCryptoError::internal("Handshake key expansion failed"))
+    }
+}
+
+/// `"cryypt-handshake/1 " || subject || suffix`
+fn label(subject: &str, suffix: &str) -> Vec<u8> {
+    let parts: [&[u8]; 4] = [PROTOCOL, b" ", subject.as_bytes(), suffix.as_bytes()];
+    parts.concat()
+}
+
+/// Sign transcript hash `hash` as `role`
+pub(super) fn sign(
+    role: Role,
+    algorithm: SignatureAlgorithm,
+    secret_key: &[u8],
+    hash: &[u8; HASH_LEN],
+) -> Result<Vec<u8>> {
+    Backend::PqClean.sign(
+        algorithm,
+        secret_key,
+        &signed_message(role, hash),
+        &SigningOptions::new(),
+    )
+}
+
+/// Verify `role`'s signature over transcript hash `hash`
+pub(super) fn verify(
+    role: Role,
+    algorithm: SignatureAlgorithm,
+    public_key: &[u8],
+    hash: &[u8; HASH_LEN],
+    signature: &[u8],
+) -> Result<()> {
+    let is_valid = Backend::PqClean.verify(
+        algorithm,
+        public_key,
+        &signed_message(role, hash),
+        signature,
+        &SigningOptions::new(),
+    )?;
+    if is_valid {
+        Ok(())
+    } else {
+        Err(PqCryptoError::auth_failed(format!(
+            "Bad {} signature",
+            role.name()
+        )))
+    }
+}
+
+fn signed_message(role: Role, hash: &[u8; HASH_LEN]) -> Vec<u8> {
+    let mut message = label(role.name(), " signature");
+    message.extend_from_slice(hash);
+    message
+}
@@ -0,0 +1,229 @@
+//! Encrypted record transport and stream framing
+
+use super::schedule::{DirectionKeys, HASH_LEN};
+use crate::{PqCryptoError, Result};
+use cryypt_cipher::AesBuilder;
+use cryypt_cipher::cipher::api::aes_builder::{AES_GCM_NONCE_SIZE, AES_GCM_TAG_SIZE};
+use std::fmt;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use zeroize::Zeroizing;
+
+/// AES-256-GCM traffic key length
+pub(super) const KEY_LEN: usize = 32;
+
+/// Per-direction IV length, XORed with the record counter to form each nonce
+pub(super) const IV_LEN: usize = AES_GCM_NONCE_SIZE;
+
+/// Largest frame [`read_frame`] accepts, in bytes
+pub const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Record protection for one direction
+struct Direction {
+    key: Zeroizing<Vec<u8>>,
+    iv: [u8; IV_LEN],
+    counter: u64,
+}
+
+impl Direction {
+    fn new(keys: DirectionKeys) -> Self {
+        Self {
+            key: keys.key,
+            iv: keys.iv,
+            counter: 0,
+        }
+    }
+
+    /// Nonce of the next record: the IV XOR the big-endian counter in its last 8 bytes
+    fn next_nonce(&self) -> Result<[u8; IV_LEN]> {
+        if self.counter == u64::MAX {
+            return Err(PqCryptoError::InvalidParameters(
+                "Transport record counter exhausted; start a new handshake".to_string(),
+            ));
+        }
+        let mut nonce = self.iv;
+        for (byte, counter) in nonce[IV_LEN - 8..]
+            .iter_mut()
+            .zip(self.counter.to_be_bytes())
+        {
+            *byte ^= counter;
+        }
+        Ok(nonce)
+    }
+}
+
+/// An established session: AES-256-GCM records with counter nonces in each direction
+///
+/// Records must be opened in the order they were sealed; a replayed, dropped, reordered or
+/// tampered record fails to open.
+pub struct Transport {
+    send: Direction,
+    receive: Direction,
+    peer_public_key: Vec<u8>,
+    transcript_hash: [u8; HASH_LEN],
+}
+
+impl Transport {
+    pub(super) fn new(
+        send: DirectionKeys,
+        receive: DirectionKeys,
+        peer_public_key: Vec<u8>,
+        transcript_hash: [u8; HASH_LEN],
+    ) -> Self {
+        Self {
+            send: Direction::new(send),
+            receive: Direction::new(receive),
+            peer_public_key,
+            transcript_hash,
+        }
+    }
+
+    /// The peer's authenticated ML-DSA identity
+    #[must_use]
+    pub fn peer_public_key(&self) -> &[u8] {
+        &self.peer_public_key
+    }
+
+    /// Hash of the whole handshake transcript, equal on both sides; usable as a session id
+    /// or for channel binding
+    #[must_use]
+    pub fn transcript_hash(&self) -> &[u8; HASH_LEN] {
+        &self.transcript_hash
+    }
+
+    /// Number of records sealed so far
+    #[must_use]
+    pub fn records_sent(&self) -> u64 {
+        self.send.counter
+    }
+
+    /// Number of records opened so far
+    #[must_use]
+    pub fn records_received(&self) -> u64 {
+        self.receive.counter
+    }
+
+    /// Encrypt the next outgoing record
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record counter is exhausted or encryption fails.
+    pub async fn seal(&mut self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let nonce = self.send.next_nonce()?;
+        let sealed = AesBuilder::new()
+            .with_key(self.send.key.to_vec())
+            .encrypt_detached_with_nonce(nonce, plaintext)
+            .await
+            .map_err(|e| PqCryptoError::internal(format!("Record encryption failed: {e}")))?;
+        self.send.counter += 1;
+
+        let mut record = sealed.ciphertext;
+        record.extend_from_slice(&sealed.tag);
+        Ok(record)
+    }
+
+    /// Decrypt the next incoming record
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::AuthenticationFailed` if the record is not the next one the
+    /// peer sealed, or was modified.
+    pub async fn open(&mut self, record: &[u8]) -> Result<Vec<u8>> {
+        let nonce = self.receive.next_nonce()?;
+        let split = record
+            .len()
+            .checked_sub(AES_GCM_TAG_SIZE)
+            .ok_or_else(|| PqCryptoError::auth_failed("Transport record too short"))?;
+        let (ciphertext, tag) = record.split_at(split);
+        let plaintext = AesBuilder::new()
+            .with_key(self.receive.key.to_vec())
+            .decrypt_detached(&nonce, ciphertext, tag)
+            .await
+            .map_err(|_| {
+                PqCryptoError::auth_failed(format!(
+                    "Transport record {} failed to authenticate",
+                    self.receive.counter
+                ))
+            })?;
+        self.receive.counter += 1;
+        Ok(plaintext)
+    }
+
+    /// Seal `plaintext` and write it to `writer` as one frame
+    ///
+    /// # Errors
+    ///
+    /// As [`Transport::seal`] and [`write_frame`].
+    pub async fn send<W: AsyncWrite + Unpin>(
+        &mut self,
+        writer: &mut W,
+        plaintext: &[u8],
+    ) -> Result<()> {
+        let record = self.seal(plaintext).await?;
+        write_frame(writer, &record).await
+    }
+
+    /// Read one frame from `reader` and open it
+    ///
+    /// # Errors
+    ///
+    /// As [`read_frame`] and [`Transport::open`].
+    pub async fn recv<R: AsyncRead + Unpin>(&mut self, reader: &mut R) -> Result<Vec<u8>> {
+        let record = read_frame(reader).await?;
+        self.open(&record).await
+    }
+}
+
+impl fmt::Debug for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transport")
+            .field("records_sent", &self.send.counter)
+            .field("records_received", &self.receive.counter)
+            .field("transcript_hash", &hex::encode(self.transcript_hash))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Write `payload` as a frame: its 4-byte big-endian length, then the bytes
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidParameters` if `payload` exceeds [`MAX_FRAME_LEN`] and
+/// `PqCryptoError::Io` if writing fails.
+pub async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, payload: &[u8]) -> Result<()> {
+    if payload.len() > MAX_FRAME_LEN {
+        return Err(PqCryptoError::InvalidParameters(format!(
+            "Frame of {} bytes exceeds the {MAX_FRAME_LEN}-byte limit",
+            payload.len()
+        )));
+    }
+    let io_error = |e: std::io::Error| PqCryptoError::Io(format!("Failed to write frame: {e}"));
+    // Bounded by MAX_FRAME_LEN above
+    #[allow(clippy::cast_possible_truncation)]
+    writer
+        .write_all(&(payload.len() as u32).to_be_bytes())
+        .await
+        .map_err(io_error)?;
+    writer.write_all(payload).await.map_err(io_error)?;
+    writer.flush().await.map_err(io_error)
+}
+
+/// Read one frame written by [`write_frame`]
+///
+/// # Errors
+///
+/// Returns `PqCryptoError::InvalidParameters` for a frame over [`MAX_FRAME_LEN`] and
+/// `PqCryptoError::Io` if reading fails or the stream ends mid-frame.
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>> {
+    let io_error = |e: std::io::Error| PqCryptoError::Io(format!("Failed to read frame: {e}"));
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len).await.map_err(io_error)?;
+    let len = usize::try_from(u32::from_be_bytes(len)).unwrap_or(usize::MAX);
+    if len > MAX_FRAME_LEN {
+        return Err(PqCryptoError::InvalidParameters(format!(
+            "Frame of {len} bytes exceeds the {MAX_FRAME_LEN}-byte limit"
+        )));
+    }
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload).await.map_err(io_error)?;
+    Ok(payload)
+}
@@ -0,0 +1,97 @@
+//! HPKE authenticated encryption algorithms
+
+use crate::{PqCryptoError, Result};
+use aes_gcm::aead::{Aead, KeyInit, Nonce, Payload};
+use aes_gcm::{Aes128Gcm, Aes256Gcm};
+use chacha20poly1305::ChaCha20Poly1305;
+
+/// AEAD of an HPKE suite
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HpkeAead {
+    /// AES-128-GCM
+    Aes128Gcm,
+    /// AES-256-GCM
+    #[default]
+    Aes256Gcm,
+    /// ChaCha20-Poly1305
+    ChaCha20Poly1305,
+    /// No encryption; the context can only export secrets
+    ExportOnly,
+}
+
+impl HpkeAead {
+    /// IANA AEAD identifier
+    #[must_use]
+    pub fn id(self) -> u16 {
+        match self {
+            Self::Aes128Gcm => 0x0001,
+            Self::Aes256Gcm => 0x0002,
+            Self::ChaCha20Poly1305 => 0x0003,
+            Self::ExportOnly => 0xFFFF,
+        }
+    }
+
+    /// Key length `Nk` in bytes
+    #[must_use]
+    pub fn key_len(self) -> usize {
+        match self {
+            Self::Aes128Gcm => 16,
+            Self::Aes256Gcm | Self::ChaCha20Poly1305 => 32,
+            Self::ExportOnly => 0,
+        }
+    }
+
+    /// Nonce length `Nn` in bytes
+    #[must_use]
+    pub fn nonce_len(self) -> usize {
+        match self {
+            Self::ExportOnly => 0,
+            _ => 12,
+        }
+    }
+
+    pub(super) fn seal(self, key: &[u8], nonce: &[u8], aad: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+        let payload = Payload { msg, aad };
+        match self {
+            Self::Aes128Gcm => {
+                cipher::<Aes128Gcm>(key)?.encrypt(Nonce::<Aes128Gcm>::from_slice(nonce), payload)
+            }
+            Self::Aes256Gcm => {
+                cipher::<Aes256Gcm>(key)?.encrypt(Nonce::<Aes256Gcm>::from_slice(nonce), payload)
+            }
+            Self::ChaCha20Poly1305 => cipher::<ChaCha20Poly1305>(key)?
+                .encrypt(Nonce::<ChaCha20Poly1305>::from_slice(nonce), payload),
+            Self::ExportOnly => return Err(export_only()),
+        }
+        .map_err(|_| PqCryptoError::InternalError("HPKE AEAD encryption failed".to_string()))
+    }
+
+    pub(super) fn open(self, key: &[u8], nonce: &[u8], aad: &[u8], msg: &[u8]) -> Result<Vec<u8>> {
+        let payload = Payload { msg, aad };
+        match self {
+            Self::Aes128Gcm => {
+                cipher::<Aes128Gcm>(key)?.decrypt(Nonce::<Aes128Gcm>::from_slice(nonce), payload)
+            }
+            Self::Aes256Gcm => {
+                cipher::<Aes256Gcm>(key)?.decrypt(Nonce::<Aes256Gcm>::from_slice(nonce), payload)
+            }
+            Self::ChaCha20Poly1305 => cipher::<ChaCha20Poly1305>(key)?
+                .decrypt(Nonce::<ChaCha20Poly1305>::from_slice(nonce), payload),
+            Self::ExportOnly => return Err(export_only()),
+        }
+        .map_err(|_| {
+            PqCryptoError::AuthenticationFailed("HPKE ciphertext failed authentication".to_string())
+        })
+    }
+}
+
+fn cipher<A: KeyInit>(key: &[u8]) -> Result<A> {
+    A::new_from_slice(key)
+        .map_err(|_| PqCryptoError::InternalError("Invalid HPKE AEAD key length".to_string()))
+}
+
+fn export_only() -> PqCryptoError {
+    PqCryptoError::UnsupportedAlgorithm(
+        "Export-only HPKE contexts cannot seal or open messages".to_string(),
+    )
+}
@@ -0,0 +1,185 @@
+//! HPKE key schedule and encryption contexts (RFC 9180 §5)
+
+use super::{HpkeAead, HpkeKdf, HpkeMode};
+use crate::{PqCryptoError, Result};
+use zeroize::Zeroizing;
+
+/// Secrets shared by the sender and receiver contexts
+pub(super) struct Context {
+    kdf: HpkeKdf,
+    aead: HpkeAead,
+    suite_id: [u8; 10],
+    key: Zeroizing<Vec<u8>>,
+    base_nonce: Zeroizing<Vec<u8>>,
+    exporter_secret: Zeroizing<Vec<u8>>,
+    sequence: u64,
+}
+
+/// Inputs to the key schedule besides the KEM shared secret
+pub(super) struct ScheduleInput<'a> {
+    pub(super) mode: HpkeMode,
+    pub(super) info: &'a [u8],
+    pub(super) psk: &'a [u8],
+    pub(super) psk_id: &'a [u8],
+}
+
+impl Context {
+    /// `KeySchedule(mode, shared_secret, info, psk, psk_id)`
+    pub(super) fn key_schedule(
+        kdf: HpkeKdf,
+        aead: HpkeAead,
+        suite_id: [u8; 10],
+        shared_secret: &[u8],
+        input: &ScheduleInput<'_>,
+    ) -> Result<Self> {
+        let psk_id_hash = kdf.labeled_extract(&suite_id, b"", b"psk_id_hash", input.psk_id);
+        let info_hash = kdf.labeled_extract(&suite_id, b"", b"info_hash", input.info);
+        let context = [
+            [input.mode.id()].as_slice(),
+            psk_id_hash.as_slice(),
+            info_hash.as_slice(),
+        ]
+        .concat();
+
+        let secret = kdf.labeled_extract(&suite_id, shared_secret, b"secret", input.psk);
+        let expand =
+            |label: &[u8], len| kdf.labeled_expand(&suite_id, &secret, label, &context, len);
+        Ok(Self {
+            kdf,
+            aead,
+            suite_id,
+            key: expand(b"key", aead.key_len())?,
+            base_nonce: expand(b"base_nonce", aead.nonce_len())?,
+            exporter_secret: expand(b"exp", kdf.hash_len())?,
+            sequence: 0,
+        })
+    }
+
+    /// `ComputeNonce(seq)`
+    fn nonce(&self) -> Vec<u8> {
+        let mut nonce = self.base_nonce.to_vec();
+        let counter = self.sequence.to_be_bytes();
+        let offset = nonce.len().saturating_sub(counter.len());
+        for (byte, count) in nonce[offset..].iter_mut().zip(counter) {
+            *byte ^= count;
+        }
+        nonce
+    }
+
+    /// `IncrementSeq()`
+    fn advance(&mut self) -> Result<()> {
+        self.sequence = self.sequence.checked_add(1).ok_or_else(|| {
+            PqCryptoError::InvalidParameters("HPKE context message limit reached".to_string())
+        })?;
+        Ok(())
+    }
+
+    fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        let ciphertext = self.aead.seal(&self.key, &self.nonce(), aad, plaintext)?;
+        self.advance()?;
+        Ok(ciphertext)
+    }
+
+    /// The sequence number only advances once a message authenticates
+    fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let plaintext = self.aead.open(&self.key, &self.nonce(), aad, ciphertext)?;
+        self.advance()?;
+        Ok(plaintext)
+    }
+
+    fn export(&self, exporter_context: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>> {
+        if len > 255 * self.kdf.hash_len() {
+            return Err(PqCryptoError::InvalidParameters(format!(
+                "HPKE exports are limited to {} bytes, requested {len}",
+                255 * self.kdf.hash_len()
+            )));
+        }
+        self.kdf.labeled_expand(
+            &self.suite_id,
+            &self.exporter_secret,
+            b"sec",
+            exporter_context,
+            len,
+        )
+    }
+
+    fn fmt_redacted(&self, name: &str, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct(name)
+            .field("kdf", &self.kdf)
+            .field("aead", &self.aead)
+            .field("sequence", &self.sequence)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sender side of an HPKE context, encrypting a sequence of messages
+pub struct HpkeSenderContext(pub(super) Context);
+
+/// Receiver side of an HPKE context, decrypting messages in the order they were sealed
+pub struct HpkeReceiverContext(pub(super) Context);
+
+impl HpkeSenderContext {
+    /// Encrypt the next message (`ContextS.Seal`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` for export-only suites and
+    /// `PqCryptoError::InvalidParameters` once the message limit is reached.
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+        self.0.seal(aad, plaintext)
+    }
+
+    /// Derive a secret from the context (`Context.Export`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidParameters` if `len` exceeds 255 hash lengths.
+    pub fn export(&self, exporter_context: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>> {
+        self.0.export(exporter_context, len)
+    }
+
+    /// Number of messages sealed so far
+    #[must_use]
+    pub fn sequence(&self) -> u64 {
+        self.0.sequence
+    }
+}
+
+impl HpkeReceiverContext {
+    /// Decrypt the next message (`ContextR.Open`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::AuthenticationFailed` if the ciphertext, `aad` or message
+    /// order do not match; the context can then still open the expected message.
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+        self.0.open(aad, ciphertext)
+    }
+
+    /// Derive a secret from the context (`Context.Export`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidParameters` if `len` exceeds 255 hash lengths.
+    pub fn export(&self, exporter_context: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>> {
+        self.0.export(exporter_context, len)
+    }
+
+    /// Number of messages opened so far
+    #[must_use]
+    pub fn sequence(&self) -> u64 {
+        self.0.sequence
+    }
+}
+
+impl std::fmt::Debug for HpkeSenderContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_redacted("HpkeSenderContext", f)
+    }
+}
+
+impl std::fmt::Debug for HpkeReceiverContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_redacted("HpkeReceiverContext", f)
+    }
+}
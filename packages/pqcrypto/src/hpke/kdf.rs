@@ -0,0 +1,86 @@
+//! HPKE key derivation functions and labelled HKDF (RFC 9180 §4)
+
+use crate::{PqCryptoError, Result};
+use hkdf::Hkdf;
+use sha2::{Sha256, Sha512};
+use zeroize::Zeroizing;
+
+/// Version label prefixed to every labelled KDF input
+const HPKE_VERSION: &[u8] = b"HPKE-v1";
+
+/// Key derivation function of an HPKE suite
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HpkeKdf {
+    /// HKDF with SHA-256
+    #[default]
+    HkdfSha256,
+    /// HKDF with SHA-512
+    HkdfSha512,
+}
+
+impl HpkeKdf {
+    /// IANA KDF identifier
+    #[must_use]
+    pub fn id(self) -> u16 {
+        match self {
+            Self::HkdfSha256 => 0x0001,
+            Self::HkdfSha512 => 0x0003,
+        }
+    }
+
+    /// Hash output length `Nh` in bytes
+    #[must_use]
+    pub fn hash_len(self) -> usize {
+        match self {
+            Self::HkdfSha256 => 32,
+            Self::HkdfSha512 => 64,
+        }
+    }
+
+    /// `LabeledExtract(salt, label, ikm)`
+    pub(super) fn labeled_extract(
+        self,
+        suite_id: &[u8],
+        salt: &[u8],
+        label: &[u8],
+        ikm: &[u8],
+    ) -> Zeroizing<Vec<u8>> {
+        let labeled_ikm = Zeroizing::new([HPKE_VERSION, suite_id, label, ikm].concat());
+        Zeroizing::new(match self {
+            Self::HkdfSha256 => Hkdf::<Sha256>::extract(Some(salt), &labeled_ikm).0.to_vec(),
+            Self::HkdfSha512 => Hkdf::<Sha512>::extract(Some(salt), &labeled_ikm).0.to_vec(),
+        })
+    }
+
+    /// `LabeledExpand(prk, label, info, len)`
+    pub(super) fn labeled_expand(
+        self,
+        suite_id: &[u8],
+        prk: &[u8],
+        label: &[u8],
+        info: &[u8],
+        len: usize,
+    ) -> Result<Zeroizing<Vec<u8>>> {
+        let too_long = || {
+            PqCryptoError::InvalidParameters(format!(
+                "HPKE cannot expand {len} bytes with {self:?} (maximum {})",
+                255 * self.hash_len()
+            ))
+        };
+        let encoded_len = u16::try_from(len).map_err(|_| too_long())?.to_be_bytes();
+        let labeled_info: [&[u8]; 5] = [&encoded_len, HPKE_VERSION, suite_id, label, info];
+        let invalid_prk = |_| PqCryptoError::InternalError("Invalid HPKE PRK length".to_string());
+
+        let mut okm = Zeroizing::new(vec![0u8; len]);
+        match self {
+            Self::HkdfSha256 => Hkdf::<Sha256>::from_prk(prk)
+                .map_err(invalid_prk)?
+                .expand_multi_info(&labeled_info, &mut okm),
+            Self::HkdfSha512 => Hkdf::<Sha512>::from_prk(prk)
+                .map_err(invalid_prk)?
+                .expand_multi_info(&labeled_info, &mut okm),
+        }
+        .map_err(|_| too_long())?;
+        Ok(okm)
+    }
+}
@@ -0,0 +1,287 @@
+//! HPKE key encapsulation: DHKEM (RFC 9180 §4.1), ML-KEM and X-Wing
+
+use super::kdf::HpkeKdf;
+use crate::KemAlgorithm;
+use crate::api::kem_builder::hybrid::{self, Ecdh};
+use crate::{PqCryptoError, Result};
+use zeroize::Zeroizing;
+
+/// Key encapsulation mechanism of an HPKE suite
+///
+/// The DHKEMs follow RFC 9180. ML-KEM and `X25519MLKEM768` (X-Wing) use the KEM's shared
+/// secret directly, with the identifiers assigned by draft-ietf-hpke-pq; their secret keys
+/// are the expanded encodings produced by this crate rather than 64-byte seeds, so they
+/// interoperate on the wire but cannot be derived from `ikm`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HpkeKem {
+    /// DHKEM(X25519, HKDF-SHA256)
+    #[default]
+    X25519HkdfSha256,
+    /// DHKEM(P-256, HKDF-SHA256)
+    P256HkdfSha256,
+    /// ML-KEM-512
+    MlKem512,
+    /// ML-KEM-768
+    MlKem768,
+    /// ML-KEM-1024
+    MlKem1024,
+    /// X25519 + ML-KEM-768 hybrid (X-Wing)
+    X25519MlKem768,
+}
+
+impl HpkeKem {
+    /// IANA KEM identifier
+    #[must_use]
+    pub fn id(self) -> u16 {
+        match self {
+            Self::P256HkdfSha256 => 0x0010,
+            Self::X25519HkdfSha256 => 0x0020,
+            Self::MlKem512 => 0x0040,
+            Self::MlKem768 => 0x0041,
+            Self::MlKem1024 => 0x0042,
+            Self::X25519MlKem768 => 0x647A,
+        }
+    }
+
+    /// Length of the encapsulated key `Nenc` in bytes
+    #[must_use]
+    pub fn enc_len(self) -> usize {
+        match self.kind() {
+            Kind::Dh(ecdh) => ecdh.public_len(),
+            Kind::Kem(algorithm) => algorithm.ciphertext_size(),
+        }
+    }
+
+    /// Length of a serialized public key `Npk` in bytes
+    #[must_use]
+    pub fn public_key_len(self) -> usize {
+        match self.kind() {
+            Kind::Dh(ecdh) => ecdh.public_len(),
+            Kind::Kem(algorithm) => algorithm.public_key_size(),
+        }
+    }
+
+    /// Length of a serialized secret key in bytes
+    #[must_use]
+    pub fn secret_key_len(self) -> usize {
+        match self.kind() {
+            Kind::Dh(ecdh) => ecdh.secret_len(),
+            Kind::Kem(algorithm) => algorithm.secret_key_size(),
+        }
+    }
+
+    /// Whether the auth and auth-PSK modes are available (DHKEMs only)
+    #[must_use]
+    pub fn supports_auth(self) -> bool {
+        matches!(self.kind(), Kind::Dh(_))
+    }
+
+    /// Generate a random key pair, returning `(public_key, secret_key)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::KeyGenerationFailed` if the random source fails.
+    pub fn generate_key_pair(self) -> Result<(Vec<u8>, Vec<u8>)> {
+        match self.kind() {
+            Kind::Dh(ecdh) => {
+                let secret = ecdh.generate()?;
+                Ok((ecdh.public_key(&secret)?, secret.to_vec()))
+            }
+            Kind::Kem(KemAlgorithm::X25519MlKem768) => {
+                let (public_key, secret_key) = hybrid::keypair(KemAlgorithm::X25519MlKem768)?;
+                Ok((public_key, secret_key.to_vec()))
+            }
+            Kind::Kem(algorithm) => {
+                let (public_key, secret_key) = hybrid::mlkem_keypair(algorithm)?;
+                Ok((public_key, secret_key.to_vec()))
+            }
+        }
+    }
+
+    /// Deterministically derive a key pair from `ikm` (`DeriveKeyPair`), returning
+    /// `(public_key, secret_key)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::UnsupportedAlgorithm` for ML-KEM and X-Wing, and
+    /// `PqCryptoError::InvalidKey` if `ikm` is shorter than 32 bytes.
+    pub fn derive_key_pair(self, ikm: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+        let secret = self.derive_secret(ikm)?;
+        let ecdh = self.ecdh()?;
+        Ok((ecdh.public_key(&secret)?, secret.to_vec()))
+    }
+
+    fn derive_secret(self, ikm: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let ecdh = self.ecdh()?;
+        if ikm.len() < ecdh.secret_len() {
+            return Err(PqCryptoError::InvalidKey(format!(
+                "HPKE key derivation needs at least {} bytes of input keying material",
+                ecdh.secret_len()
+            )));
+        }
+        let suite_id = self.suite_id();
+        let kdf = HpkeKdf::HkdfSha256;
+        let prk = kdf.labeled_extract(&suite_id, b"", b"dkp_prk", ikm);
+
+        if ecdh == Ecdh::X25519 {
+            return kdf.labeled_expand(&suite_id, &prk, b"sk", b"", ecdh.secret_len());
+        }
+        // Rejection sampling over candidate scalars (bitmask 0xFF for P-256)
+        for counter in 0..=u8::MAX {
+            let candidate =
+                kdf.labeled_expand(&suite_id, &prk, b"candidate", &[counter], ecdh.secret_len())?;
+            if ecdh.public_key(&candidate).is_ok() {
+                return Ok(candidate);
+            }
+        }
+        Err(PqCryptoError::KeyGenerationFailed(
+            "HPKE key derivation found no valid scalar".to_string(),
+        ))
+    }
+
+    /// `Encap` / `AuthEncap`, returning `(shared_secret, enc)`
+    ///
+    /// `ephemeral_ikm` derives the ephemeral key deterministically (test vectors only).
+    pub(super) fn encap(
+        self,
+        recipient: &[u8],
+        sender_secret: Option<&[u8]>,
+        ephemeral_ikm: Option<&[u8]>,
+    ) -> Result<(Zeroizing<Vec<u8>>, Vec<u8>)> {
+        check_len(recipient, self.public_key_len())?;
+        let ecdh = match self.kind() {
+            Kind::Dh(ecdh) => ecdh,
+            Kind::Kem(algorithm) => {
+                self.check_kem_inputs(sender_secret.is_some(), ephemeral_ikm.is_some())?;
+                return if algorithm == KemAlgorithm::X25519MlKem768 {
+                    let (enc, shared) = hybrid::encapsulate(algorithm, recipient)?;
+                    Ok((shared, enc))
+                } else {
+                    let (enc, shared) = hybrid::mlkem_encapsulate(algorithm, recipient)?;
+                    Ok((shared, enc))
+                };
+            }
+        };
+
+        let ephemeral = match ephemeral_ikm {
+            Some(ikm) => self.derive_secret(ikm)?,
+            None => ecdh.generate()?,
+        };
+        let enc = ecdh.public_key(&ephemeral)?;
+        let mut dh = ecdh.agree(&ephemeral, recipient)?;
+        let mut kem_context = [enc.as_slice(), recipient].concat();
+        if let Some(sender_secret) = sender_secret {
+            check_len(sender_secret, ecdh.secret_len())?;
+            dh.extend_from_slice(&ecdh.agree(sender_secret, recipient)?);
+            kem_context.extend_from_slice(&ecdh.public_key(sender_secret)?);
+        }
+        Ok((self.extract_and_expand(&dh, &kem_context)?, enc))
+    }
+
+    /// `Decap` / `AuthDecap`, returning the shared secret
+    pub(super) fn decap(
+        self,
+        enc: &[u8],
+        recipient_secret: &[u8],
+        sender_public: Option<&[u8]>,
+    ) -> Result<Zeroizing<Vec<u8>>> {
+        if enc.len() != self.enc_len() {
+            return Err(PqCryptoError::InvalidCiphertext(format!(
+                "HPKE encapsulated key must be {} bytes, got {}",
+                self.enc_len(),
+                enc.len()
+            )));
+        }
+        check_len(recipient_secret, self.secret_key_len())?;
+        let ecdh = match self.kind() {
+            Kind::Dh(ecdh) => ecdh,
+            Kind::Kem(algorithm) => {
+                self.check_kem_inputs(sender_public.is_some(), false)?;
+                return if algorithm == KemAlgorithm::X25519MlKem768 {
+                    hybrid::decapsulate(algorithm, recipient_secret, enc)
+                } else {
+                    hybrid::mlkem_decapsulate(algorithm, recipient_secret, enc)
+                };
+            }
+        };
+
+        let mut dh = ecdh.agree(recipient_secret, enc)?;
+        let mut kem_context = [enc, ecdh.public_key(recipient_secret)?.as_slice()].concat();
+        if let Some(sender_public) = sender_public {
+            check_len(sender_public, ecdh.public_len())?;
+            dh.extend_from_slice(&ecdh.agree(recipient_secret, sender_public)?);
+            kem_context.extend_from_slice(sender_public);
+        }
+        self.extract_and_expand(&dh, &kem_context)
+    }
+
+    fn extract_and_expand(self, dh: &[u8], kem_context: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+        let suite_id = self.suite_id();
+        let kdf = HpkeKdf::HkdfSha256;
+        let prk = kdf.labeled_extract(&suite_id, b"", b"eae_prk", dh);
+        kdf.labeled_expand(
+            &suite_id,
+            &prk,
+            b"shared_secret",
+            kem_context,
+            kdf.hash_len(),
+        )
+    }
+
+    fn check_kem_inputs(self, authenticated: bool, deterministic: bool) -> Result<()> {
+        if authenticated {
+            return Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "HPKE auth modes require a DHKEM, not {self:?}"
+            )));
+        }
+        if deterministic {
+            return Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{self:?} does not support deterministic encapsulation"
+            )));
+        }
+        Ok(())
+    }
+
+    fn ecdh(self) -> Result<Ecdh> {
+        match self.kind() {
+            Kind::Dh(ecdh) => Ok(ecdh),
+            Kind::Kem(_) => Err(PqCryptoError::UnsupportedAlgorithm(format!(
+                "{self:?} does not support DeriveKeyPair"
+            ))),
+        }
+    }
+
+    fn kind(self) -> Kind {
+        match self {
+            Self::X25519HkdfSha256 => Kind::Dh(Ecdh::X25519),
+            Self::P256HkdfSha256 => Kind::Dh(Ecdh::P256),
+            Self::MlKem512 => Kind::Kem(KemAlgorithm::MlKem512),
+            Self::MlKem768 => Kind::Kem(KemAlgorithm::MlKem768),
+            Self::MlKem1024 => Kind::Kem(KemAlgorithm::MlKem1024),
+            Self::X25519MlKem768 => Kind::Kem(KemAlgorithm::X25519MlKem768),
+        }
+    }
+
+    /// `"KEM" || I2OSP(kem_id, 2)`
+    fn suite_id(self) -> [u8; 5] {
+        let [hi, lo] = self.id().to_be_bytes();
+        [b'K', b'E', b'M', hi, lo]
+    }
+}
+
+/// How a suite KEM is implemented
+enum Kind {
+    Dh(Ecdh),
+    Kem(KemAlgorithm),
+}
+
+fn check_len(key: &[u8], expected: usize) -> Result<()> {
+    if key.len() == expected {
+        Ok(())
+    } else {
+        Err(PqCryptoError::InvalidKeySize {
+            expected,
+            actual: key.len(),
+        })
+    }
+}
@@ -0,0 +1,307 @@
+//! Hybrid Public Key Encryption (RFC 9180)
+//!
+//! An [`Hpke`] suite combines a KEM, a KDF and an AEAD. Senders encrypt to a recipient's
+//! public key either single-shot with [`Hpke::seal`] or through an [`HpkeSenderContext`]
+//! for a sequence of messages; each context can also export secrets for other protocols.
+//! All four modes are supported: base, PSK, auth and auth-PSK. The auth modes need a
+//! DHKEM, since ML-KEM and X-Wing have no sender-authenticated encapsulation.
+//!
+//! ```rust,ignore
+//! use cryypt_pqcrypto::hpke::{Hpke, HpkeAead, HpkeKdf, HpkeKem};
+//!
+//! let suite = Hpke::new(HpkeKem::X25519MlKem768, HpkeKdf::HkdfSha256, HpkeAead::Aes256Gcm);
+//! let (public_key, secret_key) = suite.kem().generate_key_pair()?;
+//!
+//! let (enc, ciphertext) = suite.seal(&public_key, b"app info", b"aad", b"hello")?;
+//! let plaintext = suite.open(&enc, &secret_key, b"app info", b"aad", &ciphertext)?;
+//! ```
+
+mod aead;
+mod context;
+mod kdf;
+mod kem;
+
+pub use aead::HpkeAead;
+pub use context::{HpkeReceiverContext, HpkeSenderContext};
+pub use kdf::HpkeKdf;
+pub use kem::HpkeKem;
+
+use crate::{PqCryptoError, Result};
+use context::{Context, ScheduleInput};
+use zeroize::Zeroizing;
+
+/// Minimum pre-shared key length in bytes
+const MIN_PSK_LEN: usize = 32;
+
+/// HPKE mode, chosen by which of the PSK and sender key are configured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HpkeMode {
+    /// Encryption to a public key
+    Base,
+    /// Additionally authenticated by a pre-shared key
+    Psk,
+    /// Additionally authenticated by the sender's key pair
+    Auth,
+    /// Authenticated by both a pre-shared key and the sender's key pair
+    AuthPsk,
+}
+
+impl HpkeMode {
+    /// Mode identifier used in the key schedule
+    #[must_use]
+    pub fn id(self) -> u8 {
+        match self {
+            Self::Base => 0x00,
+            Self::Psk => 0x01,
+            Self::Auth => 0x02,
+            Self::AuthPsk => 0x03,
+        }
+    }
+}
+
+/// HPKE cipher suite with optional PSK and sender authentication
+#[derive(Clone)]
+pub struct Hpke {
+    kem: HpkeKem,
+    kdf: HpkeKdf,
+    aead: HpkeAead,
+    psk: Option<(Zeroizing<Vec<u8>>, Vec<u8>)>,
+    sender_secret_key: Option<Zeroizing<Vec<u8>>>,
+    sender_public_key: Option<Vec<u8>>,
+}
+
+impl Hpke {
+    /// Suite in base mode
+    #[must_use]
+    pub fn new(kem: HpkeKem, kdf: HpkeKdf, aead: HpkeAead) -> Self {
+        Self {
+            kem,
+            kdf,
+            aead,
+            psk: None,
+            sender_secret_key: None,
+            sender_public_key: None,
+        }
+    }
+
+    /// Authenticate with a pre-shared key and its identifier (PSK modes)
+    ///
+    /// The key must hold at least 32 bytes of entropy and the identifier must be
+    /// non-empty; both are checked when a context is set up.
+    #[must_use]
+    pub fn with_psk(mut self, psk: &[u8], psk_id: &[u8]) -> Self {
+        self.psk = Some((Zeroizing::new(psk.to_vec()), psk_id.to_vec()));
+        self
+    }
+
+    /// Sender's secret key, authenticating the sender when sealing (auth modes)
+    #[must_use]
+    pub fn with_sender_secret_key(mut self, secret_key: &[u8]) -> Self {
+        self.sender_secret_key = Some(Zeroizing::new(secret_key.to_vec()));
+        self
+    }
+
+    /// Sender's public key, verifying the sender when opening (auth modes)
+    #[must_use]
+    pub fn with_sender_public_key(mut self, public_key: &[u8]) -> Self {
+        self.sender_public_key = Some(public_key.to_vec());
+        self
+    }
+
+    /// KEM of this suite
+    #[must_use]
+    pub fn kem(&self) -> HpkeKem {
+        self.kem
+    }
+
+    /// KDF of this suite
+    #[must_use]
+    pub fn kdf(&self) -> HpkeKdf {
+        self.kdf
+    }
+
+    /// AEAD of this suite
+    #[must_use]
+    pub fn aead(&self) -> HpkeAead {
+        self.aead
+    }
+
+    /// Set up a sender context (`SetupBaseS` and friends), returning `(enc, context)`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidKeySize` or `PqCryptoError::InvalidKey` for malformed
+    /// keys, `PqCryptoError::InvalidParameters` for inconsistent PSK or sender inputs and
+    /// `PqCryptoError::UnsupportedAlgorithm` for auth modes with a non-DH KEM.
+    pub fn setup_sender(
+        &self,
+        recipient_public_key: &[u8],
+        info: &[u8],
+    ) -> Result<(Vec<u8>, HpkeSenderContext)> {
+        self.sender(recipient_public_key, info, None)
+    }
+
+    /// Set up a sender context with an ephemeral key derived from `ikm`
+    ///
+    /// Only for reproducing test vectors: reusing `ikm` reuses the ephemeral key.
+    ///
+    /// # Errors
+    ///
+    /// As [`setup_sender`](Self::setup_sender); ML-KEM and X-Wing suites are unsupported.
+    #[doc(hidden)]
+    pub fn setup_sender_deterministic(
+        &self,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        ephemeral_ikm: &[u8],
+    ) -> Result<(Vec<u8>, HpkeSenderContext)> {
+        self.sender(recipient_public_key, info, Some(ephemeral_ikm))
+    }
+
+    /// Set up a receiver context (`SetupBaseR` and friends)
+    ///
+    /// # Errors
+    ///
+    /// As [`setup_sender`](Self::setup_sender), plus `PqCryptoError::InvalidCiphertext`
+    /// if `enc` has the wrong length.
+    pub fn setup_receiver(
+        &self,
+        enc: &[u8],
+        recipient_secret_key: &[u8],
+        info: &[u8],
+    ) -> Result<HpkeReceiverContext> {
+        if self.sender_secret_key.is_some() {
+            return Err(role_mismatch("opening", "public"));
+        }
+        let mode = self.mode(self.sender_public_key.is_some())?;
+        let shared_secret =
+            self.kem
+                .decap(enc, recipient_secret_key, self.sender_public_key.as_deref())?;
+        Ok(HpkeReceiverContext(self.key_schedule(
+            &shared_secret,
+            mode,
+            info,
+        )?))
+    }
+
+    /// Encrypt one message to `recipient_public_key`, returning `(enc, ciphertext)`
+    ///
+    /// # Errors
+    ///
+    /// As [`setup_sender`](Self::setup_sender) and [`HpkeSenderContext::seal`].
+    pub fn seal(
+        &self,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        aad: &[u8],
+        plaintext: &[u8],
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
+        let (enc, mut context) = self.setup_sender(recipient_public_key, info)?;
+        Ok((enc, context.seal(aad, plaintext)?))
+    }
+
+    /// Decrypt one message sealed with [`seal`](Self::seal)
+    ///
+    /// # Errors
+    ///
+    /// As [`setup_receiver`](Self::setup_receiver) and [`HpkeReceiverContext::open`].
+    pub fn open(
+        &self,
+        enc: &[u8],
+        recipient_secret_key: &[u8],
+        info: &[u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>> {
+        self.setup_receiver(enc, recipient_secret_key, info)?
+            .open(aad, ciphertext)
+    }
+
+    fn sender(
+        &self,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        ephemeral_ikm: Option<&[u8]>,
+    ) -> Result<(Vec<u8>, HpkeSenderContext)> {
+        if self.sender_public_key.is_some() {
+            return Err(role_mismatch("sealing", "secret"));
+        }
+        let mode = self.mode(self.sender_secret_key.is_some())?;
+        let (shared_secret, enc) = self.kem.encap(
+            recipient_public_key,
+            self.sender_secret_key.as_deref().map(Vec::as_slice),
+            ephemeral_ikm,
+        )?;
+        let context = self.key_schedule(&shared_secret, mode, info)?;
+        Ok((enc, HpkeSenderContext(context)))
+    }
+
+    /// Mode from the configured inputs (`VerifyPSKInputs`)
+    fn mode(&self, authenticated: bool) -> Result<HpkeMode> {
+        if let Some((psk, psk_id)) = &self.psk {
+            if psk.len() < MIN_PSK_LEN {
+                return Err(PqCryptoError::InvalidKeySize {
+                    expected: MIN_PSK_LEN,
+                    actual: psk.len(),
+                });
+            }
+            if psk_id.is_empty() {
+                return Err(PqCryptoError::InvalidParameters(
+                    "HPKE PSK identifier must not be empty".to_string(),
+                ));
+            }
+        }
+        Ok(match (self.psk.is_some(), authenticated) {
+            (false, false) => HpkeMode::Base,
+            (true, false) => HpkeMode::Psk,
+            (false, true) => HpkeMode::Auth,
+            (true, true) => HpkeMode::AuthPsk,
+        })
+    }
+
+    fn key_schedule(&self, shared_secret: &[u8], mode: HpkeMode, info: &[u8]) -> Result<Context> {
+        let (psk, psk_id) = self.psk.as_ref().map_or((&[][..], &[][..]), |(psk, id)| {
+            (psk.as_slice(), id.as_slice())
+        });
+        Context::key_schedule(
+            self.kdf,
+            self.aead,
+            self.suite_id(),
+            shared_secret,
+            &ScheduleInput {
+                mode,
+                info,
+                psk,
+                psk_id,
+            },
+        )
+    }
+
+    /// `"HPKE" || I2OSP(kem_id, 2) || I2OSP(kdf_id, 2) || I2OSP(aead_id, 2)`
+    fn suite_id(&self) -> [u8; 10] {
+        let mut suite_id = *b"HPKE\0\0\0\0\0\0";
+        suite_id[4..6].copy_from_slice(&self.kem.id().to_be_bytes());
+        suite_id[6..8].copy_from_slice(&self.kdf.id().to_be_bytes());
+        suite_id[8..10].copy_from_slice(&self.aead.id().to_be_bytes());
+        suite_id
+    }
+}
+
+fn role_mismatch(operation: &str, expected: &str) -> PqCryptoError {
+    PqCryptoError::InvalidParameters(format!(
+        "HPKE auth mode needs the sender's {expected} key when {operation}"
+    ))
+}
+
+impl std::fmt::Debug for Hpke {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Hpke")
+            .field("kem", &self.kem)
+            .field("kdf", &self.kdf)
+            .field("aead", &self.aead)
+            .field("psk", &self.psk.is_some())
+            .field("sender_secret_key", &self.sender_secret_key.is_some())
+            .field("sender_public_key", &self.sender_public_key.is_some())
+            .finish()
+    }
+}
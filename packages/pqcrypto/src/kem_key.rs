@@ -0,0 +1,109 @@
+//! Typed KEM keys for the generic KEM builder
+//!
+//! Each key records its algorithm and is checked against that algorithm's key size when it
+//! is created, so a builder can refuse keys and ciphertexts from different algorithms.
+
+use super::KemAlgorithm;
+use crate::{PqCryptoError, Result};
+use std::fmt;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A KEM public key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KemPublicKey {
+    algorithm: KemAlgorithm,
+    key: Vec<u8>,
+}
+
+impl KemPublicKey {
+    /// Wrap raw public key bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidKeySize` if `key` is not `algorithm`'s public key size.
+    pub fn new(algorithm: KemAlgorithm, key: impl Into<Vec<u8>>) -> Result<Self> {
+        let key = key.into();
+        check_size(algorithm.public_key_size(), key.len())?;
+        Ok(Self { algorithm, key })
+    }
+
+    /// Get the algorithm this key belongs to
+    #[must_use]
+    pub fn algorithm(&self) -> KemAlgorithm {
+        self.algorithm
+    }
+
+    /// Get the key as a byte slice
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Convert the key to a vector of bytes
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.key.clone()
+    }
+}
+
+/// A KEM secret key, zeroized when dropped
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct KemSecretKey {
+    #[zeroize(skip)]
+    algorithm: KemAlgorithm,
+    key: Vec<u8>,
+}
+
+impl KemSecretKey {
+    /// Wrap raw secret key bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidKeySize` if `key` is not `algorithm`'s secret key size.
+    pub fn new(algorithm: KemAlgorithm, key: impl Into<Vec<u8>>) -> Result<Self> {
+        let mut key = key.into();
+        if let Err(e) = check_size(algorithm.secret_key_size(), key.len()) {
+            key.zeroize();
+            return Err(e);
+        }
+        Ok(Self { algorithm, key })
+    }
+
+    /// Get the algorithm this key belongs to
+    #[must_use]
+    pub fn algorithm(&self) -> KemAlgorithm {
+        self.algorithm
+    }
+
+    /// Get the key as a byte slice
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl fmt::Debug for KemSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KemSecretKey")
+            .field("algorithm", &self.algorithm)
+            .field("length", &self.key.len())
+            .finish()
+    }
+}
+
+impl PartialEq for KemSecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+        self.algorithm == other.algorithm && self.key.ct_eq(&other.key).into()
+    }
+}
+
+impl Eq for KemSecretKey {}
+
+fn check_size(expected: usize, actual: usize) -> Result<()> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(PqCryptoError::InvalidKeySize { expected, actual })
+    }
+}
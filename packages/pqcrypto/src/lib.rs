@@ -10,6 +10,17 @@
 //!   - ML-KEM-512 (NIST security level 1)
 //!   - ML-KEM-768 (NIST security level 3)
 //!   - ML-KEM-1024 (NIST security level 5)
+//! - Hybrid KEMs combining ML-KEM with classical ECDH
+//!   - X-Wing (X25519 + ML-KEM-768)
+//!   - P-256 + ML-KEM-768 and P-384 + ML-KEM-1024, with a cryypt-specific combiner
+//!
+//! ## Public-Key Encryption
+//! - HPKE (RFC 9180) in base, PSK, auth and auth-PSK modes over DHKEM(X25519),
+//!   DHKEM(P-256), ML-KEM and X-Wing, see [`hpke`]
+//!
+//! ## Authenticated Sessions
+//! - A signed ML-KEM (or hybrid) handshake between ML-DSA identities with AES-256-GCM
+//!   transport records, see [`handshake`]
 //!
 //! ## Digital Signature Algorithms
 //! - ML-DSA (Module-Lattice-based Digital Signature Algorithm, formerly CRYSTALS-Dilithium)
@@ -21,28 +32,62 @@
 //!   - FALCON-1024 (NIST security level 5)
 //! - SPHINCS+ (Stateless Hash-based Signatures)
 //!   - Multiple parameter sets for different speed/size trade-offs
+//! - Composite ML-DSA + Ed25519/ECDSA signatures (draft-ietf-lamps-pq-composite-sigs)
+//!   that verify only if both components do
+//! - Streaming sign and verify of readers, chunk streams and files with detached `.sig`
+//!   files, see [`StreamSignBuilder`] and [`SignatureFile`]
+//! - Batch signing and verification spread over a bounded worker pool with per-item results,
+//!   see [`SignatureBatch`](api::SignatureBatch)
+//!
+//! ## Key Encodings
+//! - PKCS#8 and SubjectPublicKeyInfo in DER and PEM with the NIST OIDs for ML-KEM, ML-DSA
+//!   and, for keys used with the native backend, SLH-DSA, see [`encoding`]
+//!
+//! ## Backends
+//! - `PQClean` through the `pqcrypto` crates (default)
+//! - A pure-Rust FIPS 203/204/205 implementation with seeded key generation, context strings,
+//!   pre-hash (HashML-DSA, HashSLH-DSA) and deterministic signing, see [`backend`]
 
 mod algorithm;
 pub mod api;
+pub mod backend;
+pub mod encoding;
 mod error;
+pub mod handshake;
+pub mod hpke;
+mod kem_key;
 mod result;
 mod shared_secret;
+mod signature_file;
 
 // Re-export error types
 pub use self::error::{PqCryptoError, Result};
 
 // Re-export main types
-pub use self::algorithm::{KemAlgorithm, SignatureAlgorithm};
+pub use self::algorithm::{
+    ClassicalAlgorithm, CompositeAlgorithm, KemAlgorithm, SignatureAlgorithm,
+};
+pub use self::kem_key::{KemPublicKey, KemSecretKey};
 pub use self::result::{
     DecapsulationResult, EncapsulationResult, SignatureResult, VerificationResult,
 };
 pub use self::shared_secret::SharedSecret;
+pub use self::signature_file::{SignatureFile, StreamDigest};
+
+// Re-export key encoding types
+pub use self::encoding::{PqAlgorithm, PqPublicKey, PqSecretKey};
+
+// Re-export backend selection types
+pub use self::backend::{Backend, PreHash, SigningMode, SigningOptions};
+
+// Re-export HPKE suite types
+pub use self::hpke::{Hpke, HpkeAead, HpkeKdf, HpkeKem, HpkeMode};
 
 // Re-export builder traits
 pub use self::api::{
     CiphertextBuilder, DecapsulateBuilder, EncapsulateBuilder, KemBuilder, KemKeyPairBuilder,
     MessageBuilder, PqCryptoMasterBuilder, SignBuilder, SignatureBuilder, SignatureDataBuilder,
-    SignatureKeyPairBuilder, VerifyBuilder,
+    SignatureKeyPairBuilder, StreamSignBuilder, StreamVerifyBuilder, VerifyBuilder,
 };
 
 /// Prelude for post-quantum cryptography
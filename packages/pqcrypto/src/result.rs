@@ -248,7 +248,7 @@ impl VerificationResult {
 }
 
 /// Helper module for base64 serde
-mod base64_serde {
+pub(crate) mod base64_serde {
     use base64::Engine;
     use serde::{Deserialize, Deserializer, Serializer};
 
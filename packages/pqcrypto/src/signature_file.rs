@@ -0,0 +1,320 @@
+//! Detached `.sig` signature files for streamed messages
+//!
+//! A signature file is one line of JSON:
+//!
+//! ```text
+//! {"format":"cryypt-sig/2","algorithm":"ml-dsa-65","digest":"hash-sha512",
+//!  "key_fingerprint":"sha3-256:5f0c…","created":"2026-10-18T09:30:00Z","signature":"…"}
+//! ```
+//!
+//! The signature covers the message and every other field. HashML-DSA and HashSLH-DSA take
+//! the [`signed_header`](SignatureFile::signed_header), the fields as compact JSON in the
+//! order above, as their context string; FALCON signs it, length-prefixed, ahead of the
+//! digest. Editing the algorithm, fingerprint or creation time therefore invalidates the
+//! signature, and a recorded fingerprint that names another key fails verification.
+
+use crate::backend::{Backend, PreHash, PreHasher, SigningOptions};
+use crate::{PqCryptoError, Result, SignatureAlgorithm};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::path::{Path, PathBuf};
+
+/// `format` tag of the current signature file layout
+const FORMAT: &str = "cryypt-sig/2";
+
+/// Prefix of the FALCON message, which signs
+/// `FALCON_DOMAIN || u16 BE header length || header || SHA3-512(M)`
+const FALCON_DOMAIN: &[u8] = b"cryypt-sig/2 FALCON SHA3-512\0";
+
+/// How a streamed message is reduced to a digest before signing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StreamDigest {
+    /// HashML-DSA or HashSLH-DSA with SHA-512 and the file's header as the context, as
+    /// [`Backend::sign_digest`] produces
+    #[serde(rename = "hash-sha512")]
+    HashSha512,
+    /// The signature scheme's own signature over a domain-separated SHA3-512 digest, for
+    /// algorithms without a standard pre-hash variant (FALCON)
+    #[serde(rename = "sha3-512")]
+    DomainSha3_512,
+}
+
+impl StreamDigest {
+    /// The digest used for streamed messages signed with `algorithm`
+    #[must_use]
+    pub fn for_algorithm(algorithm: SignatureAlgorithm) -> Self {
+        match algorithm {
+            SignatureAlgorithm::Falcon512 | SignatureAlgorithm::Falcon1024 => Self::DomainSha3_512,
+            _ => Self::HashSha512,
+        }
+    }
+
+    /// The underlying hash function
+    #[must_use]
+    pub fn pre_hash(self) -> PreHash {
+        match self {
+            Self::HashSha512 => PreHash::Sha512,
+            Self::DomainSha3_512 => PreHash::Sha3_512,
+        }
+    }
+
+    /// Start hashing a message
+    #[must_use]
+    pub fn hasher(self) -> PreHasher {
+        self.pre_hash().hasher()
+    }
+}
+
+/// The fields a signature file authenticates, in their canonical order
+#[derive(Serialize)]
+struct Header<'a> {
+    format: &'a str,
+    algorithm: SignatureAlgorithm,
+    digest: StreamDigest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    key_fingerprint: Option<&'a str>,
+    created: DateTime<Utc>,
+}
+
+/// A detached signature over a streamed message, with the metadata needed to check it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureFile {
+    format: String,
+    algorithm: SignatureAlgorithm,
+    digest: StreamDigest,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key_fingerprint: Option<String>,
+    created: DateTime<Utc>,
+    #[serde(with = "crate::result::base64_serde")]
+    signature: Vec<u8>,
+}
+
+impl SignatureFile {
+    /// Sign a message digest produced by [`StreamDigest::for_algorithm`]
+    ///
+    /// The fingerprint is recorded when `public_key` is given.
+    pub(crate) fn sign(
+        algorithm: SignatureAlgorithm,
+        secret_key: &[u8],
+        public_key: Option<&[u8]>,
+        digest: &[u8],
+    ) -> Result<Self> {
+        let mut file = Self {
+            format: FORMAT.to_string(),
+            algorithm,
+            digest: StreamDigest::for_algorithm(algorithm),
+            key_fingerprint: public_key.map(Self::fingerprint),
+            created: Utc::now(),
+            signature: Vec::new(),
+        };
+        let header = file.signed_header()?;
+        file.signature = match file.digest {
+            StreamDigest::HashSha512 => {
+                Backend::Native.sign_digest(algorithm, secret_key, digest, &hash_options(header))?
+            }
+            StreamDigest::DomainSha3_512 => Backend::PqClean.sign(
+                algorithm,
+                secret_key,
+                &falcon_message(&header, digest)?,
+                &SigningOptions::new(),
+            )?,
+        };
+        Ok(file)
+    }
+
+    /// Verify this signature over a message digest with `public_key`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::InvalidParameters` if the file is for another algorithm or
+    /// digest, or an error if the public key is malformed.
+    pub(crate) fn verify(
+        &self,
+        algorithm: SignatureAlgorithm,
+        public_key: &[u8],
+        digest: &[u8],
+    ) -> Result<bool> {
+        if self.algorithm != algorithm {
+            return Err(PqCryptoError::InvalidParameters(format!(
+                "Signature file is for {}, not {algorithm}",
+                self.algorithm
+            )));
+        }
+        if self.digest != StreamDigest::for_algorithm(algorithm) {
+            return Err(PqCryptoError::InvalidParameters(format!(
+                "{algorithm} signature files use {:?}, not {:?}",
+                StreamDigest::for_algorithm(algorithm),
+                self.digest
+            )));
+        }
+        if self
+            .key_fingerprint
+            .as_ref()
+            .is_some_and(|fingerprint| *fingerprint != Self::fingerprint(public_key))
+        {
+            return Ok(false);
+        }
+        let header = self.signed_header()?;
+        match self.digest {
+            StreamDigest::HashSha512 => Backend::Native.verify_digest(
+                algorithm,
+                public_key,
+                digest,
+                &self.signature,
+                &hash_options(header),
+            ),
+            StreamDigest::DomainSha3_512 => Backend::PqClean.verify(
+                algorithm,
+                public_key,
+                &falcon_message(&header, digest)?,
+                &self.signature,
+                &SigningOptions::new(),
+            ),
+        }
+    }
+
+    /// The authenticated fields as compact JSON, everything but the signature
+    ///
+    /// This is the context string of the HashML-DSA and HashSLH-DSA signatures.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::SerializationError` if encoding fails.
+    pub fn signed_header(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(&Header {
+            format: &self.format,
+            algorithm: self.algorithm,
+            digest: self.digest,
+            key_fingerprint: self.key_fingerprint.as_deref(),
+            created: self.created,
+        })
+        .map_err(PqCryptoError::serialization_error)
+    }
+
+    /// Fingerprint of a public key as recorded in signature files, `sha3-256:<hex>`
+    #[must_use]
+    pub fn fingerprint(public_key: &[u8]) -> String {
+        format!("sha3-256:{}", hex::encode(Sha3_256::digest(public_key)))
+    }
+
+    /// Path of the signature file for `artifact`: the same path with `.sig` appended
+    #[must_use]
+    pub fn path_for(artifact: impl AsRef<Path>) -> PathBuf {
+        let mut path = artifact.as_ref().as_os_str().to_owned();
+        path.push(".sig");
+        PathBuf::from(path)
+    }
+
+    /// Signature algorithm
+    #[must_use]
+    pub fn algorithm(&self) -> SignatureAlgorithm {
+        self.algorithm
+    }
+
+    /// How the message was digested
+    #[must_use]
+    pub fn digest(&self) -> StreamDigest {
+        self.digest
+    }
+
+    /// Fingerprint of the signer's public key, if it was known when signing
+    #[must_use]
+    pub fn key_fingerprint(&self) -> Option<&str> {
+        self.key_fingerprint.as_deref()
+    }
+
+    /// When the signature was made, as claimed and signed by the signer
+    #[must_use]
+    pub fn created(&self) -> DateTime<Utc> {
+        self.created
+    }
+
+    /// Raw signature bytes
+    #[must_use]
+    pub fn signature(&self) -> &[u8] {
+        &self.signature
+    }
+
+    /// Encode as a line of JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::SerializationError` if encoding fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = serde_json::to_vec(self).map_err(PqCryptoError::serialization_error)?;
+        bytes.push(b'\n');
+        Ok(bytes)
+    }
+
+    /// Decode a signature file
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::SerializationError` for malformed JSON and
+    /// `PqCryptoError::InvalidParameters` for an unknown format or a signature of the wrong
+    /// size.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let file: Self =
+            serde_json::from_slice(bytes).map_err(PqCryptoError::serialization_error)?;
+        if file.format != FORMAT {
+            return Err(PqCryptoError::InvalidParameters(format!(
+                "Unsupported signature file format {:?}",
+                file.format
+            )));
+        }
+        if file.signature.len() > file.algorithm.signature_size() {
+            return Err(PqCryptoError::InvalidParameters(format!(
+                "{} signature must be at most {} bytes, got {}",
+                file.algorithm,
+                file.algorithm.signature_size(),
+                file.signature.len()
+            )));
+        }
+        Ok(file)
+    }
+
+    /// Write to `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::Io` if the file cannot be written.
+    pub async fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        tokio::fs::write(path, self.to_bytes()?)
+            .await
+            .map_err(|e| PqCryptoError::Io(format!("Failed to write signature file: {e}")))
+    }
+
+    /// Read from `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PqCryptoError::Io` if the file cannot be read, or an error as
+    /// [`SignatureFile::from_bytes`].
+    pub async fn read(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| PqCryptoError::Io(format!("Failed to read signature file: {e}")))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+fn hash_options(header: Vec<u8>) -> SigningOptions {
+    SigningOptions::new()
+        .with_context(header)
+        .with_pre_hash(PreHash::Sha512)
+}
+
+fn falcon_message(header: &[u8], digest: &[u8]) -> Result<Vec<u8>> {
+    if digest.len() != PreHash::Sha3_512.output_size() {
+        return Err(PqCryptoError::InvalidParameters(format!(
+            "SHA3-512 digest must be {} bytes, got {}",
+            PreHash::Sha3_512.output_size(),
+            digest.len()
+        )));
+    }
+    let header_len = u16::try_from(header.len()).map_err(|_| {
+        PqCryptoError::InvalidParameters("Signature file header is too long".to_string())
+    })?;
+    Ok([FALCON_DOMAIN, &header_len.to_be_bytes(), header, digest].concat())
+}
@@ -0,0 +1,441 @@
+//! Native FIPS 203/204/205 backend tests against ACVP-format vectors
+//!
+//! The vectors under `tests/vectors` are in the ACVP internal-projection layout (prompt and
+//! expected results together) and were generated with OpenSSL 3.5; see the README there.
+//! The `acvp` tests run the same checks on the NIST ACVP-Server files themselves and are
+//! ignored by default; `just test-acvp` fetches the files and runs them.
+
+use cryypt_pqcrypto::backend::{Backend, PreHash, SigningMode, SigningOptions};
+use cryypt_pqcrypto::{KemAlgorithm, PqCryptoError, SignatureAlgorithm};
+use serde_json::Value;
+
+fn load(json: &str) -> Vec<(String, Value, Value)> {
+    let vectors: Value = serde_json::from_str(json).expect("vector file");
+    let mut cases = Vec::new();
+    for group in vectors["testGroups"].as_array().expect("testGroups") {
+        let parameter_set = group["parameterSet"].as_str().expect("parameterSet");
+        for test in group["tests"].as_array().expect("tests") {
+            cases.push((parameter_set.to_string(), group.clone(), test.clone()));
+        }
+    }
+    cases
+}
+
+fn bytes(test: &Value, field: &str) -> Vec<u8> {
+    hex::decode(test[field].as_str().expect(field)).expect("hex")
+}
+
+/// A field of the test case, or of its group where ACVP hoists it there
+fn case_bytes(group: &Value, test: &Value, field: &str) -> Vec<u8> {
+    if test.get(field).is_some() {
+        bytes(test, field)
+    } else {
+        bytes(group, field)
+    }
+}
+
+/// The internal-projection file of an ACVP-Server `gen-val/json-files` directory
+fn acvp_file(name: &str) -> String {
+    let root = std::env::var("ACVP_VECTORS")
+        .unwrap_or_else(|_| concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors/acvp").into());
+    let path = format!("{root}/{name}/internalProjection.json");
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{path}: {e}"))
+}
+
+/// Cases checked and cases skipped as variants the backend does not offer
+#[derive(Default)]
+struct Tally {
+    checked: usize,
+    skipped: usize,
+}
+
+fn kem(parameter_set: &str) -> KemAlgorithm {
+    match parameter_set {
+        "ML-KEM-512" => KemAlgorithm::MlKem512,
+        "ML-KEM-768" => KemAlgorithm::MlKem768,
+        "ML-KEM-1024" => KemAlgorithm::MlKem1024,
+        other => panic!("unknown parameter set {other}"),
+    }
+}
+
+fn signature(parameter_set: &str) -> Option<SignatureAlgorithm> {
+    Some(match parameter_set {
+        "ML-DSA-44" => SignatureAlgorithm::MlDsa44,
+        "ML-DSA-65" => SignatureAlgorithm::MlDsa65,
+        "ML-DSA-87" => SignatureAlgorithm::MlDsa87,
+        "SLH-DSA-SHA2-128s" => SignatureAlgorithm::SphincsShaSha256_128sSimple,
+        "SLH-DSA-SHA2-128f" => SignatureAlgorithm::SphincsShaSha256_128fSimple,
+        "SLH-DSA-SHA2-192s" => SignatureAlgorithm::SphincsShaSha256_192sSimple,
+        "SLH-DSA-SHA2-192f" => SignatureAlgorithm::SphincsShaSha256_192fSimple,
+        "SLH-DSA-SHA2-256s" => SignatureAlgorithm::SphincsShaSha256_256sSimple,
+        "SLH-DSA-SHA2-256f" => SignatureAlgorithm::SphincsShaSha256_256fSimple,
+        _ => return None,
+    })
+}
+
+/// Signing options for an ACVP sigGen or sigVer case
+///
+/// `None` for the internal interface, external `μ` and pre-hash functions the backend does
+/// not offer.
+fn options(group: &Value, test: &Value, randomness_field: &str) -> Option<SigningOptions> {
+    if group["signatureInterface"] == "internal" || group["externalMu"] == true {
+        return None;
+    }
+    let context = test.get("context").map(|_| bytes(test, "context"));
+    let mut options = SigningOptions::new().with_context(context.unwrap_or_default());
+    match test["hashAlg"].as_str() {
+        None | Some("none") => {}
+        Some(hash) => {
+            options = options.with_pre_hash(match hash {
+                "SHA2-256" => PreHash::Sha256,
+                "SHA2-512" => PreHash::Sha512,
+                "SHA3-512" => PreHash::Sha3_512,
+                "SHAKE-256" => PreHash::Shake256,
+                _ => return None,
+            });
+        }
+    }
+    if group["deterministic"].as_bool() == Some(true) {
+        options = options.with_mode(SigningMode::Deterministic);
+    } else if test.get(randomness_field).is_some() {
+        options = options.with_test_randomness(bytes(test, randomness_field));
+    }
+    Some(options)
+}
+
+/// Run the keyGen and encapDecap files of ML-KEM
+fn check_kem_vectors(key_gen: &str, encap_decap: &str) -> Tally {
+    let mut tally = Tally::default();
+    for (parameter_set, _, test) in load(key_gen) {
+        let seed = [bytes(&test, "d"), bytes(&test, "z")].concat();
+        let (public_key, secret_key) = Backend::Native
+            .kem_keypair_from_seed(kem(&parameter_set), &seed)
+            .expect("keypair from seed");
+        assert_eq!(public_key, bytes(&test, "ek"), "{parameter_set} ek");
+        assert_eq!(*secret_key, bytes(&test, "dk"), "{parameter_set} dk");
+        tally.checked += 1;
+    }
+
+    for (parameter_set, group, test) in load(encap_decap) {
+        let algorithm = kem(&parameter_set);
+        if group["function"] == "encapsulation" {
+            let (ciphertext, shared_secret) = Backend::Native
+                .encapsulate_deterministic(
+                    algorithm,
+                    &case_bytes(&group, &test, "ek"),
+                    &bytes(&test, "m"),
+                )
+                .expect("encapsulate");
+            assert_eq!(ciphertext, bytes(&test, "c"), "{parameter_set} ciphertext");
+            assert_eq!(
+                *shared_secret,
+                bytes(&test, "k"),
+                "{parameter_set} shared secret"
+            );
+        } else if group["function"] == "decapsulation" {
+            // Includes modified ciphertexts, which decapsulate to the implicit rejection key
+            let shared_secret = Backend::Native
+                .decapsulate(
+                    algorithm,
+                    &case_bytes(&group, &test, "dk"),
+                    &bytes(&test, "c"),
+                )
+                .expect("decapsulate");
+            assert_eq!(
+                *shared_secret,
+                bytes(&test, "k"),
+                "{parameter_set} shared secret"
+            );
+        } else {
+            // The key checks validate inputs the backend checks on use
+            tally.skipped += 1;
+            continue;
+        }
+        tally.checked += 1;
+    }
+    tally
+}
+
+/// Run the keyGen, sigGen and sigVer files of one signature scheme
+fn check_signature_vectors(
+    key_gen: &str,
+    sig_gen: &str,
+    sig_ver: &str,
+    seed: impl Fn(&Value) -> Vec<u8>,
+    randomness_field: &str,
+) -> Tally {
+    let mut tally = Tally::default();
+    for (parameter_set, _, test) in load(key_gen) {
+        let Some(algorithm) = signature(&parameter_set) else {
+            tally.skipped += 1;
+            continue;
+        };
+        let (public_key, secret_key) = Backend::Native
+            .signature_keypair_from_seed(algorithm, &seed(&test))
+            .expect("keypair from seed");
+        assert_eq!(public_key, bytes(&test, "pk"), "{parameter_set} public key");
+        assert_eq!(
+            *secret_key,
+            bytes(&test, "sk"),
+            "{parameter_set} secret key"
+        );
+        tally.checked += 1;
+    }
+
+    for (parameter_set, group, test) in load(sig_gen) {
+        let (Some(algorithm), Some(options)) = (
+            signature(&parameter_set),
+            options(&group, &test, randomness_field),
+        ) else {
+            tally.skipped += 1;
+            continue;
+        };
+        let signed = Backend::Native
+            .sign(
+                algorithm,
+                &case_bytes(&group, &test, "sk"),
+                &bytes(&test, "message"),
+                &options,
+            )
+            .expect("sign");
+        assert!(
+            signed == bytes(&test, "signature"),
+            "{parameter_set} signature for case {}",
+            test["tcId"]
+        );
+        tally.checked += 1;
+    }
+
+    for (parameter_set, group, test) in load(sig_ver) {
+        let (Some(algorithm), Some(options)) = (
+            signature(&parameter_set),
+            options(&group, &test, randomness_field),
+        ) else {
+            tally.skipped += 1;
+            continue;
+        };
+        let valid = Backend::Native
+            .verify(
+                algorithm,
+                &case_bytes(&group, &test, "pk"),
+                &bytes(&test, "message"),
+                &bytes(&test, "signature"),
+                &options,
+            )
+            .expect("verify");
+        assert_eq!(
+            Some(valid),
+            test["testPassed"].as_bool(),
+            "{parameter_set} verification of case {}",
+            test["tcId"]
+        );
+        tally.checked += 1;
+    }
+    tally
+}
+
+fn check_ml_dsa_vectors(key_gen: &str, sig_gen: &str, sig_ver: &str) -> Tally {
+    check_signature_vectors(key_gen, sig_gen, sig_ver, |test| bytes(test, "seed"), "rnd")
+}
+
+fn check_slh_dsa_vectors(key_gen: &str, sig_gen: &str, sig_ver: &str) -> Tally {
+    check_signature_vectors(
+        key_gen,
+        sig_gen,
+        sig_ver,
+        |test| {
+            [
+                bytes(test, "skSeed"),
+                bytes(test, "skPrf"),
+                bytes(test, "pkSeed"),
+            ]
+            .concat()
+        },
+        "additionalRandomness",
+    )
+}
+
+/// Every checked-in case is one the backend supports
+fn assert_all_checked(tally: &Tally) {
+    assert!(tally.checked > 0);
+    assert_eq!(tally.skipped, 0);
+}
+
+/// The official files include variants the backend does not offer; report how many ran
+fn assert_acvp_checked(name: &str, tally: &Tally) {
+    assert!(tally.checked > 0, "{name}: no ACVP cases checked");
+    eprintln!(
+        "{name}: {} ACVP cases checked, {} skipped",
+        tally.checked, tally.skipped
+    );
+}
+
+#[test]
+fn test_ml_kem_vectors() {
+    assert_all_checked(&check_kem_vectors(
+        include_str!("vectors/ml_kem_key_gen.json"),
+        include_str!("vectors/ml_kem_encap_decap.json"),
+    ));
+}
+
+#[test]
+fn test_ml_dsa_vectors() {
+    assert_all_checked(&check_ml_dsa_vectors(
+        include_str!("vectors/ml_dsa_key_gen.json"),
+        include_str!("vectors/ml_dsa_sig_gen.json"),
+        include_str!("vectors/ml_dsa_sig_ver.json"),
+    ));
+}
+
+#[test]
+fn test_slh_dsa_vectors() {
+    assert_all_checked(&check_slh_dsa_vectors(
+        include_str!("vectors/slh_dsa_key_gen.json"),
+        include_str!("vectors/slh_dsa_sig_gen.json"),
+        include_str!("vectors/slh_dsa_sig_ver.json"),
+    ));
+}
+
+#[test]
+#[ignore = "needs the NIST ACVP-Server vectors; run with `just test-acvp`"]
+fn test_ml_kem_acvp() {
+    let tally = check_kem_vectors(
+        &acvp_file("ML-KEM-keyGen-FIPS203"),
+        &acvp_file("ML-KEM-encapDecap-FIPS203"),
+    );
+    assert_acvp_checked("ML-KEM", &tally);
+}
+
+#[test]
+#[ignore = "needs the NIST ACVP-Server vectors; run with `just test-acvp`"]
+fn test_ml_dsa_acvp() {
+    let tally = check_ml_dsa_vectors(
+        &acvp_file("ML-DSA-keyGen-FIPS204"),
+        &acvp_file("ML-DSA-sigGen-FIPS204"),
+        &acvp_file("ML-DSA-sigVer-FIPS204"),
+    );
+    assert_acvp_checked("ML-DSA", &tally);
+}
+
+#[test]
+#[ignore = "needs the NIST ACVP-Server vectors; run with `just test-acvp`"]
+fn test_slh_dsa_acvp() {
+    let tally = check_slh_dsa_vectors(
+        &acvp_file("SLH-DSA-keyGen-FIPS205"),
+        &acvp_file("SLH-DSA-sigGen-FIPS205"),
+        &acvp_file("SLH-DSA-sigVer-FIPS205"),
+    );
+    assert_acvp_checked("SLH-DSA", &tally);
+}
+
+#[test]
+fn test_backends_interoperate() {
+    // ML-KEM keys and ciphertexts cross between the backends in both directions
+    for algorithm in [
+        KemAlgorithm::MlKem512,
+        KemAlgorithm::MlKem768,
+        KemAlgorithm::MlKem1024,
+    ] {
+        for (keys, encapsulator) in [
+            (Backend::Native, Backend::PqClean),
+            (Backend::PqClean, Backend::Native),
+        ] {
+            let (public_key, secret_key) = keys.kem_keypair(algorithm).expect("keypair");
+            let (ciphertext, shared_secret) = encapsulator
+                .encapsulate(algorithm, &public_key)
+                .expect("encapsulate");
+            let decapsulated = keys
+                .decapsulate(algorithm, &secret_key, &ciphertext)
+                .expect("decapsulate");
+            assert_eq!(decapsulated, shared_secret, "{algorithm} {keys:?} keys");
+
+            // Both backends take the same implicit rejection path on a corrupted ciphertext
+            let mut corrupted = ciphertext;
+            corrupted[0] ^= 1;
+            let rejected = keys
+                .decapsulate(algorithm, &secret_key, &corrupted)
+                .expect("decapsulate");
+            let rejected_elsewhere = encapsulator
+                .decapsulate(algorithm, &secret_key, &corrupted)
+                .expect("decapsulate");
+            assert_ne!(rejected, shared_secret);
+            assert_eq!(
+                rejected, rejected_elsewhere,
+                "{algorithm} implicit rejection"
+            );
+        }
+    }
+
+    // Seeded ML-KEM keys work with PQClean encapsulation
+    let (public_key, secret_key) = Backend::Native
+        .kem_keypair_from_seed(KemAlgorithm::MlKem768, &[7u8; 64])
+        .expect("keypair from seed");
+    let (ciphertext, shared_secret) = Backend::PqClean
+        .encapsulate(KemAlgorithm::MlKem768, &public_key)
+        .expect("encapsulate");
+    let decapsulated = Backend::Native
+        .decapsulate(KemAlgorithm::MlKem768, &secret_key, &ciphertext)
+        .expect("decapsulate");
+    assert_eq!(decapsulated, shared_secret);
+
+    // Plain ML-DSA signatures verify across backends
+    let algorithm = SignatureAlgorithm::MlDsa44;
+    let (public_key, secret_key) = Backend::PqClean
+        .signature_keypair(algorithm)
+        .expect("keypair");
+    let signed = Backend::Native
+        .sign(algorithm, &secret_key, b"message", &SigningOptions::new())
+        .expect("sign");
+    assert!(
+        Backend::PqClean
+            .verify(
+                algorithm,
+                &public_key,
+                b"message",
+                &signed,
+                &SigningOptions::new()
+            )
+            .expect("verify")
+    );
+
+    // Deterministic signing repeats; the context is bound into the signature
+    let options = SigningOptions::new()
+        .with_context(b"app".to_vec())
+        .with_mode(SigningMode::Deterministic);
+    let first = Backend::Native
+        .sign(algorithm, &secret_key, b"message", &options)
+        .expect("sign");
+    let second = Backend::Native
+        .sign(algorithm, &secret_key, b"message", &options)
+        .expect("sign");
+    assert_eq!(first, second);
+    let other_context = SigningOptions::new().with_context(b"other".to_vec());
+    assert!(
+        !Backend::Native
+            .verify(algorithm, &public_key, b"message", &first, &other_context)
+            .expect("verify")
+    );
+
+    // Features PQClean lacks are refused rather than silently ignored
+    assert!(matches!(
+        Backend::PqClean.sign(algorithm, &secret_key, b"message", &options),
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+    assert!(matches!(
+        Backend::PqClean.kem_keypair_from_seed(KemAlgorithm::MlKem768, &[7u8; 64]),
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+    assert!(matches!(
+        Backend::Native.signature_keypair(SignatureAlgorithm::Falcon512),
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+    assert!(matches!(
+        Backend::Native.sign(
+            algorithm,
+            &secret_key,
+            b"message",
+            &SigningOptions::new().with_context(vec![0u8; 256])
+        ),
+        Err(PqCryptoError::InvalidParameters(_))
+    ));
+}
@@ -0,0 +1,171 @@
+//! Parallel batch signing and verification with per-item results
+
+use cryypt_pqcrypto::api::SignatureBatch;
+use cryypt_pqcrypto::{
+    Backend, PqCryptoError, SignatureAlgorithm, SignatureBuilder, SigningOptions,
+};
+
+fn messages(count: usize) -> Vec<Vec<u8>> {
+    (0..count)
+        .map(|i| format!("audit record {i}").into_bytes())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_verify_batch_reports_each_item() {
+    let algorithm = SignatureAlgorithm::MlDsa44;
+    let (public_key, secret_key) = Backend::PqClean
+        .signature_keypair(algorithm)
+        .expect("keypair");
+    let options = SigningOptions::new();
+    let sign = |message: &[u8]| {
+        Backend::PqClean
+            .sign(algorithm, &secret_key, message, &options)
+            .expect("sign")
+    };
+
+    let mut tampered = sign(b"second");
+    tampered[0] ^= 1;
+    let items = vec![
+        (public_key.clone(), b"first".to_vec(), sign(b"first")),
+        (public_key.clone(), b"second".to_vec(), tampered),
+        (vec![0u8; 7], b"third".to_vec(), sign(b"third")),
+        (public_key.clone(), b"fourth".to_vec(), sign(b"fourth")),
+        (public_key, b"fifth".to_vec(), sign(b"other")),
+    ];
+
+    let results = SignatureBuilder::batch(algorithm)
+        .with_workers(2)
+        .verify_batch(items)
+        .await;
+    assert_eq!(results.len(), 5);
+    assert!(results[0].as_ref().expect("first").is_valid());
+    assert!(!results[1].as_ref().expect("second").is_valid());
+    assert!(matches!(results[2], Err(PqCryptoError::InvalidKey(_))));
+    assert!(results[3].as_ref().expect("fourth").is_valid());
+    assert!(!results[4].as_ref().expect("fifth").is_valid());
+}
+
+#[tokio::test]
+async fn test_sign_batch_round_trip_in_order() {
+    for algorithm in [SignatureAlgorithm::MlDsa65, SignatureAlgorithm::Falcon512] {
+        let (public_key, secret_key) = Backend::PqClean
+            .signature_keypair(algorithm)
+            .expect("keypair");
+        let batch = SignatureBatch::new(algorithm).with_workers(3);
+        let messages = messages(10);
+
+        let signatures = batch
+            .sign_batch(secret_key.to_vec(), messages.clone())
+            .await;
+        assert_eq!(signatures.len(), messages.len());
+
+        let items = messages
+            .iter()
+            .zip(&signatures)
+            .map(|(message, signature)| {
+                let signature = signature.as_ref().expect("signature");
+                assert_eq!(signature.algorithm(), algorithm);
+                (
+                    public_key.clone(),
+                    message.clone(),
+                    signature.signature_vec(),
+                )
+            });
+        let results = batch.verify_batch(items.collect::<Vec<_>>()).await;
+        assert!(
+            results
+                .iter()
+                .all(|result| result.as_ref().expect("verify").is_valid())
+        );
+
+        // Swapping two signatures shows each result belongs to its own message
+        let swapped = batch
+            .verify_batch([
+                (
+                    public_key.clone(),
+                    messages[0].clone(),
+                    signatures[1].as_ref().expect("signature").signature_vec(),
+                ),
+                (
+                    public_key,
+                    messages[1].clone(),
+                    signatures[1].as_ref().expect("signature").signature_vec(),
+                ),
+            ])
+            .await;
+        assert!(!swapped[0].as_ref().expect("verify").is_valid());
+        assert!(swapped[1].as_ref().expect("verify").is_valid());
+    }
+}
+
+#[tokio::test]
+async fn test_batch_options_and_edge_cases() {
+    let algorithm = SignatureAlgorithm::MlDsa44;
+    let (public_key, secret_key) = Backend::Native
+        .signature_keypair(algorithm)
+        .expect("keypair");
+    let batch = SignatureBuilder::batch(algorithm)
+        .with_backend(Backend::Native)
+        .with_options(SigningOptions::new().with_context(b"audit v1".to_vec()))
+        .with_workers(0);
+    assert_eq!(batch.workers(), 1);
+
+    let signatures = batch.sign_batch(secret_key.to_vec(), messages(3)).await;
+    let items: Vec<_> = messages(3)
+        .into_iter()
+        .zip(signatures)
+        .map(|(message, signature)| {
+            let signature = signature.expect("signature").signature_vec();
+            (public_key.clone(), message, signature)
+        })
+        .collect();
+
+    let same_context = batch.verify_batch(items.clone()).await;
+    assert!(
+        same_context
+            .iter()
+            .all(|r| r.as_ref().expect("verify").is_valid())
+    );
+    let other_context = batch
+        .clone()
+        .with_options(SigningOptions::new().with_context(b"audit v2".to_vec()))
+        .verify_batch(items)
+        .await;
+    assert!(
+        other_context
+            .iter()
+            .all(|r| !r.as_ref().expect("verify").is_valid())
+    );
+
+    // PQClean rejects context strings, which fails every item rather than the batch
+    let pqclean = batch.clone().with_backend(Backend::PqClean);
+    let results = pqclean.sign_batch(vec![0u8; 4], messages(2)).await;
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(Result::is_err));
+
+    assert!(
+        batch
+            .sign_batch(Vec::new(), Vec::<Vec<u8>>::new())
+            .await
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_clones_share_the_worker_pool() {
+    let algorithm = SignatureAlgorithm::MlDsa44;
+    let (_, secret_key) = Backend::PqClean
+        .signature_keypair(algorithm)
+        .expect("keypair");
+    let batch = SignatureBuilder::batch(algorithm).with_workers(1);
+    let other = batch.clone();
+
+    // Both calls queue for the single permit rather than running side by side
+    let (first, second) = tokio::join!(
+        batch.sign_batch(secret_key.to_vec(), messages(4)),
+        other.sign_batch(secret_key.to_vec(), messages(4)),
+    );
+    assert_eq!(first.len() + second.len(), 8);
+    assert!(first.iter().chain(&second).all(Result::is_ok));
+}
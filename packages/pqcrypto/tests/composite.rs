@@ -0,0 +1,267 @@
+//! Composite ML-DSA + classical signature tests
+
+use cryypt_pqcrypto::api::{
+    CompositeBuilder, MessageBuilder, SignBuilder, SignatureBuilder, SignatureDataBuilder,
+    SignatureKeyPairBuilder, VerifyBuilder,
+};
+use cryypt_pqcrypto::{
+    Backend, ClassicalAlgorithm, CompositeAlgorithm, PqCryptoError, PqPublicKey, PqSecretKey,
+    SignatureAlgorithm, SigningOptions,
+};
+use serde_json::Value;
+
+async fn verify(algorithm: CompositeAlgorithm, public_key: &[u8], signature: Vec<u8>) -> bool {
+    CompositeBuilder::new(algorithm)
+        .with_public_key(public_key)
+        .expect("public key")
+        .with_message(b"composite message".to_vec())
+        .with_signature(signature)
+        .verify()
+        .await
+        .expect("verify")
+        .is_valid()
+}
+
+#[tokio::test]
+async fn test_composite_roundtrip_all_algorithms() {
+    for algorithm in CompositeAlgorithm::ALL {
+        let keypair = CompositeBuilder::new(algorithm)
+            .generate()
+            .await
+            .expect("generate");
+        let public_key = keypair.public_key_vec().expect("pk");
+        assert_eq!(public_key.len(), algorithm.public_key_size(), "{algorithm}");
+        assert_eq!(
+            keypair.secret_key().expect("sk").len(),
+            algorithm.secret_key_size(),
+            "{algorithm}"
+        );
+
+        let signature = keypair
+            .with_message(b"composite message".to_vec())
+            .sign()
+            .await
+            .expect("sign");
+        assert_eq!(signature.algorithm(), algorithm.ml_dsa());
+        assert!(signature.signature_size() <= algorithm.max_signature_size());
+        assert!(
+            verify(algorithm, &public_key, signature.signature_vec()).await,
+            "{algorithm}"
+        );
+    }
+}
+
+fn bytes(test: &Value, field: &str) -> Vec<u8> {
+    hex::decode(test[field].as_str().expect(field)).expect("hex")
+}
+
+fn composite(label: &str) -> CompositeAlgorithm {
+    match label {
+        "COMPSIG-MLDSA44-Ed25519-SHA512" => CompositeAlgorithm::MlDsa44Ed25519,
+        "COMPSIG-MLDSA44-ECDSA-P256-SHA256" => CompositeAlgorithm::MlDsa44EcdsaP256,
+        "COMPSIG-MLDSA65-Ed25519-SHA512" => CompositeAlgorithm::MlDsa65Ed25519,
+        "COMPSIG-MLDSA65-ECDSA-P256-SHA512" => CompositeAlgorithm::MlDsa65EcdsaP256,
+        "COMPSIG-MLDSA65-ECDSA-P384-SHA512" => CompositeAlgorithm::MlDsa65EcdsaP384,
+        "COMPSIG-MLDSA87-ECDSA-P384-SHA512" => CompositeAlgorithm::MlDsa87EcdsaP384,
+        other => panic!("unknown composite {other}"),
+    }
+}
+
+#[tokio::test]
+async fn test_composite_vectors() {
+    let vectors: Value =
+        serde_json::from_str(include_str!("vectors/composite.json")).expect("vector file");
+    for group in vectors["testGroups"].as_array().expect("testGroups") {
+        let label = group["parameterSet"].as_str().expect("parameterSet");
+        let algorithm = composite(label);
+        for test in group["tests"].as_array().expect("tests") {
+            let (public_key, secret_key) = (bytes(test, "pk"), bytes(test, "sk"));
+            let (message, signature) = (bytes(test, "message"), bytes(test, "signature"));
+            let ml_dsa_public_len = algorithm.ml_dsa().public_key_size();
+
+            // The secret key starts with the ML-DSA seed of the public key's first component
+            let (ml_dsa_public, _) = Backend::Native
+                .signature_keypair_from_seed(algorithm.ml_dsa(), &secret_key[..32])
+                .expect("keypair from seed");
+            assert_eq!(ml_dsa_public, public_key[..ml_dsa_public_len], "{label}");
+
+            // The ML-DSA component signs `M'` with the label as its context
+            let ml_dsa_len = algorithm.ml_dsa().signature_size();
+            assert!(
+                Backend::Native
+                    .verify(
+                        algorithm.ml_dsa(),
+                        &public_key[..ml_dsa_public_len],
+                        &bytes(test, "mprime"),
+                        &signature[..ml_dsa_len],
+                        &SigningOptions::new().with_context(label.as_bytes())
+                    )
+                    .expect("verify"),
+                "{label}"
+            );
+
+            let verify_message = |message: Vec<u8>, signature: Vec<u8>| {
+                CompositeBuilder::new(algorithm)
+                    .with_public_key(public_key.clone())
+                    .expect("public key")
+                    .with_message(message)
+                    .with_signature(signature)
+                    .verify()
+            };
+            assert!(
+                verify_message(message.clone(), signature)
+                    .await
+                    .expect("verify")
+                    .is_valid(),
+                "{label}"
+            );
+            assert!(
+                !verify_message(b"another message".to_vec(), bytes(test, "signature"))
+                    .await
+                    .expect("verify")
+                    .is_valid(),
+                "{label}"
+            );
+
+            // Signing with the vector's secret key verifies under the vector's public key
+            let signature = CompositeBuilder::new(algorithm)
+                .with_secret_key(secret_key)
+                .expect("secret key")
+                .with_message(message.clone())
+                .sign()
+                .await
+                .expect("sign")
+                .signature_vec();
+            assert!(
+                verify_message(message, signature)
+                    .await
+                    .expect("verify")
+                    .is_valid(),
+                "{label}"
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_both_components_must_verify() {
+    let algorithm = CompositeAlgorithm::MlDsa65Ed25519;
+    let keypair =
+        SignatureBuilder::composite(SignatureAlgorithm::MlDsa65, ClassicalAlgorithm::Ed25519)
+            .expect("composite")
+            .generate()
+            .await
+            .expect("generate");
+    let public_key = keypair.public_key_vec().expect("pk");
+    let signature = keypair
+        .with_message(b"composite message".to_vec())
+        .sign()
+        .await
+        .expect("sign")
+        .signature_vec();
+    let ml_dsa_len = SignatureAlgorithm::MlDsa65.signature_size();
+
+    // Corrupting either component fails the whole signature
+    let mut bad_ml_dsa = signature.clone();
+    bad_ml_dsa[10] ^= 1;
+    assert!(!verify(algorithm, &public_key, bad_ml_dsa).await);
+    let mut bad_classical = signature.clone();
+    bad_classical[ml_dsa_len + 10] ^= 1;
+    assert!(!verify(algorithm, &public_key, bad_classical).await);
+
+    // A stripped signature is rejected rather than checked as plain ML-DSA
+    assert!(matches!(
+        CompositeBuilder::new(algorithm)
+            .with_public_key(public_key.clone())
+            .expect("public key")
+            .with_message(b"composite message".to_vec())
+            .with_signature(signature[..ml_dsa_len].to_vec())
+            .verify()
+            .await,
+        Err(PqCryptoError::InvalidParameters(_))
+    ));
+
+    // The ML-DSA component is bound to the composite and does not verify on its own
+    let ml_dsa_public = &public_key[..SignatureAlgorithm::MlDsa65.public_key_size()];
+    assert!(
+        !Backend::Native
+            .verify(
+                SignatureAlgorithm::MlDsa65,
+                ml_dsa_public,
+                b"composite message",
+                &signature[..ml_dsa_len],
+                &SigningOptions::new()
+            )
+            .expect("verify")
+    );
+}
+
+#[tokio::test]
+async fn test_composite_key_encodings() {
+    let keypair = CompositeBuilder::new(CompositeAlgorithm::MlDsa87EcdsaP384)
+        .generate()
+        .await
+        .expect("generate");
+    let public_pem = PqPublicKey::new(
+        CompositeAlgorithm::MlDsa87EcdsaP384,
+        keypair.public_key_vec().expect("pk"),
+    )
+    .expect("public key")
+    .to_spki_pem()
+    .expect("SPKI PEM");
+    let secret_der = PqSecretKey::new(
+        CompositeAlgorithm::MlDsa87EcdsaP384,
+        keypair.secret_key_vec().expect("sk"),
+    )
+    .expect("secret key")
+    .to_pkcs8_der()
+    .expect("PKCS#8 DER");
+
+    // Encoded keys switch the builder to the composite they name
+    let signer = CompositeBuilder::new(CompositeAlgorithm::MlDsa44Ed25519)
+        .with_secret_key(secret_der.to_vec())
+        .expect("secret key");
+    assert_eq!(signer.algorithm(), CompositeAlgorithm::MlDsa87EcdsaP384);
+    let signature = signer
+        .with_message(b"composite message".to_vec())
+        .sign()
+        .await
+        .expect("sign");
+    assert!(
+        verify(
+            CompositeAlgorithm::MlDsa87EcdsaP384,
+            public_pem.as_bytes(),
+            signature.signature_vec()
+        )
+        .await
+    );
+
+    // Plain ML-DSA keys are not composite keys
+    let ml_dsa_pem = PqPublicKey::new(SignatureAlgorithm::MlDsa87, vec![0u8; 2592])
+        .expect("public key")
+        .to_spki_pem()
+        .expect("SPKI PEM");
+    assert!(matches!(
+        CompositeBuilder::new(CompositeAlgorithm::MlDsa87EcdsaP384)
+            .with_public_key(ml_dsa_pem.as_bytes()),
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+}
+
+#[test]
+fn test_unsupported_pairings() {
+    assert_eq!(
+        CompositeAlgorithm::new(SignatureAlgorithm::MlDsa44, ClassicalAlgorithm::EcdsaP256)
+            .expect("pairing"),
+        CompositeAlgorithm::MlDsa44EcdsaP256
+    );
+    assert!(matches!(
+        SignatureBuilder::composite(SignatureAlgorithm::MlDsa87, ClassicalAlgorithm::Ed25519),
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+    assert!(matches!(
+        SignatureBuilder::composite(SignatureAlgorithm::Falcon512, ClassicalAlgorithm::EcdsaP256),
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+    assert!(!Backend::Native.supports(CompositeAlgorithm::MlDsa65Ed25519));
+}
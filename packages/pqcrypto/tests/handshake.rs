@@ -0,0 +1,171 @@
+//! Signed ML-KEM handshake and session transport over an in-memory duplex stream
+
+use cryypt_pqcrypto::handshake::{HandshakeSuite, Identity, Initiator, Responder, Transport};
+use cryypt_pqcrypto::{KemAlgorithm, PqCryptoError, SignatureAlgorithm};
+use tokio::io::DuplexStream;
+
+type Result<T> = std::result::Result<T, PqCryptoError>;
+
+/// Run both sides of the handshake over one duplex pipe and return each side's session
+async fn handshake(
+    initiator: Initiator,
+    responder: Responder,
+) -> (
+    Result<(Transport, DuplexStream)>,
+    Result<(Transport, DuplexStream)>,
+) {
+    let (mut client, mut server) = tokio::io::duplex(64 * 1024);
+    let accept = tokio::spawn(async move {
+        let transport = responder.accept(&mut server).await?;
+        Ok::<_, PqCryptoError>((transport, server))
+    });
+    let connect = initiator
+        .connect(&mut client)
+        .await
+        .map(|transport| (transport, client));
+    (connect, accept.await.expect("responder task"))
+}
+
+fn peers(suite: HandshakeSuite) -> (Identity, Identity) {
+    let client = Identity::generate(suite.signature()).expect("client identity");
+    let server = Identity::generate(suite.signature()).expect("server identity");
+    (client, server)
+}
+
+#[tokio::test]
+async fn test_handshake_and_transport_both_directions() {
+    let suite = HandshakeSuite::default();
+    let (client_id, server_id) = peers(suite);
+    let initiator = Initiator::new(suite, client_id.clone())
+        .expect("initiator")
+        .with_peer_key(server_id.public_key());
+    let responder = Responder::new(suite, server_id.clone())
+        .expect("responder")
+        .with_peer_key(client_id.public_key());
+
+    let (connect, accept) = handshake(initiator, responder).await;
+    let (mut client, mut client_stream) = connect.expect("connect");
+    let (mut server, mut server_stream) = accept.expect("accept");
+
+    assert_eq!(client.transcript_hash(), server.transcript_hash());
+    assert_eq!(client.peer_public_key(), server_id.public_key());
+    assert_eq!(server.peer_public_key(), client_id.public_key());
+
+    for i in 0..3u8 {
+        let ping = [b'p', i];
+        client.send(&mut client_stream, &ping).await.expect("send");
+        assert_eq!(server.recv(&mut server_stream).await.expect("recv"), ping);
+
+        let pong = vec![i; 1000];
+        server.send(&mut server_stream, &pong).await.expect("send");
+        assert_eq!(client.recv(&mut client_stream).await.expect("recv"), pong);
+    }
+    assert_eq!(client.records_sent(), 3);
+    assert_eq!(server.records_received(), 3);
+
+    // Equal plaintexts produce distinct records under the counter nonces
+    let first = client.seal(b"same").await.expect("seal");
+    let second = client.seal(b"same").await.expect("seal");
+    assert_ne!(first, second);
+}
+
+#[tokio::test]
+async fn test_pinned_peer_mismatch_fails() {
+    let suite =
+        HandshakeSuite::new(KemAlgorithm::MlKem768, SignatureAlgorithm::MlDsa44).expect("suite");
+    let (client_id, server_id) = peers(suite);
+    let impostor = Identity::generate(suite.signature()).expect("impostor");
+
+    let initiator = Initiator::new(suite, client_id)
+        .expect("initiator")
+        .with_peer_key(impostor.public_key());
+    let (waiting, hello) = initiator.start().expect("client hello");
+    let (_, response) = Responder::new(suite, server_id)
+        .expect("responder")
+        .respond(&hello)
+        .expect("server hello");
+
+    assert!(matches!(
+        waiting.finish(&response),
+        Err(PqCryptoError::AuthenticationFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_tampered_or_mismatched_messages_fail() {
+    let suite =
+        HandshakeSuite::new(KemAlgorithm::MlKem512, SignatureAlgorithm::MlDsa44).expect("suite");
+    let (client_id, server_id) = peers(suite);
+    let responder = Responder::new(suite, server_id).expect("responder");
+
+    // Flipping the last byte of the ServerHello corrupts the responder's finished MAC
+    let (waiting, hello) = Initiator::new(suite, client_id.clone())
+        .expect("initiator")
+        .start()
+        .expect("client hello");
+    let (_, mut response) = responder.clone().respond(&hello).expect("server hello");
+    let last = response.len() - 1;
+    response[last] ^= 1;
+    assert!(matches!(
+        waiting.finish(&response),
+        Err(PqCryptoError::AuthenticationFailed(_))
+    ));
+
+    // A tampered ClientFinish is rejected by the responder
+    let (waiting, hello) = Initiator::new(suite, client_id.clone())
+        .expect("initiator")
+        .start()
+        .expect("client hello");
+    let (awaiting_finish, response) = responder.clone().respond(&hello).expect("server hello");
+    let (_, mut finish) = waiting.finish(&response).expect("client finish");
+    let last = finish.len() - 1;
+    finish[last] ^= 1;
+    assert!(matches!(
+        awaiting_finish.finish(&finish),
+        Err(PqCryptoError::AuthenticationFailed(_))
+    ));
+
+    // A responder configured for another suite refuses the ClientHello
+    let other =
+        HandshakeSuite::new(KemAlgorithm::MlKem768, SignatureAlgorithm::MlDsa44).expect("suite");
+    let (_, hello) = Initiator::new(other, client_id)
+        .expect("initiator")
+        .start()
+        .expect("client hello");
+    assert!(matches!(
+        responder.respond(&hello),
+        Err(PqCryptoError::InvalidParameters(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_replayed_and_reordered_records_fail() {
+    let suite =
+        HandshakeSuite::new(KemAlgorithm::MlKem512, SignatureAlgorithm::MlDsa44).expect("suite");
+    let (client_id, server_id) = peers(suite);
+    let (connect, accept) = handshake(
+        Initiator::new(suite, client_id).expect("initiator"),
+        Responder::new(suite, server_id).expect("responder"),
+    )
+    .await;
+    let (mut client, _) = connect.expect("connect");
+    let (mut server, _) = accept.expect("accept");
+
+    let first = client.seal(b"first").await.expect("seal");
+    let second = client.seal(b"second").await.expect("seal");
+
+    // Out of order: record 1 does not open as record 0, and the counter does not advance
+    assert!(matches!(
+        server.open(&second).await,
+        Err(PqCryptoError::AuthenticationFailed(_))
+    ));
+    assert_eq!(server.open(&first).await.expect("open"), b"first");
+
+    // Replay: record 0 does not open again as record 1
+    assert!(matches!(
+        server.open(&first).await,
+        Err(PqCryptoError::AuthenticationFailed(_))
+    ));
+    assert_eq!(server.open(&second).await.expect("open"), b"second");
+    assert_eq!(server.records_received(), 2);
+}
@@ -0,0 +1,284 @@
+//! HPKE (RFC 9180) tests
+
+use cryypt_pqcrypto::{Hpke, HpkeAead, HpkeKdf, HpkeKem, PqCryptoError};
+use hex_literal::hex;
+
+const INFO: &[u8] = &hex!("4f6465206f6e2061204772656369616e2055726e");
+const PSK: &[u8] = &hex!("0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82");
+const PSK_ID: &[u8] = &hex!("456e6e796e20447572696e206172616e204d6f726961");
+const PLAINTEXT: &[u8] = b"Beauty is truth, truth beauty";
+
+/// One RFC 9180 appendix A vector: the first two encryptions and one export
+struct Vector {
+    kem: HpkeKem,
+    aead: HpkeAead,
+    psk: bool,
+    ikm_e: &'static [u8],
+    ikm_r: &'static [u8],
+    ikm_s: Option<&'static [u8]>,
+    enc: &'static [u8],
+    ciphertexts: [&'static [u8]; 2],
+    export: &'static [u8],
+}
+
+const VECTORS: [Vector; 6] = [
+    // A.1.1 DHKEM(X25519, HKDF-SHA256), HKDF-SHA256, AES-128-GCM, base
+    Vector {
+        kem: HpkeKem::X25519HkdfSha256,
+        aead: HpkeAead::Aes128Gcm,
+        psk: false,
+        ikm_e: &hex!("7268600d403fce431561aef583ee1613527cff655c1343f29812e66706df3234"),
+        ikm_r: &hex!("6db9df30aa07dd42ee5e8181afdb977e538f5e1fec8a06223f33f7013e525037"),
+        ikm_s: None,
+        enc: &hex!("37fda3567bdbd628e88668c3c8d7e97d1d1253b6d4ea6d44c150f741f1bf4431"),
+        ciphertexts: [
+            &hex!(
+                "f938558b5d72f1a23810b4be2ab4f84331acc02fc97babc53a52ae8218a355a9"
+                "6d8770ac83d07bea87e13c512a"
+            ),
+            &hex!(
+                "af2d7e9ac9ae7e270f46ba1f975be53c09f8d875bdc8535458c2494e8a6eab25"
+                "1c03d0c22a56b8ca42c2063b84"
+            ),
+        ],
+        export: &hex!("e9e43065102c3836401bed8c3c3c75ae46be1639869391d62c61f1ec7af54931"),
+    },
+    // A.1.2 PSK
+    Vector {
+        kem: HpkeKem::X25519HkdfSha256,
+        aead: HpkeAead::Aes128Gcm,
+        psk: true,
+        ikm_e: &hex!("78628c354e46f3e169bd231be7b2ff1c77aa302460a26dbfa15515684c00130b"),
+        ikm_r: &hex!("d4a09d09f575fef425905d2ab396c1449141463f698f8efdb7accfaff8995098"),
+        ikm_s: None,
+        enc: &hex!("0ad0950d9fb9588e59690b74f1237ecdf1d775cd60be2eca57af5a4b0471c91b"),
+        ciphertexts: [
+            &hex!(
+                "e52c6fed7f758d0cf7145689f21bc1be6ec9ea097fef4e959440012f4feb73fb"
+                "611b946199e681f4cfc34db8ea"
+            ),
+            &hex!(
+                "49f3b19b28a9ea9f43e8c71204c00d4a490ee7f61387b6719db765e948123b45"
+                "b61633ef059ba22cd62437c8ba"
+            ),
+        ],
+        export: &hex!("8aff52b45a1be3a734bc7a41e20b4e055ad4c4d22104b0c20285a7c4302401cd"),
+    },
+    // A.1.3 auth
+    Vector {
+        kem: HpkeKem::X25519HkdfSha256,
+        aead: HpkeAead::Aes128Gcm,
+        psk: false,
+        ikm_e: &hex!("6e6d8f200ea2fb20c30b003a8b4f433d2f4ed4c2658d5bc8ce2fef718059c9f7"),
+        ikm_r: &hex!("f1d4a30a4cef8d6d4e3b016e6fd3799ea057db4f345472ed302a67ce1c20cdec"),
+        ikm_s: Some(&hex!(
+            "94b020ce91d73fca4649006c7e7329a67b40c55e9e93cc907d282bbbff386f58"
+        )),
+        enc: &hex!("23fb952571a14a25e3d678140cd0e5eb47a0961bb18afcf85896e5453c312e76"),
+        ciphertexts: [
+            &hex!(
+                "5fd92cc9d46dbf8943e72a07e42f363ed5f721212cd90bcfd072bfd9f44e06b8"
+                "0fd17824947496e21b680c141b"
+            ),
+            &hex!(
+                "d3736bb256c19bfa93d79e8f80b7971262cb7c887e35c26370cfed62254369a1"
+                "b52e3d505b79dd699f002bc8ed"
+            ),
+        ],
+        export: &hex!("5a0131813abc9a522cad678eb6bafaabc43389934adb8097d23c5ff68059eb64"),
+    },
+    // A.1.4 auth-PSK
+    Vector {
+        kem: HpkeKem::X25519HkdfSha256,
+        aead: HpkeAead::Aes128Gcm,
+        psk: true,
+        ikm_e: &hex!("4303619085a20ebcf18edd22782952b8a7161e1dbae6e46e143a52a96127cf84"),
+        ikm_r: &hex!("4b16221f3b269a88e207270b5e1de28cb01f847841b344b8314d6a622fe5ee90"),
+        ikm_s: Some(&hex!(
+            "62f77dcf5df0dd7eac54eac9f654f426d4161ec850cc65c54f8b65d2e0b4e345"
+        )),
+        enc: &hex!("820818d3c23993492cc5623ab437a48a0a7ca3e9639c140fe1e33811eb844b7c"),
+        ciphertexts: [
+            &hex!(
+                "a84c64df1e11d8fd11450039d4fe64ff0c8a99fca0bd72c2d4c3e0400bc14a40"
+                "f27e45e141a24001697737533e"
+            ),
+            &hex!(
+                "4d19303b848f424fc3c3beca249b2c6de0a34083b8e909b6aa4c3688505c05ff"
+                "e0c8f57a0a4c5ab9da127435d9"
+            ),
+        ],
+        export: &hex!("a30c20370c026bbea4dca51cb63761695132d342bae33a6a11527d3e7679436d"),
+    },
+    // A.2.1 DHKEM(X25519, HKDF-SHA256), HKDF-SHA256, ChaCha20-Poly1305, base
+    Vector {
+        kem: HpkeKem::X25519HkdfSha256,
+        aead: HpkeAead::ChaCha20Poly1305,
+        psk: false,
+        ikm_e: &hex!("909a9b35d3dc4713a5e72a4da274b55d3d3821a37e5d099e74a647db583a904b"),
+        ikm_r: &hex!("1ac01f181fdf9f352797655161c58b75c656a6cc2716dcb66372da835542e1df"),
+        ikm_s: None,
+        enc: &hex!("1afa08d3dec047a643885163f1180476fa7ddb54c6a8029ea33f95796bf2ac4a"),
+        ciphertexts: [
+            &hex!(
+                "1c5250d8034ec2b784ba2cfd69dbdb8af406cfe3ff938e131f0def8c8b60b4db"
+                "21993c62ce81883d2dd1b51a28"
+            ),
+            &hex!(
+                "6b53c051e4199c518de79594e1c4ab18b96f081549d45ce015be002090bb119e"
+                "85285337cc95ba5f59992dc98c"
+            ),
+        ],
+        export: &hex!("5acb09211139c43b3090489a9da433e8a30ee7188ba8b0a9a1ccf0c229283e53"),
+    },
+    // A.3.1 DHKEM(P-256, HKDF-SHA256), HKDF-SHA256, AES-128-GCM, base
+    Vector {
+        kem: HpkeKem::P256HkdfSha256,
+        aead: HpkeAead::Aes128Gcm,
+        psk: false,
+        ikm_e: &hex!("4270e54ffd08d79d5928020af4686d8f6b7d35dbe470265f1f5aa22816ce860e"),
+        ikm_r: &hex!("668b37171f1072f3cf12ea8a236a45df23fc13b82af3609ad1e354f6ef817550"),
+        ikm_s: None,
+        enc: &hex!(
+            "04a92719c6195d5085104f469a8b9814d5838ff72b60501e2c4466e5e67b325a"
+            "c98536d7b61a1af4b78e5b7f951c0900be863c403ce65c9bfcb9382657222d18c4"
+        ),
+        ciphertexts: [
+            &hex!(
+                "5ad590bb8baa577f8619db35a36311226a896e7342a6d836d8b7bcd2f20b6c7f"
+                "9076ac232e3ab2523f39513434"
+            ),
+            &hex!(
+                "fa6f037b47fc21826b610172ca9637e82d6e5801eb31cbd3748271affd4ecb06"
+                "646e0329cbdf3c3cd655b28e82"
+            ),
+        ],
+        export: &hex!("d8f1ea7942adbba7412c6d431c62d01371ea476b823eb697e1f6e6cae1dab85a"),
+    },
+];
+
+#[test]
+fn test_rfc9180_vectors() {
+    for vector in &VECTORS {
+        let (recipient_pk, recipient_sk) = vector.kem.derive_key_pair(vector.ikm_r).expect("ikmR");
+        let mut sender = Hpke::new(vector.kem, HpkeKdf::HkdfSha256, vector.aead);
+        let mut receiver = sender.clone();
+        if vector.psk {
+            sender = sender.with_psk(PSK, PSK_ID);
+            receiver = receiver.with_psk(PSK, PSK_ID);
+        }
+        if let Some(ikm_s) = vector.ikm_s {
+            let (sender_pk, sender_sk) = vector.kem.derive_key_pair(ikm_s).expect("ikmS");
+            sender = sender.with_sender_secret_key(&sender_sk);
+            receiver = receiver.with_sender_public_key(&sender_pk);
+        }
+
+        let (enc, mut sealer) = sender
+            .setup_sender_deterministic(&recipient_pk, INFO, vector.ikm_e)
+            .expect("sender");
+        assert_eq!(enc, vector.enc);
+        let mut opener = receiver
+            .setup_receiver(&enc, &recipient_sk, INFO)
+            .expect("receiver");
+
+        for (sequence, expected) in vector.ciphertexts.iter().enumerate() {
+            let aad = format!("Count-{sequence}");
+            let ciphertext = sealer.seal(aad.as_bytes(), PLAINTEXT).expect("seal");
+            assert_eq!(ciphertext, *expected, "ciphertext {sequence}");
+            let plaintext = opener.open(aad.as_bytes(), &ciphertext).expect("open");
+            assert_eq!(plaintext, PLAINTEXT);
+        }
+        for exported in [
+            sealer.export(b"TestContext", 32).expect("export"),
+            opener.export(b"TestContext", 32).expect("export"),
+        ] {
+            assert_eq!(exported.as_slice(), vector.export);
+        }
+    }
+}
+
+#[test]
+fn test_single_shot_across_suites() {
+    let kems = [
+        HpkeKem::X25519HkdfSha256,
+        HpkeKem::P256HkdfSha256,
+        HpkeKem::MlKem512,
+        HpkeKem::MlKem768,
+        HpkeKem::MlKem1024,
+        HpkeKem::X25519MlKem768,
+    ];
+    for kem in kems {
+        let (public_key, secret_key) = kem.generate_key_pair().expect("keypair");
+        assert_eq!(public_key.len(), kem.public_key_len());
+        for kdf in [HpkeKdf::HkdfSha256, HpkeKdf::HkdfSha512] {
+            for aead in [
+                HpkeAead::Aes128Gcm,
+                HpkeAead::Aes256Gcm,
+                HpkeAead::ChaCha20Poly1305,
+            ] {
+                let suite = Hpke::new(kem, kdf, aead).with_psk(PSK, PSK_ID);
+                let (enc, ciphertext) = suite
+                    .seal(&public_key, b"info", b"aad", b"hello hpke")
+                    .expect("seal");
+                assert_eq!(enc.len(), kem.enc_len());
+                let plaintext = suite
+                    .open(&enc, &secret_key, b"info", b"aad", &ciphertext)
+                    .expect("open");
+                assert_eq!(plaintext, b"hello hpke");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rejects_mismatched_inputs() {
+    let kem = HpkeKem::X25519HkdfSha256;
+    let suite = Hpke::new(kem, HpkeKdf::HkdfSha256, HpkeAead::Aes256Gcm);
+    let (public_key, secret_key) = kem.generate_key_pair().expect("keypair");
+    let (enc, ciphertext) = suite
+        .seal(&public_key, b"info", b"aad", b"secret")
+        .expect("seal");
+
+    let wrong_info = suite.open(&enc, &secret_key, b"other", b"aad", &ciphertext);
+    assert!(matches!(
+        wrong_info,
+        Err(PqCryptoError::AuthenticationFailed(_))
+    ));
+    let with_psk = suite.clone().with_psk(PSK, PSK_ID);
+    let wrong_mode = with_psk.open(&enc, &secret_key, b"info", b"aad", &ciphertext);
+    assert!(matches!(
+        wrong_mode,
+        Err(PqCryptoError::AuthenticationFailed(_))
+    ));
+
+    // A failed open leaves the context able to open the expected message
+    let (enc, mut sealer) = suite.setup_sender(&public_key, b"info").expect("sender");
+    let first = sealer.seal(b"", b"first").expect("seal");
+    let second = sealer.seal(b"", b"second").expect("seal");
+    let mut opener = suite
+        .setup_receiver(&enc, &secret_key, b"info")
+        .expect("receiver");
+    assert!(opener.open(b"", &second).is_err());
+    assert_eq!(opener.open(b"", &first).expect("open"), b"first");
+    assert_eq!(opener.open(b"", &second).expect("open"), b"second");
+
+    // Auth modes need a DHKEM, short PSKs are refused, export-only suites cannot seal
+    let mlkem = Hpke::new(HpkeKem::MlKem768, HpkeKdf::HkdfSha256, HpkeAead::Aes256Gcm);
+    let (mlkem_pk, _) = mlkem.kem().generate_key_pair().expect("keypair");
+    let auth = mlkem
+        .with_sender_secret_key(&[7u8; 32])
+        .setup_sender(&mlkem_pk, b"");
+    assert!(matches!(auth, Err(PqCryptoError::UnsupportedAlgorithm(_))));
+    let short_psk = suite
+        .clone()
+        .with_psk(&[1u8; 16], PSK_ID)
+        .seal(&public_key, b"", b"", b"");
+    assert!(matches!(
+        short_psk,
+        Err(PqCryptoError::InvalidKeySize { .. })
+    ));
+    let export_only = Hpke::new(kem, HpkeKdf::HkdfSha512, HpkeAead::ExportOnly);
+    let (_, mut exporter) = export_only.setup_sender(&public_key, b"").expect("sender");
+    assert!(exporter.seal(b"", b"data").is_err());
+    assert_eq!(exporter.export(b"ctx", 64).expect("export").len(), 64);
+}
@@ -0,0 +1,210 @@
+//! Hybrid ECDH + ML-KEM tests
+
+use cryypt_pqcrypto::api::{
+    CiphertextBuilder, DecapsulateBuilder, EncapsulateBuilder, KemBuilder, KemKeyPairBuilder,
+    MlKemBuilder, NeedKeyPair,
+};
+use cryypt_pqcrypto::backend::Backend;
+use cryypt_pqcrypto::{KemAlgorithm, PqCryptoError};
+use serde_json::Value;
+use sha3::Shake256;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+const HYBRIDS: [KemAlgorithm; 3] = [
+    KemAlgorithm::X25519MlKem768,
+    KemAlgorithm::P256MlKem768,
+    KemAlgorithm::P384MlKem1024,
+];
+
+fn builder(algorithm: KemAlgorithm) -> MlKemBuilder<NeedKeyPair> {
+    match algorithm {
+        KemAlgorithm::X25519MlKem768 => KemBuilder::x25519_ml_kem_768(),
+        KemAlgorithm::P256MlKem768 => KemBuilder::p256_ml_kem_768(),
+        _ => KemBuilder::p384_ml_kem_1024(),
+    }
+}
+
+#[tokio::test]
+async fn test_hybrid_roundtrip_and_sizes() {
+    for algorithm in HYBRIDS {
+        assert!(algorithm.is_hybrid());
+        let keypair = builder(algorithm).generate().await.expect("keypair");
+        let public_key = keypair.public_key_vec().expect("public key");
+        let secret_key = keypair.secret_key_vec().expect("secret key");
+        assert_eq!(public_key.len(), algorithm.public_key_size());
+        assert_eq!(secret_key.len(), algorithm.secret_key_size());
+
+        let encapsulated = builder(algorithm)
+            .with_public_key(public_key)
+            .expect("public key")
+            .encapsulate()
+            .await
+            .expect("encapsulate");
+        assert_eq!(encapsulated.ciphertext_size(), algorithm.ciphertext_size());
+        assert_eq!(encapsulated.shared_secret().len(), 32);
+
+        let decapsulated = builder(algorithm)
+            .with_secret_key(secret_key)
+            .expect("secret key")
+            .with_ciphertext(encapsulated.ciphertext_vec())
+            .decapsulate()
+            .await
+            .expect("decapsulate");
+        assert_eq!(
+            decapsulated.shared_secret().as_bytes(),
+            encapsulated.shared_secret().as_bytes(),
+            "{algorithm} shared secrets differ"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_wrong_key_yields_different_secret() {
+    let algorithm = KemAlgorithm::X25519MlKem768;
+    let sender = KemBuilder::x25519_ml_kem_768()
+        .generate()
+        .await
+        .expect("keypair");
+    let other = KemBuilder::x25519_ml_kem_768()
+        .generate()
+        .await
+        .expect("keypair");
+
+    let encapsulated = sender.encapsulate().await.expect("encapsulate");
+    let decapsulated = builder(algorithm)
+        .with_secret_key(other.secret_key_vec().expect("secret key"))
+        .expect("secret key")
+        .with_ciphertext(encapsulated.ciphertext_vec())
+        .decapsulate()
+        .await
+        .expect("decapsulate");
+    assert_ne!(
+        decapsulated.shared_secret().as_bytes(),
+        encapsulated.shared_secret().as_bytes()
+    );
+
+    // Keys of one hybrid are rejected by another with a different layout
+    let p384_key = KemBuilder::p384_ml_kem_1024()
+        .generate()
+        .await
+        .expect("keypair")
+        .public_key_vec()
+        .expect("public key");
+    assert!(matches!(
+        KemBuilder::p256_ml_kem_768().with_public_key(p384_key),
+        Err(PqCryptoError::InvalidKeySize { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_rejects_malformed_ecdh_components() {
+    // All-zero X25519 share is a low-order point (ML-KEM part comes first)
+    let keypair = KemBuilder::x25519_ml_kem_768()
+        .generate()
+        .await
+        .expect("keypair");
+    let mut public_key = keypair.public_key_vec().expect("public key");
+    let mlkem_len = KemAlgorithm::MlKem768.public_key_size();
+    public_key[mlkem_len..].fill(0);
+    let result = KemBuilder::x25519_ml_kem_768()
+        .with_public_key(public_key)
+        .expect("public key")
+        .encapsulate()
+        .await;
+    assert!(matches!(result, Err(PqCryptoError::InvalidKey(_))));
+
+    // A P-256 ciphertext share that is not a curve point (ECDH part comes first)
+    let keypair = KemBuilder::p256_ml_kem_768()
+        .generate()
+        .await
+        .expect("keypair");
+    let mut ciphertext = keypair
+        .clone()
+        .encapsulate()
+        .await
+        .expect("encapsulate")
+        .ciphertext_vec();
+    ciphertext[..65].fill(0);
+    let result = keypair.with_ciphertext(ciphertext).decapsulate().await;
+    assert!(matches!(result, Err(PqCryptoError::InvalidKey(_))));
+}
+
+fn bytes(test: &Value, field: &str) -> Vec<u8> {
+    hex::decode(test[field].as_str().expect(field)).expect("hex")
+}
+
+fn x25519_public(scalar: &[u8]) -> Vec<u8> {
+    let scalar: [u8; 32] = scalar.try_into().expect("X25519 scalar");
+    let secret = x25519_dalek::StaticSecret::from(scalar);
+    x25519_dalek::PublicKey::from(&secret).as_bytes().to_vec()
+}
+
+#[tokio::test]
+async fn test_x_wing_vectors() {
+    let vectors: Value =
+        serde_json::from_str(include_str!("vectors/x_wing.json")).expect("vector file");
+    let mlkem = KemAlgorithm::MlKem768;
+    for group in vectors["testGroups"].as_array().expect("testGroups") {
+        for test in group["tests"].as_array().expect("tests") {
+            let (public_key, secret_key) = (bytes(test, "pk"), bytes(test, "dk"));
+            let (ciphertext, eseed) = (bytes(test, "ct"), bytes(test, "eseed"));
+
+            // The draft's key derivation yields the vector's keys in cryypt's layout
+            let mut expanded = [0u8; 96];
+            let mut shake = Shake256::default();
+            shake.update(&bytes(test, "seed"));
+            shake.finalize_xof().read(&mut expanded);
+            let (mlkem_public, mlkem_secret) = Backend::Native
+                .kem_keypair_from_seed(mlkem, &expanded[..64])
+                .expect("keypair from seed");
+            let x25519_scalar = &expanded[64..];
+            assert_eq!(
+                public_key,
+                [mlkem_public.clone(), x25519_public(x25519_scalar)].concat()
+            );
+            assert_eq!(secret_key, [&mlkem_secret[..], x25519_scalar].concat());
+
+            // Encapsulation from `eseed` yields the vector's ciphertext
+            let (mlkem_ciphertext, _) = Backend::Native
+                .encapsulate_deterministic(mlkem, &mlkem_public, &eseed[..32])
+                .expect("encapsulate");
+            assert_eq!(
+                ciphertext,
+                [mlkem_ciphertext, x25519_public(&eseed[32..])].concat()
+            );
+
+            let decapsulated = KemBuilder::x25519_ml_kem_768()
+                .with_secret_key(secret_key.clone())
+                .expect("secret key")
+                .with_ciphertext(ciphertext)
+                .decapsulate()
+                .await
+                .expect("decapsulate");
+            assert_eq!(
+                decapsulated.shared_secret().as_bytes(),
+                bytes(test, "ss"),
+                "test case {}",
+                test["tcId"]
+            );
+
+            // A fresh encapsulation to the vector key decapsulates with the vector key
+            let encapsulated = KemBuilder::x25519_ml_kem_768()
+                .with_public_key(public_key)
+                .expect("public key")
+                .encapsulate()
+                .await
+                .expect("encapsulate");
+            let decapsulated = KemBuilder::x25519_ml_kem_768()
+                .with_secret_key(secret_key)
+                .expect("secret key")
+                .with_ciphertext(encapsulated.ciphertext_vec())
+                .decapsulate()
+                .await
+                .expect("decapsulate");
+            assert_eq!(
+                decapsulated.shared_secret().as_bytes(),
+                encapsulated.shared_secret().as_bytes()
+            );
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Generic KEM builder tests
+
+use cryypt_pqcrypto::api::{EncapsulateBuilder, KemBuilder, KemKeyPairBuilder, MlKemBuilder};
+use cryypt_pqcrypto::{KemAlgorithm, KemPublicKey, KemSecretKey, PqCryptoError};
+
+const ALL_KEMS: [KemAlgorithm; 6] = [
+    KemAlgorithm::MlKem512,
+    KemAlgorithm::MlKem768,
+    KemAlgorithm::MlKem1024,
+    KemAlgorithm::X25519MlKem768,
+    KemAlgorithm::P256MlKem768,
+    KemAlgorithm::P384MlKem1024,
+];
+
+#[tokio::test]
+async fn test_generic_roundtrip_all_algorithms() {
+    for algorithm in ALL_KEMS {
+        let (public_key, secret_key) = KemBuilder::new()
+            .with_algorithm(algorithm)
+            .on_result(|result| result.expect("generate"))
+            .generate_keypair()
+            .await;
+        assert_eq!(public_key.algorithm(), algorithm);
+        assert_eq!(secret_key.algorithm(), algorithm);
+
+        let encapsulation = KemBuilder::new()
+            .with_public_key(public_key)
+            .expect("public key")
+            .on_result(|result| result.expect("encapsulate"))
+            .encapsulate()
+            .await;
+        assert_eq!(encapsulation.ciphertext_size(), algorithm.ciphertext_size());
+
+        let shared_secret = KemBuilder::new()
+            .with_secret_key(secret_key)
+            .expect("secret key")
+            .with_ciphertext(encapsulation.ciphertext_vec())
+            .expect("ciphertext")
+            .on_result(|result| result.expect("decapsulate"))
+            .decapsulate()
+            .await;
+        assert_eq!(&shared_secret, encapsulation.shared_secret(), "{algorithm}");
+    }
+}
+
+#[tokio::test]
+async fn test_caller_supplied_secret_key() {
+    // Keys generated elsewhere decapsulate through the generic builder
+    let keypair = MlKemBuilder::new(KemAlgorithm::MlKem1024)
+        .generate()
+        .await
+        .expect("generate");
+    let secret_key = KemSecretKey::new(
+        KemAlgorithm::MlKem1024,
+        keypair.secret_key_vec().expect("sk"),
+    )
+    .expect("secret key");
+    let encapsulation = MlKemBuilder::new(KemAlgorithm::MlKem1024)
+        .with_public_key(keypair.public_key_vec().expect("pk"))
+        .expect("public key")
+        .encapsulate()
+        .await
+        .expect("encapsulate");
+
+    let shared_secret = KemBuilder::new()
+        .with_secret_key(secret_key)
+        .expect("secret key")
+        .with_ciphertext(encapsulation.ciphertext_vec())
+        .expect("ciphertext")
+        .on_result(|result| result.expect("decapsulate"))
+        .decapsulate()
+        .await;
+    assert_eq!(&shared_secret, encapsulation.shared_secret());
+}
+
+#[test]
+fn test_key_wrappers_check_lengths() {
+    assert!(matches!(
+        KemPublicKey::new(KemAlgorithm::MlKem768, vec![0u8; 800]),
+        Err(PqCryptoError::InvalidKeySize {
+            expected: 1184,
+            actual: 800
+        })
+    ));
+    assert!(matches!(
+        KemSecretKey::new(KemAlgorithm::MlKem512, vec![0u8; 2400]),
+        Err(PqCryptoError::InvalidKeySize { .. })
+    ));
+
+    let secret_key =
+        KemSecretKey::new(KemAlgorithm::MlKem512, vec![7u8; 1632]).expect("secret key");
+    let debug = format!("{secret_key:?}");
+    assert!(debug.contains("MlKem512") && !debug.contains("7, 7"));
+
+    let builder = MlKemBuilder::new(KemAlgorithm::MlKem512)
+        .with_secret_key(secret_key.as_bytes().to_vec())
+        .expect("secret key");
+    let debug = format!("{builder:?}");
+    assert!(debug.contains("MlKem512") && !debug.contains("7, 7"));
+}
+
+#[test]
+fn test_algorithm_consistency() {
+    let secret_key =
+        KemSecretKey::new(KemAlgorithm::MlKem768, vec![0u8; 2400]).expect("secret key");
+
+    // A key for another algorithm than the chosen one is refused
+    assert!(matches!(
+        KemBuilder::new()
+            .with_algorithm(KemAlgorithm::MlKem1024)
+            .with_secret_key(secret_key.clone()),
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+
+    // Ciphertexts must match the key's algorithm
+    assert!(matches!(
+        KemBuilder::new()
+            .with_secret_key(secret_key)
+            .expect("secret key")
+            .with_ciphertext(vec![0u8; KemAlgorithm::MlKem1024.ciphertext_size()]),
+        Err(PqCryptoError::InvalidCiphertext(_))
+    ));
+}
@@ -0,0 +1,132 @@
+//! ML-KEM and ML-DSA key pairs kept in `cryypt_key` stores
+
+use cryypt_key::{FileKeyStore, SimpleKeyId, StoredKeyKind, StoredKeyPair};
+use cryypt_pqcrypto::api::{
+    CiphertextBuilder, DecapsulateBuilder, EncapsulateBuilder, KemKeyPairBuilder, MessageBuilder,
+    MlKemBuilder, SignBuilder, SignatureBuilder, SignatureDataBuilder, SignatureKeyPairBuilder,
+    VerifyBuilder,
+};
+use cryypt_pqcrypto::{KemAlgorithm, PqCryptoError, PqCryptoMasterBuilder, SignatureAlgorithm};
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("cryypt-pq-store-{name}-{}", std::process::id()))
+}
+
+#[tokio::test]
+async fn test_ml_kem_keypair_round_trip() {
+    let dir = temp_dir("kem");
+    let store = FileKeyStore::at(&dir).with_master_key([5u8; 32]);
+    let key_id = SimpleKeyId::new("transport:v1");
+
+    let keypair = MlKemBuilder::new(KemAlgorithm::MlKem768)
+        .generate()
+        .await
+        .expect("generate");
+    keypair.save(&store, &key_id).await.expect("save");
+
+    let stored = StoredKeyPair::load(&store, &key_id, StoredKeyKind::MlKem768)
+        .await
+        .expect("typed load");
+    assert_eq!(stored.public_key(), keypair.public_key().expect("pk"));
+
+    let loaded = PqCryptoMasterBuilder::new()
+        .ml_kem_from_store(&store, &key_id)
+        .await
+        .expect("load");
+    assert_eq!(loaded.algorithm(), KemAlgorithm::MlKem768);
+
+    // The loaded key pair decapsulates what the original public key encapsulated
+    let encapsulation = MlKemBuilder::new(KemAlgorithm::MlKem768)
+        .with_public_key(keypair.public_key_vec().expect("pk"))
+        .expect("public key")
+        .encapsulate()
+        .await
+        .expect("encapsulate");
+    let decapsulation = loaded
+        .with_ciphertext(encapsulation.ciphertext_vec())
+        .decapsulate()
+        .await
+        .expect("decapsulate");
+    assert_eq!(decapsulation.shared_secret(), encapsulation.shared_secret());
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_ml_dsa_keypair_signs_after_load() {
+    let dir = temp_dir("dsa");
+    let store = FileKeyStore::at(&dir).with_master_key([6u8; 32]);
+    let key_id = SimpleKeyId::new("release-signing:v1");
+
+    let keypair = SignatureBuilder::ml_dsa_87()
+        .generate()
+        .await
+        .expect("generate");
+    keypair.save(&store, &key_id).await.expect("save");
+
+    let loaded = PqCryptoMasterBuilder::new()
+        .ml_dsa_from_store(&store, &key_id)
+        .await
+        .expect("load");
+    assert_eq!(loaded.algorithm(), SignatureAlgorithm::MlDsa87);
+    assert_eq!(
+        loaded.public_key().expect("pk"),
+        keypair.public_key().expect("pk")
+    );
+
+    let signature = loaded
+        .with_message(b"release-2.0.tar.gz")
+        .sign()
+        .await
+        .expect("sign");
+    let verified = SignatureBuilder::ml_dsa_87()
+        .with_public_key(keypair.public_key_vec().expect("pk"))
+        .expect("public key")
+        .with_message(b"release-2.0.tar.gz")
+        .with_signature(signature.signature_vec())
+        .verify()
+        .await
+        .expect("verify");
+    assert!(verified.is_valid());
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_key_of_another_kind_is_refused() {
+    let dir = temp_dir("kinds");
+    let store = FileKeyStore::at(&dir).with_master_key([7u8; 32]);
+    let key_id = SimpleKeyId::new("kem:v1");
+
+    MlKemBuilder::new(KemAlgorithm::MlKem512)
+        .generate()
+        .await
+        .expect("generate")
+        .save(&store, &key_id)
+        .await
+        .expect("save");
+
+    assert!(matches!(
+        PqCryptoMasterBuilder::new()
+            .ml_dsa_from_store(&store, &key_id)
+            .await,
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+    assert!(
+        StoredKeyPair::load(&store, &key_id, StoredKeyKind::MlKem1024)
+            .await
+            .is_err()
+    );
+
+    // Hybrid KEM key pairs have no stored kind
+    let hybrid = MlKemBuilder::new(KemAlgorithm::X25519MlKem768)
+        .generate()
+        .await
+        .expect("generate");
+    assert!(matches!(
+        hybrid.save(&store, &SimpleKeyId::new("hybrid:v1")).await,
+        Err(PqCryptoError::UnsupportedAlgorithm(_))
+    ));
+
+    let _ = std::fs::remove_dir_all(dir);
+}
@@ -0,0 +1,263 @@
+//! Streaming sign/verify and detached `.sig` file tests
+
+use cryypt_pqcrypto::api::{
+    SignatureBuilder, SignatureKeyPairBuilder, StreamSignBuilder, StreamVerifyBuilder,
+};
+use cryypt_pqcrypto::{
+    Backend, PqCryptoError, PreHash, SignatureAlgorithm, SignatureFile, SigningOptions,
+    StreamDigest,
+};
+
+fn artifact() -> Vec<u8> {
+    (0u8..=250).cycle().take(300_000).collect()
+}
+
+#[tokio::test]
+async fn test_ml_dsa_stream_is_hash_ml_dsa() {
+    let message = artifact();
+    let keypair = SignatureBuilder::ml_dsa_65()
+        .generate()
+        .await
+        .expect("generate");
+    let public_key = keypair.public_key_vec().expect("pk");
+
+    let signature = keypair
+        .sign_stream(message.as_slice())
+        .await
+        .expect("sign stream");
+    assert_eq!(signature.algorithm(), SignatureAlgorithm::MlDsa65);
+    assert_eq!(signature.digest(), StreamDigest::HashSha512);
+    assert_eq!(
+        signature.key_fingerprint(),
+        Some(SignatureFile::fingerprint(&public_key).as_str())
+    );
+
+    // The same message split into arbitrary chunks verifies
+    let chunks =
+        futures::stream::iter(message.chunks(4096).map(<[u8]>::to_vec).collect::<Vec<_>>());
+    let verifier = SignatureBuilder::ml_dsa_65()
+        .with_public_key(public_key.clone())
+        .expect("public key");
+    assert!(
+        verifier
+            .verify_chunks(chunks, signature.clone())
+            .await
+            .expect("verify")
+            .is_valid()
+    );
+
+    // It is a standard HashML-DSA signature over the whole message, in the header's context
+    assert!(
+        Backend::Native
+            .verify(
+                SignatureAlgorithm::MlDsa65,
+                &public_key,
+                &message,
+                signature.signature(),
+                &SigningOptions::new()
+                    .with_context(signature.signed_header().expect("header"))
+                    .with_pre_hash(PreHash::Sha512),
+            )
+            .expect("verify")
+    );
+
+    let mut tampered = message.clone();
+    tampered[150_000] ^= 1;
+    let verifier = SignatureBuilder::ml_dsa_65()
+        .with_public_key(public_key)
+        .expect("public key");
+    assert!(
+        !verifier
+            .verify_stream(tampered.as_slice(), signature)
+            .await
+            .expect("verify")
+            .is_valid()
+    );
+}
+
+#[tokio::test]
+async fn test_sign_and_verify_files() {
+    let path = std::env::temp_dir().join(format!("cryypt-artifact-{}.bin", std::process::id()));
+    tokio::fs::write(&path, artifact()).await.expect("write");
+
+    // FALCON signs a domain-separated SHA3-512 digest through PQClean
+    let falcon = SignatureBuilder::falcon_512()
+        .generate()
+        .await
+        .expect("generate");
+    let falcon_public = falcon.public_key_vec().expect("pk");
+    let written = falcon.sign_file(&path).await.expect("sign file");
+    let read = SignatureFile::read(SignatureFile::path_for(&path))
+        .await
+        .expect("read .sig");
+    assert_eq!(read, written);
+    assert_eq!(read.digest(), StreamDigest::DomainSha3_512);
+    assert!(
+        SignatureBuilder::falcon_512()
+            .with_public_key(falcon_public)
+            .expect("public key")
+            .verify_file(&path)
+            .await
+            .expect("verify file")
+            .is_valid()
+    );
+
+    // SPHINCS+ keys sign as HashSLH-DSA, replacing the previous `.sig`
+    let sphincs = SignatureBuilder::sphincs_plus("sha256-128f-simple").expect("sphincs");
+    let sphincs = sphincs.generate().await.expect("generate");
+    let sphincs_public = sphincs.public_key_vec().expect("pk");
+    sphincs.sign_file(&path).await.expect("sign file");
+    let verifier = SignatureBuilder::sphincs_plus("sha256-128f-simple")
+        .expect("sphincs")
+        .with_public_key(sphincs_public.clone())
+        .expect("public key");
+    assert!(
+        verifier
+            .verify_file(&path)
+            .await
+            .expect("verify file")
+            .is_valid()
+    );
+
+    let mut tampered = artifact();
+    tampered[0] ^= 1;
+    tokio::fs::write(&path, tampered).await.expect("write");
+    let verifier = SignatureBuilder::sphincs_plus("sha256-128f-simple")
+        .expect("sphincs")
+        .with_public_key(sphincs_public)
+        .expect("public key");
+    assert!(
+        !verifier
+            .verify_file(&path)
+            .await
+            .expect("verify file")
+            .is_valid()
+    );
+
+    let _ = tokio::fs::remove_file(SignatureFile::path_for(&path)).await;
+    let _ = tokio::fs::remove_file(&path).await;
+}
+
+#[tokio::test]
+async fn test_signature_file_metadata_is_checked() {
+    let message = artifact();
+    let keypair = SignatureBuilder::ml_dsa_44()
+        .generate()
+        .await
+        .expect("generate");
+    let signature = keypair
+        .sign_stream(message.as_slice())
+        .await
+        .expect("sign stream");
+
+    let bytes = signature.to_bytes().expect("encode");
+    assert_eq!(bytes.last(), Some(&b'\n'));
+    let decoded = SignatureFile::from_bytes(&bytes).expect("decode");
+    assert_eq!(decoded, signature);
+
+    // A key other than the recorded signer does not verify
+    let other = SignatureBuilder::ml_dsa_44()
+        .generate()
+        .await
+        .expect("generate");
+    assert!(
+        !other
+            .verify_stream(message.as_slice(), decoded.clone())
+            .await
+            .expect("verify")
+            .is_valid()
+    );
+
+    // A signature file for another algorithm is rejected outright
+    let ml_dsa_65 = SignatureBuilder::ml_dsa_65()
+        .generate()
+        .await
+        .expect("generate");
+    assert!(matches!(
+        ml_dsa_65.verify_stream(message.as_slice(), decoded).await,
+        Err(PqCryptoError::InvalidParameters(_))
+    ));
+
+    let text = String::from_utf8(bytes).expect("utf-8");
+    let unknown = text.replace("cryypt-sig/2", "cryypt-sig/9");
+    assert!(matches!(
+        SignatureFile::from_bytes(unknown.as_bytes()),
+        Err(PqCryptoError::InvalidParameters(_))
+    ));
+}
+
+/// Re-encode `signature` with one JSON field replaced, or removed for `None`
+fn edit_field(
+    signature: &SignatureFile,
+    field: &str,
+    value: Option<serde_json::Value>,
+) -> SignatureFile {
+    let mut json: serde_json::Value =
+        serde_json::from_slice(&signature.to_bytes().expect("encode")).expect("json");
+    let fields = json.as_object_mut().expect("object");
+    match value {
+        Some(value) => fields.insert(field.to_string(), value),
+        None => fields.remove(field),
+    };
+    SignatureFile::from_bytes(&serde_json::to_vec(&json).expect("encode")).expect("decode")
+}
+
+#[tokio::test]
+async fn test_signature_file_header_is_signed() {
+    let message = artifact();
+    let keypair = SignatureBuilder::ml_dsa_44()
+        .generate()
+        .await
+        .expect("generate");
+    let public_key = keypair.public_key_vec().expect("pk");
+    let signature = keypair
+        .sign_stream(message.as_slice())
+        .await
+        .expect("sign stream");
+    let verify = async |file: SignatureFile| {
+        SignatureBuilder::ml_dsa_44()
+            .with_public_key(public_key.clone())
+            .expect("public key")
+            .verify_stream(message.as_slice(), file)
+            .await
+    };
+    assert!(verify(signature.clone()).await.expect("verify").is_valid());
+
+    let backdated = edit_field(&signature, "created", Some("2001-01-01T00:00:00Z".into()));
+    assert!(!verify(backdated).await.expect("verify").is_valid());
+
+    let anonymous = edit_field(&signature, "key_fingerprint", None);
+    assert_eq!(anonymous.key_fingerprint(), None);
+    assert!(!verify(anonymous).await.expect("verify").is_valid());
+
+    let other_key = SignatureFile::fingerprint(b"another signer");
+    let misattributed = edit_field(&signature, "key_fingerprint", Some(other_key.into()));
+    assert!(!verify(misattributed).await.expect("verify").is_valid());
+
+    let relabelled = edit_field(&signature, "algorithm", Some("ml-dsa-65".into()));
+    assert!(matches!(
+        verify(relabelled).await,
+        Err(PqCryptoError::InvalidParameters(_))
+    ));
+
+    // FALCON signs the header ahead of its digest
+    let falcon = SignatureBuilder::falcon_512()
+        .generate()
+        .await
+        .expect("generate");
+    let falcon_public = falcon.public_key_vec().expect("pk");
+    let signature = falcon
+        .sign_stream(message.as_slice())
+        .await
+        .expect("sign stream");
+    let backdated = edit_field(&signature, "created", Some("2001-01-01T00:00:00Z".into()));
+    assert!(
+        !SignatureBuilder::falcon_512()
+            .with_public_key(falcon_public)
+            .expect("public key")
+            .verify_stream(message.as_slice(), backdated)
+            .await
+            .expect("verify")
+            .is_valid()
+    );
+}
@@ -5,8 +5,10 @@
 
 pub mod jwt_handler;
 pub mod key_converter;
+pub mod revocation_store;
 pub mod rsa_key_manager;
 
 pub use jwt_handler::{JwtHandler, VaultJwtClaims, extract_jwt_from_env};
 pub use key_converter::{pkcs1_to_pkcs8, pkcs1_public_to_spki, private_pkcs1_to_public_spki};
+pub use revocation_store::SurrealRevocationStore;
 pub use rsa_key_manager::RsaKeyManager;
@@ -0,0 +1,150 @@
+//! SurrealDB-backed JWT revocation store
+//!
+//! Persists revocations for `cryypt_jwt::Revocation` in the vault database so
+//! they survive restarts and are shared by every process using the vault.
+
+use chrono::{DateTime, Utc};
+use cryypt_jwt::{
+    IssuedBefore, JwtError, JwtResult, RevocationKey, RevocationRecord, RevocationStore,
+    RevokedToken,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+
+const TOKEN_TABLE: &str = "jwt_revoked_token";
+const SUBJECT_TABLE: &str = "jwt_revoked_subject";
+const ALL_TABLE: &str = "jwt_revoked_all";
+
+/// Revoked token row; `expires_at` is duplicated as epoch seconds for purging
+#[derive(Serialize)]
+struct TokenRow {
+    token: RevokedToken,
+    expires_at: Option<i64>,
+}
+
+/// Issued-before cutoff row; cutoffs are append-only and the latest wins
+#[derive(Serialize, Deserialize)]
+struct CutoffRow {
+    subject: Option<String>,
+    cutoff: IssuedBefore,
+    cutoff_at: i64,
+}
+
+/// JWT revocation store backed by the vault's SurrealDB connection
+#[derive(Debug, Clone)]
+pub struct SurrealRevocationStore {
+    db: Arc<Surreal<Any>>,
+}
+
+impl SurrealRevocationStore {
+    /// Create a store over an open vault database
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db }
+    }
+
+    /// Latest cutoff for `subject`, or the latest global cutoff when `subject` is `None`
+    async fn latest_cutoff(&self, subject: Option<&str>) -> JwtResult<Option<IssuedBefore>> {
+        // Global cutoffs live in their own table, so only subject lookups filter rows
+        let query = match subject {
+            Some(_) => format!(
+                "SELECT VALUE cutoff FROM {SUBJECT_TABLE} WHERE subject = $subject \
+                 ORDER BY cutoff_at DESC LIMIT 1"
+            ),
+            None => format!("SELECT VALUE cutoff FROM {ALL_TABLE} ORDER BY cutoff_at DESC LIMIT 1"),
+        };
+        let mut result = self
+            .db
+            .query(query)
+            .bind(("subject", subject.map(str::to_string)))
+            .await
+            .map_err(db_error)?;
+        let cutoffs: Vec<IssuedBefore> = result.take(0).map_err(db_error)?;
+        Ok(cutoffs.into_iter().next())
+    }
+}
+
+impl RevocationStore for SurrealRevocationStore {
+    async fn insert(&self, record: RevocationRecord) -> JwtResult<()> {
+        let (table, row) = match record {
+            RevocationRecord::Token(token) => {
+                let id = token.key.storage_id();
+                let row = TokenRow {
+                    expires_at: token.expires_at.map(|expires_at| expires_at.timestamp()),
+                    token,
+                };
+                self.db
+                    .query(format!("UPSERT type::thing('{TOKEN_TABLE}', $id) CONTENT $row"))
+                    .bind(("id", id))
+                    .bind(("row", row))
+                    .await
+                    .map_err(db_error)?
+                    .check()
+                    .map_err(db_error)?;
+                return Ok(());
+            }
+            RevocationRecord::Subject { subject, cutoff } => (
+                SUBJECT_TABLE,
+                CutoffRow {
+                    subject: Some(subject),
+                    cutoff_at: cutoff.cutoff.timestamp(),
+                    cutoff,
+                },
+            ),
+            RevocationRecord::All(cutoff) => (
+                ALL_TABLE,
+                CutoffRow {
+                    subject: None,
+                    cutoff_at: cutoff.cutoff.timestamp(),
+                    cutoff,
+                },
+            ),
+        };
+
+        self.db
+            .query(format!("CREATE {table} CONTENT $row"))
+            .bind(("row", row))
+            .await
+            .map_err(db_error)?
+            .check()
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    async fn token(&self, key: &RevocationKey) -> JwtResult<Option<RevokedToken>> {
+        let mut result = self
+            .db
+            .query(format!("SELECT VALUE token FROM type::thing('{TOKEN_TABLE}', $id)"))
+            .bind(("id", key.storage_id()))
+            .await
+            .map_err(db_error)?;
+        let tokens: Vec<RevokedToken> = result.take(0).map_err(db_error)?;
+        Ok(tokens.into_iter().next())
+    }
+
+    async fn subject_cutoff(&self, subject: &str) -> JwtResult<Option<IssuedBefore>> {
+        self.latest_cutoff(Some(subject)).await
+    }
+
+    async fn global_cutoff(&self) -> JwtResult<Option<IssuedBefore>> {
+        self.latest_cutoff(None).await
+    }
+
+    async fn purge_expired(&self, now: DateTime<Utc>) -> JwtResult<usize> {
+        let mut result = self
+            .db
+            .query(format!(
+                "DELETE {TOKEN_TABLE} WHERE expires_at != NONE AND expires_at <= $now RETURN BEFORE"
+            ))
+            .bind(("now", now.timestamp()))
+            .await
+            .map_err(db_error)?;
+        let purged: Vec<serde_json::Value> = result.take(0).map_err(db_error)?;
+        Ok(purged.len())
+    }
+}
+
+fn db_error(error: surrealdb::Error) -> JwtError {
+    JwtError::Internal(format!("Revocation store database error: {error}"))
+}
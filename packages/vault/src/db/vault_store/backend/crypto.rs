@@ -3,10 +3,11 @@
 //! Contains encryption, decryption, key derivation, and secure data handling.
 
 use super::super::LocalVaultProvider;
+use super::kdf_profile::{VAULT_KDF_NAMESPACE, VaultKdfProfiles};
 use crate::error::{VaultError, VaultResult};
 use crate::operation::Passphrase;
 use cryypt_cipher::Cryypt;
-use cryypt_key::api::{MasterKeyBuilder, MasterKeyProvider};
+use cryypt_key::api::MasterKeyBuilder;
 use secrecy::ExposeSecret;
 use tokio::fs;
 use std::convert::TryInto;
@@ -16,6 +17,7 @@ use rsa::pkcs1::DecodeRsaPrivateKey;
 use rsa::pkcs8::{EncodePrivateKey, EncodePublicKey};
 use hkdf::Hkdf;
 use sha2::Sha256;
+use zeroize::Zeroizing;
 
 impl LocalVaultProvider {
     /// Derive encryption key from passphrase using cryypt_key
@@ -25,15 +27,35 @@ impl LocalVaultProvider {
     ) -> VaultResult<Vec<u8>> {
         log::trace!("Starting passphrase-based key derivation...");
 
-        // Use cryypt_key PassphraseMasterKey for secure key derivation
         let master_key = MasterKeyBuilder::from_passphrase(passphrase.expose_secret());
-        let key_bytes = master_key
-            .resolve()
+        let profiles = VaultKdfProfiles::new(self.dao.db().clone());
+        let resolved = master_key
+            .resolve_with_store(&profiles, VAULT_KDF_NAMESPACE)
+            .await
             .map_err(|e| VaultError::KeyDerivation(format!("Key derivation failed: {e}")))?;
 
-        log::trace!("Successfully derived {} byte key", key_bytes.len());
+        // Entries written under the pre-profile derivation are re-encrypted once. Only
+        // the vault passphrase owns them; other passphrases (e.g. for backups) derive
+        // a key without touching the entries.
+        if !profiles.migrated(VAULT_KDF_NAMESPACE).await?
+            && self.verify_passphrase(passphrase).await.is_ok()
+        {
+            let legacy_key = match resolved.legacy_key {
+                Some(legacy_key) => legacy_key,
+                None => Zeroizing::new(master_key.resolve_legacy().map_err(|e| {
+                    VaultError::KeyDerivation(format!("Legacy key derivation failed: {e}"))
+                })?),
+            };
+            let migrated = self
+                .migrate_legacy_entries(legacy_key.as_slice(), resolved.master_key.as_slice())
+                .await?;
+            profiles.mark_migrated(VAULT_KDF_NAMESPACE).await?;
+            log::info!("Re-encrypted {migrated} vault entries under the KDF profile key");
+        }
+
+        log::trace!("Successfully derived {} byte key", resolved.master_key.len());
 
-        let output_key = key_bytes.to_vec();
+        let output_key = resolved.master_key.to_vec();
 
         // Store derived key in memory for session
         let mut key_guard = self.encryption_key.lock().await;
@@ -0,0 +1,194 @@
+//! Passphrase KDF profile storage and migration off the legacy derivation
+//!
+//! The vault derives its passphrase key with a `cryypt_key` KDF profile kept in the
+//! `vault_kdf_profiles` table. Entries encrypted under the pre-profile derivation are
+//! re-encrypted the first time the vault passphrase derives the key; the `migrated`
+//! flag on the profile row records that the job finished, so an interrupted run is
+//! picked up again on the next derivation.
+
+use super::super::{LocalVaultProvider, VaultEntry};
+use super::key_utils;
+use crate::error::{VaultError, VaultResult};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
+use cryypt_key::KeyError;
+use cryypt_key::api::{KdfProfile, KdfProfileStore};
+use std::sync::Arc;
+use surrealdb::Surreal;
+use surrealdb::engine::any::Any;
+use zeroize::Zeroizing;
+
+const PROFILE_TABLE: &str = "vault_kdf_profiles";
+
+/// Profile namespace for the vault's passphrase-derived encryption key
+pub(crate) const VAULT_KDF_NAMESPACE: &str = "vault";
+
+/// KDF profiles stored in the vault database, one row per namespace
+#[derive(Debug, Clone)]
+pub struct VaultKdfProfiles {
+    db: Arc<Surreal<Any>>,
+}
+
+impl VaultKdfProfiles {
+    /// Create a store over an open vault database
+    pub fn new(db: Arc<Surreal<Any>>) -> Self {
+        Self { db }
+    }
+
+    /// Whether legacy entries have been re-encrypted under the profile for `namespace`
+    pub(crate) async fn migrated(&self, namespace: &str) -> VaultResult<bool> {
+        let mut result = self
+            .db
+            .query(format!(
+                "SELECT VALUE migrated FROM type::thing('{PROFILE_TABLE}', $ns)"
+            ))
+            .bind(("ns", namespace.to_string()))
+            .await
+            .map_err(|e| VaultError::Provider(format!("KDF profile query failed: {e}")))?;
+        let migrated: Vec<bool> = result
+            .take(0)
+            .map_err(|e| VaultError::Provider(format!("KDF profile query failed: {e}")))?;
+        Ok(migrated.first().copied().unwrap_or(false))
+    }
+
+    /// Record that legacy entries have been re-encrypted for `namespace`
+    pub(crate) async fn mark_migrated(&self, namespace: &str) -> VaultResult<()> {
+        self.db
+            .query(format!(
+                "UPDATE type::thing('{PROFILE_TABLE}', $ns) SET migrated = true"
+            ))
+            .bind(("ns", namespace.to_string()))
+            .await
+            .map_err(|e| VaultError::Provider(format!("KDF profile update failed: {e}")))?
+            .check()
+            .map_err(|e| VaultError::Provider(format!("KDF profile update failed: {e}")))?;
+        Ok(())
+    }
+}
+
+impl KdfProfileStore for VaultKdfProfiles {
+    async fn load_profile(&self, namespace: &str) -> cryypt_key::Result<Option<KdfProfile>> {
+        let mut result = self
+            .db
+            .query(format!(
+                "SELECT VALUE profile FROM type::thing('{PROFILE_TABLE}', $ns)"
+            ))
+            .bind(("ns", namespace.to_string()))
+            .await
+            .map_err(store_error)?;
+        let profiles: Vec<String> = result.take(0).map_err(store_error)?;
+        profiles
+            .first()
+            .map(|json| KdfProfile::from_json(json))
+            .transpose()
+    }
+
+    async fn save_profile(&self, namespace: &str, profile: &KdfProfile) -> cryypt_key::Result<()> {
+        self.db
+            .query(format!(
+                "UPSERT type::thing('{PROFILE_TABLE}', $ns) SET profile = $profile, \
+                 updated_at = time::now()"
+            ))
+            .bind(("ns", namespace.to_string()))
+            .bind(("profile", profile.to_json()?))
+            .await
+            .map_err(store_error)?
+            .check()
+            .map_err(store_error)?;
+        Ok(())
+    }
+
+    async fn save_profile_if_absent(
+        &self,
+        namespace: &str,
+        profile: &KdfProfile,
+    ) -> cryypt_key::Result<Option<KdfProfile>> {
+        // CREATE fails on an existing record, so exactly one racing caller succeeds
+        let created = self
+            .db
+            .query(format!(
+                "CREATE type::thing('{PROFILE_TABLE}', $ns) SET profile = $profile, \
+                 migrated = false, updated_at = time::now()"
+            ))
+            .bind(("ns", namespace.to_string()))
+            .bind(("profile", profile.to_json()?))
+            .await
+            .and_then(surrealdb::Response::check);
+
+        match created {
+            Ok(_) => Ok(None),
+            Err(e) => match self.load_profile(namespace).await? {
+                Some(existing) => Ok(Some(existing)),
+                None => Err(store_error(e)),
+            },
+        }
+    }
+}
+
+impl LocalVaultProvider {
+    /// Re-encrypt entries readable with `legacy_key` under `master_key`
+    ///
+    /// Entries already under `master_key`, and those under the RSA-derived key that
+    /// neither key opens, are left as they are.
+    pub(crate) async fn migrate_legacy_entries(
+        &self,
+        legacy_key: &[u8],
+        master_key: &[u8],
+    ) -> VaultResult<usize> {
+        let db = self.dao.db();
+        let entries: Vec<VaultEntry> = db
+            .select("vault_entries")
+            .await
+            .map_err(|e| VaultError::Provider(format!("Failed to list vault entries: {e}")))?;
+
+        let mut migrated = 0;
+        for entry in entries {
+            let Some(record_id) = entry.id.as_ref() else {
+                continue;
+            };
+            let key = key_utils::extract_key_from_record_id(&record_id.to_string())?;
+            if key.starts_with("__vault_") {
+                continue;
+            }
+            let Ok(encrypted) = BASE64_STANDARD.decode(&entry.value) else {
+                continue;
+            };
+            if self
+                .encryption_service
+                .decrypt(&encrypted, master_key)
+                .await
+                .is_ok()
+            {
+                continue;
+            }
+            let Ok(plaintext) = self
+                .encryption_service
+                .decrypt(&encrypted, legacy_key)
+                .await
+            else {
+                continue;
+            };
+
+            let plaintext = Zeroizing::new(plaintext);
+            let value = self
+                .encryption_service
+                .encrypt_to_string(&plaintext, master_key)
+                .await?;
+            db.query(format!(
+                "UPDATE {record_id} SET value = $value, updated_at = $updated_at"
+            ))
+            .bind(("value", value))
+            .bind((
+                "updated_at",
+                surrealdb::value::Datetime::from(chrono::Utc::now()),
+            ))
+            .await
+            .map_err(|e| VaultError::Provider(format!("Failed to re-encrypt {key}: {e}")))?;
+            migrated += 1;
+        }
+        Ok(migrated)
+    }
+}
+
+fn store_error(error: surrealdb::Error) -> KeyError {
+    KeyError::KeyStorage(format!("KDF profile store: {error}"))
+}
@@ -81,6 +81,19 @@ impl LocalVaultProvider {
             .decrypt(encrypted_data.to_vec())
             .await;
 
+        // Backups written before the vault had a KDF profile are under the legacy key
+        let decrypted_data = if decrypted_data.is_empty() {
+            let legacy_key = cryypt_key::api::MasterKeyBuilder::from_passphrase(passphrase)
+                .resolve_legacy()
+                .map_err(|e| VaultError::KeyDerivation(format!("Key derivation failed: {e}")))?;
+            self.encryption_service
+                .decrypt(encrypted_data, &legacy_key)
+                .await
+                .unwrap_or_default()
+        } else {
+            decrypted_data
+        };
+
         // Validate decryption success
         if decrypted_data.is_empty() {
             let detailed_error = format!(
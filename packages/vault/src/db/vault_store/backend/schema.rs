@@ -39,6 +39,18 @@ impl LocalVaultProvider {
         .await
         .map_err(|e| DaoError::Database(e.to_string()))?;
 
+        // Define KDF profile table for passphrase-derived keys
+        db.query(
+            "
+            DEFINE TABLE IF NOT EXISTS vault_kdf_profiles SCHEMAFULL;
+            DEFINE FIELD profile ON TABLE vault_kdf_profiles TYPE string;
+            DEFINE FIELD migrated ON TABLE vault_kdf_profiles TYPE bool DEFAULT false;
+            DEFINE FIELD updated_at ON TABLE vault_kdf_profiles TYPE datetime;
+            ",
+        )
+        .await
+        .map_err(|e| DaoError::Database(e.to_string()))?;
+
         // Define JWT sessions table for secure session persistence
         db.query(
             "
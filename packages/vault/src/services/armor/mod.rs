@@ -41,15 +41,13 @@ use super::key_storage::KeyStorage;
 use crate::error::{VaultError, VaultResult};
 use crate::logging::log_security_event;
 use cryypt_cipher::cipher::api::Cipher;
+use cryypt_key::{StoredKeyKind, StoredKeyPair};
 use cryypt_pqcrypto::api::{KyberSecurityLevel as SecurityLevel, PqCryptoMasterBuilder};
 use std::path::Path;
 
 /// Magic header for .vault file format
 const VAULT_ARMOR_MAGIC: &[u8] = b"CRYYPT\x01";
 
-/// ML-KEM-768 public key size (bytes)
-const PUBLIC_KEY_SIZE_768: usize = 1184;
-
 /// PQCrypto armor service for vault file protection
 ///
 /// This service handles all PQCrypto armor operations:
@@ -100,7 +98,7 @@ impl<S: KeyStorage> PQCryptoArmorService<S> {
     /// * `key_id` - Full keychain key identifier (e.g., "pq_armor:v1:pq_keypair")
     ///
     /// # Process
-    /// 1. Retrieve PQCrypto public key from storage
+    /// 1. Retrieve the ML-KEM keypair from storage and check it matches the security level
     /// 2. Read vault database file
     /// 3. Generate random symmetric key via Kyber KEM
     /// 4. Encrypt file with AES-256-GCM
@@ -109,7 +107,7 @@ impl<S: KeyStorage> PQCryptoArmorService<S> {
     ///
     /// # Errors
     /// Returns error if:
-    /// - Key retrieval fails
+    /// - Key retrieval fails or the key is not an ML-KEM keypair of this security level
     /// - File I/O fails
     /// - Encryption fails
     /// - Atomic file operations fail
@@ -127,17 +125,16 @@ impl<S: KeyStorage> PQCryptoArmorService<S> {
 
         // Step 1: Retrieve PQCrypto keypair from storage
         let keypair = self.key_storage.retrieve(key_id).await?;
-
-        // Extract public key (first 1184 bytes for ML-KEM-768)
-        let public_key = if keypair.len() >= PUBLIC_KEY_SIZE_768 {
-            keypair[..PUBLIC_KEY_SIZE_768].to_vec()
-        } else {
+        let key_level = keypair_security_level(key_id, &keypair)?;
+        if key_level != self.security_level {
             return Err(VaultError::Crypto(format!(
-                "Invalid PQCrypto keypair: expected at least {} bytes, got {}",
-                PUBLIC_KEY_SIZE_768,
-                keypair.len()
+                "PQCrypto keypair '{}' is {}, but armor uses {:?}",
+                key_id,
+                keypair.kind(),
+                self.security_level
             )));
-        };
+        }
+        let public_key = keypair.public_key().to_vec();
 
         // Step 2: Compress vault database (file or directory) to zip archive
         use cryypt_compression::Compress;
@@ -267,15 +264,15 @@ impl<S: KeyStorage> PQCryptoArmorService<S> {
     /// * `key_id` - Full keychain key identifier (e.g., "pq_armor:v1:pq_keypair")
     ///
     /// # Process
-    /// 1. Retrieve PQCrypto private key from storage
-    /// 2. Read and parse .vault file
+    /// 1. Retrieve the ML-KEM keypair from storage
+    /// 2. Read and parse .vault file, checking the keypair matches its security level
     /// 3. Decapsulate symmetric key via Kyber KEM
     /// 4. Decrypt file with AES-256-GCM
     /// 5. Atomic write: .tmp → rename → remove armored file
     ///
     /// # Errors
     /// Returns error if:
-    /// - Key retrieval fails
+    /// - Key retrieval fails or the key is not an ML-KEM keypair of the file's level
     /// - File parsing fails
     /// - Decryption fails
     /// - Atomic file operations fail
@@ -293,17 +290,7 @@ impl<S: KeyStorage> PQCryptoArmorService<S> {
 
         // Step 1: Retrieve PQCrypto keypair from storage
         let keypair = self.key_storage.retrieve(key_id).await?;
-
-        // Extract private key (everything after public key)
-        let private_key = if keypair.len() >= PUBLIC_KEY_SIZE_768 {
-            keypair[PUBLIC_KEY_SIZE_768..].to_vec()
-        } else {
-            return Err(VaultError::Crypto(format!(
-                "Invalid PQCrypto keypair: expected at least {} bytes, got {}",
-                PUBLIC_KEY_SIZE_768,
-                keypair.len()
-            )));
-        };
+        let key_level = keypair_security_level(key_id, &keypair)?;
 
         // Step 2: Read and parse .vault file
         let armor_data = tokio::fs::read(vault_path).await.map_err(|e| {
@@ -336,11 +323,21 @@ impl<S: KeyStorage> PQCryptoArmorService<S> {
             );
         }
 
+        if key_level != security_level {
+            return Err(VaultError::Crypto(format!(
+                "PQCrypto keypair '{}' is {}, but {} was armored with {:?}",
+                key_id,
+                keypair.kind(),
+                vault_path.display(),
+                security_level
+            )));
+        }
+
         // Step 3: Decapsulate symmetric key using Kyber KEM
         let shared_secret = PqCryptoMasterBuilder::new()
             .kyber()
             .with_security_level(security_level)
-            .decapsulate_hybrid(private_key, kyber_ciphertext)
+            .decapsulate_hybrid(keypair.secret_key().to_vec(), kyber_ciphertext)
             .await
             .map_err(|e| VaultError::Crypto(format!("Kyber decapsulation failed: {}", e)))?;
 
@@ -535,6 +532,22 @@ impl<S: KeyStorage> PQCryptoArmorService<S> {
     }
 }
 
+/// Kyber security level of an ML-KEM keypair
+///
+/// # Errors
+/// Returns `VaultError::Crypto` if the keypair is not an ML-KEM keypair
+fn keypair_security_level(key_id: &str, keypair: &StoredKeyPair) -> VaultResult<SecurityLevel> {
+    match keypair.kind() {
+        StoredKeyKind::MlKem512 => Ok(SecurityLevel::Level1),
+        StoredKeyKind::MlKem768 => Ok(SecurityLevel::Level3),
+        StoredKeyKind::MlKem1024 => Ok(SecurityLevel::Level5),
+        kind => Err(VaultError::Crypto(format!(
+            "PQCrypto keypair '{}' is {}, not an ML-KEM keypair",
+            key_id, kind
+        ))),
+    }
+}
+
 /// Read key ID from .vault file header without loading entire file
 ///
 /// This is a standalone function that peeks at the file header to extract the keychain
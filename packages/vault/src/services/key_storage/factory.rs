@@ -2,6 +2,7 @@
 
 use super::{KeyStorage, KeychainStorage, FileStorage};
 use crate::error::VaultResult;
+use cryypt_key::StoredKeyPair;
 use std::path::PathBuf;
 
 pub enum KeyStorageSource {
@@ -19,14 +20,14 @@ pub enum KeyStorageBackend {
 }
 
 impl KeyStorage for KeyStorageBackend {
-    async fn store(&self, key_id: &str, keypair: &[u8]) -> VaultResult<()> {
+    async fn store(&self, key_id: &str, keypair: &StoredKeyPair) -> VaultResult<()> {
         match self {
             KeyStorageBackend::Keychain(storage) => storage.store(key_id, keypair).await,
             KeyStorageBackend::File(storage) => storage.store(key_id, keypair).await,
         }
     }
 
-    async fn retrieve(&self, key_id: &str) -> VaultResult<Vec<u8>> {
+    async fn retrieve(&self, key_id: &str) -> VaultResult<StoredKeyPair> {
         match self {
             KeyStorageBackend::Keychain(storage) => storage.retrieve(key_id).await,
             KeyStorageBackend::File(storage) => storage.retrieve(key_id).await,
@@ -1,8 +1,10 @@
 //! File-based key storage for PQCrypto keys
 
-use super::KeyStorage;
+use super::{KeyStorage, decode_keypair};
 use crate::error::{VaultError, VaultResult};
+use cryypt_key::StoredKeyPair;
 use std::path::PathBuf;
+use zeroize::Zeroizing;
 
 #[derive(Debug, Clone)]
 pub struct FileStorage {
@@ -22,7 +24,7 @@ impl FileStorage {
 }
 
 impl KeyStorage for FileStorage {
-    async fn store(&self, key_id: &str, keypair: &[u8]) -> VaultResult<()> {
+    async fn store(&self, key_id: &str, keypair: &StoredKeyPair) -> VaultResult<()> {
         let path = self.key_path(key_id);
 
         if let Some(parent) = path.parent() {
@@ -30,23 +32,24 @@ impl KeyStorage for FileStorage {
                 .map_err(|e| VaultError::Provider(format!("Failed to create key dir: {}", e)))?;
         }
 
-        tokio::fs::write(&path, keypair).await
+        tokio::fs::write(&path, keypair.to_bytes()).await
             .map_err(|e| VaultError::Provider(format!("Failed to write key: {}", e)))?;
 
         log::debug!("Stored key to file: {}", path.display());
         Ok(())
     }
 
-    async fn retrieve(&self, key_id: &str) -> VaultResult<Vec<u8>> {
+    async fn retrieve(&self, key_id: &str) -> VaultResult<StoredKeyPair> {
         let path = self.key_path(key_id);
 
-        tokio::fs::read(&path).await.map_err(|e| {
+        let bytes = Zeroizing::new(tokio::fs::read(&path).await.map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
                 VaultError::ItemNotFound
             } else {
                 VaultError::Provider(format!("Failed to read key: {}", e))
             }
-        })
+        })?);
+        decode_keypair(key_id, &bytes)
     }
 
     async fn delete(&self, key_id: &str) -> VaultResult<()> {
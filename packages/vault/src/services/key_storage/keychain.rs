@@ -5,15 +5,16 @@
 //! - Windows: Credential Manager
 //! - Linux: Secret Service API (GNOME Keyring, KWallet)
 
-use super::KeyStorage;
+use super::{KeyStorage, decode_keypair};
 use crate::error::{VaultError, VaultResult};
 use cryypt_key::{
     api::KeyRetriever,
     store::KeychainStore,
-    traits::{KeyImport, KeyStorage as CryyptKeyStorage},
-    KeyId, SimpleKeyId,
+    traits::KeyStorage as CryyptKeyStorage,
+    KeyId, SimpleKeyId, StoredKeyPair,
 };
 use std::sync::{Arc, Mutex};
+use zeroize::Zeroizing;
 
 /// OS Keychain storage backend for PQCrypto keys
 ///
@@ -49,61 +50,47 @@ impl KeychainStorage {
 }
 
 impl KeyStorage for KeychainStorage {
-    async fn store(&self, key_id: &str, keypair: &[u8]) -> VaultResult<()> {
+    async fn store(&self, key_id: &str, keypair: &StoredKeyPair) -> VaultResult<()> {
         let keychain_store = KeychainStore::for_app(&self.app_name);
         let simple_key_id = SimpleKeyId::new(key_id);
 
-        // Use Arc<Mutex> to capture errors from the callback
-        let error_state = Arc::new(Mutex::new(None::<String>));
-        let error_state_clone = Arc::clone(&error_state);
-
-        keychain_store
-            .store(&simple_key_id, keypair)
-            .on_result(move |result| match result {
-                Ok(()) => (),
-                Err(e) => {
-                    log::error!("Failed to store PQCrypto keypair in keychain: {}", e);
-                    if let Ok(mut error_guard) = error_state_clone.lock() {
-                        *error_guard = Some(format!(
-                            "Failed to store PQCrypto keypair in keychain: {}",
-                            e
-                        ));
-                    }
-                }
-            })
-            .await;
-
-        // Check if an error occurred
-        if let Ok(error_guard) = error_state.lock()
-            && let Some(error) = error_guard.as_ref() {
-            return Err(VaultError::Provider(error.clone()));
-        }
-
-        log::debug!("Successfully stored PQCrypto keypair: {}", key_id);
+        keypair
+            .save(&keychain_store, &simple_key_id)
+            .await
+            .map_err(|e| {
+                log::error!("Failed to store PQCrypto keypair in keychain: {}", e);
+                VaultError::Provider(format!(
+                    "Failed to store PQCrypto keypair in keychain: {}",
+                    e
+                ))
+            })?;
+
+        log::debug!("Successfully stored {} PQCrypto keypair: {}", keypair.kind(), key_id);
         Ok(())
     }
 
-    async fn retrieve(&self, key_id: &str) -> VaultResult<Vec<u8>> {
+    async fn retrieve(&self, key_id: &str) -> VaultResult<StoredKeyPair> {
         let keychain_store = KeychainStore::for_app(&self.app_name);
 
         // Use KeyRetriever with dummy namespace/version since we pass full key_id
-        let key_data = KeyRetriever::new()
+        let key_data = Zeroizing::new(KeyRetriever::new()
             .with_store(keychain_store)
             .with_namespace("_") // Dummy, overridden by key_id parameter
             .version(1)          // Dummy, overridden by key_id parameter
             .retrieve(key_id)
-            .await;
+            .await);
 
         if key_data.is_empty() {
             return Err(VaultError::ItemNotFound);
         }
 
+        let keypair = decode_keypair(key_id, &key_data)?;
         log::debug!(
-            "Successfully retrieved PQCrypto keypair: {} ({} bytes)",
-            key_id,
-            key_data.len()
+            "Successfully retrieved {} PQCrypto keypair: {}",
+            keypair.kind(),
+            key_id
         );
-        Ok(key_data)
+        Ok(keypair)
     }
 
     async fn delete(&self, key_id: &str) -> VaultResult<()> {
@@ -144,14 +131,17 @@ impl KeyStorage for KeychainStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use cryypt_key::StoredKeyKind;
 
     #[tokio::test]
     async fn test_keychain_storage_lifecycle() {
         let storage = KeychainStorage::new("vault_test");
         let test_key_id = "test_namespace:12345678-1234-1234-1234-123456789abc:pq_keypair";
 
-        // Generate test keypair data
-        let test_keypair = vec![0x42; 2400]; // 2400 bytes for ML-KEM-768
+        // Generate test keypair data with ML-KEM-768 key sizes
+        let test_keypair =
+            StoredKeyPair::new(StoredKeyKind::MlKem768, vec![0x42; 1184], vec![0x42; 2400])
+                .expect("Failed to build test keypair");
 
         // Clean up any existing test key
         let _ = storage.delete(test_key_id).await;
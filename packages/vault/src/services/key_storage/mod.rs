@@ -3,8 +3,12 @@
 //! Provides a trait-based abstraction over different key storage backends,
 //! allowing keys to be stored in OS keychain, files, environment variables,
 //! or cloud secret managers.
+//!
+//! Keys are `cryypt_key` [`StoredKeyPair`]s, so the vault's keypairs carry their
+//! algorithm and share the encoding of every other `cryypt_key` store.
 
 use crate::error::{VaultError, VaultResult};
+use cryypt_key::{StoredKeyKind, StoredKeyPair};
 
 pub mod keychain;
 pub mod file;
@@ -26,14 +30,11 @@ pub trait KeyStorage: Send + Sync {
     ///
     /// # Arguments
     /// * `key_id` - Full key identifier (e.g., "pq_armor:v1:pq_keypair")
-    /// * `keypair` - Combined public + private key bytes
-    ///
-    /// # Key Format
-    /// For ML-KEM-768: First 1184 bytes = public key, remaining = private key
+    /// * `keypair` - Public and private key tagged with their algorithm
     ///
     /// # Errors
     /// Returns error if storage operation fails
-    fn store(&self, key_id: &str, keypair: &[u8]) -> impl std::future::Future<Output = VaultResult<()>> + Send;
+    fn store(&self, key_id: &str, keypair: &StoredKeyPair) -> impl std::future::Future<Output = VaultResult<()>> + Send;
 
     /// Retrieve a PQCrypto keypair
     ///
@@ -41,12 +42,12 @@ pub trait KeyStorage: Send + Sync {
     /// * `key_id` - Full key identifier (e.g., "pq_armor:v1:pq_keypair")
     ///
     /// # Returns
-    /// Combined public + private key bytes
+    /// The keypair, decoded as by [`decode_keypair`]
     ///
     /// # Errors
     /// Returns ItemNotFound if key doesn't exist
-    /// Returns error if retrieval operation fails
-    fn retrieve(&self, key_id: &str) -> impl std::future::Future<Output = VaultResult<Vec<u8>>> + Send;
+    /// Returns error if retrieval operation fails or the stored bytes are not a keypair
+    fn retrieve(&self, key_id: &str) -> impl std::future::Future<Output = VaultResult<StoredKeyPair>> + Send;
 
     /// Check if a key exists
     ///
@@ -70,3 +71,28 @@ pub trait KeyStorage: Send + Sync {
     /// Returns error if deletion fails
     fn delete(&self, key_id: &str) -> impl std::future::Future<Output = VaultResult<()>> + Send;
 }
+
+/// ML-KEM-768 public key size of keypairs stored before [`StoredKeyPair`]
+const LEGACY_PUBLIC_KEY_SIZE: usize = 1184;
+
+/// ML-KEM-768 private key size of keypairs stored before [`StoredKeyPair`]
+const LEGACY_PRIVATE_KEY_SIZE: usize = 2400;
+
+/// Decode keypair bytes read from a storage backend
+///
+/// Keypairs written before the vault used [`StoredKeyPair`] are a bare ML-KEM-768 public
+/// key followed by its private key; those are read as [`StoredKeyKind::MlKem768`] pairs so
+/// existing vaults still unlock.
+///
+/// # Errors
+/// Returns `VaultError::Crypto` if the bytes are neither a stored keypair nor a legacy one
+pub(crate) fn decode_keypair(key_id: &str, bytes: &[u8]) -> VaultResult<StoredKeyPair> {
+    let decoded = match StoredKeyPair::from_bytes(bytes) {
+        Err(_) if bytes.len() == LEGACY_PUBLIC_KEY_SIZE + LEGACY_PRIVATE_KEY_SIZE => {
+            let (public_key, private_key) = bytes.split_at(LEGACY_PUBLIC_KEY_SIZE);
+            StoredKeyPair::new(StoredKeyKind::MlKem768, public_key, private_key)
+        }
+        decoded => decoded,
+    };
+    decoded.map_err(|e| VaultError::Crypto(format!("Invalid PQCrypto keypair '{}': {}", key_id, e)))
+}
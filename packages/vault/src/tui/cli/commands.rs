@@ -6,8 +6,9 @@ use crate::security::{
 };
 use clap::{Parser, Subcommand};
 use cryypt_cipher::cipher::api::Cipher;
-use cryypt_key::{KeyRetriever, store::KeychainStore};
-use cryypt_pqcrypto::api::{KyberSecurityLevel as SecurityLevel, PqCryptoMasterBuilder};
+use cryypt_key::{StoredKeyKind, StoredKeyPair};
+use cryypt_pqcrypto::KemBuilder;
+use cryypt_pqcrypto::api::{KemKeyPairBuilder, KyberSecurityLevel as SecurityLevel};
 use serde_json;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -258,23 +259,14 @@ pub enum Commands {
 
 // SUBTASK4: PQCrypto key management functions
 
-/// Load PQCrypto key from OS keychain  
-pub async fn load_pq_key_from_keychain(key_id: &str) -> Result<Vec<u8>, String> {
-    let keychain_store = KeychainStore::for_app("vault");
+/// Load PQCrypto keypair from OS keychain
+pub async fn load_pq_key_from_keychain(key_id: &str) -> Result<StoredKeyPair, String> {
+    use crate::services::{KeychainStorage, key_storage::KeyStorage};
 
-    // Use KeyRetriever with dummy namespace/version since we pass full key_id
-    let key_data = KeyRetriever::new()
-        .with_store(keychain_store)
-        .with_namespace("_") // Dummy, overridden by key_id parameter
-        .version(1)          // Dummy, overridden by key_id parameter
+    KeychainStorage::default_app()
         .retrieve(key_id)
-        .await;
-
-    if key_data.is_empty() {
-        return Err(format!("PQCrypto keypair '{}' not found in keychain", key_id));
-    }
-
-    Ok(key_data)
+        .await
+        .map_err(|e| format!("PQCrypto keypair '{}' not found in keychain: {}", key_id, e))
 }
 
 /// Generate unique PQCrypto key ID with UUID v4
@@ -323,57 +315,40 @@ pub async fn generate_pq_keypair(
     key_id: &str,
     security_level: SecurityLevel,
 ) -> Result<(), String> {
-    // Generate Kyber keypair
-    let keypair = PqCryptoMasterBuilder::new()
-        .kyber()
-        .with_security_level(security_level)
-        .on_result(|result| match result {
-            Ok(keypair) => keypair,
-            Err(e) => {
-                log::error!("PQCrypto keypair generation failed: {}", e);
-                panic!("Failed to generate PQCrypto keypair: {}", e)
-            }
-        })
-        .generate_keypair()
-        .await;
-
-    // Store in keychain using cryypt_key API
-    use cryypt_key::{KeyId, SimpleKeyId, traits::KeyImport};
-
-    let keychain_store = KeychainStore::for_app("vault");
-    let simple_key_id = SimpleKeyId::new(key_id);
-
-    use std::sync::{Arc, Mutex};
-
-    let error_state = Arc::new(Mutex::new(None::<String>));
-    let error_state_clone = Arc::clone(&error_state);
-
-    keychain_store
-        .store(&simple_key_id, &keypair)
-        .on_result(move |result| match result {
-            Ok(()) => (),
-            Err(e) => {
-                log::error!("Failed to store PQCrypto keypair in keychain: {}", e);
-                if let Ok(mut error_guard) = error_state_clone.lock() {
-                    *error_guard = Some(format!(
-                        "Failed to store PQCrypto keypair in keychain: {}",
-                        e
-                    ));
-                }
-            }
-        })
-        .await;
+    let keypair = generate_ml_kem_keypair(security_level).await?;
 
-    if let Ok(error_guard) = error_state.lock()
-        && let Some(error) = error_guard.as_ref()
-    {
-        return Err(error.clone());
-    }
+    // Store in keychain as a typed cryypt_key keypair
+    use crate::services::{KeychainStorage, key_storage::KeyStorage};
+    KeychainStorage::default_app()
+        .store(key_id, &keypair)
+        .await
+        .map_err(|e| e.to_string())?;
 
     println!("✅ PQCrypto keypair generated and stored in keychain");
     Ok(())
 }
 
+/// Generate an ML-KEM keypair for a Kyber security level
+async fn generate_ml_kem_keypair(security_level: SecurityLevel) -> Result<StoredKeyPair, String> {
+    let generation_error = |e: cryypt_pqcrypto::PqCryptoError| {
+        log::error!("PQCrypto keypair generation failed: {}", e);
+        format!("Failed to generate PQCrypto keypair: {}", e)
+    };
+
+    let keypair = KemBuilder::ml_kem(security_level as u16)
+        .map_err(generation_error)?
+        .generate()
+        .await
+        .map_err(generation_error)?;
+    let kind = StoredKeyKind::try_from(keypair.algorithm()).map_err(generation_error)?;
+    StoredKeyPair::new(
+        kind,
+        keypair.public_key().map_err(generation_error)?,
+        keypair.secret_key().map_err(generation_error)?,
+    )
+    .map_err(|e| format!("Failed to generate PQCrypto keypair: {}", e))
+}
+
 // SUBTASK2: Implement hybrid PQCrypto lock operation
 
 pub async fn handle_lock_command(
@@ -650,6 +625,7 @@ pub async fn discover_vault_files(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::{FileStorage, key_storage::KeyStorage};
     use tempfile::tempdir;
 
     #[tokio::test]
@@ -659,24 +635,13 @@ mod tests {
         let public_key_file = temp_dir.path().join("test_public_key.bin");
         let private_key_file = temp_dir.path().join("test_private_key.bin");
 
-        // Generate test keypair directly using PQCrypto API
-        let combined_keypair = PqCryptoMasterBuilder::new()
-            .kyber()
-            .with_security_level(SecurityLevel::Level3)
-            .on_result(|result| match result {
-                Ok(keypair) => keypair,
-                Err(e) => {
-                    panic!("Failed to generate test keypair: {}", e)
-                }
-            })
-            .generate_keypair()
-            .await;
-
-        // Save combined keypair to both files (functions will extract appropriate portions)
-        tokio::fs::write(&public_key_file, &combined_keypair)
+        // File storage reads keypairs by key ID from the key files' directory
+        let test_key_id = "test_namespace:12345678-1234-1234-1234-123456789abc:pq_keypair";
+        let keypair = generate_ml_kem_keypair(SecurityLevel::Level3)
             .await
             .unwrap();
-        tokio::fs::write(&private_key_file, &combined_keypair)
+        FileStorage::new(temp_dir.path())
+            .store(test_key_id, &keypair)
             .await
             .unwrap();
 
@@ -686,7 +651,6 @@ mod tests {
             .unwrap();
 
         // Test lock command with file-based public key (bypasses keychain)
-        let test_key_id = "test_namespace:12345678-1234-1234-1234-123456789abc:pq_keypair";
         handle_lock_command(
             &vault_path,
             Some(&public_key_file), // Use public key file for encryption
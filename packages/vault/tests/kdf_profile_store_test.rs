@@ -0,0 +1,70 @@
+//! Tests for the SurrealDB-backed KDF profile store
+//!
+//! Runs `cryypt_key` passphrase resolution against an on-disk SurrealKV database.
+
+use cryypt_key::api::{KdfProfile, KdfProfileStore, MasterKeyBuilder};
+use cryypt_vault::db::vault_store::backend::kdf_profile::VaultKdfProfiles;
+use std::sync::Arc;
+use surrealdb::engine::any;
+use tempfile::TempDir;
+
+async fn open_store(dir: &TempDir) -> VaultKdfProfiles {
+    let url = format!("surrealkv://{}", dir.path().join("profiles.db").display());
+    let db = any::connect(&url).await.expect("connect to SurrealKV");
+    db.use_ns("test")
+        .use_db("kdf")
+        .await
+        .expect("select namespace and database");
+    VaultKdfProfiles::new(Arc::new(db))
+}
+
+fn cheap_profile() -> KdfProfile {
+    KdfProfile::generate()
+        .with_costs(1024, 1, 1)
+        .expect("valid Argon2 parameters")
+}
+
+#[tokio::test]
+async fn test_save_if_absent_keeps_the_first_profile() {
+    let dir = TempDir::new().expect("temp dir");
+    let store = open_store(&dir).await;
+    assert!(store.load_profile("vault").await.expect("load").is_none());
+
+    let first = cheap_profile();
+    let saved = store.save_profile_if_absent("vault", &first).await;
+    assert!(saved.expect("save").is_none());
+
+    let existing = store
+        .save_profile_if_absent("vault", &cheap_profile())
+        .await
+        .expect("save");
+    assert_eq!(existing, Some(first.clone()));
+    assert_eq!(
+        store.load_profile("vault").await.expect("load"),
+        Some(first)
+    );
+}
+
+#[tokio::test]
+async fn test_racing_first_resolves_agree_on_one_profile() {
+    let dir = TempDir::new().expect("temp dir");
+    let store = open_store(&dir).await;
+
+    let resolve = |store: VaultKdfProfiles| async move {
+        MasterKeyBuilder::from_passphrase("vault passphrase")
+            .with_profile(cheap_profile())
+            .resolve_with_store(&store, "vault")
+            .await
+            .expect("resolve")
+    };
+    let resolved = futures::future::join_all((0..8).map(|_| resolve(store.clone()))).await;
+
+    // Every caller derives under the stored profile; only its creator migrates
+    let stored = store.load_profile("vault").await.expect("load");
+    for result in &resolved {
+        assert_eq!(Some(&result.profile), stored.as_ref());
+        assert_eq!(*result.master_key, *resolved[0].master_key);
+    }
+    let creators = resolved.iter().filter(|r| r.legacy_key.is_some()).count();
+    assert_eq!(creators, 1);
+}
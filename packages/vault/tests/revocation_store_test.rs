@@ -0,0 +1,200 @@
+//! Tests for the SurrealDB-backed JWT revocation store
+//!
+//! Runs the store against an on-disk SurrealKV database, both directly through
+//! the `RevocationStore` trait and behind `cryypt_jwt::Revocation`.
+
+use chrono::{Duration, Utc};
+use cryypt_jwt::{
+    IssuedBefore, Jwt, Revocation, RevocationKey, RevocationRecord, RevocationStore, RevokedToken,
+};
+use cryypt_vault::auth::SurrealRevocationStore;
+use serde_json::json;
+use std::sync::Arc;
+use surrealdb::engine::any;
+use tempfile::TempDir;
+
+const SECRET: &[u8] = b"surreal-revocation-secret";
+
+async fn open_store(dir: &TempDir) -> SurrealRevocationStore {
+    let url = format!(
+        "surrealkv://{}",
+        dir.path().join("revocations.db").display()
+    );
+    let db = any::connect(&url).await.expect("connect to SurrealKV");
+    db.use_ns("test")
+        .use_db("revocation")
+        .await
+        .expect("select namespace and database");
+    SurrealRevocationStore::new(Arc::new(db))
+}
+
+fn revoked(key: RevocationKey, reason: &str, expires_in: Option<Duration>) -> RevocationRecord {
+    RevocationRecord::Token(RevokedToken {
+        key,
+        reason: reason.to_string(),
+        revoked_at: Utc::now(),
+        expires_at: expires_in.map(|expires_in| Utc::now() + expires_in),
+    })
+}
+
+fn cutoff(hours_ago: i64, reason: &str) -> IssuedBefore {
+    IssuedBefore {
+        cutoff: Utc::now() - Duration::hours(hours_ago),
+        reason: reason.to_string(),
+        revoked_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_token_entries_upsert_and_purge() {
+    let dir = TempDir::new().expect("temp dir");
+    let store = open_store(&dir).await;
+
+    let jti = RevocationKey::Jti("abc".to_string());
+    let digest = RevocationKey::digest("header.payload.signature");
+    store
+        .insert(revoked(jti.clone(), "logout", Some(Duration::hours(1))))
+        .await
+        .expect("insert jti");
+    store
+        .insert(revoked(digest.clone(), "leaked", Some(-Duration::hours(1))))
+        .await
+        .expect("insert digest");
+    store
+        .insert(revoked(
+            RevocationKey::Jti("forever".to_string()),
+            "banned",
+            None,
+        ))
+        .await
+        .expect("insert without expiry");
+
+    assert_eq!(
+        store
+            .token(&jti)
+            .await
+            .expect("lookup")
+            .expect("entry")
+            .reason,
+        "logout"
+    );
+    assert_eq!(
+        store
+            .token(&digest)
+            .await
+            .expect("lookup")
+            .expect("entry")
+            .reason,
+        "leaked"
+    );
+    let other = RevocationKey::Jti("other".to_string());
+    assert!(store.token(&other).await.expect("lookup").is_none());
+
+    // Revoking the same key again replaces the entry instead of duplicating it
+    store
+        .insert(revoked(jti.clone(), "admin", Some(Duration::hours(1))))
+        .await
+        .expect("upsert jti");
+    assert_eq!(
+        store
+            .token(&jti)
+            .await
+            .expect("lookup")
+            .expect("entry")
+            .reason,
+        "admin"
+    );
+
+    // Only the expired entry goes; entries without expiry are kept
+    assert_eq!(store.purge_expired(Utc::now()).await.expect("purge"), 1);
+    assert!(store.token(&digest).await.expect("lookup").is_none());
+    assert!(store.token(&jti).await.expect("lookup").is_some());
+    let forever = RevocationKey::Jti("forever".to_string());
+    assert!(store.token(&forever).await.expect("lookup").is_some());
+}
+
+#[tokio::test]
+async fn test_subject_and_global_cutoffs_are_separate() {
+    let dir = TempDir::new().expect("temp dir");
+    let store = open_store(&dir).await;
+
+    assert!(store.global_cutoff().await.expect("global").is_none());
+    assert!(
+        store
+            .subject_cutoff("alice")
+            .await
+            .expect("subject")
+            .is_none()
+    );
+
+    for (subject, hours_ago, reason) in
+        [("alice", 3, "old"), ("alice", 1, "new"), ("bob", 2, "bob")]
+    {
+        store
+            .insert(RevocationRecord::Subject {
+                subject: subject.to_string(),
+                cutoff: cutoff(hours_ago, reason),
+            })
+            .await
+            .expect("insert subject cutoff");
+    }
+
+    // Subject rows never leak into the global cutoff, nor into other subjects
+    assert!(store.global_cutoff().await.expect("global").is_none());
+    let alice = store.subject_cutoff("alice").await.expect("subject");
+    assert_eq!(alice.expect("alice cutoff").reason, "new");
+    let bob = store.subject_cutoff("bob").await.expect("subject");
+    assert_eq!(bob.expect("bob cutoff").reason, "bob");
+    assert!(
+        store
+            .subject_cutoff("carol")
+            .await
+            .expect("subject")
+            .is_none()
+    );
+
+    store
+        .insert(RevocationRecord::All(cutoff(5, "older rotation")))
+        .await
+        .expect("insert global cutoff");
+    store
+        .insert(RevocationRecord::All(cutoff(4, "key rotation")))
+        .await
+        .expect("insert global cutoff");
+    let global = store.global_cutoff().await.expect("global");
+    assert_eq!(global.expect("global cutoff").reason, "key rotation");
+    let alice = store.subject_cutoff("alice").await.expect("subject");
+    assert_eq!(alice.expect("alice cutoff").reason, "new");
+}
+
+#[tokio::test]
+async fn test_revocation_over_surreal_store() {
+    let dir = TempDir::new().expect("temp dir");
+    let revocation = Revocation::with_store(open_store(&dir).await);
+    let verifier = || Jwt::builder().with_algorithm("HS256").with_secret(SECRET);
+    let sign = |claims: serde_json::Value| async move {
+        Jwt::builder()
+            .with_algorithm("HS256")
+            .with_secret(SECRET)
+            .sign(claims)
+            .await
+            .expect("signing should succeed")
+    };
+
+    let now = Utc::now().timestamp();
+    let token = sign(json!({"sub": "alice", "jti": "t1", "iat": now, "exp": now + 60})).await;
+    let other = sign(json!({"sub": "bob", "jti": "t2", "iat": now, "exp": now + 60})).await;
+
+    revocation
+        .revoke(verifier(), &token, "logout")
+        .await
+        .expect("revoke");
+    assert!(revocation.verify(verifier(), &token).await.is_err());
+    assert!(revocation.verify(verifier(), &other).await.is_ok());
+
+    revocation
+        .revoke_subject("bob", "password reset")
+        .await
+        .expect("revoke subject");
+    assert!(revocation.is_revoked(&other).await.expect("check"));
+}